@@ -3,10 +3,11 @@
 use crate::domain::entities::config::Config;
 use crate::domain::entities::server::Server;
 use crate::domain::implementations::database::Database;
+use crate::domain::implementations::log_storage::LogStorage;
 use crate::services;
 use crate::services::parser_service;
 
-use crate::services::utils::resp_type::RespType;
+use crate::services::utils::resp_type::{RespType, RESP2};
 use crate::services::web_server_parser_service;
 use crate::services::worker_service::ThreadPool;
 use std::env::args;
@@ -22,13 +23,18 @@ use std::thread;
 
 static SERVER_ARGS: usize = 2;
 
+/// Archivo del log del motor de almacenamiento `log`, si la configuración no especifica `storage-file`.
+const DEFAULT_STORAGE_FILE: &str = "storage.log";
+
 /// Inicia el servidor Redis
 ///
 /// Toma un argumento de la linea de comandos con la dirección de la configuración
 /// y la utiliza para iniciar el server y cargar la base de datos en memoria.
-/// El servidor es iniciado en la dirección "127.0.0.1".
+/// El servidor recibe conexiones en las direcciones indicadas por la directiva `bind`; si no se especifica,
+/// solo en las interfaces de loopback ("127.0.0.1 -::1").
 /// El archivo de configuración debe tener definidos los siguientes campos: verbose, port, timeout, dbfilename, logfile.
 /// De faltar algun parámetro de configuración, se corta la ejecución del programa.
+/// El motor de almacenamiento de la base de datos se elige con `storage-engine` (ver `create_database`).
 pub fn run_redis_server() {
     let argv = args().collect::<Vec<String>>();
     if argv.len() != SERVER_ARGS {
@@ -41,22 +47,32 @@ pub fn run_redis_server() {
     let dbfilename = config
         .get_attribute(String::from("dbfilename"))
         .expect("Error: Database config not set.");
-    let dir = String::from("127.0.0.1");
+    let dir = config
+        .get_attribute(String::from("bind"))
+        .unwrap_or_else(|_| String::from(services::server_service::DEFAULT_BIND));
     let logfile = config
         .get_attribute(String::from("logfile"))
         .expect("Error: Log config not set.");
     let verbose = config
         .get_attribute(String::from("verbose"))
         .expect("Error: Verbose config not set.");
-    let db = Database::new(dbfilename);
+    let db = create_database(&config, dbfilename);
     let (server_sender, server_receiver) = mpsc::channel();
     let server_receiver = Arc::new(Mutex::new(server_receiver));
     let port = config
         .get_attribute(String::from("port"))
         .expect("Error: Port config not set.");
+    let server_dir = dir.clone();
     let t = thread::spawn(|| {
-        let mut server = Server::new(port, logfile, verbose, server_receiver, config_path)
-            .expect("Server couldn't be created.");
+        let mut server = Server::new(
+            server_dir,
+            port,
+            logfile,
+            verbose,
+            server_receiver,
+            config_path,
+        )
+        .expect("Server couldn't be created.");
         server.listen();
     });
     services::server_service::init(db, config, dir, server_sender);
@@ -64,6 +80,64 @@ pub fn run_redis_server() {
         .unwrap_or_else(|_| println!("Couldn't join server thread"));
 }
 
+/// Inicia el proxy.
+///
+/// Toma un argumento de la linea de comandos con la dirección de la configuración, que debe definir los campos
+/// `port` y `backends` (las direcciones "host:puerto" de los servidores Redis, separadas por espacios).
+/// El proxy recibe conexiones en las direcciones indicadas por `bind`, con el mismo formato que en el servidor; si no
+/// se especifica, en "127.0.0.1".
+/// De faltar algun parámetro de configuración, se corta la ejecución del programa.
+pub fn run_proxy() {
+    let argv = args().collect::<Vec<String>>();
+    if argv.len() != SERVER_ARGS {
+        println!("Cantidad de argumentos inválida");
+    }
+
+    let config = Config::new(argv[1].clone());
+    let port = config
+        .get_attribute(String::from("port"))
+        .expect("Error: Port config not set.")
+        .parse::<u16>()
+        .expect("Error: Invalid port.");
+    let bind = config
+        .get_attribute(String::from("bind"))
+        .unwrap_or_else(|_| String::from(services::proxy_service::DEFAULT_PROXY_BIND));
+    let backends = config
+        .get_attribute(String::from("backends"))
+        .expect("Error: Backends config not set.")
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    let listeners = services::server_service::parse_bind_addresses(&bind)
+        .and_then(|addresses| services::server_service::bind_tcp_listeners(&addresses, port))
+        .unwrap_or_else(|e| panic!("Proxy listener couldn't be created. Error: {}", e));
+    services::proxy_service::init(listeners, backends);
+}
+
+/// Crea la base de datos con el motor de almacenamiento indicado por el atributo `storage-engine` de la configuración:
+/// * `memory` (por defecto): los datos se mantienen en memoria y se bajan periódicamente al archivo `dbfilename`.
+/// * `log`: cada modificación se registra en el archivo `storage-file` (por defecto `storage.log`), del que se
+///   recuperan los datos al reiniciar el servidor (ver `LogStorage`).
+///
+/// Si el motor no es válido o su archivo no puede abrirse, se corta la ejecución del programa.
+fn create_database(config: &Config, dbfilename: String) -> Database {
+    let engine = config
+        .get_attribute(String::from("storage-engine"))
+        .unwrap_or_else(|_| String::from("memory"));
+    match engine.to_lowercase().as_str() {
+        "memory" => Database::new(dbfilename),
+        "log" => {
+            let storage_file = config
+                .get_attribute(String::from("storage-file"))
+                .unwrap_or_else(|_| String::from(DEFAULT_STORAGE_FILE));
+            let storage = LogStorage::open(&storage_file)
+                .unwrap_or_else(|e| panic!("Error: Storage couldn't be opened: {}", e));
+            Database::with_storage(dbfilename, Box::new(storage))
+        }
+        _ => panic!("Error: Invalid storage-engine '{}'.", engine),
+    }
+}
+
 /// Inicia el servidor web.
 ///
 /// Inicia el servidor web en la dirección "127.0.0.1:8080" y
@@ -109,7 +183,7 @@ fn handle_connection(mut stream: TcpStream, mut redis_stream: TcpStream) {
                     let redis_cmd = web_server_parser_service::get_body_as_resp(parsed_cmd);
                     let parsed_cmd = parser_service::parse_response(redis_cmd);
                     // envio comando redis al servidor
-                    match redis_stream.write_all(parsed_cmd.as_bytes()) {
+                    match redis_stream.write_all(&parsed_cmd) {
                         Ok(_) => {
                             // leo respuesta del servidor redis
                             match redis_stream.read(&mut buffer_redis) {
@@ -117,7 +191,7 @@ fn handle_connection(mut stream: TcpStream, mut redis_stream: TcpStream) {
                                     println!("Redis Server - Closed connection");
                                 }
                                 Ok(size) => {
-                                    let response = if buffer_redis.starts_with(b"Error:") {
+                                    let response = if buffer_redis.starts_with(b"-") {
                                         format!("HTTP/1.1 400 Bad Request\r\nContent-Length: {}\r\n\r\n{}",
                                             String::from_utf8_lossy(&buffer_redis[..size]).len(),
                                             String::from_utf8_lossy(&buffer_redis[..size])
@@ -218,7 +292,7 @@ pub fn overwrite_file(
 
 pub fn resp_to_string(response: RespType) -> String {
     match response {
-        RespType::RBulkString(string) => string,
+        RespType::RBulkString(bytes) => String::from_utf8_lossy(&bytes).to_string(),
         RespType::RInteger(integer) => integer.to_string(),
         RespType::RSignedNumber(negative) => negative.to_string(),
         RespType::RSimpleString(string) => string,
@@ -235,5 +309,6 @@ pub fn resp_to_string(response: RespType) -> String {
         RespType::RError(message) => message,
         RespType::RNullBulkString() => "(nil)".to_string(),
         RespType::RNullArray() => "(nil)".to_string(),
+        resp3_type => resp_to_string(resp3_type.into_protocol(RESP2)),
     }
 }
@@ -0,0 +1,5 @@
+use proyecto_taller_1::app;
+
+fn main() {
+    app::run_proxy();
+}
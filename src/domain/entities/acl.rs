@@ -0,0 +1,772 @@
+//! Usuarios ACL, sus permisos y el registro de accesos denegados.
+
+use super::session::DEFAULT_USER;
+use crate::services::commander::{
+    get_category_commands, get_command_channel_patterns, get_command_channels, get_command_keys,
+    get_command_names,
+};
+use crate::services::utils::glob_pattern::g_match;
+use crate::services::utils::resp_type::RespType;
+use crate::services::utils::sha256::sha256_hex;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, SystemTime};
+
+/// Cantidad máxima de entradas que conserva el registro de accesos denegados.
+const ACL_LOG_MAX_LEN: usize = 128;
+
+/// Tiempo durante el cual los accesos denegados equivalentes se agrupan en una misma entrada del registro.
+const ACL_LOG_GROUPING_WINDOW: Duration = Duration::from_secs(60);
+
+/// Comandos cuyos permisos pueden otorgarse por subcomando, con la forma `comando|subcomando`.
+const COMMANDS_WITH_SUBCOMMANDS: [&str; 4] = ["acl", "client", "config", "pubsub"];
+
+/// Usuario ACL.
+///
+/// Se compone por:
+/// * name: nombre del usuario.
+/// * enabled: indica si el usuario puede autenticarse (`on`) o no (`off`).
+/// * nopass: indica si el usuario se autentica con cualquier contraseña.
+/// * passwords: hashes SHA-256 de las contraseñas del usuario.
+/// * commands: reglas de comandos (`+<comando>`, `-<comando>`, `+@<categoría>` o `-@<categoría>`), en el orden en que se aplicaron.
+/// * keys: patrones de las claves a las que puede acceder.
+/// * channels: patrones de los canales en los que puede publicar o a los que puede suscribirse.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AclUser {
+    name: String,
+    enabled: bool,
+    nopass: bool,
+    passwords: Vec<String>,
+    commands: Vec<String>,
+    keys: Vec<String>,
+    channels: Vec<String>,
+}
+
+impl AclUser {
+    /// Crea un usuario deshabilitado, sin contraseñas y sin permisos.
+    pub fn new(name: String) -> Self {
+        AclUser {
+            name,
+            enabled: false,
+            nopass: false,
+            passwords: Vec::new(),
+            commands: Vec::new(),
+            keys: Vec::new(),
+            channels: Vec::new(),
+        }
+    }
+
+    /// Retorna el nombre del usuario.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Indica si el usuario está habilitado.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Indica si el usuario se autentica con cualquier contraseña.
+    pub fn is_nopass(&self) -> bool {
+        self.nopass
+    }
+
+    /// Retorna los hashes SHA-256 de las contraseñas del usuario.
+    pub fn get_passwords(&self) -> &[String] {
+        &self.passwords
+    }
+
+    /// Retorna las reglas de comandos del usuario separadas por un espacio.
+    ///
+    /// Salvo que la primera regla sea `+@all`, se antepone `-@all` ya que los comandos se prohíben por defecto.
+    pub fn get_commands(&self) -> String {
+        let mut rules = Vec::new();
+        if self.commands.first().map(String::as_str) != Some("+@all") {
+            rules.push(String::from("-@all"));
+        }
+        rules.extend(self.commands.iter().cloned());
+        rules.join(" ")
+    }
+
+    /// Retorna los patrones de las claves a las que puede acceder el usuario.
+    pub fn get_keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Retorna los patrones de los canales a los que puede acceder el usuario.
+    pub fn get_channels(&self) -> &[String] {
+        &self.channels
+    }
+
+    /// Aplica una regla ACL sobre el usuario.
+    ///
+    /// Las reglas posibles son:
+    /// * `on` / `off`: habilita o deshabilita al usuario.
+    /// * `>contraseña` / `<contraseña`: agrega o elimina una contraseña.
+    /// * `#hash` / `!hash`: agrega o elimina una contraseña a partir de su hash SHA-256.
+    /// * `nopass` / `resetpass`: permite autenticarse con cualquier contraseña, o elimina todas las contraseñas.
+    /// * `~patrón` / `allkeys` / `resetkeys`: agrega un patrón de claves, permite todas las claves o las elimina.
+    /// * `&patrón` / `allchannels` / `resetchannels`: lo mismo para los canales.
+    /// * `+comando` / `-comando`: permite o prohíbe un comando, o un subcomando con la forma `comando|subcomando`.
+    /// * `+@categoría` / `-@categoría`: permite o prohíbe los comandos de una categoría.
+    /// * `allcommands` / `nocommands`: equivalen a `+@all` y `-@all`.
+    /// * `reset`: elimina todos los permisos y contraseñas y deshabilita al usuario.
+    ///
+    /// Si la regla no es válida devuelve el motivo.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::entities::acl::AclUser;
+    ///
+    /// let mut user = AclUser::new(String::from("worker"));
+    /// user.apply_rule("on").unwrap();
+    /// user.apply_rule("+@all").unwrap();
+    /// user.apply_rule("-flushdb").unwrap();
+    /// user.apply_rule("~job:*").unwrap();
+    ///
+    /// assert!(user.can_run("get", None));
+    /// assert!(!user.can_run("flushdb", None));
+    /// assert!(user.can_access_key(b"job:1"));
+    /// assert!(!user.can_access_key(b"user:1"));
+    /// assert!(user.apply_rule("+unknown").is_err());
+    /// ```
+    pub fn apply_rule(&mut self, rule: &str) -> Result<(), String> {
+        match rule.to_lowercase().as_str() {
+            "on" => self.enabled = true,
+            "off" => self.enabled = false,
+            "nopass" => {
+                self.nopass = true;
+                self.passwords.clear();
+            }
+            "resetpass" => {
+                self.nopass = false;
+                self.passwords.clear();
+            }
+            "allkeys" => self.keys = vec![String::from("*")],
+            "resetkeys" => self.keys.clear(),
+            "allchannels" => self.channels = vec![String::from("*")],
+            "resetchannels" => self.channels.clear(),
+            "allcommands" => self.commands = vec![String::from("+@all")],
+            "nocommands" => self.commands.clear(),
+            "reset" => *self = AclUser::new(self.name.clone()),
+            _ => return self.apply_modifier(rule),
+        }
+        Ok(())
+    }
+
+    /// Aplica una regla que comienza con un prefijo seguido de un valor, ver `apply_rule`.
+    fn apply_modifier(&mut self, rule: &str) -> Result<(), String> {
+        let mut chars = rule.chars();
+        let prefix = chars.next();
+        let value = chars.as_str();
+        match prefix {
+            Some('>') => {
+                self.add_password(sha256_hex(value.as_bytes()));
+            }
+            Some('#') => {
+                if !is_valid_password_hash(value) {
+                    return Err(String::from("The password hash must be exactly 64 characters and contain only lowercase hexadecimal characters"));
+                }
+                self.add_password(value.to_string());
+            }
+            Some('<') => self.remove_password(&sha256_hex(value.as_bytes()))?,
+            Some('!') => self.remove_password(value)?,
+            Some('~') if !value.is_empty() => {
+                if !self.keys.iter().any(|pattern| pattern == value) {
+                    self.keys.push(value.to_string());
+                }
+            }
+            Some('&') if !value.is_empty() => {
+                if !self.channels.iter().any(|pattern| pattern == value) {
+                    self.channels.push(value.to_string());
+                }
+            }
+            Some('+') | Some('-') => {
+                let target = value.to_lowercase();
+                if !is_valid_command_target(&target) {
+                    return Err(String::from("Unknown command or category name in ACL"));
+                }
+                if target == "@all" {
+                    self.commands.clear();
+                }
+                if prefix == Some('+') || !self.commands.is_empty() {
+                    self.commands.push(format!("{}{}", &rule[..1], target));
+                }
+            }
+            _ => return Err(String::from("Syntax error")),
+        }
+        Ok(())
+    }
+
+    /// Agrega el hash de una contraseña, si el usuario no la tenía.
+    fn add_password(&mut self, hash: String) {
+        self.nopass = false;
+        if !self.passwords.contains(&hash) {
+            self.passwords.push(hash);
+        }
+    }
+
+    /// Elimina el hash de una contraseña. Devuelve error si el usuario no la tenía.
+    fn remove_password(&mut self, hash: &str) -> Result<(), String> {
+        match self.passwords.iter().position(|password| password == hash) {
+            Some(pos) => {
+                self.passwords.remove(pos);
+                Ok(())
+            }
+            None => Err(String::from(
+                "The password you are trying to remove from the user does not exist",
+            )),
+        }
+    }
+
+    /// Indica si `password` es una de las contraseñas del usuario, o si el usuario no requiere contraseña.
+    pub fn check_password(&self, password: &str) -> bool {
+        self.nopass || self.passwords.contains(&sha256_hex(password.as_bytes()))
+    }
+
+    /// Indica si el usuario puede ejecutar el comando, o el subcomando si se especifica uno.
+    ///
+    /// Las reglas de comandos se evalúan en orden y prevalece la última que alcanza al comando.
+    pub fn can_run(&self, command: &str, subcommand: Option<&str>) -> bool {
+        let full_name = subcommand.map(|subcommand| format!("{}|{}", command, subcommand));
+        let matches = |name: &str| name == command || Some(name) == full_name.as_deref();
+        let mut allowed = false;
+        for rule in &self.commands {
+            let (sign, target) = rule.split_at(1);
+            let applies = match target.strip_prefix('@') {
+                Some(category) => get_category_commands(category)
+                    .is_some_and(|commands| commands.iter().any(|name| matches(name))),
+                None => matches(target),
+            };
+            if applies {
+                allowed = sign == "+";
+            }
+        }
+        allowed
+    }
+
+    /// Indica si el usuario puede acceder a la clave.
+    pub fn can_access_key(&self, key: &[u8]) -> bool {
+        self.keys
+            .iter()
+            .any(|pattern| g_match(pattern.as_bytes(), key))
+    }
+
+    /// Indica si el usuario puede acceder al canal.
+    pub fn can_access_channel(&self, channel: &[u8]) -> bool {
+        self.channels
+            .iter()
+            .any(|pattern| g_match(pattern.as_bytes(), channel))
+    }
+
+    /// Indica si el usuario puede suscribirse al patrón de canales.
+    ///
+    /// El patrón debe coincidir literalmente con alguno de los permitidos, ya que de otra manera podría recibir
+    /// mensajes de canales a los que no puede acceder. Si se le permiten todos los canales (`allchannels`), puede suscribirse a cualquier patrón.
+    pub fn can_access_channel_pattern(&self, pattern: &[u8]) -> bool {
+        self.channels
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.as_bytes() == pattern)
+    }
+
+    /// Describe al usuario con las reglas que permiten volver a crearlo, con el formato de `acl list` y del archivo ACL.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::entities::acl::AclUser;
+    ///
+    /// let mut user = AclUser::new(String::from("worker"));
+    /// user.apply_rule("on").unwrap();
+    /// user.apply_rule("~job:*").unwrap();
+    /// user.apply_rule("+get").unwrap();
+    /// assert_eq!(user.describe(), "user worker on ~job:* resetchannels -@all +get");
+    /// ```
+    pub fn describe(&self) -> String {
+        let mut rules = vec![
+            String::from("user"),
+            self.name.clone(),
+            String::from(if self.enabled { "on" } else { "off" }),
+        ];
+        if self.nopass {
+            rules.push(String::from("nopass"));
+        }
+        rules.extend(self.passwords.iter().map(|hash| format!("#{}", hash)));
+        if self.keys.is_empty() {
+            rules.push(String::from("resetkeys"));
+        }
+        rules.extend(self.keys.iter().map(|pattern| format!("~{}", pattern)));
+        if self.channels.is_empty() {
+            rules.push(String::from("resetchannels"));
+        }
+        rules.extend(self.channels.iter().map(|pattern| format!("&{}", pattern)));
+        rules.push(self.get_commands());
+        rules.join(" ")
+    }
+}
+
+/// Indica si `hash` es un hash SHA-256 válido: 64 dígitos hexadecimales en minúscula.
+fn is_valid_password_hash(hash: &str) -> bool {
+    hash.len() == 64
+        && hash
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+}
+
+/// Indica si `target` es una categoría (`@categoría`), un comando o un subcomando (`comando|subcomando`) existente.
+fn is_valid_command_target(target: &str) -> bool {
+    if let Some(category) = target.strip_prefix('@') {
+        return get_category_commands(category).is_some();
+    }
+    let command = target.split('|').next().unwrap_or_default();
+    get_command_names().iter().any(|name| name == command)
+}
+
+/// Motivo por el cual se le deniega un acceso a un usuario.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AclDenial {
+    Auth,
+    Command(String),
+    Key(String),
+    Channel(String),
+}
+
+impl AclDenial {
+    /// Retorna el motivo tal como lo muestra `acl log`.
+    pub fn get_reason(&self) -> &str {
+        match self {
+            AclDenial::Auth => "auth",
+            AclDenial::Command(_) => "command",
+            AclDenial::Key(_) => "key",
+            AclDenial::Channel(_) => "channel",
+        }
+    }
+
+    /// Retorna el comando, la clave o el canal al que se le denegó el acceso.
+    pub fn get_object(&self) -> String {
+        match self {
+            AclDenial::Auth => String::from("AUTH"),
+            AclDenial::Command(object) | AclDenial::Key(object) | AclDenial::Channel(object) => {
+                object.clone()
+            }
+        }
+    }
+
+    /// Retorna el error que se le devuelve al cliente.
+    pub fn get_error(&self, username: &str) -> String {
+        match self {
+            AclDenial::Auth => {
+                String::from("WRONGPASS invalid username-password pair or user is disabled.")
+            }
+            AclDenial::Command(command) => format!(
+                "NOPERM User {} has no permissions to run the '{}' command",
+                username, command
+            ),
+            AclDenial::Key(_) => String::from("NOPERM No permissions to access a key"),
+            AclDenial::Channel(_) => String::from("NOPERM No permissions to access a channel"),
+        }
+    }
+}
+
+/// Entrada del registro de accesos denegados.
+///
+/// Los accesos denegados con el mismo motivo, objeto y usuario se agrupan en una misma entrada,
+/// contando la cantidad de veces que ocurrieron.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AclLogEntry {
+    count: usize,
+    reason: String,
+    object: String,
+    username: String,
+    client_info: String,
+    created: SystemTime,
+    updated: SystemTime,
+}
+
+impl AclLogEntry {
+    /// Retorna la cantidad de veces que ocurrió el acceso denegado.
+    pub fn get_count(&self) -> usize {
+        self.count
+    }
+
+    /// Retorna el motivo: `auth`, `command`, `key` o `channel`.
+    pub fn get_reason(&self) -> &str {
+        &self.reason
+    }
+
+    /// Retorna el contexto en el que se denegó el acceso. Los comandos siempre se ejecutan en el nivel principal.
+    pub fn get_context(&self) -> &str {
+        "toplevel"
+    }
+
+    /// Retorna el comando, la clave o el canal al que se denegó el acceso.
+    pub fn get_object(&self) -> &str {
+        &self.object
+    }
+
+    /// Retorna el usuario al que se le denegó el acceso.
+    pub fn get_username(&self) -> &str {
+        &self.username
+    }
+
+    /// Retorna la información de la conexión del cliente, con el formato de `client info`.
+    pub fn get_client_info(&self) -> &str {
+        &self.client_info
+    }
+
+    /// Retorna los segundos transcurridos desde la última vez que se denegó el acceso.
+    pub fn get_age(&self) -> f64 {
+        self.updated
+            .elapsed()
+            .map(|age| age.as_secs_f64())
+            .unwrap_or_default()
+    }
+
+    /// Retorna el momento en que se registró la entrada, en milisegundos desde el epoch de UNIX.
+    pub fn get_created_timestamp(&self) -> u128 {
+        to_millis(self.created)
+    }
+
+    /// Retorna el momento en que se actualizó la entrada por última vez, en milisegundos desde el epoch de UNIX.
+    pub fn get_updated_timestamp(&self) -> u128 {
+        to_millis(self.updated)
+    }
+}
+
+/// Convierte un momento a milisegundos desde el epoch de UNIX.
+fn to_millis(time: SystemTime) -> u128 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default()
+}
+
+/// Usuarios ACL del servidor y registro de los accesos denegados.
+#[derive(Debug)]
+pub struct Acl {
+    users: BTreeMap<String, AclUser>,
+    log: VecDeque<AclLogEntry>,
+}
+
+impl Default for Acl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Acl {
+    /// Crea la lista de usuarios con el usuario `default`, que no requiere contraseña y tiene todos los permisos.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::entities::acl::Acl;
+    ///
+    /// let acl = Acl::new();
+    /// assert_eq!(acl.get_users(), vec!["default".to_string()]);
+    /// assert!(acl.is_nopass("default"));
+    /// ```
+    pub fn new() -> Self {
+        let mut default_user = AclUser::new(String::from(DEFAULT_USER));
+        for rule in ["on", "nopass", "allkeys", "allchannels", "allcommands"] {
+            default_user
+                .apply_rule(rule)
+                .expect("Invalid default user rule");
+        }
+        let mut users = BTreeMap::new();
+        users.insert(String::from(DEFAULT_USER), default_user);
+        Acl {
+            users,
+            log: VecDeque::new(),
+        }
+    }
+
+    /// Crea el usuario si no existe y le aplica las reglas.
+    ///
+    /// Las reglas se aplican todas o ninguna: si alguna no es válida, el usuario no se modifica y se
+    /// devuelve el error correspondiente.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::entities::acl::Acl;
+    ///
+    /// let mut acl = Acl::new();
+    /// acl.set_user("worker", &["on".to_string(), ">secreto".to_string()]).unwrap();
+    /// assert!(acl.authenticate("worker", "secreto"));
+    ///
+    /// assert!(acl.set_user("worker", &["off".to_string(), "+desconocido".to_string()]).is_err());
+    /// assert!(acl.get_user("worker").unwrap().is_enabled());
+    /// ```
+    pub fn set_user(&mut self, name: &str, rules: &[String]) -> Result<(), String> {
+        let mut user = self
+            .users
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| AclUser::new(name.to_string()));
+        for rule in rules {
+            user.apply_rule(rule).map_err(|reason| {
+                format!("Error in ACL SETUSER modifier '{}': {}", rule, reason)
+            })?;
+        }
+        self.users.insert(name.to_string(), user);
+        Ok(())
+    }
+
+    /// Retorna el usuario, si existe.
+    pub fn get_user(&self, name: &str) -> Option<&AclUser> {
+        self.users.get(name)
+    }
+
+    /// Elimina al usuario. Devuelve true si existía.
+    pub fn del_user(&mut self, name: &str) -> bool {
+        self.users.remove(name).is_some()
+    }
+
+    /// Retorna los nombres de los usuarios, ordenados alfabéticamente.
+    pub fn get_users(&self) -> Vec<String> {
+        self.users.keys().cloned().collect()
+    }
+
+    /// Retorna la descripción de cada usuario, ver `AclUser::describe`.
+    pub fn list(&self) -> Vec<String> {
+        self.users.values().map(AclUser::describe).collect()
+    }
+
+    /// Indica si el usuario existe, está habilitado y `password` es una de sus contraseñas.
+    pub fn authenticate(&self, name: &str, password: &str) -> bool {
+        self.users
+            .get(name)
+            .is_some_and(|user| user.is_enabled() && user.check_password(password))
+    }
+
+    /// Indica si el usuario existe, está habilitado y no requiere contraseña.
+    pub fn is_nopass(&self, name: &str) -> bool {
+        self.users
+            .get(name)
+            .is_some_and(|user| user.is_enabled() && user.is_nopass())
+    }
+
+    /// Verifica que el usuario pueda ejecutar el comando y acceder a sus claves y canales.
+    ///
+    /// Si no puede, devuelve el motivo por el que se le deniega el acceso.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::entities::acl::{Acl, AclDenial};
+    /// # use proyecto_taller_1::services::utils::resp_type::RespType;
+    ///
+    /// let mut acl = Acl::new();
+    /// acl.set_user("worker", &["on".to_string(), "+@all".to_string(), "~job:*".to_string()]).unwrap();
+    ///
+    /// let get = vec![RespType::RBulkString("get".into()), RespType::RBulkString("job:1".into())];
+    /// assert_eq!(acl.check_permissions("worker", &get), Ok(()));
+    ///
+    /// let get = vec![RespType::RBulkString("get".into()), RespType::RBulkString("user:1".into())];
+    /// assert_eq!(acl.check_permissions("worker", &get), Err(AclDenial::Key("user:1".to_string())));
+    /// ```
+    pub fn check_permissions(&self, username: &str, cmd: &[RespType]) -> Result<(), AclDenial> {
+        let command = cmd
+            .first()
+            .and_then(RespType::as_str)
+            .unwrap_or_default()
+            .to_lowercase();
+        let subcommand = if COMMANDS_WITH_SUBCOMMANDS.contains(&command.as_str()) {
+            cmd.get(1).and_then(RespType::as_str).map(str::to_lowercase)
+        } else {
+            None
+        };
+        let full_name = match &subcommand {
+            Some(subcommand) => format!("{}|{}", command, subcommand),
+            None => command.clone(),
+        };
+        // los comandos inexistentes no se verifican, para que el cliente reciba el error correspondiente
+        if !get_command_names().contains(&command) {
+            return Ok(());
+        }
+        let user = match self.users.get(username) {
+            Some(user) => user,
+            None => return Err(AclDenial::Command(full_name)),
+        };
+        if !user.can_run(&command, subcommand.as_deref()) {
+            return Err(AclDenial::Command(full_name));
+        }
+        for key in get_command_keys(cmd) {
+            let key = key.as_bytes().unwrap_or_default();
+            if !user.can_access_key(key) {
+                return Err(AclDenial::Key(String::from_utf8_lossy(key).to_string()));
+            }
+        }
+        for channel in get_command_channels(cmd) {
+            let channel = channel.as_bytes().unwrap_or_default();
+            if !user.can_access_channel(channel) {
+                return Err(AclDenial::Channel(
+                    String::from_utf8_lossy(channel).to_string(),
+                ));
+            }
+        }
+        for pattern in get_command_channel_patterns(cmd) {
+            let pattern = pattern.as_bytes().unwrap_or_default();
+            if !user.can_access_channel_pattern(pattern) {
+                return Err(AclDenial::Channel(
+                    String::from_utf8_lossy(pattern).to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Registra un acceso denegado.
+    ///
+    /// Si en el último minuto se registró un acceso denegado con el mismo motivo, objeto y usuario,
+    /// se actualiza esa entrada en lugar de agregar una nueva.
+    pub fn add_log_entry(&mut self, denial: &AclDenial, username: &str, client_info: String) {
+        let now = SystemTime::now();
+        let object = denial.get_object();
+        let existing = self.log.iter().position(|entry| {
+            entry.reason == denial.get_reason()
+                && entry.object == object
+                && entry.username == username
+                && now
+                    .duration_since(entry.updated)
+                    .is_ok_and(|elapsed| elapsed < ACL_LOG_GROUPING_WINDOW)
+        });
+        let entry = match existing.and_then(|pos| self.log.remove(pos)) {
+            Some(mut entry) => {
+                entry.count += 1;
+                entry.client_info = client_info;
+                entry.updated = now;
+                entry
+            }
+            None => AclLogEntry {
+                count: 1,
+                reason: denial.get_reason().to_string(),
+                object,
+                username: username.to_string(),
+                client_info,
+                created: now,
+                updated: now,
+            },
+        };
+        self.log.push_front(entry);
+        self.log.truncate(ACL_LOG_MAX_LEN);
+    }
+
+    /// Retorna las entradas del registro de accesos denegados, de la más reciente a la más antigua.
+    ///
+    /// Si se especifica `count`, retorna como máximo esa cantidad de entradas.
+    pub fn get_log(&self, count: Option<usize>) -> Vec<AclLogEntry> {
+        self.log
+            .iter()
+            .take(count.unwrap_or(self.log.len()))
+            .cloned()
+            .collect()
+    }
+
+    /// Elimina todas las entradas del registro de accesos denegados.
+    pub fn reset_log(&mut self) {
+        self.log.clear();
+    }
+}
+
+#[test]
+fn test_01_user_can_run_subcommand_allowed_after_command_is_denied() {
+    let mut user = AclUser::new(String::from("worker"));
+    for rule in ["+@all", "-config", "+config|get"] {
+        user.apply_rule(rule).unwrap();
+    }
+
+    assert!(user.can_run("config", Some("get")));
+    assert!(!user.can_run("config", Some("set")));
+}
+
+#[test]
+fn test_02_category_rules_apply_to_subcommands() {
+    let mut user = AclUser::new(String::from("worker"));
+    for rule in ["+@all", "-@admin"] {
+        user.apply_rule(rule).unwrap();
+    }
+
+    assert!(user.can_run("client", Some("setname")));
+    assert!(!user.can_run("client", Some("kill")));
+    assert!(!user.can_run("config", Some("get")));
+}
+
+#[test]
+fn test_03_passwords_are_stored_hashed() {
+    let mut user = AclUser::new(String::from("worker"));
+    user.apply_rule(">secreto").unwrap();
+
+    assert_eq!(user.get_passwords(), &[sha256_hex(b"secreto")]);
+    assert!(user.check_password("secreto"));
+    assert!(user.apply_rule("<otra").is_err());
+    user.apply_rule("<secreto").unwrap();
+    assert!(!user.check_password("secreto"));
+}
+
+#[test]
+fn test_04_channel_permissions() {
+    let mut acl = Acl::new();
+    acl.set_user(
+        "worker",
+        &[
+            String::from("on"),
+            String::from("+@all"),
+            String::from("&jobs.*"),
+        ],
+    )
+    .unwrap();
+    let publish = vec![
+        RespType::RBulkString("publish".into()),
+        RespType::RBulkString("news".into()),
+        RespType::RBulkString("hola".into()),
+    ];
+
+    assert_eq!(
+        acl.check_permissions("worker", &publish),
+        Err(AclDenial::Channel(String::from("news")))
+    );
+}
+
+#[test]
+fn test_05_log_groups_equivalent_denials() {
+    let mut acl = Acl::new();
+    let denial = AclDenial::Command(String::from("flushdb"));
+    acl.add_log_entry(&denial, "worker", String::new());
+    acl.add_log_entry(&AclDenial::Auth, "worker", String::new());
+    acl.add_log_entry(&denial, "worker", String::new());
+
+    let log = acl.get_log(None);
+    assert_eq!(log.len(), 2);
+    assert_eq!(log[0].get_object(), "flushdb");
+    assert_eq!(log[0].get_count(), 2);
+    assert_eq!(acl.get_log(Some(1)).len(), 1);
+}
+
+#[test]
+fn test_06_pattern_subscriptions_must_match_allowed_patterns_literally() {
+    let mut acl = Acl::new();
+    acl.set_user(
+        "worker",
+        &[
+            String::from("on"),
+            String::from("+@all"),
+            String::from("&jobs.*"),
+        ],
+    )
+    .unwrap();
+    let psubscribe = |pattern: &str| {
+        vec![
+            RespType::RBulkString("psubscribe".into()),
+            RespType::RBulkString(pattern.into()),
+        ]
+    };
+
+    assert_eq!(
+        acl.check_permissions("worker", &psubscribe("jobs.*")),
+        Ok(())
+    );
+    assert_eq!(
+        acl.check_permissions("worker", &psubscribe("jobs.urgent")),
+        Err(AclDenial::Channel(String::from("jobs.urgent")))
+    );
+    assert_eq!(
+        acl.check_permissions("worker", &psubscribe("*")),
+        Err(AclDenial::Channel(String::from("*")))
+    );
+}
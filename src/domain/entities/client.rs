@@ -1,51 +1,142 @@
 //! Representación de un cliente del Servidor.
 
-use std::{
-    io::{Error, Write},
-    net::{SocketAddr, TcpStream},
-};
+use super::replication::ReplicaState;
+use super::session::DEFAULT_USER;
+use super::tracking::ClientTracking;
+use crate::domain::implementations::connection::Connection;
+use crate::domain::implementations::output_queue::{OutputBufferLimit, OutputQueue};
+use crate::services::utils::resp_type::RESP2;
+use std::{io::Error, net::Shutdown, time::SystemTime};
 
 /// Representa un cliente conectado al servidor.
 /// Se compone por su dirección de origen, el stream sobre el cual se leen comandos y escriben respuestas, un estado de suscripción y un estado de monitoreo.
-/// La dirección de origen es única y se compone por una IP y un puerto.
+/// Todo lo que se le envía al cliente (las respuestas a sus comandos, los mensajes de los canales suscritos y los de `monitor`)
+/// se encola en su cola de salida, que los escribe sobre el stream desde un hilo propio y en el orden en que se encolaron.
+/// La dirección de origen se compone por una IP y un puerto, o por la ruta del socket Unix y el puerto 0. Como los clientes
+/// de un socket Unix comparten la dirección, cada cliente se identifica por el identificador único que le asigna el servidor.
 /// Un cliente pasa a estar en estado "suscrito" cuando ejecuta el comando `subscribe`, permanece en dicho estado hasta que se desuscriba con el comando `unsubscribe`.
 /// Un cliente pasa a estar en estado "monitor" cuando ejecuta el comando `monitor`, permanece en dicho estado hasta que detenga la conexión con ctrl-c.
 /// Ambos estados son bloqueantes, es decir que el cliente no podrá enviar otro comando mientras se encuentre en alguno de ellos.
+/// Además, registra la versión del protocolo RESP negociada con el comando `hello`, el nombre que se haya asignado a la conexión,
+/// la librería cliente informada con `client setinfo`, el momento de creación y de la última interacción, si está en modo "no-evict", el usuario ACL con el que se autenticó, el seguimiento de claves activado con `client tracking`
+/// y, si la conexión corresponde a una réplica del servidor, su estado de replicación.
 #[derive(Debug)]
 pub struct Client {
-    addrs: SocketAddr,
-    stream: TcpStream,
+    id: usize,
+    addrs: String,
+    stream: Connection,
+    output: OutputQueue,
     subscriber: bool,
     monitoring: bool,
+    protocol: usize,
+    name: Option<String>,
+    lib_name: Option<String>,
+    lib_version: Option<String>,
+    created: SystemTime,
+    last_interaction: SystemTime,
+    last_command: String,
+    no_evict: bool,
+    user: String,
+    tracking: Option<ClientTracking>,
+    replica: Option<ReplicaState>,
+}
+
+/// Criterios para seleccionar clientes en los comandos `client list` y `client kill`.
+///
+/// Un cliente cumple con el filtro si cumple con todos los criterios especificados:
+/// * ids: su identificador está incluido en la lista. Si la lista es vacía, no se filtra por identificador.
+/// * addrs: su dirección de origen coincide.
+/// * laddr: la dirección local de la conexión coincide.
+/// * user: su usuario coincide.
+/// * client_type: su tipo coincide (normal, pubsub, master o replica).
+/// * skip: se excluye al cliente con este identificador, utilizado para que un cliente no se elimine a sí mismo.
+#[derive(Debug, Default, Clone)]
+pub struct ClientFilter {
+    pub ids: Vec<usize>,
+    pub addrs: Option<String>,
+    pub laddr: Option<String>,
+    pub user: Option<String>,
+    pub client_type: Option<String>,
+    pub skip: Option<usize>,
+}
+
+impl ClientFilter {
+    /// Indica si el cliente cumple con todos los criterios del filtro.
+    pub fn matches(&self, client: &Client) -> bool {
+        (self.ids.is_empty() || self.ids.contains(client.get_id()))
+            && self
+                .addrs
+                .as_ref()
+                .is_none_or(|addrs| addrs == client.get_address())
+            && self
+                .laddr
+                .as_ref()
+                .is_none_or(|laddr| laddr == &client.get_local_address())
+            && self
+                .user
+                .as_ref()
+                .is_none_or(|user| user == client.get_user())
+            && self.client_type.as_ref().is_none_or(|client_type| {
+                client_type == client.get_type()
+                    || (client_type == "slave" && client.get_type() == "replica")
+            })
+            && self.skip.as_ref() != Some(client.get_id())
+    }
 }
 
 impl Client {
     /// Crea una instancia del cliente.
     ///
-    /// Inicia al cliente con los estados `subscriber` y `monitoring` en false.
-    pub fn new(addrs: SocketAddr, stream: TcpStream) -> Self {
+    /// Inicia al cliente con los estados `subscriber` y `monitoring` en false, utilizando RESP2, sin nombre y con el usuario `default`.
+    /// El identificador lo asigna el servidor al registrar al cliente.
+    /// Devuelve error si no puede crearse el manejador de la conexión que utiliza la cola de salida.
+    pub fn new(addrs: String, stream: Connection) -> Result<Self, Error> {
         let subscriber = false;
         let monitoring = false;
-        Client {
+        let output = OutputQueue::new(stream.try_clone()?);
+        Ok(Client {
+            id: 0,
             addrs,
             stream,
+            output,
             subscriber,
             monitoring,
-        }
+            protocol: RESP2,
+            name: None,
+            lib_name: None,
+            lib_version: None,
+            created: SystemTime::now(),
+            last_interaction: SystemTime::now(),
+            last_command: String::from("NULL"),
+            no_evict: false,
+            user: String::from(DEFAULT_USER),
+            tracking: None,
+            replica: None,
+        })
+    }
+
+    /// Retorna el identificador del cliente.
+    pub fn get_id(&self) -> &usize {
+        &self.id
+    }
+
+    /// Actualiza el identificador del cliente.
+    pub fn set_id(&mut self, id: usize) {
+        self.id = id;
     }
 
     /// Retorna una referencia al stream del cliente.
-    pub fn get_stream(&self) -> &TcpStream {
+    pub fn get_stream(&self) -> &Connection {
         &self.stream
     }
 
     /// Retorna una referencia mutable al stream del cliente.
-    pub fn get_stream_mut(&mut self) -> Result<TcpStream, Error> {
+    pub fn get_stream_mut(&mut self) -> Result<Connection, Error> {
         self.stream.try_clone()
     }
 
     /// Retorna una referencia a la dirección del cliente.
-    pub fn get_address(&self) -> &SocketAddr {
+    pub fn get_address(&self) -> &String {
         &self.addrs
     }
 
@@ -69,9 +160,218 @@ impl Client {
         self.monitoring = monitor;
     }
 
-    /// Escribe un mensaje sobre el stream del cliente.
+    /// Retorna la versión del protocolo RESP que utiliza el cliente.
+    pub fn get_protocol(&self) -> &usize {
+        &self.protocol
+    }
+
+    /// Actualiza la versión del protocolo RESP.
+    pub fn set_protocol(&mut self, protocol: usize) {
+        self.protocol = protocol;
+    }
+
+    /// Retorna el nombre asignado a la conexión, si lo tiene.
+    pub fn get_name(&self) -> Option<&String> {
+        self.name.as_ref()
+    }
+
+    /// Actualiza el nombre de la conexión. Si es `None` la conexión queda sin nombre.
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+
+    /// Actualiza el nombre de la librería cliente.
+    pub fn set_lib_name(&mut self, lib_name: String) {
+        self.lib_name = Some(lib_name);
+    }
+
+    /// Actualiza la versión de la librería cliente.
+    pub fn set_lib_version(&mut self, lib_version: String) {
+        self.lib_version = Some(lib_version);
+    }
+
+    /// Registra el último comando ejecutado por el cliente y el momento en que lo hizo.
+    pub fn set_last_command(&mut self, command: String) {
+        self.last_command = command;
+        self.last_interaction = SystemTime::now();
+    }
+
+    /// Vuelve la conexión a su estado inicial, como si el cliente se acabara de conectar.
+    ///
+    /// Se conservan el identificador, la dirección y la librería cliente.
+    pub fn reset(&mut self) {
+        self.subscriber = false;
+        self.monitoring = false;
+        self.protocol = RESP2;
+        self.name = None;
+        self.user = String::from(DEFAULT_USER);
+        self.tracking = None;
+    }
+
+    /// Retorna la dirección del cliente con el formato de las líneas de `monitor`: `ip:puerto`, o `unix:<ruta del socket>`
+    /// para los clientes del socket Unix.
+    pub fn get_monitor_address(&self) -> String {
+        match self.stream {
+            Connection::Unix(_) => format!("unix:{}", self.addrs.trim_end_matches(":0")),
+            _ => self.addrs.clone(),
+        }
+    }
+
+    /// Retorna la dirección local de la conexión.
+    pub fn get_local_address(&self) -> String {
+        self.stream.local_address().unwrap_or_default()
+    }
+
+    /// Retorna el usuario con el que está autenticado el cliente.
+    pub fn get_user(&self) -> &str {
+        &self.user
+    }
+
+    /// Actualiza el usuario con el que está autenticado el cliente.
+    pub fn set_user(&mut self, user: String) {
+        self.user = user;
+    }
+
+    /// Retorna el tipo de cliente: "replica" si es una réplica sincronizada con el servidor, "pubsub" si está suscrito
+    /// a algún canal, "normal" en otro caso.
+    pub fn get_type(&self) -> &str {
+        if self.is_replica() {
+            "replica"
+        } else if self.subscriber {
+            "pubsub"
+        } else {
+            "normal"
+        }
+    }
+
+    /// Indica si el cliente es una réplica que ya pidió la sincronización con `psync`.
+    pub fn is_replica(&self) -> bool {
+        self.replica.as_ref().is_some_and(|replica| replica.online)
+    }
+
+    /// Retorna el estado de replicación del cliente, si se identificó como réplica.
+    pub fn get_replica(&self) -> Option<&ReplicaState> {
+        self.replica.as_ref()
+    }
+
+    /// Retorna una referencia mutable al estado de replicación del cliente, si se identificó como réplica.
+    pub fn get_replica_mut(&mut self) -> Option<&mut ReplicaState> {
+        self.replica.as_mut()
+    }
+
+    /// Actualiza el estado de replicación del cliente.
+    pub fn set_replica(&mut self, replica: Option<ReplicaState>) {
+        self.replica = replica;
+    }
+
+    /// Actualiza el modo "no-evict", que excluye al cliente de los desalojos de memoria.
+    pub fn set_no_evict(&mut self, no_evict: bool) {
+        self.no_evict = no_evict;
+    }
+
+    /// Retorna el seguimiento de claves del cliente, si lo tiene activado.
+    pub fn get_tracking(&self) -> Option<&ClientTracking> {
+        self.tracking.as_ref()
+    }
+
+    /// Retorna una referencia mutable al seguimiento de claves del cliente, si lo tiene activado.
+    pub fn get_tracking_mut(&mut self) -> Option<&mut ClientTracking> {
+        self.tracking.as_mut()
+    }
+
+    /// Activa el seguimiento de claves del cliente o, si es `None`, lo desactiva.
+    pub fn set_tracking(&mut self, tracking: Option<ClientTracking>) {
+        self.tracking = tracking;
+    }
+
+    /// Retorna los segundos que pasaron desde el último comando del cliente.
+    pub fn get_idle_time(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(self.last_interaction)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    /// Cierra la conexión del cliente.
+    ///
+    /// El worker que atiende al cliente deja de recibir mensajes y termina la conexión.
+    pub fn shutdown(&self) -> Result<(), Error> {
+        self.stream.shutdown(Shutdown::Both)
+    }
+
+    /// Retorna una línea con la información de la conexión, con el formato de `client info`.
+    ///
+    /// `subscriptions`, `pattern_subscriptions` y `shard_subscriptions` son la cantidad de canales, de patrones y de canales
+    /// sharded a los que está suscrito el cliente.
+    pub fn get_info(
+        &self,
+        subscriptions: usize,
+        pattern_subscriptions: usize,
+        shard_subscriptions: usize,
+    ) -> String {
+        let mut flags = String::new();
+        if self.monitoring {
+            flags.push('O');
+        }
+        if self.subscriber {
+            flags.push('P');
+        }
+        if self.is_replica() {
+            flags.push('S');
+        }
+        if self.no_evict {
+            flags.push('e');
+        }
+        if matches!(self.stream, Connection::Unix(_)) {
+            flags.push('U');
+        }
+        if let Some(tracking) = &self.tracking {
+            flags.push('t');
+            if tracking.is_redirect_broken() {
+                flags.push('R');
+            }
+        }
+        if flags.is_empty() {
+            flags.push('N');
+        }
+        format!(
+            "id={} addr={} laddr={} name={} age={} idle={} flags={} db=0 sub={} psub={} ssub={} omem={} cmd={} user={} resp={} lib-name={} lib-ver={}\n",
+            self.id,
+            self.addrs,
+            self.get_local_address(),
+            self.name.as_deref().unwrap_or_default(),
+            SystemTime::now()
+                .duration_since(self.created)
+                .unwrap_or_default()
+                .as_secs(),
+            self.get_idle_time(),
+            flags,
+            subscriptions,
+            pattern_subscriptions,
+            shard_subscriptions,
+            self.output.pending_bytes(),
+            self.last_command,
+            self.get_user(),
+            self.protocol,
+            self.lib_name.as_deref().unwrap_or_default(),
+            self.lib_version.as_deref().unwrap_or_default()
+        )
+    }
+
+    /// Encola un mensaje para el cliente, sin esperar a que se escriba sobre su stream.
+    ///
+    /// Devuelve error si ya no es posible escribir sobre la conexión del cliente.
     pub fn write_to_stream(&mut self, message: &[u8]) -> Result<(), Error> {
-        self.stream.write_all(message)?;
-        Ok(())
+        self.output.push(message.to_vec())
+    }
+
+    /// Retorna una copia de la cola de salida del cliente, con la que el worker que lo atiende encola las respuestas a sus comandos.
+    pub fn get_output(&self) -> OutputQueue {
+        self.output.clone()
+    }
+
+    /// Indica si los mensajes pendientes de enviar al cliente superan el límite de su cola de salida.
+    pub fn exceeds_output_limit(&mut self, limit: &OutputBufferLimit) -> bool {
+        self.output.exceeds(limit)
     }
 }
@@ -0,0 +1,1311 @@
+//! Estado de Redis Cluster: los nodos que lo componen y el nodo que atiende cada hash slot.
+
+use crate::services::utils::crc16::crc16;
+use crate::services::utils::random::random_index;
+use crate::services::utils::resp_type::RespType;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Cantidad de hash slots en los que se dividen las claves.
+pub const CLUSTER_SLOTS: usize = 16384;
+
+/// Tiempo durante el cual se ignoran los anuncios de un nodo eliminado con `cluster forget`, para que los demás
+/// nodos no lo vuelvan a agregar antes de eliminarlo también.
+const FORGET_TTL: Duration = Duration::from_secs(60);
+
+/// Retorna el hash slot de la clave.
+///
+/// Si la clave contiene un *hash tag* (una subcadena no vacía entre la primera `{` y la siguiente `}`), solo se
+/// utiliza esa subcadena, para que claves distintas puedan pertenecer al mismo slot.
+///
+/// # Example
+/// ```
+/// use proyecto_taller_1::domain::entities::cluster::key_hash_slot;
+///
+/// assert_eq!(key_hash_slot(b"foo"), 12182);
+/// assert_eq!(key_hash_slot(b"{user1000}.following"), key_hash_slot(b"{user1000}.followers"));
+/// assert_eq!(key_hash_slot(b"{}foo"), key_hash_slot(b"{}foo"));
+/// assert_ne!(key_hash_slot(b"{}foo"), key_hash_slot(b"foo"));
+/// ```
+pub fn key_hash_slot(key: &[u8]) -> u16 {
+    let hashed = match key.iter().position(|byte| *byte == b'{') {
+        Some(start) => match key[start + 1..].iter().position(|byte| *byte == b'}') {
+            Some(len) if len > 0 => &key[start + 1..start + 1 + len],
+            _ => key,
+        },
+        None => key,
+    };
+    crc16(hashed) % CLUSTER_SLOTS as u16
+}
+
+/// Estado de un nodo, según lo que observa el nodo local.
+/// * Handshake: todavía no respondió al primer mensaje, por lo que se desconoce su identificador real.
+/// * Online: responde a los mensajes del bus.
+/// * PFail: no responde hace más de `cluster-node-timeout` (falla posible).
+/// * Fail: la mayoría de los nodos que atienden slots coinciden en que no responde.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeStatus {
+    Handshake,
+    Online,
+    PFail,
+    Fail,
+}
+
+impl NodeStatus {
+    fn is_failing(&self) -> bool {
+        matches!(self, NodeStatus::PFail | NodeStatus::Fail)
+    }
+}
+
+/// Nodo del cluster.
+///
+/// `config_epoch` es la versión de la asignación de slots del nodo: ante dos nodos que reclaman un mismo slot, lo
+/// atiende el de mayor versión. `fail_reports` son los nodos que informaron que el nodo no les responde.
+#[derive(Debug, Clone)]
+pub struct ClusterNode {
+    id: String,
+    ip: String,
+    port: u16,
+    bus_port: u16,
+    config_epoch: u64,
+    status: NodeStatus,
+    ping_sent: Option<SystemTime>,
+    pong_received: Option<SystemTime>,
+    fail_reports: HashSet<String>,
+}
+
+impl ClusterNode {
+    fn new(id: String, ip: String, port: u16, bus_port: u16, status: NodeStatus) -> Self {
+        ClusterNode {
+            id,
+            ip,
+            port,
+            bus_port,
+            config_epoch: 0,
+            status,
+            ping_sent: None,
+            pong_received: None,
+            fail_reports: HashSet::new(),
+        }
+    }
+
+    /// Retorna la dirección en la que el nodo recibe clientes.
+    pub fn get_address(&self) -> String {
+        format!("{}:{}", self.ip, self.port)
+    }
+
+    /// Retorna la dirección en la que el nodo recibe los mensajes del bus del cluster.
+    pub fn get_bus_address(&self) -> String {
+        format!("{}:{}", self.ip, self.bus_port)
+    }
+}
+
+/// Cambios de estado de un slot con `cluster setslot`, utilizados para moverlo de un nodo a otro.
+/// * Importing: el nodo local recibe las claves del slot desde el nodo indicado.
+/// * Migrating: el nodo local envía las claves del slot al nodo indicado.
+/// * Stable: termina la importación o migración del slot.
+/// * Node: asigna el slot al nodo indicado.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlotAction {
+    Importing(String),
+    Migrating(String),
+    Stable,
+    Node(String),
+}
+
+/// Cómo se atienden los comandos sobre las claves de un slot (ver `Cluster::route`).
+/// * Local: el nodo local atiende el slot.
+/// * Migrating: el nodo local atiende el slot, pero sus claves se están moviendo al nodo de la dirección indicada;
+///   las claves que ya no existen localmente se redirigen con `ASK`.
+/// * Importing: el slot se está moviendo al nodo local desde el nodo de la dirección indicada, que todavía lo atiende;
+///   solo se atienden los comandos precedidos por `ASKING`.
+/// * Moved: el slot lo atiende el nodo de la dirección indicada.
+/// * Unassigned: ningún nodo atiende el slot.
+/// * Down: el cluster no atiende comandos, ya que algún slot no está asignado o su nodo falló.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlotRoute {
+    Local,
+    Migrating(String),
+    Importing(String),
+    Moved(String),
+    Unassigned,
+    Down,
+}
+
+/// Subcomandos de `cluster` que consultan o modifican el estado del cluster (ver `Cluster::execute`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClusterRequest {
+    Info,
+    MyId,
+    Nodes,
+    Slots,
+    Shards,
+    AddSlots(Vec<u16>),
+    DelSlots(Vec<u16>),
+    SetSlot(u16, SlotAction),
+    Meet(String, u16, u16),
+    Forget(String),
+}
+
+/// Tipo de un mensaje del bus del cluster.
+/// * Meet: pide al nodo que lo recibe que agregue al emisor al cluster.
+/// * Ping: mensaje periódico que informa el estado del emisor y de los nodos que conoce.
+/// * Pong: respuesta a `Meet` y `Ping`, con la misma información.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClusterMessageType {
+    Meet,
+    Ping,
+    Pong,
+}
+
+/// Información que un nodo envía sobre otro nodo que conoce (*gossip*).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GossipEntry {
+    pub id: String,
+    pub ip: String,
+    pub port: u16,
+    pub bus_port: u16,
+    pub failing: bool,
+}
+
+/// Mensaje del bus del cluster.
+///
+/// Informa el identificador, la dirección, la versión de la configuración y los slots del emisor, la mayor versión
+/// que conoce (`current_epoch`) y los demás nodos que conoce, indicando si le responden.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusterMessage {
+    pub kind: ClusterMessageType,
+    pub id: String,
+    pub ip: String,
+    pub port: u16,
+    pub bus_port: u16,
+    pub config_epoch: u64,
+    pub current_epoch: u64,
+    pub slots: Vec<(u16, u16)>,
+    pub gossip: Vec<GossipEntry>,
+}
+
+impl ClusterMessage {
+    /// Codifica el mensaje como un array de bulk strings, para enviarlo por el bus con el protocolo RESP.
+    ///
+    /// Los elementos son el tipo (`meet`, `ping` o `pong`), el identificador, la IP, el puerto, el puerto del bus,
+    /// la versión de la configuración y la mayor versión conocida del emisor, sus rangos de slots separados por
+    /// espacios (`inicio-fin`) y un elemento por cada nodo conocido con la forma `<id> <ip> <puerto> <puerto del bus>
+    /// <ok|fail>`.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::cluster::{ClusterMessage, ClusterMessageType, GossipEntry};
+    ///
+    /// let message = ClusterMessage {
+    ///     kind: ClusterMessageType::Ping,
+    ///     id: "a".repeat(40),
+    ///     ip: "127.0.0.1".to_string(),
+    ///     port: 7000,
+    ///     bus_port: 17000,
+    ///     config_epoch: 1,
+    ///     current_epoch: 2,
+    ///     slots: vec![(0, 100), (200, 200)],
+    ///     gossip: vec![GossipEntry {
+    ///         id: "b".repeat(40),
+    ///         ip: "127.0.0.1".to_string(),
+    ///         port: 7001,
+    ///         bus_port: 17001,
+    ///         failing: true,
+    ///     }],
+    /// };
+    /// assert_eq!(ClusterMessage::from_resp(&message.to_resp()).unwrap(), message);
+    /// ```
+    pub fn to_resp(&self) -> RespType {
+        let kind = match self.kind {
+            ClusterMessageType::Meet => "meet",
+            ClusterMessageType::Ping => "ping",
+            ClusterMessageType::Pong => "pong",
+        };
+        let slots = self
+            .slots
+            .iter()
+            .map(|(start, end)| format!("{}-{}", start, end))
+            .collect::<Vec<String>>()
+            .join(" ");
+        let mut fields = vec![
+            kind.to_string(),
+            self.id.clone(),
+            self.ip.clone(),
+            self.port.to_string(),
+            self.bus_port.to_string(),
+            self.config_epoch.to_string(),
+            self.current_epoch.to_string(),
+            slots,
+        ];
+        fields.extend(self.gossip.iter().map(|entry| {
+            format!(
+                "{} {} {} {} {}",
+                entry.id,
+                entry.ip,
+                entry.port,
+                entry.bus_port,
+                if entry.failing { "fail" } else { "ok" }
+            )
+        }));
+        RespType::RArray(
+            fields
+                .into_iter()
+                .map(|field| RespType::RBulkString(field.into()))
+                .collect(),
+        )
+    }
+
+    /// Interpreta un mensaje codificado con `to_resp`.
+    pub fn from_resp(message: &RespType) -> Result<Self, String> {
+        let fields: Vec<String> = match message {
+            RespType::RArray(fields) if fields.len() >= 8 => fields
+                .iter()
+                .map(|field| field.as_str().map(String::from))
+                .collect::<Option<Vec<String>>>()
+                .ok_or("invalid cluster bus field")?,
+            _ => return Err(String::from("invalid cluster bus message")),
+        };
+        let kind = match fields[0].as_str() {
+            "meet" => ClusterMessageType::Meet,
+            "ping" => ClusterMessageType::Ping,
+            "pong" => ClusterMessageType::Pong,
+            kind => return Err(format!("unknown cluster bus message '{}'", kind)),
+        };
+        let slots = fields[7]
+            .split_whitespace()
+            .map(parse_slot_range)
+            .collect::<Option<Vec<(u16, u16)>>>()
+            .ok_or("invalid slot range")?;
+        let gossip = fields[8..]
+            .iter()
+            .map(|entry| {
+                let parts: Vec<&str> = entry.split(' ').collect();
+                match parts.as_slice() {
+                    [id, ip, port, bus_port, status] => Some(GossipEntry {
+                        id: id.to_string(),
+                        ip: ip.to_string(),
+                        port: port.parse().ok()?,
+                        bus_port: bus_port.parse().ok()?,
+                        failing: *status == "fail",
+                    }),
+                    _ => None,
+                }
+            })
+            .collect::<Option<Vec<GossipEntry>>>()
+            .ok_or("invalid gossip entry")?;
+        Ok(ClusterMessage {
+            kind,
+            id: fields[1].clone(),
+            ip: fields[2].clone(),
+            port: fields[3].parse().map_err(|_| "invalid port")?,
+            bus_port: fields[4].parse().map_err(|_| "invalid bus port")?,
+            config_epoch: fields[5].parse().map_err(|_| "invalid config epoch")?,
+            current_epoch: fields[6].parse().map_err(|_| "invalid current epoch")?,
+            slots,
+            gossip,
+        })
+    }
+}
+
+/// Estado del cluster que conoce el nodo local.
+///
+/// Se compone por el identificador del nodo local (`myself`), los nodos conocidos, el nodo que atiende cada slot, los
+/// slots que se están importando o migrando, la mayor versión de configuración conocida (`current_epoch`) y el
+/// tiempo sin respuesta a partir del cual un nodo se considera caído (`node_timeout`).
+/// Si se indica `config_file`, la configuración se guarda en ese archivo cada vez que cambia, con el mismo formato
+/// que `cluster nodes`, para conservar el identificador y los slots del nodo al reiniciarlo.
+#[derive(Debug)]
+pub struct Cluster {
+    myself: String,
+    nodes: BTreeMap<String, ClusterNode>,
+    slots: Vec<Option<String>>,
+    migrating: BTreeMap<u16, String>,
+    importing: BTreeMap<u16, String>,
+    current_epoch: u64,
+    node_timeout: Duration,
+    forgotten: HashMap<String, SystemTime>,
+    config_file: Option<String>,
+    saved_config: String,
+    messages_sent: usize,
+    messages_received: usize,
+}
+
+impl Cluster {
+    /// Crea un cluster con un único nodo, el local, con un identificador aleatorio y sin slots.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::cluster::{Cluster, SlotRoute};
+    /// use std::time::Duration;
+    ///
+    /// let cluster = Cluster::new("127.0.0.1".to_string(), 7000, 17000, Duration::from_secs(15));
+    /// assert_eq!(cluster.get_myself_id().len(), 40);
+    /// assert_eq!(cluster.route(0), SlotRoute::Down);
+    /// ```
+    pub fn new(ip: String, port: u16, bus_port: u16, node_timeout: Duration) -> Self {
+        let myself = generate_node_id();
+        let mut nodes = BTreeMap::new();
+        nodes.insert(
+            myself.clone(),
+            ClusterNode::new(myself.clone(), ip, port, bus_port, NodeStatus::Online),
+        );
+        Cluster {
+            myself,
+            nodes,
+            slots: vec![None; CLUSTER_SLOTS],
+            migrating: BTreeMap::new(),
+            importing: BTreeMap::new(),
+            current_epoch: 0,
+            node_timeout,
+            forgotten: HashMap::new(),
+            config_file: None,
+            saved_config: String::new(),
+            messages_sent: 0,
+            messages_received: 0,
+        }
+    }
+
+    /// Carga la configuración del archivo `path`, o crea un cluster nuevo si el archivo no existe, y la guarda en ese
+    /// archivo cada vez que cambia.
+    ///
+    /// La dirección del nodo local se toma de los parámetros, ya que pudo cambiar desde que se guardó el archivo.
+    /// Devuelve error si el archivo no puede leerse o no tiene el formato de `cluster nodes`.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::cluster::{Cluster, ClusterRequest};
+    /// use std::time::Duration;
+    ///
+    /// let mut cluster = Cluster::open("dummy_nodes_open.conf", "127.0.0.1".to_string(), 7000, 17000, Duration::from_secs(15)).unwrap();
+    /// cluster.execute(ClusterRequest::AddSlots(vec![1, 2, 3])).unwrap();
+    /// let id = cluster.get_myself_id().to_string();
+    ///
+    /// let cluster = Cluster::open("dummy_nodes_open.conf", "127.0.0.1".to_string(), 7000, 17000, Duration::from_secs(15)).unwrap();
+    /// assert_eq!(cluster.get_myself_id(), id);
+    /// assert!(cluster.get_nodes().contains("1-3"));
+    /// # let _ = std::fs::remove_file("dummy_nodes_open.conf");
+    /// ```
+    pub fn open(
+        path: &str,
+        ip: String,
+        port: u16,
+        bus_port: u16,
+        node_timeout: Duration,
+    ) -> io::Result<Self> {
+        let mut cluster = match fs::read_to_string(path) {
+            Ok(content) => Cluster::parse_config(&content, ip, port, bus_port, node_timeout)
+                .map_err(|e| {
+                    io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Invalid cluster config file '{}': {}", path, e),
+                    )
+                })?,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Cluster::new(ip, port, bus_port, node_timeout)
+            }
+            Err(e) => return Err(e),
+        };
+        cluster.config_file = Some(path.to_string());
+        cluster.save_config();
+        Ok(cluster)
+    }
+
+    /// Interpreta una configuración guardada con el formato de `cluster nodes`, seguida por la línea
+    /// `vars currentEpoch <epoch> lastVoteEpoch 0`.
+    fn parse_config(
+        content: &str,
+        ip: String,
+        port: u16,
+        bus_port: u16,
+        node_timeout: Duration,
+    ) -> Result<Self, String> {
+        let mut cluster = Cluster::new(ip.clone(), port, bus_port, node_timeout);
+        cluster.nodes.clear();
+        let mut myself = None;
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields[0] == "vars" {
+                if let Some(pos) = fields.iter().position(|field| *field == "currentEpoch") {
+                    cluster.current_epoch = fields
+                        .get(pos + 1)
+                        .and_then(|epoch| epoch.parse().ok())
+                        .ok_or("invalid currentEpoch")?;
+                }
+                continue;
+            }
+            if fields.len() < 8 {
+                return Err(format!("invalid node line '{}'", line));
+            }
+            let (node_ip, node_port, node_bus_port) =
+                parse_node_address(fields[1]).ok_or("invalid node address")?;
+            let flags: Vec<&str> = fields[2].split(',').collect();
+            if flags.contains(&"handshake") {
+                continue;
+            }
+            let mut node = ClusterNode::new(
+                fields[0].to_string(),
+                node_ip,
+                node_port,
+                node_bus_port,
+                if flags.contains(&"fail") {
+                    NodeStatus::Fail
+                } else {
+                    NodeStatus::Online
+                },
+            );
+            node.config_epoch = fields[6].parse().map_err(|_| "invalid config epoch")?;
+            if flags.contains(&"myself") {
+                node.ip = ip.clone();
+                node.port = port;
+                node.bus_port = bus_port;
+                node.status = NodeStatus::Online;
+                myself = Some(node.id.clone());
+            }
+            for range in fields[8..].iter().filter(|range| !range.starts_with('[')) {
+                let (start, end) = parse_slot_range(range).ok_or("invalid slot range")?;
+                for slot in start..=end {
+                    cluster.slots[slot as usize] = Some(node.id.clone());
+                }
+            }
+            cluster.nodes.insert(node.id.clone(), node);
+        }
+        cluster.myself = myself.ok_or("missing myself node")?;
+        Ok(cluster)
+    }
+
+    /// Guarda la configuración en el archivo del cluster, si cambió desde la última vez que se guardó.
+    fn save_config(&mut self) {
+        let path = match &self.config_file {
+            Some(path) => path,
+            None => return,
+        };
+        let config = format!(
+            "{}vars currentEpoch {} lastVoteEpoch 0\n",
+            self.describe_nodes(false),
+            self.current_epoch
+        );
+        if config == self.saved_config {
+            return;
+        }
+        match fs::write(path, &config) {
+            Ok(()) => self.saved_config = config,
+            Err(e) => println!("Cluster config couldn't be saved: {}", e),
+        }
+    }
+
+    /// Retorna el identificador del nodo local.
+    pub fn get_myself_id(&self) -> &str {
+        &self.myself
+    }
+
+    fn myself(&self) -> &ClusterNode {
+        &self.nodes[&self.myself]
+    }
+
+    fn myself_mut(&mut self) -> &mut ClusterNode {
+        self.nodes
+            .get_mut(&self.myself)
+            .expect("The local node is always known")
+    }
+
+    /// Indica si el cluster atiende comandos: todos los slots están asignados y ninguno de sus nodos falló.
+    pub fn is_ok(&self) -> bool {
+        self.slots.iter().all(|owner| {
+            owner
+                .as_ref()
+                .and_then(|owner| self.nodes.get(owner))
+                .is_some_and(|node| node.status != NodeStatus::Fail)
+        })
+    }
+
+    /// Retorna cómo se atienden los comandos sobre las claves del slot.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::cluster::{Cluster, ClusterRequest, SlotRoute, CLUSTER_SLOTS};
+    /// use std::time::Duration;
+    ///
+    /// let mut cluster = Cluster::new("127.0.0.1".to_string(), 7000, 17000, Duration::from_secs(15));
+    /// cluster.execute(ClusterRequest::AddSlots((0..CLUSTER_SLOTS as u16).collect())).unwrap();
+    /// assert_eq!(cluster.route(100), SlotRoute::Local);
+    /// ```
+    pub fn route(&self, slot: u16) -> SlotRoute {
+        if !self.is_ok() {
+            return SlotRoute::Down;
+        }
+        let owner = match &self.slots[slot as usize] {
+            Some(owner) => &self.nodes[owner],
+            None => return SlotRoute::Unassigned,
+        };
+        if owner.id == self.myself {
+            return match self.migrating.get(&slot).and_then(|id| self.nodes.get(id)) {
+                Some(target) => SlotRoute::Migrating(target.get_address()),
+                None => SlotRoute::Local,
+            };
+        }
+        if self.importing.contains_key(&slot) {
+            SlotRoute::Importing(owner.get_address())
+        } else {
+            SlotRoute::Moved(owner.get_address())
+        }
+    }
+
+    /// Ejecuta un subcomando de `cluster` y devuelve su respuesta, o el mensaje de error si no puede ejecutarse.
+    pub fn execute(&mut self, request: ClusterRequest) -> Result<RespType, String> {
+        let reply = match request {
+            ClusterRequest::Info => RespType::RVerbatimString(String::from("txt"), self.get_info()),
+            ClusterRequest::MyId => RespType::RBulkString(self.myself.clone().into()),
+            ClusterRequest::Nodes => {
+                RespType::RVerbatimString(String::from("txt"), self.get_nodes())
+            }
+            ClusterRequest::Slots => self.get_slots(),
+            ClusterRequest::Shards => self.get_shards(),
+            ClusterRequest::AddSlots(slots) => {
+                self.add_slots(&slots)?;
+                ok()
+            }
+            ClusterRequest::DelSlots(slots) => {
+                self.del_slots(&slots)?;
+                ok()
+            }
+            ClusterRequest::SetSlot(slot, action) => {
+                self.set_slot(slot, action)?;
+                ok()
+            }
+            ClusterRequest::Meet(ip, port, bus_port) => {
+                self.meet(ip, port, bus_port);
+                ok()
+            }
+            ClusterRequest::Forget(id) => {
+                self.forget(&id)?;
+                ok()
+            }
+        };
+        self.save_config();
+        Ok(reply)
+    }
+
+    /// Asigna los slots al nodo local. Devuelve error si alguno ya está asignado o se repite.
+    fn add_slots(&mut self, slots: &[u16]) -> Result<(), String> {
+        check_unique_slots(slots)?;
+        if let Some(slot) = slots
+            .iter()
+            .find(|slot| self.slots[**slot as usize].is_some())
+        {
+            return Err(format!("ERR Slot {} is already busy", slot));
+        }
+        for slot in slots {
+            self.slots[*slot as usize] = Some(self.myself.clone());
+            self.importing.remove(slot);
+        }
+        Ok(())
+    }
+
+    /// Deja sin asignar los slots. Devuelve error si alguno no está asignado o se repite.
+    fn del_slots(&mut self, slots: &[u16]) -> Result<(), String> {
+        check_unique_slots(slots)?;
+        if let Some(slot) = slots
+            .iter()
+            .find(|slot| self.slots[**slot as usize].is_none())
+        {
+            return Err(format!("ERR Slot {} is already unassigned", slot));
+        }
+        for slot in slots {
+            self.slots[*slot as usize] = None;
+            self.migrating.remove(slot);
+            self.importing.remove(slot);
+        }
+        Ok(())
+    }
+
+    /// Cambia el estado de un slot (ver `SlotAction`).
+    ///
+    /// Al asignarse al nodo local un slot que estaba importando, el nodo aumenta la versión de su configuración para
+    /// que los demás nodos reemplacen al dueño anterior del slot.
+    fn set_slot(&mut self, slot: u16, action: SlotAction) -> Result<(), String> {
+        let owner = self.slots[slot as usize].clone();
+        match action {
+            SlotAction::Importing(id) => {
+                self.check_known_node(&id)?;
+                if owner.as_deref() == Some(self.myself.as_str()) {
+                    return Err(format!("ERR I'm already the owner of hash slot {}", slot));
+                }
+                self.importing.insert(slot, id);
+            }
+            SlotAction::Migrating(id) => {
+                self.check_known_node(&id)?;
+                if owner.as_deref() != Some(self.myself.as_str()) {
+                    return Err(format!("ERR I'm not the owner of hash slot {}", slot));
+                }
+                self.migrating.insert(slot, id);
+            }
+            SlotAction::Stable => {
+                self.migrating.remove(&slot);
+                self.importing.remove(&slot);
+            }
+            SlotAction::Node(id) => {
+                self.check_known_node(&id)?;
+                self.migrating.remove(&slot);
+                if self.importing.remove(&slot).is_some() && id == self.myself {
+                    let max_epoch = self.nodes.values().map(|node| node.config_epoch).max();
+                    self.current_epoch = self.current_epoch.max(max_epoch.unwrap_or(0)) + 1;
+                    let epoch = self.current_epoch;
+                    self.myself_mut().config_epoch = epoch;
+                }
+                self.slots[slot as usize] = Some(id);
+            }
+        }
+        Ok(())
+    }
+
+    fn check_known_node(&self, id: &str) -> Result<(), String> {
+        match self.nodes.get(id) {
+            Some(node) if node.status != NodeStatus::Handshake => Ok(()),
+            _ => Err(format!("ERR I don't know about node {}", id)),
+        }
+    }
+
+    /// Agrega el nodo de la dirección indicada, que se une al cluster al responder el primer mensaje del bus.
+    fn meet(&mut self, ip: String, port: u16, bus_port: u16) {
+        if self
+            .nodes
+            .values()
+            .any(|node| node.ip == ip && node.port == port)
+        {
+            return;
+        }
+        let id = generate_node_id();
+        self.nodes.insert(
+            id.clone(),
+            ClusterNode::new(id, ip, port, bus_port, NodeStatus::Handshake),
+        );
+    }
+
+    /// Elimina un nodo del cluster y deja sin asignar sus slots.
+    fn forget(&mut self, id: &str) -> Result<(), String> {
+        if id == self.myself {
+            return Err(String::from(
+                "ERR I tried hard but I can't forget myself...",
+            ));
+        }
+        if self.nodes.remove(id).is_none() {
+            return Err(format!("ERR Unknown node {}", id));
+        }
+        for owner in self.slots.iter_mut() {
+            if owner.as_deref() == Some(id) {
+                *owner = None;
+            }
+        }
+        self.migrating.retain(|_, target| target != id);
+        self.importing.retain(|_, source| source != id);
+        self.forgotten.insert(id.to_string(), SystemTime::now());
+        Ok(())
+    }
+
+    /// Retorna los mensajes que el nodo local envía periódicamente a los demás nodos, junto con la dirección del bus
+    /// de cada uno: `meet` a los nodos en handshake y `ping` al resto.
+    ///
+    /// Antes actualiza el estado de los nodos: los que no responden hace más de `node_timeout` pasan a `PFail`
+    /// (o `Fail`, según los demás nodos) y los que no completaron el handshake en ese tiempo se eliminan.
+    pub fn get_ping_messages(&mut self) -> Vec<(String, ClusterMessage)> {
+        let now = SystemTime::now();
+        let timeout = self.node_timeout;
+        let expired = |since: Option<SystemTime>| {
+            since.is_some_and(|since| now.duration_since(since).unwrap_or_default() > timeout)
+        };
+        self.nodes
+            .retain(|_, node| !(node.status == NodeStatus::Handshake && expired(node.ping_sent)));
+        self.forgotten
+            .retain(|_, since| now.duration_since(*since).unwrap_or_default() < FORGET_TTL);
+        let myself = self.myself.clone();
+        for node in self.nodes.values_mut().filter(|node| node.id != myself) {
+            if node.status == NodeStatus::Online && expired(node.ping_sent) {
+                node.status = NodeStatus::PFail;
+            }
+            if node.ping_sent.is_none() {
+                node.ping_sent = Some(now);
+            }
+        }
+        self.check_failures();
+        self.save_config();
+        let messages: Vec<(String, ClusterMessage)> = self
+            .nodes
+            .values()
+            .filter(|node| node.id != myself)
+            .map(|node| {
+                let kind = if node.status == NodeStatus::Handshake {
+                    ClusterMessageType::Meet
+                } else {
+                    ClusterMessageType::Ping
+                };
+                (node.get_bus_address(), self.build_message(kind))
+            })
+            .collect();
+        self.messages_sent += messages.len();
+        messages
+    }
+
+    /// Procesa un mensaje del bus y devuelve la respuesta que se le envía al emisor, si corresponde.
+    ///
+    /// Un nodo desconocido solo se agrega si envía `meet`, o si responde con `pong` al `meet` que se le envió a su
+    /// dirección. Los mensajes de un nodo conocido actualizan su dirección y su estado, los slots que atiende (si su
+    /// versión de configuración es mayor que la del dueño actual de cada slot) y los reportes de falla de los nodos
+    /// que informa; los nodos que informa y no se conocen se agregan en handshake.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::cluster::{Cluster, ClusterMessageType, ClusterRequest};
+    /// use std::time::Duration;
+    ///
+    /// let timeout = Duration::from_secs(15);
+    /// let mut first = Cluster::new("127.0.0.1".to_string(), 7000, 17000, timeout);
+    /// let mut second = Cluster::new("127.0.0.1".to_string(), 7001, 17001, timeout);
+    /// first.execute(ClusterRequest::Meet("127.0.0.1".to_string(), 7001, 17001)).unwrap();
+    /// second.execute(ClusterRequest::AddSlots(vec![5])).unwrap();
+    ///
+    /// let (address, meet) = first.get_ping_messages().remove(0);
+    /// assert_eq!(address, "127.0.0.1:17001");
+    /// assert_eq!(meet.kind, ClusterMessageType::Meet);
+    /// let pong = second.process_message(meet).unwrap();
+    /// assert_eq!(first.process_message(pong), None);
+    /// assert!(first.get_nodes().contains(&format!("{} 127.0.0.1:7001@17001 master - ", second.get_myself_id())));
+    /// ```
+    pub fn process_message(&mut self, message: ClusterMessage) -> Option<ClusterMessage> {
+        self.messages_received += 1;
+        if message.id == self.myself {
+            return None;
+        }
+        self.current_epoch = self
+            .current_epoch
+            .max(message.current_epoch)
+            .max(message.config_epoch);
+        if !self.nodes.contains_key(&message.id) && !self.add_sender(&message) {
+            return None;
+        }
+        // un nodo en handshake con la misma dirección es el mismo nodo, agregado nuevamente con `cluster meet`
+        self.nodes.retain(|id, node| {
+            *id == message.id
+                || node.status != NodeStatus::Handshake
+                || node.ip != message.ip
+                || node.port != message.port
+        });
+        if let Some(node) = self.nodes.get_mut(&message.id) {
+            node.ip = message.ip.clone();
+            node.port = message.port;
+            node.bus_port = message.bus_port;
+            node.config_epoch = message.config_epoch;
+            node.status = NodeStatus::Online;
+            node.fail_reports.clear();
+            if message.kind == ClusterMessageType::Pong {
+                node.ping_sent = None;
+                node.pong_received = Some(SystemTime::now());
+            }
+        }
+        self.update_slots(&message);
+        self.handle_epoch_collision(&message);
+        self.process_gossip(&message);
+        self.check_failures();
+        self.save_config();
+        match message.kind {
+            ClusterMessageType::Meet | ClusterMessageType::Ping => {
+                self.messages_sent += 1;
+                Some(self.build_message(ClusterMessageType::Pong))
+            }
+            ClusterMessageType::Pong => None,
+        }
+    }
+
+    /// Agrega al emisor desconocido de un mensaje si corresponde (ver `process_message`); devuelve si lo agregó.
+    fn add_sender(&mut self, message: &ClusterMessage) -> bool {
+        let in_handshake = self.nodes.values().any(|node| {
+            node.status == NodeStatus::Handshake
+                && node.ip == message.ip
+                && node.port == message.port
+        });
+        let accepted = match message.kind {
+            ClusterMessageType::Meet => true,
+            ClusterMessageType::Pong => in_handshake,
+            ClusterMessageType::Ping => false,
+        };
+        if accepted {
+            self.forgotten.remove(&message.id);
+            self.nodes.insert(
+                message.id.clone(),
+                ClusterNode::new(
+                    message.id.clone(),
+                    message.ip.clone(),
+                    message.port,
+                    message.bus_port,
+                    NodeStatus::Online,
+                ),
+            );
+        }
+        accepted
+    }
+
+    /// Actualiza los slots que atiende el emisor del mensaje.
+    ///
+    /// El emisor toma los slots que reclama si no están asignados o si su dueño tiene una versión de configuración
+    /// menor, y deja de atender los que ya no reclama. Los slots que importa el nodo local no cambian, ya que los
+    /// asigna `cluster setslot`.
+    fn update_slots(&mut self, message: &ClusterMessage) {
+        let mut claimed = vec![false; CLUSTER_SLOTS];
+        for (start, end) in &message.slots {
+            for slot in *start..=*end {
+                claimed[slot as usize] = true;
+            }
+        }
+        for (slot, claimed) in claimed.into_iter().enumerate() {
+            if self.importing.contains_key(&(slot as u16)) {
+                continue;
+            }
+            let owner = self.slots[slot].clone();
+            if claimed {
+                let replace = match owner.as_ref().and_then(|owner| self.nodes.get(owner)) {
+                    Some(owner) => {
+                        owner.id != message.id && owner.config_epoch < message.config_epoch
+                    }
+                    None => true,
+                };
+                if replace {
+                    self.slots[slot] = Some(message.id.clone());
+                    self.migrating.remove(&(slot as u16));
+                }
+            } else if owner.as_deref() == Some(message.id.as_str()) {
+                self.slots[slot] = None;
+            }
+        }
+    }
+
+    /// Si el emisor tiene la misma versión de configuración que el nodo local, el de menor identificador aumenta la
+    /// suya, para que ante un conflicto por un slot siempre haya un nodo con mayor versión.
+    fn handle_epoch_collision(&mut self, message: &ClusterMessage) {
+        if message.config_epoch == self.myself().config_epoch && message.id > self.myself {
+            self.current_epoch += 1;
+            let epoch = self.current_epoch;
+            self.myself_mut().config_epoch = epoch;
+        }
+    }
+
+    /// Registra lo que el emisor informa sobre los demás nodos.
+    fn process_gossip(&mut self, message: &ClusterMessage) {
+        for entry in &message.gossip {
+            if entry.id == self.myself || self.forgotten.contains_key(&entry.id) {
+                continue;
+            }
+            match self.nodes.get_mut(&entry.id) {
+                Some(node) if entry.failing => {
+                    node.fail_reports.insert(message.id.clone());
+                }
+                Some(node) => {
+                    node.fail_reports.remove(&message.id);
+                }
+                None => {
+                    let known = self
+                        .nodes
+                        .values()
+                        .any(|node| node.ip == entry.ip && node.port == entry.port);
+                    if !known && !entry.failing {
+                        self.nodes.insert(
+                            entry.id.clone(),
+                            ClusterNode::new(
+                                entry.id.clone(),
+                                entry.ip.clone(),
+                                entry.port,
+                                entry.bus_port,
+                                NodeStatus::Handshake,
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Marca como `Fail` a los nodos en `PFail` que la mayoría de los nodos que atienden slots reportan como caídos.
+    fn check_failures(&mut self) {
+        let masters: HashSet<String> = self.slots.iter().flatten().cloned().collect();
+        let needed = masters.len() / 2 + 1;
+        let myself_votes = masters.contains(&self.myself) as usize;
+        for node in self.nodes.values_mut() {
+            if node.status != NodeStatus::PFail {
+                continue;
+            }
+            let reports = node
+                .fail_reports
+                .iter()
+                .filter(|reporter| masters.contains(*reporter))
+                .count();
+            if reports + myself_votes >= needed {
+                node.status = NodeStatus::Fail;
+            }
+        }
+    }
+
+    /// Construye un mensaje del nodo local con su estado y el de los nodos que conoce.
+    fn build_message(&self, kind: ClusterMessageType) -> ClusterMessage {
+        let myself = self.myself();
+        ClusterMessage {
+            kind,
+            id: myself.id.clone(),
+            ip: myself.ip.clone(),
+            port: myself.port,
+            bus_port: myself.bus_port,
+            config_epoch: myself.config_epoch,
+            current_epoch: self.current_epoch,
+            slots: self.get_slot_ranges(&self.myself),
+            gossip: self
+                .nodes
+                .values()
+                .filter(|node| node.id != self.myself && node.status != NodeStatus::Handshake)
+                .map(|node| GossipEntry {
+                    id: node.id.clone(),
+                    ip: node.ip.clone(),
+                    port: node.port,
+                    bus_port: node.bus_port,
+                    failing: node.status.is_failing(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Retorna los rangos de slots consecutivos que atiende el nodo.
+    fn get_slot_ranges(&self, id: &str) -> Vec<(u16, u16)> {
+        let mut ranges: Vec<(u16, u16)> = Vec::new();
+        for (slot, owner) in self.slots.iter().enumerate() {
+            if owner.as_deref() != Some(id) {
+                continue;
+            }
+            let slot = slot as u16;
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == slot => *end = slot,
+                _ => ranges.push((slot, slot)),
+            }
+        }
+        ranges
+    }
+
+    /// Retorna la respuesta de `cluster info`.
+    pub fn get_info(&self) -> String {
+        let assigned: Vec<&ClusterNode> = self
+            .slots
+            .iter()
+            .flatten()
+            .filter_map(|owner| self.nodes.get(owner))
+            .collect();
+        let pfail = assigned
+            .iter()
+            .filter(|node| node.status == NodeStatus::PFail)
+            .count();
+        let fail = assigned
+            .iter()
+            .filter(|node| node.status == NodeStatus::Fail)
+            .count();
+        let size = self
+            .slots
+            .iter()
+            .flatten()
+            .collect::<HashSet<&String>>()
+            .len();
+        format!(
+            "cluster_enabled:1\r\ncluster_state:{}\r\ncluster_slots_assigned:{}\r\ncluster_slots_ok:{}\r\ncluster_slots_pfail:{}\r\ncluster_slots_fail:{}\r\ncluster_known_nodes:{}\r\ncluster_size:{}\r\ncluster_current_epoch:{}\r\ncluster_my_epoch:{}\r\ncluster_stats_messages_sent:{}\r\ncluster_stats_messages_received:{}\r\ntotal_cluster_links_buffer_limit_exceeded:0\r\n",
+            if self.is_ok() { "ok" } else { "fail" },
+            assigned.len(),
+            assigned.len() - pfail - fail,
+            pfail,
+            fail,
+            self.nodes.len(),
+            size,
+            self.current_epoch,
+            self.myself().config_epoch,
+            self.messages_sent,
+            self.messages_received,
+        )
+    }
+
+    /// Retorna la respuesta de `cluster nodes`: una línea por nodo con la forma `<id> <ip:puerto@puerto del bus>
+    /// <flags> <master> <ping enviado> <pong recibido> <versión de configuración> <estado de la conexión> <slots>`.
+    pub fn get_nodes(&self) -> String {
+        self.describe_nodes(true)
+    }
+
+    /// Describe los nodos con el formato de `cluster nodes`; si `times` es false, los tiempos de ping y pong se
+    /// informan como 0, para guardar la configuración sin que cambie en cada mensaje.
+    fn describe_nodes(&self, times: bool) -> String {
+        let millis = |time: Option<SystemTime>| match time {
+            Some(time) if times => time
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis(),
+            _ => 0,
+        };
+        let mut description = String::new();
+        for node in self.nodes.values() {
+            let mut flags = Vec::new();
+            if node.id == self.myself {
+                flags.push("myself");
+            }
+            flags.push("master");
+            match node.status {
+                NodeStatus::Handshake => flags.push("handshake"),
+                NodeStatus::PFail => flags.push("fail?"),
+                NodeStatus::Fail => flags.push("fail"),
+                NodeStatus::Online => {}
+            }
+            let connected = node.id == self.myself || node.status == NodeStatus::Online;
+            description.push_str(&format!(
+                "{} {}:{}@{} {} - {} {} {} {}",
+                node.id,
+                node.ip,
+                node.port,
+                node.bus_port,
+                flags.join(","),
+                millis(node.ping_sent),
+                millis(node.pong_received),
+                node.config_epoch,
+                if connected {
+                    "connected"
+                } else {
+                    "disconnected"
+                },
+            ));
+            for (start, end) in self.get_slot_ranges(&node.id) {
+                if start == end {
+                    description.push_str(&format!(" {}", start));
+                } else {
+                    description.push_str(&format!(" {}-{}", start, end));
+                }
+            }
+            if node.id == self.myself {
+                for (slot, target) in &self.migrating {
+                    description.push_str(&format!(" [{}->-{}]", slot, target));
+                }
+                for (slot, source) in &self.importing {
+                    description.push_str(&format!(" [{}-<-{}]", slot, source));
+                }
+            }
+            description.push('\n');
+        }
+        description
+    }
+
+    /// Retorna la respuesta de `cluster slots`: por cada rango de slots, su inicio, su fin y el nodo que lo atiende
+    /// (IP, puerto e identificador).
+    fn get_slots(&self) -> RespType {
+        let mut ranges: Vec<(u16, u16, &ClusterNode)> = self
+            .nodes
+            .values()
+            .flat_map(|node| {
+                self.get_slot_ranges(&node.id)
+                    .into_iter()
+                    .map(move |(start, end)| (start, end, node))
+            })
+            .collect();
+        ranges.sort_by_key(|(start, _, _)| *start);
+        RespType::RArray(
+            ranges
+                .into_iter()
+                .map(|(start, end, node)| {
+                    RespType::RArray(vec![
+                        RespType::RInteger(start as usize),
+                        RespType::RInteger(end as usize),
+                        RespType::RArray(vec![
+                            RespType::RBulkString(node.ip.clone().into()),
+                            RespType::RInteger(node.port as usize),
+                            RespType::RBulkString(node.id.clone().into()),
+                        ]),
+                    ])
+                })
+                .collect(),
+        )
+    }
+
+    /// Retorna la respuesta de `cluster shards`: por cada nodo, los rangos de slots que atiende y su descripción.
+    fn get_shards(&self) -> RespType {
+        let word = |text: &str| RespType::RBulkString(text.to_string().into());
+        RespType::RArray(
+            self.nodes
+                .values()
+                .filter(|node| node.status != NodeStatus::Handshake)
+                .map(|node| {
+                    let slots = self
+                        .get_slot_ranges(&node.id)
+                        .into_iter()
+                        .flat_map(|(start, end)| {
+                            vec![
+                                RespType::RInteger(start as usize),
+                                RespType::RInteger(end as usize),
+                            ]
+                        })
+                        .collect();
+                    let health = if node.status.is_failing() {
+                        "fail"
+                    } else {
+                        "online"
+                    };
+                    let description = RespType::RMap(vec![
+                        (word("id"), word(&node.id)),
+                        (word("port"), RespType::RInteger(node.port as usize)),
+                        (word("ip"), word(&node.ip)),
+                        (word("endpoint"), word(&node.ip)),
+                        (word("role"), word("master")),
+                        (word("replication-offset"), RespType::RInteger(0)),
+                        (word("health"), word(health)),
+                    ]);
+                    RespType::RMap(vec![
+                        (word("slots"), RespType::RArray(slots)),
+                        (word("nodes"), RespType::RArray(vec![description])),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+fn ok() -> RespType {
+    RespType::RSimpleString(String::from("OK"))
+}
+
+/// Devuelve error si algún slot se indica más de una vez.
+fn check_unique_slots(slots: &[u16]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    match slots.iter().find(|slot| !seen.insert(**slot)) {
+        Some(slot) => Err(format!("ERR Slot {} specified multiple times", slot)),
+        None => Ok(()),
+    }
+}
+
+/// Interpreta un rango de slots de la forma `inicio-fin`, o un único slot.
+fn parse_slot_range(range: &str) -> Option<(u16, u16)> {
+    let (start, end) = range.split_once('-').unwrap_or((range, range));
+    let (start, end) = (start.parse::<u16>().ok()?, end.parse::<u16>().ok()?);
+    if start > end || end as usize >= CLUSTER_SLOTS {
+        return None;
+    }
+    Some((start, end))
+}
+
+/// Interpreta una dirección de la forma `ip:puerto@puerto del bus`, opcionalmente seguida de `,<hostname>`.
+fn parse_node_address(address: &str) -> Option<(String, u16, u16)> {
+    let address = address.split(',').next()?;
+    let (address, bus_port) = address.split_once('@')?;
+    let (ip, port) = address.rsplit_once(':')?;
+    Some((ip.to_string(), port.parse().ok()?, bus_port.parse().ok()?))
+}
+
+/// Genera un identificador de nodo aleatorio de 40 dígitos hexadecimales, como los de Redis.
+fn generate_node_id() -> String {
+    (0..40).map(|_| format!("{:x}", random_index(16))).collect()
+}
+
+#[test]
+fn test_01_gossip_propagates_nodes_and_slot_ownership() {
+    let timeout = Duration::from_secs(15);
+    let mut nodes: Vec<Cluster> = (0..3)
+        .map(|i| Cluster::new(String::from("127.0.0.1"), 7000 + i, 17000 + i, timeout))
+        .collect();
+    nodes[0]
+        .execute(ClusterRequest::AddSlots((0..8192).collect()))
+        .unwrap();
+    nodes[1]
+        .execute(ClusterRequest::AddSlots((8192..16384).collect()))
+        .unwrap();
+    // el primer nodo conoce a los otros dos, que se conocen entre sí a través de él
+    for port in [7001, 7002] {
+        nodes[0]
+            .execute(ClusterRequest::Meet(
+                String::from("127.0.0.1"),
+                port,
+                port + 10000,
+            ))
+            .unwrap();
+    }
+    for _ in 0..3 {
+        for sender in 0..nodes.len() {
+            for (address, message) in nodes[sender].get_ping_messages() {
+                let receiver = nodes
+                    .iter()
+                    .position(|node| node.myself().get_bus_address() == address)
+                    .unwrap();
+                if let Some(reply) = nodes[receiver].process_message(message) {
+                    nodes[sender].process_message(reply);
+                }
+            }
+        }
+    }
+
+    for node in &nodes {
+        assert_eq!(node.nodes.len(), 3);
+        assert!(node.is_ok());
+        assert!(node.get_info().contains("cluster_size:2\r\n"));
+    }
+    assert_eq!(
+        nodes[2].route(0),
+        SlotRoute::Moved(String::from("127.0.0.1:7000"))
+    );
+    assert_eq!(
+        nodes[2].route(9000),
+        SlotRoute::Moved(String::from("127.0.0.1:7001"))
+    );
+    assert_eq!(nodes[1].route(9000), SlotRoute::Local);
+}
+
+#[test]
+fn test_02_setslot_moves_a_slot_between_nodes() {
+    let timeout = Duration::from_secs(15);
+    let mut source = Cluster::new(String::from("127.0.0.1"), 7000, 17000, timeout);
+    let mut target = Cluster::new(String::from("127.0.0.1"), 7001, 17001, timeout);
+    source
+        .execute(ClusterRequest::AddSlots((0..16384).collect()))
+        .unwrap();
+    source
+        .execute(ClusterRequest::Meet(String::from("127.0.0.1"), 7001, 17001))
+        .unwrap();
+    let (_, meet) = source.get_ping_messages().remove(0);
+    let pong = target.process_message(meet).unwrap();
+    source.process_message(pong);
+    let (_, ping) = source.get_ping_messages().remove(0);
+    let pong = target.process_message(ping).unwrap();
+    source.process_message(pong);
+    let source_id = source.get_myself_id().to_string();
+    let target_id = target.get_myself_id().to_string();
+
+    target
+        .execute(ClusterRequest::SetSlot(5, SlotAction::Importing(source_id)))
+        .unwrap();
+    source
+        .execute(ClusterRequest::SetSlot(
+            5,
+            SlotAction::Migrating(target_id.clone()),
+        ))
+        .unwrap();
+    assert_eq!(
+        target.route(5),
+        SlotRoute::Importing(String::from("127.0.0.1:7000"))
+    );
+    assert_eq!(
+        source.route(5),
+        SlotRoute::Migrating(String::from("127.0.0.1:7001"))
+    );
+    assert!(source
+        .execute(ClusterRequest::AddSlots(vec![5]))
+        .unwrap_err()
+        .contains("already busy"));
+
+    target
+        .execute(ClusterRequest::SetSlot(5, SlotAction::Node(target_id)))
+        .unwrap();
+    assert_eq!(target.route(5), SlotRoute::Local);
+    // el nodo de origen acepta el cambio porque el destino aumentó la versión de su configuración
+    let (_, ping) = target.get_ping_messages().remove(0);
+    let pong = source.process_message(ping).unwrap();
+    target.process_message(pong);
+    assert_eq!(
+        source.route(5),
+        SlotRoute::Moved(String::from("127.0.0.1:7001"))
+    );
+    assert_eq!(source.route(6), SlotRoute::Local);
+}
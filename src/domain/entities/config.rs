@@ -132,7 +132,7 @@ impl Config {
 
 /// Lee las líneas del archivo ubicado en `path` y retorna un `HashMap`.
 ///
-/// Lee cada línea en `path` de la forma `clave valor` y las guarda en un HashMap.
+/// Lee cada línea en `path` de la forma `clave valor` y las guarda en un HashMap. El valor puede estar compuesto por varias palabras.
 /// Retorna error si el archivo no existe o si falla la obtención de las líneas del archivo.
 /// # Example
 /// ```
@@ -140,11 +140,12 @@ impl Config {
 /// use std::io::Write;
 ///
 /// let mut file = std::fs::File::create("config_lines.txt").unwrap();
-/// file.write_all(format!("key value\nverbose 1\n").as_bytes()).unwrap();
+/// file.write_all(format!("key value\nverbose 1\nbind 127.0.0.1 -::1\n").as_bytes()).unwrap();
 /// let hashmap = lines_from_file("config_lines.txt").unwrap();
 ///
 /// assert_eq!(hashmap.get("key").unwrap(), &"value".to_string());
 /// assert_eq!(hashmap.get("verbose").unwrap(), &"1".to_string());
+/// assert_eq!(hashmap.get("bind").unwrap(), &"127.0.0.1 -::1".to_string());
 /// # std::fs::remove_file("config_lines.txt").unwrap();
 /// ```
 pub fn lines_from_file(path: &str) -> Result<HashMap<String, String>, Error> {
@@ -153,10 +154,13 @@ pub fn lines_from_file(path: &str) -> Result<HashMap<String, String>, Error> {
     let lines: Vec<String> = f.lines().collect::<Result<_, _>>()?;
     let mut map = HashMap::new();
     for line in lines {
-        let vec_aux: Vec<String> = line.split_whitespace().map(|s| s.to_string()).collect();
-        if vec_aux.len() == 2 {
-            map.entry(vec_aux[0].clone())
-                .or_insert_with(|| vec_aux[1].clone());
+        // el valor es el resto de la línea, ya que algunos atributos (como `bind`) admiten varios valores
+        let mut words = line.split_whitespace();
+        if let Some(key) = words.next() {
+            let value = words.collect::<Vec<&str>>().join(" ");
+            if !value.is_empty() {
+                map.entry(key.to_string()).or_insert(value);
+            }
         }
     }
     Ok(map)
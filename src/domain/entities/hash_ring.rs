@@ -0,0 +1,101 @@
+//! Anillo de hashing consistente que reparte las claves entre los servidores del proxy.
+
+/// Cantidad de puntos que ocupa cada servidor en el anillo.
+const VIRTUAL_NODES: usize = 160;
+
+/// Anillo de hashing consistente.
+///
+/// Cada servidor ocupa varios puntos del anillo (nodos virtuales), calculados a partir de su dirección, para que las
+/// claves se repartan de forma pareja. Una clave pertenece al primer servidor cuyo punto sigue al hash de la clave;
+/// así, agregar o quitar un servidor solo cambia de servidor a las claves que le correspondían a él.
+#[derive(Debug, Clone)]
+pub struct HashRing {
+    points: Vec<(u64, usize)>,
+}
+
+impl HashRing {
+    /// Crea el anillo con los servidores indicados, identificados por su posición en `nodes`.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::hash_ring::HashRing;
+    ///
+    /// let ring = HashRing::new(&["127.0.0.1:7001".to_string(), "127.0.0.1:7002".to_string()]);
+    /// let node = ring.get_node(b"mascota").unwrap();
+    /// assert!(node < 2);
+    /// assert_eq!(ring.get_node(b"mascota"), Some(node));
+    /// ```
+    pub fn new(nodes: &[String]) -> Self {
+        let mut points = Vec::with_capacity(nodes.len() * VIRTUAL_NODES);
+        for (index, node) in nodes.iter().enumerate() {
+            for replica in 0..VIRTUAL_NODES {
+                points.push((hash(format!("{}-{}", node, replica).as_bytes()), index));
+            }
+        }
+        points.sort_unstable();
+        HashRing { points }
+    }
+
+    /// Retorna el servidor al que le corresponde la clave.
+    ///
+    /// Devuelve `None` si el anillo no tiene servidores.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::hash_ring::HashRing;
+    ///
+    /// let ring = HashRing::new(&["127.0.0.1:7001".to_string()]);
+    /// assert_eq!(ring.get_node(b"mascota"), Some(0));
+    /// assert_eq!(HashRing::new(&[]).get_node(b"mascota"), None);
+    /// ```
+    pub fn get_node(&self, key: &[u8]) -> Option<usize> {
+        let start = self.points.partition_point(|(point, _)| *point < hash(key));
+        self.points
+            .get(start)
+            .or_else(|| self.points.first())
+            .map(|(_, node)| *node)
+    }
+}
+
+/// Hash FNV-1a de 64 bits, con una mezcla final de los bits para repartir mejor las claves parecidas.
+fn hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+#[test]
+fn test_01_keys_are_spread_and_only_move_from_removed_nodes() {
+    let nodes: Vec<String> = (7001..7004)
+        .map(|port| format!("127.0.0.1:{}", port))
+        .collect();
+    let ring = HashRing::new(&nodes);
+    let keys: Vec<String> = (0..3000).map(|i| format!("clave:{}", i)).collect();
+    let owners: Vec<usize> = keys
+        .iter()
+        .map(|key| ring.get_node(key.as_bytes()).unwrap())
+        .collect();
+    for node in 0..nodes.len() {
+        let count = owners.iter().filter(|owner| **owner == node).count();
+        assert!(count > 600, "node {} owns only {} keys", node, count);
+    }
+
+    // sin el segundo servidor, el tercero pasa a ocupar la posición 1
+    let remaining = vec![nodes[0].clone(), nodes[2].clone()];
+    let smaller = HashRing::new(&remaining);
+    for (key, owner) in keys.iter().zip(owners) {
+        let moved = &remaining[smaller.get_node(key.as_bytes()).unwrap()];
+        if owner == 1 {
+            assert_ne!(moved, &nodes[1]);
+        } else {
+            assert_eq!(moved, &nodes[owner]);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Representa los valores almacenados en la base de datos
 
 use crate::domain::entities::key_value_item_serialized::KeyValueItemSerialized;
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 use std::fmt;
 use std::num::ParseIntError;
 use std::str::FromStr;
@@ -12,17 +12,20 @@ use std::time::SystemTime;
 /// Tipos de value almacenados
 ///
 /// Los posibles valores son: List, Set, String.
-/// Dentro de las listas o los sets, los valores son de tipo String.
+/// Los valores son binary safe: tanto los strings como los elementos de las listas y los sets se almacenan
+/// como vectores de bytes.
+/// Las listas se almacenan como `VecDeque` para que las inserciones y eliminaciones
+/// en ambos extremos sean O(1).
 pub enum ValueType {
-    ListType(Vec<String>),
-    SetType(HashSet<String>),
-    StringType(String),
+    ListType(VecDeque<Vec<u8>>),
+    SetType(HashSet<Vec<u8>>),
+    StringType(Vec<u8>),
 }
 
 /// Formato display para los valores almacenados.
 ///
 /// La lista de valores se imprimen uno tras otro
-/// separados por comas.
+/// separados por comas. Los bytes que no son UTF-8 válido se reemplazan por `U+FFFD`.
 /// En el caso de string, solo se imprimirá un valor. Para Set no existe orden
 /// y en el caso de las listas se imprime primero el elemento del head
 /// hasta ir avanzando al final.
@@ -34,10 +37,10 @@ pub enum ValueType {
 ///
 ///
 /// let mut un_list = Vec::new();
-///  un_list.push("primer_elemento".to_string());
-///  un_list.push("segundo_elemento".to_string());
+///  un_list.push(b"primer_elemento".to_vec());
+///  un_list.push(b"segundo_elemento".to_vec());
 ///
-///  let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(un_list)).build();
+///  let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(un_list.into())).build();
 ///  assert_eq!(kv_item.get_value().to_string(), "primer_elemento,segundo_elemento");
 ///
 /// ```
@@ -47,7 +50,7 @@ impl fmt::Display for ValueType {
             ValueType::ListType(value) => {
                 let mut printable_v = "".to_owned();
                 for v in value {
-                    printable_v.push_str(v);
+                    printable_v.push_str(&String::from_utf8_lossy(v));
                     printable_v.push(',')
                 }
                 printable_v.pop();
@@ -56,13 +59,13 @@ impl fmt::Display for ValueType {
             ValueType::SetType(value) => {
                 let mut printable_v = "".to_owned();
                 for v in value {
-                    printable_v.push_str(v);
+                    printable_v.push_str(&String::from_utf8_lossy(v));
                     printable_v.push(',')
                 }
                 printable_v.pop();
                 printable_v
             }
-            ValueType::StringType(value) => value.to_string(),
+            ValueType::StringType(value) => String::from_utf8_lossy(value).to_string(),
         };
         write!(f, "{}", printable)
     }
@@ -89,10 +92,10 @@ pub enum KeyAccessTime {
 ///
 ///
 /// let mut un_list = Vec::new();
-///  un_list.push("primer_elemento".to_string());
-///  un_list.push("segundo_elemento".to_string());
+///  un_list.push(b"primer_elemento".to_vec());
+///  un_list.push(b"segundo_elemento".to_vec());
 ///
-///  let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(un_list)).with_timeout(123210).build();
+///  let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(un_list.into())).with_timeout(123210).build();
 ///  assert_eq!(kv_item.get_value().to_string(), "primer_elemento,segundo_elemento");
 ///  assert_eq!(kv_item.get_timeout().to_string(), "123210".to_string());
 /// ```
@@ -100,7 +103,7 @@ impl fmt::Display for KeyAccessTime {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let printable = match self {
             KeyAccessTime::Volatile(value) => value.to_string(),
-            KeyAccessTime::Persistent {} => "".to_string(),
+            KeyAccessTime::Persistent => "".to_string(),
         };
         write!(f, "{}", printable)
     }
@@ -169,7 +172,7 @@ impl ValueTimeItemBuilder {
     /// ```
     /// use proyecto_taller_1::domain::entities::key_value_item::{ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
-    /// let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(vec!["elemento".to_string()])).build();
+    /// let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(vec![b"elemento".to_vec()].into())).build();
     /// ```
     pub fn new(value: ValueType) -> ValueTimeItemBuilder {
         ValueTimeItemBuilder {
@@ -191,7 +194,7 @@ impl ValueTimeItemBuilder {
     /// ```
     /// use proyecto_taller_1::domain::entities::key_value_item::{ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
-    /// ValueTimeItemBuilder::new(ValueType::ListType(vec!["element1".to_string()])).with_timeout(1623433677).build();
+    /// ValueTimeItemBuilder::new(ValueType::ListType(vec![b"element1".to_vec()].into())).with_timeout(1623433677).build();
     /// ```
     pub fn with_timeout(mut self, timeout: u64) -> ValueTimeItemBuilder {
         self.timeout = KeyAccessTime::Volatile(timeout);
@@ -206,7 +209,7 @@ impl ValueTimeItemBuilder {
     /// ```
     /// use proyecto_taller_1::domain::entities::key_value_item::{ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
-    /// ValueTimeItemBuilder::new(ValueType::ListType(vec!["element".to_string()])).with_last_access_time(1623433677).build();
+    /// ValueTimeItemBuilder::new(ValueType::ListType(vec![b"element".to_vec()].into())).with_last_access_time(1623433677).build();
     /// ```
     pub fn with_last_access_time(mut self, lat: u64) -> ValueTimeItemBuilder {
         self.last_access_time = lat;
@@ -220,7 +223,7 @@ impl ValueTimeItemBuilder {
     /// ```
     /// use proyecto_taller_1::domain::entities::key_value_item::{ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
-    /// let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(vec!["elemento".to_string()]))
+    /// let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(vec![b"elemento".to_vec()].into()))
     ///     .with_key_access_time(KeyAccessTime::Volatile(1623433677))
     ///     .build();
     /// ```
@@ -244,7 +247,7 @@ impl ValueTimeItemBuilder {
 /// Representa un valor que puede ser almacenado.
 /// Se compone por un tipo de valor que puede ser String, Set o List, por un timeout y un last access time.
 impl ValueTimeItem {
-    pub fn _from_file(kvis: KeyValueItemSerialized) -> (String, ValueTimeItem) {
+    pub fn _from_file(kvis: KeyValueItemSerialized) -> (Vec<u8>, ValueTimeItem) {
         kvis.transform_to_item()
     }
 
@@ -299,6 +302,13 @@ impl ValueTimeItem {
         &self.value
     }
 
+    /// Devuelve una referencia mutable al valor almacenado.
+    ///
+    /// Permite modificar el valor sin necesidad de copiarlo.
+    pub fn get_mut_value(&mut self) -> &mut ValueType {
+        &mut self.value
+    }
+
     /// Devuelve una copia del valor almacenado.
     pub fn get_copy_of_value(&self) -> ValueType {
         self.value.clone()
@@ -336,16 +346,17 @@ impl ValueTimeItem {
     ///
     /// Si el valor es de tipo string, devuelve el valor original.
     /// Si es de tipo Set o List, lo devuelve ordenado en forma descendente.
-    pub fn sort_descending(&self) -> Vec<String> {
+    pub fn sort_descending(&self) -> Vec<Vec<u8>> {
         let current_value = self.value.clone();
         match current_value {
-            ValueType::ListType(mut current_list) => {
-                current_list.sort();
-                current_list.reverse();
-                current_list
+            ValueType::ListType(current_list) => {
+                let mut vec: Vec<Vec<u8>> = current_list.into_iter().collect();
+                vec.sort();
+                vec.reverse();
+                vec
             }
             ValueType::SetType(current_set) => {
-                let mut vec: Vec<String> = current_set.into_iter().collect();
+                let mut vec: Vec<Vec<u8>> = current_set.into_iter().collect();
                 vec.sort();
                 vec.reverse();
                 vec
@@ -358,15 +369,16 @@ impl ValueTimeItem {
     ///
     /// Si el valor es de tipo string, devuelve el valor original.
     /// Si es de tipo Set o List, lo devuelve ordenado en forma ascendente.
-    pub fn sort(&self) -> Vec<String> {
+    pub fn sort(&self) -> Vec<Vec<u8>> {
         let current_value_item = self.value.clone();
         match current_value_item {
-            ValueType::ListType(mut current_list) => {
-                current_list.sort();
-                current_list
+            ValueType::ListType(current_list) => {
+                let mut vec: Vec<Vec<u8>> = current_list.into_iter().collect();
+                vec.sort();
+                vec
             }
             ValueType::SetType(current_set) => {
-                let mut vec: Vec<String> = current_set.into_iter().collect();
+                let mut vec: Vec<Vec<u8>> = current_set.into_iter().collect();
                 vec.sort();
                 vec
             }
@@ -375,31 +387,23 @@ impl ValueTimeItem {
     }
 
     /// Devuelve el valor en forma de vector.
-    pub fn get_value_as_vec(&self) -> Vec<&String> {
+    pub fn get_value_as_vec(&self) -> Vec<Vec<u8>> {
         let current_value_item = &self.value;
         match current_value_item {
-            ValueType::ListType(current_list) => current_list.iter().collect(),
-            ValueType::SetType(current_set) => current_set.iter().collect(),
-            ValueType::StringType(current_string) => vec![current_string],
+            ValueType::ListType(current_list) => current_list.iter().cloned().collect(),
+            ValueType::SetType(current_set) => current_set.iter().cloned().collect(),
+            ValueType::StringType(current_string) => vec![current_string.clone()],
         }
     }
 
     /// Devuelve el tipo de valor en forma de string.
     pub fn get_value_type(&self) -> String {
-        let value_type;
         let current_value = &self.value;
         match current_value {
-            ValueType::ListType(_current_list) => {
-                value_type = "list".to_string();
-            }
-            ValueType::SetType(_current_set) => {
-                value_type = "set".to_string();
-            }
-            ValueType::StringType(_current_string) => {
-                value_type = "string".to_string();
-            }
+            ValueType::ListType(_current_list) => "list".to_string(),
+            ValueType::SetType(_current_set) => "set".to_string(),
+            ValueType::StringType(_current_string) => "string".to_string(),
         }
-        value_type
     }
 }
 
@@ -407,7 +411,7 @@ impl ValueTimeItem {
 fn test_001_key_value_item_string_created() {
     use crate::domain::entities::key_value_item::{KeyAccessTime, ValueTimeItemBuilder, ValueType};
 
-    let kv_item = ValueTimeItemBuilder::new(ValueType::StringType("un_string".to_string()))
+    let kv_item = ValueTimeItemBuilder::new(ValueType::StringType("un_string".into()))
         .with_timeout(0)
         .build();
 
@@ -426,7 +430,7 @@ fn test_002_key_value_item_set_created() {
     use std::collections::HashSet;
 
     let mut un_set = HashSet::new();
-    un_set.insert("un_set_string".to_string());
+    un_set.insert(b"un_set_string".to_vec());
 
     let kv_item = ValueTimeItemBuilder::new(ValueType::SetType(un_set))
         .with_timeout(0)
@@ -443,9 +447,9 @@ fn test_002_key_value_item_set_created() {
 #[test]
 fn test_003_key_value_item_list_created() {
     use crate::domain::entities::key_value_item::{KeyAccessTime, ValueTimeItemBuilder, ValueType};
-    let mut un_list = Vec::new();
-    un_list.push("un_list_string".to_string());
-    un_list.push("otro_list_string".to_string());
+    let mut un_list = VecDeque::new();
+    un_list.push_back(b"un_list_string".to_vec());
+    un_list.push_back(b"otro_list_string".to_vec());
 
     let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(un_list))
         .with_timeout(0)
@@ -463,12 +467,12 @@ fn test_003_key_value_item_list_created() {
 fn test_004_key_value_item_changes_to_persist() {
     use crate::domain::entities::key_value_item::{KeyAccessTime, ValueTimeItemBuilder, ValueType};
 
-    let mut kv_item = ValueTimeItemBuilder::new(ValueType::StringType("un_string".to_string()))
+    let mut kv_item = ValueTimeItemBuilder::new(ValueType::StringType("un_string".into()))
         .with_timeout(0)
         .build();
 
     let res = kv_item.make_persistent();
-    assert_eq!(res, true);
+    assert!(res);
     match kv_item.timeout {
         KeyAccessTime::Volatile(_t) => assert!(false),
         KeyAccessTime::Persistent => assert!(true),
@@ -480,12 +484,15 @@ fn test_004_key_value_item_changes_to_persist() {
 fn test_005_list_of_numbers_is_sorted_ascending() {
     use crate::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
 
-    let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        20.to_string(),
-        65.to_string(),
-        1.to_string(),
-        34.to_string(),
-    ]))
+    let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            20.to_string().into_bytes(),
+            65.to_string().into_bytes(),
+            1.to_string().into_bytes(),
+            34.to_string().into_bytes(),
+        ]
+        .into(),
+    ))
     .with_timeout(0)
     .build();
 
@@ -493,10 +500,10 @@ fn test_005_list_of_numbers_is_sorted_ascending() {
     assert_eq!(
         lista_ordenada,
         vec![
-            String::from("1"),
-            String::from("20"),
-            String::from("34"),
-            String::from("65")
+            b"1".to_vec(),
+            b"20".to_vec(),
+            b"34".to_vec(),
+            b"65".to_vec()
         ]
     );
 }
@@ -505,12 +512,15 @@ fn test_005_list_of_numbers_is_sorted_ascending() {
 fn test_006_list_of_numbers_is_sorted_descending() {
     use crate::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
 
-    let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        20.to_string(),
-        65.to_string(),
-        1.to_string(),
-        34.to_string(),
-    ]))
+    let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            20.to_string().into_bytes(),
+            65.to_string().into_bytes(),
+            1.to_string().into_bytes(),
+            34.to_string().into_bytes(),
+        ]
+        .into(),
+    ))
     .with_timeout(0)
     .build();
 
@@ -518,10 +528,10 @@ fn test_006_list_of_numbers_is_sorted_descending() {
     assert_eq!(
         lista_ordenada_inversamente,
         vec![
-            String::from("65"),
-            String::from("34"),
-            String::from("20"),
-            String::from("1")
+            b"65".to_vec(),
+            b"34".to_vec(),
+            b"20".to_vec(),
+            b"1".to_vec()
         ]
     );
 }
@@ -530,12 +540,15 @@ fn test_006_list_of_numbers_is_sorted_descending() {
 fn test_007_list_of_words_is_sorted_inverse_abc() {
     use crate::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
 
-    let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "juan".to_string(),
-        "domingo".to_string(),
-        "irma".to_string(),
-        "dominga".to_string(),
-    ]))
+    let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"juan".to_vec(),
+            b"domingo".to_vec(),
+            b"irma".to_vec(),
+            b"dominga".to_vec(),
+        ]
+        .into(),
+    ))
     .with_timeout(0)
     .build();
 
@@ -543,10 +556,10 @@ fn test_007_list_of_words_is_sorted_inverse_abc() {
     assert_eq!(
         lista_ordenada_inversamente,
         vec![
-            String::from("juan"),
-            String::from("irma"),
-            String::from("domingo"),
-            String::from("dominga")
+            b"juan".to_vec(),
+            b"irma".to_vec(),
+            b"domingo".to_vec(),
+            b"dominga".to_vec()
         ]
     )
 }
@@ -555,22 +568,25 @@ fn test_007_list_of_words_is_sorted_inverse_abc() {
 fn test_008_list_of_words_is_sorted_abc() {
     use crate::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
 
-    let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "juan".to_string(),
-        "domingo".to_string(),
-        "irma".to_string(),
-        "dominga".to_string(),
-    ]))
+    let kv_item = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"juan".to_vec(),
+            b"domingo".to_vec(),
+            b"irma".to_vec(),
+            b"dominga".to_vec(),
+        ]
+        .into(),
+    ))
     .with_timeout(0)
     .build();
     let lista_ordenada = kv_item.sort();
     assert_eq!(
         lista_ordenada,
         vec![
-            String::from("dominga"),
-            String::from("domingo"),
-            String::from("irma"),
-            String::from("juan")
+            b"dominga".to_vec(),
+            b"domingo".to_vec(),
+            b"irma".to_vec(),
+            b"juan".to_vec()
         ]
     );
 }
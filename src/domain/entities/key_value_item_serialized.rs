@@ -3,7 +3,7 @@
 use crate::domain::entities::key_value_item::{
     KeyAccessTime, ValueTimeItem, ValueTimeItemBuilder, ValueType,
 };
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 use std::str::FromStr;
 /// Struct que representa una línea en el dump de la base de datos
 pub struct KeyValueItemSerialized {
@@ -29,6 +29,8 @@ impl KeyValueItemSerialized {
     ///
     /// \<value\>: valor o valores almacenados en la key. Separados por coma.
     ///
+    /// Tanto la clave como los valores se escriben escapados con `escape_field`.
+    ///
     /// # Example
     /// ```
     /// use proyecto_taller_1::domain::entities::key_value_item_serialized::KeyValueItemSerialized;
@@ -59,32 +61,62 @@ impl KeyValueItemSerialized {
     /// let kvis = KeyValueItemSerialized::new("123key;1623427130;1623427130;set;3,2,4".to_string());
     /// let kvi = kvis.transform_to_item();
     ///
-    ///  assert_eq!(kvi.0.to_string(), "123key");
+    ///  assert_eq!(kvi.0, b"123key");
     ///  if let SetType(_) = kvi.1.get_value(){assert!(true)}else{ assert!(false)}
     ///  assert_eq!(kvi.1.get_timeout().to_string(), "1623427130");
     /// ```
-    pub fn transform_to_item(&self) -> (String, ValueTimeItem) {
+    pub fn transform_to_item(&self) -> (Vec<u8>, ValueTimeItem) {
+        match self.try_transform_to_item() {
+            Ok(item) => item,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Igual que `transform_to_item`, pero devuelve un error en lugar de un panic si la línea no tiene los cinco
+    /// campos, si el tipo de dato no es uno de los 3 posibles o si el last_access_time no es un número.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use proyecto_taller_1::domain::entities::key_value_item_serialized::KeyValueItemSerialized;
+    ///
+    /// let kvis = KeyValueItemSerialized::new("123key;1623427130;;string;valor".to_string());
+    /// assert_eq!(kvis.try_transform_to_item().unwrap().0, b"123key");
+    ///
+    /// let kvis = KeyValueItemSerialized::new("123key;1623427130;;no_type;valor".to_string());
+    /// assert!(kvis.try_transform_to_item().is_err());
+    /// ```
+    pub fn try_transform_to_item(&self) -> Result<(Vec<u8>, ValueTimeItem), String> {
         // Format: key; last_access_time; timeout; type; value
         let line: Vec<&str> = self.line.split(';').collect();
+        if line.len() != 5 {
+            return Err(String::from(
+                "Archivo corrupto. La línea no tiene los cinco campos.",
+            ));
+        }
         let value = match line[3] {
-            "string" => ValueType::StringType(line[4].to_string()),
+            "string" => ValueType::StringType(unescape_field(line[4])),
             "set" => {
                 let mut hash_set = HashSet::new();
                 let values: Vec<&str> = line[4].split(',').collect();
                 for value in values {
-                    hash_set.insert(value.to_string());
+                    hash_set.insert(unescape_field(value));
                 }
                 ValueType::SetType(hash_set)
             }
             "list" => {
-                let mut list = Vec::new();
+                let mut list = VecDeque::new();
                 let values: Vec<&str> = line[4].split(',').collect();
                 for value in values {
-                    list.push(value.to_string());
+                    list.push_back(unescape_field(value));
                 }
                 ValueType::ListType(list)
             }
-            _ => panic!("Archivo corrupto. No pertenece a ningún tipo de dato soportado."),
+            _ => {
+                return Err(String::from(
+                    "Archivo corrupto. No pertenece a ningún tipo de dato soportado.",
+                ))
+            }
         };
         let last_access_time_r = u64::from_str(line[1]);
         match last_access_time_r {
@@ -92,19 +124,120 @@ impl KeyValueItemSerialized {
                 let timeout = line[2]
                     .parse::<KeyAccessTime>()
                     .unwrap_or(KeyAccessTime::Volatile(0));
-                (
-                    line[0].to_string(),
+                Ok((
+                    unescape_field(line[0]),
                     ValueTimeItemBuilder::new(value)
                         .with_key_access_time(timeout)
                         .with_last_access_time(last_access_time)
                         .build(),
-                )
+                ))
             }
-            Err(_) => {
-                panic!("Archivo corrupto. No se pudo levantar el last_access_time.")
+            Err(_) => Err(String::from(
+                "Archivo corrupto. No se pudo levantar el last_access_time.",
+            )),
+        }
+    }
+}
+
+/// Serializa la clave `key` y su valor `item` en una línea del dump, con el formato que interpreta
+/// `KeyValueItemSerialized::transform_to_item`. La línea no incluye el salto de línea final.
+///
+/// # Example
+///
+/// ```
+/// use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+/// use proyecto_taller_1::domain::entities::key_value_item_serialized::serialize_item;
+///
+/// let item = ValueTimeItemBuilder::new(ValueType::StringType("a;b".into()))
+///     .with_last_access_time(1623427130)
+///     .build();
+/// assert_eq!(serialize_item(b"clave", &item), "clave;1623427130;;string;a%3Bb");
+/// ```
+pub fn serialize_item(key: &[u8], item: &ValueTimeItem) -> String {
+    let (kvi_type, value) = match item.get_value() {
+        ValueType::StringType(value) => ("string", escape_field(value)),
+        ValueType::SetType(values) => (
+            "set",
+            values
+                .iter()
+                .map(|v| escape_field(v))
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+        ValueType::ListType(values) => (
+            "list",
+            values
+                .iter()
+                .map(|v| escape_field(v))
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+    };
+    format!(
+        "{};{};{};{};{}",
+        escape_field(key),
+        item.get_last_access_time(),
+        item.get_timeout(),
+        kvi_type,
+        value
+    )
+}
+
+/// Escapa un campo para escribirlo en una línea del dump.
+///
+/// Los bytes no imprimibles y los que el formato usa como separadores (`;`, `,` y `%`) se reemplazan
+/// por `%XX`, su valor en hexadecimal. El resultado es siempre ASCII y no contiene saltos de línea.
+///
+/// # Example
+///
+/// ```
+/// use proyecto_taller_1::domain::entities::key_value_item_serialized::escape_field;
+///
+/// assert_eq!(escape_field(b"hola"), "hola");
+/// assert_eq!(escape_field(b"a;b,c%\r\n"), "a%3Bb%2Cc%25%0D%0A");
+/// ```
+pub fn escape_field(bytes: &[u8]) -> String {
+    let mut escaped = String::with_capacity(bytes.len());
+    for byte in bytes {
+        match byte {
+            b';' | b',' | b'%' => escaped.push_str(&format!("%{:02X}", byte)),
+            0x20..=0x7e => escaped.push(*byte as char),
+            _ => escaped.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    escaped
+}
+
+/// Revierte el escapado realizado por `escape_field`.
+///
+/// Las secuencias `%` que no estén seguidas por dos dígitos hexadecimales se conservan tal cual.
+///
+/// # Example
+///
+/// ```
+/// use proyecto_taller_1::domain::entities::key_value_item_serialized::unescape_field;
+///
+/// assert_eq!(unescape_field("a%3Bb%FF"), b"a;b\xff");
+/// ```
+pub fn unescape_field(field: &str) -> Vec<u8> {
+    let bytes = field.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut pos = 0;
+    while pos < bytes.len() {
+        if bytes[pos] == b'%' && pos + 2 < bytes.len() {
+            if let Some(byte) = std::str::from_utf8(&bytes[pos + 1..pos + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                unescaped.push(byte);
+                pos += 3;
+                continue;
             }
         }
+        unescaped.push(bytes[pos]);
+        pos += 1;
     }
+    unescaped
 }
 
 #[test]
@@ -124,7 +257,7 @@ fn line_string_type() {
     let kvis = KeyValueItemSerialized::new("123key;1623427130;1623427130;string;value".to_string());
     let kvi = kvis.transform_to_item();
 
-    assert_eq!(kvi.0.to_string(), "123key");
+    assert_eq!(kvi.0, b"123key");
     assert_eq!(kvi.1.get_value().to_string(), "value");
     assert_eq!(kvi.1.get_timeout().to_string(), "1623427130");
 }
@@ -137,13 +270,13 @@ fn line_set_type() {
     let kvis = KeyValueItemSerialized::new("123key;1623427130;1623427130;set;3,2,4".to_string());
     let kvi = kvis.transform_to_item();
 
-    assert_eq!(kvi.0.to_string(), "123key");
+    assert_eq!(kvi.0, b"123key");
     match kvi.1.get_value() {
         ValueType::SetType(hs) => {
             assert_eq!(hs.len(), 3);
-            assert!(hs.contains("2"));
-            assert!(hs.contains("3"));
-            assert!(hs.contains("4"));
+            assert!(hs.contains(&b"2".to_vec()));
+            assert!(hs.contains(&b"3".to_vec()));
+            assert!(hs.contains(&b"4".to_vec()));
         }
         _ => assert!(false),
     }
@@ -157,14 +290,14 @@ fn line_list_type() {
 
     let kvis = KeyValueItemSerialized::new("123key;1623427130;1623427130;list;1,2,3".to_string());
     let kvi = kvis.transform_to_item();
-    assert_eq!(kvi.0.to_string(), "123key");
+    assert_eq!(kvi.0, b"123key");
     match kvi.1.get_value() {
         ValueType::ListType(l) => {
             assert_eq!(l.len(), 3);
             let mut iter = l.iter();
-            assert_eq!(iter.next(), Some(&"1".to_string()));
-            assert_eq!(iter.next(), Some(&"2".to_string()));
-            assert_eq!(iter.next(), Some(&"3".to_string()));
+            assert_eq!(iter.next(), Some(&b"1".to_vec()));
+            assert_eq!(iter.next(), Some(&b"2".to_vec()));
+            assert_eq!(iter.next(), Some(&b"3".to_vec()));
         }
         _ => assert!(false),
     }
@@ -180,10 +313,51 @@ fn line_persistent() {
     let kvis = KeyValueItemSerialized::new("123key;1623427130;;string;value".to_string());
     let kvi = kvis.transform_to_item();
 
-    assert_eq!(kvi.0.to_string(), "123key");
+    assert_eq!(kvi.0, b"123key");
     assert_eq!(kvi.1.get_value().to_string(), "value");
     match kvi.1.get_timeout() {
         KeyAccessTime::Persistent => assert!(true),
         _ => assert!(false),
     }
 }
+
+#[test]
+fn line_with_escaped_binary_string() {
+    use crate::domain::entities::key_value_item::ValueType;
+    use crate::domain::entities::key_value_item_serialized::KeyValueItemSerialized;
+
+    let value = b"a;b,c\r\n\xff%".to_vec();
+    let line = format!(
+        "{};1623427130;;string;{}",
+        escape_field(b"k;1"),
+        escape_field(&value)
+    );
+    let kvi = KeyValueItemSerialized::new(line).transform_to_item();
+
+    assert_eq!(kvi.0, b"k;1");
+    match kvi.1.get_value() {
+        ValueType::StringType(s) => assert_eq!(s, &value),
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn line_with_escaped_binary_key_and_members() {
+    use crate::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+    use std::collections::HashSet;
+
+    let members: HashSet<Vec<u8>> = vec![b"a,b".to_vec(), b"\xff\r\n".to_vec()]
+        .into_iter()
+        .collect();
+    let item = ValueTimeItemBuilder::new(ValueType::SetType(members.clone()))
+        .with_last_access_time(1623427130)
+        .build();
+    let line = serialize_item(b"\xfe;k", &item);
+    let kvi = KeyValueItemSerialized::new(line).transform_to_item();
+
+    assert_eq!(kvi.0, b"\xfe;k");
+    match kvi.1.get_value() {
+        ValueType::SetType(set) => assert_eq!(set, &members),
+        _ => assert!(false),
+    }
+}
@@ -0,0 +1,190 @@
+//! Notificaciones de los eventos que modifican las claves de la base de datos.
+
+use std::io::{Error, ErrorKind};
+
+/// Letras de las clases de eventos que habilita el alias `A` de `notify-keyspace-events`.
+const ALL_CLASSES: &str = "g$lshzxetd";
+
+/// Clase de un evento sobre una clave, identificada por su letra en `notify-keyspace-events`.
+/// * Generic (`g`): comandos que no dependen del tipo de dato, como `del`, `expire` o `rename`.
+/// * String (`$`), List (`l`), Set (`s`), Hash (`h`), SortedSet (`z`) y Stream (`t`): comandos de cada tipo de dato.
+/// * Expired (`x`): claves eliminadas por haber expirado.
+/// * Evicted (`e`): claves desalojadas por superar el límite `maxmemory`.
+/// * KeyMiss (`m`): accesos a claves inexistentes.
+/// * Module (`d`): eventos de módulos.
+/// * New (`n`): claves agregadas a la base de datos.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventClass {
+    Generic,
+    String,
+    List,
+    Set,
+    Hash,
+    SortedSet,
+    Expired,
+    Evicted,
+    Stream,
+    KeyMiss,
+    Module,
+    New,
+}
+
+impl EventClass {
+    /// Retorna la letra que identifica a la clase en `notify-keyspace-events`.
+    pub fn flag(&self) -> char {
+        match self {
+            EventClass::Generic => 'g',
+            EventClass::String => '$',
+            EventClass::List => 'l',
+            EventClass::Set => 's',
+            EventClass::Hash => 'h',
+            EventClass::SortedSet => 'z',
+            EventClass::Expired => 'x',
+            EventClass::Evicted => 'e',
+            EventClass::Stream => 't',
+            EventClass::KeyMiss => 'm',
+            EventClass::Module => 'd',
+            EventClass::New => 'n',
+        }
+    }
+}
+
+/// Mensaje que se publica en un canal para notificar un evento sobre una clave.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyspaceNotification {
+    pub channel: String,
+    pub message: String,
+}
+
+/// Eventos que se notifican, configurados con la directiva `notify-keyspace-events`.
+///
+/// El valor de la directiva es una combinación de letras: `K` publica los eventos en el canal `__keyspace@0__:<clave>`
+/// con el nombre del evento como mensaje, y `E` en el canal `__keyevent@0__:<evento>` con la clave como mensaje.
+/// Las demás letras indican las clases de eventos que se notifican (ver `EventClass`), y `A` equivale a `g$lshzxetd`.
+/// Si no se indica `K` ni `E`, o ninguna clase, no se notifica ningún evento.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyspaceEvents {
+    keyspace: bool,
+    keyevent: bool,
+    classes: Vec<char>,
+}
+
+impl KeyspaceEvents {
+    /// Interpreta el valor de la directiva `notify-keyspace-events`.
+    ///
+    /// Devuelve error si contiene alguna letra que no corresponde a una clase de eventos.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::keyspace_events::{EventClass, KeyspaceEvents};
+    ///
+    /// let events = KeyspaceEvents::parse("KEA").unwrap();
+    /// assert!(events.notifies(EventClass::Expired));
+    /// assert!(!events.notifies(EventClass::New));
+    /// assert!(!KeyspaceEvents::parse("A").unwrap().notifies(EventClass::Generic));
+    /// assert!(KeyspaceEvents::parse("Kq").is_err());
+    /// ```
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        let mut events = KeyspaceEvents::default();
+        for flag in value.chars() {
+            match flag {
+                'K' => events.keyspace = true,
+                'E' => events.keyevent = true,
+                'A' => events.classes.extend(ALL_CLASSES.chars()),
+                'g' | '$' | 'l' | 's' | 'h' | 'z' | 'x' | 'e' | 't' | 'm' | 'd' | 'n' => {
+                    events.classes.push(flag)
+                }
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Invalid notify-keyspace-events '{}'", value),
+                    ))
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    /// Indica si se notifican los eventos de la clase.
+    pub fn notifies(&self, class: EventClass) -> bool {
+        (self.keyspace || self.keyevent) && self.classes.contains(&class.flag())
+    }
+
+    /// Retorna los mensajes que notifican el evento sobre la clave, o ninguno si no se notifican los eventos de su clase.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::keyspace_events::{EventClass, KeyspaceEvents};
+    ///
+    /// let events = KeyspaceEvents::parse("Eg").unwrap();
+    /// let notifications = events.notifications(EventClass::Generic, "del", "mykey");
+    /// assert_eq!(notifications.len(), 1);
+    /// assert_eq!(notifications[0].channel, "__keyevent@0__:del");
+    /// assert_eq!(notifications[0].message, "mykey");
+    /// assert!(events.notifications(EventClass::String, "set", "mykey").is_empty());
+    /// ```
+    pub fn notifications(
+        &self,
+        class: EventClass,
+        event: &str,
+        key: &str,
+    ) -> Vec<KeyspaceNotification> {
+        let mut notifications = Vec::new();
+        if !self.notifies(class) {
+            return notifications;
+        }
+        if self.keyspace {
+            notifications.push(KeyspaceNotification {
+                channel: format!("__keyspace@0__:{}", key),
+                message: event.to_string(),
+            });
+        }
+        if self.keyevent {
+            notifications.push(KeyspaceNotification {
+                channel: format!("__keyevent@0__:{}", event),
+                message: key.to_string(),
+            });
+        }
+        notifications
+    }
+}
+
+#[test]
+fn test_01_keyspace_and_keyevent_channels_are_notified() {
+    let events = KeyspaceEvents::parse("KE$").unwrap();
+    let notifications = events.notifications(EventClass::String, "set", "mykey");
+    assert_eq!(
+        notifications,
+        vec![
+            KeyspaceNotification {
+                channel: String::from("__keyspace@0__:mykey"),
+                message: String::from("set"),
+            },
+            KeyspaceNotification {
+                channel: String::from("__keyevent@0__:set"),
+                message: String::from("mykey"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_02_alias_all_does_not_include_key_miss_and_new_events() {
+    let events = KeyspaceEvents::parse("KA").unwrap();
+    for class in [
+        EventClass::Generic,
+        EventClass::String,
+        EventClass::List,
+        EventClass::Set,
+        EventClass::Expired,
+        EventClass::Evicted,
+    ] {
+        assert!(events.notifies(class));
+    }
+    assert!(!events.notifies(EventClass::KeyMiss));
+    assert!(!events.notifies(EventClass::New));
+    assert_eq!(
+        KeyspaceEvents::parse("").unwrap(),
+        KeyspaceEvents::default()
+    );
+}
@@ -1,8 +1,14 @@
 //! Tipos de mensajes para enviar entre canales.
 
-use super::client::Client;
+use super::acl::{AclLogEntry, AclUser};
+use super::client::{Client, ClientFilter};
+use super::cluster::{Cluster, ClusterMessage, ClusterRequest, SlotRoute};
+use super::keyspace_events::KeyspaceNotification;
+use super::replication::MasterLinkEvent;
+use super::tracking::{ClientTracking, TrackingOptions};
+use crate::domain::implementations::output_queue::OutputBufferLimit;
 use crate::services::utils::resp_type::RespType;
-use std::{net::SocketAddr, sync::mpsc::Sender};
+use std::{sync::mpsc::Sender, time::Duration};
 
 /// Tipo de mensaje para comunicar al threadpool con sus workers.
 /// El threadpool puede enviar dos tipos de mensajes:
@@ -16,6 +22,7 @@ pub enum Message {
 type Job = Box<dyn FnOnce() + Send + 'static>;
 
 /// Tipo de mensaje para comunicar al cliente con el servidor.
+/// Los mensajes referidos a un cliente lo identifican con el identificador que le asigna el servidor al registrarlo.
 /// El cliente puede enviar los siguientes mensajes al servidor:
 /// * Log: envia un mensaje que debe escribirse en el archivo de log.
 /// * Verb: envia un mensaje para que imprima el servidor.
@@ -24,29 +31,171 @@ type Job = Box<dyn FnOnce() + Send + 'static>;
 /// * InfoServer: pide información del servidor.
 /// * InfoClients: pide información de los clientes conectados al servidor.
 /// * InfoStats: pide estadísticas sobre el uso del servidor.
-/// * AddClient: registra al nuevo cliente conectado.
+/// * InfoReplication: pide información sobre la replicación, indicando si las réplicas son de solo lectura.
+/// * AddClient: registra al nuevo cliente conectado; devuelve el identificador que le asigna el servidor.
 /// * CloseClient: elimina un cliente del registro de clientes conectados.
 /// * Subscribe: suscribe un cliente a un canal.
 /// * Unsubscribe: desuscribe un cliente de un canal.
-/// * UnsubscribeAll: desuscribe un cliente de todos los canales.
+/// * UnsubscribeAll: desuscribe un cliente de todos los canales; devuelve esos canales y las suscripciones que le quedan.
+/// * PSubscribe: suscribe un cliente a un patrón de canales.
+/// * PUnsubscribe: desuscribe un cliente de un patrón de canales.
+/// * PUnsubscribeAll: desuscribe un cliente de todos los patrones; devuelve esos patrones y las suscripciones que le quedan.
+/// * SSubscribe: suscribe un cliente a un canal sharded; devuelve la cantidad de canales sharded a los que está suscrito.
+/// * SUnsubscribe: desuscribe un cliente de un canal sharded.
+/// * SUnsubscribeAll: desuscribe un cliente de todos los canales sharded; devuelve esos canales y los canales sharded que le quedan.
+/// * SPublish: publica un mensaje en un canal sharded.
 /// * Publish: publica un mensaje en un canal.
+/// * KeyspaceNotifications: publica los mensajes que notifican eventos sobre las claves de la base de datos.
 /// * Channels: pide los nombres de los canales que cumplen con cierto patrón.
 /// * Numsub: pide la cantidad de suscriptores por canal.
+/// * Numpat: pide la cantidad de patrones con suscriptores.
+/// * ShardChannels: pide los nombres de los canales sharded que cumplen con cierto patrón.
+/// * ShardNumsub: pide la cantidad de suscriptores por canal sharded.
+/// * SetProtocol: registra la versión del protocolo RESP negociada por el cliente.
+/// * SetClientName: registra el nombre de la conexión del cliente, o lo elimina.
+/// * SetClientLibName: registra el nombre de la librería que utiliza el cliente.
+/// * SetClientLibVersion: registra la versión de la librería que utiliza el cliente.
+/// * ClientName: pide el nombre de la conexión del cliente.
+/// * ClientInfo: pide la información de la conexión del cliente.
+/// * ResetClient: vuelve la conexión del cliente a su estado inicial.
+/// * ClientList: pide la información de los clientes que cumplen con un filtro.
+/// * ClientKill: cierra la conexión de los clientes que cumplen con un filtro.
+/// * SetClientNoEvict: activa o desactiva el modo "no-evict" del cliente.
+/// * ClientTracking: activa el seguimiento de claves del cliente con las opciones indicadas, o lo desactiva si es `None`; devuelve el error si no puede.
+/// * ClientCaching: registra si se recuerdan las claves que lee el siguiente comando del cliente; devuelve el error si no puede.
+/// * ClientTrackingInfo: pide el estado del seguimiento de claves del cliente.
+/// * TrackKeys: recuerda las claves que lee el cliente en el comando que está ejecutando, si tiene activado el seguimiento.
+/// * InvalidateKeys: envía los mensajes de invalidación de las claves modificadas, indicando el cliente que las modificó.
+/// * ReplicaOf: convierte al servidor en réplica del master indicado, o en master si es `None`; devuelve el identificador de la conexión con el master.
+/// * MasterLink: informa un evento de la conexión con el master; devuelve si la conexión sigue vigente.
+/// * IsReplica: pregunta si el servidor es una réplica.
+/// * ReplicaListeningPort: registra el puerto en el que recibe conexiones la réplica que representa el cliente.
+/// * AddReplica: envía al cliente la copia completa de los datos y lo registra como réplica; avisa cuando terminó.
+/// * ReplicaAck: registra el offset que confirmó haber procesado la réplica.
+/// * Propagate: envía un comando de escritura a las réplicas.
+/// * EnableCluster: activa el modo cluster con el estado del cluster indicado.
+/// * ClusterRoute: pregunta cómo se atienden los comandos sobre las claves de un slot; devuelve `None` si el servidor no está en modo cluster.
+/// * Cluster: ejecuta un subcomando de `cluster`; devuelve su respuesta o el error si no puede ejecutarse.
+/// * ClusterBusMessage: procesa un mensaje recibido por el bus del cluster; devuelve la respuesta para el emisor, si corresponde.
+/// * ClusterPingMessages: pide los mensajes periódicos para los demás nodos del cluster, con la dirección del bus de cada uno.
+/// * CloseIdleClients: cierra la conexión de los clientes inactivos durante más de la cantidad de segundos indicada.
+/// * CheckOutputBuffers: actualiza el límite de la cola de salida de los clientes suscritos y cierra la conexión de los que lo superan.
+/// * PauseClients: suspende los comandos de los clientes durante el tiempo indicado; solo los de escritura si se indica.
+/// * UnpauseClients: reanuda los comandos de los clientes suspendidos.
+/// * CheckPause: pregunta cuánto tiempo falta para poder ejecutar un comando, indicando si es de escritura.
+/// * AclAuthenticate: verifica el usuario y la contraseña con los que se quiere autenticar el cliente.
+/// * AclNoPass: pregunta si un usuario está habilitado y no requiere contraseña.
+/// * AclCheck: verifica que el usuario del cliente pueda ejecutar un comando; devuelve el error si no puede.
+/// * AclSetUser: crea o modifica un usuario ACL aplicándole reglas.
+/// * AclGetUser: pide un usuario ACL.
+/// * AclDelUser: elimina usuarios ACL y cierra las conexiones autenticadas con ellos.
+/// * AclList: pide la descripción de los usuarios ACL.
+/// * AclUsers: pide los nombres de los usuarios ACL.
+/// * AclLog: pide las entradas del registro de accesos denegados.
+/// * AclLogReset: elimina las entradas del registro de accesos denegados.
+/// * CloseAllClients: cierra la conexión de todos los clientes; devuelve la cantidad de clientes desconectados.
+/// * Shutdown: escribe los mensajes pendientes del log y deja de atender mensajes; avisa cuando terminó.
 #[derive(Debug)]
 pub enum WorkerMessage {
     Log(String),
     Verb(String),
-    NewOperation(RespType, SocketAddr, Sender<bool>),
-    SetMonitor(SocketAddr),
+    NewOperation(RespType, usize, Sender<bool>),
+    SetMonitor(usize),
     InfoServer(Sender<String>),
     InfoClients(Sender<String>),
     InfoStats(Sender<String>),
-    AddClient(Client),
-    CloseClient(SocketAddr),
-    Subscribe(String, SocketAddr, Sender<usize>),
-    Unsubscribe(String, SocketAddr, Sender<usize>),
-    UnsubscribeAll(SocketAddr, Sender<usize>),
+    InfoReplication(bool, Sender<String>),
+    AddClient(Box<Client>, Sender<usize>),
+    CloseClient(usize),
+    Subscribe(String, usize, Sender<usize>),
+    Unsubscribe(String, usize, Sender<usize>),
+    UnsubscribeAll(usize, Sender<(Vec<String>, usize)>),
+    PSubscribe(String, usize, Sender<usize>),
+    PUnsubscribe(String, usize, Sender<usize>),
+    PUnsubscribeAll(usize, Sender<(Vec<String>, usize)>),
+    SSubscribe(String, usize, Sender<usize>),
+    SUnsubscribe(String, usize, Sender<usize>),
+    SUnsubscribeAll(usize, Sender<(Vec<String>, usize)>),
+    SPublish(String, Sender<usize>, String),
     Publish(String, Sender<usize>, String),
+    KeyspaceNotifications(Vec<KeyspaceNotification>),
     Channels(Sender<Vec<RespType>>, Option<String>),
     Numsub(Vec<String>, Sender<Vec<RespType>>),
+    Numpat(Sender<usize>),
+    ShardChannels(Sender<Vec<RespType>>, Option<String>),
+    ShardNumsub(Vec<String>, Sender<Vec<RespType>>),
+    SetProtocol(usize, usize),
+    SetClientName(usize, Option<String>),
+    SetClientLibName(usize, String),
+    SetClientLibVersion(usize, String),
+    ClientName(usize, Sender<Option<String>>),
+    ClientInfo(usize, Sender<String>),
+    ResetClient(usize),
+    ClientList(ClientFilter, Sender<String>),
+    ClientKill(ClientFilter, Sender<usize>),
+    SetClientNoEvict(usize, bool),
+    ClientTracking(usize, Option<TrackingOptions>, Sender<Result<(), String>>),
+    ClientCaching(usize, bool, Sender<Result<(), String>>),
+    ClientTrackingInfo(usize, Sender<Option<ClientTracking>>),
+    TrackKeys(usize, Vec<Vec<u8>>),
+    InvalidateKeys(Vec<Vec<u8>>, Option<usize>),
+    ReplicaOf(Option<(String, u16)>, Sender<usize>),
+    MasterLink(usize, MasterLinkEvent, Sender<bool>),
+    IsReplica(Sender<bool>),
+    ReplicaListeningPort(usize, u16),
+    AddReplica(usize, Vec<u8>, Sender<()>),
+    ReplicaAck(usize, usize),
+    Propagate(RespType),
+    EnableCluster(Box<Cluster>),
+    ClusterRoute(u16, Sender<Option<SlotRoute>>),
+    Cluster(ClusterRequest, Sender<Result<RespType, String>>),
+    ClusterBusMessage(Box<ClusterMessage>, Sender<Option<ClusterMessage>>),
+    ClusterPingMessages(Sender<Vec<(String, ClusterMessage)>>),
+    CloseIdleClients(u64),
+    CheckOutputBuffers(OutputBufferLimit),
+    PauseClients(Duration, bool),
+    UnpauseClients,
+    CheckPause(bool, Sender<Option<Duration>>),
+    AclAuthenticate(usize, String, String, Sender<bool>),
+    AclNoPass(String, Sender<bool>),
+    AclCheck(usize, String, Vec<RespType>, Sender<Option<String>>),
+    AclSetUser(String, Vec<String>, Sender<Result<(), String>>),
+    AclGetUser(String, Sender<Option<AclUser>>),
+    AclDelUser(Vec<String>, Sender<usize>),
+    AclList(Sender<Vec<String>>),
+    AclUsers(Sender<Vec<String>>),
+    AclLog(Option<usize>, Sender<Vec<AclLogEntry>>),
+    AclLogReset,
+    CloseAllClients(Sender<usize>),
+    Shutdown(Sender<()>),
+}
+
+/// Opciones con las que se detiene el servidor.
+/// * save: si se guardan los datos en el archivo `dbfilename` antes de detenerlo (`SAVE`, por defecto) o no (`NOSAVE`).
+/// * now: no espera a que terminen los comandos que se están ejecutando (`NOW`).
+/// * force: lo detiene aunque no se hayan podido guardar los datos (`FORCE`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShutdownOptions {
+    pub save: bool,
+    pub now: bool,
+    pub force: bool,
+}
+
+impl Default for ShutdownOptions {
+    fn default() -> Self {
+        ShutdownOptions {
+            save: true,
+            now: false,
+            force: false,
+        }
+    }
+}
+
+/// Pedido para detener el servidor, enviado por el comando `shutdown` o al recibir las señales SIGTERM o SIGINT.
+///
+/// Si lo envía un cliente, se le responde por `reply` si el servidor se detiene o el motivo por el que no pudo hacerlo.
+#[derive(Debug)]
+pub struct ShutdownRequest {
+    pub options: ShutdownOptions,
+    pub reply: Option<Sender<Result<(), String>>>,
 }
@@ -1,9 +1,17 @@
 //! Entidades involucradas durante la ejecución del servidor Redis.
 
+pub mod acl;
 pub mod client;
+pub mod cluster;
 pub mod config;
+pub mod hash_ring;
 pub mod key_value_item;
 pub mod key_value_item_serialized;
+pub mod keyspace_events;
 pub mod message;
+pub mod pubsub_registry;
+pub mod replication;
 pub mod server;
+pub mod session;
+pub mod tracking;
 pub mod worker;
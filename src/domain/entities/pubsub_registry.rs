@@ -0,0 +1,196 @@
+//! Registro de las suscripciones Pub/Sub de los clientes.
+
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Registro bidireccional de suscripciones a canales (o a patrones de canales).
+///
+/// Guarda, por cada canal, los identificadores de sus suscriptores y, por cada cliente, los canales a los que está suscrito.
+/// Así, publicar un mensaje sólo recorre los suscriptores del canal, y desuscribir a un cliente de un canal,
+/// o de todos cuando se desconecta, no requiere recorrer el registro completo.
+/// Los canales que se quedan sin suscriptores y los clientes que se quedan sin suscripciones se eliminan del registro.
+#[derive(Debug, Default)]
+pub struct PubSubRegistry {
+    subscribers: HashMap<String, HashSet<usize>>,
+    subscriptions: HashMap<usize, HashSet<String>>,
+}
+
+impl PubSubRegistry {
+    /// Crea un registro sin suscripciones.
+    pub fn new() -> Self {
+        PubSubRegistry::default()
+    }
+
+    /// Suscribe al cliente al canal.
+    ///
+    /// Devuelve false si el cliente ya estaba suscrito.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::pubsub_registry::PubSubRegistry;
+    ///
+    /// let mut registry = PubSubRegistry::new();
+    /// assert!(registry.subscribe("news", 1));
+    /// assert!(!registry.subscribe("news", 1));
+    /// assert_eq!(registry.subscriber_count("news"), 1);
+    /// ```
+    pub fn subscribe(&mut self, channel: &str, client_id: usize) -> bool {
+        let added = self
+            .subscribers
+            .entry(channel.to_string())
+            .or_default()
+            .insert(client_id);
+        if added {
+            self.subscriptions
+                .entry(client_id)
+                .or_default()
+                .insert(channel.to_string());
+        }
+        added
+    }
+
+    /// Desuscribe al cliente del canal.
+    ///
+    /// Devuelve false si el cliente no estaba suscrito.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::pubsub_registry::PubSubRegistry;
+    ///
+    /// let mut registry = PubSubRegistry::new();
+    /// registry.subscribe("news", 1);
+    /// assert!(registry.unsubscribe("news", 1));
+    /// assert!(!registry.unsubscribe("news", 1));
+    /// assert!(registry.is_empty());
+    /// ```
+    pub fn unsubscribe(&mut self, channel: &str, client_id: usize) -> bool {
+        let removed = remove_from(&mut self.subscribers, channel, &client_id);
+        if removed {
+            remove_from(&mut self.subscriptions, &client_id, channel);
+        }
+        removed
+    }
+
+    /// Desuscribe al cliente de todos sus canales.
+    ///
+    /// Devuelve los canales de los que se lo desuscribió.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::pubsub_registry::PubSubRegistry;
+    ///
+    /// let mut registry = PubSubRegistry::new();
+    /// registry.subscribe("news", 1);
+    /// registry.subscribe("news", 2);
+    /// assert_eq!(registry.unsubscribe_all(1), vec![String::from("news")]);
+    /// assert_eq!(registry.subscription_count(1), 0);
+    /// assert_eq!(registry.subscriber_count("news"), 1);
+    /// ```
+    pub fn unsubscribe_all(&mut self, client_id: usize) -> Vec<String> {
+        let channels: Vec<String> = self
+            .subscriptions
+            .remove(&client_id)
+            .map(|channels| channels.into_iter().collect())
+            .unwrap_or_default();
+        for channel in &channels {
+            remove_from(&mut self.subscribers, channel.as_str(), &client_id);
+        }
+        channels
+    }
+
+    /// Retorna los identificadores de los suscriptores del canal.
+    pub fn subscribers(&self, channel: &str) -> Vec<usize> {
+        self.subscribers
+            .get(channel)
+            .map(|subscribers| subscribers.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Retorna la cantidad de suscriptores del canal.
+    pub fn subscriber_count(&self, channel: &str) -> usize {
+        self.subscribers.get(channel).map_or(0, HashSet::len)
+    }
+
+    /// Retorna la cantidad de canales a los que está suscrito el cliente.
+    pub fn subscription_count(&self, client_id: usize) -> usize {
+        self.subscriptions.get(&client_id).map_or(0, HashSet::len)
+    }
+
+    /// Retorna los canales que tienen al menos un suscriptor.
+    pub fn channels(&self) -> impl Iterator<Item = &String> {
+        self.subscribers.keys()
+    }
+
+    /// Retorna la cantidad de canales que tienen al menos un suscriptor.
+    pub fn len(&self) -> usize {
+        self.subscribers.len()
+    }
+
+    /// Indica si ningún cliente está suscrito a un canal.
+    pub fn is_empty(&self) -> bool {
+        self.subscribers.is_empty()
+    }
+}
+
+/// Elimina el valor del conjunto asociado a la clave, y la clave si el conjunto queda vacío.
+///
+/// Devuelve false si el valor no estaba en el conjunto.
+pub(crate) fn remove_from<K, V, Q, R>(map: &mut HashMap<K, HashSet<V>>, key: &Q, value: &R) -> bool
+where
+    K: Borrow<Q> + Hash + Eq,
+    V: Borrow<R> + Hash + Eq,
+    Q: Hash + Eq + ?Sized,
+    R: Hash + Eq + ?Sized,
+{
+    let mut removed = false;
+    if let Some(set) = map.get_mut(key) {
+        removed = set.remove(value);
+        if set.is_empty() {
+            map.remove(key);
+        }
+    }
+    removed
+}
+
+#[test]
+fn test_01_subscriptions_are_registered_in_both_directions() {
+    let mut registry = PubSubRegistry::new();
+    registry.subscribe("news", 1);
+    registry.subscribe("sports", 1);
+    registry.subscribe("news", 2);
+
+    let mut subscribers = registry.subscribers("news");
+    subscribers.sort_unstable();
+    assert_eq!(subscribers, vec![1, 2]);
+    assert_eq!(registry.subscription_count(1), 2);
+    assert_eq!(registry.subscription_count(2), 1);
+    assert_eq!(registry.len(), 2);
+}
+
+#[test]
+fn test_02_unsubscribing_a_client_that_is_not_subscribed_keeps_other_subscribers() {
+    let mut registry = PubSubRegistry::new();
+    registry.subscribe("news", 1);
+    registry.subscribe("news", 2);
+
+    assert!(!registry.unsubscribe("news", 3));
+    assert!(!registry.unsubscribe("sports", 1));
+    assert!(registry.unsubscribe("news", 2));
+    assert_eq!(registry.subscribers("news"), vec![1]);
+}
+
+#[test]
+fn test_03_unsubscribing_from_all_removes_empty_channels() {
+    let mut registry = PubSubRegistry::new();
+    registry.subscribe("news", 1);
+    registry.subscribe("sports", 1);
+    registry.subscribe("sports", 2);
+
+    let mut removed = registry.unsubscribe_all(1);
+    removed.sort();
+    assert_eq!(removed, vec![String::from("news"), String::from("sports")]);
+    assert_eq!(registry.channels().collect::<Vec<_>>(), vec!["sports"]);
+    assert_eq!(registry.subscription_count(1), 0);
+    assert!(registry.unsubscribe_all(1).is_empty());
+}
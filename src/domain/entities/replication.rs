@@ -0,0 +1,299 @@
+//! Estado de la replicación entre un servidor master y sus réplicas.
+
+use crate::services::utils::random::random_index;
+use std::net::{Shutdown, TcpStream};
+use std::time::SystemTime;
+
+/// Identificador de replicación nulo, que Redis informa como `master_replid2` cuando no hubo un cambio de master.
+const NULL_REPLID: &str = "0000000000000000000000000000000000000000";
+
+/// Estado de la conexión de una réplica con su master.
+/// * Connecting: intentando conectarse con el master.
+/// * Sync: conectada, realizando el handshake y recibiendo la copia completa de los datos.
+/// * Connected: sincronizada, recibiendo los comandos de escritura del master.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinkStatus {
+    Connecting,
+    Sync,
+    Connected,
+}
+
+/// Eventos que informa el hilo que mantiene la conexión de una réplica con su master.
+/// * Connecting: se perdió la conexión, o todavía no se estableció, y se intenta conectar.
+/// * Sync: se estableció la conexión, cuyo stream se guarda para poder cerrarla con `replicaof no one`.
+/// * Connected: terminó la sincronización completa; indica el identificador de replicación del master y su offset.
+/// * Offset: cantidad de bytes de comandos recibidos del master desde el inicio de su replicación.
+#[derive(Debug)]
+pub enum MasterLinkEvent {
+    Connecting,
+    Sync(TcpStream),
+    Connected(String, usize),
+    Offset(usize),
+}
+
+/// Conexión de una réplica con su master.
+///
+/// `generation` identifica a cada `replicaof`, para que los eventos de un hilo de replicación anterior se descarten.
+#[derive(Debug)]
+struct MasterLink {
+    host: String,
+    port: u16,
+    generation: usize,
+    status: LinkStatus,
+    stream: Option<TcpStream>,
+    last_io: SystemTime,
+}
+
+/// Estado de una réplica conectada al servidor, que se guarda junto al cliente que la representa.
+/// * listening_port: puerto en el que la réplica recibe conexiones, informado con `replconf listening-port`.
+/// * ack_offset: último offset que la réplica confirmó haber procesado con `replconf ack`.
+/// * online: la réplica ya pidió la sincronización con `psync` y recibe los comandos de escritura.
+/// * last_ack: momento de la última confirmación de la réplica.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicaState {
+    pub listening_port: u16,
+    pub ack_offset: usize,
+    pub online: bool,
+    pub last_ack: SystemTime,
+}
+
+impl ReplicaState {
+    /// Crea el estado de una réplica que todavía no pidió la sincronización.
+    pub fn new(listening_port: u16) -> Self {
+        ReplicaState {
+            listening_port,
+            ack_offset: 0,
+            online: false,
+            last_ack: SystemTime::now(),
+        }
+    }
+}
+
+/// Estado de la replicación del servidor.
+///
+/// Se compone por el identificador de replicación (`replid`), la cantidad de bytes de comandos de escritura enviados
+/// a las réplicas (`offset`) y, si el servidor es una réplica, la conexión con su master.
+#[derive(Debug)]
+pub struct Replication {
+    replid: String,
+    offset: usize,
+    master: Option<MasterLink>,
+    generation: usize,
+}
+
+impl Default for Replication {
+    fn default() -> Self {
+        Replication::new()
+    }
+}
+
+impl Replication {
+    /// Crea el estado de un servidor master, con un identificador de replicación aleatorio.
+    pub fn new() -> Self {
+        Replication {
+            replid: generate_replid(),
+            offset: 0,
+            master: None,
+            generation: 0,
+        }
+    }
+
+    /// Retorna el identificador de replicación.
+    pub fn get_replid(&self) -> &str {
+        &self.replid
+    }
+
+    /// Retorna la cantidad de bytes de comandos de escritura replicados.
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Registra que se enviaron a las réplicas `bytes` bytes de comandos de escritura.
+    pub fn add_offset(&mut self, bytes: usize) {
+        self.offset += bytes;
+    }
+
+    /// Indica si el servidor es una réplica.
+    pub fn is_replica(&self) -> bool {
+        self.master.is_some()
+    }
+
+    /// Convierte al servidor en réplica del master indicado o, si es `None`, en master.
+    ///
+    /// Cierra la conexión con el master anterior. Al dejar de ser réplica se genera un nuevo identificador de
+    /// replicación, ya que los datos pueden empezar a diferir de los del master.
+    /// Devuelve el identificador de la nueva conexión, con el que el hilo de replicación informa sus eventos.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::replication::Replication;
+    ///
+    /// let mut replication = Replication::new();
+    /// let generation = replication.set_master(Some((String::from("127.0.0.1"), 6379)));
+    /// assert!(replication.is_replica());
+    /// assert!(replication.is_current_link(generation));
+    ///
+    /// replication.set_master(None);
+    /// assert!(!replication.is_replica());
+    /// assert!(!replication.is_current_link(generation));
+    /// ```
+    pub fn set_master(&mut self, master: Option<(String, u16)>) -> usize {
+        self.close_master_link();
+        self.generation += 1;
+        self.master = master.map(|(host, port)| MasterLink {
+            host,
+            port,
+            generation: self.generation,
+            status: LinkStatus::Connecting,
+            stream: None,
+            last_io: SystemTime::now(),
+        });
+        if self.master.is_none() {
+            self.replid = generate_replid();
+        }
+        self.generation
+    }
+
+    /// Indica si `generation` corresponde a la conexión actual con el master.
+    pub fn is_current_link(&self, generation: usize) -> bool {
+        self.master
+            .as_ref()
+            .is_some_and(|master| master.generation == generation)
+    }
+
+    /// Registra un evento de la conexión con el master.
+    ///
+    /// Devuelve false si el evento corresponde a una conexión anterior, en cuyo caso el hilo de replicación debe
+    /// terminar; si el evento trae el stream de la conexión, se lo cierra.
+    pub fn update_master_link(&mut self, generation: usize, event: MasterLinkEvent) -> bool {
+        let master = match self.master.as_mut() {
+            Some(master) if master.generation == generation => master,
+            _ => {
+                if let MasterLinkEvent::Sync(stream) = event {
+                    let _ = stream.shutdown(Shutdown::Both);
+                }
+                return false;
+            }
+        };
+        master.last_io = SystemTime::now();
+        match event {
+            MasterLinkEvent::Connecting => {
+                master.status = LinkStatus::Connecting;
+                master.stream = None;
+            }
+            MasterLinkEvent::Sync(stream) => {
+                master.status = LinkStatus::Sync;
+                master.stream = Some(stream);
+            }
+            MasterLinkEvent::Connected(replid, offset) => {
+                master.status = LinkStatus::Connected;
+                self.replid = replid;
+                self.offset = offset;
+            }
+            MasterLinkEvent::Offset(offset) => self.offset = offset,
+        }
+        true
+    }
+
+    /// Cierra la conexión con el master, si existe, para que termine el hilo de replicación.
+    pub fn close_master_link(&mut self) {
+        if let Some(stream) = self.master.as_mut().and_then(|master| master.stream.take()) {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+
+    /// Retorna la sección `replication` de `info`.
+    ///
+    /// `replicas` son las direcciones IP y los estados de las réplicas conectadas, y `read_only` indica si el servidor,
+    /// cuando es una réplica, rechaza los comandos de escritura de sus clientes (`replica-read-only`).
+    pub fn get_info(&self, replicas: &[(String, ReplicaState)], read_only: bool) -> String {
+        let mut info = String::from("# Replication\r\n");
+        match &self.master {
+            Some(master) => {
+                let last_io = if master.status == LinkStatus::Connected {
+                    SystemTime::now()
+                        .duration_since(master.last_io)
+                        .unwrap_or_default()
+                        .as_secs() as i64
+                } else {
+                    -1
+                };
+                info.push_str(&format!(
+                    "role:slave\r\nmaster_host:{}\r\nmaster_port:{}\r\nmaster_link_status:{}\r\nmaster_last_io_seconds_ago:{}\r\nmaster_sync_in_progress:{}\r\nslave_read_repl_offset:{}\r\nslave_repl_offset:{}\r\nslave_priority:100\r\nslave_read_only:{}\r\nreplica_announced:1\r\n",
+                    master.host,
+                    master.port,
+                    if master.status == LinkStatus::Connected { "up" } else { "down" },
+                    last_io,
+                    (master.status == LinkStatus::Sync) as u8,
+                    self.offset,
+                    self.offset,
+                    read_only as u8,
+                ));
+            }
+            None => info.push_str("role:master\r\n"),
+        }
+        let online: Vec<&(String, ReplicaState)> =
+            replicas.iter().filter(|(_, state)| state.online).collect();
+        info.push_str(&format!("connected_slaves:{}\r\n", online.len()));
+        for (index, (ip, state)) in online.iter().enumerate() {
+            info.push_str(&format!(
+                "slave{}:ip={},port={},state=online,offset={},lag={}\r\n",
+                index,
+                ip,
+                state.listening_port,
+                state.ack_offset,
+                SystemTime::now()
+                    .duration_since(state.last_ack)
+                    .unwrap_or_default()
+                    .as_secs()
+            ));
+        }
+        info.push_str(&format!(
+            "master_failover_state:no-failover\r\nmaster_replid:{}\r\nmaster_replid2:{}\r\nmaster_repl_offset:{}\r\nsecond_repl_offset:-1\r\nrepl_backlog_active:0\r\nrepl_backlog_size:1048576\r\nrepl_backlog_first_byte_offset:0\r\nrepl_backlog_histlen:0\r\n",
+            self.replid, NULL_REPLID, self.offset
+        ));
+        info
+    }
+}
+
+/// Genera un identificador de replicación aleatorio de 40 dígitos hexadecimales, como los de Redis.
+fn generate_replid() -> String {
+    (0..40).map(|_| format!("{:x}", random_index(16))).collect()
+}
+
+#[test]
+fn test_01_events_of_a_previous_link_are_discarded() {
+    let mut replication = Replication::new();
+    let old = replication.set_master(Some((String::from("127.0.0.1"), 6379)));
+    let new = replication.set_master(Some((String::from("127.0.0.1"), 6380)));
+
+    assert!(!replication.update_master_link(old, MasterLinkEvent::Offset(10)));
+    assert!(replication.update_master_link(
+        new,
+        MasterLinkEvent::Connected(String::from(NULL_REPLID), 5)
+    ));
+    assert_eq!(replication.get_replid(), NULL_REPLID);
+    assert_eq!(replication.get_offset(), 5);
+
+    let info = replication.get_info(&[], true);
+    assert!(info.contains("role:slave\r\nmaster_host:127.0.0.1\r\nmaster_port:6380\r\n"));
+    assert!(info.contains("master_link_status:up\r\n"));
+    assert!(info.contains("slave_read_only:1\r\n"));
+}
+
+#[test]
+fn test_02_master_info_lists_online_replicas() {
+    let replication = Replication::new();
+    let mut online = ReplicaState::new(6380);
+    online.online = true;
+    online.ack_offset = 42;
+    let replicas = vec![
+        (String::from("127.0.0.1"), online),
+        (String::from("127.0.0.1"), ReplicaState::new(6381)),
+    ];
+
+    let info = replication.get_info(&replicas, true);
+    assert!(info.starts_with("# Replication\r\nrole:master\r\nconnected_slaves:1\r\n"));
+    assert!(info.contains("slave0:ip=127.0.0.1,port=6380,state=online,offset=42,lag=0\r\n"));
+    assert_eq!(replication.get_replid().len(), 40);
+}
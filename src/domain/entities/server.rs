@@ -1,61 +1,88 @@
 //! Servidor Redis
 
-use super::client::Client;
+use super::acl::{Acl, AclDenial};
+use super::client::{Client, ClientFilter};
+use super::cluster::Cluster;
 use super::message::WorkerMessage;
+use super::pubsub_registry::PubSubRegistry;
+use super::replication::{ReplicaState, Replication};
+use super::tracking::{ClientTracking, TrackingOptions, TrackingTable, INVALIDATE_CHANNEL};
 use crate::domain::implementations::logger_impl::Logger;
+use crate::domain::implementations::output_queue::{OutputBufferLimit, DEFAULT_PUBSUB_LIMIT};
+use crate::services::commander::get_redacted_command;
 use crate::services::parser_service;
 use crate::services::utils::glob_pattern;
-use crate::services::utils::resp_type::RespType;
-use std::collections::HashMap;
+use crate::services::utils::resp_type::{RespType, RESP3};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Error;
 use std::process;
 use std::sync::mpsc;
 use std::sync::mpsc::Receiver;
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
-use std::usize;
-use std::{io::Error, net::SocketAddr};
 
 /// Servidor que procesa comandos de clientes Redis.
 ///
 /// El servidor se compone por los siguientes elementos:
-/// * dir: dirección donde recibe conexiones.
+/// * dir: direcciones donde recibe conexiones.
 /// * port: puerto donde recibe conexiones.
 /// * verbose: indica si debe imprimir mensajes sobre su comportamiento.
 /// * logger: estructura que escribe mensajes sobre su comportamiento en un archivo log.
-/// * clients: vector de clientes conectados.
+/// * clients: clientes conectados, indexados por su identificador.
 /// * total_connections: cantidad total de conexiones establecidas.
 /// * total_commands: cantidad total de comandos procesados.
-/// * channels: lista de canales establecidos, diferenciados por el nombre de canal.
+/// * channels: registro de los canales con suscriptores y de los canales a los que está suscrito cada cliente.
+/// * patterns: registro de los patrones a los que se suscribieron los clientes con `psubscribe`.
+/// * shard_channels: registro de los canales sharded, a los que se suscriben los clientes con `ssubscribe`. Se mantiene
+///   separado de `channels`: los mensajes publicados con `publish` no llegan a los canales sharded, ni los de `spublish` a los globales.
 /// * receiver: receptor de mensajes provenientes del cliente.
 /// * init_time: almacena el tiempo en que fue iniciado el servidor.
 /// * config_path: dirección del archivo de configuración.
+/// * pause: momento hasta el cual están suspendidos los comandos de los clientes, y si solo se suspenden los de escritura.
+/// * auth_failures: cantidad de intentos de autenticación fallidos.
+/// * acl: usuarios ACL y registro de accesos denegados.
+/// * pubsub_limit: límite de mensajes pendientes de los clientes suscritos (ver `client-output-buffer-limit`).
+/// * tracking: claves leídas y prefijos registrados por los clientes que activaron `client tracking`.
+/// * replication: estado de la replicación: identificador y offset de replicación y, si es una réplica, la conexión con su master.
+/// * cluster: nodos del cluster y slots que atiende cada uno, si el servidor está en modo cluster (ver `cluster-enabled`).
 #[derive(Debug)]
 pub struct Server {
     dir: String,
     port: String,
     verbose: String,
     logger: Logger,
-    clients: Vec<Client>,
+    clients: BTreeMap<usize, Client>,
     total_connections: usize,
     total_commands: usize,
-    channels: HashMap<String, Vec<String>>,
+    channels: PubSubRegistry,
+    patterns: PubSubRegistry,
+    shard_channels: PubSubRegistry,
     receiver: Arc<Mutex<mpsc::Receiver<WorkerMessage>>>,
     init_time: SystemTime,
     config_path: String,
+    pause: Option<(SystemTime, bool)>,
+    auth_failures: usize,
+    acl: Acl,
+    pubsub_limit: OutputBufferLimit,
+    tracking: TrackingTable,
+    replication: Replication,
+    cluster: Option<Cluster>,
 }
 
 impl Server {
     /// Crea una nueva instancia Server.
     ///
-    /// Por defecto, inicia el servidor en la dirección 127.0.0.1
+    /// `dir` son las direcciones donde el servidor recibe conexiones, separadas por espacios (ver la directiva `bind`).
     /// # Ejemplo
     /// ```
     /// use proyecto_taller_1::domain::entities::server::Server;
     /// use std::sync::{Arc, Mutex, mpsc};
     ///
+    /// let dir = "127.0.0.1".to_string();
     /// let port = "8080".to_string();
     /// let logfile = "./src/dummy_logfile.out".to_string();
     /// let verbose = "1".to_string();
@@ -63,26 +90,24 @@ impl Server {
     /// let recv = Arc::new(Mutex::new(receiver));
     /// let configfile = "./src/dummy_configfile.txt".to_string();
     ///
-    /// let server = Server::new(port, logfile, verbose, recv, configfile);
+    /// let server = Server::new(dir, port, logfile, verbose, recv, configfile);
     /// # let _ = std::fs::remove_file("./src/dummy_logfile.out");
     /// ```
     pub fn new(
+        dir: String,
         port: String,
         logfile: String,
         verb: String,
         receiver: Arc<Mutex<Receiver<WorkerMessage>>>,
         config_path: String,
     ) -> Result<Self, Error> {
-        let dir = "127.0.0.1".to_string();
-        let port = port;
         let verbose = verb;
         let receiver = receiver;
         let logger_path = &logfile;
         let logger = Logger::new(logger_path)?;
-        let channels = HashMap::new();
-        let clients = Vec::new();
+        let channels = PubSubRegistry::new();
+        let clients = BTreeMap::new();
         let init_time = SystemTime::now();
-        let config_path = config_path;
         let total_connections = 0;
         let total_commands = 0;
 
@@ -95,9 +120,18 @@ impl Server {
             total_connections,
             total_commands,
             channels,
+            patterns: PubSubRegistry::new(),
+            shard_channels: PubSubRegistry::new(),
             receiver,
             init_time,
             config_path,
+            pause: None,
+            auth_failures: 0,
+            acl: Acl::new(),
+            pubsub_limit: DEFAULT_PUBSUB_LIMIT,
+            tracking: TrackingTable::new(),
+            replication: Replication::new(),
+            cluster: None,
         })
     }
 
@@ -114,15 +148,48 @@ impl Server {
     /// * Subscribe: suscribe al cliente a un canal dado.
     /// * Unsubscribe: desuscribe al cliente del canal dado.
     /// * UnsubscribeAll: desuscribe al cliente de todos los canales a los que se haya suscrito.
+    /// * PSubscribe, PUnsubscribe y PUnsubscribeAll: suscriben o desuscriben al cliente de patrones de canales.
+    /// * SSubscribe, SUnsubscribe y SUnsubscribeAll: suscriben o desuscriben al cliente de canales sharded.
+    /// * SPublish: publica un mensaje en un canal sharded.
     /// * Publish: publica un mensaje en los canales especificados.
     /// * Channels: lista canales activos.
     /// * Numsub: lista cantidad de suscriptores por canal.
+    /// * Numpat: devuelve la cantidad de patrones con suscriptores.
+    /// * ShardChannels y ShardNumsub: listan los canales sharded activos o la cantidad de suscriptores por canal sharded.
+    /// * SetProtocol: actualiza la versión del protocolo RESP del cliente.
+    /// * SetClientName: actualiza el nombre de la conexión del cliente.
+    /// * SetClientLibName y SetClientLibVersion: actualizan la librería que utiliza el cliente.
+    /// * ClientId, ClientName y ClientInfo: devuelven el identificador, el nombre o la información de la conexión del cliente.
+    /// * ResetClient: desuscribe al cliente de todos los canales y vuelve su conexión al estado inicial.
+    /// * ClientList y ClientKill: listan o cierran las conexiones de los clientes que cumplen con un filtro.
+    /// * SetClientNoEvict: actualiza el modo "no-evict" del cliente.
+    /// * ClientTracking, ClientCaching y ClientTrackingInfo: activan, configuran o consultan el seguimiento de claves del cliente.
+    /// * TrackKeys: recuerda las claves que lee el cliente, si las sigue.
+    /// * InvalidateKeys: envía los mensajes de invalidación a los clientes que siguen las claves modificadas.
+    /// * InfoReplication: devuelve la información de replicación.
+    /// * ReplicaOf, MasterLink e IsReplica: cambian, actualizan o consultan la conexión con el master.
+    /// * ReplicaListeningPort, AddReplica y ReplicaAck: registran a los clientes que son réplicas y su estado.
+    /// * Propagate: envía un comando de escritura a las réplicas.
+    /// * EnableCluster: activa el modo cluster con el estado indicado.
+    /// * ClusterRoute y Cluster: consultan cómo se atiende un slot o ejecutan un subcomando de `cluster`.
+    /// * ClusterBusMessage y ClusterPingMessages: procesan los mensajes que se reciben o se envían por el bus del cluster.
+    /// * CloseIdleClients: cierra las conexiones inactivas.
+    /// * CheckOutputBuffers: actualiza el límite `client-output-buffer-limit` y cierra las conexiones de los suscriptores que lo superan.
+    /// * PauseClients, UnpauseClients y CheckPause: suspenden, reanudan o consultan la suspensión de los comandos de los clientes.
+    /// * AclAuthenticate: autentica al cliente, o registra y cuenta el intento fallido.
+    /// * AclNoPass: indica si un usuario puede autenticarse sin contraseña.
+    /// * AclCheck: verifica los permisos del usuario del cliente y registra los accesos denegados.
+    /// * AclSetUser, AclGetUser, AclDelUser, AclList y AclUsers: administran los usuarios ACL.
+    /// * AclLog y AclLogReset: devuelven o eliminan las entradas del registro de accesos denegados.
+    /// * CloseAllClients: cierra las conexiones de todos los clientes y termina la suspensión de sus comandos.
+    /// * Shutdown: escribe los mensajes pendientes del log y termina de escuchar mensajes.
     /// # Ejemplo
     /// ```
     /// # use proyecto_taller_1::domain::entities::server::Server;
     /// # use std::sync::{Arc, Mutex, mpsc};
     /// use std::thread;
     ///
+    /// # let dir = "127.0.0.1".to_string();
     /// # let port = "8080".to_string();
     /// # let logfile = "./src/dummy_logfile.out".to_string();
     /// # let verbose = "1".to_string();
@@ -130,7 +197,7 @@ impl Server {
     /// # let recv = Arc::new(Mutex::new(receiver));
     /// # let configfile = "./src/dummy_configfile.txt".to_string();
     ///
-    /// let mut server = Server::new(port, logfile, verbose, recv, configfile).unwrap();
+    /// let mut server = Server::new(dir, port, logfile, verbose, recv, configfile).unwrap();
     /// thread::spawn(move || server.listen());
     /// # let _ = std::fs::remove_file("./src/dummy_logfile.out");
     /// ```
@@ -139,21 +206,36 @@ impl Server {
             let msg = self.receiver.lock().unwrap().recv().unwrap();
             match msg {
                 WorkerMessage::Log(log_msg) => self.log(log_msg),
-                WorkerMessage::SetMonitor(addrs) => {
+                WorkerMessage::SetMonitor(client_id) => {
                     self.log("Setting client to monitor state".to_string());
                     self.verbose("Setting client to monitor state".to_string());
-                    self.set_client_to_monitor_state(addrs);
+                    self.set_client_to_monitor_state(client_id);
                 }
-                WorkerMessage::AddClient(client) => {
-                    self.clients.push(client);
+                WorkerMessage::AddClient(mut client, sender) => {
                     self.total_connections += 1;
+                    client.set_id(self.total_connections);
+                    self.clients.insert(self.total_connections, *client);
+                    sender.send(self.total_connections).unwrap();
                 }
-                WorkerMessage::CloseClient(addrs) => {
-                    self.remove_client(addrs);
+                WorkerMessage::CloseClient(client_id) => {
+                    self.remove_client(client_id);
                 }
-                WorkerMessage::NewOperation(operation, addrs, ps_sender) => {
-                    self.check_monitor(operation, addrs);
-                    self.check_pubsub(addrs, ps_sender);
+                WorkerMessage::NewOperation(operation, client_id, ps_sender) => {
+                    if let (Some(client), RespType::RArray(array)) =
+                        (self.get_client_mut(client_id), &operation)
+                    {
+                        if let Some(command) = array.first() {
+                            client.set_last_command(command.to_string().to_lowercase());
+                        }
+                        if let Some(tracking) = client.get_tracking_mut() {
+                            let is_caching_command = array.len() > 1
+                                && array[0].to_string().eq_ignore_ascii_case("client")
+                                && array[1].to_string().eq_ignore_ascii_case("caching");
+                            tracking.begin_command(is_caching_command);
+                        }
+                    }
+                    self.check_monitor(operation, client_id);
+                    self.check_pubsub(client_id, ps_sender);
                     self.total_commands += 1;
                 }
                 WorkerMessage::InfoServer(sender) => {
@@ -171,45 +253,122 @@ impl Server {
                     self.verbose("Retrieving stats info".to_string());
                     sender.send(self.get_stats_info()).unwrap();
                 }
+                WorkerMessage::InfoReplication(read_only, sender) => {
+                    sender.send(self.get_replication_info(read_only)).unwrap();
+                }
                 WorkerMessage::Verb(verbose_txt) => {
                     self.verbose(verbose_txt);
                 }
-                WorkerMessage::Subscribe(channel, addrs, message_sender) => {
+                WorkerMessage::Subscribe(channel, client_id, message_sender) => {
                     self.log(format!(
                         "Subscribing client {} to channel {}",
-                        &addrs.to_string(),
-                        &channel
+                        &client_id, &channel
                     ));
                     self.verbose(format!(
                         "Subscribing client {} to channel {}",
-                        &addrs.to_string(),
-                        &channel
+                        &client_id, &channel
                     ));
-                    self.subscribe_to_channel(channel, addrs, message_sender);
+                    self.subscribe_to_channel(channel, client_id, message_sender);
                 }
-                WorkerMessage::Unsubscribe(channel, addrs, message_sender) => {
+                WorkerMessage::Unsubscribe(channel, client_id, message_sender) => {
                     self.log(format!(
-                        "Subscribing client {} from channel {}",
-                        &addrs.to_string(),
-                        &channel
+                        "Unsubscribing client {} from channel {}",
+                        &client_id, &channel
                     ));
                     self.verbose(format!(
-                        "Subscribing client {} from channel {}",
-                        &addrs.to_string(),
-                        &channel
+                        "Unsubscribing client {} from channel {}",
+                        &client_id, &channel
+                    ));
+                    self.unsubscribe(channel, client_id, message_sender);
+                }
+                WorkerMessage::UnsubscribeAll(client_id, message_sender) => {
+                    self.log(format!(
+                        "Unsubscribing client {} from all channels",
+                        &client_id
+                    ));
+                    self.verbose(format!(
+                        "Unsubscribing client {} from all channels",
+                        &client_id
+                    ));
+                    self.unsubscribe_to_all_channels(client_id, message_sender);
+                }
+                WorkerMessage::PSubscribe(pattern, client_id, message_sender) => {
+                    self.log(format!(
+                        "Subscribing client {} to pattern {}",
+                        &client_id, &pattern
+                    ));
+                    self.verbose(format!(
+                        "Subscribing client {} to pattern {}",
+                        &client_id, &pattern
+                    ));
+                    self.subscribe_to_pattern(pattern, client_id, message_sender);
+                }
+                WorkerMessage::PUnsubscribe(pattern, client_id, message_sender) => {
+                    self.log(format!(
+                        "Unsubscribing client {} from pattern {}",
+                        &client_id, &pattern
                     ));
-                    self.unsubscribe(channel, addrs, message_sender);
+                    self.verbose(format!(
+                        "Unsubscribing client {} from pattern {}",
+                        &client_id, &pattern
+                    ));
+                    self.unsubscribe_from_pattern(pattern, client_id, message_sender);
+                }
+                WorkerMessage::PUnsubscribeAll(client_id, message_sender) => {
+                    self.log(format!(
+                        "Unsubscribing client {} from all patterns",
+                        &client_id
+                    ));
+                    self.verbose(format!(
+                        "Unsubscribing client {} from all patterns",
+                        &client_id
+                    ));
+                    self.unsubscribe_from_all_patterns(client_id, message_sender);
                 }
-                WorkerMessage::UnsubscribeAll(addrs, message_sender) => {
+                WorkerMessage::SSubscribe(channel, client_id, message_sender) => {
                     self.log(format!(
-                        "Subscribing client {} from all channels",
-                        &addrs.to_string()
+                        "Subscribing client {} to shard channel {}",
+                        &client_id, &channel
                     ));
                     self.verbose(format!(
-                        "Subscribing client {} from all channels",
-                        &addrs.to_string()
+                        "Subscribing client {} to shard channel {}",
+                        &client_id, &channel
                     ));
-                    self.unsubscribe_to_all_channels(addrs, message_sender);
+                    self.subscribe_to_shard_channel(channel, client_id, message_sender);
+                }
+                WorkerMessage::SUnsubscribe(channel, client_id, message_sender) => {
+                    self.log(format!(
+                        "Unsubscribing client {} from shard channel {}",
+                        &client_id, &channel
+                    ));
+                    self.verbose(format!(
+                        "Unsubscribing client {} from shard channel {}",
+                        &client_id, &channel
+                    ));
+                    self.unsubscribe_from_shard_channel(channel, client_id, message_sender);
+                }
+                WorkerMessage::SUnsubscribeAll(client_id, message_sender) => {
+                    self.log(format!(
+                        "Unsubscribing client {} from all shard channels",
+                        &client_id
+                    ));
+                    self.verbose(format!(
+                        "Unsubscribing client {} from all shard channels",
+                        &client_id
+                    ));
+                    self.unsubscribe_from_all_shard_channels(client_id, message_sender);
+                }
+                WorkerMessage::SPublish(channel, response_sender, message) => {
+                    self.log(format!(
+                        "Publishing message \"{}\" to shard channel {}",
+                        &message, &channel
+                    ));
+                    self.verbose(format!(
+                        "Publishing message \"{}\" to shard channel {}",
+                        &message, &channel
+                    ));
+                    let messages_sent = self.send_message_to_shard_channel(channel, message);
+                    response_sender.send(messages_sent).unwrap();
                 }
                 WorkerMessage::Publish(channel, response_sender, message) => {
                     self.log(format!(
@@ -225,6 +384,11 @@ impl Server {
                     let messages_sent = self.send_message_to_channel(channel, message);
                     response_sender.send(messages_sent).unwrap();
                 }
+                WorkerMessage::KeyspaceNotifications(notifications) => {
+                    for notification in notifications {
+                        self.send_message_to_channel(notification.channel, notification.message);
+                    }
+                }
                 WorkerMessage::Channels(response_sender, pattern) => {
                     if let Some(pattern) = pattern {
                         self.log(format!(
@@ -235,17 +399,312 @@ impl Server {
                             "Searching channels with pattern {}",
                             &pattern.to_string()
                         ));
-                        self.list_active_channels_by_pattern(response_sender, pattern);
+                        list_active_channels_by_pattern(&self.channels, response_sender, pattern);
                     } else {
                         self.log(String::from("Listing all channels"));
                         self.verbose(String::from("Listing all channels"));
-                        self.list_active_channels(response_sender);
+                        list_active_channels(&self.channels, response_sender);
                     }
                 }
                 WorkerMessage::Numsub(channels, sender) => {
                     self.log("Searching number of subscribers".to_string());
                     self.verbose("Searching number of subscribers".to_string());
-                    self.list_number_of_subscribers(channels, sender);
+                    list_number_of_subscribers(&self.channels, channels, sender);
+                }
+                WorkerMessage::Numpat(sender) => {
+                    sender.send(self.patterns.len()).unwrap();
+                }
+                WorkerMessage::ShardChannels(response_sender, pattern) => {
+                    self.log(String::from("Listing shard channels"));
+                    self.verbose(String::from("Listing shard channels"));
+                    match pattern {
+                        Some(pattern) => list_active_channels_by_pattern(
+                            &self.shard_channels,
+                            response_sender,
+                            pattern,
+                        ),
+                        None => list_active_channels(&self.shard_channels, response_sender),
+                    }
+                }
+                WorkerMessage::ShardNumsub(channels, sender) => {
+                    self.log("Searching number of shard subscribers".to_string());
+                    self.verbose("Searching number of shard subscribers".to_string());
+                    list_number_of_subscribers(&self.shard_channels, channels, sender);
+                }
+                WorkerMessage::SetProtocol(client_id, protocol) => {
+                    if let Some(client) = self.get_client_mut(client_id) {
+                        client.set_protocol(protocol);
+                    }
+                }
+                WorkerMessage::SetClientName(client_id, name) => {
+                    if let Some(client) = self.get_client_mut(client_id) {
+                        client.set_name(name);
+                    }
+                }
+                WorkerMessage::SetClientLibName(client_id, lib_name) => {
+                    if let Some(client) = self.get_client_mut(client_id) {
+                        client.set_lib_name(lib_name);
+                    }
+                }
+                WorkerMessage::SetClientLibVersion(client_id, lib_version) => {
+                    if let Some(client) = self.get_client_mut(client_id) {
+                        client.set_lib_version(lib_version);
+                    }
+                }
+                WorkerMessage::ClientName(client_id, sender) => {
+                    if let Some(client) = self.get_client_mut(client_id) {
+                        sender.send(client.get_name().cloned()).unwrap();
+                    }
+                }
+                WorkerMessage::ClientInfo(client_id, sender) => {
+                    let subscriptions = self.get_listening_channels(client_id);
+                    let pattern_subscriptions = self.get_listening_patterns(client_id);
+                    let shard_subscriptions = self.shard_channels.subscription_count(client_id);
+                    if let Some(client) = self.get_client_mut(client_id) {
+                        sender
+                            .send(client.get_info(
+                                subscriptions,
+                                pattern_subscriptions,
+                                shard_subscriptions,
+                            ))
+                            .unwrap();
+                    }
+                }
+                WorkerMessage::ResetClient(client_id) => {
+                    self.log(format!("Resetting client {}", &client_id));
+                    self.verbose(format!("Resetting client {}", &client_id));
+                    self.reset_client(client_id);
+                }
+                WorkerMessage::ClientList(filter, sender) => {
+                    sender.send(self.list_clients(&filter)).unwrap();
+                }
+                WorkerMessage::ClientKill(filter, sender) => {
+                    let killed = self.kill_clients(&filter);
+                    self.log(format!("Killed {} clients", killed));
+                    self.verbose(format!("Killed {} clients", killed));
+                    sender.send(killed).unwrap();
+                }
+                WorkerMessage::SetClientNoEvict(client_id, no_evict) => {
+                    if let Some(client) = self.get_client_mut(client_id) {
+                        client.set_no_evict(no_evict);
+                    }
+                }
+                WorkerMessage::ClientTracking(client_id, options, sender) => {
+                    sender
+                        .send(self.set_client_tracking(client_id, options))
+                        .unwrap();
+                }
+                WorkerMessage::ClientCaching(client_id, caching, sender) => {
+                    let result = match self
+                        .get_client_mut(client_id)
+                        .and_then(Client::get_tracking_mut)
+                    {
+                        Some(tracking) => tracking.set_caching(caching),
+                        None => Err(String::from("ERR CLIENT CACHING can be called only when the client is in tracking mode with OPTIN or OPTOUT mode enabled")),
+                    };
+                    sender.send(result).unwrap();
+                }
+                WorkerMessage::ClientTrackingInfo(client_id, sender) => {
+                    let tracking = self
+                        .clients
+                        .get(&client_id)
+                        .and_then(Client::get_tracking)
+                        .cloned();
+                    sender.send(tracking).unwrap();
+                }
+                WorkerMessage::TrackKeys(client_id, keys) => {
+                    if self
+                        .clients
+                        .get(&client_id)
+                        .and_then(Client::get_tracking)
+                        .is_some_and(ClientTracking::tracks_command_keys)
+                    {
+                        for key in keys {
+                            self.tracking.track_key(&key, client_id);
+                        }
+                    }
+                }
+                WorkerMessage::InvalidateKeys(keys, origin) => {
+                    self.invalidate_keys(keys, origin);
+                }
+                WorkerMessage::ReplicaOf(master, sender) => {
+                    match &master {
+                        Some((host, port)) => {
+                            self.log(format!("Connecting to master {}:{}", host, port));
+                            self.verbose(format!("Connecting to master {}:{}", host, port));
+                        }
+                        None => {
+                            self.log("Master mode enabled".to_string());
+                            self.verbose("Master mode enabled".to_string());
+                        }
+                    }
+                    sender.send(self.replication.set_master(master)).unwrap();
+                }
+                WorkerMessage::MasterLink(generation, event, sender) => {
+                    let current = self.replication.update_master_link(generation, event);
+                    let _ = sender.send(current);
+                }
+                WorkerMessage::IsReplica(sender) => {
+                    sender.send(self.replication.is_replica()).unwrap();
+                }
+                WorkerMessage::ReplicaListeningPort(client_id, port) => {
+                    if let Some(client) = self.get_client_mut(client_id) {
+                        match client.get_replica_mut() {
+                            Some(replica) => replica.listening_port = port,
+                            None => client.set_replica(Some(ReplicaState::new(port))),
+                        }
+                    }
+                }
+                WorkerMessage::AddReplica(client_id, snapshot, sender) => {
+                    self.add_replica(client_id, snapshot);
+                    sender.send(()).unwrap();
+                }
+                WorkerMessage::ReplicaAck(client_id, offset) => {
+                    if let Some(replica) = self
+                        .get_client_mut(client_id)
+                        .and_then(Client::get_replica_mut)
+                    {
+                        replica.ack_offset = offset;
+                        replica.last_ack = SystemTime::now();
+                    }
+                }
+                WorkerMessage::Propagate(command) => {
+                    self.propagate(command);
+                }
+                WorkerMessage::EnableCluster(cluster) => {
+                    self.log(format!("Cluster node {}", cluster.get_myself_id()));
+                    self.cluster = Some(*cluster);
+                }
+                WorkerMessage::ClusterRoute(slot, sender) => {
+                    let _ = sender.send(self.cluster.as_ref().map(|cluster| cluster.route(slot)));
+                }
+                WorkerMessage::Cluster(request, sender) => {
+                    let reply = match self.cluster.as_mut() {
+                        Some(cluster) => cluster.execute(request),
+                        None => Err(String::from(
+                            "ERR This instance has cluster support disabled",
+                        )),
+                    };
+                    let _ = sender.send(reply);
+                }
+                WorkerMessage::ClusterBusMessage(message, sender) => {
+                    let reply = self
+                        .cluster
+                        .as_mut()
+                        .and_then(|cluster| cluster.process_message(*message));
+                    let _ = sender.send(reply);
+                }
+                WorkerMessage::ClusterPingMessages(sender) => {
+                    let messages = self
+                        .cluster
+                        .as_mut()
+                        .map(Cluster::get_ping_messages)
+                        .unwrap_or_default();
+                    let _ = sender.send(messages);
+                }
+                WorkerMessage::CloseIdleClients(timeout) => {
+                    self.close_idle_clients(timeout);
+                }
+                WorkerMessage::CheckOutputBuffers(limit) => {
+                    self.pubsub_limit = limit;
+                    let ids: Vec<usize> = self.clients.keys().copied().collect();
+                    self.close_clients_over_output_limit(&ids);
+                }
+                WorkerMessage::PauseClients(duration, write_only) => {
+                    self.log(format!("Pausing clients for {:?}", duration));
+                    self.verbose(format!("Pausing clients for {:?}", duration));
+                    self.pause = Some((SystemTime::now() + duration, write_only));
+                }
+                WorkerMessage::UnpauseClients => {
+                    self.log("Unpausing clients".to_string());
+                    self.verbose("Unpausing clients".to_string());
+                    self.pause = None;
+                }
+                WorkerMessage::CheckPause(is_write, sender) => {
+                    sender.send(self.get_pause_remaining(is_write)).unwrap();
+                }
+                WorkerMessage::AclAuthenticate(client_id, username, password, sender) => {
+                    let authenticated = self.acl.authenticate(&username, &password);
+                    if authenticated {
+                        if let Some(client) = self.get_client_mut(client_id) {
+                            client.set_user(username);
+                        }
+                    } else {
+                        self.auth_failures += 1;
+                        self.log(format!(
+                            "Failed authentication attempt from client {}",
+                            client_id
+                        ));
+                        self.verbose(format!(
+                            "Failed authentication attempt from client {}",
+                            client_id
+                        ));
+                        self.add_acl_log_entry(&AclDenial::Auth, &username, client_id);
+                    }
+                    sender.send(authenticated).unwrap();
+                }
+                WorkerMessage::AclNoPass(username, sender) => {
+                    sender.send(self.acl.is_nopass(&username)).unwrap();
+                }
+                WorkerMessage::AclCheck(client_id, username, cmd, sender) => {
+                    let error = match self.acl.check_permissions(&username, &cmd) {
+                        Ok(()) => None,
+                        Err(denial) => {
+                            self.add_acl_log_entry(&denial, &username, client_id);
+                            Some(denial.get_error(&username))
+                        }
+                    };
+                    sender.send(error).unwrap();
+                }
+                WorkerMessage::AclSetUser(username, rules, sender) => {
+                    sender.send(self.acl.set_user(&username, &rules)).unwrap();
+                }
+                WorkerMessage::AclGetUser(username, sender) => {
+                    sender.send(self.acl.get_user(&username).cloned()).unwrap();
+                }
+                WorkerMessage::AclDelUser(usernames, sender) => {
+                    let mut deleted = 0;
+                    for username in usernames {
+                        if self.acl.del_user(&username) {
+                            deleted += 1;
+                            self.kill_clients(&ClientFilter {
+                                user: Some(username),
+                                ..ClientFilter::default()
+                            });
+                        }
+                    }
+                    sender.send(deleted).unwrap();
+                }
+                WorkerMessage::AclList(sender) => {
+                    sender.send(self.acl.list()).unwrap();
+                }
+                WorkerMessage::AclUsers(sender) => {
+                    sender.send(self.acl.get_users()).unwrap();
+                }
+                WorkerMessage::AclLog(count, sender) => {
+                    sender.send(self.acl.get_log(count)).unwrap();
+                }
+                WorkerMessage::AclLogReset => {
+                    self.acl.reset_log();
+                }
+                WorkerMessage::CloseAllClients(sender) => {
+                    // los clientes suspendidos dejan de esperar para que sus workers puedan terminar
+                    self.pause = None;
+                    let ids: Vec<usize> = self.clients.keys().copied().collect();
+                    let closed = self.close_clients(&ids);
+                    self.log(format!("Closed {} clients", closed));
+                    self.verbose(format!("Closed {} clients", closed));
+                    sender.send(closed).unwrap();
+                }
+                WorkerMessage::Shutdown(sender) => {
+                    self.replication.close_master_link();
+                    self.log("Server is now ready to exit".to_string());
+                    self.verbose("Server is now ready to exit".to_string());
+                    if self.logger.flush().is_err() {
+                        println!("Could not flush log file.");
+                    }
+                    let _ = sender.send(());
+                    return;
                 }
             }
         }
@@ -257,6 +716,7 @@ impl Server {
     /// # use proyecto_taller_1::domain::entities::server::Server;
     /// # use std::sync::{Arc, Mutex, mpsc};
     ///
+    /// # let dir = "127.0.0.1".to_string();
     /// # let port = "8080".to_string();
     /// # let logfile = "./src/dummy_logfile.out".to_string();
     /// # let verbose = "1".to_string();
@@ -264,7 +724,7 @@ impl Server {
     /// # let recv = Arc::new(Mutex::new(receiver));
     /// # let configfile = "./src/dummy_configfile.txt".to_string();
     ///
-    /// let mut server = Server::new(port, logfile, verbose, recv, configfile).unwrap();
+    /// let mut server = Server::new(dir, port, logfile, verbose, recv, configfile).unwrap();
     /// assert_eq!(server.get_port(), &String::from("8080"));
     /// # let _ = std::fs::remove_file("./src/dummy_logfile.out");
     /// ```
@@ -272,12 +732,13 @@ impl Server {
         &self.port
     }
 
-    /// Retorna la dirección IP del server.
+    /// Retorna las direcciones IP donde el server recibe conexiones.
     /// # Ejemplo
     /// ```
     /// # use proyecto_taller_1::domain::entities::server::Server;
     /// # use std::sync::{Arc, Mutex, mpsc};
     ///
+    /// # let dir = "127.0.0.1".to_string();
     /// # let port = "8080".to_string();
     /// # let logfile = "./src/dummy_logfile.out".to_string();
     /// # let verbose = "1".to_string();
@@ -285,7 +746,7 @@ impl Server {
     /// # let recv = Arc::new(Mutex::new(receiver));
     /// # let configfile = "./src/dummy_configfile.txt".to_string();
     ///
-    /// let mut server = Server::new(port, logfile, verbose, recv, configfile).unwrap();
+    /// let mut server = Server::new(dir, port, logfile, verbose, recv, configfile).unwrap();
     /// assert_eq!(server.get_dir(), &String::from("127.0.0.1"));
     /// # let _ = std::fs::remove_file("./src/dummy_logfile.out");
     /// ```
@@ -302,6 +763,7 @@ impl Server {
     /// # use proyecto_taller_1::domain::entities::server::Server;
     /// # use std::sync::{Arc, Mutex, mpsc};
     ///
+    /// # let dir = "127.0.0.1".to_string();
     /// # let port = "8080".to_string();
     /// # let logfile = "./src/dummy_logfile.out".to_string();
     /// # let verbose = "1".to_string();
@@ -309,7 +771,7 @@ impl Server {
     /// # let recv = Arc::new(Mutex::new(receiver));
     /// # let configfile = "./src/dummy_configfile.txt".to_string();
     ///
-    /// let mut server = Server::new(port, logfile, verbose, recv, configfile).unwrap();
+    /// let mut server = Server::new(dir, port, logfile, verbose, recv, configfile).unwrap();
     /// assert_eq!(server.get_verbose(), &String::from("1"));
     /// # let _ = std::fs::remove_file("./src/dummy_logfile.out");
     /// ```
@@ -323,6 +785,7 @@ impl Server {
     /// # use proyecto_taller_1::domain::entities::server::Server;
     /// # use std::sync::{Arc, Mutex, mpsc};
     ///
+    /// # let dir = "127.0.0.1".to_string();
     /// # let port = "8080".to_string();
     /// # let logfile = "./src/dummy_logfile.out".to_string();
     /// # let verbose = "1".to_string();
@@ -330,7 +793,7 @@ impl Server {
     /// # let recv = Arc::new(Mutex::new(receiver));
     /// # let configfile = "./src/dummy_configfile.txt".to_string();
     ///
-    /// let mut server = Server::new(port, logfile, verbose, recv, configfile).unwrap();
+    /// let mut server = Server::new(dir, port, logfile, verbose, recv, configfile).unwrap();
     /// server.log(String::from("random log message"));
     /// # let _ = std::fs::remove_file("./src/dummy_logfile.out");
     /// ```
@@ -348,6 +811,7 @@ impl Server {
     /// # use proyecto_taller_1::domain::entities::server::Server;
     /// # use std::sync::{Arc, Mutex, mpsc};
     ///
+    /// # let dir = "127.0.0.1".to_string();
     /// # let port = "8080".to_string();
     /// # let logfile = "./src/dummy_logfile.out".to_string();
     /// # let verbose = "1".to_string();
@@ -355,7 +819,7 @@ impl Server {
     /// # let recv = Arc::new(Mutex::new(receiver));
     /// # let configfile = "./src/dummy_configfile.txt".to_string();
     ///
-    /// let mut server = Server::new(port, logfile, verbose, recv, configfile).unwrap();
+    /// let mut server = Server::new(dir, port, logfile, verbose, recv, configfile).unwrap();
     /// server.verbose(String::from("random verbose message"));
     /// # let _ = std::fs::remove_file("./src/dummy_logfile.out");
     /// ```
@@ -393,6 +857,7 @@ impl Server {
     /// # use proyecto_taller_1::domain::entities::server::Server;
     /// # use std::sync::{Arc, Mutex, mpsc};
     ///
+    /// # let dir = "127.0.0.1".to_string();
     /// # let port = "8080".to_string();
     /// # let logfile = "./src/dummy_logfile.out".to_string();
     /// # let verbose = "1".to_string();
@@ -400,12 +865,12 @@ impl Server {
     /// # let recv = Arc::new(Mutex::new(receiver));
     /// # let configfile = "./src/dummy_configfile.txt".to_string();
     ///
-    /// let mut server = Server::new(port, logfile, verbose, recv, configfile).unwrap();
+    /// let mut server = Server::new(dir, port, logfile, verbose, recv, configfile).unwrap();
     /// server.get_server_info();
     /// # let _ = std::fs::remove_file("./src/dummy_logfile.out");
     /// ```
     pub fn get_server_info(&self) -> String {
-        let info = format!("# Server\r\nredis_version:6.2.3\r\nredis_git_sha1:00000000\r\nredis_git_dirty:0\r\nredis_build_id:ea3be5cbc55dfd19\r\nredis_mode:standalone\r\nos:Linux 5.4.0-1030-aws x86_64\r\narch_bits:64\r\nmultiplexing_api:epoll\r\natomicvar_api:c11-builtin\r\ngcc_version:9.3.0\r\nprocess_id:{}\r\nprocess_supervised:no\r\nrun_id:eba2478b32af796180fdf364700b411432cb6932\r\ntcp_port:{}\r\nserver_time_usec:{}\r\nuptime_in_seconds:{}\r\nuptime_in_days:{}\r\nhz:10\r\nconfigured_hz:10\r\nlru_clock:15868238\r\nexecutable:/usr/local/bin/redis-server\r\nconfig_file:{}\r\n", process::id(), self.get_port(), SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_micros(), SystemTime::now().duration_since(self.init_time).unwrap().as_secs(), SystemTime::now().duration_since(self.init_time).unwrap().as_secs()/86400, self.config_path);
+        let info = format!("# Server\r\nredis_version:6.2.3\r\nredis_git_sha1:00000000\r\nredis_git_dirty:0\r\nredis_build_id:ea3be5cbc55dfd19\r\nredis_mode:{}\r\nos:Linux 5.4.0-1030-aws x86_64\r\narch_bits:64\r\nmultiplexing_api:epoll\r\natomicvar_api:c11-builtin\r\ngcc_version:9.3.0\r\nprocess_id:{}\r\nprocess_supervised:no\r\nrun_id:eba2478b32af796180fdf364700b411432cb6932\r\ntcp_port:{}\r\nserver_time_usec:{}\r\nuptime_in_seconds:{}\r\nuptime_in_days:{}\r\nhz:10\r\nconfigured_hz:10\r\nlru_clock:15868238\r\nexecutable:/usr/local/bin/redis-server\r\nconfig_file:{}\r\n", if self.cluster.is_some() { "cluster" } else { "standalone" }, process::id(), self.get_port(), SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_micros(), SystemTime::now().duration_since(self.init_time).unwrap().as_secs(), SystemTime::now().duration_since(self.init_time).unwrap().as_secs()/86400, self.config_path);
         info
     }
 
@@ -426,6 +891,7 @@ impl Server {
     /// # use proyecto_taller_1::domain::entities::server::Server;
     /// # use std::sync::{Arc, Mutex, mpsc};
     ///
+    /// # let dir = "127.0.0.1".to_string();
     /// # let port = "8080".to_string();
     /// # let logfile = "./src/dummy_logfile.out".to_string();
     /// # let verbose = "1".to_string();
@@ -433,7 +899,7 @@ impl Server {
     /// # let recv = Arc::new(Mutex::new(receiver));
     /// # let configfile = "./src/dummy_configfile.txt".to_string();
     ///
-    /// let mut server = Server::new(port, logfile, verbose, recv, configfile).unwrap();
+    /// let mut server = Server::new(dir, port, logfile, verbose, recv, configfile).unwrap();
     /// assert_eq!(server.get_clients_info(), String::from("# Clients\r\nconnected_clients:0\r\ncluster_connections:0\r\nmaxclients:10000\r\nclient_longest_output_list:24\r\nclient_biggest_input_buf:50\r\nblocked_clients:0\r\ntracking_clients:0\r\nclients_in_timeout_table:0\r\nio_threads_active:0\r\n"));
     /// # let _ = std::fs::remove_file("./src/dummy_logfile.out");
     /// ```
@@ -454,14 +920,18 @@ impl Server {
     /// * keyspace_hits: Cantidad de búsquedas de claves exitosas
     /// * keyspace_misses: Cantidad de búsquedas de claves fallidas
     /// * pubsub_channels: Cantidad de canales pub/sub con suscripciones
+    /// * pubsub_patterns: Cantidad de patrones pub/sub con suscripciones
+    /// * pubsubshard_channels: Cantidad de canales pub/sub sharded con suscripciones
     /// * total_error_replies: Cantidad total de errores emitidos como respuesta
     /// * total_reads_processed: Cantidad de lecturas procesadas
     /// * total_writes_processed: Cantidad de escrituras procesadas
+    /// * acl_access_denied_auth: Cantidad de intentos de autenticación fallidos
     /// # Ejemplo
     /// ```
     /// # use proyecto_taller_1::domain::entities::server::Server;
     /// # use std::sync::{Arc, Mutex, mpsc};
     ///
+    /// # let dir = "127.0.0.1".to_string();
     /// # let port = "8080".to_string();
     /// # let logfile = "./src/dummy_logfile.out".to_string();
     /// # let verbose = "1".to_string();
@@ -469,12 +939,12 @@ impl Server {
     /// # let recv = Arc::new(Mutex::new(receiver));
     /// # let configfile = "./src/dummy_configfile.txt".to_string();
     ///
-    /// let mut server = Server::new(port, logfile, verbose, recv, configfile).unwrap();
-    /// assert_eq!(server.get_stats_info(), String::from("# Stats\r\ntotal_connections_received:0\r\ntotal_commands_processed:0\r\ntotal_net_input_bytes:6656\r\ntotal_net_output_bytes:8192\r\nrejected_connections:0\r\nexpired_keys:0\r\nkeyspace_hits:3\r\nkeyspace_misses:2\r\npubsub_channels:0\r\ntotal_error_replies:2\r\ntotal_reads_processed:10\r\ntotal_writes_processed:5\r\n"));
+    /// let mut server = Server::new(dir, port, logfile, verbose, recv, configfile).unwrap();
+    /// assert_eq!(server.get_stats_info(), String::from("# Stats\r\ntotal_connections_received:0\r\ntotal_commands_processed:0\r\ntotal_net_input_bytes:6656\r\ntotal_net_output_bytes:8192\r\nrejected_connections:0\r\nexpired_keys:0\r\nkeyspace_hits:3\r\nkeyspace_misses:2\r\npubsub_channels:0\r\npubsub_patterns:0\r\npubsubshard_channels:0\r\ntotal_error_replies:2\r\ntotal_reads_processed:10\r\ntotal_writes_processed:5\r\nacl_access_denied_auth:0\r\n"));
     /// # let _ = std::fs::remove_file("./src/dummy_logfile.out");
     /// ```
     pub fn get_stats_info(&self) -> String {
-        let info = format!("# Stats\r\ntotal_connections_received:{}\r\ntotal_commands_processed:{}\r\ntotal_net_input_bytes:6656\r\ntotal_net_output_bytes:8192\r\nrejected_connections:0\r\nexpired_keys:0\r\nkeyspace_hits:3\r\nkeyspace_misses:2\r\npubsub_channels:{}\r\ntotal_error_replies:2\r\ntotal_reads_processed:10\r\ntotal_writes_processed:5\r\n", self.total_connections, self.total_commands, self.channels.len());
+        let info = format!("# Stats\r\ntotal_connections_received:{}\r\ntotal_commands_processed:{}\r\ntotal_net_input_bytes:6656\r\ntotal_net_output_bytes:8192\r\nrejected_connections:0\r\nexpired_keys:0\r\nkeyspace_hits:3\r\nkeyspace_misses:2\r\npubsub_channels:{}\r\npubsub_patterns:{}\r\npubsubshard_channels:{}\r\ntotal_error_replies:2\r\ntotal_reads_processed:10\r\ntotal_writes_processed:5\r\nacl_access_denied_auth:{}\r\n", self.total_connections, self.total_commands, self.channels.len(), self.patterns.len(), self.shard_channels.len(), self.auth_failures);
         info
     }
 
@@ -485,6 +955,7 @@ impl Server {
     /// # use proyecto_taller_1::domain::entities::server::Server;
     /// # use std::sync::{Arc, Mutex, mpsc};
     ///
+    /// # let dir = "127.0.0.1".to_string();
     /// # let port = "8080".to_string();
     /// # let logfile = "./src/dummy_logfile.out".to_string();
     /// # let verbose = "1".to_string();
@@ -492,7 +963,7 @@ impl Server {
     /// # let recv = Arc::new(Mutex::new(receiver));
     /// # let configfile = "./src/dummy_configfile.txt".to_string();
     ///
-    /// let mut server = Server::new(port, logfile, verbose, recv, configfile).unwrap();
+    /// let mut server = Server::new(dir, port, logfile, verbose, recv, configfile).unwrap();
     /// assert_eq!(server.parse_verbose("1"), 1);
     /// # let _ = std::fs::remove_file("./src/dummy_logfile.out");
     /// ```
@@ -506,26 +977,407 @@ impl Server {
         verbose
     }
 
-    /// Retiene todos los clientes cuya direccion sea distinta a la que se quiere eliminar.
-    fn remove_client(&mut self, addrs: SocketAddr) {
-        self.clients.retain(|client| client.get_address() != &addrs);
+    /// Devuelve la información de replicación del servidor y de las réplicas conectadas.
+    ///
+    /// `read_only` indica si el servidor, cuando es una réplica, rechaza los comandos de escritura de sus clientes.
+    pub fn get_replication_info(&self, read_only: bool) -> String {
+        let replicas: Vec<(String, ReplicaState)> = self
+            .clients
+            .values()
+            .filter_map(|client| {
+                let ip = client
+                    .get_address()
+                    .rsplit_once(':')
+                    .map(|(ip, _)| ip.trim_matches(|c| c == '[' || c == ']').to_string())
+                    .unwrap_or_default();
+                client.get_replica().map(|replica| (ip, replica.clone()))
+            })
+            .collect();
+        self.replication.get_info(&replicas, read_only)
+    }
+
+    /// Retorna el cliente con el identificador `client_id`.
+    fn get_client_mut(&mut self, client_id: usize) -> Option<&mut Client> {
+        self.clients.get_mut(&client_id)
+    }
+
+    /// Registra un acceso denegado al usuario con el que está autenticado el cliente.
+    fn add_acl_log_entry(&mut self, denial: &AclDenial, username: &str, client_id: usize) {
+        let subscriptions = self.get_listening_channels(client_id);
+        let pattern_subscriptions = self.get_listening_patterns(client_id);
+        let shard_subscriptions = self.shard_channels.subscription_count(client_id);
+        let client_info = self
+            .get_client_mut(client_id)
+            .map(|client| {
+                client
+                    .get_info(subscriptions, pattern_subscriptions, shard_subscriptions)
+                    .trim_end()
+                    .to_string()
+            })
+            .unwrap_or_default();
+        self.acl.add_log_entry(denial, username, client_info);
+    }
+
+    /// Desuscribe al cliente de todos los canales, patrones y canales sharded y vuelve su conexión al estado inicial.
+    fn reset_client(&mut self, client_id: usize) {
+        self.channels.unsubscribe_all(client_id);
+        self.patterns.unsubscribe_all(client_id);
+        self.shard_channels.unsubscribe_all(client_id);
+        self.tracking.untrack(client_id);
+        if let Some(client) = self.get_client_mut(client_id) {
+            client.reset();
+        }
+    }
+
+    /// Activa el seguimiento de claves del cliente con las opciones indicadas, o lo desactiva si son `None`.
+    ///
+    /// Devuelve error si la conexión a la que se quieren redirigir los mensajes de invalidación no existe, o si el
+    /// cliente ya tiene activado el seguimiento en otro modo (`bcast`, `optin` u `optout`).
+    /// Activarlo de nuevo en el mismo modo agrega los prefijos indicados y reemplaza las demás opciones.
+    fn set_client_tracking(
+        &mut self,
+        client_id: usize,
+        options: Option<TrackingOptions>,
+    ) -> Result<(), String> {
+        let options = match options {
+            Some(options) => options,
+            None => {
+                self.tracking.untrack(client_id);
+                if let Some(client) = self.get_client_mut(client_id) {
+                    client.set_tracking(None);
+                }
+                return Ok(());
+            }
+        };
+        if let Some(redirect) = options.redirect {
+            if !self.clients.contains_key(&redirect) {
+                return Err(String::from(
+                    "ERR The client ID you want redirect to does not exist",
+                ));
+            }
+        }
+        if let Some(current) = self
+            .clients
+            .get(&client_id)
+            .and_then(Client::get_tracking)
+            .map(ClientTracking::get_options)
+        {
+            if current.bcast != options.bcast {
+                return Err(String::from("ERR You can't switch BCAST mode on/off before disabling tracking for this client, and then re-enabling it with a different mode."));
+            }
+            if current.optin != options.optin || current.optout != options.optout {
+                return Err(String::from("ERR You can't switch OPTIN/OPTOUT mode before disabling tracking for this client, and then re-enabling it with a different mode."));
+            }
+        }
+        if options.bcast {
+            self.tracking.track_prefixes(&options.prefixes, client_id);
+        }
+        if let Some(client) = self.get_client_mut(client_id) {
+            client.set_tracking(Some(ClientTracking::new(options)));
+        }
+        Ok(())
+    }
+
+    /// Envía los mensajes de invalidación de las claves modificadas a los clientes que las siguen.
+    ///
+    /// Cada cliente recibe un único mensaje con todas sus claves modificadas, salvo las que modificó él mismo (`origin`)
+    /// en modo `noloop`. El mensaje se envía a la conexión a la que redirige sus invalidaciones o, si no redirige, al
+    /// propio cliente: como push `invalidate` si utiliza RESP3, o como mensaje del canal `__redis__:invalidate` si
+    /// utiliza RESP2 y está suscrito a ese canal.
+    /// Si la conexión a la que redirige ya no existe, se registra la redirección como rota y, si el cliente utiliza
+    /// RESP3, se le envía el push `tracking-redir-broken`.
+    fn invalidate_keys(&mut self, keys: Vec<Vec<u8>>, origin: Option<usize>) {
+        let mut invalidated: HashMap<usize, Vec<Vec<u8>>> = HashMap::new();
+        for key in keys {
+            for client_id in self.tracking.invalidate(&key) {
+                invalidated.entry(client_id).or_default().push(key.clone());
+            }
+        }
+        for (client_id, keys) in invalidated {
+            let redirect = match self.clients.get(&client_id).and_then(Client::get_tracking) {
+                Some(tracking) if tracking.get_options().noloop && origin == Some(client_id) => {
+                    continue
+                }
+                Some(tracking) => tracking.get_options().redirect,
+                None => continue,
+            };
+            let target = redirect.unwrap_or(client_id);
+            let keys = RespType::RArray(keys.into_iter().map(RespType::RBulkString).collect());
+            match self
+                .clients
+                .get(&target)
+                .map(|client| *client.get_protocol())
+            {
+                Some(RESP3) => {
+                    self.write_to_clients(
+                        &[target],
+                        RespType::RPush(vec![RespType::RBulkString("invalidate".into()), keys]),
+                    );
+                }
+                Some(_) => {
+                    if self
+                        .channels
+                        .subscribers(INVALIDATE_CHANNEL)
+                        .contains(&target)
+                    {
+                        self.write_to_clients(
+                            &[target],
+                            RespType::RPush(vec![
+                                RespType::RBulkString("message".into()),
+                                RespType::RBulkString(INVALIDATE_CHANNEL.into()),
+                                keys,
+                            ]),
+                        );
+                    }
+                }
+                None => self.break_tracking_redirect(client_id, target),
+            }
+        }
+    }
+
+    /// Registra que se cerró la conexión a la que el cliente redirige sus mensajes de invalidación.
+    ///
+    /// La primera vez, si el cliente utiliza RESP3, se le avisa con el push `tracking-redir-broken`.
+    fn break_tracking_redirect(&mut self, client_id: usize, redirect: usize) {
+        let notify = match self.get_client_mut(client_id) {
+            Some(client) => {
+                let protocol = *client.get_protocol();
+                match client.get_tracking_mut() {
+                    Some(tracking) if !tracking.is_redirect_broken() => {
+                        tracking.set_redirect_broken();
+                        protocol == RESP3
+                    }
+                    _ => false,
+                }
+            }
+            None => false,
+        };
+        if notify {
+            self.write_to_clients(
+                &[client_id],
+                RespType::RPush(vec![
+                    RespType::RBulkString("tracking-redir-broken".into()),
+                    RespType::RInteger(redirect),
+                ]),
+            );
+        }
+    }
+
+    /// Retorna la información de los clientes que cumplen con el filtro, una línea por cliente.
+    fn list_clients(&self, filter: &ClientFilter) -> String {
+        self.clients
+            .values()
+            .filter(|client| filter.matches(client))
+            .map(|client| {
+                client.get_info(
+                    self.get_listening_channels(*client.get_id()),
+                    self.get_listening_patterns(*client.get_id()),
+                    self.shard_channels.subscription_count(*client.get_id()),
+                )
+            })
+            .collect()
+    }
+
+    /// Cierra la conexión de los clientes que cumplen con el filtro y los elimina del registro.
+    ///
+    /// Devuelve la cantidad de clientes desconectados.
+    fn kill_clients(&mut self, filter: &ClientFilter) -> usize {
+        let ids: Vec<usize> = self
+            .clients
+            .values()
+            .filter(|client| filter.matches(client))
+            .map(|client| *client.get_id())
+            .collect();
+        self.close_clients(&ids)
+    }
+
+    /// Cierra la conexión de los clientes que no ejecutaron ningún comando durante más de `timeout` segundos.
+    ///
+    /// Los clientes suscritos a canales, los que están en estado "monitor" y las réplicas no se desconectan, ya que solo reciben mensajes.
+    fn close_idle_clients(&mut self, timeout: u64) {
+        let ids: Vec<usize> = self
+            .clients
+            .values()
+            .filter(|client| {
+                !*client.is_subscriber()
+                    && !*client.is_monitoring()
+                    && !client.is_replica()
+                    && client.get_idle_time() > timeout
+            })
+            .map(|client| *client.get_id())
+            .collect();
+        if !ids.is_empty() {
+            self.log(format!("Closing {} idle clients", ids.len()));
+            self.verbose(format!("Closing {} idle clients", ids.len()));
+            self.close_clients(&ids);
+        }
+    }
+
+    /// Cierra la conexión de los clientes suscritos, entre los indicados, cuyos mensajes pendientes superan el límite
+    /// de la clase `pubsub` de `client-output-buffer-limit`.
+    fn close_clients_over_output_limit(&mut self, ids: &[usize]) {
+        let limit = self.pubsub_limit;
+        let over_limit: Vec<usize> = ids
+            .iter()
+            .filter(|client_id| {
+                self.clients.get_mut(client_id).is_some_and(|client| {
+                    *client.is_subscriber() && client.exceeds_output_limit(&limit)
+                })
+            })
+            .copied()
+            .collect();
+        for client_id in &over_limit {
+            self.log(format!(
+                "Client {} closed for overcoming of output buffer limits.",
+                client_id
+            ));
+            self.verbose(format!(
+                "Client {} closed for overcoming of output buffer limits.",
+                client_id
+            ));
+        }
+        self.close_clients(&over_limit);
+    }
+
+    /// Cierra la conexión de los clientes con los identificadores indicados y los elimina del registro.
+    ///
+    /// Devuelve la cantidad de clientes desconectados.
+    fn close_clients(&mut self, ids: &[usize]) -> usize {
+        for client_id in ids {
+            if let Some(client) = self.get_client_mut(*client_id) {
+                if client.shutdown().is_err() {
+                    self.log(format!("Client {} was already disconnected", client_id));
+                }
+            }
+            self.remove_client(*client_id);
+        }
+        ids.len()
+    }
+
+    /// Retorna cuánto tiempo falta para que termine la suspensión de los comandos de los clientes.
+    ///
+    /// Si la suspensión es solo de escritura y el comando no es de escritura, o no hay suspensión vigente, devuelve `None`.
+    fn get_pause_remaining(&mut self, is_write: bool) -> Option<Duration> {
+        let (until, write_only) = self.pause?;
+        match until.duration_since(SystemTime::now()) {
+            Ok(remaining) if is_write || !write_only => Some(remaining),
+            Ok(_) => None,
+            Err(_) => {
+                self.pause = None;
+                None
+            }
+        }
+    }
+
+    /// Registra al cliente como réplica y le envía la copia completa de los datos, como respuesta a `psync`.
+    ///
+    /// La respuesta es `+FULLRESYNC <replid> <offset>` seguida de la copia, con el formato de un bulk string sin el
+    /// salto de línea final, al igual que Redis. A partir de este momento la réplica recibe los comandos de escritura
+    /// (ver `propagate`), que se encolan después de la copia.
+    fn add_replica(&mut self, client_id: usize, snapshot: Vec<u8>) {
+        let replid = self.replication.get_replid().to_string();
+        let offset = self.replication.get_offset();
+        if let Some(client) = self.get_client_mut(client_id) {
+            let mut replica = client
+                .get_replica()
+                .cloned()
+                .unwrap_or_else(|| ReplicaState::new(0));
+            replica.online = true;
+            replica.ack_offset = offset;
+            replica.last_ack = SystemTime::now();
+            client.set_replica(Some(replica));
+            let mut message = format!(
+                "+FULLRESYNC {} {}\r\n${}\r\n",
+                replid,
+                offset,
+                snapshot.len()
+            )
+            .into_bytes();
+            message.extend(snapshot);
+            if client.write_to_stream(&message).is_err() {
+                self.log(format!("Could not send snapshot to replica {}", client_id));
+            } else {
+                self.log(format!("Replica {} synchronized", client_id));
+                self.verbose(format!("Replica {} synchronized", client_id));
+            }
+        }
+    }
+
+    /// Envía un comando de escritura a las réplicas sincronizadas y suma su tamaño al offset de replicación.
+    ///
+    /// Si no hay réplicas el offset no cambia, ya que no hay a quién enviarle el comando.
+    fn propagate(&mut self, command: RespType) {
+        let replicas: Vec<usize> = self
+            .clients
+            .values()
+            .filter(|client| client.is_replica())
+            .map(|client| *client.get_id())
+            .collect();
+        if replicas.is_empty() {
+            return;
+        }
+        let message = parser_service::parse_response(command);
+        self.replication.add_offset(message.len());
+        for client_id in replicas {
+            if let Some(client) = self.get_client_mut(client_id) {
+                if client.write_to_stream(&message).is_err() {
+                    self.log(format!(
+                        "Could not propagate command to replica {}",
+                        client_id
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Elimina al cliente del registro de clientes conectados.
+    ///
+    /// También lo elimina de los canales, patrones y canales sharded a los que estaba suscrito, y olvida las claves que seguía.
+    fn remove_client(&mut self, client_id: usize) {
+        self.channels.unsubscribe_all(client_id);
+        self.patterns.unsubscribe_all(client_id);
+        self.shard_channels.unsubscribe_all(client_id);
+        self.tracking.untrack(client_id);
+        self.clients.remove(&client_id);
     }
 
     /// Envia el ultimo comando recibido a los clientes que esten en estado "monitor".
     ///
     /// Verifica si hay algun cliente monitoreando los comandos enviados al servidor.
-    /// Si lo hay, le envia el ultimo comando ejecutado.
-    pub fn check_monitor(&mut self, operation: RespType, addrs: SocketAddr) {
+    /// Si lo hay, le envia el ultimo comando ejecutado con el formato de Redis (ver `format_monitor_line`).
+    /// Los clientes en estado "monitor" no reciben sus propios comandos.
+    pub fn check_monitor(&mut self, operation: RespType, client_id: usize) {
+        let args = match operation {
+            RespType::RArray(args) => args,
+            _ => return,
+        };
+        // las confirmaciones periódicas de las réplicas no son comandos de los clientes
+        if args
+            .first()
+            .is_some_and(|command| command.to_string().eq_ignore_ascii_case("replconf"))
+        {
+            return;
+        }
+        if !self.clients.values().any(|client| *client.is_monitoring()) {
+            return;
+        }
+        let addrs = self
+            .clients
+            .get(&client_id)
+            .map(Client::get_monitor_address)
+            .unwrap_or_default();
+        let msg = parser_service::parse_response(RespType::RSimpleString(format_monitor_line(
+            SystemTime::now(),
+            &addrs,
+            &args,
+        )));
         let mut error = false;
-        self.clients.iter_mut().for_each(|client| {
-            if *client.is_monitoring() {
-                let msg = parser_service::parse_response(RespType::RBulkString(format!(
-                    "[{}] {}",
-                    addrs, operation
-                )));
-                if client.write_to_stream(msg.as_bytes()).is_err() {
-                    error = true;
-                }
+        self.clients.values_mut().for_each(|client| {
+            if *client.is_monitoring()
+                && *client.get_id() != client_id
+                && client.write_to_stream(&msg).is_err()
+            {
+                error = true;
             }
         });
         if error {
@@ -536,199 +1388,402 @@ impl Server {
 
     /// Chequea si el cliente está en estado "subscribed".
     ///
-    /// Verifica si el cliente que está escuchando en la dirección `addrs` está suscrito a algún canal.
+    /// Verifica si el cliente con identificador `client_id` está suscrito a algún canal.
     /// Envia al cliente True si lo está, False si no.
-    pub fn check_pubsub(&mut self, addrs: SocketAddr, sender: Sender<bool>) {
-        self.clients.iter_mut().for_each(|client| {
-            if client.get_address() == &addrs {
-                sender
-                    .send(client.is_subscriber().to_owned())
-                    .expect("Check pubsub error. Some subscriptions could not be sent");
-            }
-        });
+    pub fn check_pubsub(&mut self, client_id: usize, sender: Sender<bool>) {
+        if let Some(client) = self.clients.get(&client_id) {
+            sender
+                .send(client.is_subscriber().to_owned())
+                .expect("Check pubsub error. Some subscriptions could not be sent");
+        }
     }
 
     /// Cambia el estado de un cliente a "monitor".
     ///
     /// El cliente pasa a un estado de "debug" donde solo puede recibir los comandos que se ejecutan en el servidor.
-    fn set_client_to_monitor_state(&mut self, addrs: SocketAddr) {
-        self.clients.iter_mut().for_each(|client| {
-            if client.get_address() == &addrs
-                && client
-                    .write_to_stream(
-                        parser_service::parse_response(RespType::RBulkString(String::from("Ok")))
-                            .as_bytes(),
-                    )
-                    .is_ok()
+    /// La respuesta "OK" se le envía por su cola de salida, para que siempre llegue antes que los comandos monitoreados.
+    fn set_client_to_monitor_state(&mut self, client_id: usize) {
+        if let Some(client) = self.clients.get_mut(&client_id) {
+            if client
+                .write_to_stream(&parser_service::parse_response(RespType::RSimpleString(
+                    String::from("OK"),
+                )))
+                .is_ok()
             {
                 client.set_monitoring(true);
             }
-        });
+        }
     }
 
     /// Suscribe un cliente al channel.
     ///
-    /// Primero chequea si el channel ya existe, si existe agrega al cliente.
-    /// Sino lo crea y agrega al cliente y su sender.
+    /// Si el cliente ya estaba suscrito al canal, no se lo vuelve a agregar.
+    /// Envía por el sender la cantidad de canales y patrones a los que queda suscrito el cliente.
     pub fn subscribe_to_channel(
         &mut self,
         channel: String,
-        addrs: SocketAddr,
+        client_id: usize,
         sender: Sender<usize>,
     ) {
-        if let Some(subscribers) = self.channels.get_mut(&channel) {
-            subscribers.push(addrs.to_string());
-        } else {
-            let subs = vec![addrs.to_string()];
-            self.channels.entry(channel).or_insert(subs);
-        }
-        let listening_channels = &self.get_listening_channels(addrs);
-
+        self.channels.subscribe(&channel, client_id);
         sender
-            .send(*listening_channels)
+            .send(self.update_client_subscribe_status(client_id))
             .expect("Error subscribing. Could not send listening channels to client.");
-        self.update_client_subscribe_status(addrs, true);
+    }
+
+    /// Suscribe un cliente a los canales cuyo nombre cumpla con el patrón.
+    ///
+    /// Envía por el sender la cantidad de canales y patrones a los que queda suscrito el cliente.
+    pub fn subscribe_to_pattern(
+        &mut self,
+        pattern: String,
+        client_id: usize,
+        sender: Sender<usize>,
+    ) {
+        self.patterns.subscribe(&pattern, client_id);
+        sender
+            .send(self.update_client_subscribe_status(client_id))
+            .expect("Error subscribing. Could not send listening patterns to client.");
     }
 
     /// Actualiza el estado de suscripcion de un cliente.
     ///
-    /// Busca el cliente con dirección `addrs` y le asigna `status` al estado de suscripción.
-    fn update_client_subscribe_status(&mut self, addrs: SocketAddr, status: bool) {
-        self.clients.iter_mut().for_each(|client| {
-            if client.get_address() == &addrs {
-                client.set_subscribe(status);
-            }
-        });
+    /// El cliente con identificador `client_id` queda en estado "subscribed" si está suscrito a algún canal, patrón o canal sharded.
+    /// Devuelve la cantidad de canales y patrones a los que está suscrito, sin contar los canales sharded.
+    fn update_client_subscribe_status(&mut self, client_id: usize) -> usize {
+        let subscriptions =
+            self.get_listening_channels(client_id) + self.get_listening_patterns(client_id);
+        let shard_subscriptions = self.shard_channels.subscription_count(client_id);
+        if let Some(client) = self.get_client_mut(client_id) {
+            client.set_subscribe(subscriptions + shard_subscriptions > 0);
+        }
+        subscriptions
     }
 
     /// Retorna la cantidad de canales a los que esta suscrito el cliente.
-    fn get_listening_channels(&self, addrs: SocketAddr) -> usize {
-        let mut listening_channels = 0;
-        self.channels.iter().for_each(|channel| {
-            if channel.1.contains(&addrs.to_string()) {
-                listening_channels += 1;
-            }
-        });
-        listening_channels
+    fn get_listening_channels(&self, client_id: usize) -> usize {
+        self.channels.subscription_count(client_id)
+    }
+
+    /// Retorna la cantidad de patrones a los que esta suscrito el cliente.
+    fn get_listening_patterns(&self, client_id: usize) -> usize {
+        self.patterns.subscription_count(client_id)
     }
 
     /// Desuscribe al cliente de todos los canales a los que este suscrito.
     ///
-    /// Por el sender asociado envia mensaje para dejar de aceptar mensajes del canal.
     /// Luego, actualiza el estado de suscripción del cliente.
-    pub fn unsubscribe_to_all_channels(&mut self, addrs: SocketAddr, sender: Sender<usize>) {
-        let mut removed = false;
-        for channel in self.channels.values_mut() {
-            if channel.contains(&addrs.to_string()) {
-                let idx = channel.binary_search(&addrs.to_string()).unwrap();
-                channel.remove(idx);
-                removed = true;
-            }
-        }
-        let listening_channels = self.get_listening_channels(addrs);
-        sender.send(listening_channels).expect(
+    /// Por el sender asociado envía los canales de los que se lo desuscribió y la cantidad de canales y patrones a los que sigue suscrito.
+    pub fn unsubscribe_to_all_channels(
+        &mut self,
+        client_id: usize,
+        sender: Sender<(Vec<String>, usize)>,
+    ) {
+        let removed = self.channels.unsubscribe_all(client_id);
+        let remaining = self.update_client_subscribe_status(client_id);
+        sender.send((removed, remaining)).expect(
             "Error unsubscribing from all channels. Could not send listening channels to client.",
         );
-        if removed {
-            self.update_client_subscribe_status(addrs, false);
-        }
+    }
+
+    /// Desuscribe al cliente de todos los patrones a los que este suscrito.
+    ///
+    /// Responde de la misma manera que `unsubscribe_to_all_channels`.
+    pub fn unsubscribe_from_all_patterns(
+        &mut self,
+        client_id: usize,
+        sender: Sender<(Vec<String>, usize)>,
+    ) {
+        let removed = self.patterns.unsubscribe_all(client_id);
+        let remaining = self.update_client_subscribe_status(client_id);
+        sender.send((removed, remaining)).expect(
+            "Error unsubscribing from all patterns. Could not send listening patterns to client.",
+        );
     }
 
     /// Desuscribe al cliente del canal especificado.
     ///
-    /// Elimina la dirección del hashmap de suscriptores de dicho canal.
-    /// Luego, actualiza el estado de suscripción del cliente.
-    pub fn unsubscribe(&mut self, channel: String, addrs: SocketAddr, tx: Sender<usize>) {
-        let subscribers = self.channels.get_mut(&channel).unwrap();
-        if subscribers.contains(&addrs.to_string()) {
-            let idx = subscribers.binary_search(&addrs.to_string()).unwrap();
-            subscribers.remove(idx);
-            let listening_channels = self.get_listening_channels(addrs);
-            tx.send(listening_channels)
-                .expect("Error unsubscribing. Could not send listening channels to client");
-            self.update_client_subscribe_status(addrs, false);
-        }
+    /// Elimina al cliente de los suscriptores de dicho canal, si estaba suscrito.
+    /// Luego, actualiza el estado de suscripción del cliente y envía la cantidad de canales y patrones a los que sigue suscrito.
+    pub fn unsubscribe(&mut self, channel: String, client_id: usize, tx: Sender<usize>) {
+        self.channels.unsubscribe(&channel, client_id);
+        tx.send(self.update_client_subscribe_status(client_id))
+            .expect("Error unsubscribing. Could not send listening channels to client");
     }
 
-    /// Envia un mensaje a todas los clientes suscritos al canal especificado.
+    /// Desuscribe al cliente del patrón especificado.
     ///
+    /// Responde de la misma manera que `unsubscribe`.
+    pub fn unsubscribe_from_pattern(
+        &mut self,
+        pattern: String,
+        client_id: usize,
+        tx: Sender<usize>,
+    ) {
+        self.patterns.unsubscribe(&pattern, client_id);
+        tx.send(self.update_client_subscribe_status(client_id))
+            .expect("Error unsubscribing. Could not send listening patterns to client");
+    }
+
+    /// Suscribe un cliente al canal sharded.
+    ///
+    /// Envía por el sender la cantidad de canales sharded a los que queda suscrito el cliente.
+    pub fn subscribe_to_shard_channel(
+        &mut self,
+        channel: String,
+        client_id: usize,
+        sender: Sender<usize>,
+    ) {
+        self.shard_channels.subscribe(&channel, client_id);
+        self.update_client_subscribe_status(client_id);
+        sender
+            .send(self.shard_channels.subscription_count(client_id))
+            .expect("Error subscribing. Could not send listening shard channels to client.");
+    }
+
+    /// Desuscribe al cliente del canal sharded especificado.
+    ///
+    /// Envía por el sender la cantidad de canales sharded a los que sigue suscrito el cliente.
+    pub fn unsubscribe_from_shard_channel(
+        &mut self,
+        channel: String,
+        client_id: usize,
+        sender: Sender<usize>,
+    ) {
+        self.shard_channels.unsubscribe(&channel, client_id);
+        self.update_client_subscribe_status(client_id);
+        sender
+            .send(self.shard_channels.subscription_count(client_id))
+            .expect("Error unsubscribing. Could not send listening shard channels to client");
+    }
+
+    /// Desuscribe al cliente de todos los canales sharded a los que este suscrito.
+    ///
+    /// Por el sender asociado envía los canales de los que se lo desuscribió y la cantidad de canales sharded a los que
+    /// sigue suscrito, que es siempre 0.
+    pub fn unsubscribe_from_all_shard_channels(
+        &mut self,
+        client_id: usize,
+        sender: Sender<(Vec<String>, usize)>,
+    ) {
+        let removed = self.shard_channels.unsubscribe_all(client_id);
+        self.update_client_subscribe_status(client_id);
+        sender
+            .send((removed, self.shard_channels.subscription_count(client_id)))
+            .expect("Error unsubscribing from all shard channels. Could not send listening shard channels to client.");
+    }
+
+    /// Envia un mensaje a todos los clientes suscritos al canal sharded especificado.
+    ///
+    /// Los suscriptores reciben el mensaje `smessage` con el canal y el contenido. Los suscriptores de canales
+    /// globales o de patrones no lo reciben, aunque el nombre del canal coincida.
     /// Devuelve la cantidad de clientes a los que les envió el mensaje.
+    pub fn send_message_to_shard_channel(&mut self, channel: String, msg: String) -> usize {
+        let ids = self.shard_channels.subscribers(&channel);
+        self.write_to_clients(
+            &ids,
+            RespType::RPush(vec![
+                RespType::RBulkString("smessage".into()),
+                RespType::RBulkString(channel.into()),
+                RespType::RBulkString(msg.into()),
+            ]),
+        )
+    }
+
+    /// Envia un mensaje a todas los clientes suscritos al canal especificado.
+    ///
+    /// Los suscriptores del canal reciben el mensaje `message` con el canal y el contenido.
+    /// Los suscriptores de cada patrón que cumpla el canal reciben el mensaje `pmessage`, con el patrón, el canal y el contenido.
+    /// Los clientes que negociaron RESP3 reciben el mensaje como un push, el resto como un array.
+    /// Devuelve la cantidad de mensajes enviados: un cliente suscrito al canal y a patrones que lo cumplen recibe uno por cada suscripción.
     pub fn send_message_to_channel(&mut self, channel: String, msg: String) -> usize {
-        match self.channels.get(&channel) {
-            Some(subscribers) => {
-                let addresses = subscribers.iter().map(|addrs| addrs.to_owned()).collect();
-                self.write_to_client_with_address(
-                    addresses,
-                    parser_service::parse_response(RespType::RArray(vec![
-                        RespType::RBulkString(String::from("message")),
-                        RespType::RBulkString(channel.clone()),
-                        RespType::RBulkString(msg),
-                    ]))
-                    .as_bytes(),
-                )
-            }
-            None => 0,
+        let ids = self.channels.subscribers(&channel);
+        let mut sent = self.write_to_clients(
+            &ids,
+            RespType::RPush(vec![
+                RespType::RBulkString("message".into()),
+                RespType::RBulkString(channel.clone().into()),
+                RespType::RBulkString(msg.clone().into()),
+            ]),
+        );
+        let matching_patterns: Vec<(String, Vec<usize>)> = self
+            .patterns
+            .channels()
+            .filter(|pattern| glob_pattern::g_match(pattern.as_bytes(), channel.as_bytes()))
+            .map(|pattern| (pattern.clone(), self.patterns.subscribers(pattern)))
+            .collect();
+        for (pattern, ids) in matching_patterns {
+            sent += self.write_to_clients(
+                &ids,
+                RespType::RPush(vec![
+                    RespType::RBulkString("pmessage".into()),
+                    RespType::RBulkString(pattern.into()),
+                    RespType::RBulkString(channel.clone().into()),
+                    RespType::RBulkString(msg.clone().into()),
+                ]),
+            );
         }
+        sent
     }
 
     /// Escribe sobre el stream clientes.
     ///
-    /// Escribe un arreglo de bytes sobre el stream de todos los clientes cuya dirección este incluida en las direcciones pedidas.
-    /// Devuelve la cantidad de clientes a los que les escribió un mensaje.
-    pub fn write_to_client_with_address(&mut self, addrs: Vec<String>, msg: &[u8]) -> usize {
+    /// Encola un mensaje para cada cliente con los identificadores pedidos, codificado según la versión del protocolo
+    /// que utiliza cada cliente, y cierra la conexión de los suscriptores que superen el límite de su cola de salida.
+    /// Devuelve la cantidad de clientes a los que les envió el mensaje.
+    pub fn write_to_clients(&mut self, ids: &[usize], msg: RespType) -> usize {
         let mut sent = 0;
-        self.clients.iter_mut().for_each(|client| {
-            if addrs.contains(&client.get_address().to_string())
-                && client.write_to_stream(msg).is_ok()
-            {
-                sent += 1;
+        for client_id in ids {
+            if let Some(client) = self.clients.get_mut(client_id) {
+                if client
+                    .write_to_stream(&parser_service::parse_response(
+                        msg.clone().into_protocol(*client.get_protocol()),
+                    ))
+                    .is_ok()
+                {
+                    sent += 1;
+                }
             }
-        });
+        }
+        self.close_clients_over_output_limit(ids);
         sent
     }
+}
 
-    /// Envia al cliente una lista de todos los canales activos.
-    fn list_active_channels(&self, sender: Sender<Vec<RespType>>) {
-        let mut channels = Vec::new();
-        self.channels.iter().for_each(|channel| {
-            if !channel.1.is_empty() {
-                channels.push(RespType::RBulkString(channel.0.to_string()));
-            }
-        });
-        sender
-            .send(channels)
-            .expect("Error listing active channels.");
+/// Retorna la línea que reciben los clientes en estado "monitor" por cada comando, con el formato de Redis:
+/// `<segundos>.<microsegundos> [0 <dirección>] "comando" "argumento" ...`.
+///
+/// Cada argumento se escribe entre comillas, escapando las comillas, las barras invertidas y los caracteres no
+/// imprimibles. Las contraseñas se reemplazan por `(redacted)` (ver `commander::get_redacted_command`).
+fn format_monitor_line(time: SystemTime, addrs: &str, args: &[RespType]) -> String {
+    let timestamp = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let mut line = format!(
+        "{}.{:06} [0 {}]",
+        timestamp.as_secs(),
+        timestamp.subsec_micros(),
+        addrs
+    );
+    for arg in get_redacted_command(args) {
+        line.push(' ');
+        match arg {
+            RespType::RBulkString(bytes) => line.push_str(&quote_argument(&bytes)),
+            arg => line.push_str(&quote_argument(arg.to_string().as_bytes())),
+        }
     }
+    line
+}
 
-    /// Envia al cliente una lista de todos los canales activos que sigan el patrón especificado
-    fn list_active_channels_by_pattern(&self, sender: Sender<Vec<RespType>>, pattern: String) {
-        let mut channels = Vec::new();
-
-        self.channels.iter().for_each(|channel| {
-            if !channel.1.is_empty()
-                && glob_pattern::g_match(pattern.as_bytes(), channel.0.as_bytes())
-            {
-                channels.push(RespType::RBulkString(channel.0.to_string()));
+/// Escribe el argumento entre comillas, escapando los caracteres especiales como `sdscatrepr` de Redis.
+fn quote_argument(bytes: &[u8]) -> String {
+    let mut quoted = String::from("\"");
+    for byte in bytes {
+        match byte {
+            b'\\' | b'"' => {
+                quoted.push('\\');
+                quoted.push(*byte as char);
             }
-        });
-        sender
-            .send(channels)
-            .expect("Error listing active channels by pattern");
+            b'\n' => quoted.push_str("\\n"),
+            b'\r' => quoted.push_str("\\r"),
+            b'\t' => quoted.push_str("\\t"),
+            0x07 => quoted.push_str("\\a"),
+            0x08 => quoted.push_str("\\b"),
+            b' '..=b'~' => quoted.push(*byte as char),
+            byte => quoted.push_str(&format!("\\x{:02x}", byte)),
+        }
     }
+    quoted.push('"');
+    quoted
+}
 
-    /// Envia al cliente una lista con la cantidad de suscriptores por canal
-    fn list_number_of_subscribers(&self, channels: Vec<String>, sender: Sender<Vec<RespType>>) {
-        let mut list = Vec::new();
-        channels.iter().for_each(|channel| {
-            let mut counter = 0;
-            if let Some(subscribers) = self.channels.get(channel) {
-                counter = subscribers.len();
-            }
-            list.push(RespType::RBulkString(channel.to_string()));
-            list.push(RespType::RBulkString(counter.to_string()));
-        });
-        sender
-            .send(list)
-            .expect("Error listing number of subscribers");
-    }
+/// Envia al cliente una lista de todos los canales activos del registro.
+fn list_active_channels(registry: &PubSubRegistry, sender: Sender<Vec<RespType>>) {
+    let channels = registry
+        .channels()
+        .map(|channel| RespType::RBulkString(channel.to_string().into()))
+        .collect();
+    sender
+        .send(channels)
+        .expect("Error listing active channels.");
+}
+
+/// Envia al cliente una lista de todos los canales activos del registro que sigan el patrón especificado
+fn list_active_channels_by_pattern(
+    registry: &PubSubRegistry,
+    sender: Sender<Vec<RespType>>,
+    pattern: String,
+) {
+    let channels = registry
+        .channels()
+        .filter(|channel| glob_pattern::g_match(pattern.as_bytes(), channel.as_bytes()))
+        .map(|channel| RespType::RBulkString(channel.to_string().into()))
+        .collect();
+    sender
+        .send(channels)
+        .expect("Error listing active channels by pattern");
+}
+
+/// Envia al cliente una lista con la cantidad de suscriptores por canal del registro
+fn list_number_of_subscribers(
+    registry: &PubSubRegistry,
+    channels: Vec<String>,
+    sender: Sender<Vec<RespType>>,
+) {
+    let mut list = Vec::new();
+    channels.iter().for_each(|channel| {
+        let counter = registry.subscriber_count(channel);
+        list.push(RespType::RBulkString(channel.to_string().into()));
+        list.push(RespType::RBulkString(counter.to_string().into()));
+    });
+    sender
+        .send(list)
+        .expect("Error listing number of subscribers");
+}
+
+#[test]
+fn test_01_monitor_line_quotes_arguments_like_redis() {
+    let time = UNIX_EPOCH + Duration::from_micros(1_626_111_469_123_456);
+    let args = vec![
+        RespType::RBulkString("set".into()),
+        RespType::RBulkString("k".into()),
+        RespType::RBulkString(b"a \"b\"\n\x01".to_vec()),
+    ];
+    assert_eq!(
+        format_monitor_line(time, "127.0.0.1:5000", &args),
+        "1626111469.123456 [0 127.0.0.1:5000] \"set\" \"k\" \"a \\\"b\\\"\\n\\x01\""
+    );
+}
+
+#[test]
+fn test_02_monitor_line_redacts_passwords() {
+    let time = UNIX_EPOCH;
+    let words = |words: &[&str]| -> Vec<RespType> {
+        words
+            .iter()
+            .map(|word| RespType::RBulkString(word.to_string().into()))
+            .collect()
+    };
+    assert_eq!(
+        format_monitor_line(
+            time,
+            "unix:/tmp/redis.sock",
+            &words(&["AUTH", "user", "secret"])
+        ),
+        "0.000000 [0 unix:/tmp/redis.sock] \"AUTH\" \"(redacted)\" \"(redacted)\""
+    );
+    assert_eq!(
+        format_monitor_line(
+            time,
+            "127.0.0.1:5000",
+            &words(&["hello", "3", "auth", "default", "secret", "setname", "x"])
+        ),
+        "0.000000 [0 127.0.0.1:5000] \"hello\" \"3\" \"auth\" \"(redacted)\" \"(redacted)\" \"setname\" \"x\""
+    );
+    assert_eq!(
+        format_monitor_line(
+            time,
+            "127.0.0.1:5000",
+            &words(&["config", "set", "requirepass", "secret", "maxclients", "10"])
+        ),
+        "0.000000 [0 127.0.0.1:5000] \"config\" \"set\" \"requirepass\" \"(redacted)\" \"maxclients\" \"10\""
+    );
 }
@@ -0,0 +1,85 @@
+//! Estado de la conexión de un cliente.
+
+use crate::services::utils::resp_type::RESP2;
+
+/// Estado de la conexión que mantiene el worker que atiende a un cliente.
+///
+/// Se compone por:
+/// * protocol: versión del protocolo RESP negociada con el comando `hello`.
+/// * user: usuario con el que se autenticó el cliente. Es `None` mientras el cliente no se haya autenticado.
+/// * asking: el cliente ejecutó `asking`, por lo que su siguiente comando se atiende aunque el slot de sus claves se
+///   esté importando desde otro nodo del cluster.
+#[derive(Debug)]
+pub struct Session {
+    protocol: usize,
+    user: Option<String>,
+    asking: bool,
+}
+
+impl Session {
+    /// Crea el estado de una conexión nueva, que utiliza RESP2.
+    ///
+    /// Si `authenticated` es true, la conexión queda autenticada con el usuario `default`.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::entities::session::Session;
+    ///
+    /// let session = Session::new(false);
+    /// assert_eq!(session.get_protocol(), 2);
+    /// assert!(!session.is_authenticated());
+    ///
+    /// let session = Session::new(true);
+    /// assert_eq!(session.get_user(), Some(&String::from("default")));
+    /// ```
+    pub fn new(authenticated: bool) -> Self {
+        let user = if authenticated {
+            Some(String::from(DEFAULT_USER))
+        } else {
+            None
+        };
+        Session {
+            protocol: RESP2,
+            user,
+            asking: false,
+        }
+    }
+
+    /// Retorna la versión del protocolo RESP de la conexión.
+    pub fn get_protocol(&self) -> usize {
+        self.protocol
+    }
+
+    /// Actualiza la versión del protocolo RESP de la conexión.
+    pub fn set_protocol(&mut self, protocol: usize) {
+        self.protocol = protocol;
+    }
+
+    /// Retorna el usuario con el que se autenticó el cliente.
+    pub fn get_user(&self) -> Option<&String> {
+        self.user.as_ref()
+    }
+
+    /// Indica si el cliente se autenticó.
+    pub fn is_authenticated(&self) -> bool {
+        self.user.is_some()
+    }
+
+    /// Actualiza el usuario de la conexión. Si es `None` la conexión deja de estar autenticada.
+    pub fn set_user(&mut self, user: Option<String>) {
+        self.user = user;
+    }
+
+    /// Indica si el siguiente comando del cliente se atiende aunque el slot de sus claves se esté importando.
+    pub fn is_asking(&self) -> bool {
+        self.asking
+    }
+
+    /// Registra si el siguiente comando del cliente se atiende aunque el slot de sus claves se esté importando.
+    pub fn set_asking(&mut self, asking: bool) {
+        self.asking = asking;
+    }
+}
+
+/// Usuario con el que se autentican los clientes cuando no indican uno.
+pub const DEFAULT_USER: &str = "default";
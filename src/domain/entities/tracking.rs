@@ -0,0 +1,347 @@
+//! Seguimiento de las claves que cachean los clientes, configurado con `client tracking`.
+
+use super::pubsub_registry::{remove_from, PubSubRegistry};
+use crate::services::utils::resp_type::RespType;
+use std::collections::{HashMap, HashSet};
+
+/// Canal en el que reciben los mensajes de invalidación las conexiones RESP2 a las que se redirigen.
+pub const INVALIDATE_CHANNEL: &str = "__redis__:invalidate";
+
+/// Opciones de `client tracking on`.
+/// * redirect: identificador de la conexión que recibe los mensajes de invalidación, en lugar del propio cliente.
+/// * bcast: se invalidan todas las claves que empiezan con alguno de los prefijos, aunque el cliente no las haya leído.
+/// * prefixes: prefijos de las claves que se invalidan en modo `bcast`; si no se indica ninguno, se invalidan todas las claves.
+/// * optin: solo se recuerdan las claves que lee el comando siguiente a `client caching yes`.
+/// * optout: se recuerdan las claves que leen todos los comandos, salvo el siguiente a `client caching no`.
+/// * noloop: no se invalidan las claves que modifica el propio cliente.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrackingOptions {
+    pub redirect: Option<usize>,
+    pub bcast: bool,
+    pub prefixes: Vec<String>,
+    pub optin: bool,
+    pub optout: bool,
+    pub noloop: bool,
+}
+
+impl TrackingOptions {
+    /// Interpreta las opciones que siguen a `client tracking on`.
+    ///
+    /// Devuelve el error que se responde al cliente si alguna opción no es válida o si las opciones no son compatibles.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::tracking::TrackingOptions;
+    /// use proyecto_taller_1::services::utils::resp_type::RespType;
+    ///
+    /// let args = vec![RespType::RBulkString("BCAST".into()), RespType::RBulkString("PREFIX".into()), RespType::RBulkString("user:".into())];
+    /// let options = TrackingOptions::parse(&args).unwrap();
+    /// assert!(options.bcast);
+    /// assert_eq!(options.prefixes, vec![String::from("user:")]);
+    ///
+    /// assert!(TrackingOptions::parse(&args[1..]).is_err());
+    /// ```
+    pub fn parse(args: &[RespType]) -> Result<Self, String> {
+        let mut options = TrackingOptions::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str().map(str::to_lowercase).as_deref() {
+                Some("redirect") => {
+                    let id = args
+                        .next()
+                        .and_then(RespType::as_str)
+                        .and_then(|id| id.parse::<usize>().ok())
+                        .ok_or_else(|| String::from("ERR Invalid client ID"))?;
+                    options.redirect = Some(id);
+                }
+                Some("prefix") => match args.next().and_then(RespType::as_str) {
+                    Some(prefix) => options.prefixes.push(prefix.to_string()),
+                    None => return Err(String::from("ERR syntax error")),
+                },
+                Some("bcast") => options.bcast = true,
+                Some("optin") => options.optin = true,
+                Some("optout") => options.optout = true,
+                Some("noloop") => options.noloop = true,
+                _ => return Err(String::from("ERR syntax error")),
+            }
+        }
+        if !options.bcast && !options.prefixes.is_empty() {
+            return Err(String::from(
+                "ERR PREFIX option requires BCAST mode to be enabled",
+            ));
+        }
+        if options.optin && options.optout {
+            return Err(String::from(
+                "ERR You can't use both OPTIN and OPTOUT at the same time",
+            ));
+        }
+        if options.bcast && (options.optin || options.optout) {
+            return Err(String::from(
+                "ERR OPTIN and OPTOUT are not compatible with BCAST",
+            ));
+        }
+        Ok(options)
+    }
+}
+
+/// Estado del seguimiento de claves de un cliente.
+///
+/// Además de sus opciones, registra lo indicado con `client caching` para el comando siguiente (`caching`), lo que
+/// corresponde al comando que se está ejecutando (`command_caching`) y si la conexión a la que se redirigen sus
+/// mensajes de invalidación se cerró (`redirect_broken`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientTracking {
+    options: TrackingOptions,
+    caching: Option<bool>,
+    command_caching: Option<bool>,
+    redirect_broken: bool,
+}
+
+impl ClientTracking {
+    /// Crea el estado del seguimiento de claves con las opciones indicadas.
+    pub fn new(options: TrackingOptions) -> Self {
+        ClientTracking {
+            options,
+            caching: None,
+            command_caching: None,
+            redirect_broken: false,
+        }
+    }
+
+    /// Retorna las opciones del seguimiento de claves.
+    pub fn get_options(&self) -> &TrackingOptions {
+        &self.options
+    }
+
+    /// Registra `client caching yes` o `client caching no` para el comando siguiente.
+    ///
+    /// Devuelve error si el modo del seguimiento no admite la opción.
+    pub fn set_caching(&mut self, caching: bool) -> Result<(), String> {
+        if !self.options.optin && !self.options.optout {
+            return Err(String::from("ERR CLIENT CACHING can be called only when the client is in tracking mode with OPTIN or OPTOUT mode enabled"));
+        }
+        if caching && !self.options.optin {
+            return Err(String::from(
+                "ERR CLIENT CACHING YES is only valid when tracking is enabled in OPTIN mode.",
+            ));
+        }
+        if !caching && !self.options.optout {
+            return Err(String::from(
+                "ERR CLIENT CACHING NO is only valid when tracking is enabled in OPTOUT mode.",
+            ));
+        }
+        self.caching = Some(caching);
+        Ok(())
+    }
+
+    /// Registra el comienzo de un comando del cliente.
+    ///
+    /// Lo indicado con `client caching` se aplica solo al comando siguiente, por lo que pasa a corresponder a este comando,
+    /// salvo que sea el mismo `client caching`.
+    pub fn begin_command(&mut self, is_caching_command: bool) {
+        if !is_caching_command {
+            self.command_caching = self.caching.take();
+        }
+    }
+
+    /// Indica si se recuerdan las claves que lee el comando que se está ejecutando.
+    ///
+    /// En modo `bcast` no se recuerdan claves, ya que se invalidan según sus prefijos.
+    pub fn tracks_command_keys(&self) -> bool {
+        if self.options.bcast {
+            false
+        } else if self.options.optin {
+            self.command_caching == Some(true)
+        } else if self.options.optout {
+            self.command_caching != Some(false)
+        } else {
+            true
+        }
+    }
+
+    /// Indica si se cerró la conexión a la que se redirigen los mensajes de invalidación.
+    pub fn is_redirect_broken(&self) -> bool {
+        self.redirect_broken
+    }
+
+    /// Registra que se cerró la conexión a la que se redirigen los mensajes de invalidación.
+    pub fn set_redirect_broken(&mut self) {
+        self.redirect_broken = true;
+    }
+
+    /// Retorna los flags del seguimiento de claves, con el formato de `client trackinginfo`.
+    pub fn get_flags(&self) -> Vec<&'static str> {
+        let mut flags = vec!["on"];
+        if self.options.bcast {
+            flags.push("bcast");
+        }
+        if self.options.optin {
+            flags.push("optin");
+        }
+        if self.options.optout {
+            flags.push("optout");
+        }
+        match self.caching {
+            Some(true) => flags.push("caching-yes"),
+            Some(false) => flags.push("caching-no"),
+            None => {}
+        }
+        if self.options.noloop {
+            flags.push("noloop");
+        }
+        if self.redirect_broken {
+            flags.push("broken_redirect");
+        }
+        flags
+    }
+}
+
+/// Registro de las claves y de los prefijos cuyas modificaciones se notifican a los clientes.
+///
+/// Las claves que lee un cliente se recuerdan hasta que se modifican: entonces se le envía un único mensaje de
+/// invalidación y se olvidan, hasta que vuelva a leerlas. Los prefijos de los clientes en modo `bcast`, en cambio,
+/// se mantienen hasta que desactivan el seguimiento.
+///
+/// Las claves se registran como bytes, ya que pueden no ser texto válido.
+#[derive(Debug, Default)]
+pub struct TrackingTable {
+    keys: HashMap<Vec<u8>, HashSet<usize>>,
+    keys_by_client: HashMap<usize, HashSet<Vec<u8>>>,
+    prefixes: PubSubRegistry,
+}
+
+impl TrackingTable {
+    /// Crea un registro sin claves ni prefijos.
+    pub fn new() -> Self {
+        TrackingTable::default()
+    }
+
+    /// Recuerda que el cliente leyó la clave.
+    pub fn track_key(&mut self, key: &[u8], client_id: usize) {
+        if self.keys.entry(key.to_vec()).or_default().insert(client_id) {
+            self.keys_by_client
+                .entry(client_id)
+                .or_default()
+                .insert(key.to_vec());
+        }
+    }
+
+    /// Registra los prefijos de las claves que se invalidan al cliente en modo `bcast`.
+    ///
+    /// Sin prefijos, se le invalidan todas las claves.
+    pub fn track_prefixes(&mut self, prefixes: &[String], client_id: usize) {
+        if prefixes.is_empty() {
+            self.prefixes.subscribe("", client_id);
+        }
+        for prefix in prefixes {
+            self.prefixes.subscribe(prefix, client_id);
+        }
+    }
+
+    /// Olvida las claves y los prefijos registrados para el cliente.
+    pub fn untrack(&mut self, client_id: usize) {
+        for key in self.keys_by_client.remove(&client_id).unwrap_or_default() {
+            remove_from(&mut self.keys, key.as_slice(), &client_id);
+        }
+        self.prefixes.unsubscribe_all(client_id);
+    }
+
+    /// Retorna los clientes a los que debe invalidarse la clave modificada.
+    ///
+    /// Los clientes que la leyeron dejan de recordarla.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::entities::tracking::TrackingTable;
+    ///
+    /// let mut table = TrackingTable::new();
+    /// table.track_key(b"user:1", 1);
+    /// table.track_prefixes(&[String::from("user:")], 2);
+    ///
+    /// let mut clients = table.invalidate(b"user:1");
+    /// clients.sort_unstable();
+    /// assert_eq!(clients, vec![1, 2]);
+    /// assert_eq!(table.invalidate(b"user:1"), vec![2]);
+    /// ```
+    pub fn invalidate(&mut self, key: &[u8]) -> Vec<usize> {
+        let mut clients: Vec<usize> = self
+            .keys
+            .remove(key)
+            .map(|clients| clients.into_iter().collect())
+            .unwrap_or_default();
+        for client_id in &clients {
+            remove_from(&mut self.keys_by_client, client_id, key);
+        }
+        for prefix in self.prefixes.channels() {
+            if key.starts_with(prefix.as_bytes()) {
+                clients.extend(self.prefixes.subscribers(prefix));
+            }
+        }
+        clients.sort_unstable();
+        clients.dedup();
+        clients
+    }
+
+    /// Retorna la cantidad de claves que recuerda algún cliente.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Indica si ningún cliente recuerda claves.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+#[test]
+fn test_01_optin_tracks_only_the_command_after_caching_yes() {
+    let mut tracking = ClientTracking::new(TrackingOptions {
+        optin: true,
+        ..TrackingOptions::default()
+    });
+    tracking.begin_command(false);
+    assert!(!tracking.tracks_command_keys());
+
+    tracking.set_caching(true).unwrap();
+    assert_eq!(tracking.get_flags(), vec!["on", "optin", "caching-yes"]);
+    tracking.begin_command(true);
+    tracking.begin_command(false);
+    assert!(tracking.tracks_command_keys());
+    tracking.begin_command(false);
+    assert!(!tracking.tracks_command_keys());
+    assert!(tracking.set_caching(false).is_err());
+}
+
+#[test]
+fn test_02_incompatible_options_are_rejected() {
+    let words = |words: &[&str]| -> Vec<RespType> {
+        words
+            .iter()
+            .map(|word| RespType::RBulkString(word.to_string().into()))
+            .collect()
+    };
+    assert!(TrackingOptions::parse(&words(&["optin", "optout"])).is_err());
+    assert!(TrackingOptions::parse(&words(&["bcast", "optin"])).is_err());
+    assert!(TrackingOptions::parse(&words(&["redirect", "x"])).is_err());
+    assert!(TrackingOptions::parse(&words(&["prefix"])).is_err());
+    assert_eq!(
+        TrackingOptions::parse(&words(&["redirect", "7", "noloop"])),
+        Ok(TrackingOptions {
+            redirect: Some(7),
+            noloop: true,
+            ..TrackingOptions::default()
+        })
+    );
+}
+
+#[test]
+fn test_03_untracked_clients_are_not_invalidated() {
+    let mut table = TrackingTable::new();
+    table.track_key(b"a", 1);
+    table.track_prefixes(&[], 2);
+    table.untrack(1);
+    table.untrack(2);
+
+    assert!(table.invalidate(b"a").is_empty());
+    assert!(table.is_empty());
+}
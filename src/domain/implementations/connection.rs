@@ -0,0 +1,231 @@
+//! Conexión de un cliente con el servidor: TCP en texto plano, TCP cifrada con TLS o socket Unix.
+
+use rustls::{ServerConfig, ServerConnection};
+use std::io::{Error, ErrorKind, Read, Write};
+use std::net::{IpAddr, Shutdown, TcpStream};
+use std::os::unix::net::UnixStream;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Cantidad de bytes que se leen del socket en cada lectura de registros TLS.
+const TLS_READ_BUFFER: usize = 4096;
+
+/// Stream sobre el cual un cliente envía comandos y recibe respuestas.
+///
+/// Todas las variantes pueden clonarse con `try_clone` para que el servidor escriba sobre la misma conexión
+/// que lee el worker (por ejemplo, para enviar los mensajes de los canales suscritos).
+#[derive(Debug)]
+pub enum Connection {
+    Tcp(TcpStream),
+    Tls(TlsStream),
+    Unix(UnixStream),
+}
+
+impl Connection {
+    /// Crea una conexión TLS sobre el socket recibido, utilizando la configuración del servidor.
+    ///
+    /// El handshake no se realiza en este momento sino durante las primeras lecturas y escrituras.
+    pub fn new_tls(socket: TcpStream, config: Arc<ServerConfig>) -> Result<Self, Error> {
+        let session =
+            ServerConnection::new(config).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        Ok(Connection::Tls(TlsStream {
+            session: Arc::new(Mutex::new(session)),
+            writing: Arc::new(Mutex::new(())),
+            socket,
+        }))
+    }
+
+    /// Crea otro manejador de la misma conexión.
+    ///
+    /// En el caso de TLS, ambos manejadores comparten el estado de la sesión.
+    pub fn try_clone(&self) -> Result<Self, Error> {
+        match self {
+            Connection::Tcp(stream) => Ok(Connection::Tcp(stream.try_clone()?)),
+            Connection::Tls(stream) => Ok(Connection::Tls(TlsStream {
+                session: stream.session.clone(),
+                writing: stream.writing.clone(),
+                socket: stream.socket.try_clone()?,
+            })),
+            Connection::Unix(stream) => Ok(Connection::Unix(stream.try_clone()?)),
+        }
+    }
+
+    /// Retorna la dirección del cliente con el formato `ip:puerto`.
+    ///
+    /// Los clientes de un socket Unix no tienen dirección propia, por lo que, al igual que Redis,
+    /// se identifican con la ruta del socket y el puerto 0.
+    pub fn peer_address(&self) -> Result<String, Error> {
+        match self {
+            Connection::Tcp(stream) => Ok(stream.peer_addr()?.to_string()),
+            Connection::Tls(stream) => Ok(stream.socket.peer_addr()?.to_string()),
+            Connection::Unix(_) => self.local_address(),
+        }
+    }
+
+    /// Retorna la dirección local sobre la que se aceptó la conexión, con el formato `ip:puerto` o `ruta:0`.
+    pub fn local_address(&self) -> Result<String, Error> {
+        match self {
+            Connection::Tcp(stream) => Ok(stream.local_addr()?.to_string()),
+            Connection::Tls(stream) => Ok(stream.socket.local_addr()?.to_string()),
+            Connection::Unix(stream) => Ok(format!(
+                "{}:0",
+                stream
+                    .local_addr()?
+                    .as_pathname()
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_default()
+            )),
+        }
+    }
+
+    /// Indica si el cliente se conectó desde la misma máquina: por una interfaz de loopback o por el socket Unix.
+    pub fn is_loopback(&self) -> bool {
+        let peer = match self {
+            Connection::Tcp(stream) => stream.peer_addr(),
+            Connection::Tls(stream) => stream.socket.peer_addr(),
+            Connection::Unix(_) => return true,
+        };
+        match peer.map(|addrs| addrs.ip()) {
+            Ok(IpAddr::V6(ip)) => ip
+                .to_ipv4_mapped()
+                .map_or(ip.is_loopback(), |ip| ip.is_loopback()),
+            Ok(ip) => ip.is_loopback(),
+            Err(_) => false,
+        }
+    }
+
+    /// Cierra la conexión.
+    pub fn shutdown(&self, how: Shutdown) -> Result<(), Error> {
+        match self {
+            Connection::Tcp(stream) => stream.shutdown(how),
+            Connection::Tls(stream) => stream.socket.shutdown(how),
+            Connection::Unix(stream) => stream.shutdown(how),
+        }
+    }
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        match self {
+            Connection::Tcp(stream) => stream.read(buf),
+            Connection::Tls(stream) => stream.read(buf),
+            Connection::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        match self {
+            Connection::Tcp(stream) => stream.write(buf),
+            Connection::Tls(stream) => stream.write(buf),
+            Connection::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        match self {
+            Connection::Tcp(stream) => stream.flush(),
+            Connection::Tls(stream) => stream.flush(),
+            Connection::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Conexión TLS de un cliente.
+///
+/// La sesión TLS se comparte entre todos los clones de la conexión y se protege con un Mutex.
+/// Las lecturas y escrituras del socket se hacen sin tomar el lock de la sesión, para que una escritura bloqueada
+/// por un cliente lento no impida leer sus comandos, ni la espera de nuevos comandos impida escribirle.
+/// Las escrituras se serializan con un segundo Mutex, `writing`, que se toma antes que el de la sesión.
+#[derive(Debug)]
+pub struct TlsStream {
+    session: Arc<Mutex<ServerConnection>>,
+    writing: Arc<Mutex<()>>,
+    socket: TcpStream,
+}
+
+/// Toma el lock de la sesión TLS compartida o el de escritura.
+fn lock<T>(mutex: &Mutex<T>) -> Result<MutexGuard<'_, T>, Error> {
+    mutex
+        .lock()
+        .map_err(|_| Error::other("TLS session lock poisoned"))
+}
+
+impl TlsStream {
+    /// Escribe sobre el socket los registros TLS que generó la sesión.
+    ///
+    /// Los registros se obtienen con el lock de la sesión y se escriben luego de liberarlo. El lock de escritura
+    /// se mantiene durante todo el proceso, para que los registros lleguen al cliente en el orden en que se generaron.
+    fn write_pending_tls(&mut self) -> Result<(), Error> {
+        let _writing = lock(&self.writing)?;
+        let mut records = Vec::new();
+        {
+            let mut session = lock(&self.session)?;
+            while session.wants_write() {
+                session.write_tls(&mut records)?;
+            }
+        }
+        self.socket.write_all(&records)
+    }
+}
+
+impl Read for TlsStream {
+    /// Devuelve los datos descifrados disponibles. Si no los hay, lee registros TLS del socket hasta obtenerlos.
+    ///
+    /// Durante el handshake, las respuestas que genera la sesión se escriben sobre el socket antes de volver a leer.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        loop {
+            if lock(&self.session)?.wants_write() {
+                self.write_pending_tls()?;
+            }
+            match lock(&self.session)?.reader().read(buf) {
+                Ok(size) => return Ok(size),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {}
+                Err(e) => return Err(e),
+            }
+
+            let mut raw = [0u8; TLS_READ_BUFFER];
+            let size = self.socket.read(&mut raw)?;
+            if size == 0 {
+                return Ok(0);
+            }
+            let mut session = lock(&self.session)?;
+            let mut data = &raw[..size];
+            while !data.is_empty() {
+                session.read_tls(&mut data)?;
+                if let Err(e) = session.process_new_packets() {
+                    // se intenta avisar al cliente el motivo del error antes de cerrar la conexión
+                    drop(session);
+                    let _ = self.write_pending_tls();
+                    return Err(Error::new(ErrorKind::InvalidData, e));
+                }
+            }
+        }
+    }
+}
+
+impl Write for TlsStream {
+    /// Cifra los datos y los escribe sobre el socket.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let size = lock(&self.session)?.writer().write(buf)?;
+        self.write_pending_tls()?;
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        lock(&self.session)?.writer().flush()?;
+        self.write_pending_tls()?;
+        self.socket.flush()
+    }
+}
+
+#[test]
+fn test_01_local_connections_are_loopback() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let _client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (stream, _) = listener.accept().unwrap();
+    assert!(Connection::Tcp(stream).is_loopback());
+
+    let (stream, _) = UnixStream::pair().unwrap();
+    assert!(Connection::Unix(stream).is_loopback());
+}
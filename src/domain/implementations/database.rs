@@ -1,12 +1,15 @@
 //! Base de datos.
 
+use crate::domain::entities::cluster::key_hash_slot;
 use crate::domain::entities::key_value_item::{KeyAccessTime, ValueTimeItem};
 use crate::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
-use crate::domain::entities::key_value_item_serialized::KeyValueItemSerialized;
-use crate::errors::database_error::DatabaseError;
+use crate::domain::entities::key_value_item_serialized::{serialize_item, KeyValueItemSerialized};
+use crate::domain::entities::keyspace_events::{EventClass, KeyspaceEvents, KeyspaceNotification};
+use crate::domain::implementations::storage::{MemoryStorage, Storage};
+use crate::errors::database_error::{DatabaseError, WRONGTYPE};
 use crate::services::utils::glob_pattern;
-use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
+use crate::services::utils::random;
+use std::collections::HashSet;
 use std::fs::File;
 use std::fs::OpenOptions;
 use std::io::BufRead;
@@ -16,16 +19,32 @@ use std::path::Path;
 use std::str::FromStr;
 use std::time::SystemTime;
 
-/// Estructura que almacena todos los datos. Está compuesta por la dirección del archivo donde periodicamente se bajan los datos y por un HashMap que contiene
-/// todos los datos en la forma (clave, valor), siendo la clave de tipo `String` y el valor de tipo `ValueTimeItem`.
+/// Longitud máxima, en bytes, que puede alcanzar un valor de tipo String.
+const MAX_STRING_LENGTH: usize = 512 * 1024 * 1024;
+
+/// Pares (elemento, valor de la clave externa asociada) con los que se ordena con `sort by`.
+pub type ExternalValues = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// Estructura que almacena todos los datos. Está compuesta por la dirección del archivo donde periodicamente se bajan los datos y por el motor de
+/// almacenamiento (ver `Storage`) que contiene todos los datos en la forma (clave, valor), siendo la clave de tipo `Vec<u8>` y el valor de tipo `ValueTimeItem`.
+///
+/// Además guarda los mensajes que notifican los eventos sobre las claves, según los eventos configurados con
+/// `notify-keyspace-events`, hasta que se los retira para publicarlos (ver `take_notifications`), y las claves
+/// modificadas, hasta que se las retira para invalidarlas a los clientes que las siguen (ver `take_modified_keys`).
+/// También guarda las claves que eliminó por sí misma, al expirar o al desalojarlas, hasta que se las retira para
+/// propagar su eliminación a las réplicas (ver `take_deleted_keys`).
 #[derive(Debug)]
 pub struct Database {
     dbfilename: String,
-    items: HashMap<String, ValueTimeItem>,
+    items: Box<dyn Storage>,
+    notify_keyspace_events: KeyspaceEvents,
+    notifications: Vec<KeyspaceNotification>,
+    modified_keys: Vec<Vec<u8>>,
+    deleted_keys: Vec<Vec<u8>>,
 }
 
 impl Database {
-    /// Crea una nueva instancia de Database.
+    /// Crea una nueva instancia de Database, que almacena los datos en memoria (ver `MemoryStorage`).
     ///
     /// Añade todos los datos almacenados en el archivo `filename`.
     ///
@@ -36,14 +55,54 @@ impl Database {
     /// # let _ = std::fs::remove_file("dummy_db.csv");
     /// ```
     pub fn new(filename: String) -> Database {
+        Database::with_storage(filename, Box::new(MemoryStorage::new()))
+    }
+
+    /// Crea una nueva instancia de Database que almacena los datos en el motor `storage`.
+    ///
+    /// Si el motor no tiene datos, añade todos los datos almacenados en el archivo `filename`; así, un motor
+    /// persistente vacío comienza con los datos del último *dump*.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// use proyecto_taller_1::domain::implementations::database::Database;
+    /// use proyecto_taller_1::domain::implementations::log_storage::LogStorage;
+    ///
+    /// let storage = LogStorage::open("dummy_db_with_storage.log").unwrap();
+    /// let db = Database::with_storage("dummy_db_with_storage.csv".to_string(), Box::new(storage));
+    /// assert!(db.has_persistent_storage());
+    /// # drop(db);
+    /// # let _ = std::fs::remove_file("dummy_db_with_storage.csv");
+    /// # let _ = std::fs::remove_file("dummy_db_with_storage.log");
+    /// ```
+    pub fn with_storage(filename: String, storage: Box<dyn Storage>) -> Database {
         let mut db = Database {
             dbfilename: filename,
-            items: HashMap::new(),
+            items: storage,
+            notify_keyspace_events: KeyspaceEvents::default(),
+            notifications: Vec::new(),
+            modified_keys: Vec::new(),
+            deleted_keys: Vec::new(),
         };
-        db.load_items();
+        if db.items.is_empty() {
+            db.load_items();
+        }
         db
     }
 
+    /// Indica si el motor de almacenamiento conserva los datos entre reinicios, en cuyo caso no hace falta
+    /// bajarlos periódicamente al archivo *dump*.
+    pub fn has_persistent_storage(&self) -> bool {
+        self.items.is_persistent()
+    }
+
+    /// Guarda en el motor de almacenamiento las modificaciones pendientes (ver `Storage::sync`).
+    ///
+    /// Devuelve error si no pudieron guardarse.
+    pub fn sync_storage(&mut self) -> io::Result<()> {
+        self.items.sync()
+    }
+
     /// Retorna la dirección del archivo database.
     ///
     /// # Ejemplo
@@ -70,19 +129,19 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// # let mut db = Database::new("dummy_db_items.csv".to_string());
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".to_string())).build();
-    /// db.add("tipo_mascota".to_string(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build();
+    /// db.add(b"tipo_mascota".to_vec(), vt);
     ///
-    /// match db.get_live_item("tipo_mascota").unwrap().get_value() {
-    ///     ValueType::StringType(tipo) => {assert_eq!(tipo, &String::from("perro"))}
+    /// match db.get_live_item(b"tipo_mascota").unwrap().get_value() {
+    ///     ValueType::StringType(tipo) => {assert_eq!(tipo, b"perro")}
     ///     _ => assert!(false)
     /// }
     /// # let _ = std::fs::remove_file("dummy_db_items.csv");
     /// ```
-    pub fn get_live_item(&mut self, key: &str) -> Option<&ValueTimeItem> {
+    pub fn get_live_item(&mut self, key: &[u8]) -> Option<&ValueTimeItem> {
         let (items, expired) = self.check_timeout_item(key);
         if items.is_some() && expired {
-            self.items.remove(key).take();
+            self.remove_expired_key(key);
         }
         self.items.get(key)
     }
@@ -96,19 +155,19 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// # let mut db = Database::new("dummy_db_items.csv".to_string());
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".to_string())).build();
-    /// db.add("tipo_mascota".to_string(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build();
+    /// db.add(b"tipo_mascota".to_vec(), vt);
     ///
-    /// match db.get_mut_live_item("tipo_mascota").unwrap().get_value() {
-    ///     ValueType::StringType(tipo) => {assert_eq!(tipo, &String::from("perro"))}
+    /// match db.get_mut_live_item(b"tipo_mascota").unwrap().get_value() {
+    ///     ValueType::StringType(tipo) => {assert_eq!(tipo, b"perro")}
     ///     _ => assert!(false)
     /// }
     /// # let _ = std::fs::remove_file("dummy_db_items.csv");
     /// ```
-    pub fn get_mut_live_item(&mut self, key: &str) -> Option<&mut ValueTimeItem> {
+    pub fn get_mut_live_item(&mut self, key: &[u8]) -> Option<&mut ValueTimeItem> {
         let (items, expired) = self.check_timeout_item(key);
         if items.is_some() && expired {
-            self.items.remove(key);
+            self.remove_expired_key(key);
         }
         self.items.get_mut(key)
     }
@@ -123,14 +182,14 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// # let mut db = Database::new("dummy_db_check_timeout.csv".to_string());
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".to_string())).build();
-    /// db.add("tipo_mascota".to_string(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build();
+    /// db.add(b"tipo_mascota".to_vec(), vt);
     ///
-    /// assert!(db.check_timeout_item("tipo_mascota").0.is_some());
-    /// assert_eq!(db.check_timeout_item("tipo_mascota").1,false);
+    /// assert!(db.check_timeout_item(b"tipo_mascota").0.is_some());
+    /// assert_eq!(db.check_timeout_item(b"tipo_mascota").1,false);
     /// # let _ = std::fs::remove_file("dummy_db_check_timeout.csv");
     /// ```
-    pub fn check_timeout_item(&self, key: &str) -> (Option<&ValueTimeItem>, bool) {
+    pub fn check_timeout_item(&self, key: &[u8]) -> (Option<&ValueTimeItem>, bool) {
         let option_item = self.items.get(key);
         if let Some(item) = option_item {
             let expired = item.is_expired();
@@ -147,15 +206,16 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// # let mut db = Database::new("dummy_db_clean.csv".to_string());
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".to_string())).build();
-    /// db.add("tipo_mascota".to_string(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build();
+    /// db.add(b"tipo_mascota".to_vec(), vt);
     /// db.clean_items();
-    /// assert!(db.get_live_item("tipo_mascota").is_none());
+    /// assert!(db.get_live_item(b"tipo_mascota").is_none());
     /// # let _ = std::fs::remove_file("dummy_db_clean.csv");
     /// ```
-    pub fn clean_items(&mut self) -> &HashMap<String, ValueTimeItem> {
+    pub fn clean_items(&mut self) {
+        self.modified_keys
+            .extend(self.items.iter().map(|(key, _)| key.to_vec()));
         self.items.clear();
-        &self.items
     }
 
     /// Devuelve todas las claves que coinciden con el patrón.
@@ -166,40 +226,88 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// # let mut db = Database::new("dummy_db_matching_keys.csv".to_string());
-    /// let mut list = vec![String::from("pedro"), String::from("luis"), String::from("juan"), String::from("pepe")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("amigos".to_string(), vt);
-    ///
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("25".to_string())).build();
-    /// db.add("edad_pedro".to_string(), vt);
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("23".to_string())).build();
-    /// db.add("edad_luis".to_string(), vt);
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("35".to_string())).build();
-    /// db.add("edad_juan".to_string(), vt);
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("22".to_string())).build();
-    /// db.add("ed_pepe".to_string(), vt);
-    ///
-    /// let matching = db.get_keys_that_match_pattern("edad*");
-    /// assert!(matching.contains(&"edad_pedro".to_string()));
-    /// assert!(matching.contains(&"edad_luis".to_string()));
-    /// assert!(matching.contains(&"edad_juan".to_string()));
-    /// assert!(!matching.contains(&"ed_pepe".to_string()));
+    /// let mut list = vec![b"pedro".to_vec(), b"luis".to_vec(), b"juan".to_vec(), b"pepe".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"amigos".to_vec(), vt);
+    ///
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("25".into())).build();
+    /// db.add(b"edad_pedro".to_vec(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("23".into())).build();
+    /// db.add(b"edad_luis".to_vec(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("35".into())).build();
+    /// db.add(b"edad_juan".to_vec(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("22".into())).build();
+    /// db.add(b"ed_pepe".to_vec(), vt);
+    ///
+    /// let matching = db.get_keys_that_match_pattern(b"edad*");
+    /// assert!(matching.contains(&b"edad_pedro".to_vec()));
+    /// assert!(matching.contains(&b"edad_luis".to_vec()));
+    /// assert!(matching.contains(&b"edad_juan".to_vec()));
+    /// assert!(!matching.contains(&b"ed_pepe".to_vec()));
     ///
     /// # let _ = std::fs::remove_file("dummy_db_matching_keys.csv");
     /// ```
-    pub fn get_keys_that_match_pattern(&self, pattern: &str) -> Vec<String> {
+    pub fn get_keys_that_match_pattern(&self, pattern: &[u8]) -> Vec<Vec<u8>> {
         let mut matching_keys = vec![];
-        for key in &self.items {
-            let current_key = key.0.to_string();
-            if !key.1.is_expired()
-                && glob_pattern::g_match(pattern.as_bytes(), current_key.as_bytes())
-            {
-                matching_keys.push(current_key);
+        for (key, item) in self.items.iter() {
+            if !item.is_expired() && glob_pattern::g_match(pattern, key) {
+                matching_keys.push(key.to_vec());
             }
         }
         matching_keys
     }
 
+    /// Devuelve hasta `count` claves no expiradas que pertenecen al hash slot del cluster, sin un orden definido.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    /// use proyecto_taller_1::domain::entities::cluster::key_hash_slot;
+    ///
+    /// # let mut db = Database::new("dummy_db_keys_in_slot.csv".to_string());
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build();
+    /// db.add(b"{mascota}.tipo".to_vec(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("firulais".into())).build();
+    /// db.add(b"{mascota}.nombre".to_vec(), vt);
+    ///
+    /// let mut keys = db.get_keys_in_slot(key_hash_slot(b"mascota"), 10);
+    /// keys.sort();
+    /// assert_eq!(keys, vec![b"{mascota}.nombre".to_vec(), b"{mascota}.tipo".to_vec()]);
+    /// assert_eq!(db.get_keys_in_slot(key_hash_slot(b"mascota"), 1).len(), 1);
+    /// # let _ = std::fs::remove_file("dummy_db_keys_in_slot.csv");
+    /// ```
+    pub fn get_keys_in_slot(&self, slot: u16, count: usize) -> Vec<Vec<u8>> {
+        self.items
+            .iter()
+            .filter(|(key, item)| !item.is_expired() && key_hash_slot(key) == slot)
+            .take(count)
+            .map(|(key, _)| key.to_vec())
+            .collect()
+    }
+
+    /// Devuelve la cantidad de claves no expiradas que pertenecen al hash slot del cluster.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    /// use proyecto_taller_1::domain::entities::cluster::key_hash_slot;
+    ///
+    /// # let mut db = Database::new("dummy_db_count_keys_in_slot.csv".to_string());
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build();
+    /// db.add(b"{mascota}.tipo".to_vec(), vt);
+    ///
+    /// assert_eq!(db.count_keys_in_slot(key_hash_slot(b"mascota")), 1);
+    /// # let _ = std::fs::remove_file("dummy_db_count_keys_in_slot.csv");
+    /// ```
+    pub fn count_keys_in_slot(&self, slot: u16) -> usize {
+        self.items
+            .iter()
+            .filter(|(key, item)| !item.is_expired() && key_hash_slot(key) == slot)
+            .count()
+    }
+
     /// Devuelve si la clave existe en la base de datos.
     ///
     /// # Ejemplo
@@ -208,14 +316,14 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// # let mut db = Database::new("dummy_db_exists.csv".to_string());
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".to_string())).build();
-    /// db.add("tipo_mascota".to_string(), vt);
-    /// assert!(db.key_exists("tipo_mascota".to_string()));
-    /// assert!(!db.key_exists("nombre_mascota".to_string()));
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build();
+    /// db.add(b"tipo_mascota".to_vec(), vt);
+    /// assert!(db.key_exists(b"tipo_mascota".to_vec()));
+    /// assert!(!db.key_exists(b"nombre_mascota".to_vec()));
     /// # let _ = std::fs::remove_file("dummy_db_exists.csv");
     /// ```
-    pub fn key_exists(&mut self, key: String) -> bool {
-        return self.get_live_item(&key).is_some();
+    pub fn key_exists(&mut self, key: Vec<u8>) -> bool {
+        self.get_live_item(&key).is_some()
     }
 
     /// Devuelve si la clave existe en la base de datos y si está expirada.
@@ -229,14 +337,14 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// # let mut db = Database::new("dummy_db_exists_expired.csv".to_string());
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".to_string())).with_timeout(123).build();
-    /// db.add("tipo_mascota".to_string(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).with_timeout(123).build();
+    /// db.add(b"tipo_mascota".to_vec(), vt);
     ///
-    /// assert_eq!(db.key_exists_expired("tipo_mascota".to_string()).1,true);
-    /// assert!(!db.key_exists_expired("nombre_mascota".to_string()).0);
+    /// assert_eq!(db.key_exists_expired(b"tipo_mascota".to_vec()).1,true);
+    /// assert!(!db.key_exists_expired(b"nombre_mascota".to_vec()).0);
     /// # let _ = std::fs::remove_file("dummy_db_exists_expired.csv");
     /// ```
-    pub fn key_exists_expired(&self, key: String) -> (bool, bool) {
+    pub fn key_exists_expired(&self, key: Vec<u8>) -> (bool, bool) {
         let (items, expired) = self.check_timeout_item(&key);
         (items.is_some(), expired)
     }
@@ -251,15 +359,18 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// # let mut db = Database::new("dummy_db_key_expired.csv".to_string());
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".to_string())).with_timeout(123).build();
-    /// db.add("tipo_mascota".to_string(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).with_timeout(123).build();
+    /// db.add(b"tipo_mascota".to_vec(), vt);
     ///
-    /// db.remove_expired_key("tipo_mascota");
-    /// assert!(!db.key_exists_expired("tipo_mascota".to_string()).0);
+    /// db.remove_expired_key(b"tipo_mascota");
+    /// assert!(!db.key_exists_expired(b"tipo_mascota".to_vec()).0);
     /// # let _ = std::fs::remove_file("dummy_db_key_expired.csv");
     /// ```
-    pub fn remove_expired_key(&mut self, key: &str) {
-        self.items.remove(key);
+    pub fn remove_expired_key(&mut self, key: &[u8]) {
+        if self.items.delete(key).is_some() {
+            self.notify(EventClass::Expired, "expired", key);
+            self.deleted_keys.push(key.to_vec());
+        }
     }
 
     /// Agrega a la base de datos una `key` con un `value` asociado.
@@ -271,20 +382,186 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// # let mut db = Database::new("dummy_db_add.csv".to_string());
-    /// let mut list = vec![String::from("pedro"), String::from("luis"), String::from("juan"), String::from("pepe")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("amigos".to_string(), vt);
+    /// let mut list = vec![b"pedro".to_vec(), b"luis".to_vec(), b"juan".to_vec(), b"pepe".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"amigos".to_vec(), vt);
     ///
-    /// match db.get_live_item("amigos").unwrap().get_value() {
-    ///     ValueType::ListType(list) => {assert_eq!(list, &vec![String::from("pedro"), String::from("luis"), String::from("juan"), String::from("pepe")])}
+    /// match db.get_live_item(b"amigos").unwrap().get_value() {
+    ///     ValueType::ListType(list) => {assert_eq!(list, &vec![b"pedro".to_vec(), b"luis".to_vec(), b"juan".to_vec(), b"pepe".to_vec()])}
     ///     _ => assert!(false)
     /// }
     /// # let _ = std::fs::remove_file("dummy_db_add.csv");
     /// ```
-    pub fn add(&mut self, key: String, value: ValueTimeItem) {
-        self.items.insert(key, value);
+    pub fn add(&mut self, key: Vec<u8>, value: ValueTimeItem) {
+        if self.items.put(key.clone(), value).is_none() {
+            self.notify(EventClass::New, "new", &key);
+        }
+    }
+
+    /// Configura los eventos sobre las claves que se notifican (ver `KeyspaceEvents`).
+    pub fn set_notify_keyspace_events(&mut self, events: KeyspaceEvents) {
+        self.notify_keyspace_events = events;
+    }
+
+    /// Registra el evento `event` sobre la clave `key`, si se notifican los eventos de su clase.
+    ///
+    /// Los mensajes que lo notifican se guardan hasta que se retiran con `take_notifications`. La clave se registra
+    /// como modificada aunque no se notifiquen los eventos de su clase.
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::keyspace_events::{EventClass, KeyspaceEvents};
+    ///
+    /// # let mut db = Database::new("dummy_db_notify.csv".to_string());
+    /// db.notify(EventClass::Generic, "del", b"mascota");
+    /// assert!(db.take_notifications().is_empty());
+    ///
+    /// db.set_notify_keyspace_events(KeyspaceEvents::parse("Kg").unwrap());
+    /// db.notify(EventClass::Generic, "del", b"mascota");
+    /// let notifications = db.take_notifications();
+    /// assert_eq!(notifications[0].channel, "__keyspace@0__:mascota");
+    /// assert_eq!(notifications[0].message, "del");
+    /// # let _ = std::fs::remove_file("dummy_db_notify.csv");
+    /// ```
+    pub fn notify(&mut self, class: EventClass, event: &str, key: &[u8]) {
+        let notifications =
+            self.notify_keyspace_events
+                .notifications(class, event, &String::from_utf8_lossy(key));
+        self.notifications.extend(notifications);
+        self.modified_keys.push(key.to_vec());
+    }
+
+    /// Retira los mensajes que notifican los eventos registrados, en el orden en que ocurrieron.
+    pub fn take_notifications(&mut self) -> Vec<KeyspaceNotification> {
+        std::mem::take(&mut self.notifications)
+    }
+
+    /// Retira las claves modificadas desde la última vez que se retiraron, sin repetidas.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::keyspace_events::EventClass;
+    /// let mut db = Database::new("dummy_db_modified.csv".to_string());
+    /// db.notify(EventClass::Generic, "del", b"mascota");
+    /// db.notify(EventClass::String, "set", b"mascota");
+    /// assert_eq!(db.take_modified_keys(), vec![b"mascota".to_vec()]);
+    /// assert!(db.take_modified_keys().is_empty());
+    /// # let _ = std::fs::remove_file("dummy_db_modified.csv");
+    /// ```
+    pub fn take_modified_keys(&mut self) -> Vec<Vec<u8>> {
+        let mut keys = std::mem::take(&mut self.modified_keys);
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+
+    /// Retira las claves que la base de datos eliminó por sí misma, al expirar o al desalojarlas, en el orden en que
+    /// las eliminó.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    /// let mut db = Database::new("dummy_db_deleted.csv".to_string());
+    /// db.add(b"vencida".to_vec(), ValueTimeItemBuilder::new(ValueType::StringType("a".into())).with_timeout(123).build());
+    /// db.delete_key(b"otra".to_vec());
+    ///
+    /// assert!(db.get_live_item(b"vencida").is_none());
+    /// assert_eq!(db.take_deleted_keys(), vec![b"vencida".to_vec()]);
+    /// assert!(db.take_deleted_keys().is_empty());
+    /// # let _ = std::fs::remove_file("dummy_db_deleted.csv");
+    /// ```
+    pub fn take_deleted_keys(&mut self) -> Vec<Vec<u8>> {
+        std::mem::take(&mut self.deleted_keys)
+    }
+
+    /// Elimina todas las claves expiradas y devuelve la cantidad eliminada.
+    ///
+    /// Complementa a la eliminación de cada clave expirada al accederla, para que las claves que no se vuelven
+    /// a acceder no ocupen memoria y se notifique su evento `expired`. Solo se revisan las claves volátiles, según
+    /// el índice del motor de almacenamiento.
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    ///
+    /// # let mut db = Database::new("dummy_db_remove_expired_keys.csv".to_string());
+    /// db.add(b"vencida".to_vec(), ValueTimeItemBuilder::new(ValueType::StringType("a".into())).with_timeout(123).build());
+    /// db.add(b"vigente".to_vec(), ValueTimeItemBuilder::new(ValueType::StringType("b".into())).build());
+    ///
+    /// assert_eq!(db.remove_expired_keys(), 1);
+    /// assert_eq!(db.get_size(), 1);
+    /// # let _ = std::fs::remove_file("dummy_db_remove_expired_keys.csv");
+    /// ```
+    pub fn remove_expired_keys(&mut self) -> usize {
+        let expired: Vec<Vec<u8>> = self
+            .items
+            .volatile_keys()
+            .into_iter()
+            .filter(|key| self.items.get(key).is_some_and(ValueTimeItem::is_expired))
+            .collect();
+        for key in &expired {
+            self.remove_expired_key(key);
+        }
+        expired.len()
+    }
+
+    /// Retorna una estimación de la memoria, en bytes, que ocupan las claves y sus valores.
+    pub fn used_memory(&self) -> usize {
+        self.items
+            .iter()
+            .map(|(key, item)| key.len() + value_size(item.get_value()))
+            .sum()
     }
 
+    /// Desaloja las claves accedidas hace más tiempo hasta que la memoria estimada (ver `used_memory`) no supere `maxmemory`.
+    ///
+    /// Devuelve la cantidad de claves desalojadas. Si `maxmemory` es 0 no se desaloja ninguna.
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    ///
+    /// # let mut db = Database::new("dummy_db_evict_keys.csv".to_string());
+    /// db.add(b"vieja".to_vec(), ValueTimeItemBuilder::new(ValueType::StringType("a".into())).with_last_access_time(1).build());
+    /// db.add(b"nueva".to_vec(), ValueTimeItemBuilder::new(ValueType::StringType("b".into())).build());
+    ///
+    /// assert_eq!(db.evict_keys(0), 0);
+    /// assert_eq!(db.evict_keys(6), 1);
+    /// assert!(db.get_live_item(b"vieja").is_none());
+    /// # let _ = std::fs::remove_file("dummy_db_evict_keys.csv");
+    /// ```
+    pub fn evict_keys(&mut self, maxmemory: usize) -> usize {
+        let mut used_memory = self.used_memory();
+        if maxmemory == 0 || used_memory <= maxmemory {
+            return 0;
+        }
+        let mut candidates: Vec<(u64, Vec<u8>, usize)> = self
+            .items
+            .iter()
+            .map(|(key, item)| {
+                (
+                    *item.get_last_access_time(),
+                    key.to_vec(),
+                    key.len() + value_size(item.get_value()),
+                )
+            })
+            .collect();
+        candidates.sort_unstable();
+        let mut evicted = 0;
+        for (_, key, size) in candidates {
+            if used_memory <= maxmemory {
+                break;
+            }
+            self.items.delete(&key);
+            self.notify(EventClass::Evicted, "evicted", &key);
+            self.deleted_keys.push(key);
+            used_memory -= size;
+            evicted += 1;
+        }
+        evicted
+    }
     /// Busca los valores de las claves asociadas al patrón especificado.
     ///
     /// Reemplaza el "*" del patrón por cada elemento perteneciente a `key` y busca su valor asociado.
@@ -297,53 +574,47 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// # let mut db = Database::new("dummy_db_external_keys.csv".to_string());
-    /// let mut list = vec![String::from("pedro"), String::from("luis"), String::from("juan"), String::from("pepe")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("amigos".to_string(), vt);
-    ///
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("25".to_string())).build();
-    /// db.add("edad_pedro".to_string(), vt);
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("23".to_string())).build();
-    /// db.add("edad_luis".to_string(), vt);
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("35".to_string())).build();
-    /// db.add("edad_juan".to_string(), vt);
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("22".to_string())).build();
-    /// db.add("edad_pepe".to_string(), vt);
-    ///
-    /// let (values,expired) = db.get_values_of_keys_matching_pattern("edad_*".to_string(), "amigos".to_string());
-    /// assert!(values.contains(&("pedro".to_string(), "25".to_string())));
-    /// assert!(values.contains(&("luis".to_string(), "23".to_string())));
-    /// assert!(values.contains(&("juan".to_string(), "35".to_string())));
-    /// assert!(values.contains(&("pepe".to_string(), "22".to_string())));
+    /// let mut list = vec![b"pedro".to_vec(), b"luis".to_vec(), b"juan".to_vec(), b"pepe".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"amigos".to_vec(), vt);
+    ///
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("25".into())).build();
+    /// db.add(b"edad_pedro".to_vec(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("23".into())).build();
+    /// db.add(b"edad_luis".to_vec(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("35".into())).build();
+    /// db.add(b"edad_juan".to_vec(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("22".into())).build();
+    /// db.add(b"edad_pepe".to_vec(), vt);
+    ///
+    /// let (values,expired) = db.get_values_of_keys_matching_pattern(b"edad_*", b"amigos");
+    /// assert!(values.contains(&(b"pedro".to_vec(), b"25".to_vec())));
+    /// assert!(values.contains(&(b"luis".to_vec(), b"23".to_vec())));
+    /// assert!(values.contains(&(b"juan".to_vec(), b"35".to_vec())));
+    /// assert!(values.contains(&(b"pepe".to_vec(), b"22".to_vec())));
     ///
     /// # let _ = std::fs::remove_file("dummy_db_external_keys.csv");
     /// ```
     pub fn get_values_of_keys_matching_pattern(
         &self,
-        pat: String,
-        key: String,
-    ) -> (Vec<(String, String)>, Vec<String>) {
+        pat: &[u8],
+        key: &[u8],
+    ) -> (ExternalValues, Vec<Vec<u8>>) {
         let mut associated_values = Vec::new();
         let mut expired_items = Vec::new();
-        if let (Some(item), expired) = self.check_timeout_item(&key) {
+        if let (Some(item), expired) = self.check_timeout_item(key) {
             if expired {
-                expired_items.push(key);
+                expired_items.push(key.to_vec());
             }
-            let elements: Vec<String> = item
-                .get_value_as_vec()
-                .iter()
-                .map(|e| e.to_string())
-                .collect();
-            elements.iter().for_each(|element| {
-                let patterned_key = pat.replace('*', element.as_str());
-                if self.items.contains_key(&patterned_key) {
-                    if let ValueType::StringType(value) =
-                        self.items.get(&patterned_key).unwrap().get_value()
-                    {
-                        associated_values.push((element.to_string(), value.to_string()));
-                    }
+            let pattern_parts: Vec<&[u8]> = pat.split(|byte| *byte == b'*').collect();
+            for element in item.get_value_as_vec() {
+                let patterned_key = pattern_parts.join(element.as_slice());
+                if let Some(ValueType::StringType(value)) =
+                    self.items.get(&patterned_key).map(ValueTimeItem::get_value)
+                {
+                    associated_values.push((element, value.to_vec()));
                 }
-            });
+            }
         }
         (associated_values, expired_items)
     }
@@ -361,36 +632,80 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// # let mut db = Database::new("dummy_db_addtolist.csv".to_string());
-    /// let mut list = vec![String::from("pedro"), String::from("luis"), String::from("juan"), String::from("pepe")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("amigos".to_string(), vt);
+    /// let mut list = vec![b"pedro".to_vec(), b"luis".to_vec(), b"juan".to_vec(), b"pepe".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"amigos".to_vec(), vt);
     ///
-    /// let added = db.add_to_list_type(vec!["pepe".to_string(), "bruno".to_string()], "amigos", true).unwrap();
+    /// let added = db.add_to_list_type(vec![b"pepe".to_vec(), b"bruno".to_vec()], b"amigos", true).unwrap();
     /// assert_eq!(added, 6);
     ///
     /// # let _ = std::fs::remove_file("dummy_db_addtolist.csv");
     /// ```
     pub fn add_to_list_type(
         &mut self,
-        mut new_vec: Vec<String>,
-        key: &str,
+        new_vec: Vec<Vec<u8>>,
+        key: &[u8],
+        strict: bool,
+    ) -> Option<usize> {
+        if let Some(item) = self.get_mut_live_item(key) {
+            if let ValueType::ListType(current_value) = item.get_mut_value() {
+                for element in new_vec.into_iter().rev() {
+                    current_value.push_front(element);
+                }
+                let len = current_value.len();
+                self.notify(EventClass::List, "lpush", key);
+                return Some(len);
+            }
+        } else if !strict {
+            let vec_len = new_vec.len();
+            let vt_item = ValueTimeItemBuilder::new(ValueType::ListType(new_vec.into())).build();
+            self.add(key.to_vec(), vt_item);
+            self.notify(EventClass::List, "lpush", key);
+            return Some(vec_len);
+        }
+        None
+    }
+
+    /// Agrega nuevos elementos al final de una lista.
+    ///
+    /// A diferencia de `add_to_list_type`, esta función agrega los nuevos elementos al final de la lista.
+    /// Si `strict` es True, sólo agrega elementos a una clave ya existente, si la clave no existe no hace nada.
+    /// Si `strict` es False y la clave no existe, la crea.
+    /// Devuelve la cantidad de elementos de la lista luego de haber agregado los nuevos valores, o None si
+    /// el valor almacenado no es de tipo lista.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    ///
+    /// # let mut db = Database::new("dummy_db_appendtolist.csv".to_string());
+    /// let added = db.append_to_list_type(vec![b"pedro".to_vec(), b"luis".to_vec()], b"amigos", false).unwrap();
+    /// assert_eq!(added, 2);
+    ///
+    /// let added = db.append_to_list_type(vec![b"bruno".to_vec()], b"amigos", true).unwrap();
+    /// assert_eq!(added, 3);
+    /// assert_eq!(db.get_values_in_range(b"amigos", "0", "-1").unwrap(), vec![b"pedro".to_vec(), b"luis".to_vec(), b"bruno".to_vec()]);
+    ///
+    /// # let _ = std::fs::remove_file("dummy_db_appendtolist.csv");
+    /// ```
+    pub fn append_to_list_type(
+        &mut self,
+        new_vec: Vec<Vec<u8>>,
+        key: &[u8],
         strict: bool,
     ) -> Option<usize> {
-        if self.key_exists(key.to_string()) {
-            if let ValueType::ListType(current_value) =
-                self.get_live_item(key).unwrap().get_value().to_owned()
-            {
-                let mut old_vector = current_value;
-                new_vec.append(&mut old_vector);
-                let len = new_vec.len();
-                let vt_item = ValueTimeItemBuilder::new(ValueType::ListType(new_vec)).build();
-                self.add(key.to_string(), vt_item);
+        if let Some(item) = self.get_mut_live_item(key) {
+            if let ValueType::ListType(current_value) = item.get_mut_value() {
+                current_value.extend(new_vec);
+                let len = current_value.len();
+                self.notify(EventClass::List, "rpush", key);
                 return Some(len);
             }
         } else if !strict {
             let vec_len = new_vec.len();
-            let vt_item = ValueTimeItemBuilder::new(ValueType::ListType(new_vec)).build();
-            self.add(key.to_string(), vt_item);
+            let vt_item = ValueTimeItemBuilder::new(ValueType::ListType(new_vec.into())).build();
+            self.add(key.to_vec(), vt_item);
+            self.notify(EventClass::List, "rpush", key);
             return Some(vec_len);
         }
         None
@@ -406,27 +721,18 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_rpush.csv".to_string());
-    /// let mut list = vec![String::from("argentina"), String::from("brasil"), String::from("chile"), String::from("uruguay")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("paises".to_string(), vt);
+    /// let mut list = vec![b"argentina".to_vec(), b"brasil".to_vec(), b"chile".to_vec(), b"uruguay".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"paises".to_vec(), vt);
     ///
-    /// let len = db.push_vec_to_list(vec![String::from("bolivia"), String::from("paraguay")], "paises");
+    /// let len = db.push_vec_to_list(vec![b"bolivia".to_vec(), b"paraguay".to_vec()], b"paises");
     /// assert_eq!(len, 6);
     ///
     /// # std::fs::remove_file("dummy_db_rpush.csv");
     /// ```
-    pub fn push_vec_to_list(&mut self, new_elements: Vec<String>, key: &str) -> usize {
-        if let Some(item) = self.get_mut_live_item(key) {
-            if let ValueType::ListType(mut current_value) = item.get_copy_of_value() {
-                for element in new_elements {
-                    current_value.push(element);
-                }
-                let len = current_value.len();
-                item.set_value(ValueType::ListType(current_value));
-                return len;
-            }
-        }
-        0
+    pub fn push_vec_to_list(&mut self, new_elements: Vec<Vec<u8>>, key: &[u8]) -> usize {
+        self.append_to_list_type(new_elements, key, true)
+            .unwrap_or(0)
     }
 
     /// Reemplaza el elemento en la posición `index` de la lista.
@@ -440,31 +746,31 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_replace.csv".to_string());
-    /// let mut list = vec![String::from("argentina"), String::from("brasil"), String::from("chile")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("paises".to_string(), vt);
+    /// let mut list = vec![b"argentina".to_vec(), b"brasil".to_vec(), b"chile".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"paises".to_vec(), vt);
     ///
-    /// let replaced = db.replace_element_in_list_type_value("paises", "uruguay", "1");
+    /// let replaced = db.replace_element_in_list_type_value(b"paises", b"uruguay", "1");
     /// assert!(replaced);
     ///
     /// # std::fs::remove_file("dummy_db_replace.csv");
     /// ```
     pub fn replace_element_in_list_type_value(
         &mut self,
-        key: &str,
-        value: &str,
+        key: &[u8],
+        value: &[u8],
         index: &str,
     ) -> bool {
         if let Some(item) = self.get_mut_live_item(key) {
-            if let ValueType::ListType(mut current_value) = item.get_copy_of_value() {
+            if let ValueType::ListType(current_value) = item.get_mut_value() {
                 let current_value_len = current_value.len() as isize;
                 let mut current_index = index.parse::<isize>().unwrap_or(current_value_len + 1);
                 if current_index < 0 {
                     current_index += current_value_len;
                 };
-                if current_index < current_value_len {
-                    current_value[current_index as usize] = value.to_string();
-                    item.set_value(ValueType::ListType(current_value));
+                if current_index >= 0 && current_index < current_value_len {
+                    current_value[current_index as usize] = value.to_vec();
+                    self.notify(EventClass::List, "lset", key);
                     return true;
                 }
             }
@@ -476,6 +782,7 @@ impl Database {
     ///
     /// Busca los elementos cuya posición se encuentra en el rango [`lower_bound`, `upper_bound`].
     /// Si alguno de los extremos es negativo, le suma la longitud de la lista original.
+    /// Si el rango no contiene ningún elemento de la lista, devuelve una lista vacía.
     ///
     /// # Ejemplo
     /// ```
@@ -483,25 +790,24 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_range.csv".to_string());
-    /// let mut list = vec![String::from("argentina"), String::from("brasil"), String::from("chile")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("paises".to_string(), vt);
+    /// let mut list = vec![b"argentina".to_vec(), b"brasil".to_vec(), b"chile".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"paises".to_vec(), vt);
     ///
-    /// let values = db.get_values_in_range("paises", "1", "2").unwrap();
-    /// assert_eq!(values, vec!["brasil".to_string(), "chile".to_string()]);
+    /// let values = db.get_values_in_range(b"paises", "1", "2").unwrap();
+    /// assert_eq!(values, vec![b"brasil".to_vec(), b"chile".to_vec()]);
     ///
     /// # std::fs::remove_file("dummy_db_range.csv");
     /// ```
     pub fn get_values_in_range(
         &self,
-        key: &str,
+        key: &[u8],
         lower_bound: &str,
         upper_bound: &str,
-    ) -> Option<Vec<String>> {
+    ) -> Option<Vec<Vec<u8>>> {
         if let (Some(item), false) = self.check_timeout_item(key) {
-            if let ValueType::ListType(current_value) = item.get_value().to_owned() {
+            if let ValueType::ListType(current_value) = item.get_value() {
                 let current_value_len = current_value.len() as isize;
-                let mut vec_values_selected_by_index = vec![];
                 let mut lb = lower_bound.parse::<isize>().unwrap_or(0);
                 let mut ub = upper_bound.parse::<isize>().unwrap_or(current_value_len);
                 if lb < 0 {
@@ -510,20 +816,21 @@ impl Database {
                 if ub < 0 {
                     ub += current_value_len;
                 }
-                if ub > lb {
-                    if ub <= current_value_len {
-                        for j in lb..(ub + 1) {
-                            vec_values_selected_by_index.push(current_value[j as usize].to_owned());
-                        }
-                    } else {
-                        for j in lb..current_value_len {
-                            vec_values_selected_by_index.push(current_value[j as usize].to_owned());
-                        }
-                    }
-                    Some(vec_values_selected_by_index)
-                } else {
-                    Some(vec!["".to_string()])
+                if lb < 0 {
+                    lb = 0;
+                }
+                if ub >= current_value_len {
+                    ub = current_value_len - 1;
                 }
+                if lb > ub {
+                    return Some(vec![]);
+                }
+                Some(
+                    current_value
+                        .range(lb as usize..=ub as usize)
+                        .map(|value| value.to_owned())
+                        .collect(),
+                )
             } else {
                 None
             }
@@ -534,8 +841,9 @@ impl Database {
 
     /// Devuelve el valor de la lista en la posición `index`.
     ///
-    /// Devuelve None si el valor almacenado en `key` no es de tipo lista.
+    /// Devuelve error si el valor almacenado en `key` no es de tipo lista.
     /// Si el indice es menor a 0, le suma la longitud original de la lista.
+    /// Si el indice queda fuera del rango de la lista, devuelve `DatabaseError::MissingKey`.
     ///
     /// # Ejemplo
     /// ```
@@ -543,34 +851,36 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_index.csv".to_string());
-    /// let mut list = vec![String::from("argentina"), String::from("brasil"), String::from("chile")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("paises".to_string(), vt);
+    /// let mut list = vec![b"argentina".to_vec(), b"brasil".to_vec(), b"chile".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"paises".to_vec(), vt);
     ///
-    /// let value = db.get_value_by_index("paises", "1").unwrap();
-    /// assert_eq!(value, "brasil".to_string());
+    /// let value = db.get_value_by_index(b"paises", "1").unwrap();
+    /// assert_eq!(value, b"brasil".to_vec());
     ///
     /// # std::fs::remove_file("dummy_db_index.csv");
     /// ```
-    pub fn get_value_by_index(&self, key: &str, index: &str) -> Result<String, DatabaseError> {
+    pub fn get_value_by_index(&self, key: &[u8], index: &str) -> Result<Vec<u8>, DatabaseError> {
         if let (Some(item), false) = self.check_timeout_item(key) {
-            let mut index_aux = index.parse::<isize>().unwrap();
-            if let ValueType::ListType(current_value) = item.get_value().to_owned() {
+            let mut index_aux = index.parse::<isize>().map_err(|_| {
+                DatabaseError::InvalidParameter(
+                    "value is not an integer or out of range".to_string(),
+                )
+            })?;
+            if let ValueType::ListType(current_value) = item.get_value() {
                 let current_value_len = current_value.len() as isize;
-                if index_aux >= current_value_len {
-                    return Err(DatabaseError::InvalidValueType(
-                        "index out of bounds".to_string(),
-                    ));
-                }
                 if index_aux < 0 {
                     index_aux += current_value_len;
                 };
+                if index_aux < 0 || index_aux >= current_value_len {
+                    return Err(DatabaseError::MissingKey());
+                }
                 Ok(current_value[index_aux as usize].to_owned())
             } else {
-                Err(DatabaseError::InvalidValueType("Not list type".to_string()))
+                Err(DatabaseError::InvalidValueType(WRONGTYPE.to_string()))
             }
         } else {
-            Ok("".to_string())
+            Ok(Vec::new())
         }
     }
 
@@ -593,17 +903,17 @@ impl Database {
     ///
     /// // Agrego los datos en la base de datos
     /// let mut db = Database::new("dummy_db_doc_reboot.csv".to_string());
-    /// db.add("altura_juan".to_string(),ValueTimeItemBuilder::new(
-    /// ValueType::StringType("1.78".to_string())
+    /// db.add(b"altura_juan".to_vec(),ValueTimeItemBuilder::new(
+    /// ValueType::StringType("1.78".into())
     /// ).build());
     ///
-    /// let time_before_reboot = db.get_live_item("altura_juan").unwrap().get_last_access_time().clone();
+    /// let time_before_reboot = db.get_live_item(b"altura_juan").unwrap().get_last_access_time().clone();
     ///
     /// println!("Antes de la actualización: {}", time_before_reboot);
     /// sleep(Duration::from_secs(2));
     ///
     /// //Reseteo el tiempo de acceso a la key: "altura_juan"
-    /// let res = db.reboot_time("altura_juan".to_string());
+    /// let res = db.reboot_time(b"altura_juan".to_vec());
     ///
     /// match res {
     ///     Some(item) => { assert!(item.get_last_access_time() > &time_before_reboot); }
@@ -628,14 +938,14 @@ impl Database {
     ///  .duration_since(SystemTime::UNIX_EPOCH)
     ///   .unwrap().as_secs();
     ///
-    /// db.add("altura_juan".to_string(),ValueTimeItemBuilder::new(
-    /// ValueType::StringType("1.78".to_string())).with_timeout(timeout).build());
+    /// db.add(b"altura_juan".to_vec(),ValueTimeItemBuilder::new(
+    /// ValueType::StringType("1.78".into())).with_timeout(timeout).build());
     ///
     /// //Dejo vencer la key
     /// sleep(Duration::from_secs(1));
     ///
     /// //Seteo last_access_time
-    /// let res = db.reboot_time("altura_juan".to_string());
+    /// let res = db.reboot_time(b"altura_juan".to_vec());
     ///
     /// match res {
     ///     None => assert!(true),
@@ -644,7 +954,7 @@ impl Database {
     ///
     /// let _ = std::fs::remove_file("dummy_db_doc_reboot2.csv");
     /// ```
-    pub fn reboot_time(&mut self, key: String) -> Option<&mut ValueTimeItem> {
+    pub fn reboot_time(&mut self, key: Vec<u8>) -> Option<&mut ValueTimeItem> {
         let mut item = self.get_mut_live_item(&key);
         if let Some(item) = &mut item {
             item.reboot_last_access_time();
@@ -660,16 +970,16 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_valuetype.csv".to_string());
-    /// let mut list = vec![String::from("argentina"), String::from("brasil"), String::from("chile")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("paises".to_string(), vt);
+    /// let mut list = vec![b"argentina".to_vec(), b"brasil".to_vec(), b"chile".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"paises".to_vec(), vt);
     ///
-    /// let value_type = db.get_type_of_value("paises".to_string());
+    /// let value_type = db.get_type_of_value(b"paises".to_vec());
     /// assert_eq!(value_type, "list".to_string());
     ///
     /// # std::fs::remove_file("dummy_db_valuetype.csv");
     /// ```
-    pub fn get_type_of_value(&self, key: String) -> String {
+    pub fn get_type_of_value(&self, key: Vec<u8>) -> String {
         if let Some(value) = self.items.get(&key) {
             value.get_value_type()
         } else {
@@ -688,26 +998,27 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, KeyAccessTime, ValueType, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_copy.csv".to_string());
-    /// db.add("dolly".to_string(),ValueTimeItemBuilder::new(
-    /// ValueType::StringType("sheep".to_string())).build()
+    /// db.add(b"dolly".to_vec(),ValueTimeItemBuilder::new(
+    /// ValueType::StringType("sheep".into())).build()
     /// );
-    /// db.copy(String::from("dolly"), String::from("clone"), true);
+    /// db.copy(b"dolly".to_vec(), b"clone".to_vec(), true);
     ///
-    /// let copied = db.get_live_item("clone").unwrap();
+    /// let copied = db.get_live_item(b"clone").unwrap();
     /// if let ValueType::StringType(str) = copied.get_value() {
-    ///    assert_eq!(str, &String::from("sheep"));
+    ///    assert_eq!(str, b"sheep");
     /// }
     ///
     /// # std::fs::remove_file("dummy_db_copy.csv");
     /// ```
-    pub fn copy(&mut self, source: String, destination: String, replace: bool) -> Option<()> {
-        return if let Some(source_item) = self.get_live_item(&source) {
+    pub fn copy(&mut self, source: Vec<u8>, destination: Vec<u8>, replace: bool) -> Option<()> {
+        if let Some(source_item) = self.get_live_item(&source) {
             let new_value = source_item.get_copy_of_value();
             let timeout = source_item.get_copy_of_timeout();
             match self.get_mut_live_item(&destination) {
                 Some(dest) => {
                     if replace {
                         dest.set_value(new_value);
+                        self.notify(EventClass::Generic, "copy_to", &destination);
                         Some(())
                     } else {
                         None
@@ -715,17 +1026,18 @@ impl Database {
                 }
                 None => {
                     self.add(
-                        destination,
+                        destination.to_vec(),
                         ValueTimeItemBuilder::new(new_value)
                             .with_key_access_time(timeout)
                             .build(),
                     );
+                    self.notify(EventClass::Generic, "copy_to", &destination);
                     Some(())
                 }
             }
         } else {
             None
-        };
+        }
     }
 
     /// Persiste una clave.
@@ -739,19 +1051,23 @@ impl Database {
     /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, KeyAccessTime, ValueType, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_persist.csv".to_string());
-    /// db.add("dolly".to_string(), ValueTimeItemBuilder::new(
-    ///     ValueType::StringType("sheep".to_string())).with_timeout(1825601548).build()
+    /// db.add(b"dolly".to_vec(), ValueTimeItemBuilder::new(
+    ///     ValueType::StringType("sheep".into())).with_timeout(1825601548).build()
     /// );
     ///
-    /// assert!(db.persist("dolly".to_string()));
+    /// assert!(db.persist(b"dolly".to_vec()));
     ///
     /// # std::fs::remove_file("dummy_db_persist.csv");
     /// ```
-    pub fn persist(&mut self, key: String) -> bool {
-        match self.get_mut_live_item(&key) {
+    pub fn persist(&mut self, key: Vec<u8>) -> bool {
+        let persisted = match self.get_mut_live_item(&key) {
             Some(item) => item.make_persistent(),
             None => false,
+        };
+        if persisted {
+            self.notify(EventClass::Generic, "persist", &key);
         }
+        persisted
     }
 
     /// Renombra una clave.
@@ -766,27 +1082,29 @@ impl Database {
     ///
     /// // Agrego los datos en la base de datos
     /// let mut db = Database::new("dummy_db_rename.csv".to_string());
-    /// db.add("dolly".to_string(),ValueTimeItemBuilder::new(
-    /// ValueType::StringType("sheep".to_string())
+    /// db.add(b"dolly".to_vec(),ValueTimeItemBuilder::new(
+    /// ValueType::StringType("sheep".into())
     /// ).build());
-    /// let renamed = db.rename_key(String::from("dolly"), String::from("newdolly"));
+    /// let renamed = db.rename_key(b"dolly".to_vec(), b"newdolly".to_vec());
     ///
     /// assert_eq!(renamed, true);
     ///
     /// let _ = std::fs::remove_file("dummy_db_rename.csv");
     /// ```
-    pub fn rename_key(&mut self, current_key: String, new_key: String) -> bool {
+    pub fn rename_key(&mut self, current_key: Vec<u8>, new_key: Vec<u8>) -> bool {
         let item = self.get_mut_live_item(&current_key);
         if let Some(item) = item {
             let item_value = item.get_copy_of_value();
             let item_time = item.get_copy_of_timeout();
-            self.delete_key(current_key);
+            self.items.delete(&current_key);
+            self.notify(EventClass::Generic, "rename_from", &current_key);
             self.add(
-                new_key,
+                new_key.to_vec(),
                 ValueTimeItemBuilder::new(item_value)
                     .with_key_access_time(item_time)
                     .build(),
             );
+            self.notify(EventClass::Generic, "rename_to", &new_key);
             true
         } else {
             false
@@ -806,32 +1124,33 @@ impl Database {
     ///
     /// // Agrego los datos en la base de datos
     /// let mut db = Database::new("dummy_db_append.csv".to_string());
-    /// db.add("field".to_string(),
-    /// ValueTimeItemBuilder::new(ValueType::StringType("first".to_string())).build());
+    /// db.add(b"field".to_vec(),
+    /// ValueTimeItemBuilder::new(ValueType::StringType("first".into())).build());
     ///
-    /// let len = db.append_string("field", "name");
+    /// let len = db.append_string(b"field", b"name");
     ///
     /// assert_eq!(len, 9);
     ///
     /// let _ = std::fs::remove_file("dummy_db_append.csv");
     /// ```
-    pub fn append_string(&mut self, key: &str, string: &str) -> usize {
-        match self.get_mut_live_item(&key.to_string()) {
+    pub fn append_string(&mut self, key: &[u8], string: &[u8]) -> usize {
+        match self.get_mut_live_item(key) {
             Some(item) => {
-                if let ValueType::StringType(old_value) = item.get_copy_of_value() {
-                    let len = old_value.len() + string.len();
-                    let new_value = ValueType::StringType(old_value + string);
-                    item.set_value(new_value);
+                if let ValueType::StringType(old_value) = item.get_mut_value() {
+                    old_value.extend_from_slice(string);
+                    let len = old_value.len();
+                    self.notify(EventClass::String, "append", key);
                     len
                 } else {
                     0
                 }
             }
             None => {
-                self.items.insert(
-                    key.to_string(),
-                    ValueTimeItemBuilder::new(ValueType::StringType(string.to_string())).build(),
+                self.add(
+                    key.to_vec(),
+                    ValueTimeItemBuilder::new(ValueType::StringType(string.to_vec())).build(),
                 );
+                self.notify(EventClass::String, "append", key);
                 string.len()
             }
         }
@@ -851,23 +1170,28 @@ impl Database {
     ///
     /// // Agrego los datos en la base de datos
     /// let mut db = Database::new("dummy_db_decrement.csv".to_string());
-    /// db.add("edad".to_string(),ValueTimeItemBuilder::new(
-    /// ValueType::StringType("25".to_string())
+    /// db.add(b"edad".to_vec(),ValueTimeItemBuilder::new(
+    /// ValueType::StringType("25".into())
     /// ).build());
-    /// let nueva_edad = db.decrement_key_by("edad", 10).unwrap();
+    /// let nueva_edad = db.decrement_key_by(b"edad", 10).unwrap();
     ///
     /// assert_eq!(nueva_edad, 15);
     ///
     /// let _ = std::fs::remove_file("dummy_db_decrement.csv");
     /// ```
-    pub fn decrement_key_by(&mut self, key: &str, decr: i64) -> Result<i64, DatabaseError> {
-        match self.get_mut_live_item(&key.to_string()) {
+    pub fn decrement_key_by(&mut self, key: &[u8], decr: i64) -> Result<i64, DatabaseError> {
+        match self.get_mut_live_item(key) {
             Some(item) => {
                 if let ValueType::StringType(str) = item.get_copy_of_value() {
-                    if let Ok(str_as_number) = str.parse::<i64>() {
-                        let new_value = ValueType::StringType((str_as_number - decr).to_string());
-                        item.set_value(new_value);
-                        Ok(str_as_number - decr)
+                    if let Some(str_as_number) = parse_integer(&str) {
+                        let result = str_as_number.checked_sub(decr).ok_or_else(|| {
+                            DatabaseError::InvalidParameter(String::from(
+                                "increment or decrement would overflow",
+                            ))
+                        })?;
+                        item.set_value(ValueType::StringType(result.to_string().into_bytes()));
+                        self.notify(EventClass::String, "incrby", key);
+                        Ok(result)
                     } else {
                         Err(DatabaseError::InvalidParameter(String::from(
                             "Value at key can't be represented as integer",
@@ -881,11 +1205,17 @@ impl Database {
                 }
             }
             None => {
-                let new_value = 0 - decr;
-                self.items.insert(
-                    key.to_string(),
-                    ValueTimeItemBuilder::new(ValueType::StringType(new_value.to_string())).build(),
+                let new_value = 0i64.checked_sub(decr).ok_or_else(|| {
+                    DatabaseError::InvalidParameter(String::from(
+                        "increment or decrement would overflow",
+                    ))
+                })?;
+                self.add(
+                    key.to_vec(),
+                    ValueTimeItemBuilder::new(ValueType::StringType(new_value.to_string().into()))
+                        .build(),
                 );
+                self.notify(EventClass::String, "incrby", key);
                 Ok(new_value)
             }
         }
@@ -905,43 +1235,114 @@ impl Database {
     ///
     /// // Agrego los datos en la base de datos
     /// let mut db = Database::new("dummy_db_increment.csv".to_string());
-    /// db.add("edad".to_string(),
-    /// ValueTimeItemBuilder::new(ValueType::StringType("25".to_string())).build()
+    /// db.add(b"edad".to_vec(),
+    /// ValueTimeItemBuilder::new(ValueType::StringType("25".into())).build()
     /// );
-    /// let nueva_edad = db.increment_key_by("edad", 10).unwrap();
+    /// let nueva_edad = db.increment_key_by(b"edad", 10).unwrap();
     ///
     /// assert_eq!(nueva_edad, 35);
     ///
     /// let _ = std::fs::remove_file("dummy_db_increment.csv");
     /// ```
-    pub fn increment_key_by(&mut self, key: &str, incr: i64) -> Result<i64, DatabaseError> {
-        if let Some(item) = self.get_mut_live_item(&key.to_string()) {
+    pub fn increment_key_by(&mut self, key: &[u8], incr: i64) -> Result<i64, DatabaseError> {
+        if let Some(item) = self.get_mut_live_item(key) {
             if let ValueType::StringType(str) = item.get_copy_of_value() {
-                if let Ok(str_as_number) = str.parse::<i64>() {
-                    let new_value = ValueType::StringType((str_as_number + incr).to_string());
-                    item.set_value(new_value);
-                    Ok(str_as_number + incr)
+                if let Some(str_as_number) = parse_integer(&str) {
+                    let result = str_as_number.checked_add(incr).ok_or_else(|| {
+                        DatabaseError::InvalidParameter(String::from(
+                            "increment or decrement would overflow",
+                        ))
+                    })?;
+                    item.set_value(ValueType::StringType(result.to_string().into_bytes()));
+                    self.notify(EventClass::String, "incrby", key);
+                    Ok(result)
                 } else {
                     Err(DatabaseError::InvalidParameter(String::from(
                         "Value at key can't be represented as integer",
                     )))
                 }
             } else {
-                return Err(DatabaseError::InvalidValueType(format!(
+                Err(DatabaseError::InvalidValueType(format!(
                     "Invalid value type. Expected: string. Got: {}",
                     item.get_value_type()
-                )));
+                )))
             }
         } else {
             let new_value = incr;
-            self.items.insert(
-                key.to_string(),
-                ValueTimeItemBuilder::new(ValueType::StringType(new_value.to_string())).build(),
+            self.add(
+                key.to_vec(),
+                ValueTimeItemBuilder::new(ValueType::StringType(new_value.to_string().into()))
+                    .build(),
             );
+            self.notify(EventClass::String, "incrby", key);
             Ok(new_value)
         }
     }
 
+    /// Incrementa el valor del numero decimal almacenado en la clave especificada.
+    ///
+    /// Si la clave no existe, se crea con el valor 0 y luego se realiza la operación.
+    /// Si el valor almacenado en la clave no es de tipo String, si no puede ser representado como un numero
+    /// decimal o si el resultado no es finito, devuelve error. Sino devuelve el nuevo valor como String.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::implementations::database::Database;
+    /// use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    ///
+    /// let mut db = Database::new("dummy_db_increment_float.csv".to_string());
+    /// db.add(b"altura".to_vec(),
+    /// ValueTimeItemBuilder::new(ValueType::StringType("1.5".into())).build()
+    /// );
+    /// let nueva_altura = db.increment_key_by_float(b"altura", 0.25).unwrap();
+    ///
+    /// assert_eq!(nueva_altura, "1.75".to_string());
+    ///
+    /// let _ = std::fs::remove_file("dummy_db_increment_float.csv");
+    /// ```
+    pub fn increment_key_by_float(
+        &mut self,
+        key: &[u8],
+        incr: f64,
+    ) -> Result<String, DatabaseError> {
+        let current = match self.get_live_item(key) {
+            Some(item) => match item.get_value() {
+                ValueType::StringType(str) => std::str::from_utf8(str)
+                    .ok()
+                    .and_then(|str| str.trim().parse::<f64>().ok())
+                    .ok_or_else(|| {
+                        DatabaseError::InvalidParameter(String::from("value is not a valid float"))
+                    })?,
+                _ => {
+                    return Err(DatabaseError::InvalidValueType(format!(
+                        "Invalid value type. Expected: string. Got: {}",
+                        item.get_value_type()
+                    )))
+                }
+            },
+            None => 0.0,
+        };
+        let result = current + incr;
+        if !result.is_finite() {
+            return Err(DatabaseError::InvalidParameter(String::from(
+                "increment would produce NaN or Infinity",
+            )));
+        }
+        let new_value = result.to_string();
+        match self.get_mut_live_item(key) {
+            Some(item) => item.set_value(ValueType::StringType(new_value.to_string().into())),
+            None => {
+                self.add(
+                    key.to_vec(),
+                    ValueTimeItemBuilder::new(ValueType::StringType(new_value.to_string().into()))
+                        .build(),
+                );
+            }
+        }
+        self.notify(EventClass::String, "incrbyfloat", key);
+        Ok(new_value)
+    }
+
     /// Devuelve el valor almacenado en `key` si dicho valor es de tipo String.
     ///
     /// Retorna Error si la clave no existe o si el valor almacenado no es de tipo String.
@@ -953,22 +1354,22 @@ impl Database {
     ///
     /// let mut db = Database::new("dummy_db_get_string".to_string());
     /// let vt_1 = ValueTimeItemBuilder::new(
-    ///    ValueType::StringType("hola".to_string())
+    ///    ValueType::StringType("hola".into())
     /// ).build();
     /// let vt_2 = ValueTimeItemBuilder::new(
-    ///    ValueType::ListType(vec!["hola".to_string(), "chau".to_string()])
+    ///    ValueType::ListType(vec![b"hola".to_vec(), b"chau".to_vec()].into())
     /// ).build();
-    /// db.add("saludo".to_string(), vt_1);
-    /// db.add("saludo_despido".to_string(), vt_2);
-    /// let aux = db.get_string_value_by_key("saludo").unwrap();
-    /// assert_eq!(aux, String::from("hola"));
-    /// let aux = db.get_string_value_by_key("saludo_despido");
+    /// db.add(b"saludo".to_vec(), vt_1);
+    /// db.add(b"saludo_despido".to_vec(), vt_2);
+    /// let aux = db.get_string_value_by_key(b"saludo").unwrap();
+    /// assert_eq!(aux, b"hola".to_vec());
+    /// let aux = db.get_string_value_by_key(b"saludo_despido");
     /// assert!(aux.is_err());
     ///
     /// let _ = std::fs::remove_file("dummy_db_get_string");
     /// ```
-    pub fn get_string_value_by_key(&self, key: &str) -> Result<String, DatabaseError> {
-        if let (Some(item), false) = self.check_timeout_item(&key.to_string()) {
+    pub fn get_string_value_by_key(&self, key: &[u8]) -> Result<Vec<u8>, DatabaseError> {
+        if let (Some(item), false) = self.check_timeout_item(key) {
             let value = item.get_copy_of_value();
             if let ValueType::StringType(str) = value {
                 return Ok(str);
@@ -992,16 +1393,16 @@ impl Database {
     /// use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_strlen.csv".to_string());
-    /// db.add("mykey".to_string(),ValueTimeItemBuilder::new(
-    /// ValueType::StringType("myvalue".to_string())
+    /// db.add(b"mykey".to_vec(),ValueTimeItemBuilder::new(
+    /// ValueType::StringType("myvalue".into())
     /// ).build());
-    /// let len = db.get_strlen_by_key("mykey").unwrap();
+    /// let len = db.get_strlen_by_key(b"mykey").unwrap();
     ///
     /// assert_eq!(len, 7);
     ///
     /// let _ = std::fs::remove_file("dummy_db_strlen.csv");
     /// ```
-    pub fn get_strlen_by_key(&self, key: &str) -> Option<usize> {
+    pub fn get_strlen_by_key(&self, key: &[u8]) -> Option<usize> {
         if let (Some(item), false) = self.check_timeout_item(key) {
             let value = item.get_copy_of_value();
             if let ValueType::StringType(str) = value {
@@ -1026,21 +1427,21 @@ impl Database {
     /// use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_getdel.csv".to_string());
-    /// db.add("mykey".to_string(),ValueTimeItemBuilder::new(
-    /// ValueType::StringType("myvalue".to_string())
+    /// db.add(b"mykey".to_vec(),ValueTimeItemBuilder::new(
+    /// ValueType::StringType("myvalue".into())
     /// ).build());
-    /// let deleted_value = db.getdel_value_by_key("mykey").unwrap();
+    /// let deleted_value = db.getdel_value_by_key(b"mykey").unwrap();
     ///
-    /// assert_eq!(deleted_value, String::from("myvalue"));
+    /// assert_eq!(deleted_value, b"myvalue".to_vec());
     ///
     /// let _ = std::fs::remove_file("dummy_db_getdel.csv");
     /// ```
-    pub fn getdel_value_by_key(&mut self, key: &str) -> Result<String, DatabaseError> {
-        let item = self.get_live_item(&key.to_string());
+    pub fn getdel_value_by_key(&mut self, key: &[u8]) -> Result<Vec<u8>, DatabaseError> {
+        let item = self.get_live_item(key);
         if let Some(item) = item {
             let value = item.get_copy_of_value();
             if let ValueType::StringType(str) = value {
-                self.delete_key(key.to_string());
+                self.delete_key(key.to_vec());
                 Ok(str)
             } else {
                 Err(DatabaseError::InvalidValueType(format!(
@@ -1065,25 +1466,26 @@ impl Database {
     /// use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_getset.csv".to_string());
-    /// db.add("mykey".to_string(),ValueTimeItemBuilder::new(
-    /// ValueType::StringType("myvalue".to_string())
+    /// db.add(b"mykey".to_vec(),ValueTimeItemBuilder::new(
+    /// ValueType::StringType("myvalue".into())
     /// ).build());
-    /// let deleted_value = db.getset_value_by_key("mykey", "newvalue").unwrap();
+    /// let deleted_value = db.getset_value_by_key(b"mykey", b"newvalue").unwrap();
     ///
-    /// assert_eq!(deleted_value, String::from("myvalue"));
+    /// assert_eq!(deleted_value, b"myvalue".to_vec());
     ///
     /// let _ = std::fs::remove_file("dummy_db_getset.csv");
     /// ```
     pub fn getset_value_by_key(
         &mut self,
-        key: &str,
-        new_value: &str,
-    ) -> Result<String, DatabaseError> {
-        let item_optional = self.get_mut_live_item(&key.to_string());
+        key: &[u8],
+        new_value: &[u8],
+    ) -> Result<Vec<u8>, DatabaseError> {
+        let item_optional = self.get_mut_live_item(key);
         if let Some(item) = item_optional {
             let value = item.get_copy_of_value();
             if let ValueType::StringType(str) = value {
-                item.set_value(ValueType::StringType(new_value.to_string()));
+                item.set_value(ValueType::StringType(new_value.to_vec()));
+                self.notify(EventClass::String, "set", key);
                 Ok(str)
             } else {
                 Err(DatabaseError::InvalidValueType(format!(
@@ -1096,6 +1498,164 @@ impl Database {
         }
     }
 
+    /// Obtiene el valor almacenado en `key` y actualiza su tiempo de expiración.
+    ///
+    /// `timeout` admite los mismos parámetros que `get_expire_at`, y además PERSIST, que elimina el
+    /// tiempo de expiración de la clave. Con cualquier otro parámetro, la expiración no se modifica.
+    /// Si la clave no existe, retorna error `MissingKey`. Si el valor almacenado no es de tipo String
+    /// retorna error de tipo `InvalidValueType`.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::implementations::database::Database;
+    /// use proyecto_taller_1::domain::entities::key_value_item::{ValueType, KeyAccessTime, ValueTimeItemBuilder};
+    ///
+    /// let mut db = Database::new("dummy_db_getex.csv".to_string());
+    /// db.add(b"mykey".to_vec(),ValueTimeItemBuilder::new(
+    /// ValueType::StringType("myvalue".into())
+    /// ).build());
+    /// let value = db.getex_value_by_key(b"mykey", (&"ex".to_string(), Some(&"100".to_string()))).unwrap();
+    ///
+    /// assert_eq!(value, b"myvalue".to_vec());
+    /// match db.get_live_item(b"mykey").unwrap().get_timeout() {
+    ///     KeyAccessTime::Volatile(_) => assert!(true),
+    ///     KeyAccessTime::Persistent => assert!(false),
+    /// }
+    ///
+    /// let _ = std::fs::remove_file("dummy_db_getex.csv");
+    /// ```
+    pub fn getex_value_by_key(
+        &mut self,
+        key: &[u8],
+        timeout: (&String, Option<&String>),
+    ) -> Result<Vec<u8>, DatabaseError> {
+        let expire_at = self.get_expire_at(timeout);
+        match self.get_mut_live_item(key) {
+            Some(item) => {
+                if let ValueType::StringType(str) = item.get_copy_of_value() {
+                    if timeout.0 == "persist" {
+                        if item.make_persistent() {
+                            self.notify(EventClass::Generic, "persist", key);
+                        }
+                    } else if expire_at != 0 {
+                        item.set_timeout(KeyAccessTime::Volatile(expire_at));
+                        self.notify(EventClass::Generic, "expire", key);
+                    }
+                    Ok(str)
+                } else {
+                    Err(DatabaseError::InvalidValueType(format!(
+                        "Invalid value type. Expected: String. Got: {}",
+                        item.get_value_type()
+                    )))
+                }
+            }
+            None => Err(DatabaseError::MissingKey()),
+        }
+    }
+
+    /// Retorna la subcadena del string almacenado en `key`, entre las posiciones `start` y `end` (inclusive).
+    ///
+    /// Las posiciones se cuentan en bytes. Los índices negativos se cuentan desde el final del string.
+    /// Si la clave no existe, retorna un string vacío. Si el valor almacenado no es de tipo String
+    /// retorna error de tipo `InvalidValueType`.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::implementations::database::Database;
+    /// use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    ///
+    /// let mut db = Database::new("dummy_db_getrange.csv".to_string());
+    /// db.add(b"saludo".to_vec(),ValueTimeItemBuilder::new(
+    /// ValueType::StringType("hola mundo".into())
+    /// ).build());
+    ///
+    /// assert_eq!(db.get_range_of_string(b"saludo", 0, 3).unwrap(), b"hola".to_vec());
+    /// assert_eq!(db.get_range_of_string(b"saludo", -5, -1).unwrap(), b"mundo".to_vec());
+    ///
+    /// let _ = std::fs::remove_file("dummy_db_getrange.csv");
+    /// ```
+    pub fn get_range_of_string(
+        &self,
+        key: &[u8],
+        start: isize,
+        end: isize,
+    ) -> Result<Vec<u8>, DatabaseError> {
+        let bytes = match self.get_string_value_by_key(key) {
+            Ok(bytes) => bytes,
+            Err(DatabaseError::MissingKey()) => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let len = bytes.len() as isize;
+        let start = if start < 0 {
+            (len + start).max(0)
+        } else {
+            start
+        };
+        let end = if end < 0 { len + end } else { end.min(len - 1) };
+        if len == 0 || start > end {
+            return Ok(Vec::new());
+        }
+        Ok(bytes[start as usize..=end as usize].to_vec())
+    }
+
+    /// Sobrescribe parte del string almacenado en `key` a partir de la posición `offset`.
+    ///
+    /// Si el string es más corto que `offset`, se completa con bytes nulos. Si la clave no existe, se considera
+    /// un string vacío. Retorna la longitud del string luego de la modificación.
+    /// Si el valor almacenado no es de tipo String retorna error de tipo `InvalidValueType`.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::implementations::database::Database;
+    /// use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    ///
+    /// let mut db = Database::new("dummy_db_setrange.csv".to_string());
+    /// db.add(b"saludo".to_vec(),ValueTimeItemBuilder::new(
+    /// ValueType::StringType("hola mundo".into())
+    /// ).build());
+    ///
+    /// assert_eq!(db.set_range_of_string(b"saludo", 5, b"gente").unwrap(), 10);
+    /// assert_eq!(db.get_string_value_by_key(b"saludo").unwrap(), b"hola gente".to_vec());
+    ///
+    /// let _ = std::fs::remove_file("dummy_db_setrange.csv");
+    /// ```
+    pub fn set_range_of_string(
+        &mut self,
+        key: &[u8],
+        offset: usize,
+        value: &[u8],
+    ) -> Result<usize, DatabaseError> {
+        if offset + value.len() > MAX_STRING_LENGTH {
+            return Err(DatabaseError::InvalidParameter(String::from(
+                "string exceeds maximum allowed size (512MB)",
+            )));
+        }
+        let mut bytes = match self.get_string_value_by_key(key) {
+            Ok(bytes) => bytes,
+            Err(DatabaseError::MissingKey()) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        if value.is_empty() {
+            return Ok(bytes.len());
+        }
+        if bytes.len() < offset + value.len() {
+            bytes.resize(offset + value.len(), 0);
+        }
+        bytes[offset..offset + value.len()].copy_from_slice(value);
+        let len = bytes.len();
+        match self.get_mut_live_item(key) {
+            Some(item) => item.set_value(ValueType::StringType(bytes)),
+            None => {
+                self.add(
+                    key.to_vec(),
+                    ValueTimeItemBuilder::new(ValueType::StringType(bytes)).build(),
+                );
+            }
+        }
+        self.notify(EventClass::String, "setrange", key);
+        Ok(len)
+    }
+
     /// Retorna la cantidad de elementos almacenados en el Set de la clave especificada.
     ///
     /// Si la clave no existe o el valor no es de tipo Set, devuelve 0.
@@ -1107,18 +1667,18 @@ impl Database {
     ///
     /// let mut db = Database::new("dummy_db_setlen.csv".to_string());
     /// let mut set = HashSet::new();
-    /// set.insert("25".to_string());
-    /// set.insert("40".to_string());
+    /// set.insert(b"25".to_vec());
+    /// set.insert(b"40".to_vec());
     /// let vt = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    /// db.add("edades".to_string(), vt);
+    /// db.add(b"edades".to_vec(), vt);
     ///
-    /// let len = db.get_len_of_set("edades");
+    /// let len = db.get_len_of_set(b"edades");
     ///
     /// assert_eq!(len, 2);
     ///
     /// let _ = std::fs::remove_file("dummy_db_setlen.csv");
     /// ```
-    pub fn get_len_of_set(&self, key: &str) -> usize {
+    pub fn get_len_of_set(&self, key: &[u8]) -> usize {
         let (item, expire) = self.check_timeout_item(key);
         if expire {
             return 0;
@@ -1147,17 +1707,17 @@ impl Database {
     ///
     /// let mut db = Database::new("dummy_db_ismember.csv".to_string());
     /// let mut set = HashSet::new();
-    /// set.insert("25".to_string());
-    /// set.insert("40".to_string());
+    /// set.insert(b"25".to_vec());
+    /// set.insert(b"40".to_vec());
     /// let vt = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    /// db.add("edades".to_string(), vt);
+    /// db.add(b"edades".to_vec(), vt);
     ///
-    /// assert_eq!(1, db.is_member_of_set("edades", "40"));
-    /// assert_eq!(0, db.is_member_of_set("edades", "45"));
+    /// assert_eq!(1, db.is_member_of_set(b"edades", b"40"));
+    /// assert_eq!(0, db.is_member_of_set(b"edades", b"45"));
     ///
     /// # let _ = std::fs::remove_file("dummy_db_ismember.csv");
     /// ```
-    pub fn is_member_of_set(&self, key: &str, member: &str) -> usize {
+    pub fn is_member_of_set(&self, key: &[u8], member: &[u8]) -> usize {
         if let (Some(item), false) = self.check_timeout_item(key) {
             if let ValueType::SetType(item) = item.get_value() {
                 if item.get(member).is_some() {
@@ -1180,18 +1740,18 @@ impl Database {
     ///
     /// let mut db = Database::new("dummy_db_getmembers.csv".to_string());
     /// let mut set = HashSet::new();
-    /// set.insert("25".to_string());
-    /// set.insert("40".to_string());
+    /// set.insert(b"25".to_vec());
+    /// set.insert(b"40".to_vec());
     /// let vt = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    /// db.add("edades".to_string(), vt);
+    /// db.add(b"edades".to_vec(), vt);
     ///
-    /// let res = db.get_members_of_set("edades");
-    /// assert!(res.contains(&&"40".to_string()));
-    /// assert!(res.contains(&&"25".to_string()));
+    /// let res = db.get_members_of_set(b"edades");
+    /// assert!(res.contains(&&b"40".to_vec()));
+    /// assert!(res.contains(&&b"25".to_vec()));
     ///
     /// # let _ = std::fs::remove_file("dummy_db_getmembers.csv");
     /// ```
-    pub fn get_members_of_set(&self, key: &str) -> Vec<&String> {
+    pub fn get_members_of_set(&self, key: &[u8]) -> Vec<&Vec<u8>> {
         let (item, expired) = self.check_timeout_item(key);
         let mut members = Vec::new();
         if let Some(item) = item {
@@ -1219,17 +1779,17 @@ impl Database {
     ///
     /// let mut db = Database::new("dummy_db_rmember.csv".to_string());
     /// let mut set = HashSet::new();
-    /// set.insert("25".to_string());
-    /// set.insert("40".to_string());
-    /// set.insert("45".to_string());
+    /// set.insert(b"25".to_vec());
+    /// set.insert(b"40".to_vec());
+    /// set.insert(b"45".to_vec());
     /// let vt = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    /// db.add("edades".to_string(), vt);
+    /// db.add(b"edades".to_vec(), vt);
     ///
-    /// assert_eq!(true, db.remove_member_from_set("edades", "40").unwrap());
+    /// assert_eq!(true, db.remove_member_from_set(b"edades", b"40").unwrap());
     ///
     /// # let _ = std::fs::remove_file("dummy_db_rmember.csv");
     /// ```
-    pub fn remove_member_from_set(&mut self, key: &str, member: &str) -> Option<bool> {
+    pub fn remove_member_from_set(&mut self, key: &[u8], member: &[u8]) -> Option<bool> {
         let item = self.get_mut_live_item(key);
         match item {
             Some(item) => {
@@ -1237,6 +1797,7 @@ impl Database {
                     let removed = value.remove(member);
                     if removed {
                         item.set_value(ValueType::SetType(value));
+                        self.notify(EventClass::Set, "srem", key);
                         return Some(true);
                     }
                 }
@@ -1264,19 +1825,20 @@ impl Database {
     /// # use std::collections::HashSet;
     ///
     /// let mut db = Database::new("dummy_db_setstr.csv".to_string());
-    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".to_string())).build();
-    /// db.add("mascota".to_string(), vt);
+    /// let vt = ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build();
+    /// db.add(b"mascota".to_vec(), vt);
     ///
-    /// assert_eq!(true, db.set_string("mascota", "gato", (&"ex".to_string(), None), Some(&"xx".to_string())));
+    /// assert_eq!(true, db.set_string(b"mascota", b"gato", (&"ex".to_string(), None), Some(&"xx".to_string()), false));
     ///
     /// # let _ = std::fs::remove_file("dummy_db_setstr.csv");
     /// ```
     pub fn set_string(
         &mut self,
-        key: &str,
-        value: &str,
+        key: &[u8],
+        value: &[u8],
         timeout: (&String, Option<&String>),
         set_if_exists: Option<&String>,
+        keep_ttl: bool,
     ) -> bool {
         let mut set_if_non_existing = false;
         let mut set_if_existing = false;
@@ -1294,17 +1856,19 @@ impl Database {
         match item {
             Some(item) => {
                 if set_if_existing || !set_if_non_existing {
-                    item.set_value(ValueType::StringType(value.to_string()));
-
+                    item.set_value(ValueType::StringType(value.to_vec()));
                     if expire_at != 0 {
                         item.set_timeout(KeyAccessTime::Volatile(expire_at));
+                    } else if !keep_ttl {
+                        item.make_persistent();
                     }
+                    self.notify_set(key, expire_at);
                     return true;
                 }
             }
             None => {
                 if set_if_non_existing || !set_if_existing {
-                    let value = ValueType::StringType(value.to_string());
+                    let value = ValueType::StringType(value.to_vec());
                     let mut time = KeyAccessTime::Persistent;
                     if expire_at != 0 {
                         time = KeyAccessTime::Volatile(expire_at);
@@ -1312,7 +1876,8 @@ impl Database {
                     let new_item = ValueTimeItemBuilder::new(value)
                         .with_key_access_time(time)
                         .build();
-                    self.add(key.to_string(), new_item);
+                    self.add(key.to_vec(), new_item);
+                    self.notify_set(key, expire_at);
                     return true;
                 }
             }
@@ -1320,6 +1885,14 @@ impl Database {
         false
     }
 
+    /// Registra los eventos de asignar un valor String a la clave y, si `expire_at` no es 0, su expiración.
+    fn notify_set(&mut self, key: &[u8], expire_at: u64) {
+        self.notify(EventClass::String, "set", key);
+        if expire_at != 0 {
+            self.notify(EventClass::Generic, "expire", key);
+        }
+    }
+
     /// Obtiene el tiempo de expiración a partir de un offset.
     ///
     /// Admite los siguientes parámetros:
@@ -1354,23 +1927,17 @@ impl Database {
                     let now = SystemTime::now()
                         .duration_since(SystemTime::UNIX_EPOCH)
                         .expect("SystemTime clock failure");
-                    expire_at = px.parse::<u64>().unwrap_or(0) / 1000 + now.as_millis() as u64;
+                    expire_at = px.parse::<u64>().unwrap_or(0) / 1000 + now.as_secs();
                 }
             }
             "exat" => {
                 if let Some(exat) = timeout.1 {
-                    let now = SystemTime::now()
-                        .duration_since(SystemTime::UNIX_EPOCH)
-                        .expect("SystemTime clock failure");
-                    expire_at = exat.parse::<u64>().unwrap_or(0) + now.as_secs();
+                    expire_at = exat.parse::<u64>().unwrap_or(0);
                 }
             }
             "pxat" => {
                 if let Some(pxat) = timeout.1 {
-                    let now = SystemTime::now()
-                        .duration_since(SystemTime::UNIX_EPOCH)
-                        .expect("SystemTime clock failure");
-                    expire_at = pxat.parse::<u64>().unwrap_or(0) / 1000 + now.as_secs();
+                    expire_at = pxat.parse::<u64>().unwrap_or(0) / 1000;
                 }
             }
             _ => {}
@@ -1389,36 +1956,35 @@ impl Database {
     /// use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_pop.csv".to_string());
-    /// let mut list = vec![String::from("argentina"), String::from("brasil"), String::from("chile"), String::from("uruguay")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("paises".to_string(), vt);
+    /// let mut list = vec![b"argentina".to_vec(), b"brasil".to_vec(), b"chile".to_vec(), b"uruguay".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"paises".to_vec(), vt);
     ///
-    /// let removed = db.pop_elements_from_list("paises", 1).unwrap();
-    /// assert_eq!(removed, vec![String::from("argentina")]);
+    /// let removed = db.pop_elements_from_list(b"paises", 1).unwrap();
+    /// assert_eq!(removed, vec![b"argentina".to_vec()]);
     ///
-    /// let removed = db.pop_elements_from_list("paises", 2).unwrap();
-    /// assert_eq!(removed, vec![String::from("brasil"), String::from("chile")]);
+    /// let removed = db.pop_elements_from_list(b"paises", 2).unwrap();
+    /// assert_eq!(removed, vec![b"brasil".to_vec(), b"chile".to_vec()]);
     ///
     /// let _ = std::fs::remove_file("dummy_db_pop.csv");
     /// ```
-    pub fn pop_elements_from_list(&mut self, key: &str, count: usize) -> Option<Vec<String>> {
-        if let Some(item) = self.get_mut_live_item(key) {
-            match item.get_copy_of_value() {
-                ValueType::ListType(mut list) => {
-                    let popped_elements;
-                    if count < list.len() {
-                        popped_elements = list.drain(..count).collect();
-                    } else {
-                        popped_elements = list.drain(..list.len()).collect();
-                    }
-                    item.set_value(ValueType::ListType(list));
-                    Some(popped_elements)
+    pub fn pop_elements_from_list(&mut self, key: &[u8], count: usize) -> Option<Vec<Vec<u8>>> {
+        let popped_elements = if let Some(item) = self.get_mut_live_item(key) {
+            match item.get_mut_value() {
+                ValueType::ListType(list) => {
+                    let count = count.min(list.len());
+                    list.drain(..count).collect::<Vec<Vec<u8>>>()
                 }
-                _ => None,
+                _ => return None,
             }
         } else {
-            None
+            return None;
+        };
+        if !popped_elements.is_empty() {
+            self.notify(EventClass::List, "lpop", key);
         }
+        self.remove_key_if_empty_list(key);
+        Some(popped_elements)
     }
 
     /// Elimina y retorna los últimos elementos de la lista almacenada en `key`.
@@ -1432,37 +1998,40 @@ impl Database {
     /// use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_rpop.csv".to_string());
-    /// let mut list = vec![String::from("argentina"), String::from("brasil"), String::from("chile"), String::from("uruguay")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("paises".to_string(), vt);
+    /// let mut list = vec![b"argentina".to_vec(), b"brasil".to_vec(), b"chile".to_vec(), b"uruguay".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"paises".to_vec(), vt);
     ///
-    /// let removed = db.rpop_elements_from_list("paises", 1).unwrap();
-    /// assert_eq!(removed, vec![String::from("uruguay")]);
+    /// let removed = db.rpop_elements_from_list(b"paises", 1).unwrap();
+    /// assert_eq!(removed, vec![b"uruguay".to_vec()]);
     ///
-    /// let removed = db.rpop_elements_from_list("paises", 2).unwrap();
-    /// assert_eq!(removed, vec![String::from("chile"), String::from("brasil")]);
+    /// let removed = db.rpop_elements_from_list(b"paises", 2).unwrap();
+    /// assert_eq!(removed, vec![b"chile".to_vec(), b"brasil".to_vec()]);
     ///
     /// let _ = std::fs::remove_file("dummy_db_rpop.csv");
     /// ```
-    pub fn rpop_elements_from_list(&mut self, key: &str, count: usize) -> Option<Vec<String>> {
+    pub fn rpop_elements_from_list(&mut self, key: &[u8], count: usize) -> Option<Vec<Vec<u8>>> {
         let mut popped_elements = Vec::new();
         if let Some(item) = self.get_mut_live_item(key) {
-            if let ValueType::ListType(mut list) = item.get_copy_of_value() {
+            if let ValueType::ListType(list) = item.get_mut_value() {
                 for _ in 0..count {
-                    match list.pop() {
+                    match list.pop_back() {
                         Some(last_element) => {
                             popped_elements.push(last_element);
                         }
                         None => break,
                     }
                 }
-                item.set_value(ValueType::ListType(list));
             } else {
                 return None;
             }
         } else {
             return None;
         }
+        if !popped_elements.is_empty() {
+            self.notify(EventClass::List, "rpop", key);
+        }
+        self.remove_key_if_empty_list(key);
         Some(popped_elements)
     }
 
@@ -1479,23 +2048,26 @@ impl Database {
     ///
     /// let mut db = Database::new("dummy_db_sadd.csv".to_string());
     /// let mut set = HashSet::new();
-    /// set.insert("25".to_string());
-    /// set.insert("40".to_string());
+    /// set.insert(b"25".to_vec());
+    /// set.insert(b"40".to_vec());
     /// let vt = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    /// db.add("edades".to_string(), vt);
+    /// db.add(b"edades".to_vec(), vt);
     ///
-    /// assert_eq!(2, db.add_element_to_set("edades", vec![&"45".to_string(), &"15".to_string()]).unwrap());
+    /// assert_eq!(2, db.add_element_to_set(b"edades", vec![b"45".as_slice(), b"15".as_slice()]).unwrap());
     ///
     /// # let _ = std::fs::remove_file("dummy_db_sadd.csv");
     /// ```
-    pub fn add_element_to_set(&mut self, key: &str, values_to_add: Vec<&String>) -> Option<usize> {
+    pub fn add_element_to_set(&mut self, key: &[u8], values_to_add: Vec<&[u8]>) -> Option<usize> {
         let mut added = 0;
         if let Some(item) = self.get_mut_live_item(key) {
             if let ValueType::SetType(mut old_value) = item.get_copy_of_value() {
                 values_to_add.iter().for_each(|element| {
-                    added += old_value.insert(element.to_string()) as usize;
+                    added += old_value.insert(element.to_vec()) as usize;
                 });
                 item.set_value(ValueType::SetType(old_value));
+                if added > 0 {
+                    self.notify(EventClass::Set, "sadd", key);
+                }
                 Some(added)
             } else {
                 None
@@ -1503,33 +2075,379 @@ impl Database {
         } else {
             let mut set = HashSet::new();
             values_to_add.iter().for_each(|element| {
-                added += set.insert(element.to_string()) as usize;
+                added += set.insert(element.to_vec()) as usize;
             });
             let vti = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-            self.add(key.to_string(), vti);
+            self.add(key.to_vec(), vti);
+            self.notify(EventClass::Set, "sadd", key);
             Some(added)
         }
     }
 
-    /// Lee los datos del archivo de base de datos.
+    /// Devuelve una referencia al set almacenado en `key`, si la clave existe y no expiró.
+    ///
+    /// Si el valor almacenado no es de tipo Set, devuelve error.
     ///
-    /// Lee las lineas del archivo y las transforma a un KeyValueItem. Almacena estos datos en el HashMap `items`.
     /// # Ejemplo
     /// ```
     /// # use proyecto_taller_1::domain::implementations::database::Database;
-    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
     /// # use std::collections::HashSet;
     ///
-    /// let mut db = Database::new("dummy_db_load.csv".to_string());
+    /// # let mut db = Database::new("dummy_db_get_set.csv".to_string());
+    /// let set: HashSet<Vec<u8>> = vec![b"rojo".to_vec(), b"verde".to_vec()].into_iter().collect();
+    /// db.add(b"colores".to_vec(), ValueTimeItemBuilder::new(ValueType::SetType(set)).build());
+    /// db.add(b"nombre".to_vec(), ValueTimeItemBuilder::new(ValueType::StringType("ana".into())).build());
+    ///
+    /// assert_eq!(db.get_set(b"colores").unwrap().map(HashSet::len), Some(2));
+    /// assert!(db.get_set(b"formas").unwrap().is_none());
+    /// assert!(db.get_set(b"nombre").is_err());
+    /// # let _ = std::fs::remove_file("dummy_db_get_set.csv");
+    /// ```
+    pub fn get_set(&self, key: &[u8]) -> Result<Option<&HashSet<Vec<u8>>>, DatabaseError> {
+        if let (Some(item), false) = self.check_timeout_item(key) {
+            if let ValueType::SetType(set) = item.get_value() {
+                return Ok(Some(set));
+            }
+            return Err(DatabaseError::InvalidValueType(WRONGTYPE.to_string()));
+        }
+        Ok(None)
+    }
+
+    /// Devuelve la intersección de los sets almacenados en `keys`.
+    ///
+    /// Las claves que no existen se consideran sets vacíos, por lo que el resultado también es vacío.
+    /// Si alguna clave almacena un valor que no es de tipo Set, devuelve error.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    /// # use std::collections::HashSet;
+    ///
+    /// # let mut db = Database::new("dummy_db_sinter.csv".to_string());
+    /// let set: HashSet<Vec<u8>> = vec![b"rojo".to_vec(), b"verde".to_vec()].into_iter().collect();
+    /// db.add(b"colores".to_vec(), ValueTimeItemBuilder::new(ValueType::SetType(set)).build());
+    /// let set: HashSet<Vec<u8>> = vec![b"rojo".to_vec(), b"azul".to_vec()].into_iter().collect();
+    /// db.add(b"bandera".to_vec(), ValueTimeItemBuilder::new(ValueType::SetType(set)).build());
+    ///
+    /// let inter = db.intersect_sets(&[b"colores".to_vec(), b"bandera".to_vec()]).unwrap();
+    /// assert_eq!(inter.len(), 1);
+    /// assert!(inter.contains(b"rojo".as_slice()));
+    ///
+    /// # let _ = std::fs::remove_file("dummy_db_sinter.csv");
+    /// ```
+    pub fn intersect_sets(&self, keys: &[Vec<u8>]) -> Result<HashSet<Vec<u8>>, DatabaseError> {
+        let mut sets = Vec::new();
+        for key in keys {
+            sets.push(self.get_set(key)?);
+        }
+        if sets.iter().any(|set| set.is_none()) {
+            return Ok(HashSet::new());
+        }
+        let mut sets: Vec<&HashSet<Vec<u8>>> = sets.into_iter().flatten().collect();
+        sets.sort_by_key(|set| set.len());
+        match sets.split_first() {
+            Some((smallest, rest)) => Ok(smallest
+                .iter()
+                .filter(|member| rest.iter().all(|set| set.contains(*member)))
+                .cloned()
+                .collect()),
+            None => Ok(HashSet::new()),
+        }
+    }
+
+    /// Devuelve la unión de los sets almacenados en `keys`.
+    ///
+    /// Las claves que no existen se consideran sets vacíos.
+    /// Si alguna clave almacena un valor que no es de tipo Set, devuelve error.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    /// # use std::collections::HashSet;
+    ///
+    /// # let mut db = Database::new("dummy_db_sunion.csv".to_string());
+    /// let set: HashSet<Vec<u8>> = vec![b"rojo".to_vec(), b"verde".to_vec()].into_iter().collect();
+    /// db.add(b"colores".to_vec(), ValueTimeItemBuilder::new(ValueType::SetType(set)).build());
+    /// let set: HashSet<Vec<u8>> = vec![b"rojo".to_vec(), b"azul".to_vec()].into_iter().collect();
+    /// db.add(b"bandera".to_vec(), ValueTimeItemBuilder::new(ValueType::SetType(set)).build());
+    ///
+    /// let union = db.union_sets(&[b"colores".to_vec(), b"bandera".to_vec(), b"vacio".to_vec()]).unwrap();
+    /// assert_eq!(union.len(), 3);
+    ///
+    /// # let _ = std::fs::remove_file("dummy_db_sunion.csv");
+    /// ```
+    pub fn union_sets(&self, keys: &[Vec<u8>]) -> Result<HashSet<Vec<u8>>, DatabaseError> {
+        let mut union = HashSet::new();
+        for key in keys {
+            if let Some(set) = self.get_set(key)? {
+                union.extend(set.iter().cloned());
+            }
+        }
+        Ok(union)
+    }
+
+    /// Devuelve la diferencia entre el set almacenado en la primera clave y los sets de las claves restantes.
+    ///
+    /// Las claves que no existen se consideran sets vacíos.
+    /// Si alguna clave almacena un valor que no es de tipo Set, devuelve error.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    /// # use std::collections::HashSet;
+    ///
+    /// # let mut db = Database::new("dummy_db_sdiff.csv".to_string());
+    /// let set: HashSet<Vec<u8>> = vec![b"rojo".to_vec(), b"verde".to_vec()].into_iter().collect();
+    /// db.add(b"colores".to_vec(), ValueTimeItemBuilder::new(ValueType::SetType(set)).build());
+    /// let set: HashSet<Vec<u8>> = vec![b"rojo".to_vec(), b"azul".to_vec()].into_iter().collect();
+    /// db.add(b"bandera".to_vec(), ValueTimeItemBuilder::new(ValueType::SetType(set)).build());
+    ///
+    /// let diff = db.diff_sets(&[b"colores".to_vec(), b"bandera".to_vec()]).unwrap();
+    /// assert_eq!(diff.len(), 1);
+    /// assert!(diff.contains(b"verde".as_slice()));
+    ///
+    /// # let _ = std::fs::remove_file("dummy_db_sdiff.csv");
+    /// ```
+    pub fn diff_sets(&self, keys: &[Vec<u8>]) -> Result<HashSet<Vec<u8>>, DatabaseError> {
+        let mut sets = Vec::new();
+        for key in keys {
+            sets.push(self.get_set(key)?);
+        }
+        match sets.split_first() {
+            Some((Some(first), rest)) => Ok(first
+                .iter()
+                .filter(|member| {
+                    !rest
+                        .iter()
+                        .flatten()
+                        .any(|set: &&HashSet<Vec<u8>>| set.contains(*member))
+                })
+                .cloned()
+                .collect()),
+            _ => Ok(HashSet::new()),
+        }
+    }
+
+    /// Guarda `set` en la clave `destination`, reemplazando cualquier valor que tuviera, y registra el evento `event`.
+    ///
+    /// Si `set` está vacío, se elimina `destination`.
+    /// Devuelve la cantidad de elementos almacenados.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use std::collections::HashSet;
+    ///
+    /// # let mut db = Database::new("dummy_db_store_set.csv".to_string());
+    /// let set: HashSet<Vec<u8>> = vec![b"rojo".to_vec(), b"verde".to_vec()].into_iter().collect();
+    /// assert_eq!(db.store_set(b"colores", set, "sunionstore"), 2);
+    /// assert_eq!(db.get_len_of_set(b"colores"), 2);
+    ///
+    /// assert_eq!(db.store_set(b"colores", HashSet::new(), "sunionstore"), 0);
+    /// assert!(db.get_live_item(b"colores").is_none());
+    ///
+    /// # let _ = std::fs::remove_file("dummy_db_store_set.csv");
+    /// ```
+    pub fn store_set(&mut self, destination: &[u8], set: HashSet<Vec<u8>>, event: &str) -> usize {
+        let len = set.len();
+        if len == 0 {
+            if self.items.delete(destination).is_some() {
+                self.notify(EventClass::Generic, "del", destination);
+            }
+        } else {
+            let vt_item = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
+            self.add(destination.to_vec(), vt_item);
+            self.notify(EventClass::Set, event, destination);
+        }
+        len
+    }
+
+    /// Mueve `member` del set almacenado en `source` al set almacenado en `destination`.
+    ///
+    /// Si `destination` no existe, se crea. Si `source` queda vacío, se elimina.
+    /// Devuelve True si el elemento fue movido, o False si no pertenecía a `source`.
+    /// Si alguna de las claves almacena un valor que no es de tipo Set, devuelve error.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    /// # use std::collections::HashSet;
+    ///
+    /// # let mut db = Database::new("dummy_db_smove.csv".to_string());
+    /// let set: HashSet<Vec<u8>> = vec![b"rojo".to_vec(), b"verde".to_vec()].into_iter().collect();
+    /// db.add(b"colores".to_vec(), ValueTimeItemBuilder::new(ValueType::SetType(set)).build());
+    ///
+    /// assert!(db.move_member_between_sets(b"colores", b"elegidos", b"rojo").unwrap());
+    /// assert!(!db.move_member_between_sets(b"colores", b"elegidos", b"azul").unwrap());
+    /// assert_eq!(db.is_member_of_set(b"elegidos", b"rojo"), 1);
+    ///
+    /// # let _ = std::fs::remove_file("dummy_db_smove.csv");
+    /// ```
+    pub fn move_member_between_sets(
+        &mut self,
+        source: &[u8],
+        destination: &[u8],
+        member: &[u8],
+    ) -> Result<bool, DatabaseError> {
+        let is_member = match self.get_set(source)? {
+            Some(set) => set.contains(member),
+            None => false,
+        };
+        self.get_set(destination)?;
+        if !is_member {
+            return Ok(false);
+        }
+        if source == destination {
+            return Ok(true);
+        }
+        if let Some(item) = self.get_mut_live_item(source) {
+            if let ValueType::SetType(set) = item.get_mut_value() {
+                set.remove(member);
+                let is_empty = set.is_empty();
+                self.notify(EventClass::Set, "srem", source);
+                if is_empty {
+                    self.items.delete(source);
+                    self.notify(EventClass::Generic, "del", source);
+                }
+            }
+        }
+        self.add_element_to_set(destination, vec![member]);
+        Ok(true)
+    }
+
+    /// Elimina y devuelve hasta `count` elementos al azar del set almacenado en `key`.
+    ///
+    /// Si el set queda vacío, se elimina la clave.
+    /// Si la clave no existe, devuelve una lista vacía.
+    /// Si el valor almacenado no es de tipo Set, devuelve error.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    /// # use std::collections::HashSet;
+    ///
+    /// # let mut db = Database::new("dummy_db_spop.csv".to_string());
+    /// let set: HashSet<Vec<u8>> = vec![b"rojo".to_vec(), b"verde".to_vec(), b"azul".to_vec()].into_iter().collect();
+    /// db.add(b"colores".to_vec(), ValueTimeItemBuilder::new(ValueType::SetType(set)).build());
+    ///
+    /// let popped = db.pop_members_from_set(b"colores", 2).unwrap();
+    /// assert_eq!(popped.len(), 2);
+    /// assert_eq!(db.get_len_of_set(b"colores"), 1);
+    ///
+    /// # let _ = std::fs::remove_file("dummy_db_spop.csv");
+    /// ```
+    pub fn pop_members_from_set(
+        &mut self,
+        key: &[u8],
+        count: usize,
+    ) -> Result<Vec<Vec<u8>>, DatabaseError> {
+        let chosen: Option<Vec<Vec<u8>>> = match self.get_set(key)? {
+            Some(set) if count < set.len() => Some(
+                random::sample_iter(set.iter(), count)
+                    .into_iter()
+                    .cloned()
+                    .collect(),
+            ),
+            Some(_) => None,
+            None => return Ok(Vec::new()),
+        };
+        let mut popped = Vec::new();
+        if let Some(item) = self.get_mut_live_item(key) {
+            if let ValueType::SetType(set) = item.get_mut_value() {
+                match chosen {
+                    Some(chosen) => {
+                        for member in chosen {
+                            if let Some(member) = set.take(&member) {
+                                popped.push(member);
+                            }
+                        }
+                    }
+                    None => popped = std::mem::take(set).into_iter().collect(),
+                }
+                let is_empty = set.is_empty();
+                if !popped.is_empty() {
+                    self.notify(EventClass::Set, "spop", key);
+                }
+                if is_empty {
+                    self.items.delete(key);
+                    self.notify(EventClass::Generic, "del", key);
+                }
+            }
+        }
+        Ok(popped)
+    }
+
+    /// Devuelve elementos al azar del set almacenado en `key`, sin eliminarlos.
+    ///
+    /// Si `count` es positivo, devuelve hasta `count` elementos distintos.
+    /// Si `count` es negativo, devuelve exactamente `|count|` elementos, que pueden repetirse.
+    /// Si la clave no existe, devuelve una lista vacía.
+    /// Si el valor almacenado no es de tipo Set, devuelve error.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    /// # use std::collections::HashSet;
+    ///
+    /// # let mut db = Database::new("dummy_db_srandmember.csv".to_string());
+    /// let set: HashSet<Vec<u8>> = vec![b"rojo".to_vec(), b"verde".to_vec()].into_iter().collect();
+    /// db.add(b"colores".to_vec(), ValueTimeItemBuilder::new(ValueType::SetType(set)).build());
+    ///
+    /// assert_eq!(db.get_random_members_of_set(b"colores", 5).unwrap().len(), 2);
+    /// assert_eq!(db.get_random_members_of_set(b"colores", -5).unwrap().len(), 5);
+    ///
+    /// # let _ = std::fs::remove_file("dummy_db_srandmember.csv");
+    /// ```
+    pub fn get_random_members_of_set(
+        &self,
+        key: &[u8],
+        count: isize,
+    ) -> Result<Vec<Vec<u8>>, DatabaseError> {
+        match self.get_set(key)? {
+            Some(set) => {
+                let members: Vec<&Vec<u8>> = set.iter().collect();
+                if count >= 0 {
+                    Ok(random::sample(members, count as usize)
+                        .into_iter()
+                        .cloned()
+                        .collect())
+                } else {
+                    Ok((0..count.unsigned_abs())
+                        .map(|_| members[random::random_index(members.len())].to_vec())
+                        .collect())
+                }
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Lee los datos del archivo de base de datos.
+    ///
+    /// Lee las lineas del archivo y las transforma a un KeyValueItem. Almacena estos datos en el motor de almacenamiento.
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
+    /// # use std::collections::HashSet;
+    ///
+    /// let mut db = Database::new("dummy_db_load.csv".to_string());
     /// db.load_items();
+    /// # let _ = std::fs::remove_file("dummy_db_load.csv");
     /// ```
     pub fn load_items(&mut self) {
-        if let Ok(lines) = Database::read_lines(self.dbfilename.to_string()) {
+        if let Ok(lines) = Database::read_lines(&self.dbfilename) {
             for line in lines {
                 if let Ok(kvi_serialized) = line {
                     let kvis = KeyValueItemSerialized::new(kvi_serialized);
                     let item = kvis.transform_to_item();
-                    self.items.insert(item.0, item.1);
+                    self.items.put(item.0, item.1);
                 } else {
                     panic!("Error al leer línea del archivo:");
                 }
@@ -1547,14 +2465,14 @@ impl Database {
         P: AsRef<Path>,
     {
         let path = Path::new(filename.as_ref());
-        let file = File::open(&path);
+        let file = File::open(path);
 
         match file {
             Ok(_) => Ok(io::BufReader::new(file.unwrap()).lines()),
             Err(_) => {
-                let mut _file = File::create(&path)?; //Lo crea en write-only mode.
+                let mut _file = File::create(path)?; //Lo crea en write-only mode.
                 let path = Path::new(filename.as_ref());
-                let file_op = File::open(&path).expect("Could not open new database file");
+                let file_op = File::open(path).expect("Could not open new database file");
                 Ok(io::BufReader::new(file_op).lines())
             }
         }
@@ -1563,34 +2481,65 @@ impl Database {
     /// Guarda cada item que tiene en memoria, en el formato adecuado para la serialización.
     ///
     /// El formato es: key;last_access_time;timeout;type;value
-    pub fn save_items_to_file(&self) {
+    ///
+    /// La clave y los valores se escapan con `escape_field`, para que puedan contener cualquier byte.
+    /// Devuelve error si el archivo no puede abrirse o escribirse.
+    pub fn save_items_to_file(&self) -> io::Result<()> {
         let mut file = OpenOptions::new()
             .write(true)
             .append(false)
             .create(true)
             .truncate(true)
-            .open(self.dbfilename.to_string())
-            .expect("Could not open database file");
-
-        for kvi in &self.items {
-            let kvi_type = match kvi.1.get_value() {
-                ValueType::StringType(_) => "string",
-                ValueType::SetType(_) => "set",
-                ValueType::ListType(_) => "list",
-            };
-            writeln!(
-                file,
-                "{};{};{};{};{}",
-                kvi.0,
-                kvi.1.get_last_access_time().to_string(),
-                kvi.1.get_timeout().to_string(),
-                kvi_type,
-                kvi.1.get_value().to_string()
-            )
-            .unwrap_or_else(|_| println!("Could not write to database file"));
+            .open(&self.dbfilename)?;
+        self.write_items(&mut file)?;
+        file.flush()
+    }
+
+    /// Retorna una copia completa de los datos, con el mismo formato que el archivo *dump*.
+    ///
+    /// Es la copia que recibe una réplica al sincronizarse con el servidor (ver `load_snapshot`).
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut snapshot = Vec::new();
+        // la escritura sobre un Vec no puede fallar
+        let _ = self.write_items(&mut snapshot);
+        snapshot
+    }
+
+    /// Reemplaza todos los datos por los de una copia generada con `snapshot`.
+    ///
+    /// Las claves anteriores y las de la copia se registran como modificadas, para invalidarlas a los clientes que las siguen.
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+    /// let mut master = Database::new("dummy_db_snapshot_master.csv".to_string());
+    /// master.add(b"mascota".to_vec(), ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build());
+    /// let mut replica = Database::new("dummy_db_snapshot_replica.csv".to_string());
+    /// replica.add(b"animal".to_vec(), ValueTimeItemBuilder::new(ValueType::StringType("leon".into())).build());
+    ///
+    /// replica.load_snapshot(&master.snapshot());
+    /// assert_eq!(replica.get_size(), 1);
+    /// assert_eq!(replica.get_string_value_by_key(b"mascota").unwrap(), b"perro".to_vec());
+    /// # let _ = std::fs::remove_file("dummy_db_snapshot_master.csv");
+    /// # let _ = std::fs::remove_file("dummy_db_snapshot_replica.csv");
+    /// ```
+    pub fn load_snapshot(&mut self, snapshot: &[u8]) {
+        self.clean_items();
+        for line in snapshot.lines().map_while(Result::ok) {
+            let (key, item) = KeyValueItemSerialized::new(line).transform_to_item();
+            self.modified_keys.push(key.clone());
+            self.items.put(key, item);
         }
     }
 
+    /// Escribe cada item con el formato `key;last_access_time;timeout;type;value`, uno por línea.
+    fn write_items(&self, writer: &mut impl Write) -> io::Result<()> {
+        for (key, item) in self.items.iter() {
+            writeln!(writer, "{}", serialize_item(key, item))?;
+        }
+        Ok(())
+    }
+
     /// Devuelve la cantidad de claves almacenadas en la base de datos
     /// # Ejemplo
     /// ```
@@ -1599,8 +2548,8 @@ impl Database {
     /// # use std::collections::HashSet;
     ///
     /// let mut db = Database::new("dummy_db_size.csv".to_string());
-    /// db.add("mascota".to_string(),  ValueTimeItemBuilder::new(ValueType::StringType("perro".to_string())).build());
-    /// db.add("animal".to_string(),  ValueTimeItemBuilder::new(ValueType::StringType("leon".to_string())).build());
+    /// db.add(b"mascota".to_vec(),  ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build());
+    /// db.add(b"animal".to_vec(),  ValueTimeItemBuilder::new(ValueType::StringType("leon".into())).build());
     ///
     /// assert_eq!(2, db.get_size());
     ///
@@ -1618,16 +2567,20 @@ impl Database {
     /// # use std::collections::HashSet;
     ///
     /// let mut db = Database::new("dummy_db_del.csv".to_string());
-    /// db.add("mascota".to_string(),  ValueTimeItemBuilder::new(ValueType::StringType("perro".to_string())).build());
-    /// db.add("animal".to_string(),  ValueTimeItemBuilder::new(ValueType::StringType("leon".to_string())).build());
+    /// db.add(b"mascota".to_vec(),  ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build());
+    /// db.add(b"animal".to_vec(),  ValueTimeItemBuilder::new(ValueType::StringType("leon".into())).build());
     ///
-    /// assert_eq!(true, db.delete_key("animal".to_string()));
+    /// assert_eq!(true, db.delete_key(b"animal".to_vec()));
     /// assert_eq!(1, db.get_size());
     ///
     /// # let _ = std::fs::remove_file("dummy_db_del.csv");
     /// ```
-    pub fn delete_key(&mut self, key: String) -> bool {
-        matches!(self.items.remove(&key), Some(_key))
+    pub fn delete_key(&mut self, key: Vec<u8>) -> bool {
+        let deleted = matches!(self.items.delete(&key), Some(_key));
+        if deleted {
+            self.notify(EventClass::Generic, "del", &key);
+        }
+        deleted
     }
 
     /// Asigna un timestamp de expiración a una determinada key.
@@ -1640,18 +2593,22 @@ impl Database {
     /// # use std::collections::HashSet;
     ///
     /// let mut db = Database::new("dummy_db_expire.csv".to_string());
-    /// db.add("mascota".to_string(),  ValueTimeItemBuilder::new(ValueType::StringType("perro".to_string())).build());
+    /// db.add(b"mascota".to_vec(),  ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build());
     ///
-    /// assert_eq!(true, db.expire_key("mascota", "1623433677"));
+    /// assert_eq!(true, db.expire_key(b"mascota", "1623433677"));
     ///
     /// # let _ = std::fs::remove_file("dummy_db_expire.csv");
     /// ```
-    pub fn expire_key(&mut self, key: &str, timeout: &str) -> bool {
+    pub fn expire_key(&mut self, key: &[u8], timeout: &str) -> bool {
         let kvi = self.get_mut_live_item(key);
-        match kvi {
+        let updated = match kvi {
             Some(k) => k.set_timeout(KeyAccessTime::Volatile(u64::from_str(timeout).unwrap_or(0))),
             None => false,
+        };
+        if updated {
+            self.notify(EventClass::Generic, "expire", key);
         }
+        updated
     }
 
     /// Elimina la primer `cantidad_maxima` de ocurrencias de elementos de la lista almacenada en `key`.
@@ -1669,78 +2626,344 @@ impl Database {
     /// use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
     ///
     /// let mut db = Database::new("dummy_db_ldel.csv".to_string());
-    /// let mut list = vec![String::from("argentina"), String::from("brasil"), String::from("argentina"), String::from("uruguay")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("paises".to_string(), vt);
+    /// let mut list = vec![b"argentina".to_vec(), b"brasil".to_vec(), b"argentina".to_vec(), b"uruguay".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"paises".to_vec(), vt);
     ///
-    /// let removed = db.delete_elements_of_value_list("paises", "4".to_string(), "argentina".to_string());
+    /// let removed = db.delete_elements_of_value_list(b"paises", "4".to_string(), b"argentina");
     /// assert_eq!(removed, 2);
     ///
-    /// let mut list = vec![String::from("argentina"), String::from("brasil"), String::from("argentina"), String::from("uruguay")];
-    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list)).build();
-    /// db.add("paises2".to_string(), vt);
+    /// let mut list = vec![b"argentina".to_vec(), b"brasil".to_vec(), b"argentina".to_vec(), b"uruguay".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"paises2".to_vec(), vt);
     ///
-    /// let removed = db.delete_elements_of_value_list("paises2", "1".to_string(), "argentina".to_string());
+    /// let removed = db.delete_elements_of_value_list(b"paises2", "1".to_string(), b"argentina");
     /// assert_eq!(removed, 1);
     ///
     /// let _ = std::fs::remove_file("dummy_db_ldel.csv");
     /// ```
     pub fn delete_elements_of_value_list(
         &mut self,
-        key: &str,
+        key: &[u8],
         cantidad_maxima: String,
-        element: String,
+        element: &[u8],
     ) -> usize {
         let mut cant_elementos_eliminados = 0;
         let cant_max = cantidad_maxima.parse::<isize>().unwrap_or(1);
-        if self.key_exists(key.to_string()) {
-            let old_item = self.get_mut_live_item(&key.to_string()).unwrap();
-            let item_optional = old_item.get_value();
-            if let ValueType::ListType(mut items) = item_optional.to_owned() {
+        if let Some(old_item) = self.get_mut_live_item(key) {
+            if let ValueType::ListType(items) = old_item.get_mut_value() {
                 let len_value_list = items.len();
-                match cant_max.cmp(&0) {
-                    Ordering::Greater => {
-                        let mut index = 0;
-                        for item in items.to_owned() {
-                            if cant_elementos_eliminados == cant_max {
-                                break;
-                            }
-                            if item == element {
-                                items.remove(index);
-                                cant_elementos_eliminados += 1;
-                            } else {
-                                index += 1;
-                            }
-                        }
+                // Se conservan las primeras coincidencias y se eliminan las siguientes; si la cantidad es
+                // negativa, las que se eliminan son las últimas.
+                let mut a_conservar = if cant_max < 0 {
+                    let coincidencias = items.iter().filter(|x| **x == element).count();
+                    coincidencias.saturating_sub(cant_max.unsigned_abs())
+                } else {
+                    0
+                };
+                let mut a_eliminar = match cant_max {
+                    0 => usize::MAX,
+                    _ => cant_max.unsigned_abs(),
+                };
+                items.retain(|x| {
+                    if *x != element {
+                        true
+                    } else if a_conservar > 0 {
+                        a_conservar -= 1;
+                        true
+                    } else if a_eliminar > 0 {
+                        a_eliminar -= 1;
+                        false
+                    } else {
+                        true
+                    }
+                });
+                cant_elementos_eliminados = len_value_list - items.len();
+            }
+        }
+        if cant_elementos_eliminados > 0 {
+            self.notify(EventClass::List, "lrem", key);
+        }
+        self.remove_key_if_empty_list(key);
+        cant_elementos_eliminados
+    }
+
+    /// Elimina la clave si almacena una lista vacía.
+    ///
+    /// Redis no mantiene listas vacías: cuando una operación deja la lista sin elementos, la clave se elimina.
+    fn remove_key_if_empty_list(&mut self, key: &[u8]) {
+        if let Some(item) = self.items.get(key) {
+            if let ValueType::ListType(list) = item.get_value() {
+                if list.is_empty() {
+                    self.items.delete(key);
+                    self.notify(EventClass::Generic, "del", key);
+                }
+            }
+        }
+    }
+
+    /// Verifica que el valor almacenado en `key` sea de tipo lista.
+    ///
+    /// Si la clave no existe o está expirada, no se considera un error.
+    fn check_list_type(&self, key: &[u8]) -> Result<(), DatabaseError> {
+        if let (Some(item), false) = self.check_timeout_item(key) {
+            if let ValueType::ListType(_) = item.get_value() {
+                return Ok(());
+            }
+            return Err(DatabaseError::InvalidValueType(WRONGTYPE.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Inserta `element` antes o después del primer elemento igual a `pivot` en la lista almacenada en `key`.
+    ///
+    /// Si `before` es True, lo inserta antes del pivot, sino lo inserta después.
+    /// Devuelve la longitud de la lista luego de la inserción, -1 si no se encontró el pivot
+    /// y 0 si la clave no existe.
+    /// Si el valor almacenado no es de tipo lista, devuelve error.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
+    ///
+    /// # let mut db = Database::new("dummy_db_linsert.csv".to_string());
+    /// let list = vec![b"argentina".to_vec(), b"chile".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"paises".to_vec(), vt);
+    ///
+    /// let len = db.insert_element_in_list(b"paises", true, b"chile", b"brasil").unwrap();
+    /// assert_eq!(len, 3);
+    /// assert_eq!(db.get_values_in_range(b"paises", "0", "-1").unwrap(), vec![b"argentina".to_vec(), b"brasil".to_vec(), b"chile".to_vec()]);
+    ///
+    /// let len = db.insert_element_in_list(b"paises", false, b"uruguay", b"peru").unwrap();
+    /// assert_eq!(len, -1);
+    ///
+    /// # let _ = std::fs::remove_file("dummy_db_linsert.csv");
+    /// ```
+    pub fn insert_element_in_list(
+        &mut self,
+        key: &[u8],
+        before: bool,
+        pivot: &[u8],
+        element: &[u8],
+    ) -> Result<isize, DatabaseError> {
+        if let Some(item) = self.get_mut_live_item(key) {
+            if let ValueType::ListType(list) = item.get_mut_value() {
+                match list.iter().position(|e| e == pivot) {
+                    Some(position) => {
+                        let index = if before { position } else { position + 1 };
+                        list.insert(index, element.to_vec());
+                        let len = list.len() as isize;
+                        self.notify(EventClass::List, "linsert", key);
+                        Ok(len)
+                    }
+                    None => Ok(-1),
+                }
+            } else {
+                Err(DatabaseError::InvalidValueType(WRONGTYPE.to_string()))
+            }
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Recorta la lista almacenada en `key` para que sólo contenga los elementos del rango [`start`, `stop`].
+    ///
+    /// Los índices negativos se cuentan desde el final de la lista, siendo -1 el último elemento.
+    /// Si `start` es mayor al final de la lista o mayor a `stop`, la lista queda vacía y se elimina la clave.
+    /// Si `stop` es mayor al final de la lista, se lo considera como el último elemento.
+    /// Si el valor almacenado no es de tipo lista, devuelve error.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
+    ///
+    /// # let mut db = Database::new("dummy_db_ltrim.csv".to_string());
+    /// let list = vec![b"argentina".to_vec(), b"brasil".to_vec(), b"chile".to_vec(), b"uruguay".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"paises".to_vec(), vt);
+    ///
+    /// db.trim_list(b"paises", 1, -2).unwrap();
+    /// assert_eq!(db.get_values_in_range(b"paises", "0", "-1").unwrap(), vec![b"brasil".to_vec(), b"chile".to_vec()]);
+    ///
+    /// db.trim_list(b"paises", 5, 10).unwrap();
+    /// assert!(db.get_live_item(b"paises").is_none());
+    ///
+    /// # let _ = std::fs::remove_file("dummy_db_ltrim.csv");
+    /// ```
+    pub fn trim_list(
+        &mut self,
+        key: &[u8],
+        start: isize,
+        stop: isize,
+    ) -> Result<(), DatabaseError> {
+        if let Some(item) = self.get_mut_live_item(key) {
+            if let ValueType::ListType(list) = item.get_mut_value() {
+                let len = list.len() as isize;
+                let mut start = if start < 0 { start + len } else { start };
+                let mut stop = if stop < 0 { stop + len } else { stop };
+                if start < 0 {
+                    start = 0;
+                }
+                if start > stop || start >= len {
+                    list.clear();
+                } else {
+                    if stop >= len {
+                        stop = len - 1;
                     }
-                    Ordering::Less => {
-                        let mut index = len_value_list - 1;
-                        for item in items.to_owned().into_iter().rev() {
-                            if cant_elementos_eliminados == cant_max.abs() {
+                    list.truncate((stop + 1) as usize);
+                    list.drain(..start as usize);
+                }
+                self.notify(EventClass::List, "ltrim", key);
+            } else {
+                return Err(DatabaseError::InvalidValueType(WRONGTYPE.to_string()));
+            }
+        }
+        self.remove_key_if_empty_list(key);
+        Ok(())
+    }
+
+    /// Devuelve las posiciones de los elementos iguales a `element` en la lista almacenada en `key`.
+    ///
+    /// `rank` indica a partir de qué coincidencia se empiezan a devolver posiciones: 1 es la primera,
+    /// 2 la segunda, etc. Si es negativo, la búsqueda se realiza desde el final de la lista.
+    /// `count` indica la cantidad máxima de posiciones a devolver, siendo 0 todas las coincidencias.
+    /// `maxlen` indica la cantidad máxima de elementos a comparar, siendo 0 toda la lista.
+    /// Si la clave no existe, devuelve una lista vacía.
+    /// Si `rank` es 0 o el valor almacenado no es de tipo lista, devuelve error.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
+    ///
+    /// # let mut db = Database::new("dummy_db_lpos.csv".to_string());
+    /// let list = vec!["a", "b", "c", "1", "2", "3", "c", "c"].iter().map(|e| e.as_bytes().to_vec()).collect::<Vec<Vec<u8>>>();
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"letras".to_vec(), vt);
+    ///
+    /// assert_eq!(db.get_positions_in_list(b"letras", b"c", 1, 0, 0).unwrap(), vec![2, 6, 7]);
+    /// assert_eq!(db.get_positions_in_list(b"letras", b"c", -1, 2, 0).unwrap(), vec![7, 6]);
+    /// assert_eq!(db.get_positions_in_list(b"letras", b"c", 2, 1, 0).unwrap(), vec![6]);
+    /// assert!(db.get_positions_in_list(b"letras", b"c", 1, 0, 2).unwrap().is_empty());
+    ///
+    /// # let _ = std::fs::remove_file("dummy_db_lpos.csv");
+    /// ```
+    pub fn get_positions_in_list(
+        &mut self,
+        key: &[u8],
+        element: &[u8],
+        rank: isize,
+        count: usize,
+        maxlen: usize,
+    ) -> Result<Vec<usize>, DatabaseError> {
+        if rank == 0 {
+            return Err(DatabaseError::InvalidParameter(
+                "RANK can't be zero: use 1 to start from the first match, 2 from the second ... or use negative to start from the end of the list".to_string(),
+            ));
+        }
+        let mut positions = Vec::new();
+        if let Some(item) = self.get_live_item(key) {
+            if let ValueType::ListType(list) = item.get_value() {
+                let len = list.len();
+                let maxlen = if maxlen == 0 { len } else { maxlen.min(len) };
+                let mut skip = rank.unsigned_abs() - 1;
+                let indexes: Box<dyn Iterator<Item = usize>> = if rank > 0 {
+                    Box::new(0..maxlen)
+                } else {
+                    Box::new((len - maxlen..len).rev())
+                };
+                for index in indexes {
+                    if list[index] == element {
+                        if skip > 0 {
+                            skip -= 1;
+                        } else {
+                            positions.push(index);
+                            if count != 0 && positions.len() == count {
                                 break;
-                            } else {
-                                if item == element {
-                                    items.remove(index);
-                                    cant_elementos_eliminados += 1;
-                                };
-                                if index > 0 {
-                                    index -= 1;
-                                } else {
-                                    index = 0
-                                }
                             }
                         }
                     }
-                    Ordering::Equal => {
-                        items.retain(|x| *x != element);
-                        cant_elementos_eliminados =
-                            (len_value_list as isize) - (items.len() as isize);
-                    }
                 }
-                old_item.set_value(ValueType::ListType(items));
+            } else {
+                return Err(DatabaseError::InvalidValueType(WRONGTYPE.to_string()));
+            }
+        }
+        Ok(positions)
+    }
+
+    /// Elimina un elemento de un extremo de la lista `source` y lo inserta en un extremo de la lista `destination`.
+    ///
+    /// Si `from_left` es True, elimina el primer elemento de `source`, sino el último.
+    /// Si `to_left` es True, inserta el elemento al comienzo de `destination`, sino al final.
+    /// Si `destination` no existe, la crea. `source` y `destination` pueden ser la misma clave,
+    /// en cuyo caso la lista se rota.
+    /// Devuelve el elemento movido, o None si `source` no existe.
+    /// Si alguna de las claves almacena un valor que no es de tipo lista, devuelve error.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::domain::implementations::database::Database;
+    /// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItem, ValueType, KeyAccessTime, ValueTimeItemBuilder};
+    ///
+    /// # let mut db = Database::new("dummy_db_lmove.csv".to_string());
+    /// let list = vec![b"argentina".to_vec(), b"brasil".to_vec(), b"chile".to_vec()];
+    /// let vt = ValueTimeItemBuilder::new(ValueType::ListType(list.into())).build();
+    /// db.add(b"paises".to_vec(), vt);
+    ///
+    /// let moved = db.move_element_between_lists(b"paises", b"visitados", false, true).unwrap();
+    /// assert_eq!(moved, Some(b"chile".to_vec()));
+    /// assert_eq!(db.get_values_in_range(b"visitados", "0", "-1").unwrap(), vec![b"chile".to_vec()]);
+    ///
+    /// let moved = db.move_element_between_lists(b"paises", b"paises", true, false).unwrap();
+    /// assert_eq!(moved, Some(b"argentina".to_vec()));
+    /// assert_eq!(db.get_values_in_range(b"paises", "0", "-1").unwrap(), vec![b"brasil".to_vec(), b"argentina".to_vec()]);
+    ///
+    /// # let _ = std::fs::remove_file("dummy_db_lmove.csv");
+    /// ```
+    pub fn move_element_between_lists(
+        &mut self,
+        source: &[u8],
+        destination: &[u8],
+        from_left: bool,
+        to_left: bool,
+    ) -> Result<Option<Vec<u8>>, DatabaseError> {
+        self.check_list_type(source)?;
+        self.check_list_type(destination)?;
+        let popped = if from_left {
+            self.pop_elements_from_list(source, 1)
+        } else {
+            self.rpop_elements_from_list(source, 1)
+        };
+        match popped.and_then(|mut elements| elements.pop()) {
+            Some(element) => {
+                if to_left {
+                    self.add_to_list_type(vec![element.clone()], destination, false);
+                } else {
+                    self.append_to_list_type(vec![element.clone()], destination, false);
+                }
+                Ok(Some(element))
             }
+            None => Ok(None),
         }
-        cant_elementos_eliminados as usize
+    }
+}
+
+/// Interpreta un valor de tipo String como número entero.
+///
+/// Devuelve `None` si el valor no es UTF-8 válido o no representa un entero.
+fn parse_integer(value: &[u8]) -> Option<i64> {
+    std::str::from_utf8(value).ok()?.parse::<i64>().ok()
+}
+
+/// Retorna la cantidad de bytes de los elementos del valor.
+fn value_size(value: &ValueType) -> usize {
+    match value {
+        ValueType::StringType(string) => string.len(),
+        ValueType::ListType(list) => list.iter().map(Vec::len).sum(),
+        ValueType::SetType(set) => set.iter().map(Vec::len).sum(),
     }
 }
 
@@ -1748,17 +2971,17 @@ impl Database {
 fn test_000_filter_keys_by_pattern() {
     let mut db = Database::new(String::from("./src/dummy_00.txt"));
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("valor_1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("valor_2".to_string())).build();
-    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("valor_3".to_string())).build();
-    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("valor_4".to_string())).build();
-    db.items.insert("weight_bananas".to_string(), vt_1);
-    db.items.insert("apples_weight".to_string(), vt_2);
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("valor_1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("valor_2".into())).build();
+    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("valor_3".into())).build();
+    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("valor_4".into())).build();
+    db.items.put(b"weight_bananas".to_vec(), vt_1);
+    db.items.put(b"apples_weight".to_vec(), vt_2);
     db.items
-        .insert("deliciosos_kiwi_weight_baratos".to_string(), vt_3);
-    db.items.insert("banana_weight".to_string(), vt_4);
+        .put(b"deliciosos_kiwi_weight_baratos".to_vec(), vt_3);
+    db.items.put(b"banana_weight".to_vec(), vt_4);
 
-    let vec_filtered = db.get_keys_that_match_pattern("*weight*");
+    let vec_filtered = db.get_keys_that_match_pattern(b"*weight*");
     assert_eq!(vec_filtered.len(), 4);
     let _ = std::fs::remove_file("./src/dummy_00.txt");
 }
@@ -1767,7 +2990,11 @@ fn test_000_filter_keys_by_pattern() {
 fn test_001_empty_database_returns_cero() {
     let db = Database {
         dbfilename: "file".to_string(),
-        items: HashMap::new(),
+        items: Box::new(MemoryStorage::new()),
+        notify_keyspace_events: KeyspaceEvents::default(),
+        notifications: Vec::new(),
+        modified_keys: Vec::new(),
+        deleted_keys: Vec::new(),
     };
 
     assert_eq!(db.get_size(), 0);
@@ -1777,17 +3004,21 @@ fn test_001_empty_database_returns_cero() {
 fn test_002_database_copies_value_to_new_key() {
     let mut db = Database::new(String::from("./src/dummy.txt"));
     db.add(
-        "clave_1".to_string(),
-        ValueTimeItemBuilder::new(ValueType::StringType("valor_1".to_string())).build(),
+        b"clave_1".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("valor_1".into())).build(),
     );
 
     let source = String::from("clave_1");
     let destination = String::from("clone");
-    assert_eq!(db.copy(source, destination, false).unwrap(), ());
+    assert_eq!(
+        db.copy(source.into_bytes(), destination.into_bytes(), false)
+            .unwrap(),
+        ()
+    );
 
-    let new_item = db.get_live_item("clone").unwrap();
+    let new_item = db.get_live_item(b"clone").unwrap();
     if let ValueType::StringType(str) = new_item.get_value() {
-        assert_eq!(str, &String::from("valor_1"));
+        assert_eq!(str, b"valor_1");
     }
     let _ = std::fs::remove_file("./src/dummy.txt");
 }
@@ -1796,30 +3027,38 @@ fn test_002_database_copies_value_to_new_key() {
 fn test_003_database_copy_replaces_key_with_new_value() {
     let mut db = Database::new(String::from("./src/dummy2.txt"));
     db.add(
-        "clave_1".to_string(),
-        ValueTimeItemBuilder::new(ValueType::StringType("valor_1".to_string())).build(),
+        b"clave_1".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("valor_1".into())).build(),
     );
     db.add(
-        "clave_2".to_string(),
-        ValueTimeItemBuilder::new(ValueType::StringType("valor_2".to_string())).build(),
+        b"clave_2".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("valor_2".into())).build(),
     );
 
     let source = String::from("clave_1");
     let destination = String::from("clone");
-    assert_eq!(db.copy(source, destination, false).unwrap(), ());
+    assert_eq!(
+        db.copy(source.into_bytes(), destination.into_bytes(), false)
+            .unwrap(),
+        ()
+    );
 
-    let new_item = db.get_live_item("clone").unwrap();
+    let new_item = db.get_live_item(b"clone").unwrap();
     if let ValueType::StringType(str) = new_item.get_value() {
-        assert_eq!(str, &String::from("valor_1"));
+        assert_eq!(str, b"valor_1");
     }
 
     let source = String::from("clave_2");
     let destination = String::from("clone");
-    assert_eq!(db.copy(source, destination, true).unwrap(), ());
+    assert_eq!(
+        db.copy(source.into_bytes(), destination.into_bytes(), true)
+            .unwrap(),
+        ()
+    );
 
-    let new_item = db.get_live_item("clone").unwrap();
+    let new_item = db.get_live_item(b"clone").unwrap();
     if let ValueType::StringType(str) = new_item.get_value() {
-        assert_eq!(str, &String::from("valor_2"));
+        assert_eq!(str, b"valor_2");
     }
     let _ = std::fs::remove_file("./src/dummy2.txt");
 }
@@ -1836,16 +3075,16 @@ fn test_004_clean_items_deletes_all_items() {
 fn test_005_deletes_an_item_succesfully() {
     let mut db = Database::new("file2".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("valor_1".to_string()))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("valor_1".into()))
         .with_timeout(0)
         .build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("valor_2".to_string()))
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("valor_2".into()))
         .with_timeout(0)
         .build();
-    db.items.insert("weight_bananas".to_string(), vt_1);
-    db.items.insert("apples_weight".to_string(), vt_2);
+    db.items.put(b"weight_bananas".to_vec(), vt_1);
+    db.items.put(b"apples_weight".to_vec(), vt_2);
 
-    db.delete_key("apples_weight".to_string());
+    db.delete_key(b"apples_weight".to_vec());
 
     assert_eq!(db.get_size(), 1);
     std::fs::remove_file("file2").unwrap();
@@ -1856,19 +3095,19 @@ fn test_006_persist_changes_type_of_access_time() {
     use crate::domain::entities::key_value_item::KeyAccessTime;
     let mut db = Database::new("file".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("valor_1".to_string()))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("valor_1".into()))
         .with_timeout(1825601548)
         .build();
 
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("valor_2".to_string()))
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("valor_2".into()))
         .with_timeout(1825601548)
         .build();
-    db.items.insert("weight_bananas".to_string(), vt_1);
-    db.items.insert("apples_weight".to_string(), vt_2);
+    db.items.put(b"weight_bananas".to_vec(), vt_1);
+    db.items.put(b"apples_weight".to_vec(), vt_2);
 
-    let _res = db.persist("weight_bananas".to_string());
+    let _res = db.persist(b"weight_bananas".to_vec());
 
-    let item = db.items.get(&"weight_bananas".to_string()).unwrap();
+    let item = db.items.get(b"weight_bananas").unwrap();
     match *item.get_timeout() {
         KeyAccessTime::Persistent => assert!(true),
         KeyAccessTime::Volatile(_tmt) => assert!(false),
@@ -1881,15 +3120,19 @@ fn test_006_persist_changes_type_of_access_time() {
 fn test_007_add_item() {
     let mut db = Database {
         dbfilename: "file".to_string(),
-        items: HashMap::new(),
+        items: Box::new(MemoryStorage::new()),
+        notify_keyspace_events: KeyspaceEvents::default(),
+        notifications: Vec::new(),
+        modified_keys: Vec::new(),
+        deleted_keys: Vec::new(),
     };
     db.add(
-        String::from("nueva_key"),
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("222"))).build(),
+        b"nueva_key".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("222".into())).build(),
     );
 
     assert_eq!(
-        db.items.get("nueva_key").unwrap().get_value().to_string(),
+        db.items.get(b"nueva_key").unwrap().get_value().to_string(),
         String::from("222")
     );
     assert_eq!(db.items.len(), 1)
@@ -1899,15 +3142,19 @@ fn test_007_add_item() {
 fn test_008_delete_item() {
     let mut db = Database {
         dbfilename: "file".to_string(),
-        items: HashMap::new(),
+        items: Box::new(MemoryStorage::new()),
+        notify_keyspace_events: KeyspaceEvents::default(),
+        notifications: Vec::new(),
+        modified_keys: Vec::new(),
+        deleted_keys: Vec::new(),
     };
-    db.items.insert(
-        String::from("nueva_key"),
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("222"))).build(),
+    db.items.put(
+        b"nueva_key".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("222".into())).build(),
     );
 
     assert_eq!(db.items.len(), 1);
-    db.delete_key(String::from("nueva_key"));
+    db.delete_key(b"nueva_key".to_vec());
     assert_eq!(db.items.len(), 0);
 }
 
@@ -1915,14 +3162,18 @@ fn test_008_delete_item() {
 fn test_009_filename_is_correct() {
     let db = Database {
         dbfilename: "file".to_string(),
-        items: HashMap::new(),
+        items: Box::new(MemoryStorage::new()),
+        notify_keyspace_events: KeyspaceEvents::default(),
+        notifications: Vec::new(),
+        modified_keys: Vec::new(),
+        deleted_keys: Vec::new(),
     };
     assert_eq!(db.get_filename(), &"file".to_string());
 }
 
 #[test]
 fn test_010_load_items_from_file() {
-    let mut file = File::create("file_5".to_string()).expect("Unable to open");
+    let mut file = File::create("file_5").expect("Unable to open");
     file.write_all(b"124key;1623433670;1623433677;string;value2\n")
         .unwrap();
 
@@ -1931,7 +3182,7 @@ fn test_010_load_items_from_file() {
     let mut iter = db.items.iter();
     let kvi = iter.next().unwrap();
 
-    assert_eq!(kvi.0, "124key");
+    assert_eq!(kvi.0, b"124key");
     assert_eq!(kvi.1.get_value().to_string(), String::from("value2"));
     match kvi.1.get_timeout() {
         KeyAccessTime::Volatile(1623433677) => assert!(true),
@@ -1955,24 +3206,24 @@ fn test_012_save_items_to_file() {
     let mut db = Database::new("file_save".to_string());
 
     let list = vec![
-        "un_item_string".to_string(),
-        "segundo_item_list_string".to_string(),
+        b"un_item_string".to_vec(),
+        b"segundo_item_list_string".to_vec(),
     ];
 
-    db.items.insert(
-        "clave_2".to_string(),
-        ValueTimeItemBuilder::new(ValueType::ListType(list))
+    db.items.put(
+        b"clave_2".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::ListType(list.into()))
             .with_timeout(1231230)
             .build(),
     );
     let last_access_time = db
         .items
-        .get("clave_2")
+        .get(b"clave_2")
         .unwrap()
         .get_last_access_time()
         .to_string();
 
-    db.save_items_to_file();
+    db.save_items_to_file().unwrap();
 
     let file = File::open(&db.dbfilename);
     let reader = BufReader::new(file.unwrap());
@@ -1994,15 +3245,15 @@ fn test_012_save_items_to_file() {
 fn test_013_size_in_memory_is_correct() {
     let mut db = Database::new("file013".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("valor_1".to_string()))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("valor_1".into()))
         .with_timeout(0)
         .build();
 
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("valor_2".to_string()))
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("valor_2".into()))
         .with_timeout(0)
         .build();
-    db.items.insert("weight_bananas".to_string(), vt_1);
-    db.items.insert("apples_weight".to_string(), vt_2);
+    db.items.put(b"weight_bananas".to_vec(), vt_1);
+    db.items.put(b"apples_weight".to_vec(), vt_2);
     std::fs::remove_file("file013").unwrap();
 }
 
@@ -2010,74 +3261,74 @@ fn test_013_size_in_memory_is_correct() {
 fn test_014_persist_changes_type_of_access_time() {
     use crate::domain::entities::key_value_item::KeyAccessTime;
     let mut db = Database::new(String::from("./src/dummy_persist.txt"));
-    let _res = db.items.insert(
-        "clave_1".to_string(),
-        ValueTimeItemBuilder::new(ValueType::StringType("value".to_string())).build(),
+    let _res = db.items.put(
+        b"clave_1".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("value".into())).build(),
     );
 
-    let item = db.items.get("clave_1").unwrap();
+    let item = db.items.get(b"clave_1").unwrap();
     match *item.get_timeout() {
         KeyAccessTime::Persistent => assert!(true),
         KeyAccessTime::Volatile(_tmt) => assert!(false),
     }
-    std::fs::remove_file("./src/dummy_persist.txt".to_string()).unwrap();
+    std::fs::remove_file("./src/dummy_persist.txt").unwrap();
 }
 
 #[test]
 fn test_015_append_adds_string_to_end_of_existing_value() {
     let mut db = Database::new(String::from("./src/dummy_appends_2.txt"));
-    let _res = db.items.insert(
-        "mykey".to_string(),
-        ValueTimeItemBuilder::new(ValueType::StringType("Hello".to_string())).build(),
+    let _res = db.items.put(
+        b"mykey".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("Hello".into())).build(),
     );
 
-    let len = db.append_string(&"mykey".to_string(), &" World".to_string());
+    let len = db.append_string(b"mykey", b" World");
     assert_eq!(len, 11);
-    std::fs::remove_file("./src/dummy_appends_2.txt".to_string()).unwrap();
+    std::fs::remove_file("./src/dummy_appends_2.txt").unwrap();
 }
 
 #[test]
 fn test_016_append_adds_string_to_new_value() {
     let mut db = Database::new(String::from("./src/dummy_appends_1.txt"));
 
-    let len = db.append_string(&"mykey".to_string(), &" World".to_string());
+    let len = db.append_string(b"mykey", b" World");
     assert_eq!(len, 6);
-    std::fs::remove_file("./src/dummy_appends_1.txt".to_string()).unwrap();
+    std::fs::remove_file("./src/dummy_appends_1.txt").unwrap();
 }
 
 #[test]
 fn test_017_decr_key_to_existing_key() {
     let mut db = Database::new(String::from("./src/dummy_decr_1.txt"));
-    let _res = db.items.insert(
-        "mykey".to_string(),
-        ValueTimeItemBuilder::new(ValueType::StringType("10".to_string())).build(),
+    let _res = db.items.put(
+        b"mykey".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("10".into())).build(),
     );
 
-    let res = db.decrement_key_by(&"mykey".to_string(), 3).unwrap();
+    let res = db.decrement_key_by(b"mykey", 3).unwrap();
     assert_eq!(res, 7);
-    std::fs::remove_file("./src/dummy_decr_1.txt".to_string()).unwrap();
+    std::fs::remove_file("./src/dummy_decr_1.txt").unwrap();
 }
 
 #[test]
 fn test_018_decr_by_to_new_key() {
     let mut db = Database::new(String::from("./src/dummy_decr.txt"));
 
-    let res = db.decrement_key_by(&"mykey".to_string(), 3).unwrap();
+    let res = db.decrement_key_by(b"mykey", 3).unwrap();
     assert_eq!(res, -3);
-    std::fs::remove_file("./src/dummy_decr.txt".to_string()).unwrap();
+    std::fs::remove_file("./src/dummy_decr.txt").unwrap();
 }
 
 #[test]
 fn test_019_decr_by_to_invalid_string_value() {
     let mut db = Database::new(String::from("./src/dummy_decr_2.txt"));
-    let _res = db.items.insert(
-        "mykey".to_string(),
-        ValueTimeItemBuilder::new(ValueType::StringType("Hello".to_string())).build(),
+    let _res = db.items.put(
+        b"mykey".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("Hello".into())).build(),
     );
 
-    let res = db.decrement_key_by(&"mykey".to_string(), 3);
+    let res = db.decrement_key_by(b"mykey", 3);
     assert!(res.is_err());
-    let _ = std::fs::remove_file("./src/dummy_decr_2.txt".to_string());
+    let _ = std::fs::remove_file("./src/dummy_decr_2.txt");
 }
 
 #[test]
@@ -2085,294 +3336,291 @@ fn test_020_se_obtienen_valores_de_claves_externas_a_partir_de_un_patron_y_una_l
 {
     let mut db = Database::new("file020".to_string());
 
-    let fruits = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "sandia".to_string(),
-        "pear".to_string(),
-        "apples".to_string(),
-    ]))
+    let fruits = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![b"sandia".to_vec(), b"pear".to_vec(), b"apples".to_vec()].into(),
+    ))
     .build();
 
-    db.items.insert("frutas".to_string(), fruits);
+    db.items.put(b"frutas".to_vec(), fruits);
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("2".to_string())).build();
-    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("11".to_string())).build();
-    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("5".to_string())).build();
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("2".into())).build();
+    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("11".into())).build();
+    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("5".into())).build();
 
-    db.items.insert("weight_bananas".to_string(), vt_1);
-    db.items.insert("weight_apples".to_string(), vt_2);
-    db.items.insert("weight_kiwi".to_string(), vt_3);
-    db.items.insert("weight_pear".to_string(), vt_4);
+    db.items.put(b"weight_bananas".to_vec(), vt_1);
+    db.items.put(b"weight_apples".to_vec(), vt_2);
+    db.items.put(b"weight_kiwi".to_vec(), vt_3);
+    db.items.put(b"weight_pear".to_vec(), vt_4);
 
-    let (tuplas, _) =
-        db.get_values_of_keys_matching_pattern("weight_*".to_string(), "frutas".to_string());
+    let (tuplas, _) = db.get_values_of_keys_matching_pattern(b"weight_*", b"frutas");
 
-    assert!(tuplas.contains(&("pear".to_string(), "5".to_string())));
-    assert!(tuplas.contains(&("apples".to_string(), "2".to_string())));
-    let _removed = std::fs::remove_file("file020".to_string());
+    assert!(tuplas.contains(&(b"pear".to_vec(), b"5".to_vec())));
+    assert!(tuplas.contains(&(b"apples".to_vec(), b"2".to_vec())));
+    let _removed = std::fs::remove_file("file020");
 }
 
 #[test]
 fn test_021_se_obtienen_keys_que_contienen_patron_regex_con_signo_de_pregunta() {
     let mut db = Database::new("file021".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("2".to_string())).build();
-    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("11".to_string())).build();
-    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("5".to_string())).build();
-    let vt_5 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_6 = ValueTimeItemBuilder::new(ValueType::StringType("2".to_string())).build();
-    let vt_7 = ValueTimeItemBuilder::new(ValueType::StringType("11".to_string())).build();
-    let vt_8 = ValueTimeItemBuilder::new(ValueType::StringType("5".to_string())).build();
-    db.items.insert("pablo".to_string(), vt_1);
-    db.items.insert("juan".to_string(), vt_2);
-    db.items.insert("mariana".to_string(), vt_3);
-    db.items.insert("lucia".to_string(), vt_4);
-    db.items.insert("mariano".to_string(), vt_5);
-    db.items.insert("meriana".to_string(), vt_6);
-    db.items.insert("miriana".to_string(), vt_7);
-    db.items.insert("luciana".to_string(), vt_8);
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("2".into())).build();
+    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("11".into())).build();
+    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("5".into())).build();
+    let vt_5 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_6 = ValueTimeItemBuilder::new(ValueType::StringType("2".into())).build();
+    let vt_7 = ValueTimeItemBuilder::new(ValueType::StringType("11".into())).build();
+    let vt_8 = ValueTimeItemBuilder::new(ValueType::StringType("5".into())).build();
+    db.items.put(b"pablo".to_vec(), vt_1);
+    db.items.put(b"juan".to_vec(), vt_2);
+    db.items.put(b"mariana".to_vec(), vt_3);
+    db.items.put(b"lucia".to_vec(), vt_4);
+    db.items.put(b"mariano".to_vec(), vt_5);
+    db.items.put(b"meriana".to_vec(), vt_6);
+    db.items.put(b"miriana".to_vec(), vt_7);
+    db.items.put(b"luciana".to_vec(), vt_8);
 
     let pat = "m?riana";
-    let matching_keys = db.get_keys_that_match_pattern(pat);
-    assert!(matching_keys.contains(&String::from("meriana")));
-    assert!(matching_keys.contains(&String::from("mariana")));
-    assert!(matching_keys.contains(&String::from("miriana")));
-    let _removed = std::fs::remove_file("file021".to_string());
+    let matching_keys = db.get_keys_that_match_pattern(pat.as_bytes());
+    assert!(matching_keys.contains(&b"meriana".to_vec()));
+    assert!(matching_keys.contains(&b"mariana".to_vec()));
+    assert!(matching_keys.contains(&b"miriana".to_vec()));
+    let _removed = std::fs::remove_file("file021");
 }
 
 #[test]
 fn test_022_se_obtienen_keys_que_contienen_patron_regex_solo_exp_entre_corchetes() {
     let mut db = Database::new("file022".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("2".to_string())).build();
-    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("11".to_string())).build();
-    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("5".to_string())).build();
-    let vt_5 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("2".into())).build();
+    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("11".into())).build();
+    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("5".into())).build();
+    let vt_5 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
 
-    let vt_6 = ValueTimeItemBuilder::new(ValueType::StringType("2".to_string())).build();
+    let vt_6 = ValueTimeItemBuilder::new(ValueType::StringType("2".into())).build();
 
-    let vt_7 = ValueTimeItemBuilder::new(ValueType::StringType("11".to_string())).build();
+    let vt_7 = ValueTimeItemBuilder::new(ValueType::StringType("11".into())).build();
 
-    let vt_8 = ValueTimeItemBuilder::new(ValueType::StringType("5".to_string())).build();
+    let vt_8 = ValueTimeItemBuilder::new(ValueType::StringType("5".into())).build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("juan".to_string(), vt_2);
-    db.items.insert("mariana".to_string(), vt_3);
-    db.items.insert("lucia".to_string(), vt_4);
-    db.items.insert("malala".to_string(), vt_5);
-    db.items.insert("meriana".to_string(), vt_6);
-    db.items.insert("miriana".to_string(), vt_7);
-    db.items.insert("luciana".to_string(), vt_8);
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"juan".to_vec(), vt_2);
+    db.items.put(b"mariana".to_vec(), vt_3);
+    db.items.put(b"lucia".to_vec(), vt_4);
+    db.items.put(b"malala".to_vec(), vt_5);
+    db.items.put(b"meriana".to_vec(), vt_6);
+    db.items.put(b"miriana".to_vec(), vt_7);
+    db.items.put(b"luciana".to_vec(), vt_8);
 
     let pat = "m[ae]riana";
-    let matching_keys = db.get_keys_that_match_pattern(pat);
+    let matching_keys = db.get_keys_that_match_pattern(pat.as_bytes());
 
-    assert!(matching_keys.contains(&String::from("meriana")));
-    assert!(matching_keys.contains(&String::from("mariana")));
+    assert!(matching_keys.contains(&b"meriana".to_vec()));
+    assert!(matching_keys.contains(&b"mariana".to_vec()));
 
-    let _removed = std::fs::remove_file("file022".to_string());
+    let _removed = std::fs::remove_file("file022");
 }
 
 #[test]
 fn test_023_se_obtienen_keys_que_contienen_patron_regex_excepto_exp_entre_corchetes_tipo_1() {
     let mut db = Database::new("file023".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("2".to_string())).build();
-    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("11".to_string())).build();
-    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("5".to_string())).build();
-    let vt_5 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_6 = ValueTimeItemBuilder::new(ValueType::StringType("2".to_string())).build();
-    let vt_7 = ValueTimeItemBuilder::new(ValueType::StringType("11".to_string())).build();
-    let vt_8 = ValueTimeItemBuilder::new(ValueType::StringType("5".to_string())).build();
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("juan".to_string(), vt_2);
-    db.items.insert("mariana".to_string(), vt_3);
-    db.items.insert("lucia".to_string(), vt_4);
-    db.items.insert("malala".to_string(), vt_5);
-    db.items.insert("meriana".to_string(), vt_6);
-    db.items.insert("miriana".to_string(), vt_7);
-    db.items.insert("luciana".to_string(), vt_8);
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("2".into())).build();
+    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("11".into())).build();
+    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("5".into())).build();
+    let vt_5 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_6 = ValueTimeItemBuilder::new(ValueType::StringType("2".into())).build();
+    let vt_7 = ValueTimeItemBuilder::new(ValueType::StringType("11".into())).build();
+    let vt_8 = ValueTimeItemBuilder::new(ValueType::StringType("5".into())).build();
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"juan".to_vec(), vt_2);
+    db.items.put(b"mariana".to_vec(), vt_3);
+    db.items.put(b"lucia".to_vec(), vt_4);
+    db.items.put(b"malala".to_vec(), vt_5);
+    db.items.put(b"meriana".to_vec(), vt_6);
+    db.items.put(b"miriana".to_vec(), vt_7);
+    db.items.put(b"luciana".to_vec(), vt_8);
 
     let pat = "m[^a]riana";
-    let matching_keys = db.get_keys_that_match_pattern(pat);
+    let matching_keys = db.get_keys_that_match_pattern(pat.as_bytes());
 
-    assert!(matching_keys.contains(&"mariana".to_string()));
+    assert!(matching_keys.contains(&b"mariana".to_vec()));
     assert!(matching_keys.len() == 1);
 
-    let _removed = std::fs::remove_file("file023".to_string());
+    let _removed = std::fs::remove_file("file023");
 }
 
 #[test]
 fn test_024_se_obtienen_keys_que_contienen_patron_regex_excepto_exp_entre_corchetes_tipo_2_rango() {
     let mut db = Database::new("file024".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("2".to_string())).build();
-
-    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("11".to_string())).build();
-    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("5".to_string())).build();
-    let vt_5 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_6 = ValueTimeItemBuilder::new(ValueType::StringType("2".to_string())).build();
-    let vt_7 = ValueTimeItemBuilder::new(ValueType::StringType("11".to_string())).build();
-    let vt_8 = ValueTimeItemBuilder::new(ValueType::StringType("5".to_string())).build();
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("juan".to_string(), vt_2);
-    db.items.insert("mariana".to_string(), vt_3);
-    db.items.insert("muriana".to_string(), vt_4);
-    db.items.insert("malala".to_string(), vt_5);
-    db.items.insert("meriana".to_string(), vt_6);
-    db.items.insert("miriana".to_string(), vt_7);
-    db.items.insert("moriana".to_string(), vt_8);
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("2".into())).build();
+
+    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("11".into())).build();
+    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("5".into())).build();
+    let vt_5 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_6 = ValueTimeItemBuilder::new(ValueType::StringType("2".into())).build();
+    let vt_7 = ValueTimeItemBuilder::new(ValueType::StringType("11".into())).build();
+    let vt_8 = ValueTimeItemBuilder::new(ValueType::StringType("5".into())).build();
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"juan".to_vec(), vt_2);
+    db.items.put(b"mariana".to_vec(), vt_3);
+    db.items.put(b"muriana".to_vec(), vt_4);
+    db.items.put(b"malala".to_vec(), vt_5);
+    db.items.put(b"meriana".to_vec(), vt_6);
+    db.items.put(b"miriana".to_vec(), vt_7);
+    db.items.put(b"moriana".to_vec(), vt_8);
 
     let pat = "m[a-o]riana";
-    let matching_keys = db.get_keys_that_match_pattern(pat);
+    let matching_keys = db.get_keys_that_match_pattern(pat.as_bytes());
 
-    assert!(matching_keys.contains(&String::from("meriana")));
-    assert!(matching_keys.contains(&String::from("mariana")));
-    assert!(matching_keys.contains(&String::from("miriana")));
-    assert!(matching_keys.contains(&String::from("moriana")));
-    let _ = std::fs::remove_file("file024".to_string());
+    assert!(matching_keys.contains(&b"meriana".to_vec()));
+    assert!(matching_keys.contains(&b"mariana".to_vec()));
+    assert!(matching_keys.contains(&b"miriana".to_vec()));
+    assert!(matching_keys.contains(&b"moriana".to_vec()));
+    let _ = std::fs::remove_file("file024");
 }
 
 #[test]
 fn test_025_se_obtienen_keys_que_contienen_patron_regex_asterisco() {
     let mut db = Database::new("file025".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("2".to_string())).build();
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("2".into())).build();
 
-    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("11".to_string())).build();
-    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("5".to_string())).build();
+    let vt_3 = ValueTimeItemBuilder::new(ValueType::StringType("11".into())).build();
+    let vt_4 = ValueTimeItemBuilder::new(ValueType::StringType("5".into())).build();
 
-    let vt_5 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_6 = ValueTimeItemBuilder::new(ValueType::StringType("2".to_string())).build();
-    let vt_7 = ValueTimeItemBuilder::new(ValueType::StringType("11".to_string())).build();
-    let vt_8 = ValueTimeItemBuilder::new(ValueType::StringType("5".to_string())).build();
+    let vt_5 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_6 = ValueTimeItemBuilder::new(ValueType::StringType("2".into())).build();
+    let vt_7 = ValueTimeItemBuilder::new(ValueType::StringType("11".into())).build();
+    let vt_8 = ValueTimeItemBuilder::new(ValueType::StringType("5".into())).build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("jose".to_string(), vt_2);
-    db.items.insert("mariana".to_string(), vt_3);
-    db.items.insert("pedro".to_string(), vt_4);
-    db.items.insert("malala".to_string(), vt_5);
-    db.items.insert("meriana".to_string(), vt_6);
-    db.items.insert("miriana".to_string(), vt_7);
-    db.items.insert("moriana".to_string(), vt_8);
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"jose".to_vec(), vt_2);
+    db.items.put(b"mariana".to_vec(), vt_3);
+    db.items.put(b"pedro".to_vec(), vt_4);
+    db.items.put(b"malala".to_vec(), vt_5);
+    db.items.put(b"meriana".to_vec(), vt_6);
+    db.items.put(b"miriana".to_vec(), vt_7);
+    db.items.put(b"moriana".to_vec(), vt_8);
 
     let pat = "m*a";
-    let matching_keys = db.get_keys_that_match_pattern(pat);
+    let matching_keys = db.get_keys_that_match_pattern(pat.as_bytes());
 
-    assert!(matching_keys.contains(&String::from("miriana")));
-    assert!(matching_keys.contains(&String::from("meriana")));
-    assert!(matching_keys.contains(&String::from("moriana")));
-    assert!(matching_keys.contains(&String::from("mariana")));
-    assert!(matching_keys.contains(&String::from("malala")));
-    assert!(matching_keys.contains(&String::from("mia")));
+    assert!(matching_keys.contains(&b"miriana".to_vec()));
+    assert!(matching_keys.contains(&b"meriana".to_vec()));
+    assert!(matching_keys.contains(&b"moriana".to_vec()));
+    assert!(matching_keys.contains(&b"mariana".to_vec()));
+    assert!(matching_keys.contains(&b"malala".to_vec()));
+    assert!(matching_keys.contains(&b"mia".to_vec()));
 
-    let _ = std::fs::remove_file("file025".to_string());
+    let _ = std::fs::remove_file("file025");
 }
 
 #[test]
 fn test_026_expire_key() {
     let mut db = Database::new("file026".to_string());
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string()))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into()))
         .with_timeout(1825601548)
         .build();
-    db.items.insert("key123".to_string(), vt_1);
+    db.items.put(b"key123".to_vec(), vt_1);
     let now = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap();
     let new_timeout = u64::from_str("10").unwrap() + now.as_secs();
-    db.expire_key("key123", &new_timeout.to_string());
-    let new_item = db.items.get("key123");
+    db.expire_key(b"key123", &new_timeout.to_string());
+    let new_item = db.items.get(b"key123");
     match new_item {
         Some(vti) => {
             assert_eq!(vti.get_timeout().to_string(), new_timeout.to_string());
         }
         None => assert!(false),
     }
-    let _ = std::fs::remove_file("file026".to_string());
+    let _ = std::fs::remove_file("file026");
 }
 
 #[test]
 fn test_027_reboot_time() {
     let mut db = Database::new("file027".to_string());
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string()))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into()))
         .with_timeout(1925583652)
         .with_last_access_time(u64::from_str("1211111").unwrap())
         .build();
-    db.items.insert("key123".to_string(), vt_1);
-    let old_access_time = db.items.get("key123").unwrap().get_last_access_time();
+    db.items.put(b"key123".to_vec(), vt_1);
+    let old_access_time = db.items.get(b"key123").unwrap().get_last_access_time();
     assert_eq!(old_access_time, &u64::from_str("1211111").unwrap());
     let now = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap()
         .as_secs();
 
-    if let Some(vti) = db.reboot_time("key123".to_string()) {
+    if let Some(vti) = db.reboot_time(b"key123".to_vec()) {
         assert!(vti.get_last_access_time().ge(&now));
     } else {
         assert!(false)
     }
 
-    let _ = std::fs::remove_file("file027".to_string());
+    let _ = std::fs::remove_file("file027");
 }
 #[test]
 fn test_028_reboot_time_expired() {
     let mut db = Database::new("file028".to_string());
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string()))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into()))
         .with_timeout(12123120)
         .with_last_access_time(u64::from_str("1211111").unwrap())
         .build();
-    db.items.insert("key123".to_string(), vt_1);
-    let old_access_time = db.items.get("key123").unwrap().get_last_access_time();
+    db.items.put(b"key123".to_vec(), vt_1);
+    let old_access_time = db.items.get(b"key123").unwrap().get_last_access_time();
     assert_eq!(old_access_time, &u64::from_str("1211111").unwrap());
 
-    if let None = db.reboot_time("key123".to_string()) {
+    if db.reboot_time(b"key123".to_vec()).is_none() {
         assert!(true)
     } else {
         assert!(false)
     }
 
-    let _ = std::fs::remove_file("file028".to_string());
+    let _ = std::fs::remove_file("file028");
 }
 
 #[test]
 fn test_029_expired_passive_keys() {
     let mut db = Database::new("file029".to_string());
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string()))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into()))
         .with_timeout(1625326138)
         .build();
-    db.items.insert("key123".to_string(), vt_1);
+    db.items.put(b"key123".to_vec(), vt_1);
 
-    assert!(db.items.get("key123").is_some());
-    let item_expired = db.get_live_item(&"key123".to_string());
+    assert!(db.items.contains_key(b"key123"));
+    let item_expired = db.get_live_item(b"key123");
     match item_expired {
         Some(_) => assert!(false),
         None => assert!(true),
     }
-    assert!(db.items.get("key123").is_none());
-    let _ = std::fs::remove_file("file029".to_string());
+    assert!(!db.items.contains_key(b"key123"));
+    let _ = std::fs::remove_file("file029");
 }
 
 #[test]
 fn test_030_retrieve_live_keys() {
     let mut db = Database::new("file030".to_string());
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string()))
-        .with_timeout(1665326138)
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into()))
+        .with_timeout(1925487534)
         .build();
-    db.items.insert("key123".to_string(), vt_1);
+    db.items.put(b"key123".to_vec(), vt_1);
 
-    assert!(db.items.get("key123").is_some());
-    let item_expired = db.get_live_item(&"key123".to_string());
+    assert!(db.items.contains_key(b"key123"));
+    let item_expired = db.get_live_item(b"key123");
     match item_expired {
         Some(_) => assert!(true),
         None => assert!(false),
     }
-    assert!(db.items.get("key123").is_some());
-    let _ = std::fs::remove_file("file030".to_string());
+    assert!(db.items.contains_key(b"key123"));
+    let _ = std::fs::remove_file("file030");
 }
 
 #[test]
@@ -2381,33 +3629,32 @@ fn test_031_se_obtienen_las_claves_que_contienen_solo_string_values() {
 
     let mut db = Database::new("file031".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("hola".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("chau".to_string())).build();
-    let vt_3 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "hola".to_string(),
-        "chau".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("hola".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::StringType("chau".into())).build();
+    let vt_3 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![b"hola".to_vec(), b"chau".to_vec()].into(),
+    ))
     .build();
     let mut this_set = HashSet::new();
-    this_set.insert("value_1".to_string());
-    this_set.insert("value_2".to_string());
+    this_set.insert(b"value_1".to_vec());
+    this_set.insert(b"value_2".to_vec());
     let vt_4 = ValueTimeItemBuilder::new(ValueType::SetType(this_set))
         .with_timeout(0)
         .build();
-    db.add("saludo".to_string(), vt_1);
-    db.add("despido".to_string(), vt_2);
-    db.add("saludo_despido".to_string(), vt_3);
-    db.add("valores".to_string(), vt_4);
-
-    let aux = db.get_string_value_by_key("saludo").unwrap();
-    assert_eq!(aux, String::from("hola"));
-    let aux = db.get_string_value_by_key("despido").unwrap();
-    assert_eq!(aux, String::from("chau"));
-    let aux = db.get_string_value_by_key("saludo_despido");
+    db.add(b"saludo".to_vec(), vt_1);
+    db.add(b"despido".to_vec(), vt_2);
+    db.add(b"saludo_despido".to_vec(), vt_3);
+    db.add(b"valores".to_vec(), vt_4);
+
+    let aux = db.get_string_value_by_key(b"saludo").unwrap();
+    assert_eq!(aux, b"hola");
+    let aux = db.get_string_value_by_key(b"despido").unwrap();
+    assert_eq!(aux, b"chau");
+    let aux = db.get_string_value_by_key(b"saludo_despido");
     assert!(aux.is_err());
-    let aux = db.get_string_value_by_key("valores");
+    let aux = db.get_string_value_by_key(b"valores");
     assert!(aux.is_err());
-    let _ = std::fs::remove_file("file031".to_string());
+    let _ = std::fs::remove_file("file031");
 }
 
 #[test]
@@ -2415,60 +3662,60 @@ fn test_032_scard_de_set_existente_devuelve_cantidad_de_elementos() {
     use std::collections::HashSet;
     let mut db = Database::new("file032".to_string());
     let mut this_set = HashSet::new();
-    this_set.insert("value_1".to_string());
-    this_set.insert("value_2".to_string());
+    this_set.insert(b"value_1".to_vec());
+    this_set.insert(b"value_2".to_vec());
 
     let vt = ValueTimeItemBuilder::new(ValueType::SetType(this_set)).build();
 
-    db.items.insert("valores".to_string(), vt);
-    let len = db.get_len_of_set("valores");
+    db.items.put(b"valores".to_vec(), vt);
+    let len = db.get_len_of_set(b"valores");
     assert_eq!(len, 2);
-    let _ = std::fs::remove_file("file032".to_string());
+    let _ = std::fs::remove_file("file032");
 }
 
 #[test]
 fn test_033_scard_de_set_devuelve_cero_si_no_existe() {
     let db = Database::new("file033".to_string());
 
-    let len = db.get_len_of_set("valores");
+    let len = db.get_len_of_set(b"valores");
     assert_eq!(len, 0);
-    let _ = std::fs::remove_file("file033".to_string());
+    let _ = std::fs::remove_file("file033");
 }
 
 #[test]
 fn test_034_scard_de_set_devuelve_cero_si_no_es_tipo_set() {
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("hola".to_string()))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("hola".into()))
         .with_timeout(0)
         .build();
     let mut db = Database::new("file034".to_string());
-    db.items.insert("saludo".to_string(), vt_1);
+    db.items.put(b"saludo".to_vec(), vt_1);
 
-    let len = db.get_len_of_set("saludo");
+    let len = db.get_len_of_set(b"saludo");
     assert_eq!(len, 0);
-    let _ = std::fs::remove_file("file034".to_string());
+    let _ = std::fs::remove_file("file034");
 }
 
 #[test]
 fn test_035_ismember_de_set_devuelve_cero_si_no_es_tipo_set() {
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("hola".to_string()))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("hola".into()))
         .with_timeout(0)
         .build();
 
     let mut db = Database::new("file035".to_string());
-    db.items.insert("saludo".to_string(), vt_1);
+    db.items.put(b"saludo".to_vec(), vt_1);
 
-    let len = db.is_member_of_set("saludo", "hola");
+    let len = db.is_member_of_set(b"saludo", b"hola");
     assert_eq!(len, 0);
-    let _ = std::fs::remove_file("file035".to_string());
+    let _ = std::fs::remove_file("file035");
 }
 
 #[test]
 fn test_036_ismember_de_set_devuelve_cero_si_no_existe_clave() {
     let db = Database::new("file036".to_string());
 
-    let len = db.is_member_of_set("valores", "hola");
+    let len = db.is_member_of_set(b"valores", b"hola");
     assert_eq!(len, 0);
-    let _ = std::fs::remove_file("file036".to_string());
+    let _ = std::fs::remove_file("file036");
 }
 
 #[test]
@@ -2476,14 +3723,14 @@ fn test_037_ismember_de_set_existente_devuelve_uno() {
     use std::collections::HashSet;
     let mut db = Database::new("file037".to_string());
     let mut this_set = HashSet::new();
-    this_set.insert("value_1".to_string());
+    this_set.insert(b"value_1".to_vec());
 
     let vt = ValueTimeItemBuilder::new(ValueType::SetType(this_set)).build();
 
-    db.items.insert("valores".to_string(), vt);
-    let is_member = db.is_member_of_set("valores", "value_1");
+    db.items.put(b"valores".to_vec(), vt);
+    let is_member = db.is_member_of_set(b"valores", b"value_1");
     assert_eq!(is_member, 1);
-    let _ = std::fs::remove_file("file037".to_string());
+    let _ = std::fs::remove_file("file037");
 }
 
 #[test]
@@ -2491,14 +3738,14 @@ fn test_038_ismember_de_set_existente_devuelve_cero_si_no_pertenece_al_set() {
     use std::collections::HashSet;
     let mut db = Database::new("file038".to_string());
     let mut this_set = HashSet::new();
-    this_set.insert("value_1".to_string());
+    this_set.insert(b"value_1".to_vec());
 
     let vt = ValueTimeItemBuilder::new(ValueType::SetType(this_set)).build();
 
-    db.items.insert("valores".to_string(), vt);
-    let is_member = db.is_member_of_set("valores", "value_2");
+    db.items.put(b"valores".to_vec(), vt);
+    let is_member = db.is_member_of_set(b"valores", b"value_2");
     assert_eq!(is_member, 0);
-    let _ = std::fs::remove_file("file038".to_string());
+    let _ = std::fs::remove_file("file038");
 }
 
 #[test]
@@ -2506,19 +3753,19 @@ fn test_039_get_members_of_set_existente_devuelve_elementos_del_set() {
     use std::collections::HashSet;
     let mut db = Database::new("file039".to_string());
     let mut this_set = HashSet::new();
-    this_set.insert("value_1".to_string());
-    this_set.insert("value_2".to_string());
-    this_set.insert("value_3".to_string());
+    this_set.insert(b"value_1".to_vec());
+    this_set.insert(b"value_2".to_vec());
+    this_set.insert(b"value_3".to_vec());
 
     let vt = ValueTimeItemBuilder::new(ValueType::SetType(this_set)).build();
 
-    db.items.insert("valores".to_string(), vt);
-    let members = db.get_members_of_set("valores");
-    assert!(members.contains(&&String::from("value_1")));
-    assert!(members.contains(&&String::from("value_2")));
-    assert!(members.contains(&&String::from("value_3")));
+    db.items.put(b"valores".to_vec(), vt);
+    let members = db.get_members_of_set(b"valores");
+    assert!(members.contains(&&b"value_1".to_vec()));
+    assert!(members.contains(&&b"value_2".to_vec()));
+    assert!(members.contains(&&b"value_3".to_vec()));
     assert_eq!(members.len(), 3);
-    let _ = std::fs::remove_file("file039".to_string());
+    let _ = std::fs::remove_file("file039");
 }
 
 #[test]
@@ -2526,16 +3773,16 @@ fn test_040_remove_member_from_existing_set_returns_true() {
     use std::collections::HashSet;
     let mut db = Database::new("file040".to_string());
     let mut this_set = HashSet::new();
-    this_set.insert("value_1".to_string());
-    this_set.insert("value_2".to_string());
+    this_set.insert(b"value_1".to_vec());
+    this_set.insert(b"value_2".to_vec());
 
     let vt = ValueTimeItemBuilder::new(ValueType::SetType(this_set)).build();
 
-    db.items.insert("valores".to_string(), vt);
-    let removed = db.remove_member_from_set("valores", "value_1").unwrap();
-    assert_eq!(removed, true);
+    db.items.put(b"valores".to_vec(), vt);
+    let removed = db.remove_member_from_set(b"valores", b"value_1").unwrap();
+    assert!(removed);
 
-    let _ = std::fs::remove_file("file040".to_string());
+    let _ = std::fs::remove_file("file040");
 }
 
 #[test]
@@ -2543,591 +3790,1067 @@ fn test_041_remove_member_from_non_existing_set_returns_false() {
     use std::collections::HashSet;
     let mut db = Database::new("file041".to_string());
     let mut this_set = HashSet::new();
-    this_set.insert("value_1".to_string());
-    this_set.insert("value_2".to_string());
+    this_set.insert(b"value_1".to_vec());
+    this_set.insert(b"value_2".to_vec());
 
     let vt = ValueTimeItemBuilder::new(ValueType::SetType(this_set)).build();
 
-    db.items.insert("values".to_string(), vt);
-    let removed = db.remove_member_from_set("valores", "value_1").unwrap();
-    assert_eq!(removed, false);
+    db.items.put(b"values".to_vec(), vt);
+    let removed = db.remove_member_from_set(b"valores", b"value_1").unwrap();
+    assert!(!removed);
 
-    let _ = std::fs::remove_file("file041".to_string());
+    let _ = std::fs::remove_file("file041");
 }
 
 #[test]
 fn test_042_remove_member_from_list_type_returns_none() {
     let mut db = Database::new("file042".to_string());
-    let vt = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "hola".to_string(),
-        "chau".to_string(),
-    ]))
+    let vt = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![b"hola".to_vec(), b"chau".to_vec()].into(),
+    ))
     .build();
-    db.items.insert("saludo".to_string(), vt);
-    let removed = db.remove_member_from_set("saludo", "value_1");
+    db.items.put(b"saludo".to_vec(), vt);
+    let removed = db.remove_member_from_set(b"saludo", b"value_1");
     assert!(removed.is_none());
 
-    let _ = std::fs::remove_file("file042".to_string());
+    let _ = std::fs::remove_file("file042");
 }
 
 #[test]
 fn test_043_pop_one_element_from_list_returns_popped_element() {
     let mut db = Database::new("file043".to_string());
-    let vt = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "hola".to_string(),
-        "chau".to_string(),
-    ]))
+    let vt = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![b"hola".to_vec(), b"chau".to_vec()].into(),
+    ))
     .build();
-    db.items.insert("saludo".to_string(), vt);
-    let removed = db.pop_elements_from_list("saludo", 1).unwrap();
-    assert_eq!(removed, vec![String::from("hola")]);
-    let item = db.get_live_item("saludo").unwrap();
+    db.items.put(b"saludo".to_vec(), vt);
+    let removed = db.pop_elements_from_list(b"saludo", 1).unwrap();
+    assert_eq!(removed, vec![b"hola".to_vec()]);
+    let item = db.get_live_item(b"saludo").unwrap();
     if let ValueType::ListType(item) = item.get_value() {
-        assert_eq!(item, &vec![String::from("chau")]);
+        assert_eq!(item, &vec![b"chau".to_vec()]);
     } else {
         assert!(false);
     }
 
-    let _ = std::fs::remove_file("file043".to_string());
+    let _ = std::fs::remove_file("file043");
 }
 
 #[test]
 fn test_044_pop_multiple_elements_from_list_returns_popped_elements() {
     let mut db = Database::new("file044".to_string());
-    let vt = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "hola".to_string(),
-        "chau".to_string(),
-        "hello".to_string(),
-        "bye".to_string(),
-    ]))
+    let vt = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"hola".to_vec(),
+            b"chau".to_vec(),
+            b"hello".to_vec(),
+            b"bye".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    db.items.insert("saludo".to_string(), vt);
-    let removed = db.pop_elements_from_list("saludo", 2).unwrap();
-    assert_eq!(removed, vec![String::from("hola"), String::from("chau")]);
-    let item = db.get_live_item("saludo").unwrap();
+    db.items.put(b"saludo".to_vec(), vt);
+    let removed = db.pop_elements_from_list(b"saludo", 2).unwrap();
+    assert_eq!(removed, vec![b"hola".to_vec(), b"chau".to_vec()]);
+    let item = db.get_live_item(b"saludo").unwrap();
     if let ValueType::ListType(item) = item.get_value() {
-        assert!(item.contains(&String::from("hello")) && item.contains(&String::from("bye")));
+        assert!(item.contains(&b"hello".to_vec()) && item.contains(&b"bye".to_vec()));
     } else {
         assert!(false);
     }
 
-    let _ = std::fs::remove_file("file044".to_string());
+    let _ = std::fs::remove_file("file044");
 }
 
 #[test]
 fn test_045_rpop_one_element_from_list_returns_popped_element() {
     let mut db = Database::new("file045".to_string());
-    let vt = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "hola".to_string(),
-        "chau".to_string(),
-    ]))
+    let vt = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![b"hola".to_vec(), b"chau".to_vec()].into(),
+    ))
     .build();
-    db.items.insert("saludo".to_string(), vt);
-    let removed = db.rpop_elements_from_list("saludo", 1).unwrap();
-    assert_eq!(removed, vec![String::from("chau")]);
-    let item = db.get_live_item("saludo").unwrap();
+    db.items.put(b"saludo".to_vec(), vt);
+    let removed = db.rpop_elements_from_list(b"saludo", 1).unwrap();
+    assert_eq!(removed, vec![b"chau".to_vec()]);
+    let item = db.get_live_item(b"saludo").unwrap();
     if let ValueType::ListType(item) = item.get_value() {
-        assert_eq!(item, &vec![String::from("hola")]);
+        assert_eq!(item, &vec![b"hola".to_vec()]);
     } else {
         assert!(false);
     }
 
-    let _ = std::fs::remove_file("file045".to_string());
+    let _ = std::fs::remove_file("file045");
 }
 
 #[test]
 fn test_046_rpop_multiple_elements_from_list_returns_popped_elements() {
     let mut db = Database::new("file046".to_string());
-    let vt = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "hola".to_string(),
-        "chau".to_string(),
-        "hello".to_string(),
-        "bye".to_string(),
-    ]))
+    let vt = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"hola".to_vec(),
+            b"chau".to_vec(),
+            b"hello".to_vec(),
+            b"bye".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    db.items.insert("saludo".to_string(), vt);
-    let removed = db.rpop_elements_from_list("saludo", 2).unwrap();
-    assert_eq!(removed, vec![String::from("bye"), String::from("hello")]);
-    let item = db.get_live_item("saludo").unwrap();
+    db.items.put(b"saludo".to_vec(), vt);
+    let removed = db.rpop_elements_from_list(b"saludo", 2).unwrap();
+    assert_eq!(removed, vec![b"bye".to_vec(), b"hello".to_vec()]);
+    let item = db.get_live_item(b"saludo").unwrap();
     if let ValueType::ListType(item) = item.get_value() {
-        assert!(item.contains(&String::from("hola")) && item.contains(&String::from("chau")));
+        assert!(item.contains(&b"hola".to_vec()) && item.contains(&b"chau".to_vec()));
     } else {
         assert!(false);
     }
 
-    let _ = std::fs::remove_file("file046".to_string());
+    let _ = std::fs::remove_file("file046");
 }
 
 #[test]
 fn test_047_rpush_multiple_elements_to_list_returns_length() {
     let mut db = Database::new("file047".to_string());
-    let vt = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "hola".to_string(),
-        "chau".to_string(),
-        "hello".to_string(),
-        "bye".to_string(),
-    ]))
+    let vt = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"hola".to_vec(),
+            b"chau".to_vec(),
+            b"hello".to_vec(),
+            b"bye".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    db.items.insert("saludo".to_string(), vt);
-    let len = db.push_vec_to_list(
-        vec![String::from("salut"), String::from("au revoir")],
-        "saludo",
-    );
+    db.items.put(b"saludo".to_vec(), vt);
+    let len = db.push_vec_to_list(vec![b"salut".to_vec(), b"au revoir".to_vec()], b"saludo");
     assert_eq!(len, 6);
 
-    let _ = std::fs::remove_file("file047".to_string());
+    let _ = std::fs::remove_file("file047");
 }
 
 #[test]
 fn test_048_rpush_to_nonexisting_key_returns_zero() {
     let mut db = Database::new("file048".to_string());
-    let vt = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "hola".to_string(),
-        "chau".to_string(),
-        "hello".to_string(),
-        "bye".to_string(),
-    ]))
+    let vt = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"hola".to_vec(),
+            b"chau".to_vec(),
+            b"hello".to_vec(),
+            b"bye".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    db.items.insert("despido".to_string(), vt);
-    let len = db.push_vec_to_list(
-        vec![String::from("salut"), String::from("au revoir")],
-        "saludo",
-    );
+    db.items.put(b"despido".to_vec(), vt);
+    let len = db.push_vec_to_list(vec![b"salut".to_vec(), b"au revoir".to_vec()], b"saludo");
     assert_eq!(len, 0);
 
-    let _ = std::fs::remove_file("file048".to_string());
+    let _ = std::fs::remove_file("file048");
 }
 
 #[test]
 fn test_049_rpush_to_string_returns_zero() {
     let mut db = Database::new("file049".to_string());
-    let vt = ValueTimeItemBuilder::new(ValueType::StringType("hola".to_string())).build();
-    db.items.insert("saludo".to_string(), vt);
-    let len = db.push_vec_to_list(
-        vec![String::from("salut"), String::from("au revoir")],
-        "saludo",
-    );
+    let vt = ValueTimeItemBuilder::new(ValueType::StringType("hola".into())).build();
+    db.items.put(b"saludo".to_vec(), vt);
+    let len = db.push_vec_to_list(vec![b"salut".to_vec(), b"au revoir".to_vec()], b"saludo");
     assert_eq!(len, 0);
 
-    let _ = std::fs::remove_file("file049".to_string());
+    let _ = std::fs::remove_file("file049");
 }
 
 #[test]
 fn test_050_se_eliminan_3_elementos_de_value_list_type() {
     let mut db = Database::new("file050".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "dog".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"dog".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("phrase".to_string(), vt_2);
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"phrase".to_vec(), vt_2);
 
-    let values_deleted =
-        db.delete_elements_of_value_list("phrase", "3".to_string(), "my".to_string());
+    let values_deleted = db.delete_elements_of_value_list(b"phrase", "3".to_string(), b"my");
 
     assert_eq!(3, values_deleted);
 
-    std::fs::remove_file("file050".to_string()).unwrap();
+    std::fs::remove_file("file050").unwrap();
 }
 
 #[test]
 fn test_051_se_eliminan_todos_los_elementos_de_value_list_type() {
     let mut db = Database::new("file051".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "dog".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"dog".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("phrase".to_string(), vt_2);
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"phrase".to_vec(), vt_2);
 
-    let values_deleted =
-        db.delete_elements_of_value_list("phrase", "0".to_string(), "my".to_string());
+    let values_deleted = db.delete_elements_of_value_list(b"phrase", "0".to_string(), b"my");
 
     assert_eq!(4, values_deleted);
-    std::fs::remove_file("file051".to_string()).unwrap();
+    std::fs::remove_file("file051").unwrap();
 }
 
 #[test]
 fn test_052_se_eliminan_3_elementos_de_value_list_type_en_reversa() {
     let mut db = Database::new("file052".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "dog".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "dear".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"dog".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"dear".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("phrase".to_string(), vt_2);
-    let values_deleted =
-        db.delete_elements_of_value_list("phrase", "-3".to_string(), "my".to_string());
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"phrase".to_vec(), vt_2);
+    let values_deleted = db.delete_elements_of_value_list(b"phrase", "-3".to_string(), b"my");
     assert_eq!(3, values_deleted);
-    std::fs::remove_file("file052".to_string()).unwrap();
+    std::fs::remove_file("file052").unwrap();
 }
 
 #[test]
 fn test_053_se_obtienen_3_elementos_de_un_value_de_tipo_list_clave_existe() {
     let mut db = Database::new("file053".to_string());
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "dog".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "dear".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"dog".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"dear".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("phrase".to_string(), vt_2);
-    let elements_got = db.get_values_in_range("phrase", "2", "4").unwrap();
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"phrase".to_vec(), vt_2);
+    let elements_got = db.get_values_in_range(b"phrase", "2", "4").unwrap();
     assert_eq!(3, elements_got.len());
-    std::fs::remove_file("file053".to_string()).unwrap();
+    std::fs::remove_file("file053").unwrap();
 }
 
 #[test]
 fn test_054_se_obtienen_3_elementos_de_un_value_de_tipo_list_clave_existe_con_lb_y_ub_negativos() {
     let mut db = Database::new("file054".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "dog".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "dear".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"dog".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"dear".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("phrase".to_string(), vt_2);
-    let elements_got = db.get_values_in_range("phrase", "-4", "-2").unwrap();
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"phrase".to_vec(), vt_2);
+    let elements_got = db.get_values_in_range(b"phrase", "-4", "-2").unwrap();
     assert_eq!(3, elements_got.len());
-    std::fs::remove_file("file054".to_string()).unwrap();
+    std::fs::remove_file("file054").unwrap();
 }
 
 #[test]
-fn test_055_se_obtiene_un_vector_vacio_de_1_elemento_cuando_lb_es_mayor_que_ub() {
+fn test_055_se_obtiene_un_vector_vacio_cuando_lb_es_mayor_que_ub() {
     let mut db = Database::new("file055".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "dog".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "dear".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"dog".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"dear".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("phrase".to_string(), vt_2);
-    let elements_got = db.get_values_in_range("phrase", "5", "2").unwrap();
-    assert_eq!(1, elements_got.len());
-    std::fs::remove_file("file055".to_string()).unwrap();
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"phrase".to_vec(), vt_2);
+    let elements_got = db.get_values_in_range(b"phrase", "5", "2").unwrap();
+    assert!(elements_got.is_empty());
+    std::fs::remove_file("file055").unwrap();
 }
 
 #[test]
 fn test_056_se_obtiene_un_vector_de_longitud_maxima_lenght_cuando_ub_es_mayor_que_lenght() {
     let mut db = Database::new("file056".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "dog".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "dear".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"dog".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"dear".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("phrase".to_string(), vt_2);
-    let elements_got = db.get_values_in_range("phrase", "0", "200").unwrap();
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"phrase".to_vec(), vt_2);
+    let elements_got = db.get_values_in_range(b"phrase", "0", "200").unwrap();
     assert_eq!(8, elements_got.len());
-    std::fs::remove_file("file056".to_string()).unwrap();
+    std::fs::remove_file("file056").unwrap();
 }
 
 #[test]
 fn test_057_se_obtiene_trozo_de_lista_de_value_de_tipo_list() {
     let mut db = Database::new("file057".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "dog".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "dear".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"dog".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"dear".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("phrase".to_string(), vt_2);
-    let trozo_value_list_type = db.get_values_in_range("phrase", "0", "2"); //("phrase", "-3".to_string(), "my".to_string());
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"phrase".to_vec(), vt_2);
+    let trozo_value_list_type = db.get_values_in_range(b"phrase", "0", "2"); //("phrase", "-3".to_string(), "my".to_string());
     assert_eq!(3, trozo_value_list_type.unwrap().len());
-    std::fs::remove_file("file057".to_string()).unwrap();
+    std::fs::remove_file("file057").unwrap();
 }
 
 #[test]
 fn test_058_se_obtiene_trozo_de_lista_de_value_de_tipo_list_lower_bound_negativo() {
     let mut db = Database::new("file058".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "dog".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "dear".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"dog".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"dear".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("phrase".to_string(), vt_2);
-    let trozo_value_list_type = db.get_values_in_range("phrase", "0", "-5"); //("phrase", "-3".to_string(), "my".to_string());
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"phrase".to_vec(), vt_2);
+    let trozo_value_list_type = db.get_values_in_range(b"phrase", "0", "-5"); //("phrase", "-3".to_string(), "my".to_string());
     assert_eq!(4, trozo_value_list_type.unwrap().len());
-    std::fs::remove_file("file058".to_string()).unwrap();
+    std::fs::remove_file("file058").unwrap();
 }
 
 #[test]
 fn test_059_se_obtiene_trozo_de_lista_de_value_de_tipo_list_lower_y_upper_bound_negativos() {
     let mut db = Database::new("file059".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "dog".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "dear".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"dog".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"dear".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("phrase".to_string(), vt_2);
-    let trozo_value_list_type = db.get_values_in_range("phrase", "-7", "-5"); //("phrase", "-3".to_string(), "my".to_string());
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"phrase".to_vec(), vt_2);
+    let trozo_value_list_type = db.get_values_in_range(b"phrase", "-7", "-5"); //("phrase", "-3".to_string(), "my".to_string());
     assert_eq!(3, trozo_value_list_type.unwrap().len());
-    std::fs::remove_file("file059".to_string()).unwrap();
+    std::fs::remove_file("file059").unwrap();
 }
 
 #[test]
 fn test_060_se_pisan_valores_en_value_de_tipo_list_type() {
     let mut db = Database::new("file060".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "juan".to_string(),
-        "pedro".to_string(),
-        "santiago".to_string(),
-        "mariano".to_string(),
-        "francisco".to_string(),
-        "domingo".to_string(),
-        "rolando".to_string(),
-        "fernando".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"juan".to_vec(),
+            b"pedro".to_vec(),
+            b"santiago".to_vec(),
+            b"mariano".to_vec(),
+            b"francisco".to_vec(),
+            b"domingo".to_vec(),
+            b"rolando".to_vec(),
+            b"fernando".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("nombres_masculinos".to_string(), vt_2);
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"nombres_masculinos".to_vec(), vt_2);
     let vec_actualizado =
-        db.replace_element_in_list_type_value("nombres_masculinos", "sergio", "0");
-    let current_value = db.get_mut_live_item("nombres_masculinos").unwrap();
+        db.replace_element_in_list_type_value(b"nombres_masculinos", b"sergio", "0");
+    let current_value = db.get_mut_live_item(b"nombres_masculinos").unwrap();
     let item_optional = current_value.get_value();
     if let ValueType::ListType(items) = item_optional.to_owned() {
-        assert_eq!("sergio".to_string(), items[0]);
+        assert_eq!(b"sergio".to_vec(), items[0]);
     }
-    assert_eq!(true, vec_actualizado);
-    std::fs::remove_file("file060".to_string()).unwrap();
+    assert!(vec_actualizado);
+    std::fs::remove_file("file060").unwrap();
 }
 
 #[test]
 fn test_061_no_se_reemplaza_valor_en_value_de_tipo_list_type_porque_fuera_de_rango() {
     let mut db = Database::new("file061".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "juan".to_string(),
-        "pedro".to_string(),
-        "santiago".to_string(),
-        "mariano".to_string(),
-        "francisco".to_string(),
-        "domingo".to_string(),
-        "rolando".to_string(),
-        "fernando".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"juan".to_vec(),
+            b"pedro".to_vec(),
+            b"santiago".to_vec(),
+            b"mariano".to_vec(),
+            b"francisco".to_vec(),
+            b"domingo".to_vec(),
+            b"rolando".to_vec(),
+            b"fernando".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("nombres_masculinos".to_string(), vt_2);
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"nombres_masculinos".to_vec(), vt_2);
     let vec_actualizado =
-        db.replace_element_in_list_type_value("nombres_masculinos", "sergio", "10");
-    assert_eq!(false, vec_actualizado);
-    std::fs::remove_file("file061".to_string()).unwrap();
+        db.replace_element_in_list_type_value(b"nombres_masculinos", b"sergio", "10");
+    assert!(!vec_actualizado);
+    std::fs::remove_file("file061").unwrap();
 }
 
 #[test]
 fn test_062_se_pisan_valores_en_value_de_tipo_list_type_con_indice_negativo_inbound() {
     let mut db = Database::new("file062".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "juan".to_string(),
-        "pedro".to_string(),
-        "santiago".to_string(),
-        "mariano".to_string(),
-        "francisco".to_string(),
-        "domingo".to_string(),
-        "rolando".to_string(),
-        "fernando".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"juan".to_vec(),
+            b"pedro".to_vec(),
+            b"santiago".to_vec(),
+            b"mariano".to_vec(),
+            b"francisco".to_vec(),
+            b"domingo".to_vec(),
+            b"rolando".to_vec(),
+            b"fernando".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    db.items.insert("mia".to_string(), vt_1);
-    db.items.insert("nombres_masculinos".to_string(), vt_2);
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"nombres_masculinos".to_vec(), vt_2);
     let vec_actualizado =
-        db.replace_element_in_list_type_value("nombres_masculinos", "sergio", "-1");
-    let current_value = db.get_mut_live_item("nombres_masculinos").unwrap();
+        db.replace_element_in_list_type_value(b"nombres_masculinos", b"sergio", "-1");
+    let current_value = db.get_mut_live_item(b"nombres_masculinos").unwrap();
     let item_optional = current_value.get_value();
     if let ValueType::ListType(items) = item_optional.to_owned() {
-        assert_eq!("sergio".to_string(), items[7]);
+        assert_eq!(b"sergio".to_vec(), items[7]);
     }
-    assert_eq!(true, vec_actualizado);
-    std::fs::remove_file("file062".to_string()).unwrap();
+    assert!(vec_actualizado);
+    std::fs::remove_file("file062").unwrap();
 }
 
 #[test]
 fn test_063_set_string_con_expire_returns_true() {
     let mut db = Database::new("file063".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    db.items.insert("mia".to_string(), vt_1);
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    db.items.put(b"mia".to_vec(), vt_1);
 
     assert!(db.set_string(
-        "mia",
-        "2",
+        b"mia",
+        b"2",
         (&"ex".to_string(), Some(&"10".to_string())),
-        None
+        None,
+        false
     ));
 
-    std::fs::remove_file("file063".to_string()).unwrap();
+    std::fs::remove_file("file063").unwrap();
 }
 
 #[test]
 fn test_064_set_string_con_expire_and_set_only_returns_true() {
     let mut db = Database::new("file064".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    db.items.insert("mia".to_string(), vt_1);
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    db.items.put(b"mia".to_vec(), vt_1);
 
     assert!(db.set_string(
-        "mia",
-        "2",
+        b"mia",
+        b"2",
         (&"ex".to_string(), Some(&"10".to_string())),
-        Some(&"xx".to_string())
+        Some(&"xx".to_string()),
+        false
     ));
 
-    std::fs::remove_file("file064".to_string()).unwrap();
+    std::fs::remove_file("file064").unwrap();
 }
 
 #[test]
 fn test_065_set_string_con_expire_and_set_only_returns_false() {
     let mut db = Database::new("file065".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".to_string())).build();
-    db.items.insert("mia".to_string(), vt_1);
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    db.items.put(b"mia".to_vec(), vt_1);
 
     assert!(!db.set_string(
-        "another",
-        "2",
+        b"another",
+        b"2",
         (&"ex".to_string(), Some(&"10".to_string())),
-        Some(&"xx".to_string())
+        Some(&"xx".to_string()),
+        false
     ));
 
-    std::fs::remove_file("file065".to_string()).unwrap();
+    std::fs::remove_file("file065").unwrap();
 }
 
 #[test]
 fn test_066_add_to_list_adds_elements_from_beginning() {
     let mut db = Database::new("file066".to_string());
 
-    let vt_1 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "1".to_string(),
-        "2".to_string(),
-        "3".to_string(),
-    ]))
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![b"1".to_vec(), b"2".to_vec(), b"3".to_vec()].into(),
+    ))
     .build();
-    db.items.insert("numbers".to_string(), vt_1);
+    db.items.put(b"numbers".to_vec(), vt_1);
 
     assert_eq!(
         db.add_to_list_type(
-            vec!["primero".to_string(), "segundo".to_string()],
-            "numbers",
+            vec![b"primero".to_vec(), b"segundo".to_vec()],
+            b"numbers",
             false
         )
         .unwrap(),
         5
     );
-    let item = db.get_live_item("numbers").unwrap().get_value();
+    let item = db.get_live_item(b"numbers").unwrap().get_value();
     if let ValueType::ListType(list) = item {
         assert_eq!(
             list,
             &vec![
-                "primero".to_string(),
-                "segundo".to_string(),
-                "1".to_string(),
-                "2".to_string(),
-                "3".to_string()
+                b"primero".to_vec(),
+                b"segundo".to_vec(),
+                b"1".to_vec(),
+                b"2".to_vec(),
+                b"3".to_vec()
             ]
         )
     }
-    std::fs::remove_file("file066".to_string()).unwrap();
+    std::fs::remove_file("file066").unwrap();
+}
+
+#[test]
+fn test_067_pop_all_elements_removes_list_key() {
+    let mut db = Database::new("file067".to_string());
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![b"1".to_vec(), b"2".to_vec()].into(),
+    ))
+    .build();
+    db.items.put(b"numbers".to_vec(), vt_1);
+
+    assert_eq!(db.pop_elements_from_list(b"numbers", 5).unwrap().len(), 2);
+    assert!(!db.items.contains_key(b"numbers"));
+    std::fs::remove_file("file067").unwrap();
+}
+
+#[test]
+fn test_068_append_to_list_keeps_order_of_new_elements() {
+    let mut db = Database::new("file068".to_string());
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::ListType(vec![b"1".to_vec()].into()))
+        .with_timeout(1925487534)
+        .build();
+    db.items.put(b"numbers".to_vec(), vt_1);
+
+    assert_eq!(
+        db.append_to_list_type(vec![b"2".to_vec(), b"3".to_vec()], b"numbers", false)
+            .unwrap(),
+        3
+    );
+    assert_eq!(
+        db.get_values_in_range(b"numbers", "0", "-1").unwrap(),
+        vec![b"1".to_vec(), b"2".to_vec(), b"3".to_vec()]
+    );
+    assert!(matches!(
+        db.get_live_item(b"numbers").unwrap().get_timeout(),
+        KeyAccessTime::Volatile(1925487534)
+    ));
+    std::fs::remove_file("file068").unwrap();
+}
+
+#[test]
+fn test_069_get_values_in_range_handles_out_of_range_bounds() {
+    let mut db = Database::new("file069".to_string());
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![b"1".to_vec(), b"2".to_vec(), b"3".to_vec()].into(),
+    ))
+    .build();
+    db.items.put(b"numbers".to_vec(), vt_1);
+
+    assert_eq!(
+        db.get_values_in_range(b"numbers", "-100", "0").unwrap(),
+        vec![b"1".to_vec()]
+    );
+    assert_eq!(
+        db.get_values_in_range(b"numbers", "2", "100").unwrap(),
+        vec![b"3".to_vec()]
+    );
+    assert!(db
+        .get_values_in_range(b"numbers", "3", "5")
+        .unwrap()
+        .is_empty());
+    assert!(db
+        .get_values_in_range(b"numbers", "0", "-4")
+        .unwrap()
+        .is_empty());
+    std::fs::remove_file("file069").unwrap();
+}
+
+#[test]
+fn test_070_trim_list_with_negative_indexes() {
+    let mut db = Database::new("file070".to_string());
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![b"1".to_vec(), b"2".to_vec(), b"3".to_vec()].into(),
+    ))
+    .build();
+    db.items.put(b"numbers".to_vec(), vt_1);
+
+    db.trim_list(b"numbers", -100, -2).unwrap();
+    assert_eq!(
+        db.get_values_in_range(b"numbers", "0", "-1").unwrap(),
+        vec![b"1".to_vec(), b"2".to_vec()]
+    );
+    db.trim_list(b"numbers", 1, 0).unwrap();
+    assert!(!db.items.contains_key(b"numbers"));
+    std::fs::remove_file("file070").unwrap();
+}
+
+#[test]
+fn test_071_insert_and_move_on_wrong_type_returns_error() {
+    let mut db = Database::new("file071".to_string());
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into())).build();
+    let vt_2 = ValueTimeItemBuilder::new(ValueType::ListType(vec![b"a".to_vec()].into())).build();
+    db.items.put(b"mia".to_vec(), vt_1);
+    db.items.put(b"letters".to_vec(), vt_2);
+
+    assert!(db.insert_element_in_list(b"mia", true, b"1", b"0").is_err());
+    assert_eq!(
+        db.insert_element_in_list(b"missing", true, b"1", b"0"),
+        Ok(0)
+    );
+    assert!(db
+        .move_element_between_lists(b"letters", b"mia", true, true)
+        .is_err());
+    assert!(db.items.contains_key(b"letters"));
+    assert_eq!(
+        db.move_element_between_lists(b"missing", b"letters", true, true),
+        Ok(None)
+    );
+    std::fs::remove_file("file071").unwrap();
+}
+
+#[test]
+fn test_072_get_positions_in_list_with_rank_zero_returns_error() {
+    let mut db = Database::new("file072".to_string());
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::ListType(vec![b"a".to_vec()].into())).build();
+    db.items.put(b"letters".to_vec(), vt_1);
+
+    assert!(db.get_positions_in_list(b"letters", b"a", 0, 1, 0).is_err());
+    assert_eq!(
+        db.get_positions_in_list(b"letters", b"a", -1, 0, 0),
+        Ok(vec![0])
+    );
+    assert_eq!(
+        db.get_positions_in_list(b"missing", b"a", 1, 0, 0),
+        Ok(vec![])
+    );
+    std::fs::remove_file("file072").unwrap();
+}
+
+#[test]
+fn test_073_set_operations_ignore_expired_keys() {
+    let mut db = Database::new("file073".to_string());
+    let set: HashSet<Vec<u8>> = vec![b"a".to_vec(), b"b".to_vec()].into_iter().collect();
+    db.items.put(
+        b"live".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::SetType(set.clone())).build(),
+    );
+    db.items.put(
+        b"expired".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::SetType(set))
+            .with_timeout(1)
+            .build(),
+    );
+
+    let keys = vec![b"live".to_vec(), b"expired".to_vec()];
+    assert!(db.intersect_sets(&keys).unwrap().is_empty());
+    assert_eq!(db.union_sets(&keys).unwrap().len(), 2);
+    assert_eq!(db.diff_sets(&keys).unwrap().len(), 2);
+    std::fs::remove_file("file073").unwrap();
+}
+
+#[test]
+fn test_074_set_operations_on_wrong_type_return_error() {
+    let mut db = Database::new("file074".to_string());
+    db.items.put(
+        b"string".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("a".into())).build(),
+    );
+
+    let keys = vec![b"missing".to_vec(), b"string".to_vec()];
+    assert!(db.intersect_sets(&keys).is_err());
+    assert!(db.union_sets(&keys).is_err());
+    assert!(db
+        .move_member_between_sets(b"string", b"dest", b"a")
+        .is_err());
+    assert!(db.pop_members_from_set(b"string", 1).is_err());
+    assert!(db.get_random_members_of_set(b"string", 1).is_err());
+    std::fs::remove_file("file074").unwrap();
+}
+
+#[test]
+fn test_075_move_member_and_pop_members_of_set() {
+    let mut db = Database::new("file075".to_string());
+    let set: HashSet<Vec<u8>> = vec![b"a".to_vec(), b"b".to_vec()].into_iter().collect();
+    db.items.put(
+        b"source".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::SetType(set)).build(),
+    );
+
+    assert_eq!(
+        db.move_member_between_sets(b"source", b"dest", b"a"),
+        Ok(true)
+    );
+    assert_eq!(
+        db.move_member_between_sets(b"source", b"dest", b"z"),
+        Ok(false)
+    );
+    assert_eq!(db.is_member_of_set(b"dest", b"a"), 1);
+    assert_eq!(
+        db.pop_members_from_set(b"source", 3),
+        Ok(vec![b"b".to_vec()])
+    );
+    assert!(!db.items.contains_key(b"source"));
+    assert_eq!(db.get_random_members_of_set(b"dest", -3).unwrap().len(), 3);
+    assert_eq!(db.get_random_members_of_set(b"dest", 3).unwrap().len(), 1);
+    std::fs::remove_file("file075").unwrap();
+}
+
+#[test]
+fn test_076_increment_key_by_overflow_returns_error() {
+    let mut db = Database::new("file076".to_string());
+    let vt_1 =
+        ValueTimeItemBuilder::new(ValueType::StringType(i64::MAX.to_string().into())).build();
+    db.items.put(b"max".to_vec(), vt_1);
+
+    assert!(db.increment_key_by(b"max", 1).is_err());
+    assert!(db.decrement_key_by(b"missing", i64::MIN).is_err());
+    assert_eq!(
+        db.get_string_value_by_key(b"max"),
+        Ok(i64::MAX.to_string().into_bytes())
+    );
+    std::fs::remove_file("file076").unwrap();
+}
+
+#[test]
+fn test_077_increment_key_by_float_on_invalid_value_returns_error() {
+    let mut db = Database::new("file077".to_string());
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("abc".into())).build();
+    db.items.put(b"text".to_vec(), vt_1);
+
+    assert!(db.increment_key_by_float(b"text", 1.0).is_err());
+    assert_eq!(
+        db.increment_key_by_float(b"new", 2.5),
+        Ok("2.5".to_string())
+    );
+    assert!(db.increment_key_by_float(b"new", f64::MAX).is_ok());
+    assert!(db.increment_key_by_float(b"new", f64::MAX).is_err());
+    std::fs::remove_file("file077").unwrap();
+}
+
+#[test]
+fn test_078_set_range_pads_missing_key_with_zero_bytes() {
+    let mut db = Database::new("file078".to_string());
+
+    assert_eq!(db.set_range_of_string(b"missing", 0, b""), Ok(0));
+    assert!(!db.items.contains_key(b"missing"));
+    assert_eq!(db.set_range_of_string(b"padded", 3, b"ab"), Ok(5));
+    assert_eq!(
+        db.get_string_value_by_key(b"padded"),
+        Ok(b"\0\0\0ab".to_vec())
+    );
+    assert_eq!(
+        db.get_range_of_string(b"padded", 3, 100),
+        Ok(b"ab".to_vec())
+    );
+    assert_eq!(db.get_range_of_string(b"padded", 4, 2), Ok(vec![]));
+    std::fs::remove_file("file078").unwrap();
+}
+
+#[test]
+fn test_079_set_string_discards_ttl_unless_keep_ttl() {
+    let mut db = Database::new("file079".to_string());
+    let vt_1 = ValueTimeItemBuilder::new(ValueType::StringType("1".into()))
+        .with_timeout(4_000_000_000)
+        .build();
+    db.items.put(b"mia".to_vec(), vt_1);
+
+    assert!(db.set_string(b"mia", b"2", (&"".to_string(), None), None, true));
+    assert!(matches!(
+        db.items.get(b"mia").unwrap().get_timeout(),
+        KeyAccessTime::Volatile(4_000_000_000)
+    ));
+    assert!(db.set_string(b"mia", b"3", (&"".to_string(), None), None, false));
+    assert!(matches!(
+        db.items.get(b"mia").unwrap().get_timeout(),
+        KeyAccessTime::Persistent
+    ));
+    std::fs::remove_file("file079").unwrap();
+}
+
+#[test]
+fn test_080_binary_string_values_survive_dump_and_load() {
+    let value = b"\xff\x00;,%\r\nfin".to_vec();
+    let mut db = Database::new("file080".to_string());
+    assert!(db.set_string(b"binario", &value, (&"".to_string(), None), None, false));
+    assert_eq!(db.append_string(b"binario", b"\xc3\x28"), value.len() + 2);
+    db.save_items_to_file().unwrap();
+
+    let loaded = Database::new("file080".to_string());
+    assert_eq!(
+        loaded.get_string_value_by_key(b"binario"),
+        Ok([value, b"\xc3\x28".to_vec()].concat())
+    );
+    std::fs::remove_file("file080").unwrap();
+}
+
+#[test]
+fn test_081_writes_and_lazy_expiry_register_keyspace_events() {
+    let mut db = Database::new("file081".to_string());
+    db.set_notify_keyspace_events(KeyspaceEvents::parse("Egx$l").unwrap());
+    db.add(
+        b"vencida".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("a".into()))
+            .with_timeout(123)
+            .build(),
+    );
+    assert!(db.set_string(
+        b"clave",
+        b"1",
+        (&"ex".to_string(), Some(&"10".to_string())),
+        None,
+        false
+    ));
+    db.rename_key(b"clave".to_vec(), b"nueva".to_vec());
+    db.pop_elements_from_list(b"vencida", 1);
+    db.add_to_list_type(vec![b"a".to_vec()], b"lista", false);
+    db.pop_elements_from_list(b"lista", 1);
+
+    let events: Vec<String> = db
+        .take_notifications()
+        .into_iter()
+        .map(|notification| format!("{} {}", notification.channel, notification.message))
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            "__keyevent@0__:set clave",
+            "__keyevent@0__:expire clave",
+            "__keyevent@0__:rename_from clave",
+            "__keyevent@0__:rename_to nueva",
+            "__keyevent@0__:expired vencida",
+            "__keyevent@0__:lpush lista",
+            "__keyevent@0__:lpop lista",
+            "__keyevent@0__:del lista",
+        ]
+    );
+    assert!(db.take_notifications().is_empty());
+    let _ = std::fs::remove_file("file081");
+}
+
+#[test]
+fn test_082_active_expiry_and_eviction_register_keyspace_events() {
+    let mut db = Database::new("file082".to_string());
+    db.set_notify_keyspace_events(KeyspaceEvents::parse("Kxe").unwrap());
+    db.add(
+        b"vencida".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("a".into()))
+            .with_timeout(123)
+            .build(),
+    );
+    db.add(
+        b"vieja".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("abc".into()))
+            .with_last_access_time(1)
+            .build(),
+    );
+    db.add(
+        b"nueva".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("abc".into())).build(),
+    );
+
+    assert_eq!(db.remove_expired_keys(), 1);
+    assert_eq!(db.used_memory(), 16);
+    assert_eq!(db.evict_keys(10), 1);
+    assert_eq!(
+        db.take_notifications(),
+        vec![
+            KeyspaceNotification {
+                channel: String::from("__keyspace@0__:vencida"),
+                message: String::from("expired"),
+            },
+            KeyspaceNotification {
+                channel: String::from("__keyspace@0__:vieja"),
+                message: String::from("evicted"),
+            },
+        ]
+    );
+    let _ = std::fs::remove_file("file082");
+}
+
+#[test]
+fn test_083_delete_elements_of_list_respects_count_direction() {
+    use std::collections::VecDeque;
+
+    let mut db = Database::new("file083".to_string());
+    let list = |db: &mut Database| {
+        let values: VecDeque<Vec<u8>> = ["a", "b", "a", "c", "a"]
+            .iter()
+            .map(|value| value.as_bytes().to_vec())
+            .collect();
+        db.add(
+            b"letras".to_vec(),
+            ValueTimeItemBuilder::new(ValueType::ListType(values)).build(),
+        );
+    };
+
+    list(&mut db);
+    assert_eq!(
+        db.delete_elements_of_value_list(b"letras", "2".to_string(), b"a"),
+        2
+    );
+    assert_eq!(
+        db.get_values_in_range(b"letras", "0", "-1").unwrap(),
+        vec![b"b".to_vec(), b"c".to_vec(), b"a".to_vec()]
+    );
+
+    list(&mut db);
+    assert_eq!(
+        db.delete_elements_of_value_list(b"letras", "-2".to_string(), b"a"),
+        2
+    );
+    assert_eq!(
+        db.get_values_in_range(b"letras", "0", "-1").unwrap(),
+        vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]
+    );
+
+    list(&mut db);
+    assert_eq!(
+        db.delete_elements_of_value_list(b"letras", "0".to_string(), b"a"),
+        3
+    );
+    assert_eq!(
+        db.get_values_in_range(b"letras", "0", "-1").unwrap(),
+        vec![b"b".to_vec(), b"c".to_vec()]
+    );
+    let _ = std::fs::remove_file("file083");
+}
+
+#[test]
+fn test_084_pop_members_from_set_removes_popped_members() {
+    let mut db = Database::new("file084".to_string());
+    let set: HashSet<Vec<u8>> = (0..10).map(|n| n.to_string().into_bytes()).collect();
+    db.add(
+        b"numeros".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::SetType(set)).build(),
+    );
+
+    let popped = db.pop_members_from_set(b"numeros", 4).unwrap();
+    let distinct: HashSet<&Vec<u8>> = popped.iter().collect();
+    assert_eq!(distinct.len(), 4);
+    assert_eq!(db.get_len_of_set(b"numeros"), 6);
+    for member in &popped {
+        assert_eq!(db.is_member_of_set(b"numeros", member), 0);
+    }
+
+    let rest = db.pop_members_from_set(b"numeros", 10).unwrap();
+    assert_eq!(rest.len(), 6);
+    assert!(!db.key_exists(b"numeros".to_vec()));
+    let _ = std::fs::remove_file("file084");
 }
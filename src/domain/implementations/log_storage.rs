@@ -0,0 +1,301 @@
+//! Motor de almacenamiento persistente, que registra cada modificación en un log en disco.
+
+use super::storage::{MemoryStorage, Storage};
+use crate::domain::entities::key_value_item::ValueTimeItem;
+use crate::domain::entities::key_value_item_serialized::{
+    escape_field, serialize_item, unescape_field, KeyValueItemSerialized,
+};
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Error, ErrorKind, Write};
+
+/// Cantidad mínima de registros del log a partir de la cual se lo compacta (ver `LogStorage::sync`).
+const COMPACTION_MIN_RECORDS: usize = 1024;
+
+/// Motor de almacenamiento que mantiene los datos en memoria y registra cada modificación al final de un archivo de log,
+/// para que los datos sobrevivan a un reinicio del servidor sin bajar periódicamente el archivo *dump* completo.
+///
+/// Cada línea del log es un registro:
+/// * `+<key>;<last_access_time>;<timeout>;<type>;<value>`: la clave tiene el valor indicado, con el formato del *dump*.
+/// * `-<key>`: la clave se eliminó.
+///
+/// Al abrirse se aplican los registros en orden; un último registro sin salto de línea quedó a medio escribir y se
+/// descarta. Las modificaciones se escriben al llamar a `sync`, una vez por clave modificada, y cuando el log acumula
+/// más del doble de registros que claves se lo reescribe con un registro por clave (compactación).
+#[derive(Debug)]
+pub struct LogStorage {
+    path: String,
+    memory: MemoryStorage,
+    log: BufWriter<File>,
+    dirty: HashSet<Vec<u8>>,
+    records: usize,
+    rewrite: bool,
+}
+
+impl LogStorage {
+    /// Abre el log del archivo `path`, o lo crea si no existe, y carga los datos que registra.
+    ///
+    /// Devuelve error si el archivo no puede leerse o escribirse, o si contiene un registro que no es válido.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::implementations::log_storage::LogStorage;
+    /// use proyecto_taller_1::domain::implementations::storage::Storage;
+    /// use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+    ///
+    /// let mut storage = LogStorage::open("dummy_log_storage_open.log").unwrap();
+    /// storage.put(b"mascota".to_vec(), ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).build());
+    /// storage.sync().unwrap();
+    /// drop(storage);
+    ///
+    /// let storage = LogStorage::open("dummy_log_storage_open.log").unwrap();
+    /// assert_eq!(storage.get(b"mascota").unwrap().get_value().to_string(), "perro");
+    /// # let _ = std::fs::remove_file("dummy_log_storage_open.log");
+    /// ```
+    pub fn open(path: &str) -> io::Result<Self> {
+        let mut memory = MemoryStorage::new();
+        let mut records = 0;
+        let mut valid_len = 0;
+        match fs::read(path) {
+            Ok(content) => {
+                for line in content.split_inclusive(|byte| *byte == b'\n') {
+                    let record = match line.strip_suffix(b"\n") {
+                        Some(record) => record,
+                        None => break,
+                    };
+                    apply_record(&mut memory, &String::from_utf8_lossy(record))?;
+                    valid_len += line.len();
+                    records += 1;
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.set_len(valid_len as u64)?;
+        Ok(LogStorage {
+            path: path.to_string(),
+            memory,
+            log: BufWriter::new(file),
+            dirty: HashSet::new(),
+            records,
+            rewrite: false,
+        })
+    }
+
+    /// Reescribe el log con un registro por cada clave almacenada.
+    ///
+    /// El log nuevo se escribe en un archivo temporal que luego reemplaza al anterior, para que una falla
+    /// durante la compactación no pierda datos.
+    fn compact(&mut self) -> io::Result<()> {
+        let tmp_path = format!("{}.tmp", self.path);
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        for (key, item) in self.memory.iter() {
+            writeln!(writer, "+{}", serialize_item(key, item))?;
+        }
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+        self.log = BufWriter::new(OpenOptions::new().append(true).open(&self.path)?);
+        self.records = self.memory.len();
+        self.dirty.clear();
+        self.rewrite = false;
+        Ok(())
+    }
+
+    /// Escribe un registro por cada clave modificada desde la última sincronización y espera a que lleguen al disco.
+    fn append_dirty(&mut self) -> io::Result<()> {
+        for key in std::mem::take(&mut self.dirty) {
+            let record = match self.memory.get(&key) {
+                Some(item) => format!("+{}\n", serialize_item(&key, item)),
+                None => format!("-{}\n", escape_field(&key)),
+            };
+            self.log.write_all(record.as_bytes())?;
+            self.records += 1;
+        }
+        self.log.flush()?;
+        self.log.get_ref().sync_data()
+    }
+}
+
+impl Storage for LogStorage {
+    fn get(&self, key: &[u8]) -> Option<&ValueTimeItem> {
+        self.memory.get(key)
+    }
+
+    fn get_mut(&mut self, key: &[u8]) -> Option<&mut ValueTimeItem> {
+        if self.memory.contains_key(key) {
+            self.dirty.insert(key.to_vec());
+        }
+        self.memory.get_mut(key)
+    }
+
+    fn put(&mut self, key: Vec<u8>, item: ValueTimeItem) -> Option<ValueTimeItem> {
+        self.dirty.insert(key.clone());
+        self.memory.put(key, item)
+    }
+
+    fn delete(&mut self, key: &[u8]) -> Option<ValueTimeItem> {
+        let deleted = self.memory.delete(key);
+        if deleted.is_some() {
+            self.dirty.insert(key.to_vec());
+        }
+        deleted
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&Vec<u8>, &ValueTimeItem)> + '_> {
+        self.memory.iter()
+    }
+
+    fn len(&self) -> usize {
+        self.memory.len()
+    }
+
+    fn clear(&mut self) {
+        self.memory.clear();
+        self.dirty.clear();
+        self.rewrite = true;
+    }
+
+    fn volatile_keys(&mut self) -> Vec<Vec<u8>> {
+        self.memory.volatile_keys()
+    }
+
+    /// Escribe en el log las claves modificadas o, si se eliminaron todas las claves o el log acumula demasiados
+    /// registros, lo compacta.
+    ///
+    /// Si la escritura falla, la próxima sincronización reescribe el log completo, ya que pudo quedar incompleto.
+    fn sync(&mut self) -> io::Result<()> {
+        let result = if self.rewrite {
+            self.compact()
+        } else {
+            self.append_dirty().and_then(|_| {
+                if self.records > COMPACTION_MIN_RECORDS && self.records > 2 * self.memory.len() {
+                    self.compact()
+                } else {
+                    Ok(())
+                }
+            })
+        };
+        if result.is_err() {
+            self.rewrite = true;
+        }
+        result
+    }
+
+    fn is_persistent(&self) -> bool {
+        true
+    }
+}
+
+impl Drop for LogStorage {
+    fn drop(&mut self) {
+        let _ = self.sync();
+    }
+}
+
+/// Aplica un registro del log sobre los datos cargados.
+fn apply_record(memory: &mut MemoryStorage, record: &str) -> io::Result<()> {
+    if let Some(line) = record.strip_prefix('+') {
+        let (key, item) = KeyValueItemSerialized::new(line.to_string())
+            .try_transform_to_item()
+            .map_err(|error| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid storage log record '{}': {}", record, error),
+                )
+            })?;
+        memory.put(key, item);
+    } else if let Some(key) = record.strip_prefix('-') {
+        memory.delete(&unescape_field(key));
+    } else {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Invalid storage log record '{}'", record),
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_01_changes_survive_reopening_the_log() {
+    use crate::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+
+    let path = "dummy_log_storage_01.log";
+    let mut storage = LogStorage::open(path).unwrap();
+    storage.put(
+        b"fruta".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("kiwi".into())).build(),
+    );
+    storage.put(
+        b"borrada".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("x".into())).build(),
+    );
+    storage.sync().unwrap();
+    storage
+        .get_mut(b"fruta")
+        .unwrap()
+        .set_value(ValueType::StringType("pera".into()));
+    storage.delete(b"borrada");
+    storage.sync().unwrap();
+    drop(storage);
+    // un registro a medio escribir se descarta al abrir el log
+    let mut log = OpenOptions::new().append(true).open(path).unwrap();
+    log.write_all(b"+incompleta;0;;str").unwrap();
+    drop(log);
+
+    let storage = LogStorage::open(path).unwrap();
+    assert_eq!(storage.len(), 1);
+    assert_eq!(
+        storage.get(b"fruta").unwrap().get_value().to_string(),
+        "pera"
+    );
+    assert_eq!(fs::read_to_string(path).unwrap().lines().count(), 4);
+    drop(storage);
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn test_02_clear_rewrites_the_log() {
+    use crate::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+
+    let path = "dummy_log_storage_02.log";
+    let mut storage = LogStorage::open(path).unwrap();
+    for key in ["a", "b", "c"] {
+        storage.put(
+            key.as_bytes().to_vec(),
+            ValueTimeItemBuilder::new(ValueType::StringType(key.into())).build(),
+        );
+    }
+    storage.sync().unwrap();
+    storage.clear();
+    storage.put(
+        b"d".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("d".into())).build(),
+    );
+    storage.sync().unwrap();
+
+    assert_eq!(fs::read_to_string(path).unwrap().lines().count(), 1);
+    drop(storage);
+    let storage = LogStorage::open(path).unwrap();
+    assert!(storage.contains_key(b"d"));
+    assert!(!storage.contains_key(b"a"));
+    drop(storage);
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn test_03_corrupt_record_is_invalid_data() {
+    let path = "dummy_log_storage_03.log";
+    fs::write(
+        path,
+        "+a;1623427130;;string;a\n+b;no_es_un_tiempo;;string;b\n",
+    )
+    .unwrap();
+
+    let error = LogStorage::open(path).unwrap_err();
+
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(fs::read_to_string(path).unwrap().lines().count(), 2);
+    let _ = fs::remove_file(path);
+}
@@ -37,6 +37,21 @@ impl Logger {
         self.file.write_all(message)?;
         Ok(())
     }
+
+    /// Se asegura de que todos los mensajes escritos queden guardados en el archivo de log.
+    ///
+    /// ```
+    /// use proyecto_taller_1::domain::implementations::logger_impl::Logger;
+    ///
+    /// let mut logger = Logger::new("dummy_logger_flush").unwrap();
+    /// logger.log("log some message".as_bytes()).unwrap();
+    /// logger.flush().unwrap();
+    /// # std::fs::remove_file("dummy_logger_flush");
+    /// ```
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+        self.file.sync_data()
+    }
 }
 
 #[test]
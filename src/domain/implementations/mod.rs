@@ -1,2 +1,6 @@
+pub mod connection;
 pub mod database;
+pub mod log_storage;
 pub mod logger_impl;
+pub mod output_queue;
+pub mod storage;
@@ -0,0 +1,235 @@
+//! Cola de mensajes salientes de un cliente, que un hilo propio escribe sobre su conexión.
+
+use super::connection::Connection;
+use std::io::{Error, ErrorKind, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Límite por defecto de la clase `pubsub` de `client-output-buffer-limit`: 32mb, 8mb durante 60 segundos.
+pub const DEFAULT_PUBSUB_LIMIT: OutputBufferLimit = OutputBufferLimit {
+    hard: 32 * 1024 * 1024,
+    soft: 8 * 1024 * 1024,
+    soft_seconds: 60,
+};
+
+/// Límite de bytes pendientes de enviar a un cliente, configurado con la directiva `client-output-buffer-limit`.
+///
+/// El cliente se desconecta en cuanto sus bytes pendientes alcanzan `hard`, o si se mantienen por encima de `soft`
+/// durante `soft_seconds` segundos seguidos. Un límite en 0 no se aplica.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutputBufferLimit {
+    pub hard: usize,
+    pub soft: usize,
+    pub soft_seconds: u64,
+}
+
+impl OutputBufferLimit {
+    /// Obtiene el límite de la clase `pubsub` del valor de la directiva `client-output-buffer-limit`.
+    ///
+    /// El valor se compone de grupos `<clase> <hard> <soft> <segundos>`, donde los tamaños admiten las unidades
+    /// `k`, `kb`, `m`, `mb`, `g` y `gb`. Las clases `normal` y `replica` se aceptan pero no se aplican.
+    /// Si el valor no especifica la clase `pubsub`, se utiliza `DEFAULT_PUBSUB_LIMIT`.
+    ///
+    /// # Example
+    /// ```
+    /// use proyecto_taller_1::domain::implementations::output_queue::OutputBufferLimit;
+    ///
+    /// let limit = OutputBufferLimit::parse_pubsub("normal 0 0 0 pubsub 1mb 512kb 10").unwrap();
+    /// assert_eq!(limit, OutputBufferLimit { hard: 1024 * 1024, soft: 512 * 1024, soft_seconds: 10 });
+    /// assert!(OutputBufferLimit::parse_pubsub("pubsub 1mb").is_err());
+    /// ```
+    pub fn parse_pubsub(value: &str) -> Result<Self, Error> {
+        let words: Vec<&str> = value.split_whitespace().collect();
+        if words.is_empty() || !words.len().is_multiple_of(4) {
+            return Err(invalid_limit(value));
+        }
+        let mut pubsub = DEFAULT_PUBSUB_LIMIT;
+        for group in words.chunks(4) {
+            let limit = OutputBufferLimit {
+                hard: parse_memory(group[1]).ok_or_else(|| invalid_limit(value))?,
+                soft: parse_memory(group[2]).ok_or_else(|| invalid_limit(value))?,
+                soft_seconds: group[3].parse().map_err(|_| invalid_limit(value))?,
+            };
+            match group[0].to_lowercase().as_str() {
+                "pubsub" => pubsub = limit,
+                "normal" | "replica" | "slave" => {}
+                _ => return Err(invalid_limit(value)),
+            }
+        }
+        Ok(pubsub)
+    }
+}
+
+/// Error que se devuelve cuando el valor de `client-output-buffer-limit` no es válido.
+fn invalid_limit(value: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidInput,
+        format!("Invalid client-output-buffer-limit '{}'", value),
+    )
+}
+
+/// Convierte un tamaño en bytes, con unidades opcionales `k`, `kb`, `m`, `mb`, `g` o `gb`.
+pub fn parse_memory(size: &str) -> Option<usize> {
+    let size = size.to_lowercase();
+    let digits = size.trim_end_matches(char::is_alphabetic);
+    let unit = match &size[digits.len()..] {
+        "" | "b" => 1,
+        "k" | "kb" => 1024,
+        "m" | "mb" => 1024 * 1024,
+        "g" | "gb" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    digits.parse::<usize>().ok()?.checked_mul(unit)
+}
+
+/// Cola de mensajes pendientes de enviar a un cliente.
+///
+/// Los mensajes se encolan sin bloquear a quien los envía, y un hilo propio del cliente los escribe sobre su conexión
+/// en el mismo orden. Así, un cliente lento no demora al servidor ni a los demás clientes.
+/// La cola lleva la cuenta de los bytes encolados que todavía no se escribieron, para aplicar el límite de la
+/// directiva `client-output-buffer-limit`.
+/// Las copias de la cola comparten el hilo y la cuenta de bytes pendientes: el worker que atiende al cliente encola
+/// las respuestas a sus comandos en la misma cola que el servidor utiliza para los mensajes de los canales.
+#[derive(Debug, Clone)]
+pub struct OutputQueue {
+    sender: Sender<Vec<u8>>,
+    pending: Arc<AtomicUsize>,
+    soft_limit_since: Option<Instant>,
+}
+
+impl OutputQueue {
+    /// Crea la cola e inicia el hilo que escribe sus mensajes sobre la conexión.
+    ///
+    /// El hilo termina cuando se descartan todas las copias de la cola, luego de escribir los mensajes pendientes,
+    /// o cuando falla una escritura, por ejemplo porque se cerró la conexión.
+    pub fn new(mut stream: Connection) -> Self {
+        let (sender, receiver) = mpsc::channel::<Vec<u8>>();
+        let pending = Arc::new(AtomicUsize::new(0));
+        let written = pending.clone();
+        thread::spawn(move || {
+            for message in receiver {
+                if stream
+                    .write_all(&message)
+                    .and_then(|_| stream.flush())
+                    .is_err()
+                {
+                    break;
+                }
+                written.fetch_sub(message.len(), Ordering::SeqCst);
+            }
+        });
+        OutputQueue {
+            sender,
+            pending,
+            soft_limit_since: None,
+        }
+    }
+
+    /// Encola un mensaje para el cliente.
+    ///
+    /// Devuelve error si el hilo que escribe sobre la conexión ya terminó.
+    pub fn push(&self, message: Vec<u8>) -> Result<(), Error> {
+        let size = message.len();
+        self.pending.fetch_add(size, Ordering::SeqCst);
+        self.sender.send(message).map_err(|_| {
+            self.pending.fetch_sub(size, Ordering::SeqCst);
+            Error::new(ErrorKind::BrokenPipe, "Client connection is closed")
+        })
+    }
+
+    /// Retorna la cantidad de bytes encolados que todavía no se escribieron sobre la conexión.
+    pub fn pending_bytes(&self) -> usize {
+        self.pending.load(Ordering::SeqCst)
+    }
+
+    /// Indica si los bytes pendientes superan el límite y el cliente debe desconectarse.
+    ///
+    /// Registra desde cuándo se supera el límite `soft`, por lo que debe consultarse periódicamente.
+    pub fn exceeds(&mut self, limit: &OutputBufferLimit) -> bool {
+        let pending = self.pending_bytes();
+        if limit.hard > 0 && pending >= limit.hard {
+            return true;
+        }
+        if limit.soft > 0 && pending >= limit.soft {
+            let since = *self.soft_limit_since.get_or_insert_with(Instant::now);
+            return since.elapsed() >= Duration::from_secs(limit.soft_seconds);
+        }
+        self.soft_limit_since = None;
+        false
+    }
+}
+
+#[test]
+fn test_01_messages_are_written_in_order() {
+    use std::io::Read;
+    use std::os::unix::net::UnixStream;
+
+    let (local, mut remote) = UnixStream::pair().unwrap();
+    let queue = OutputQueue::new(Connection::Unix(local));
+    queue.push(b"hola ".to_vec()).unwrap();
+    queue.push(b"mundo".to_vec()).unwrap();
+
+    let mut received = [0u8; 10];
+    remote.read_exact(&mut received).unwrap();
+    assert_eq!(&received, b"hola mundo");
+}
+
+#[test]
+fn test_02_pending_bytes_over_the_hard_limit_exceed_it() {
+    use std::os::unix::net::UnixStream;
+
+    // nadie lee del otro extremo, por lo que los mensajes se acumulan una vez que se llena el buffer del socket
+    let (local, _remote) = UnixStream::pair().unwrap();
+    let mut queue = OutputQueue::new(Connection::Unix(local));
+    let limit = OutputBufferLimit {
+        hard: 1024 * 1024,
+        soft: 0,
+        soft_seconds: 0,
+    };
+    for _ in 0..64 {
+        queue.push(vec![0u8; 64 * 1024]).unwrap();
+    }
+    assert!(queue.exceeds(&limit));
+}
+
+#[test]
+fn test_03_soft_limit_is_exceeded_only_after_the_configured_time() {
+    use std::os::unix::net::UnixStream;
+
+    let (local, _remote) = UnixStream::pair().unwrap();
+    let mut queue = OutputQueue::new(Connection::Unix(local));
+    let limit = OutputBufferLimit {
+        hard: 0,
+        soft: 1024 * 1024,
+        soft_seconds: 60,
+    };
+    for _ in 0..64 {
+        queue.push(vec![0u8; 64 * 1024]).unwrap();
+    }
+    assert!(!queue.exceeds(&limit));
+    assert!(queue.exceeds(&OutputBufferLimit {
+        soft_seconds: 0,
+        ..limit
+    }));
+}
+
+#[test]
+fn test_04_copies_share_the_order_and_the_pending_bytes() {
+    use std::io::Read;
+    use std::os::unix::net::UnixStream;
+
+    let (local, mut remote) = UnixStream::pair().unwrap();
+    let queue = OutputQueue::new(Connection::Unix(local));
+    let replies = queue.clone();
+    replies.push(b"+OK\r\n".to_vec()).unwrap();
+    queue.push(b"mensaje".to_vec()).unwrap();
+    drop(queue);
+    drop(replies);
+
+    let mut received = Vec::new();
+    remote.read_to_end(&mut received).unwrap();
+    assert_eq!(received, b"+OK\r\nmensaje");
+}
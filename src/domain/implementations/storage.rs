@@ -0,0 +1,164 @@
+//! Almacenamiento de las claves y sus valores, sobre el que opera la base de datos.
+
+use crate::domain::entities::key_value_item::{KeyAccessTime, ValueTimeItem};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::io;
+
+/// Motor de almacenamiento de la base de datos (ver `Database::with_storage`).
+///
+/// Guarda cada clave con su valor y mantiene un índice de las claves volátiles, para que la eliminación de las claves
+/// expiradas no tenga que recorrer todas las claves.
+/// Los valores que se modifican a través de `get_mut` se registran como modificados; los motores persistentes
+/// los guardan al llamar a `sync`.
+pub trait Storage: Debug + Send + Sync {
+    /// Retorna el valor de la clave, si existe.
+    fn get(&self, key: &[u8]) -> Option<&ValueTimeItem>;
+
+    /// Retorna el valor de la clave para modificarlo, si existe.
+    fn get_mut(&mut self, key: &[u8]) -> Option<&mut ValueTimeItem>;
+
+    /// Guarda el valor de la clave y devuelve el anterior, si existía.
+    fn put(&mut self, key: Vec<u8>, item: ValueTimeItem) -> Option<ValueTimeItem>;
+
+    /// Elimina la clave y devuelve su valor, si existía.
+    fn delete(&mut self, key: &[u8]) -> Option<ValueTimeItem>;
+
+    /// Retorna un iterador sobre las claves y sus valores, en cualquier orden.
+    fn iter(&self) -> Box<dyn Iterator<Item = (&Vec<u8>, &ValueTimeItem)> + '_>;
+
+    /// Retorna la cantidad de claves almacenadas.
+    fn len(&self) -> usize;
+
+    /// Elimina todas las claves.
+    fn clear(&mut self);
+
+    /// Retorna las claves que tienen un tiempo de expiración, según el índice de claves volátiles.
+    fn volatile_keys(&mut self) -> Vec<Vec<u8>>;
+
+    /// Indica si la clave existe.
+    fn contains_key(&self, key: &[u8]) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Indica si no hay claves almacenadas.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Guarda las modificaciones pendientes. Los motores que no son persistentes no hacen nada.
+    fn sync(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Indica si los datos sobreviven a un reinicio del servidor sin necesidad de bajarlos al archivo *dump*.
+    fn is_persistent(&self) -> bool {
+        false
+    }
+}
+
+/// Motor de almacenamiento en memoria, que se utiliza por defecto.
+///
+/// Se compone por un HashMap con las claves y sus valores, el índice de claves volátiles y las claves obtenidas con
+/// `get_mut` cuya entrada en el índice todavía no se actualizó, ya que pudo cambiar su tiempo de expiración.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    items: HashMap<Vec<u8>, ValueTimeItem>,
+    volatile: HashSet<Vec<u8>>,
+    touched: HashSet<Vec<u8>>,
+}
+
+impl MemoryStorage {
+    /// Crea un almacenamiento en memoria vacío.
+    pub fn new() -> Self {
+        MemoryStorage::default()
+    }
+
+    /// Actualiza la entrada de la clave en el índice de claves volátiles.
+    fn index(&mut self, key: &[u8]) {
+        match self.items.get(key).map(ValueTimeItem::get_timeout) {
+            Some(KeyAccessTime::Volatile(_)) => {
+                self.volatile.insert(key.to_vec());
+            }
+            _ => {
+                self.volatile.remove(key);
+            }
+        }
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, key: &[u8]) -> Option<&ValueTimeItem> {
+        self.items.get(key)
+    }
+
+    fn get_mut(&mut self, key: &[u8]) -> Option<&mut ValueTimeItem> {
+        if self.items.contains_key(key) {
+            self.touched.insert(key.to_vec());
+        }
+        self.items.get_mut(key)
+    }
+
+    fn put(&mut self, key: Vec<u8>, item: ValueTimeItem) -> Option<ValueTimeItem> {
+        let previous = self.items.insert(key.clone(), item);
+        self.index(&key);
+        previous
+    }
+
+    fn delete(&mut self, key: &[u8]) -> Option<ValueTimeItem> {
+        self.volatile.remove(key);
+        self.touched.remove(key);
+        self.items.remove(key)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&Vec<u8>, &ValueTimeItem)> + '_> {
+        Box::new(self.items.iter())
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn clear(&mut self) {
+        self.items.clear();
+        self.volatile.clear();
+        self.touched.clear();
+    }
+
+    fn volatile_keys(&mut self) -> Vec<Vec<u8>> {
+        let touched: Vec<Vec<u8>> = self.touched.drain().collect();
+        for key in touched {
+            self.index(&key);
+        }
+        self.volatile.iter().cloned().collect()
+    }
+}
+
+#[test]
+fn test_01_volatile_keys_follow_timeouts_changed_through_get_mut() {
+    use crate::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+
+    let mut storage = MemoryStorage::new();
+    storage.put(
+        b"vence".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("a".into()))
+            .with_timeout(123)
+            .build(),
+    );
+    storage.put(
+        b"persiste".to_vec(),
+        ValueTimeItemBuilder::new(ValueType::StringType("b".into())).build(),
+    );
+    assert_eq!(storage.volatile_keys(), vec![b"vence".to_vec()]);
+
+    storage.get_mut(b"vence").unwrap().make_persistent();
+    storage
+        .get_mut(b"persiste")
+        .unwrap()
+        .set_timeout(KeyAccessTime::Volatile(123));
+    assert_eq!(storage.volatile_keys(), vec![b"persiste".to_vec()]);
+
+    storage.delete(b"persiste");
+    assert!(storage.volatile_keys().is_empty());
+    assert_eq!(storage.len(), 1);
+}
@@ -2,6 +2,9 @@
 
 use std::fmt;
 
+/// Error con el que se rechazan las operaciones sobre una clave que almacena un valor de otro tipo.
+pub const WRONGTYPE: &str = "WRONGTYPE Operation against a key holding the wrong kind of value";
+
 /// Se establecen los siguientes tipos de error:
 /// * InvalidValueType
 /// * MissingKey
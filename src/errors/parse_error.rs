@@ -1,5 +1,7 @@
 //! Errores relacionados al parseo de solicitudes.
 
+use std::fmt;
+
 /// Se establecen los siguientes tipos de error:
 /// * InvalidProtocol
 /// * InvalidSize
@@ -14,3 +16,15 @@ pub enum ParseError {
     UnexpectedError(String),
     InvalidRequest(String),
 }
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidProtocol(msg)
+            | ParseError::InvalidSize(msg)
+            | ParseError::IntParseError(msg)
+            | ParseError::UnexpectedError(msg)
+            | ParseError::InvalidRequest(msg) => write!(f, "{}", msg),
+        }
+    }
+}
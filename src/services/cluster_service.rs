@@ -0,0 +1,211 @@
+//! Servicio que mantiene el bus del cluster: recibe los mensajes de los demás nodos y les envía los del nodo local.
+
+use crate::domain::entities::cluster::{Cluster, ClusterMessage};
+use crate::domain::entities::config::Config;
+use crate::domain::entities::message::WorkerMessage;
+use crate::services::commands::command_cluster::CLUSTER_PORT_INCREMENT;
+use crate::services::parser_service::{get_complete_request_len, parse_request, parse_response};
+use crate::services::server_service::bind_tcp_listeners;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Duration;
+
+/// Archivo donde se guarda la configuración del cluster si la configuración no especifica `cluster-config-file`.
+const DEFAULT_CONFIG_FILE: &str = "nodes.conf";
+
+/// Tiempo sin respuesta, en milisegundos, a partir del cual un nodo se considera caído, si la configuración no
+/// especifica `cluster-node-timeout`.
+const DEFAULT_NODE_TIMEOUT: u64 = 15000;
+
+/// Intervalo con el que el nodo local envía sus mensajes a los demás nodos.
+const GOSSIP_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Tiempo máximo que se espera para conectarse con otro nodo y para recibir su respuesta.
+const BUS_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Inicia el nodo del cluster, si la configuración especifica `cluster-enabled yes`.
+///
+/// Carga la configuración del cluster del archivo `cluster-config-file` (por defecto `nodes.conf`), o crea un cluster
+/// nuevo si no existe, y se la entrega a la entidad `Server`. Luego recibe los mensajes de los demás nodos en el puerto
+/// `cluster-port` (por defecto el puerto del servidor más 10000) de las direcciones `addresses` y, en otro hilo, les
+/// envía periódicamente los mensajes del nodo local (ver `send_gossip`). Los demás nodos se conectan con el nodo
+/// local en la dirección `cluster-announce-ip`; si no se especifica, es la primera dirección IPv4 de `addresses`.
+/// Los hilos del bus terminan junto con el servidor.
+pub fn start_cluster(
+    config: &Arc<RwLock<Config>>,
+    addresses: &[(IpAddr, bool)],
+    port: u16,
+    tx: &Sender<WorkerMessage>,
+) -> Result<(), String> {
+    let attribute = |name: &str| {
+        config
+            .read()
+            .ok()
+            .and_then(|conf| conf.get_attribute(String::from(name)).ok())
+    };
+    let config_file =
+        attribute("cluster-config-file").unwrap_or_else(|| String::from(DEFAULT_CONFIG_FILE));
+    let node_timeout = match attribute("cluster-node-timeout") {
+        Some(timeout) => timeout
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid cluster-node-timeout '{}'", timeout))?,
+        None => DEFAULT_NODE_TIMEOUT,
+    };
+    let bus_port = match attribute("cluster-port") {
+        Some(bus_port) => bus_port
+            .parse::<u16>()
+            .map_err(|_| format!("Invalid cluster-port '{}'", bus_port))?,
+        None => port
+            .checked_add(CLUSTER_PORT_INCREMENT)
+            .ok_or("Port is too high to derive the cluster bus port")?,
+    };
+    let announce_ip = match attribute("cluster-announce-ip") {
+        Some(ip) => ip
+            .parse::<IpAddr>()
+            .map_err(|_| format!("Invalid cluster-announce-ip '{}'", ip))?,
+        None => addresses
+            .iter()
+            .map(|(ip, _)| *ip)
+            .find(|ip| ip.is_ipv4() && !ip.is_unspecified())
+            .unwrap_or_else(|| IpAddr::from([127, 0, 0, 1])),
+    };
+    let cluster = Cluster::open(
+        &config_file,
+        announce_ip.to_string(),
+        port,
+        bus_port,
+        Duration::from_millis(node_timeout),
+    )
+    .map_err(|e| e.to_string())?;
+    let listeners = bind_tcp_listeners(addresses, bus_port)?;
+    tx.send(WorkerMessage::EnableCluster(Box::new(cluster)))
+        .map_err(|e| e.to_string())?;
+    for listener in listeners {
+        let tx = tx.clone();
+        thread::spawn(move || receive_bus_connections(listener, tx));
+    }
+    let tx = tx.clone();
+    thread::spawn(move || send_gossip(tx));
+    Ok(())
+}
+
+/// Atiende, cada una en su propio hilo, las conexiones de los demás nodos al bus del cluster.
+fn receive_bus_connections(listener: TcpListener, tx: Sender<WorkerMessage>) {
+    for stream in listener.incoming().flatten() {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            if let Err(e) = handle_bus_connection(stream, &tx) {
+                println!("Cluster bus connection closed: {}", e);
+            }
+        });
+    }
+}
+
+/// Le entrega a la entidad `Server` cada mensaje que envía otro nodo y le envía al nodo la respuesta, si corresponde.
+fn handle_bus_connection(
+    mut stream: TcpStream,
+    tx: &Sender<WorkerMessage>,
+) -> Result<(), Box<dyn Error>> {
+    let mut buffer = Vec::new();
+    loop {
+        let message = read_message(&mut stream, &mut buffer)?;
+        if let Some(reply) = process_message(message, tx)? {
+            stream.write_all(&parse_response(reply.to_resp()))?;
+            stream.flush()?;
+        }
+    }
+}
+
+/// Envía periódicamente los mensajes del nodo local a los demás nodos y procesa sus respuestas.
+///
+/// Se mantiene una conexión con cada nodo, que se vuelve a establecer si falla; los nodos que no responden no
+/// reciben el mensaje y, pasado `cluster-node-timeout`, se consideran caídos. Termina cuando se detiene el servidor.
+fn send_gossip(tx: Sender<WorkerMessage>) {
+    let mut links: HashMap<String, TcpStream> = HashMap::new();
+    loop {
+        thread::sleep(GOSSIP_INTERVAL);
+        let (ping_tx, ping_rx) = mpsc::channel();
+        if tx
+            .send(WorkerMessage::ClusterPingMessages(ping_tx))
+            .is_err()
+        {
+            return;
+        }
+        let messages = match ping_rx.recv() {
+            Ok(messages) => messages,
+            Err(_) => return,
+        };
+        links.retain(|address, _| messages.iter().any(|(target, _)| target == address));
+        for (address, message) in messages {
+            match exchange(&mut links, &address, &message) {
+                Ok(reply) => {
+                    if process_message(reply, &tx).is_err() {
+                        return;
+                    }
+                }
+                Err(_) => {
+                    links.remove(&address);
+                }
+            }
+        }
+    }
+}
+
+/// Envía un mensaje al nodo del bus `address`, conectándose con él si todavía no hay una conexión, y devuelve su respuesta.
+fn exchange(
+    links: &mut HashMap<String, TcpStream>,
+    address: &str,
+    message: &ClusterMessage,
+) -> Result<ClusterMessage, Box<dyn Error>> {
+    if !links.contains_key(address) {
+        let socket: SocketAddr = address
+            .to_socket_addrs()?
+            .next()
+            .ok_or("Invalid cluster bus address")?;
+        let stream = TcpStream::connect_timeout(&socket, BUS_TIMEOUT)?;
+        stream.set_read_timeout(Some(BUS_TIMEOUT))?;
+        links.insert(address.to_string(), stream);
+    }
+    let stream = links.get_mut(address).ok_or("Cluster bus link not found")?;
+    stream.write_all(&parse_response(message.to_resp()))?;
+    stream.flush()?;
+    read_message(stream, &mut Vec::new())
+}
+
+/// Le entrega un mensaje del bus a la entidad `Server` y devuelve la respuesta para el emisor, si corresponde.
+fn process_message(
+    message: ClusterMessage,
+    tx: &Sender<WorkerMessage>,
+) -> Result<Option<ClusterMessage>, Box<dyn Error>> {
+    let (reply_tx, reply_rx) = mpsc::channel();
+    tx.send(WorkerMessage::ClusterBusMessage(
+        Box::new(message),
+        reply_tx,
+    ))?;
+    Ok(reply_rx.recv()?)
+}
+
+/// Lee del stream el siguiente mensaje del bus. Los bytes recibidos que no forman parte de él quedan en `buffer`.
+fn read_message(
+    stream: &mut TcpStream,
+    buffer: &mut Vec<u8>,
+) -> Result<ClusterMessage, Box<dyn Error>> {
+    loop {
+        if let Some(len) = get_complete_request_len(buffer).map_err(|e| e.to_string())? {
+            let request = parse_request(&buffer[..len]).map_err(|e| e.to_string())?;
+            buffer.drain(..len);
+            return Ok(ClusterMessage::from_resp(&request)?);
+        }
+        let mut chunk = [0; 4096];
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            return Err("connection closed by peer".into());
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+}
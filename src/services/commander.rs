@@ -1,11 +1,13 @@
 //! Servicio que delega el comando ingresado según su tipo.
-//! Los tipos pueden ser: list, key, server, string, pubsub y set.
+//! Los tipos pueden ser: connection, cluster, list, key, server, string, pubsub y set.
 
 use super::utils::resp_type::RespType;
 use crate::domain::implementations::database::Database;
-use crate::services::commands::{command_pubsub, command_set};
+use crate::services::commands::{
+    command_acl, command_cluster, command_connection, command_pubsub, command_set,
+};
 use crate::{
-    domain::entities::{config::Config, message::WorkerMessage},
+    domain::entities::{config::Config, message::WorkerMessage, session::Session},
     services::commands::command_key,
     services::commands::command_list,
     services::commands::command_server,
@@ -14,16 +16,29 @@ use crate::{
 use std::error::Error;
 #[allow(unused)]
 use std::fs::File;
-use std::{
-    net::SocketAddr,
-    sync::{mpsc::Sender, Arc, RwLock},
-};
+use std::sync::{mpsc::Sender, Arc, RwLock};
 
 /// Delega el comando ingresado por el cliente al servicio de comandos que corresponda.
 ///
 /// Los comandos posibles son:
+/// * auth
+/// * acl
+/// * ping
+/// * echo
+/// * quit
+/// * reset
+/// * client
 /// * monitor
+/// * hello
 /// * info
+/// * shutdown
+/// * replicaof
+/// * slaveof
+/// * replconf
+/// * cluster
+/// * asking
+/// * psync
+/// * sync
 /// * config
 /// * dbsize
 /// * flushdb
@@ -49,10 +64,26 @@ use std::{
 /// * get
 /// * mget
 /// * mset
+/// * incr
+/// * decr
+/// * incrbyfloat
+/// * setex
+/// * psetex
+/// * setnx
+/// * msetnx
+/// * getrange
+/// * setrange
+/// * getex
+/// * lcs
 /// * subscribe
 /// * unsubscribe
+/// * psubscribe
+/// * punsubscribe
+/// * ssubscribe
+/// * sunsubscribe
 /// * pubsub
 /// * publish
+/// * spublish
 /// * ttl
 /// * command
 /// * lpush
@@ -61,11 +92,33 @@ use std::{
 /// * lrange
 /// * lindex
 /// * lpop
+/// * linsert
+/// * ltrim
+/// * lpos
+/// * lmove
+/// * rpoplpush
+/// * lmpop
 /// * sadd
 /// * scard
 /// * sismember
 /// * smembers
 /// * srem
+/// * sinter
+/// * sunion
+/// * sdiff
+/// * sinterstore
+/// * sunionstore
+/// * sdiffstore
+/// * sintercard
+/// * smismember
+/// * smove
+/// * spop
+/// * srandmember
+///
+/// `session` es el estado de la conexión: la versión del protocolo RESP, que el comando `hello` puede modificar,
+/// y el usuario autenticado. Mientras la conexión no se autentique solo se admiten los comandos devueltos por
+/// `get_no_auth_commands`; el resto devuelve un error `NOAUTH`. Una vez autenticada, se verifica que el usuario ACL
+/// tenga permiso para ejecutar el comando y acceder a sus claves y canales; si no lo tiene devuelve un error `NOPERM`.
 ///
 /// Devuelve un Option de tipo RespType con la respuesta que se le devolverá al cliente.
 ///
@@ -78,35 +131,109 @@ use std::{
 ///     let msg_len = stream.read(&mut buf).unwrap();
 ///     let msg = &buf[..msg_len];
 ///     let parsed_msg = parser_service::parse_request(msg).unwrap();
-///     commander::parser_service(parsed_msg, &server_sender, stream.peer_addrs().unwrap(), database, config, stream);
+///     commander::handle_command(parsed_msg, &server_sender, client_id, database, config, false, &mut session);
 /// }
 /// ```
 pub fn handle_command(
     operation: RespType,
     tx: &Sender<WorkerMessage>,
-    addrs: SocketAddr,
+    client_id: usize,
+    database: &Arc<RwLock<Database>>,
+    config: &Arc<RwLock<Config>>,
+    subscribed: bool,
+    session: &mut Session,
+) -> Result<RespType, Box<dyn Error>> {
+    if let RespType::RArray(array) = &operation {
+        if let Some(error) = get_access_error(array, tx, client_id, session) {
+            return Err(error.into());
+        }
+    }
+    execute_command(
+        operation, tx, client_id, database, config, subscribed, session,
+    )
+}
+
+/// Devuelve el error con el que se rechaza el comando si la conexión no está autenticada (`NOAUTH`) o si el usuario
+/// ACL no tiene permiso para ejecutarlo o para acceder a sus claves y canales (`NOPERM`).
+///
+/// Los comandos devueltos por `get_no_auth_commands` siempre se admiten.
+pub fn get_access_error(
+    cmd: &[RespType],
+    tx: &Sender<WorkerMessage>,
+    client_id: usize,
+    session: &Session,
+) -> Option<String> {
+    let command = cmd.first()?.as_str()?.to_lowercase();
+    if get_no_auth_commands().contains(&command) {
+        return None;
+    }
+    if !session.is_authenticated() {
+        return Some(String::from("NOAUTH Authentication required."));
+    }
+    session
+        .get_user()
+        .and_then(|user| command_acl::check_permissions(cmd, tx, client_id, user))
+}
+
+/// Ejecuta el comando sin verificar la autenticación ni los permisos del usuario, que ya se verificaron con
+/// `get_access_error` (ver `handle_command`).
+pub fn execute_command(
+    operation: RespType,
+    tx: &Sender<WorkerMessage>,
+    client_id: usize,
     database: &Arc<RwLock<Database>>,
     config: &Arc<RwLock<Config>>,
     subscribed: bool,
+    session: &mut Session,
 ) -> Result<RespType, Box<dyn Error>> {
     if let RespType::RArray(array) = operation {
-        if let RespType::RBulkString(actual_command) = &array[0] {
-            if subscribed && !get_pubsub_commands().contains(actual_command) {
-                return Err(
-                    "In subscribed state. Commands allowed: [subscribe, unsubscribe]".into(),
-                );
+        if let Some(actual_command) = array[0].as_str() {
+            let actual_command = actual_command.to_lowercase();
+            if subscribed && !get_pubsub_commands().contains(&actual_command) {
+                return Err(format!(
+                    "In subscribed state. Commands allowed: [{}]",
+                    get_pubsub_commands().join(", ")
+                )
+                .into());
             }
             match actual_command.as_str() {
-                "monitor" => command_server::monitor(tx, addrs),
-                "info" => return Ok(command_server::info(&array, tx)),
+                "auth" => return Ok(command_connection::auth(&array, tx, client_id, session)),
+                "ping" => {
+                    return Ok(command_connection::ping(
+                        &array,
+                        subscribed,
+                        session.get_protocol(),
+                    ))
+                }
+                "echo" => return Ok(command_connection::echo(&array)),
+                "quit" => return Ok(command_connection::quit()),
+                "reset" => return Ok(command_connection::reset(tx, client_id, session)),
+                "client" => return Ok(command_connection::client(&array, tx, client_id)),
+                "acl" => return Ok(command_acl::acl(&array, tx, session)),
+                "monitor" => return Ok(command_server::monitor(tx, client_id)),
+                "hello" => return Ok(command_server::hello(&array, tx, client_id, session)),
+                "info" => return Ok(command_server::info(&array, tx, config)),
+                "replicaof" | "slaveof" => {
+                    return Ok(command_server::replicaof(&array, tx, database, config))
+                }
+                "replconf" => return Ok(command_server::replconf(&array, tx, client_id)),
+                "psync" | "sync" => return Ok(command_server::psync(tx, client_id, database)),
+                "cluster" => return Ok(command_cluster::cluster(&array, tx, database, config)),
+                "asking" => return Ok(command_cluster::asking(config, session)),
+                "shutdown" => return Ok(command_server::shutdown(&array)),
                 "config" => {
-                    if let RespType::RBulkString(instruction) = &array[1] {
-                        match instruction.as_str() {
+                    if let Some(instruction) = array[1].as_str() {
+                        match instruction.to_lowercase().as_str() {
                             "get" => {
                                 return Ok(command_server::config_get(config, &array[1..]));
                             }
                             "set" => {
-                                return Ok(command_server::config_set(config, &array[1..]));
+                                return Ok(command_server::config_set(
+                                    config,
+                                    &array[1..],
+                                    tx,
+                                    database,
+                                ));
                             }
                             _ => {}
                         }
@@ -135,20 +262,32 @@ pub fn handle_command(
                 "mget" => return Ok(command_string::mget(&array, database)),
                 "mset" => return Ok(command_string::mset(&array, database)),
                 "set" => return Ok(command_string::set(&array, database)),
-                "subscribe" => return Ok(command_pubsub::subscribe(&array, tx, addrs)),
-                "unsubscribe" => return Ok(command_pubsub::unsubscribe(&array, tx, addrs)),
-                "punsubscribe" => {
-                    //no se pide implementar esta funcion pero el cliente Redis la llama despues de un subscribe. Implemento igual que unsubscribe
-                    return Ok(command_pubsub::unsubscribe(&array, tx, addrs));
-                }
+                "incr" => return Ok(command_string::incr(&array, database)),
+                "decr" => return Ok(command_string::decr(&array, database)),
+                "incrbyfloat" => return Ok(command_string::incrbyfloat(&array, database)),
+                "setex" => return Ok(command_string::setex(&array, database)),
+                "psetex" => return Ok(command_string::psetex(&array, database)),
+                "setnx" => return Ok(command_string::setnx(&array, database)),
+                "msetnx" => return Ok(command_string::msetnx(&array, database)),
+                "getrange" => return Ok(command_string::getrange(&array, database)),
+                "setrange" => return Ok(command_string::setrange(&array, database)),
+                "getex" => return Ok(command_string::getex(&array, database)),
+                "lcs" => return Ok(command_string::lcs(&array, database)),
+                "subscribe" => return Ok(command_pubsub::subscribe(&array, tx, client_id)),
+                "unsubscribe" => return Ok(command_pubsub::unsubscribe(&array, tx, client_id)),
+                "psubscribe" => return Ok(command_pubsub::psubscribe(&array, tx, client_id)),
+                "punsubscribe" => return Ok(command_pubsub::punsubscribe(&array, tx, client_id)),
                 "pubsub" => return Ok(command_pubsub::pubsub(&array, tx)),
                 "publish" => return Ok(command_pubsub::publish(&array, tx)),
+                "ssubscribe" => return Ok(command_pubsub::ssubscribe(&array, tx, client_id)),
+                "sunsubscribe" => return Ok(command_pubsub::sunsubscribe(&array, tx, client_id)),
+                "spublish" => return Ok(command_pubsub::spublish(&array, tx)),
                 "ttl" => return Ok(command_key::get_ttl(&array, database)),
                 "command" => {
                     return Ok(RespType::RArray(
                         get_commands()
                             .iter()
-                            .map(|c| RespType::RBulkString(c.to_string()))
+                            .map(|c| RespType::RBulkString(c.to_string().into()))
                             .collect(),
                     ))
                 }
@@ -167,7 +306,24 @@ pub fn handle_command(
                 "sismember" => return Ok(command_set::sismember(&array, database)),
                 "smembers" => return Ok(command_set::smembers(&array, database)),
                 "srem" => return Ok(command_set::srem(&array, database)),
+                "sinter" => return Ok(command_set::sinter(&array, database)),
+                "sunion" => return Ok(command_set::sunion(&array, database)),
+                "sdiff" => return Ok(command_set::sdiff(&array, database)),
+                "sinterstore" => return Ok(command_set::sinterstore(&array, database)),
+                "sunionstore" => return Ok(command_set::sunionstore(&array, database)),
+                "sdiffstore" => return Ok(command_set::sdiffstore(&array, database)),
+                "sintercard" => return Ok(command_set::sintercard(&array, database)),
+                "smismember" => return Ok(command_set::smismember(&array, database)),
+                "smove" => return Ok(command_set::smove(&array, database)),
+                "spop" => return Ok(command_set::spop(&array, database)),
+                "srandmember" => return Ok(command_set::srandmember(&array, database)),
                 "rpush" => return Ok(command_list::push(&array, database, false)),
+                "linsert" => return Ok(command_list::linsert(&array, database)),
+                "ltrim" => return Ok(command_list::ltrim(&array, database)),
+                "lpos" => return Ok(command_list::lpos(&array, database)),
+                "lmove" => return Ok(command_list::lmove(&array, database)),
+                "rpoplpush" => return Ok(command_list::rpoplpush(&array, database)),
+                "lmpop" => return Ok(command_list::lmpop(&array, database)),
                 _ => {}
             }
         }
@@ -179,17 +335,471 @@ pub fn get_pubsub_commands() -> Vec<String> {
     vec![
         String::from("subscribe"),
         String::from("unsubscribe"),
+        String::from("psubscribe"),
         String::from("punsubscribe"),
+        String::from("ssubscribe"),
+        String::from("sunsubscribe"),
+        String::from("ping"),
+        String::from("quit"),
+        String::from("reset"),
+    ]
+}
+
+/// Comandos que se admiten antes de que la conexión se autentique.
+pub fn get_no_auth_commands() -> Vec<String> {
+    vec![
+        String::from("auth"),
+        String::from("hello"),
+        String::from("quit"),
+        String::from("reset"),
+    ]
+}
+
+/// Comandos que modifican la base de datos o publican mensajes.
+///
+/// Son los comandos que se suspenden con `client pause write`, junto con `sort` cuando utiliza `store` (ver `is_write_command`).
+pub fn get_write_commands() -> Vec<String> {
+    vec![
+        String::from("set"),
+        String::from("append"),
+        String::from("decrby"),
+        String::from("incrby"),
+        String::from("incr"),
+        String::from("decr"),
+        String::from("incrbyfloat"),
+        String::from("getdel"),
+        String::from("getset"),
+        String::from("getex"),
+        String::from("mset"),
+        String::from("setex"),
+        String::from("psetex"),
+        String::from("setnx"),
+        String::from("msetnx"),
+        String::from("setrange"),
+        String::from("copy"),
+        String::from("del"),
+        String::from("persist"),
+        String::from("rename"),
+        String::from("expire"),
+        String::from("expireat"),
+        String::from("flushdb"),
+        String::from("lpush"),
+        String::from("rpush"),
+        String::from("lpushx"),
+        String::from("rpushx"),
+        String::from("lpop"),
+        String::from("rpop"),
+        String::from("lset"),
+        String::from("lrem"),
+        String::from("linsert"),
+        String::from("ltrim"),
+        String::from("lmove"),
+        String::from("rpoplpush"),
+        String::from("lmpop"),
+        String::from("sadd"),
+        String::from("srem"),
+        String::from("sinterstore"),
+        String::from("sunionstore"),
+        String::from("sdiffstore"),
+        String::from("smove"),
+        String::from("spop"),
+        String::from("publish"),
+        String::from("spublish"),
+    ]
+}
+
+/// Indica si el comando modifica la base de datos o publica mensajes.
+///
+/// Además de los comandos de `get_write_commands`, `sort` es de escritura cuando guarda el resultado con `store`.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// use proyecto_taller_1::services::commander::is_write_command;
+///
+/// let sort = vec![RespType::RBulkString("SORT".into()), RespType::RBulkString("numeros".into())];
+/// assert!(!is_write_command(&sort));
+///
+/// let mut sort_store = sort.clone();
+/// sort_store.push(RespType::RBulkString("STORE".into()));
+/// sort_store.push(RespType::RBulkString("ordenados".into()));
+/// assert!(is_write_command(&sort_store));
+/// assert!(is_write_command(&[RespType::RBulkString("set".into())]));
+/// ```
+pub fn is_write_command(cmd: &[RespType]) -> bool {
+    let command = match cmd.first().and_then(RespType::as_str) {
+        Some(command) => command.to_lowercase(),
+        None => return false,
+    };
+    if command == "sort" {
+        return cmd.iter().skip(2).any(|arg| arg.is_word("store"));
+    }
+    get_write_commands().contains(&command)
+}
+
+/// Categorías de comandos que pueden utilizarse en las reglas ACL (`+@<categoría>` y `-@<categoría>`).
+pub fn get_acl_categories() -> Vec<String> {
+    vec![
+        String::from("keyspace"),
+        String::from("read"),
+        String::from("write"),
+        String::from("string"),
+        String::from("list"),
+        String::from("set"),
+        String::from("pubsub"),
+        String::from("admin"),
+        String::from("dangerous"),
+        String::from("connection"),
+    ]
+}
+
+/// Comandos que pertenecen a una categoría ACL, o `None` si la categoría no existe.
+///
+/// La categoría `all` contiene a todos los comandos. Los subcomandos que pertenecen a una categoría
+/// sin que lo haga el comando completo se indican como `comando|subcomando`.
+pub fn get_category_commands(category: &str) -> Option<Vec<String>> {
+    let commands: Vec<&str> = match category {
+        "all" => return Some(get_command_names()),
+        "keyspace" => vec![
+            "copy", "del", "exists", "persist", "rename", "expire", "expireat", "sort", "keys",
+            "touch", "type", "ttl", "dbsize", "flushdb",
+        ],
+        "read" => vec![
+            "get",
+            "getrange",
+            "strlen",
+            "mget",
+            "lcs",
+            "exists",
+            "keys",
+            "sort",
+            "ttl",
+            "type",
+            "dbsize",
+            "lrange",
+            "lindex",
+            "llen",
+            "lpos",
+            "scard",
+            "sismember",
+            "smembers",
+            "smismember",
+            "sinter",
+            "sunion",
+            "sdiff",
+            "sintercard",
+            "srandmember",
+        ],
+        "write" => {
+            return Some(
+                get_write_commands()
+                    .into_iter()
+                    .filter(|command| command != "publish" && command != "spublish")
+                    .collect(),
+            )
+        }
+        "string" => vec![
+            "append",
+            "decrby",
+            "get",
+            "getdel",
+            "getset",
+            "incrby",
+            "strlen",
+            "mget",
+            "mset",
+            "set",
+            "incr",
+            "decr",
+            "incrbyfloat",
+            "setex",
+            "psetex",
+            "setnx",
+            "msetnx",
+            "getrange",
+            "setrange",
+            "getex",
+            "lcs",
+        ],
+        "list" => vec![
+            "lpush",
+            "rpush",
+            "lpushx",
+            "rpushx",
+            "llen",
+            "lrange",
+            "lindex",
+            "lpop",
+            "rpop",
+            "lset",
+            "lrem",
+            "linsert",
+            "ltrim",
+            "lpos",
+            "lmove",
+            "rpoplpush",
+            "lmpop",
+        ],
+        "set" => vec![
+            "sadd",
+            "scard",
+            "sismember",
+            "smembers",
+            "srem",
+            "sinter",
+            "sunion",
+            "sdiff",
+            "sinterstore",
+            "sunionstore",
+            "sdiffstore",
+            "sintercard",
+            "smismember",
+            "smove",
+            "spop",
+            "srandmember",
+        ],
+        "pubsub" => vec![
+            "subscribe",
+            "unsubscribe",
+            "psubscribe",
+            "punsubscribe",
+            "ssubscribe",
+            "sunsubscribe",
+            "pubsub",
+            "publish",
+            "spublish",
+        ],
+        "admin" => get_admin_commands(),
+        "dangerous" => {
+            let mut commands = get_admin_commands();
+            commands.extend(["flushdb", "keys", "sort", "info"]);
+            commands
+        }
+        "connection" => vec![
+            "ping",
+            "echo",
+            "quit",
+            "reset",
+            "hello",
+            "auth",
+            "command",
+            "client|setname",
+            "client|getname",
+            "client|id",
+            "client|info",
+            "client|setinfo",
+            "client|tracking",
+            "client|caching",
+            "client|getredir",
+            "client|trackinginfo",
+            "asking",
+        ],
+        _ => return None,
+    };
+    Some(commands.into_iter().map(String::from).collect())
+}
+
+/// Comandos de la categoría ACL `admin`, que administran el servidor y sus conexiones.
+fn get_admin_commands() -> Vec<&'static str> {
+    vec![
+        "config",
+        "monitor",
+        "shutdown",
+        "replicaof",
+        "slaveof",
+        "replconf",
+        "psync",
+        "sync",
+        "client|list",
+        "client|kill",
+        "client|pause",
+        "client|unpause",
+        "client|no-evict",
+        "acl|setuser",
+        "acl|getuser",
+        "acl|deluser",
+        "acl|list",
+        "acl|users",
+        "acl|log",
+        "cluster|addslots",
+        "cluster|addslotsrange",
+        "cluster|delslots",
+        "cluster|delslotsrange",
+        "cluster|setslot",
+        "cluster|meet",
+        "cluster|forget",
     ]
 }
 
+/// Nombres de los comandos que admite el servidor, sin sus subcomandos.
+pub fn get_command_names() -> Vec<String> {
+    let mut names: Vec<String> = get_commands()
+        .iter()
+        .filter_map(|command| command.split(' ').next())
+        .map(String::from)
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Devuelve las claves a las que accede el comando, según la posición que ocupan en sus argumentos.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commander::get_command_keys;
+///
+/// let cmd = vec![
+///     RespType::RBulkString("mset".into()),
+///     RespType::RBulkString("clave_1".into()),
+///     RespType::RBulkString("valor_1".into()),
+///     RespType::RBulkString("clave_2".into()),
+///     RespType::RBulkString("valor_2".into()),
+/// ];
+/// assert_eq!(get_command_keys(&cmd), vec![&cmd[1], &cmd[3]]);
+/// ```
+pub fn get_command_keys(cmd: &[RespType]) -> Vec<&RespType> {
+    let command = match cmd.first().and_then(RespType::as_str) {
+        Some(command) => command.to_lowercase(),
+        None => return vec![],
+    };
+    let args = &cmd[1..];
+    match command.as_str() {
+        "del" | "exists" | "touch" | "mget" | "sinter" | "sunion" | "sdiff" | "sinterstore"
+        | "sunionstore" | "sdiffstore" => args.iter().collect(),
+        "copy" | "rename" | "lmove" | "rpoplpush" | "smove" | "lcs" => {
+            args.iter().take(2).collect()
+        }
+        "mset" | "msetnx" => args.iter().step_by(2).collect(),
+        "sintercard" | "lmpop" => {
+            let numkeys = args
+                .first()
+                .and_then(RespType::as_str)
+                .and_then(|numkeys| numkeys.parse::<usize>().ok())
+                .unwrap_or(0);
+            args.iter().skip(1).take(numkeys).collect()
+        }
+        "sort" => {
+            let mut keys: Vec<&RespType> = args.iter().take(1).collect();
+            if let Some(pos) = args.iter().position(|arg| arg.is_word("store")) {
+                keys.extend(args.get(pos + 1));
+            }
+            keys
+        }
+        "get" | "set" | "append" | "decrby" | "incrby" | "incr" | "decr" | "incrbyfloat"
+        | "getdel" | "getset" | "getex" | "setex" | "psetex" | "setnx" | "strlen" | "getrange"
+        | "setrange" | "persist" | "expire" | "expireat" | "ttl" | "type" | "lpush" | "rpush"
+        | "lpushx" | "rpushx" | "lpop" | "rpop" | "llen" | "lrange" | "lindex" | "lset"
+        | "lrem" | "linsert" | "ltrim" | "lpos" | "sadd" | "srem" | "scard" | "sismember"
+        | "smembers" | "smismember" | "spop" | "srandmember" => args.iter().take(1).collect(),
+        _ => vec![],
+    }
+}
+
+/// Devuelve una copia del comando en la que las contraseñas se reemplazan por `(redacted)`, para poder registrarlo en el
+/// log o enviarlo a los clientes en estado "monitor".
+///
+/// Se ocultan los argumentos de `auth`, el usuario y la contraseña de la opción `auth` de `hello`, el valor de
+/// `requirepass` y `masterauth` en `config set` y las reglas de contraseñas de `acl setuser`.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commander::get_redacted_command;
+///
+/// let cmd = vec![
+///     RespType::RBulkString("config".into()),
+///     RespType::RBulkString("set".into()),
+///     RespType::RBulkString("requirepass".into()),
+///     RespType::RBulkString("secreto".into()),
+/// ];
+/// assert_eq!(get_redacted_command(&cmd)[3], RespType::RBulkString("(redacted)".into()));
+/// ```
+pub fn get_redacted_command(cmd: &[RespType]) -> Vec<RespType> {
+    let is_word =
+        |position: usize, word: &str| cmd.get(position).is_some_and(|arg| arg.is_word(word));
+    let is_secret = |position: usize| -> bool {
+        if is_word(0, "auth") {
+            position > 0
+        } else if is_word(0, "hello") {
+            cmd.iter()
+                .position(|arg| arg.is_word("auth"))
+                .is_some_and(|auth| position == auth + 1 || position == auth + 2)
+        } else if is_word(0, "config") && is_word(1, "set") {
+            position > 2
+                && position % 2 == 1
+                && (is_word(position - 1, "requirepass") || is_word(position - 1, "masterauth"))
+        } else if is_word(0, "acl") && is_word(1, "setuser") {
+            position > 2
+                && cmd[position]
+                    .as_bytes()
+                    .is_some_and(|rule| rule.starts_with(b">") || rule.starts_with(b"<"))
+        } else {
+            false
+        }
+    };
+    cmd.iter()
+        .enumerate()
+        .map(|(position, arg)| {
+            if is_secret(position) {
+                RespType::RBulkString("(redacted)".into())
+            } else {
+                arg.clone()
+            }
+        })
+        .collect()
+}
+
+/// Devuelve los canales en los que el comando publica o a los que se suscribe.
+pub fn get_command_channels(cmd: &[RespType]) -> Vec<&RespType> {
+    match cmd.first() {
+        Some(command) if command.is_word("subscribe") || command.is_word("ssubscribe") => {
+            cmd[1..].iter().collect()
+        }
+        Some(command) if command.is_word("publish") || command.is_word("spublish") => {
+            cmd.iter().skip(1).take(1).collect()
+        }
+        _ => vec![],
+    }
+}
+
+/// Devuelve los patrones de canales a los que se suscribe el comando.
+pub fn get_command_channel_patterns(cmd: &[RespType]) -> Vec<&RespType> {
+    match cmd.first() {
+        Some(command) if command.is_word("psubscribe") => cmd[1..].iter().collect(),
+        _ => vec![],
+    }
+}
+
 pub fn get_commands() -> Vec<String> {
     vec![
         String::from("subscribe"),
         String::from("unsubscribe"),
+        String::from("psubscribe"),
         String::from("punsubscribe"),
+        String::from("ssubscribe"),
+        String::from("sunsubscribe"),
+        String::from("spublish"),
         String::from("rpush"),
+        String::from("linsert"),
+        String::from("ltrim"),
+        String::from("lpos"),
+        String::from("lmove"),
+        String::from("rpoplpush"),
+        String::from("lmpop"),
         String::from("srem"),
+        String::from("sinter"),
+        String::from("sunion"),
+        String::from("sdiff"),
+        String::from("sinterstore"),
+        String::from("sunionstore"),
+        String::from("sdiffstore"),
+        String::from("sintercard"),
+        String::from("smismember"),
+        String::from("smove"),
+        String::from("spop"),
+        String::from("srandmember"),
         String::from("smembers"),
         String::from("sismember"),
         String::from("scard"),
@@ -208,6 +818,17 @@ pub fn get_commands() -> Vec<String> {
         String::from("publish"),
         String::from("pubsub"),
         String::from("set"),
+        String::from("incr"),
+        String::from("decr"),
+        String::from("incrbyfloat"),
+        String::from("setex"),
+        String::from("psetex"),
+        String::from("setnx"),
+        String::from("msetnx"),
+        String::from("getrange"),
+        String::from("setrange"),
+        String::from("getex"),
+        String::from("lcs"),
         String::from("mset"),
         String::from("mget"),
         String::from("strlen"),
@@ -234,5 +855,94 @@ pub fn get_commands() -> Vec<String> {
         String::from("monitor"),
         String::from("copy"),
         String::from("info"),
+        String::from("shutdown"),
+        String::from("replicaof"),
+        String::from("slaveof"),
+        String::from("replconf"),
+        String::from("psync"),
+        String::from("sync"),
+        String::from("cluster info"),
+        String::from("cluster myid"),
+        String::from("cluster nodes"),
+        String::from("cluster slots"),
+        String::from("cluster shards"),
+        String::from("cluster keyslot"),
+        String::from("cluster countkeysinslot"),
+        String::from("cluster getkeysinslot"),
+        String::from("cluster addslots"),
+        String::from("cluster addslotsrange"),
+        String::from("cluster delslots"),
+        String::from("cluster delslotsrange"),
+        String::from("cluster setslot"),
+        String::from("cluster meet"),
+        String::from("cluster forget"),
+        String::from("asking"),
+        String::from("hello"),
+        String::from("auth"),
+        String::from("ping"),
+        String::from("echo"),
+        String::from("quit"),
+        String::from("reset"),
+        String::from("client setname"),
+        String::from("client getname"),
+        String::from("client id"),
+        String::from("client info"),
+        String::from("client setinfo"),
+        String::from("client list"),
+        String::from("client kill"),
+        String::from("client pause"),
+        String::from("client unpause"),
+        String::from("client no-evict"),
+        String::from("client tracking"),
+        String::from("client caching"),
+        String::from("client getredir"),
+        String::from("client trackinginfo"),
+        String::from("acl setuser"),
+        String::from("acl getuser"),
+        String::from("acl deluser"),
+        String::from("acl list"),
+        String::from("acl users"),
+        String::from("acl whoami"),
+        String::from("acl cat"),
+        String::from("acl log"),
     ]
 }
+
+#[test]
+fn test_001_unauthenticated_connection_only_accepts_auth_commands() {
+    std::fs::File::create("./src/dummy_config_noauth.txt").unwrap();
+    std::fs::File::create("./src/dummy_database_noauth.txt").unwrap();
+    let config = Arc::new(RwLock::new(Config::new(String::from(
+        "./src/dummy_config_noauth.txt",
+    ))));
+    let database = Arc::new(RwLock::new(Database::new(String::from(
+        "./src/dummy_database_noauth.txt",
+    ))));
+    let (tx, _rx) = std::sync::mpsc::channel();
+    let client_id = 1;
+    let mut session = Session::new(false);
+
+    let get = RespType::RArray(vec![
+        RespType::RBulkString("get".into()),
+        RespType::RBulkString("clave".into()),
+    ]);
+    let res = handle_command(get, &tx, client_id, &database, &config, false, &mut session);
+    let hello = RespType::RArray(vec![RespType::RBulkString("hello".into())]);
+    let hello_res = handle_command(
+        hello,
+        &tx,
+        client_id,
+        &database,
+        &config,
+        false,
+        &mut session,
+    );
+    std::fs::remove_file("./src/dummy_config_noauth.txt").unwrap();
+    let _ = std::fs::remove_file("./src/dummy_database_noauth.txt");
+
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "NOAUTH Authentication required."
+    );
+    assert!(matches!(hello_res, Ok(RespType::RError(e)) if e.starts_with("NOAUTH")));
+}
@@ -0,0 +1,363 @@
+//! Servicio que implementa los comandos ACL y la verificación de permisos de los usuarios
+
+use crate::domain::entities::acl::{AclLogEntry, AclUser};
+use crate::domain::entities::message::WorkerMessage;
+use crate::domain::entities::session::{Session, DEFAULT_USER};
+use crate::services::commander::{get_acl_categories, get_category_commands};
+use crate::services::commands::command_connection;
+use crate::services::utils::resp_type::RespType;
+use std::sync::mpsc::{self, Sender};
+
+/// Administra los usuarios ACL y sus permisos.
+///
+/// Los subcomandos posibles son:
+/// * setuser: crea un usuario, o lo modifica si existe, aplicándole las reglas indicadas (ver `AclUser::apply_rule`).
+/// * getuser: devuelve las reglas de un usuario.
+/// * deluser: elimina usuarios y cierra las conexiones autenticadas con ellos. Devuelve la cantidad de usuarios eliminados.
+/// * list: devuelve la descripción de cada usuario, con el formato del archivo ACL.
+/// * users: devuelve los nombres de los usuarios.
+/// * whoami: devuelve el usuario con el que está autenticada la conexión.
+/// * cat: devuelve las categorías de comandos, o los comandos de una categoría.
+/// * log: devuelve los accesos denegados más recientes, o los elimina con `acl log reset`.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::entities::session::Session;
+/// # use proyecto_taller_1::services::commands::command_acl;
+/// # use std::sync::mpsc;
+///
+/// let (tx, _rx) = mpsc::channel();
+/// let session = Session::new(true);
+///
+/// let res = command_acl::acl(&[RespType::RBulkString("acl".into()), RespType::RBulkString("whoami".into())], &tx, &session);
+/// assert_eq!(res, RespType::RBulkString("default".into()));
+/// ```
+pub fn acl(cmd: &[RespType], tx: &Sender<WorkerMessage>, session: &Session) -> RespType {
+    let subcommand = cmd.get(1).and_then(RespType::as_str).map(str::to_lowercase);
+    match (subcommand.as_deref(), cmd.len()) {
+        (Some("setuser"), len) if len >= 3 => acl_setuser(&cmd[2..], tx),
+        (Some("getuser"), 3) => acl_getuser(&cmd[2], tx),
+        (Some("deluser"), len) if len >= 3 => acl_deluser(&cmd[2..], tx),
+        (Some("list"), 2) => {
+            let (list_tx, list_rx) = mpsc::channel();
+            tx.send(WorkerMessage::AclList(list_tx))
+                .expect("Could not send AclList message");
+            to_bulk_array(list_rx.recv().unwrap_or_default())
+        }
+        (Some("users"), 2) => {
+            let (users_tx, users_rx) = mpsc::channel();
+            tx.send(WorkerMessage::AclUsers(users_tx))
+                .expect("Could not send AclUsers message");
+            to_bulk_array(users_rx.recv().unwrap_or_default())
+        }
+        (Some("whoami"), 2) => match session.get_user() {
+            Some(user) => RespType::RBulkString(user.clone().into()),
+            None => RespType::RNullBulkString(),
+        },
+        (Some("cat"), 2) => to_bulk_array(get_acl_categories()),
+        (Some("cat"), 3) => {
+            let category = cmd[2].as_str().unwrap_or_default().to_lowercase();
+            match get_category_commands(&category) {
+                Some(commands) => to_bulk_array(commands),
+                None => RespType::RError(format!("ERR Unknown category '{}'", cmd[2])),
+            }
+        }
+        (Some("log"), 2) | (Some("log"), 3) => acl_log(cmd.get(2), tx),
+        (Some(subcommand), _) => RespType::RError(format!(
+            "ERR unknown subcommand or wrong number of arguments for '{}'",
+            subcommand
+        )),
+        (None, _) => RespType::RError(String::from(
+            "ERR wrong number of arguments for 'acl' command",
+        )),
+    }
+}
+
+/// Verifica que el usuario `username` pueda ejecutar el comando y acceder a las claves y canales que utiliza.
+///
+/// Si no puede, el servidor registra el acceso denegado y se devuelve el error `NOPERM` correspondiente.
+pub fn check_permissions(
+    cmd: &[RespType],
+    tx: &Sender<WorkerMessage>,
+    client_id: usize,
+    username: &str,
+) -> Option<String> {
+    let (check_tx, check_rx) = mpsc::channel();
+    tx.send(WorkerMessage::AclCheck(
+        client_id,
+        username.to_string(),
+        cmd.to_vec(),
+        check_tx,
+    ))
+    .expect("Could not send AclCheck message");
+    check_rx.recv().unwrap_or(None)
+}
+
+/// Verifica las credenciales de un cliente.
+///
+/// Si son incorrectas, el servidor registra el intento fallido.
+pub fn authenticate(
+    tx: &Sender<WorkerMessage>,
+    client_id: usize,
+    username: &str,
+    password: &RespType,
+) -> bool {
+    let password = String::from_utf8_lossy(password.as_bytes().unwrap_or_default()).to_string();
+    let (auth_tx, auth_rx) = mpsc::channel();
+    tx.send(WorkerMessage::AclAuthenticate(
+        client_id,
+        username.to_string(),
+        password,
+        auth_tx,
+    ))
+    .expect("Could not send AclAuthenticate message");
+    auth_rx.recv().unwrap_or(false)
+}
+
+/// Indica si el usuario está habilitado y no requiere contraseña.
+///
+/// Las conexiones nuevas quedan autenticadas con el usuario `default` solo si este no requiere contraseña.
+pub fn is_nopass_user(tx: &Sender<WorkerMessage>, username: &str) -> bool {
+    let (nopass_tx, nopass_rx) = mpsc::channel();
+    tx.send(WorkerMessage::AclNoPass(username.to_string(), nopass_tx))
+        .expect("Could not send AclNoPass message");
+    nopass_rx.recv().unwrap_or(false)
+}
+
+/// Reemplaza la contraseña del usuario `default` por la configurada en `requirepass`.
+///
+/// Si `password` es vacía, el usuario deja de requerir contraseña.
+pub fn set_requirepass(tx: &Sender<WorkerMessage>, password: &str) {
+    let rule = if password.is_empty() {
+        String::from("nopass")
+    } else {
+        format!(">{}", password)
+    };
+    let (set_tx, set_rx) = mpsc::channel();
+    tx.send(WorkerMessage::AclSetUser(
+        String::from(DEFAULT_USER),
+        vec![String::from("resetpass"), rule],
+        set_tx,
+    ))
+    .expect("Could not send AclSetUser message");
+    let _ = set_rx.recv();
+}
+
+/// Crea o modifica un usuario, donde `args[0]` es el nombre del usuario y el resto son las reglas a aplicar.
+fn acl_setuser(args: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
+    let username = match args[0].as_str() {
+        Some(username) if command_connection::is_valid_client_name(username) => username,
+        _ => {
+            return RespType::RError(String::from(
+                "ERR Usernames can't contain spaces or null characters",
+            ))
+        }
+    };
+    let rules: Vec<String> = args[1..].iter().map(|rule| rule.to_string()).collect();
+    let (set_tx, set_rx) = mpsc::channel();
+    tx.send(WorkerMessage::AclSetUser(
+        username.to_string(),
+        rules,
+        set_tx,
+    ))
+    .expect("Could not send AclSetUser message");
+    match set_rx.recv() {
+        Ok(Ok(())) => RespType::RSimpleString(String::from("OK")),
+        Ok(Err(e)) => RespType::RError(format!("ERR {}", e)),
+        Err(_) => RespType::RError(String::from("ERR could not set user")),
+    }
+}
+
+/// Devuelve un map con las reglas del usuario, o nulo si no existe.
+fn acl_getuser(username: &RespType, tx: &Sender<WorkerMessage>) -> RespType {
+    let (user_tx, user_rx) = mpsc::channel();
+    tx.send(WorkerMessage::AclGetUser(username.to_string(), user_tx))
+        .expect("Could not send AclGetUser message");
+    match user_rx.recv() {
+        Ok(Some(user)) => describe_user(&user),
+        _ => RespType::RNullBulkString(),
+    }
+}
+
+/// Representa las reglas de un usuario como lo hace `acl getuser`.
+fn describe_user(user: &AclUser) -> RespType {
+    let mut flags = vec![String::from(if user.is_enabled() { "on" } else { "off" })];
+    if user.is_nopass() {
+        flags.push(String::from("nopass"));
+    }
+    let keys: Vec<String> = user
+        .get_keys()
+        .iter()
+        .map(|pattern| format!("~{}", pattern))
+        .collect();
+    let channels: Vec<String> = user
+        .get_channels()
+        .iter()
+        .map(|pattern| format!("&{}", pattern))
+        .collect();
+    RespType::RMap(vec![
+        (RespType::RBulkString("flags".into()), to_bulk_array(flags)),
+        (
+            RespType::RBulkString("passwords".into()),
+            to_bulk_array(user.get_passwords().to_vec()),
+        ),
+        (
+            RespType::RBulkString("commands".into()),
+            RespType::RBulkString(user.get_commands().into()),
+        ),
+        (
+            RespType::RBulkString("keys".into()),
+            RespType::RBulkString(keys.join(" ").into()),
+        ),
+        (
+            RespType::RBulkString("channels".into()),
+            RespType::RBulkString(channels.join(" ").into()),
+        ),
+        (
+            RespType::RBulkString("selectors".into()),
+            RespType::RArray(vec![]),
+        ),
+    ])
+}
+
+/// Elimina los usuarios indicados. El usuario `default` no puede eliminarse.
+fn acl_deluser(usernames: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
+    if usernames
+        .iter()
+        .any(|username| username.is_word(DEFAULT_USER))
+    {
+        return RespType::RError(String::from("ERR The 'default' user cannot be removed"));
+    }
+    let usernames = usernames
+        .iter()
+        .map(|username| username.to_string())
+        .collect();
+    let (del_tx, del_rx) = mpsc::channel();
+    tx.send(WorkerMessage::AclDelUser(usernames, del_tx))
+        .expect("Could not send AclDelUser message");
+    RespType::RInteger(del_rx.recv().unwrap_or(0))
+}
+
+/// Devuelve las entradas más recientes del registro de accesos denegados, como máximo `option` si es un número.
+///
+/// Si `option` es `reset`, elimina todas las entradas.
+fn acl_log(option: Option<&RespType>, tx: &Sender<WorkerMessage>) -> RespType {
+    let count = match option {
+        None => None,
+        Some(option) if option.is_word("reset") => {
+            tx.send(WorkerMessage::AclLogReset)
+                .expect("Could not send AclLogReset message");
+            return RespType::RSimpleString(String::from("OK"));
+        }
+        Some(option) => match option.as_str().map(str::parse::<usize>) {
+            Some(Ok(count)) => Some(count),
+            _ => {
+                return RespType::RError(String::from(
+                    "ERR value is out of range, must be positive",
+                ))
+            }
+        },
+    };
+    let (log_tx, log_rx) = mpsc::channel();
+    tx.send(WorkerMessage::AclLog(count, log_tx))
+        .expect("Could not send AclLog message");
+    RespType::RArray(
+        log_rx
+            .recv()
+            .unwrap_or_default()
+            .iter()
+            .map(describe_log_entry)
+            .collect(),
+    )
+}
+
+/// Representa una entrada del registro de accesos denegados como lo hace `acl log`.
+fn describe_log_entry(entry: &AclLogEntry) -> RespType {
+    let field = |name: &str| RespType::RBulkString(name.into());
+    let text = |value: &str| RespType::RBulkString(value.into());
+    RespType::RMap(vec![
+        (field("count"), RespType::RInteger(entry.get_count())),
+        (field("reason"), text(entry.get_reason())),
+        (field("context"), text(entry.get_context())),
+        (field("object"), text(entry.get_object())),
+        (field("username"), text(entry.get_username())),
+        (field("age-seconds"), RespType::RDouble(entry.get_age())),
+        (field("client-info"), text(entry.get_client_info())),
+        (
+            field("timestamp-created"),
+            RespType::RInteger(entry.get_created_timestamp() as usize),
+        ),
+        (
+            field("timestamp-last-updated"),
+            RespType::RInteger(entry.get_updated_timestamp() as usize),
+        ),
+    ])
+}
+
+/// Convierte una lista de strings en un array de bulk strings.
+fn to_bulk_array(values: Vec<String>) -> RespType {
+    RespType::RArray(
+        values
+            .into_iter()
+            .map(|value| RespType::RBulkString(value.into()))
+            .collect(),
+    )
+}
+
+#[test]
+fn test_001_acl_deluser_rejects_default_user() {
+    let (tx, _rx) = mpsc::channel();
+    let session = Session::new(true);
+    let cmd = vec![
+        RespType::RBulkString("acl".into()),
+        RespType::RBulkString("deluser".into()),
+        RespType::RBulkString("DEFAULT".into()),
+    ];
+
+    assert_eq!(
+        acl(&cmd, &tx, &session),
+        RespType::RError(String::from("ERR The 'default' user cannot be removed"))
+    );
+}
+
+#[test]
+fn test_002_acl_setuser_sends_rules_to_server() {
+    let (tx, rx) = mpsc::channel();
+    let cmd = vec![
+        RespType::RBulkString("acl".into()),
+        RespType::RBulkString("setuser".into()),
+        RespType::RBulkString("worker".into()),
+        RespType::RBulkString("on".into()),
+        RespType::RBulkString("~job:*".into()),
+    ];
+
+    let handle = std::thread::spawn(move || acl(&cmd, &tx, &Session::new(true)));
+    match rx.recv().unwrap() {
+        WorkerMessage::AclSetUser(username, rules, sender) => {
+            assert_eq!(username, "worker");
+            assert_eq!(rules, vec!["on".to_string(), "~job:*".to_string()]);
+            sender.send(Ok(())).unwrap();
+        }
+        _ => panic!("Unexpected message"),
+    }
+    assert_eq!(
+        handle.join().unwrap(),
+        RespType::RSimpleString(String::from("OK"))
+    );
+}
+
+#[test]
+fn test_003_acl_cat_rejects_unknown_category() {
+    let (tx, _rx) = mpsc::channel();
+    let cmd = vec![
+        RespType::RBulkString("acl".into()),
+        RespType::RBulkString("cat".into()),
+        RespType::RBulkString("unknown".into()),
+    ];
+
+    assert_eq!(
+        acl(&cmd, &tx, &Session::new(true)),
+        RespType::RError(String::from("ERR Unknown category 'unknown'"))
+    );
+}
@@ -0,0 +1,248 @@
+//! Servicio que implementa los comandos de Redis Cluster: `cluster` y `asking`.
+
+use crate::domain::entities::cluster::{
+    key_hash_slot, ClusterRequest, SlotAction, SlotRoute, CLUSTER_SLOTS,
+};
+use crate::domain::entities::config::Config;
+use crate::domain::entities::message::WorkerMessage;
+use crate::domain::entities::session::Session;
+use crate::domain::implementations::database::Database;
+use crate::services::utils::resp_type::RespType;
+use std::net::IpAddr;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, RwLock};
+
+/// Diferencia entre el puerto de un nodo y el puerto de su bus, si no se indica otro.
+pub const CLUSTER_PORT_INCREMENT: u16 = 10000;
+
+/// Error con el que se rechazan los comandos de cluster si el servidor no está en modo cluster.
+const CLUSTER_DISABLED_ERROR: &str = "ERR This instance has cluster support disabled";
+
+/// Indica si el servidor está en modo cluster, según el atributo `cluster-enabled` de la configuración (por defecto `no`).
+pub fn is_cluster_enabled(config: &Arc<RwLock<Config>>) -> bool {
+    config
+        .read()
+        .ok()
+        .and_then(|conf| conf.get_attribute(String::from("cluster-enabled")).ok())
+        .is_some_and(|enabled| enabled.eq_ignore_ascii_case("yes"))
+}
+
+/// Consulta o modifica el estado del cluster.
+///
+/// Los subcomandos posibles son:
+/// * info, myid, nodes, slots y shards: describen el cluster y el nodo local.
+/// * keyslot `<clave>`: devuelve el hash slot de la clave.
+/// * countkeysinslot `<slot>` y getkeysinslot `<slot> <cantidad>`: cuentan o listan las claves locales del slot.
+/// * addslots `<slot> ...` y addslotsrange `<inicio> <fin> ...`: asignan slots al nodo local.
+/// * delslots `<slot> ...` y delslotsrange `<inicio> <fin> ...`: dejan slots sin asignar.
+/// * setslot `<slot> importing|migrating|node <id>` y setslot `<slot> stable`: mueven un slot entre nodos.
+/// * meet `<ip> <puerto> [<puerto del bus>]`: agrega un nodo al cluster.
+/// * forget `<id>`: elimina un nodo del cluster.
+///
+/// Devuelve error si el servidor no está en modo cluster.
+pub fn cluster(
+    cmd: &[RespType],
+    tx: &Sender<WorkerMessage>,
+    database: &Arc<RwLock<Database>>,
+    config: &Arc<RwLock<Config>>,
+) -> RespType {
+    if !is_cluster_enabled(config) {
+        return RespType::RError(String::from(CLUSTER_DISABLED_ERROR));
+    }
+    let subcommand = cmd.get(1).and_then(RespType::as_str).map(str::to_lowercase);
+    let request = match (subcommand.as_deref(), cmd.len()) {
+        (Some("info"), 2) => Ok(ClusterRequest::Info),
+        (Some("myid"), 2) => Ok(ClusterRequest::MyId),
+        (Some("nodes"), 2) => Ok(ClusterRequest::Nodes),
+        (Some("slots"), 2) => Ok(ClusterRequest::Slots),
+        (Some("shards"), 2) => Ok(ClusterRequest::Shards),
+        (Some("keyslot"), 3) => {
+            return RespType::RInteger(key_hash_slot(cmd[2].as_bytes().unwrap_or_default()) as usize)
+        }
+        (Some("countkeysinslot"), 3) => return count_keys_in_slot(&cmd[2], database),
+        (Some("getkeysinslot"), 4) => return get_keys_in_slot(&cmd[2], &cmd[3], database),
+        (Some("addslots"), len) if len >= 3 => parse_slots(&cmd[2..]).map(ClusterRequest::AddSlots),
+        (Some("delslots"), len) if len >= 3 => parse_slots(&cmd[2..]).map(ClusterRequest::DelSlots),
+        (Some("addslotsrange"), len) if len >= 4 && len % 2 == 0 => {
+            parse_slot_ranges(&cmd[2..]).map(ClusterRequest::AddSlots)
+        }
+        (Some("delslotsrange"), len) if len >= 4 && len % 2 == 0 => {
+            parse_slot_ranges(&cmd[2..]).map(ClusterRequest::DelSlots)
+        }
+        (Some("setslot"), 4) | (Some("setslot"), 5) => parse_setslot(&cmd[2..]),
+        (Some("meet"), 4) | (Some("meet"), 5) => parse_meet(&cmd[2..]),
+        (Some("forget"), 3) => match cmd[2].as_str() {
+            Some(id) => Ok(ClusterRequest::Forget(id.to_string())),
+            None => Err(format!("ERR Unknown node {:?}", cmd[2])),
+        },
+        (Some(subcommand), _) => Err(format!(
+            "ERR unknown subcommand or wrong number of arguments for '{}'",
+            subcommand
+        )),
+        (None, _) => Err(String::from(
+            "ERR wrong number of arguments for 'cluster' command",
+        )),
+    };
+    match request.and_then(|request| execute(request, tx)) {
+        Ok(reply) => reply,
+        Err(e) => RespType::RError(e),
+    }
+}
+
+/// Registra que el siguiente comando del cliente se atiende aunque el slot de sus claves se esté importando desde
+/// otro nodo, luego de una redirección `ASK`.
+pub fn asking(config: &Arc<RwLock<Config>>, session: &mut Session) -> RespType {
+    if !is_cluster_enabled(config) {
+        return RespType::RError(String::from(CLUSTER_DISABLED_ERROR));
+    }
+    session.set_asking(true);
+    RespType::RSimpleString(String::from("OK"))
+}
+
+/// Pregunta al servidor cómo se atienden los comandos sobre las claves del slot.
+///
+/// Devuelve `None` si el servidor todavía no inició el cluster.
+pub fn get_slot_route(slot: u16, tx: &Sender<WorkerMessage>) -> Option<SlotRoute> {
+    let (route_tx, route_rx) = mpsc::channel();
+    tx.send(WorkerMessage::ClusterRoute(slot, route_tx))
+        .expect("Could not send ClusterRoute message");
+    route_rx.recv().ok().flatten()
+}
+
+/// Envía un subcomando de `cluster` al servidor y devuelve su respuesta.
+fn execute(request: ClusterRequest, tx: &Sender<WorkerMessage>) -> Result<RespType, String> {
+    let (cluster_tx, cluster_rx) = mpsc::channel();
+    tx.send(WorkerMessage::Cluster(request, cluster_tx))
+        .expect("Could not send Cluster message");
+    cluster_rx
+        .recv()
+        .unwrap_or_else(|_| Err(String::from(CLUSTER_DISABLED_ERROR)))
+}
+
+/// Devuelve la cantidad de claves locales del slot.
+fn count_keys_in_slot(slot: &RespType, database: &Arc<RwLock<Database>>) -> RespType {
+    let slot = match parse_slot(slot) {
+        Ok(slot) => slot,
+        Err(_) => return RespType::RError(String::from("ERR Invalid slot")),
+    };
+    match database.read() {
+        Ok(db) => RespType::RInteger(db.count_keys_in_slot(slot)),
+        Err(_) => RespType::RError(String::from("ERR database couldn't be read")),
+    }
+}
+
+/// Devuelve hasta `count` claves locales del slot.
+fn get_keys_in_slot(
+    slot: &RespType,
+    count: &RespType,
+    database: &Arc<RwLock<Database>>,
+) -> RespType {
+    let count = count.as_str().and_then(|count| count.parse::<usize>().ok());
+    let (slot, count) = match (parse_slot(slot), count) {
+        (Ok(slot), Some(count)) => (slot, count),
+        _ => return RespType::RError(String::from("ERR Invalid slot or number of keys")),
+    };
+    match database.read() {
+        Ok(db) => RespType::RArray(
+            db.get_keys_in_slot(slot, count)
+                .into_iter()
+                .map(RespType::RBulkString)
+                .collect(),
+        ),
+        Err(_) => RespType::RError(String::from("ERR database couldn't be read")),
+    }
+}
+
+/// Interpreta un número de slot.
+fn parse_slot(slot: &RespType) -> Result<u16, String> {
+    slot.as_str()
+        .and_then(|slot| slot.parse::<u16>().ok())
+        .filter(|slot| (*slot as usize) < CLUSTER_SLOTS)
+        .ok_or_else(|| String::from("ERR Invalid or out of range slot"))
+}
+
+fn parse_slots(slots: &[RespType]) -> Result<Vec<u16>, String> {
+    slots.iter().map(parse_slot).collect()
+}
+
+/// Interpreta pares de slots `<inicio> <fin>` y devuelve todos los slots de los rangos.
+fn parse_slot_ranges(ranges: &[RespType]) -> Result<Vec<u16>, String> {
+    let mut slots = Vec::new();
+    for range in ranges.chunks(2) {
+        let (start, end) = (parse_slot(&range[0])?, parse_slot(&range[1])?);
+        if start > end {
+            return Err(format!(
+                "ERR start slot number {} is greater than end slot number {}",
+                start, end
+            ));
+        }
+        slots.extend(start..=end);
+    }
+    Ok(slots)
+}
+
+/// Interpreta los argumentos de `cluster setslot`.
+fn parse_setslot(args: &[RespType]) -> Result<ClusterRequest, String> {
+    let slot = parse_slot(&args[0])?;
+    let action = args[1].as_str().map(str::to_lowercase);
+    let node = args.get(2).and_then(RespType::as_str).map(String::from);
+    let action = match (action.as_deref(), node) {
+        (Some("importing"), Some(id)) => SlotAction::Importing(id),
+        (Some("migrating"), Some(id)) => SlotAction::Migrating(id),
+        (Some("node"), Some(id)) => SlotAction::Node(id),
+        (Some("stable"), None) => SlotAction::Stable,
+        _ => {
+            return Err(String::from(
+                "ERR Invalid CLUSTER SETSLOT action or number of arguments. Try CLUSTER HELP",
+            ))
+        }
+    };
+    Ok(ClusterRequest::SetSlot(slot, action))
+}
+
+/// Interpreta los argumentos de `cluster meet`; si no se indica el puerto del bus, es el puerto más 10000.
+fn parse_meet(args: &[RespType]) -> Result<ClusterRequest, String> {
+    let ip = args[0].as_str().unwrap_or_default();
+    let port = args[1].as_str().unwrap_or_default();
+    let invalid = || format!("ERR Invalid node address specified: {}:{}", ip, port);
+    let address = ip.parse::<IpAddr>().map_err(|_| invalid())?;
+    let port = port.parse::<u16>().map_err(|_| invalid())?;
+    let bus_port = match args.get(2) {
+        Some(bus_port) => bus_port.as_str().and_then(|bus_port| bus_port.parse().ok()),
+        None => port.checked_add(CLUSTER_PORT_INCREMENT),
+    }
+    .ok_or_else(|| String::from("ERR Invalid bus port specified"))?;
+    Ok(ClusterRequest::Meet(address.to_string(), port, bus_port))
+}
+
+#[test]
+fn test_01_cluster_arguments_are_parsed_into_requests() {
+    let args = |words: &[&str]| -> Vec<RespType> {
+        words
+            .iter()
+            .map(|word| RespType::RBulkString(word.to_string().into()))
+            .collect()
+    };
+    assert_eq!(
+        parse_slot_ranges(&args(&["0", "2", "10", "10"])),
+        Ok(vec![0, 1, 2, 10])
+    );
+    assert!(parse_slot_ranges(&args(&["5", "2"]))
+        .unwrap_err()
+        .contains("greater than end slot"));
+    assert!(parse_slots(&args(&["16384"])).is_err());
+    assert_eq!(
+        parse_setslot(&args(&["7", "node", "abc"])),
+        Ok(ClusterRequest::SetSlot(
+            7,
+            SlotAction::Node(String::from("abc"))
+        ))
+    );
+    assert!(parse_setslot(&args(&["7", "stable", "abc"])).is_err());
+    assert_eq!(
+        parse_meet(&args(&["127.0.0.1", "7000"])),
+        Ok(ClusterRequest::Meet(String::from("127.0.0.1"), 7000, 17000))
+    );
+    assert!(parse_meet(&args(&["localhost", "7000"])).is_err());
+    assert!(parse_meet(&args(&["127.0.0.1", "60000"])).is_err());
+}
@@ -0,0 +1,637 @@
+//! Servicio que implementa todos los comandos de tipo Connection
+
+use crate::domain::entities::client::ClientFilter;
+use crate::domain::entities::message::WorkerMessage;
+use crate::domain::entities::session::{Session, DEFAULT_USER};
+use crate::domain::entities::tracking::{ClientTracking, TrackingOptions};
+use crate::services::commands::command_acl;
+use crate::services::utils::resp_type::{RespType, RESP3};
+use std::sync::mpsc::{self, Sender};
+use std::time::Duration;
+
+/// Verifica que el servidor esté respondiendo.
+///
+/// Devuelve "PONG", o el mensaje recibido como argumento.
+/// Si el cliente está suscrito a algún canal y utiliza RESP2, la respuesta es un array con "pong" y el mensaje,
+/// ya que en ese estado el cliente solo espera recibir arrays.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_connection;
+///
+/// let res = command_connection::ping(&[RespType::RBulkString("ping".into())], false, 2);
+/// assert_eq!(res, RespType::RSimpleString("PONG".to_string()));
+///
+/// let res = command_connection::ping(&[RespType::RBulkString("ping".into()), RespType::RBulkString("hola".into())], false, 2);
+/// assert_eq!(res, RespType::RBulkString("hola".into()));
+///
+/// let res = command_connection::ping(&[RespType::RBulkString("ping".into())], true, 2);
+/// assert_eq!(res, RespType::RArray(vec![RespType::RBulkString("pong".into()), RespType::RBulkString("".into())]));
+/// ```
+pub fn ping(cmd: &[RespType], subscribed: bool, protocol: usize) -> RespType {
+    if cmd.len() > 2 {
+        return RespType::RError(String::from(
+            "ERR wrong number of arguments for 'ping' command",
+        ));
+    }
+    if subscribed && protocol < RESP3 {
+        let message = cmd
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| RespType::RBulkString(vec![]));
+        return RespType::RArray(vec![RespType::RBulkString("pong".into()), message]);
+    }
+    match cmd.get(1) {
+        Some(message) => message.clone(),
+        None => RespType::RSimpleString(String::from("PONG")),
+    }
+}
+
+/// Devuelve el mensaje recibido.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_connection;
+///
+/// let res = command_connection::echo(&[RespType::RBulkString("echo".into()), RespType::RBulkString("hola mundo".into())]);
+/// assert_eq!(res, RespType::RBulkString("hola mundo".into()));
+/// ```
+pub fn echo(cmd: &[RespType]) -> RespType {
+    if cmd.len() != 2 {
+        return RespType::RError(String::from(
+            "ERR wrong number of arguments for 'echo' command",
+        ));
+    }
+    cmd[1].clone()
+}
+
+/// Cierra la conexión del cliente.
+///
+/// Devuelve "OK"; el servidor cierra la conexión una vez enviada la respuesta.
+pub fn quit() -> RespType {
+    RespType::RSimpleString(String::from("OK"))
+}
+
+/// Vuelve la conexión al estado en el que se encontraba al conectarse.
+///
+/// Desuscribe al cliente de todos los canales, lo saca del estado "monitor", elimina el nombre de la conexión
+/// y vuelve a utilizar RESP2. La conexión queda autenticada con el usuario `default` solo si este no requiere contraseña.
+/// Devuelve "RESET".
+pub fn reset(tx: &Sender<WorkerMessage>, client_id: usize, session: &mut Session) -> RespType {
+    tx.send(WorkerMessage::ResetClient(client_id))
+        .expect("Could not send ResetClient message");
+    *session = Session::new(command_acl::is_nopass_user(tx, DEFAULT_USER));
+    RespType::RSimpleString(String::from("RESET"))
+}
+
+/// Autentica la conexión.
+///
+/// Admite las formas `auth <contraseña>` y `auth <usuario> <contraseña>`; en la primera se utiliza el usuario `default`,
+/// cuya contraseña se configura con el atributo `requirepass`.
+/// Si las credenciales son incorrectas el servidor registra el intento fallido y se devuelve un error `WRONGPASS`.
+pub fn auth(
+    cmd: &[RespType],
+    tx: &Sender<WorkerMessage>,
+    client_id: usize,
+    session: &mut Session,
+) -> RespType {
+    let (username, password) = match cmd {
+        [_, password] => (DEFAULT_USER, password),
+        [_, username, password] => (username.as_str().unwrap_or_default(), password),
+        _ => {
+            return RespType::RError(String::from(
+                "ERR wrong number of arguments for 'auth' command",
+            ))
+        }
+    };
+    if cmd.len() == 2 && command_acl::is_nopass_user(tx, DEFAULT_USER) {
+        return RespType::RError(String::from("ERR AUTH <password> called without any password configured for the default user. Are you sure your configuration is correct?"));
+    }
+    if !command_acl::authenticate(tx, client_id, username, password) {
+        return RespType::RError(String::from(
+            "WRONGPASS invalid username-password pair or user is disabled.",
+        ));
+    }
+    session.set_user(Some(username.to_string()));
+    RespType::RSimpleString(String::from("OK"))
+}
+
+/// Administra la conexión del cliente.
+///
+/// Los subcomandos posibles son:
+/// * setname: asigna un nombre a la conexión. Si el nombre es vacío, se elimina.
+/// * getname: devuelve el nombre de la conexión.
+/// * id: devuelve el identificador de la conexión.
+/// * info: devuelve información sobre la conexión.
+/// * setinfo: registra el nombre (`lib-name`) o la versión (`lib-ver`) de la librería que utiliza el cliente.
+/// * list: devuelve información sobre las conexiones, opcionalmente filtradas por tipo (`type`) o identificador (`id`).
+/// * kill: cierra conexiones, ver `client_kill`.
+/// * pause: suspende los comandos de todos los clientes (`all`, por defecto) o solo los de escritura (`write`)
+///   durante la cantidad de milisegundos indicada.
+/// * unpause: reanuda los comandos suspendidos.
+/// * no-evict: activa (`on`) o desactiva (`off`) el modo "no-evict" de la conexión.
+/// * tracking: activa (`on`) o desactiva (`off`) el seguimiento de las claves que lee la conexión, ver `client_tracking`.
+/// * caching: indica si se recuerdan (`yes`) o no (`no`) las claves que lee el siguiente comando, en los modos `optin` y `optout`.
+/// * getredir: devuelve el identificador de la conexión a la que se redirigen los mensajes de invalidación,
+///   0 si no se redirigen o -1 si el seguimiento de claves no está activado.
+/// * trackinginfo: devuelve el estado del seguimiento de claves de la conexión.
+pub fn client(cmd: &[RespType], tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    let subcommand = cmd.get(1).and_then(RespType::as_str).map(str::to_lowercase);
+    match (subcommand.as_deref(), cmd.len()) {
+        (Some("setname"), 3) => client_setname(&cmd[2], tx, client_id),
+        (Some("getname"), 2) => client_getname(tx, client_id),
+        (Some("id"), 2) => RespType::RInteger(client_id),
+        (Some("info"), 2) => client_info(tx, client_id),
+        (Some("setinfo"), 4) => client_setinfo(&cmd[2], &cmd[3], tx, client_id),
+        (Some("list"), _) => client_list(&cmd[2..], tx),
+        (Some("kill"), len) if len >= 3 => client_kill(&cmd[2..], tx, client_id),
+        (Some("pause"), 3) | (Some("pause"), 4) => client_pause(&cmd[2..], tx),
+        (Some("unpause"), 2) => {
+            tx.send(WorkerMessage::UnpauseClients)
+                .expect("Could not send UnpauseClients message");
+            RespType::RSimpleString(String::from("OK"))
+        }
+        (Some("no-evict"), 3) => client_no_evict(&cmd[2], tx, client_id),
+        (Some("tracking"), len) if len >= 3 => client_tracking(&cmd[2..], tx, client_id),
+        (Some("caching"), 3) => client_caching(&cmd[2], tx, client_id),
+        (Some("getredir"), 2) => {
+            RespType::RSignedNumber(get_tracking_redirect(get_client_tracking(tx, client_id)))
+        }
+        (Some("trackinginfo"), 2) => client_trackinginfo(tx, client_id),
+        (Some(subcommand), _) => RespType::RError(format!(
+            "ERR unknown subcommand or wrong number of arguments for '{}'",
+            subcommand
+        )),
+        (None, _) => RespType::RError(String::from(
+            "ERR wrong number of arguments for 'client' command",
+        )),
+    }
+}
+
+/// Indica si `name` puede utilizarse como nombre de una conexión o de una librería cliente.
+///
+/// El nombre no puede contener espacios, saltos de línea ni caracteres especiales.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::commands::command_connection;
+///
+/// assert!(command_connection::is_valid_client_name("worker-1"));
+/// assert!(!command_connection::is_valid_client_name("worker 1"));
+/// ```
+pub fn is_valid_client_name(name: &str) -> bool {
+    name.chars().all(|c| ('!'..='~').contains(&c))
+}
+
+/// Asigna un nombre a la conexión.
+fn client_setname(name: &RespType, tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    match name.as_str() {
+        Some(name) if is_valid_client_name(name) => {
+            let name = Some(name.to_string()).filter(|name| !name.is_empty());
+            tx.send(WorkerMessage::SetClientName(client_id, name))
+                .expect("Could not send SetClientName message");
+            RespType::RSimpleString(String::from("OK"))
+        }
+        _ => RespType::RError(String::from(
+            "ERR Client names cannot contain spaces, newlines or special characters.",
+        )),
+    }
+}
+
+/// Devuelve el nombre de la conexión, o nulo si no tiene.
+fn client_getname(tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    let (name_tx, name_rx) = mpsc::channel();
+    tx.send(WorkerMessage::ClientName(client_id, name_tx))
+        .expect("Could not send ClientName message");
+    match name_rx.recv() {
+        Ok(Some(name)) => RespType::RBulkString(name.into()),
+        _ => RespType::RNullBulkString(),
+    }
+}
+
+/// Devuelve información sobre la conexión, como un verbatim string de texto.
+fn client_info(tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    let (info_tx, info_rx) = mpsc::channel();
+    tx.send(WorkerMessage::ClientInfo(client_id, info_tx))
+        .expect("Could not send ClientInfo message");
+    match info_rx.recv() {
+        Ok(info) => RespType::RVerbatimString(String::from("txt"), info),
+        Err(_) => RespType::RError(String::from("ERR client not found")),
+    }
+}
+
+/// Registra el nombre o la versión de la librería que utiliza el cliente.
+fn client_setinfo(
+    attribute: &RespType,
+    value: &RespType,
+    tx: &Sender<WorkerMessage>,
+    client_id: usize,
+) -> RespType {
+    let value =
+        match value.as_str() {
+            Some(value) if is_valid_client_name(value) => value.to_string(),
+            _ => return RespType::RError(String::from(
+                "ERR lib-name and lib-ver cannot contain spaces, newlines or special characters.",
+            )),
+        };
+    let message = if attribute.is_word("lib-name") {
+        WorkerMessage::SetClientLibName(client_id, value)
+    } else if attribute.is_word("lib-ver") {
+        WorkerMessage::SetClientLibVersion(client_id, value)
+    } else {
+        return RespType::RError(format!("ERR Unrecognized option '{}'", attribute));
+    };
+    tx.send(message)
+        .expect("Could not send client library information");
+    RespType::RSimpleString(String::from("OK"))
+}
+
+/// Devuelve información sobre las conexiones que cumplen con los filtros `type <tipo>` o `id <id> [<id> ...]`.
+fn client_list(args: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
+    let mut filter = ClientFilter::default();
+    match args.first() {
+        None => {}
+        Some(option) if option.is_word("type") && args.len() == 2 => {
+            match args[1].as_str().map(str::to_lowercase) {
+                Some(client_type) if is_valid_client_type(&client_type) => {
+                    filter.client_type = Some(client_type)
+                }
+                _ => return RespType::RError(format!("ERR Unknown client type '{}'", args[1])),
+            }
+        }
+        Some(option) if option.is_word("id") && args.len() > 1 => {
+            for id in &args[1..] {
+                match id.as_str().map(str::parse::<usize>) {
+                    Some(Ok(id)) => filter.ids.push(id),
+                    _ => return RespType::RError(String::from("ERR Invalid client ID")),
+                }
+            }
+        }
+        Some(_) => return RespType::RError(String::from("ERR syntax error")),
+    }
+    let (list_tx, list_rx) = mpsc::channel();
+    tx.send(WorkerMessage::ClientList(filter, list_tx))
+        .expect("Could not send ClientList message");
+    match list_rx.recv() {
+        Ok(list) => RespType::RVerbatimString(String::from("txt"), list),
+        Err(_) => RespType::RError(String::from("ERR could not list clients")),
+    }
+}
+
+/// Cierra las conexiones indicadas.
+///
+/// Admite dos formas:
+/// * `client kill <ip:puerto>`: cierra la conexión con esa dirección. Devuelve "OK", o error si no existe.
+/// * `client kill <filtro> <valor> ...`: cierra las conexiones que cumplen con todos los filtros y devuelve la cantidad
+///   de conexiones cerradas. Los filtros posibles son `id`, `addr`, `laddr`, `user`, `type` y `skipme`.
+///   Salvo que se indique `skipme no`, la conexión que ejecuta el comando no se cierra.
+fn client_kill(args: &[RespType], tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    let (kill_tx, kill_rx) = mpsc::channel();
+    if args.len() == 1 {
+        let filter = ClientFilter {
+            addrs: args[0].as_str().map(String::from),
+            ..ClientFilter::default()
+        };
+        tx.send(WorkerMessage::ClientKill(filter, kill_tx))
+            .expect("Could not send ClientKill message");
+        return match kill_rx.recv() {
+            Ok(killed) if killed > 0 => RespType::RSimpleString(String::from("OK")),
+            _ => RespType::RError(String::from("ERR No such client")),
+        };
+    }
+    if !args.len().is_multiple_of(2) {
+        return RespType::RError(String::from("ERR syntax error"));
+    }
+
+    let mut filter = ClientFilter {
+        skip: Some(client_id),
+        ..ClientFilter::default()
+    };
+    for pair in args.chunks(2) {
+        let value = match pair[1].as_str() {
+            Some(value) => value.to_string(),
+            None => return RespType::RError(String::from("ERR syntax error")),
+        };
+        match pair[0].as_str().map(str::to_lowercase).as_deref() {
+            Some("id") => match value.parse::<usize>() {
+                Ok(id) => filter.ids.push(id),
+                Err(_) => {
+                    return RespType::RError(String::from("ERR client-id should be greater than 0"))
+                }
+            },
+            Some("addr") => filter.addrs = Some(value),
+            Some("laddr") => filter.laddr = Some(value),
+            Some("user") => filter.user = Some(value),
+            Some("type") => {
+                let client_type = value.to_lowercase();
+                if !is_valid_client_type(&client_type) {
+                    return RespType::RError(format!("ERR Unknown client type '{}'", value));
+                }
+                filter.client_type = Some(client_type);
+            }
+            Some("skipme") => match value.to_lowercase().as_str() {
+                "yes" => filter.skip = Some(client_id),
+                "no" => filter.skip = None,
+                _ => return RespType::RError(String::from("ERR syntax error")),
+            },
+            _ => return RespType::RError(String::from("ERR syntax error")),
+        }
+    }
+    tx.send(WorkerMessage::ClientKill(filter, kill_tx))
+        .expect("Could not send ClientKill message");
+    RespType::RInteger(kill_rx.recv().unwrap_or(0))
+}
+
+/// Suspende los comandos de los clientes durante `args[0]` milisegundos.
+///
+/// Si `args[1]` es `write`, solo se suspenden los comandos de escritura.
+fn client_pause(args: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
+    let timeout = match args[0].as_str().map(str::parse::<u64>) {
+        Some(Ok(timeout)) => timeout,
+        _ => {
+            return RespType::RError(String::from(
+                "ERR timeout is not an integer or out of range",
+            ))
+        }
+    };
+    let write_only = match args.get(1) {
+        None => false,
+        Some(mode) if mode.is_word("all") => false,
+        Some(mode) if mode.is_word("write") => true,
+        Some(_) => return RespType::RError(String::from("ERR syntax error")),
+    };
+    tx.send(WorkerMessage::PauseClients(
+        Duration::from_millis(timeout),
+        write_only,
+    ))
+    .expect("Could not send PauseClients message");
+    RespType::RSimpleString(String::from("OK"))
+}
+
+/// Activa o desactiva el modo "no-evict" de la conexión.
+fn client_no_evict(mode: &RespType, tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    let no_evict = if mode.is_word("on") {
+        true
+    } else if mode.is_word("off") {
+        false
+    } else {
+        return RespType::RError(String::from("ERR syntax error"));
+    };
+    tx.send(WorkerMessage::SetClientNoEvict(client_id, no_evict))
+        .expect("Could not send SetClientNoEvict message");
+    RespType::RSimpleString(String::from("OK"))
+}
+
+/// Activa (`on`) o desactiva (`off`) el seguimiento de las claves que lee la conexión, para invalidarlas en la caché
+/// del cliente cuando se modifican.
+///
+/// Al activarlo pueden indicarse las opciones `redirect <id>`, `bcast`, `prefix <prefijo>` (una vez por prefijo),
+/// `optin`, `optout` y `noloop` (ver `TrackingOptions`).
+/// Devuelve error si las opciones no son válidas, si la conexión a la que se redirige no existe o si el seguimiento
+/// ya estaba activado en otro modo.
+fn client_tracking(args: &[RespType], tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    let options = if args[0].is_word("on") {
+        match TrackingOptions::parse(&args[1..]) {
+            Ok(options) => Some(options),
+            Err(error) => return RespType::RError(error),
+        }
+    } else if args[0].is_word("off") && args.len() == 1 {
+        None
+    } else {
+        return RespType::RError(String::from("ERR syntax error"));
+    };
+    let (tracking_tx, tracking_rx) = mpsc::channel();
+    tx.send(WorkerMessage::ClientTracking(
+        client_id,
+        options,
+        tracking_tx,
+    ))
+    .expect("Could not send ClientTracking message");
+    match tracking_rx.recv() {
+        Ok(Ok(())) => RespType::RSimpleString(String::from("OK")),
+        Ok(Err(error)) => RespType::RError(error),
+        Err(_) => RespType::RError(String::from("ERR client not found")),
+    }
+}
+
+/// Indica si se recuerdan (`yes`) o no (`no`) las claves que lee el siguiente comando de la conexión.
+fn client_caching(mode: &RespType, tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    let caching = if mode.is_word("yes") {
+        true
+    } else if mode.is_word("no") {
+        false
+    } else {
+        return RespType::RError(String::from("ERR syntax error"));
+    };
+    let (caching_tx, caching_rx) = mpsc::channel();
+    tx.send(WorkerMessage::ClientCaching(client_id, caching, caching_tx))
+        .expect("Could not send ClientCaching message");
+    match caching_rx.recv() {
+        Ok(Ok(())) => RespType::RSimpleString(String::from("OK")),
+        Ok(Err(error)) => RespType::RError(error),
+        Err(_) => RespType::RError(String::from("ERR client not found")),
+    }
+}
+
+/// Devuelve el estado del seguimiento de claves de la conexión: sus flags, la conexión a la que se redirigen los
+/// mensajes de invalidación y los prefijos del modo `bcast`.
+fn client_trackinginfo(tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    let tracking = get_client_tracking(tx, client_id);
+    let flags = match &tracking {
+        Some(tracking) => tracking.get_flags(),
+        None => vec!["off"],
+    };
+    let prefixes = tracking
+        .as_ref()
+        .map(|tracking| tracking.get_options().prefixes.clone())
+        .unwrap_or_default();
+    RespType::RMap(vec![
+        (
+            RespType::RBulkString("flags".into()),
+            RespType::RSet(
+                flags
+                    .into_iter()
+                    .map(|flag| RespType::RBulkString(flag.into()))
+                    .collect(),
+            ),
+        ),
+        (
+            RespType::RBulkString("redirect".into()),
+            RespType::RSignedNumber(get_tracking_redirect(tracking)),
+        ),
+        (
+            RespType::RBulkString("prefixes".into()),
+            RespType::RArray(
+                prefixes
+                    .into_iter()
+                    .map(|prefix| RespType::RBulkString(prefix.into()))
+                    .collect(),
+            ),
+        ),
+    ])
+}
+
+/// Pide a la entidad `Server` el seguimiento de claves de la conexión, si lo tiene activado.
+fn get_client_tracking(tx: &Sender<WorkerMessage>, client_id: usize) -> Option<ClientTracking> {
+    let (tracking_tx, tracking_rx) = mpsc::channel();
+    tx.send(WorkerMessage::ClientTrackingInfo(client_id, tracking_tx))
+        .expect("Could not send ClientTrackingInfo message");
+    tracking_rx.recv().ok().flatten()
+}
+
+/// Retorna el identificador de la conexión a la que se redirigen los mensajes de invalidación, 0 si no se redirigen
+/// o -1 si el seguimiento de claves no está activado.
+fn get_tracking_redirect(tracking: Option<ClientTracking>) -> isize {
+    match tracking {
+        Some(tracking) => tracking.get_options().redirect.unwrap_or(0) as isize,
+        None => -1,
+    }
+}
+
+/// Indica si `client_type` es un tipo de cliente válido para filtrar conexiones.
+fn is_valid_client_type(client_type: &str) -> bool {
+    matches!(
+        client_type,
+        "normal" | "pubsub" | "master" | "replica" | "slave"
+    )
+}
+
+#[test]
+fn test_001_client_setname_sends_name_to_server() {
+    let (tx, rx) = mpsc::channel();
+    let client_id = 1;
+    let cmd = vec![
+        RespType::RBulkString("client".into()),
+        RespType::RBulkString("SETNAME".into()),
+        RespType::RBulkString("worker-1".into()),
+    ];
+
+    assert_eq!(
+        client(&cmd, &tx, client_id),
+        RespType::RSimpleString(String::from("OK"))
+    );
+    match rx.recv().unwrap() {
+        WorkerMessage::SetClientName(_, name) => assert_eq!(name, Some("worker-1".to_string())),
+        _ => panic!("Unexpected message"),
+    }
+}
+
+#[test]
+fn test_002_client_setname_rejects_spaces() {
+    let (tx, _rx) = mpsc::channel();
+    let client_id = 1;
+    let cmd = vec![
+        RespType::RBulkString("client".into()),
+        RespType::RBulkString("setname".into()),
+        RespType::RBulkString("worker 1".into()),
+    ];
+
+    assert!(matches!(client(&cmd, &tx, client_id), RespType::RError(_)));
+}
+
+#[test]
+fn test_003_client_setinfo_rejects_unknown_attribute() {
+    let (tx, _rx) = mpsc::channel();
+    let client_id = 1;
+    let cmd = vec![
+        RespType::RBulkString("client".into()),
+        RespType::RBulkString("setinfo".into()),
+        RespType::RBulkString("lib-color".into()),
+        RespType::RBulkString("red".into()),
+    ];
+
+    assert!(matches!(client(&cmd, &tx, client_id), RespType::RError(_)));
+}
+
+#[test]
+fn test_004_client_kill_with_filters_skips_own_connection() {
+    let (tx, rx) = mpsc::channel();
+    let client_id = 1;
+    let cmd = vec![
+        RespType::RBulkString("client".into()),
+        RespType::RBulkString("kill".into()),
+        RespType::RBulkString("TYPE".into()),
+        RespType::RBulkString("pubsub".into()),
+    ];
+
+    let handle = std::thread::spawn(move || client(&cmd, &tx, client_id));
+    match rx.recv().unwrap() {
+        WorkerMessage::ClientKill(filter, sender) => {
+            assert_eq!(filter.client_type, Some("pubsub".to_string()));
+            assert_eq!(filter.skip, Some(client_id));
+            sender.send(2).unwrap();
+        }
+        _ => panic!("Unexpected message"),
+    }
+    assert_eq!(handle.join().unwrap(), RespType::RInteger(2));
+}
+
+#[test]
+fn test_005_client_pause_rejects_invalid_mode() {
+    let (tx, _rx) = mpsc::channel();
+    let client_id = 1;
+    let cmd = vec![
+        RespType::RBulkString("client".into()),
+        RespType::RBulkString("pause".into()),
+        RespType::RBulkString("100".into()),
+        RespType::RBulkString("read".into()),
+    ];
+
+    assert_eq!(
+        client(&cmd, &tx, client_id),
+        RespType::RError(String::from("ERR syntax error"))
+    );
+}
+
+#[test]
+fn test_006_auth_with_wrong_password_fails() {
+    let (tx, rx) = mpsc::channel();
+    let client_id = 1;
+    let cmd = vec![
+        RespType::RBulkString("auth".into()),
+        RespType::RBulkString("worker".into()),
+        RespType::RBulkString("otra".into()),
+    ];
+
+    let handle = std::thread::spawn(move || {
+        let mut session = Session::new(false);
+        let res = auth(&cmd, &tx, client_id, &mut session);
+        (res, session.is_authenticated())
+    });
+    match rx.recv().unwrap() {
+        WorkerMessage::AclAuthenticate(_, username, password, sender) => {
+            assert_eq!((username.as_str(), password.as_str()), ("worker", "otra"));
+            sender.send(false).unwrap();
+        }
+        _ => panic!("Unexpected message"),
+    }
+    let (res, authenticated) = handle.join().unwrap();
+    assert!(matches!(res, RespType::RError(e) if e.starts_with("WRONGPASS")));
+    assert!(!authenticated);
+}
+
+#[test]
+fn test_007_reset_requires_authentication_again_when_default_user_has_password() {
+    let (tx, rx) = mpsc::channel();
+    let client_id = 1;
+
+    let handle = std::thread::spawn(move || {
+        let mut session = Session::new(true);
+        session.set_protocol(RESP3);
+        reset(&tx, client_id, &mut session);
+        session
+    });
+    assert!(matches!(rx.recv().unwrap(), WorkerMessage::ResetClient(_)));
+    match rx.recv().unwrap() {
+        WorkerMessage::AclNoPass(username, sender) => {
+            assert_eq!(username, DEFAULT_USER);
+            sender.send(false).unwrap();
+        }
+        _ => panic!("Unexpected message"),
+    }
+    let session = handle.join().unwrap();
+    assert!(!session.is_authenticated());
+    assert_eq!(session.get_protocol(), 2);
+}
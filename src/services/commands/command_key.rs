@@ -24,20 +24,20 @@ use std::time::SystemTime;
 /// # let db = Database::new("dummy_db_del.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
-/// database.write().unwrap().add("fruta".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("pera"))
+/// database.write().unwrap().add(b"fruta".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("pera".into())
 /// ).build());
-/// database.write().unwrap().add("verdura".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("lechuga"))
+/// database.write().unwrap().add(b"verdura".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("lechuga".into())
 /// ).build());
-/// database.write().unwrap().add("postre".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("helado"))
+/// database.write().unwrap().add(b"postre".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("helado".into())
 /// ).build());
 ///
 /// let res = command_key::del(&vec![
-///     RespType::RBulkString("DEL".to_string()),
-///     RespType::RBulkString("fruta".to_string()),
-///     RespType::RBulkString("verdura".to_string()),
+///     RespType::RBulkString("DEL".into()),
+///     RespType::RBulkString("fruta".into()),
+///     RespType::RBulkString("verdura".into()),
 ///     ], &database);
 ///
 /// # match res {
@@ -51,11 +51,11 @@ use std::time::SystemTime;
 pub fn del(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     let mut n_key_deleted = 0;
     for n in cmd.iter().skip(1) {
-        if let RespType::RBulkString(current_key) = n {
+        if let Some(current_key) = n.as_bytes() {
             let mut new_database = database
                 .write()
                 .expect("Could not get database lock on del");
-            if new_database.delete_key(current_key.to_string()) {
+            if new_database.delete_key(current_key.to_vec()) {
                 n_key_deleted += 1;
             }
         }
@@ -82,14 +82,14 @@ pub fn del(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let db = Database::new("dummy_db_copy.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
-/// database.write().unwrap().add("dolly".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("sheep"))
+/// database.write().unwrap().add(b"dolly".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("sheep".into())
 /// ).build());
 ///
 /// let res = command_key::copy(&vec![
-///     RespType::RBulkString("COPY".to_string()),
-///     RespType::RBulkString("dolly".to_string()),
-///     RespType::RBulkString("clone".to_string()),
+///     RespType::RBulkString("COPY".into()),
+///     RespType::RBulkString("dolly".into()),
+///     RespType::RBulkString("clone".into()),
 ///     ], &database);
 ///
 /// # match res {
@@ -99,15 +99,15 @@ pub fn del(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// #    _ => assert!(false)
 /// # }
 ///
-/// database.write().unwrap().add("pet".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("cat"))
+/// database.write().unwrap().add(b"pet".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("cat".into())
 /// ).build());
 ///
 /// let res = command_key::copy(&vec![
-///     RespType::RBulkString("COPY".to_string()),
-///     RespType::RBulkString("pet".to_string()),
-///     RespType::RBulkString("clone".to_string()),
-///     RespType::RBulkString("replace".to_string()),
+///     RespType::RBulkString("COPY".into()),
+///     RespType::RBulkString("pet".into()),
+///     RespType::RBulkString("clone".into()),
+///     RespType::RBulkString("replace".into()),
 ///     ], &database);
 ///
 /// # match res {
@@ -120,13 +120,13 @@ pub fn del(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// ```
 pub fn copy(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() > 2 {
-        if let RespType::RBulkString(source) = &cmd[1] {
-            if let RespType::RBulkString(destination) = &cmd[2] {
+        if let Some(source) = cmd[1].as_bytes() {
+            if let Some(destination) = cmd[2].as_bytes() {
                 let mut db = database
                     .write()
                     .expect("Could not get database lock on copy");
                 let replace = copy_should_replace(cmd);
-                match db.copy(source.to_string(), destination.to_string(), replace) {
+                match db.copy(source.to_vec(), destination.to_vec(), replace) {
                     Some(_) => {
                         return RespType::RInteger(1);
                     }
@@ -150,18 +150,18 @@ pub fn copy(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 ///
 /// let replace = command_key::copy_should_replace(&vec![
-///     RespType::RBulkString("COPY".to_string()),
-///     RespType::RBulkString("pet".to_string()),
-///     RespType::RBulkString("clone".to_string()),
-///     RespType::RBulkString("replace".to_string()),
+///     RespType::RBulkString("COPY".into()),
+///     RespType::RBulkString("pet".into()),
+///     RespType::RBulkString("clone".into()),
+///     RespType::RBulkString("replace".into()),
 ///     ]);
 ///
 /// assert!(replace);
 /// ```
 pub fn copy_should_replace(cmd: &[RespType]) -> bool {
     if cmd.len() == 4 {
-        if let RespType::RBulkString(replace) = &cmd[3] {
-            if replace == "replace" {
+        if let Some(replace) = cmd[3].as_str() {
+            if replace.eq_ignore_ascii_case("replace") {
                 return true;
             }
         }
@@ -185,17 +185,17 @@ pub fn copy_should_replace(cmd: &[RespType]) -> bool {
 /// # let db = Database::new("dummy_db_exists.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
-/// database.write().unwrap().add("fruta".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("pera"))
+/// database.write().unwrap().add(b"fruta".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("pera".into())
 /// ).build());
-/// database.write().unwrap().add("verdura".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("lechuga"))
+/// database.write().unwrap().add(b"verdura".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("lechuga".into())
 /// ).build());
 ///
 /// let res = command_key::exists(&vec![
-///     RespType::RBulkString("EXISTS".to_string()),    
-///     RespType::RBulkString("fruta".to_string()),
-///     RespType::RBulkString("verdura".to_string()),
+///     RespType::RBulkString("EXISTS".into()),    
+///     RespType::RBulkString("fruta".into()),
+///     RespType::RBulkString("verdura".into()),
 ///     ], &database);
 ///
 /// # match res {
@@ -210,11 +210,11 @@ pub fn exists(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     let mut key_found = 0;
     if cmd.len() > 1 {
         for n in cmd.iter().skip(1) {
-            if let RespType::RBulkString(current_key) = n {
+            if let Some(current_key) = n.as_bytes() {
                 let (exist, expired) = database
                     .read()
                     .expect("Could not get database read lock on exists")
-                    .key_exists_expired(current_key.to_string());
+                    .key_exists_expired(current_key.to_vec());
                 if exist {
                     if !expired {
                         key_found += 1;
@@ -248,12 +248,12 @@ pub fn exists(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let db = Database::new("dummy_db_persist.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
-/// database.write().unwrap().add("fruta".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("pera"))).with_timeout(1925487534).build());
+/// database.write().unwrap().add(b"fruta".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("pera".into())).with_timeout(1925487534).build());
 ///
 /// let res = command_key::persist(&vec![
-///     RespType::RBulkString("PERSIST".to_string()),
-///     RespType::RBulkString("fruta".to_string()),
+///     RespType::RBulkString("PERSIST".into()),
+///     RespType::RBulkString("fruta".into()),
 ///     ], &database);
 ///
 /// # match res {
@@ -265,11 +265,11 @@ pub fn exists(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let _ = std::fs::remove_file("dummy_db_persist.csv");
 /// ```
 pub fn persist(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
-    if let RespType::RBulkString(key) = &cmd[1] {
+    if let Some(key) = cmd[1].as_bytes() {
         if database
             .write()
             .expect("Could not get database write lock on persist")
-            .persist(key.to_string())
+            .persist(key.to_vec())
         {
             return RespType::RInteger(1);
         } else {
@@ -296,19 +296,19 @@ pub fn persist(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let db = Database::new("dummy_db_rename.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
-/// database.write().unwrap().add("animal".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("perro"))
+/// database.write().unwrap().add(b"animal".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("perro".into())
 /// ).build());
 ///
 /// let res = command_key::rename(&vec![
-///     RespType::RBulkString("RENAME".to_string()),
-///     RespType::RBulkString("animal".to_string()),
-///     RespType::RBulkString("mascota".to_string()),
+///     RespType::RBulkString("RENAME".into()),
+///     RespType::RBulkString("animal".into()),
+///     RespType::RBulkString("mascota".into()),
 ///     ], &database);
 ///
 /// # match res {
 /// #    RespType::RBulkString(response) => {
-///         assert_eq!(response, "OK".to_string())
+///         assert_eq!(response, b"OK")
 /// #    }
 /// #    _ => assert!(false)
 /// # }
@@ -316,20 +316,20 @@ pub fn persist(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// ```
 pub fn rename(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() > 1 {
-        if let RespType::RBulkString(current_key) = &cmd[1] {
+        if let Some(current_key) = cmd[1].as_bytes() {
             let mut new_database = database
                 .write()
                 .expect("Could not get database write lock on rename");
-            if let RespType::RBulkString(new_key) = &cmd[2] {
-                if new_database.rename_key(current_key.to_string(), new_key.to_string()) {
-                    return RespType::RBulkString("OK".to_string());
+            if let Some(new_key) = cmd[2].as_bytes() {
+                if new_database.rename_key(current_key.to_vec(), new_key.to_vec()) {
+                    return RespType::RBulkString("OK".into());
                 } else {
                     return RespType::RError("key not found".to_string());
                 }
             }
         }
     }
-    RespType::RBulkString("missing parameters".to_string())
+    RespType::RBulkString("missing parameters".into())
 }
 
 /// Configura un tiempo de expiracion sobre una clave a partir del momento en que se envia el comando.
@@ -350,14 +350,14 @@ pub fn rename(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let db = Database::new("dummy_db_expire.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
-/// database.write().unwrap().add("fruta".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("pera"))
+/// database.write().unwrap().add(b"fruta".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("pera".into())
 /// ).build());
 ///
 /// let res = command_key::expire(&vec![
-///     RespType::RBulkString("EXPIRE".to_string()),
-///     RespType::RBulkString("fruta".to_string()),
-///     RespType::RBulkString("30".to_string()),
+///     RespType::RBulkString("EXPIRE".into()),
+///     RespType::RBulkString("fruta".into()),
+///     RespType::RBulkString("30".into()),
 ///     ], &database);
 ///
 /// # match res {
@@ -370,11 +370,11 @@ pub fn rename(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// ```
 pub fn expire(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() != 3 {
-    } else if let RespType::RBulkString(key) = &cmd[1] {
+    } else if let Some(key) = cmd[1].as_bytes() {
         let mut db = database
             .write()
             .expect("Could not get database write lock on expire");
-        if let RespType::RBulkString(timeout) = &cmd[2] {
+        if let Some(timeout) = cmd[2].as_str() {
             let now = SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .unwrap();
@@ -405,14 +405,14 @@ pub fn expire(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let db = Database::new("dummy_db_expireat.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
-/// database.write().unwrap().add("fruta".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("pera"))
+/// database.write().unwrap().add(b"fruta".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("pera".into())
 /// ).build());
 ///
 /// let res = command_key::expireat(&vec![
-///     RespType::RBulkString("EXPIREAT".to_string()),
-///     RespType::RBulkString("fruta".to_string()),
-///     RespType::RBulkString("1925487534".to_string()),
+///     RespType::RBulkString("EXPIREAT".into()),
+///     RespType::RBulkString("fruta".into()),
+///     RespType::RBulkString("1925487534".into()),
 ///     ], &database);
 ///
 /// # match res {
@@ -425,11 +425,11 @@ pub fn expire(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// ```
 pub fn expireat(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() != 3 {
-    } else if let RespType::RBulkString(key) = &cmd[1] {
+    } else if let Some(key) = cmd[1].as_bytes() {
         let mut db = database
             .write()
             .expect("Could not get database write lock on expireat");
-        if let RespType::RBulkString(timeout) = &cmd[2] {
+        if let Some(timeout) = cmd[2].as_str() {
             let result = db.expire_key(key, timeout);
             if result {
                 return RespType::RInteger(1);
@@ -448,7 +448,7 @@ pub fn expireat(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType
 /// * ASC | DESC: Ordena de menor a mayor (asc) o de mayor a menor (desc).
 /// * ALPHA: Ordena alfabeticamente.
 /// * LIMIT lower count: Limita la cantidad de elementos. Toma `count` elementos desde la posicion `lower`.
-/// Si alguno de los límites no puede representarse con un número entero positivo, se asignan como default 0 para límite inferior y el largo del vector para límite superior.
+///   Si alguno de los límites no puede representarse con un número entero positivo, se asignan como default 0 para límite inferior y el largo del vector para límite superior.
 /// * BY pattern: Permite ordenar a partir de claves externas y sus valores asociados.
 ///
 /// Devuelve una lista con los elementos ordenados. Si se especifica el parámetro `store`, devuelve la cantidad de elementos ordenados y almacenados en la nueva clave.
@@ -464,18 +464,18 @@ pub fn expireat(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType
 /// # let db = Database::new("dummy_db_sort.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec![String::from("pera"), String::from("manzana"), String::from("sandia")])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"pera".to_vec(), b"manzana".to_vec(), b"sandia".to_vec()].into())
 /// ).build());
 ///
 /// let res = command_key::sort(&vec![
-///     RespType::RBulkString("SORT".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
+///     RespType::RBulkString("SORT".into()),
+///     RespType::RBulkString("frutas".into()),
 ///     ], &database);
 ///
 /// # match res {
 /// #    RespType::RArray(sorted) => {
-///         assert_eq!(sorted, vec![RespType::RBulkString("manzana".to_string()), RespType::RBulkString("pera".to_string()), RespType::RBulkString("sandia".to_string())])
+///         assert_eq!(sorted, vec![RespType::RBulkString("manzana".into()), RespType::RBulkString("pera".into()), RespType::RBulkString("sandia".into())])
 /// #    }
 /// #    _ => assert!(false)
 /// # }
@@ -483,15 +483,15 @@ pub fn expireat(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType
 /// ```
 pub fn sort(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     let parameters = generate_hashmap(cmd);
-    if let RespType::RBulkString(key) = &cmd[1] {
+    if let Some(key) = cmd[1].as_bytes() {
         let db = database
             .read()
             .expect("Could not get database read lock on sort");
-        let mut sorted: Vec<String> = Vec::new();
+        let mut sorted: Vec<Vec<u8>> = Vec::new();
         if parameters.contains_key("by") {
-            if let RespType::RBulkString(pattern) = parameters.get("by").unwrap() {
+            if let Some(pattern) = parameters.get("by").unwrap().as_bytes() {
                 let (mut elements_to_sort, expired) =
-                    db.get_values_of_keys_matching_pattern(pattern.to_string(), key.to_string());
+                    db.get_values_of_keys_matching_pattern(pattern, key);
                 if !expired.is_empty() {
                     drop(db);
                     for v in &expired {
@@ -519,8 +519,8 @@ pub fn sort(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
             }
         }
         if (parameters.contains_key("lower")) && (parameters.contains_key("upper")) {
-            if let RespType::RBulkString(lower_bound) = parameters.get("lower").unwrap() {
-                if let RespType::RBulkString(upper_bound) = parameters.get("upper").unwrap() {
+            if let Some(lower_bound) = parameters.get("lower").unwrap().as_str() {
+                if let Some(upper_bound) = parameters.get("upper").unwrap().as_str() {
                     let min = lower_bound.parse::<usize>().unwrap_or(0);
                     let max = upper_bound.parse::<usize>().unwrap_or(sorted.len());
                     sorted = sorted[min..max].to_vec();
@@ -530,7 +530,7 @@ pub fn sort(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
         RespType::RArray(
             sorted
                 .iter()
-                .map(|e| RespType::RBulkString(e.to_string()))
+                .map(|e| RespType::RBulkString(e.to_vec()))
                 .collect(),
         )
     } else {
@@ -554,52 +554,52 @@ pub fn sort(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # use std::collections::HashMap;
 ///
 /// let cmd = vec![
-///     RespType::RBulkString("SORT".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("ASC".to_string()),
+///     RespType::RBulkString("SORT".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("ASC".into()),
 ///     ];
 ///
 /// let asc_map = command_key::generate_hashmap(&cmd);
 /// let mut map = HashMap::new();
-/// let key = RespType::RBulkString(String::from("frutas"));
+/// let key = RespType::RBulkString("frutas".into());
 /// map.insert(String::from("key"), &key);
 /// map.insert(String::from("asc"), &RespType::RInteger(1));
 /// assert_eq!(asc_map, map);
 ///
 /// let cmd = vec![
-///     RespType::RBulkString("SORT".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("BY".to_string()),
-///     RespType::RBulkString("max*".to_string()),
+///     RespType::RBulkString("SORT".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("BY".into()),
+///     RespType::RBulkString("max*".into()),
 ///     ];
 ///
 /// let by_map = command_key::generate_hashmap(&cmd);
 /// let mut map = HashMap::new();
-/// let key = RespType::RBulkString(String::from("frutas"));
+/// let key = RespType::RBulkString("frutas".into());
 /// map.insert(String::from("key"), &key);
-/// let by = RespType::RBulkString(String::from("max*"));
+/// let by = RespType::RBulkString("max*".into());
 /// map.insert(String::from("by"), &by);
 /// assert_eq!(by_map, map);
 ///
 /// let cmd = vec![
-///     RespType::RBulkString("SORT".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("BY".to_string()),
-///     RespType::RBulkString("max*".to_string()),
-///     RespType::RBulkString("LIMIT".to_string()),
-///     RespType::RBulkString("0".to_string()),
-///     RespType::RBulkString("10".to_string()),
+///     RespType::RBulkString("SORT".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("BY".into()),
+///     RespType::RBulkString("max*".into()),
+///     RespType::RBulkString("LIMIT".into()),
+///     RespType::RBulkString("0".into()),
+///     RespType::RBulkString("10".into()),
 ///     ];
 ///
 /// let multi_map = command_key::generate_hashmap(&cmd);
 /// let mut map = HashMap::new();
-/// let key = RespType::RBulkString(String::from("frutas"));
+/// let key = RespType::RBulkString("frutas".into());
 /// map.insert(String::from("key"), &key);
-/// let by = RespType::RBulkString(String::from("max*"));
+/// let by = RespType::RBulkString("max*".into());
 /// map.insert(String::from("by"), &by);
-/// let lower = RespType::RBulkString(String::from("0"));
+/// let lower = RespType::RBulkString("0".into());
 /// map.insert(String::from("lower"), &lower);
-/// let upper = RespType::RBulkString(String::from("10"));
+/// let upper = RespType::RBulkString("10".into());
 /// map.insert(String::from("upper"), &upper);
 /// assert_eq!(multi_map, map);
 /// ```
@@ -607,22 +607,20 @@ pub fn generate_hashmap(cmd: &[RespType]) -> HashMap<String, &RespType> {
     let mut aux_hash_map = HashMap::new();
     let mut pos = 1;
     while pos < cmd.len() {
-        if let RespType::RBulkString(arg) = &cmd[pos] {
-            let arg = arg.to_lowercase();
-            if (arg == "asc") || (arg == "desc") || (arg == "alpha") {
-                aux_hash_map.insert(arg.to_string(), &RespType::RInteger(1));
-                pos += 1;
-            } else if (arg == "by") || (arg == "store") {
-                aux_hash_map.insert(arg.to_string(), &cmd[pos + 1]);
-                pos += 2;
-            } else if arg == "limit" {
-                aux_hash_map.insert("lower".to_string(), &cmd[pos + 1]);
-                aux_hash_map.insert("upper".to_string(), &cmd[pos + 2]);
-                pos += 3;
-            } else {
-                aux_hash_map.insert("key".to_string(), &cmd[pos]);
-                pos += 1;
-            }
+        let arg = cmd[pos].as_str().unwrap_or_default().to_lowercase();
+        if (arg == "asc") || (arg == "desc") || (arg == "alpha") {
+            aux_hash_map.insert(arg.to_string(), &RespType::RInteger(1));
+            pos += 1;
+        } else if (arg == "by") || (arg == "store") {
+            aux_hash_map.insert(arg.to_string(), &cmd[pos + 1]);
+            pos += 2;
+        } else if arg == "limit" {
+            aux_hash_map.insert("lower".to_string(), &cmd[pos + 1]);
+            aux_hash_map.insert("upper".to_string(), &cmd[pos + 2]);
+            pos += 3;
+        } else {
+            aux_hash_map.insert("key".to_string(), &cmd[pos]);
+            pos += 1;
         }
     }
     aux_hash_map
@@ -655,43 +653,43 @@ pub fn generate_hashmap(cmd: &[RespType]) -> HashMap<String, &RespType> {
 /// # let db = Database::new("dummy_db_keys.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
-///  database.write().unwrap().add("animal".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("oso"))
+///  database.write().unwrap().add(b"animal".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("oso".into())
 ///  ).build());
-///  database.write().unwrap().add("animacion".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("soul"))
+///  database.write().unwrap().add(b"animacion".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("soul".into())
 ///  ).build());
-///  database.write().unwrap().add("comida".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("pizza"))
+///  database.write().unwrap().add(b"comida".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("pizza".into())
 ///  ).build());
 ///
 /// let res = command_key::keys(&vec![
-///     RespType::RBulkString("KEYS".to_string()),
-///     RespType::RBulkString("anima*".to_string()),
+///     RespType::RBulkString("KEYS".into()),
+///     RespType::RBulkString("anima*".into()),
 ///     ], &database);
 ///
 /// # match res {
 /// #    RespType::RArray(matched) => {
-///         assert!(matched.contains(&RespType::RBulkString("animal".to_string())));
-///         assert!(matched.contains(&RespType::RBulkString("animacion".to_string())));
+///         assert!(matched.contains(&RespType::RBulkString("animal".into())));
+///         assert!(matched.contains(&RespType::RBulkString("animacion".into())));
 /// #    }
 /// #    _ => assert!(false)
 /// # }
 /// # let _ = std::fs::remove_file("dummy_db_keys.csv");
 /// ```
 pub fn keys(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
-    if let RespType::RBulkString(pattern) = &cmd[1] {
+    if let Some(pattern) = cmd[1].as_bytes() {
         let db = database
             .read()
             .expect("Could not get database lock on keys");
         let matching_keys = db.get_keys_that_match_pattern(pattern);
         let vec = matching_keys
             .iter()
-            .map(|k| RespType::RBulkString(k.to_string()))
+            .map(|k| RespType::RBulkString(k.to_vec()))
             .collect();
         RespType::RArray(vec)
     } else {
-        RespType::RBulkString("No matching keys".to_string())
+        RespType::RBulkString("No matching keys".into())
     }
 }
 /// Actualiza el `last_access_time` de las keys recibidas.
@@ -722,19 +720,19 @@ pub fn keys(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///   .unwrap().as_secs();
 /// timeout_10seg += 10;
 ///
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string()])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
 /// ).build());
 ///
-/// database.write().unwrap().add("verduras".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["acelga".to_string(),"cebolla".to_string(),"zanahoria".to_string()])).with_timeout(timeout_10seg).build()
+/// database.write().unwrap().add(b"verduras".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"acelga".to_vec(),b"cebolla".to_vec(),b"zanahoria".to_vec()].into())).with_timeout(timeout_10seg).build()
 /// );
 ///
 /// //Ejecuto el comando con los parámetros necesarios:
 /// let res = command_key::touch(&vec![
-///     RespType::RBulkString("TOUCH".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("verduras".to_string())
+///     RespType::RBulkString("TOUCH".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("verduras".into())
 /// ], &database);
 ///
 /// # match res {
@@ -765,15 +763,15 @@ pub fn keys(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// sleep(Duration::from_secs(1));
 ///
-/// database.write().unwrap().add("verduras".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["acelga".to_string(),"cebolla".to_string(),"zanahoria".to_string()])).with_timeout(timeout_now).build()
+/// database.write().unwrap().add(b"verduras".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"acelga".to_vec(),b"cebolla".to_vec(),b"zanahoria".to_vec()].into())).with_timeout(timeout_now).build()
 /// );
 ///
 /// //Ejecuto el comando con los parámetros necesarios:
 /// let res = command_key::touch(&vec![
-///     RespType::RBulkString("TOUCH".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("verduras".to_string())
+///     RespType::RBulkString("TOUCH".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("verduras".into())
 /// ], &database);
 ///
 /// # match res {
@@ -792,8 +790,8 @@ pub fn touch(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
         .expect("Could not get database lock on touch");
     if cmd.len() > 1 {
         for n in cmd.iter().skip(1) {
-            if let RespType::RBulkString(current_key) = n {
-                if db.reboot_time(current_key.to_string()).is_some() {
+            if let Some(current_key) = n.as_bytes() {
+                if db.reboot_time(current_key.to_vec()).is_some() {
                     number_of_touched_keys += 1
                 }
             }
@@ -816,12 +814,12 @@ pub fn touch(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let db = Database::new("dummy_db_ttl.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
-/// database.write().unwrap().add("fruta".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("pera"))).with_timeout(1925487534).build());
+/// database.write().unwrap().add(b"fruta".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("pera".into())).with_timeout(1925487534).build());
 ///
 /// let res = command_key::get_ttl(&vec![
-///     RespType::RBulkString("TTL".to_string()),
-///     RespType::RBulkString("fruta".to_string())
+///     RespType::RBulkString("TTL".into()),
+///     RespType::RBulkString("fruta".into())
 ///     ], &database);
 ///
 /// # match res {
@@ -834,7 +832,7 @@ pub fn touch(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// ```
 pub fn get_ttl(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() > 1 {
-        if let RespType::RBulkString(key) = &cmd[1] {
+        if let Some(key) = cmd[1].as_bytes() {
             let db = database
                 .read()
                 .expect("Could not get database read lock on ttl");
@@ -875,18 +873,18 @@ pub fn get_ttl(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let db = Database::new("dummy_db_type.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
-/// database.write().unwrap().add("fruta".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType(String::from("pera"))
+/// database.write().unwrap().add(b"fruta".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("pera".into())
 /// ).build());
 ///
 /// let res = command_key::get_type(&vec![
-///     RespType::RBulkString("TYPE".to_string()),
-///     RespType::RBulkString("fruta".to_string())
+///     RespType::RBulkString("TYPE".into()),
+///     RespType::RBulkString("fruta".into())
 ///     ], &database);
 ///
 /// # match res {
 /// #    RespType::RBulkString(value_type) => {
-///         assert_eq!(value_type, "string".to_string())
+///         assert_eq!(value_type, b"string")
 /// #    }
 /// #    _ => assert!(false)
 /// # }
@@ -894,11 +892,11 @@ pub fn get_ttl(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// ```
 pub fn get_type(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     let mut tipo = String::from("");
-    if let RespType::RBulkString(current_key) = &cmd[1] {
+    if let Some(current_key) = cmd[1].as_bytes() {
         let (exist, expired) = database
             .read()
             .unwrap()
-            .key_exists_expired(current_key.to_string());
+            .key_exists_expired(current_key.to_vec());
         if exist {
             if expired {
                 database.write().unwrap().remove_expired_key(current_key)
@@ -906,9 +904,9 @@ pub fn get_type(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType
                 tipo = database
                     .read()
                     .unwrap()
-                    .get_type_of_value(current_key.to_string());
+                    .get_type_of_value(current_key.to_vec());
             }
         }
     }
-    RespType::RBulkString(tipo)
+    RespType::RBulkString(tipo.into())
 }
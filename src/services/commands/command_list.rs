@@ -2,10 +2,9 @@
 
 use crate::domain::entities::key_value_item::ValueType;
 use crate::domain::implementations::database::Database;
-use crate::errors::database_error::DatabaseError;
+use crate::errors::database_error::{DatabaseError, WRONGTYPE};
 use crate::services::utils::resp_type::RespType;
 use std::sync::{Arc, RwLock};
-use std::usize;
 
 /// Retorna la longitud de la lista almacenada en la clave especificada.
 ///
@@ -21,13 +20,13 @@ use std::usize;
 ///
 /// # let db = Database::new("dummy_db_llen.csv".to_string());
 /// let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string()])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
 /// ).build());
 ///
 /// let res = command_list::llen(&vec![
-///     RespType::RBulkString("LLEN".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
+///     RespType::RBulkString("LLEN".into()),
+///     RespType::RBulkString("frutas".into()),
 /// ], &database);
 ///
 /// # match res {
@@ -42,13 +41,13 @@ pub fn llen(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     let new_database = database
         .read()
         .expect("Could not get database lock on llen");
-    if let RespType::RBulkString(key) = &cmd[1] {
+    if let Some(key) = cmd[1].as_bytes() {
         if let (Some(item), false) = new_database.check_timeout_item(key) {
             if let ValueType::ListType(current_value) = item.get_value().to_owned() {
                 let list_size = current_value.len();
                 RespType::RInteger(list_size)
             } else {
-                RespType::RError("Not list type".to_string())
+                RespType::RError(WRONGTYPE.to_string())
             }
         } else {
             if let (Some(_), true) = new_database.check_timeout_item(key) {
@@ -82,19 +81,19 @@ pub fn llen(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_lpop.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string(), "melon".to_string(), "ciruela".to_string()])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec(), b"melon".to_vec(), b"ciruela".to_vec()].into())
 /// ).build());
 ///
 /// let res = command_list::lpop(&vec![
-///     RespType::RBulkString("LPOP".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("3".to_string())
+///     RespType::RBulkString("LPOP".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("3".into())
 /// ], &database);
 ///
 /// # match res {
 /// #    RespType::RArray(frutas_eliminadas) => {
-///     assert_eq!(frutas_eliminadas, vec![RespType::RBulkString("kiwi".to_string()),RespType::RBulkString("pomelo".to_string()), RespType::RBulkString("sandia".to_string())])
+///     assert_eq!(frutas_eliminadas, vec![RespType::RBulkString("kiwi".into()),RespType::RBulkString("pomelo".into()), RespType::RBulkString("sandia".into())])
 /// # }
 /// #    _ => assert!(false)
 /// # }
@@ -105,16 +104,16 @@ pub fn lpop(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     let mut db = database
         .write()
         .expect("Could not get database lock on lpop");
-    if let RespType::RBulkString(key) = &cmd[1] {
+    if let Some(key) = cmd[1].as_bytes() {
         if cmd.len() == 3 {
-            if let RespType::RBulkString(cantidad) = &cmd[2] {
+            if let Some(cantidad) = cmd[2].as_str() {
                 let popped_elements =
                     db.pop_elements_from_list(key, cantidad.parse::<usize>().unwrap_or(1));
                 if let Some(popped) = popped_elements {
                     return RespType::RArray(
                         popped
                             .iter()
-                            .map(|element| RespType::RBulkString(element.to_string()))
+                            .map(|element| RespType::RBulkString(element.to_vec()))
                             .collect(),
                     );
                 } else {
@@ -124,7 +123,7 @@ pub fn lpop(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
         } else {
             let popped_elements = db.pop_elements_from_list(key, 1);
             if popped_elements.is_none() {
-                return RespType::RBulkString("()".to_string());
+                return RespType::RBulkString("()".into());
             }
             if let Some(popped_element) = popped_elements {
                 if !popped_element.is_empty() {
@@ -154,15 +153,15 @@ pub fn lpop(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_push.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string()])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
 /// ).build());
 ///
 /// let res = command_list::push(&vec![
-///     RespType::RBulkString("LPUSH".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("frutilla".to_string()),
-///     RespType::RBulkString("melon".to_string()),
+///     RespType::RBulkString("LPUSH".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("frutilla".into()),
+///     RespType::RBulkString("melon".into()),
 /// ], &database, true);
 ///
 /// # match res {
@@ -174,28 +173,36 @@ pub fn lpop(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let _ = std::fs::remove_file("dummy_db_push.csv");
 /// ```
 pub fn push(cmd: &[RespType], database: &Arc<RwLock<Database>>, is_reverse: bool) -> RespType {
+    if cmd.len() < 3 {
+        return wrong_number_of_arguments(if is_reverse { "lpush" } else { "rpush" });
+    }
     let mut new_database = database
         .write()
         .expect("Could not get database lock on push");
     let mut vec_aux = vec![];
-    if let RespType::RBulkString(key) = &cmd[1] {
+    if let Some(key) = cmd[1].as_bytes() {
         if is_reverse {
             for n in cmd.iter().skip(2).rev() {
-                if let RespType::RBulkString(value) = n {
-                    vec_aux.push(value.to_string());
+                if let Some(value) = n.as_bytes() {
+                    vec_aux.push(value.to_vec());
                 }
             }
         } else {
             for n in cmd.iter().skip(2) {
-                if let RespType::RBulkString(value) = n {
-                    vec_aux.push(value.to_string());
+                if let Some(value) = n.as_bytes() {
+                    vec_aux.push(value.to_vec());
                 }
             }
         }
-        if let Some(resultado) = new_database.add_to_list_type(vec_aux, key, false) {
+        let resultado = if is_reverse {
+            new_database.add_to_list_type(vec_aux, key, false)
+        } else {
+            new_database.append_to_list_type(vec_aux, key, false)
+        };
+        if let Some(resultado) = resultado {
             RespType::RInteger(resultado)
         } else {
-            RespType::RError("error - not list type".to_string())
+            RespType::RError(WRONGTYPE.to_string())
         }
     } else {
         RespType::RError("Invalid request".to_string())
@@ -206,7 +213,8 @@ pub fn push(cmd: &[RespType], database: &Arc<RwLock<Database>>, is_reverse: bool
 ///
 /// Si la clave existe y guarda un elemento de tipo lista, inserta los elementos al comienzo de la misma.
 /// Retorna un valor de tipo entero que representa la longitud de la lista luego de haber insertado los nuevos elementos.
-/// Si la clave no es de tipo lista, devuelve 0.
+/// Si la clave no existe, devuelve 0.
+/// Si la clave no es de tipo lista, devuelve Error.
 /// Ante un error inesperado, devuelve Error `Invalid request`.
 ///
 /// # Ejemplos
@@ -220,15 +228,15 @@ pub fn push(cmd: &[RespType], database: &Arc<RwLock<Database>>, is_reverse: bool
 ///
 /// # let db = Database::new("dummy_db_lpushx.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string()]),
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into()),
 /// ).build());
 ///
 /// let res = command_list::lpushx(&vec![
-///     RespType::RBulkString("LPUSHX".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("melon".to_string()),
-///     RespType::RBulkString("sandia".to_string())
+///     RespType::RBulkString("LPUSHX".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("melon".into()),
+///     RespType::RBulkString("sandia".into())
 /// ], &database);
 ///
 /// # match res {
@@ -241,14 +249,20 @@ pub fn push(cmd: &[RespType], database: &Arc<RwLock<Database>>, is_reverse: bool
 /// # let _ = std::fs::remove_file("dummy_db_lpushx.csv");
 /// ```
 pub fn lpushx(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() < 3 {
+        return wrong_number_of_arguments("lpushx");
+    }
     let mut new_database = database
         .write()
         .expect("Could not get database lock on lpushx");
     let mut vec_aux = vec![];
-    if let RespType::RBulkString(key) = &cmd[1] {
+    if let Some(key) = cmd[1].as_bytes() {
+        if holds_other_type(&mut new_database, key) {
+            return RespType::RError(WRONGTYPE.to_string());
+        }
         for n in cmd.iter().skip(2).rev() {
-            if let RespType::RBulkString(value) = n {
-                vec_aux.push(value.to_string());
+            if let Some(value) = n.as_bytes() {
+                vec_aux.push(value.to_vec());
             }
         }
         let resultado = new_database
@@ -280,20 +294,20 @@ pub fn lpushx(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_lrange.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string(), "melon".to_string(), "ciruela".to_string()])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec(), b"melon".to_vec(), b"ciruela".to_vec()].into())
 /// ).build());
 ///
 /// let res = command_list::lrange(&vec![
-///     RespType::RBulkString("LRANGE".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("1".to_string()),
-///     RespType::RBulkString("3".to_string())
+///     RespType::RBulkString("LRANGE".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("1".into()),
+///     RespType::RBulkString("3".into())
 /// ], &database);
 ///
 /// # match res {
 /// #    RespType::RArray(frutas_range) => {
-///     assert_eq!(frutas_range, vec![RespType::RBulkString("pomelo".to_string()),RespType::RBulkString("sandia".to_string()), RespType::RBulkString("melon".to_string())])
+///     assert_eq!(frutas_range, vec![RespType::RBulkString("pomelo".into()),RespType::RBulkString("sandia".into()), RespType::RBulkString("melon".into())])
 /// # }
 /// #    _ => assert!(false)
 /// # }
@@ -305,9 +319,9 @@ pub fn lrange(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
         .read()
         .expect("Could not get database lock on lrange");
     if cmd.len() > 3 {
-        if let RespType::RBulkString(key) = &cmd[1] {
-            if let RespType::RBulkString(lower_bound) = &cmd[2] {
-                if let RespType::RBulkString(upper_bound) = &cmd[3] {
+        if let Some(key) = cmd[1].as_bytes() {
+            if let Some(lower_bound) = cmd[2].as_str() {
+                if let Some(upper_bound) = cmd[3].as_str() {
                     if let Some(value_vec) =
                         new_database.get_values_in_range(key, lower_bound, upper_bound)
                     {
@@ -318,8 +332,9 @@ pub fn lrange(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
                         return RespType::RArray(value_vec_resptype);
                     }
                     return RespType::RBulkString(
-                        "error - Expected list. Got another value type or key does not exist"
-                            .to_string(),
+                        ("error - Expected list. Got another value type or key does not exist"
+                            .to_string())
+                        .into(),
                     );
                 }
             }
@@ -355,20 +370,20 @@ pub fn lrange(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_doc1.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string()])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
 /// ).build());
 ///
 /// // key: "frutas , index: "0"
 /// let res = command_list::lindex(&vec![
-///     RespType::RBulkString("LINDEX".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("0".to_string())
+///     RespType::RBulkString("LINDEX".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("0".into())
 /// ], &database);
 ///
 /// # match res {
 /// #    RespType::RBulkString(fruta) => {
-///     assert_eq!(fruta, "kiwi")
+///     assert_eq!(fruta, b"kiwi")
 /// # }
 /// #    _ => assert!(false)
 /// # }
@@ -386,20 +401,20 @@ pub fn lrange(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_doc2.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string()])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
 /// ).build());
 ///
 /// // key: "frutas , index: "-1"
 /// let res = command_list::lindex(&vec![
-///     RespType::RBulkString("LINDEX".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("-1".to_string())
+///     RespType::RBulkString("LINDEX".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("-1".into())
 /// ], &database);
 ///
 /// # match res {
 /// #    RespType::RBulkString(fruta) => {
-/// assert_eq!(fruta, "sandia")
+/// assert_eq!(fruta, b"sandia")
 /// # },
 /// #    _ => assert!(false)
 /// # }
@@ -410,8 +425,8 @@ pub fn lindex(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
         let db = database
             .read()
             .expect("Could not get database read lock on lindex");
-        if let RespType::RBulkString(key) = &cmd[1] {
-            if let RespType::RBulkString(index) = &cmd[2] {
+        if let Some(key) = cmd[1].as_bytes() {
+            if let Some(index) = cmd[2].as_str() {
                 let (item, expire) = db.check_timeout_item(key);
                 if item.is_some() && expire {
                     drop(db);
@@ -467,15 +482,15 @@ pub fn lindex(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_lrem.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string(),"pomelo".to_string(),"sandia".to_string()])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
 /// ).build());
 ///
 /// let res = command_list::lrem(&vec![
-///     RespType::RBulkString("LREM".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("2".to_string()),
-///     RespType::RBulkString("pomelo".to_string())
+///     RespType::RBulkString("LREM".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("2".into()),
+///     RespType::RBulkString("pomelo".into())
 /// ], &database);
 ///
 /// # match res {
@@ -492,13 +507,13 @@ pub fn lrem(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
         let mut db = database
             .write()
             .expect("Could not get database lock on lrem");
-        if let RespType::RBulkString(key) = &cmd[1] {
-            if let RespType::RBulkString(count) = &cmd[2] {
-                if let RespType::RBulkString(element) = &cmd[3] {
+        if let Some(key) = cmd[1].as_bytes() {
+            if let Some(count) = cmd[2].as_str() {
+                if let Some(element) = cmd[3].as_bytes() {
                     RespType::RInteger(db.delete_elements_of_value_list(
                         key,
                         count.to_string(),
-                        element.to_string(),
+                        element,
                     ))
                 } else {
                     RespType::RInteger(0)
@@ -530,20 +545,20 @@ pub fn lrem(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_lset.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string()])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
 /// ).build());
 ///
 /// let res = command_list::lset(&vec![
-///     RespType::RBulkString("LSET".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("0".to_string()),
-///     RespType::RBulkString("manzana".to_string())
+///     RespType::RBulkString("LSET".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("0".into()),
+///     RespType::RBulkString("manzana".into())
 /// ], &database);
 ///
 /// # match res {
 /// #    RespType::RBulkString(response) => {
-///     assert_eq!(response, "Ok".to_string())
+///     assert_eq!(response, b"Ok")
 /// # }
 /// #    _ => assert!(false)
 /// # }
@@ -555,12 +570,12 @@ pub fn lset(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
         let mut db = database
             .write()
             .expect("Could not get database lock on lset");
-        if let RespType::RBulkString(key) = &cmd[1] {
-            if let RespType::RBulkString(index) = &cmd[2] {
-                if let RespType::RBulkString(value) = &cmd[3] {
+        if let Some(key) = cmd[1].as_bytes() {
+            if let Some(index) = cmd[2].as_str() {
+                if let Some(value) = cmd[3].as_bytes() {
                     let succeful_replace = db.replace_element_in_list_type_value(key, value, index);
                     if succeful_replace {
-                        return RespType::RBulkString("Ok".to_string());
+                        return RespType::RBulkString("Ok".into());
                     } else {
                         return RespType::RError("error".to_string());
                     }
@@ -568,7 +583,7 @@ pub fn lset(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
             }
         }
     }
-    RespType::RBulkString("Invalid request".to_string())
+    RespType::RBulkString("Invalid request".into())
 }
 
 /// Elimina y devuelve los últimos elementos de la lista almacenada en `key`.
@@ -591,19 +606,19 @@ pub fn lset(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_rpop_command.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string(), "melon".to_string(), "ciruela".to_string()])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec(), b"melon".to_vec(), b"ciruela".to_vec()].into())
 /// ).build());
 ///
 /// let res = command_list::rpop(&vec![
-///     RespType::RBulkString("RPOP".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("3".to_string())
+///     RespType::RBulkString("RPOP".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("3".into())
 /// ], &database);
 ///
 /// # match res {
 /// #    RespType::RArray(frutas_eliminadas) => {
-///     assert_eq!(frutas_eliminadas, vec![RespType::RBulkString("ciruela".to_string()),RespType::RBulkString("melon".to_string()), RespType::RBulkString("sandia".to_string())])
+///     assert_eq!(frutas_eliminadas, vec![RespType::RBulkString("ciruela".into()),RespType::RBulkString("melon".into()), RespType::RBulkString("sandia".into())])
 /// # }
 /// #    _ => assert!(false)
 /// # }
@@ -614,16 +629,16 @@ pub fn rpop(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     let mut db = database
         .write()
         .expect("Could not get database lock on rpop");
-    if let RespType::RBulkString(key) = &cmd[1] {
+    if let Some(key) = cmd[1].as_bytes() {
         if cmd.len() == 3 {
-            if let RespType::RBulkString(cantidad) = &cmd[2] {
+            if let Some(cantidad) = cmd[2].as_str() {
                 let popped_elements =
                     db.rpop_elements_from_list(key, cantidad.parse::<usize>().unwrap_or(1));
                 if let Some(popped) = popped_elements {
                     return RespType::RArray(
                         popped
                             .iter()
-                            .map(|element| RespType::RBulkString(element.to_string()))
+                            .map(|element| RespType::RBulkString(element.to_vec()))
                             .collect(),
                     );
                 } else {
@@ -648,6 +663,8 @@ pub fn rpop(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// Si la clave existe y guarda un elemento de tipo lista, inserta los elementos al final de la misma.
 /// Retorna un valor de tipo entero que representa la longitud de la lista luego de haber insertado los nuevos elementos.
+/// Si la clave no existe, devuelve 0.
+/// Si la clave no es de tipo lista, devuelve Error.
 /// Ante un error inesperado, devuelve Error `Invalid request`.
 ///
 /// # Ejemplo
@@ -661,15 +678,15 @@ pub fn rpop(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_rpushx_command.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string()])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
 /// ).build());
 ///
 /// let res = command_list::rpushx(&vec![
-///     RespType::RBulkString("RPUSHX".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("melon".to_string()),
-///     RespType::RBulkString("sandia".to_string())
+///     RespType::RBulkString("RPUSHX".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("melon".into()),
+///     RespType::RBulkString("sandia".into())
 /// ], &database);
 ///
 /// # match res {
@@ -682,14 +699,20 @@ pub fn rpop(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let _ = std::fs::remove_file("dummy_db_rpushx_command.csv");
 /// ```
 pub fn rpushx(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() < 3 {
+        return wrong_number_of_arguments("rpushx");
+    }
     let mut new_database = database
         .write()
         .expect("Could not get database lock on rpushx");
     let mut new_elements = vec![];
-    if let RespType::RBulkString(key) = &cmd[1] {
+    if let Some(key) = cmd[1].as_bytes() {
+        if holds_other_type(&mut new_database, key) {
+            return RespType::RError(WRONGTYPE.to_string());
+        }
         for n in cmd.iter().skip(2) {
-            if let RespType::RBulkString(value) = n {
-                new_elements.push(value.to_string());
+            if let Some(value) = n.as_bytes() {
+                new_elements.push(value.to_vec());
             }
         }
         RespType::RInteger(new_database.push_vec_to_list(new_elements, key))
@@ -697,3 +720,478 @@ pub fn rpushx(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
         RespType::RError("Invalid request".to_string())
     }
 }
+
+/// Inserta un elemento antes o después de un elemento de referencia (`pivot`) en la lista almacenada en `key`.
+///
+/// El comando recibe `LINSERT key BEFORE|AFTER pivot element`.
+/// Retorna la longitud de la lista luego de la inserción, -1 si no se encontró el `pivot`
+/// y 0 si la clave no existe.
+/// Si el valor almacenado no es de tipo lista, devuelve error.
+///
+/// # Ejemplo
+///
+/// ```
+/// # use proyecto_taller_1::services::commands::command_list;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_linsert_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"sandia".to_vec()].into())
+/// ).build());
+///
+/// let res = command_list::linsert(&vec![
+///     RespType::RBulkString("LINSERT".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("before".into()),
+///     RespType::RBulkString("sandia".into()),
+///     RespType::RBulkString("pomelo".into())
+/// ], &database);
+///
+/// # match res {
+/// #    RespType::RSignedNumber(len) => {
+///     assert_eq!(len, 3)
+/// # }
+/// #    _ => assert!(false)
+/// # }
+///
+/// # let _ = std::fs::remove_file("dummy_db_linsert_command.csv");
+/// ```
+pub fn linsert(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 5 {
+        if let (Some(key), Some(position), Some(pivot), Some(element)) = (
+            cmd[1].as_bytes(),
+            cmd[2].as_str(),
+            cmd[3].as_bytes(),
+            cmd[4].as_bytes(),
+        ) {
+            let before = match position.to_lowercase().as_str() {
+                "before" => true,
+                "after" => false,
+                _ => return RespType::RError("syntax error".to_string()),
+            };
+            let mut db = database
+                .write()
+                .expect("Could not get database lock on linsert");
+            return match db.insert_element_in_list(key, before, pivot, element) {
+                Ok(len) => RespType::RSignedNumber(len),
+                Err(e) => database_error_to_resp(e),
+            };
+        }
+    }
+    RespType::RError("Invalid request".to_string())
+}
+
+/// Recorta la lista almacenada en `key` para que sólo contenga los elementos del rango especificado.
+///
+/// El comando recibe `LTRIM key start stop`. Tanto `start` como `stop` pueden ser negativos, en cuyo caso
+/// se cuentan desde el final de la lista.
+/// Si el rango resultante está vacío, la clave se elimina.
+/// Retorna "OK" en caso de éxito.
+///
+/// # Ejemplo
+///
+/// ```
+/// # use proyecto_taller_1::services::commands::command_list;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_ltrim_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
+/// ).build());
+///
+/// let res = command_list::ltrim(&vec![
+///     RespType::RBulkString("LTRIM".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("1".into()),
+///     RespType::RBulkString("-1".into())
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RSimpleString("OK".to_string()));
+/// let values = database.read().unwrap().get_values_in_range(b"frutas", "0", "-1").unwrap();
+/// assert_eq!(values, vec![b"pomelo".to_vec(), b"sandia".to_vec()]);
+///
+/// # let _ = std::fs::remove_file("dummy_db_ltrim_command.csv");
+/// ```
+pub fn ltrim(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 4 {
+        if let (Some(key), Some(start), Some(stop)) =
+            (cmd[1].as_bytes(), cmd[2].as_str(), cmd[3].as_str())
+        {
+            let (start, stop) = match (start.parse::<isize>(), stop.parse::<isize>()) {
+                (Ok(start), Ok(stop)) => (start, stop),
+                _ => return RespType::RError(NOT_AN_INTEGER.to_string()),
+            };
+            let mut db = database
+                .write()
+                .expect("Could not get database lock on ltrim");
+            return match db.trim_list(key, start, stop) {
+                Ok(()) => RespType::RSimpleString("OK".to_string()),
+                Err(e) => database_error_to_resp(e),
+            };
+        }
+    }
+    RespType::RError("Invalid request".to_string())
+}
+
+/// Devuelve la posición de los elementos iguales a `element` en la lista almacenada en `key`.
+///
+/// El comando recibe `LPOS key element [RANK rank] [COUNT num-matches] [MAXLEN len]`.
+/// * RANK: indica a partir de qué coincidencia se devuelven posiciones. Si es negativo, la búsqueda
+///   comienza desde el final de la lista.
+/// * COUNT: cantidad de posiciones a devolver. Si es 0, se devuelven todas las coincidencias.
+/// * MAXLEN: cantidad máxima de elementos a comparar.
+///
+/// Sin COUNT, retorna la posición de la coincidencia o `nil` si no hay ninguna.
+/// Con COUNT, retorna un array con las posiciones encontradas.
+///
+/// # Ejemplo
+///
+/// ```
+/// # use proyecto_taller_1::services::commands::command_list;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_lpos_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"kiwi".to_vec()].into())
+/// ).build());
+///
+/// let res = command_list::lpos(&vec![
+///     RespType::RBulkString("LPOS".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("kiwi".into()),
+///     RespType::RBulkString("rank".into()),
+///     RespType::RBulkString("-1".into())
+/// ], &database);
+/// assert_eq!(res, RespType::RInteger(2));
+///
+/// let res = command_list::lpos(&vec![
+///     RespType::RBulkString("LPOS".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("kiwi".into()),
+///     RespType::RBulkString("count".into()),
+///     RespType::RBulkString("0".into())
+/// ], &database);
+/// assert_eq!(res, RespType::RArray(vec![RespType::RInteger(0), RespType::RInteger(2)]));
+///
+/// # let _ = std::fs::remove_file("dummy_db_lpos_command.csv");
+/// ```
+pub fn lpos(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() < 3 || cmd.len().is_multiple_of(2) {
+        return RespType::RError("Invalid request".to_string());
+    }
+    if let (Some(key), Some(element)) = (cmd[1].as_bytes(), cmd[2].as_bytes()) {
+        let mut rank = 1;
+        let mut count = None;
+        let mut maxlen = 0;
+        for option in cmd[3..].chunks(2) {
+            if let (Some(name), Some(value)) = (option[0].as_str(), option[1].as_str()) {
+                match name.to_lowercase().as_str() {
+                    "rank" => match value.parse::<isize>() {
+                        Ok(r) => rank = r,
+                        Err(_) => return RespType::RError(NOT_AN_INTEGER.to_string()),
+                    },
+                    "count" => match value.parse::<usize>() {
+                        Ok(c) => count = Some(c),
+                        Err(_) => return RespType::RError("COUNT can't be negative".to_string()),
+                    },
+                    "maxlen" => match value.parse::<usize>() {
+                        Ok(m) => maxlen = m,
+                        Err(_) => return RespType::RError("MAXLEN can't be negative".to_string()),
+                    },
+                    _ => return RespType::RError("syntax error".to_string()),
+                }
+            }
+        }
+        let mut db = database
+            .write()
+            .expect("Could not get database lock on lpos");
+        return match db.get_positions_in_list(key, element, rank, count.unwrap_or(1), maxlen) {
+            Ok(positions) => match count {
+                Some(_) => RespType::RArray(
+                    positions
+                        .iter()
+                        .map(|position| RespType::RInteger(*position))
+                        .collect(),
+                ),
+                None => match positions.first() {
+                    Some(position) => RespType::RInteger(*position),
+                    None => RespType::RNullBulkString(),
+                },
+            },
+            Err(e) => database_error_to_resp(e),
+        };
+    }
+    RespType::RError("Invalid request".to_string())
+}
+
+/// Elimina un elemento de un extremo de la lista `source` y lo inserta en un extremo de la lista `destination`.
+///
+/// El comando recibe `LMOVE source destination LEFT|RIGHT LEFT|RIGHT`. El primer LEFT|RIGHT indica de qué
+/// extremo de `source` se extrae el elemento y el segundo en qué extremo de `destination` se inserta.
+/// Si `destination` no existe, se crea. Si `source` y `destination` son la misma clave, la lista se rota.
+/// Retorna el elemento movido, o `nil` si `source` no existe.
+///
+/// # Ejemplo
+///
+/// ```
+/// # use proyecto_taller_1::services::commands::command_list;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_lmove_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
+/// ).build());
+///
+/// let res = command_list::lmove(&vec![
+///     RespType::RBulkString("LMOVE".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("compradas".into()),
+///     RespType::RBulkString("left".into()),
+///     RespType::RBulkString("right".into())
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RBulkString("kiwi".into()));
+///
+/// # let _ = std::fs::remove_file("dummy_db_lmove_command.csv");
+/// ```
+pub fn lmove(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 5 {
+        if let (Some(source), Some(destination), Some(wherefrom), Some(whereto)) = (
+            cmd[1].as_bytes(),
+            cmd[2].as_bytes(),
+            cmd[3].as_str(),
+            cmd[4].as_str(),
+        ) {
+            return match (parse_direction(wherefrom), parse_direction(whereto)) {
+                (Some(from_left), Some(to_left)) => {
+                    move_element(database, source, destination, from_left, to_left)
+                }
+                _ => RespType::RError("syntax error".to_string()),
+            };
+        }
+    }
+    RespType::RError("Invalid request".to_string())
+}
+
+/// Elimina el último elemento de la lista `source` y lo inserta al comienzo de la lista `destination`.
+///
+/// Es equivalente a `LMOVE source destination RIGHT LEFT`.
+/// Retorna el elemento movido, o `nil` si `source` no existe.
+///
+/// # Ejemplo
+///
+/// ```
+/// # use proyecto_taller_1::services::commands::command_list;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_rpoplpush_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
+/// ).build());
+///
+/// let res = command_list::rpoplpush(&vec![
+///     RespType::RBulkString("RPOPLPUSH".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("frutas".into())
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RBulkString("sandia".into()));
+/// let values = database.read().unwrap().get_values_in_range(b"frutas", "0", "-1").unwrap();
+/// assert_eq!(values, vec![b"sandia".to_vec(), b"kiwi".to_vec(), b"pomelo".to_vec()]);
+///
+/// # let _ = std::fs::remove_file("dummy_db_rpoplpush_command.csv");
+/// ```
+pub fn rpoplpush(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 3 {
+        if let (Some(source), Some(destination)) = (cmd[1].as_bytes(), cmd[2].as_bytes()) {
+            return move_element(database, source, destination, false, true);
+        }
+    }
+    RespType::RError("Invalid request".to_string())
+}
+
+/// Elimina y devuelve elementos de la primera lista no vacía entre las claves especificadas.
+///
+/// El comando recibe `LMPOP numkeys key [key ...] LEFT|RIGHT [COUNT count]`.
+/// LEFT elimina los elementos desde el comienzo de la lista y RIGHT desde el final.
+/// Por defecto se elimina un elemento; con COUNT se eliminan hasta `count` elementos.
+/// Retorna un array con el nombre de la clave y los elementos eliminados, o `nil` si ninguna
+/// de las listas tiene elementos.
+///
+/// # Ejemplo
+///
+/// ```
+/// # use proyecto_taller_1::services::commands::command_list;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_lmpop_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
+/// ).build());
+///
+/// let res = command_list::lmpop(&vec![
+///     RespType::RBulkString("LMPOP".into()),
+///     RespType::RBulkString("2".into()),
+///     RespType::RBulkString("verduras".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("right".into()),
+///     RespType::RBulkString("count".into()),
+///     RespType::RBulkString("2".into())
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RArray(vec![
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RArray(vec![RespType::RBulkString("sandia".into()), RespType::RBulkString("pomelo".into())])
+/// ]));
+///
+/// let res = command_list::lmpop(&vec![
+///     RespType::RBulkString("LMPOP".into()),
+///     RespType::RBulkString("3".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("left".into())
+/// ], &database);
+/// assert_eq!(res, RespType::RError("syntax error".to_string()));
+///
+/// # let _ = std::fs::remove_file("dummy_db_lmpop_command.csv");
+/// ```
+pub fn lmpop(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() < 4 {
+        return RespType::RError("Invalid request".to_string());
+    }
+    let numkeys = match cmd[1].as_str() {
+        Some(numkeys) => match numkeys.parse::<isize>() {
+            Ok(n) if n > 0 => n as usize,
+            _ => {
+                return RespType::RError("numkeys should be greater than 0".to_string());
+            }
+        },
+        _ => return RespType::RError("Invalid request".to_string()),
+    };
+    if numkeys > cmd.len() - 3 || (cmd.len() != numkeys + 3 && cmd.len() != numkeys + 5) {
+        return RespType::RError("syntax error".to_string());
+    }
+    let from_left = match cmd[numkeys + 2].as_str() {
+        Some(direction) => match parse_direction(direction) {
+            Some(from_left) => from_left,
+            None => return RespType::RError("syntax error".to_string()),
+        },
+        _ => return RespType::RError("Invalid request".to_string()),
+    };
+    let mut count = 1;
+    if cmd.len() == numkeys + 5 {
+        if let (Some(option), Some(value)) = (cmd[numkeys + 3].as_str(), cmd[numkeys + 4].as_str())
+        {
+            if !option.eq_ignore_ascii_case("count") {
+                return RespType::RError("syntax error".to_string());
+            }
+            match value.parse::<usize>() {
+                Ok(c) if c > 0 => count = c,
+                _ => return RespType::RError("count should be greater than 0".to_string()),
+            }
+        }
+    }
+    let mut db = database
+        .write()
+        .expect("Could not get database lock on lmpop");
+    for key in &cmd[2..numkeys + 2] {
+        if let Some(key) = key.as_bytes() {
+            match db.get_live_item(key) {
+                Some(item) if !matches!(item.get_value(), ValueType::ListType(_)) => {
+                    return RespType::RError(WRONGTYPE.to_string());
+                }
+                None => continue,
+                _ => {}
+            }
+            let popped = if from_left {
+                db.pop_elements_from_list(key, count)
+            } else {
+                db.rpop_elements_from_list(key, count)
+            };
+            if let Some(popped) = popped {
+                return RespType::RArray(vec![
+                    RespType::RBulkString(key.to_vec()),
+                    RespType::RArray(
+                        popped
+                            .iter()
+                            .map(|element| RespType::RBulkString(element.to_vec()))
+                            .collect(),
+                    ),
+                ]);
+            }
+        }
+    }
+    RespType::RNullArray()
+}
+
+const NOT_AN_INTEGER: &str = "value is not an integer or out of range";
+
+/// Interpreta el extremo de una lista: `left` devuelve true, `right` devuelve false.
+fn parse_direction(direction: &str) -> Option<bool> {
+    match direction.to_lowercase().as_str() {
+        "left" => Some(true),
+        "right" => Some(false),
+        _ => None,
+    }
+}
+
+/// Indica si `key` existe y almacena un valor que no es de tipo lista.
+fn holds_other_type(db: &mut Database, key: &[u8]) -> bool {
+    db.get_live_item(key)
+        .is_some_and(|item| !matches!(item.get_value(), ValueType::ListType(_)))
+}
+
+fn wrong_number_of_arguments(command: &str) -> RespType {
+    RespType::RError(format!(
+        "ERR wrong number of arguments for '{}' command",
+        command
+    ))
+}
+
+fn move_element(
+    database: &Arc<RwLock<Database>>,
+    source: &[u8],
+    destination: &[u8],
+    from_left: bool,
+    to_left: bool,
+) -> RespType {
+    let mut db = database
+        .write()
+        .expect("Could not get database lock on lmove");
+    match db.move_element_between_lists(source, destination, from_left, to_left) {
+        Ok(Some(element)) => RespType::RBulkString(element),
+        Ok(None) => RespType::RNullBulkString(),
+        Err(e) => database_error_to_resp(e),
+    }
+}
+
+fn database_error_to_resp(error: DatabaseError) -> RespType {
+    match error {
+        DatabaseError::InvalidParameter(err) => RespType::RError(err),
+        DatabaseError::InvalidValueType(err) => RespType::RError(err),
+        DatabaseError::MissingKey() => RespType::RNullBulkString(),
+    }
+}
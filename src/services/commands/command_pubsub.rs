@@ -1,78 +1,203 @@
 //! Servicio que implementa todos los comandos Pub/Sub
 
-use std::{
-    net::SocketAddr,
-    sync::mpsc::{self, Sender},
-};
+use std::sync::mpsc::{self, Sender};
 
 use crate::{domain::entities::message::WorkerMessage, services::utils::resp_type::RespType};
 
-/// Suscribe un cliente al canal indicado.
+/// Suscribe un cliente a los canales indicados.
 ///
-/// Una vez que el cliente se suscribe a un canal, no puede ejecutar ningún otro comando.
-/// `Subscribe` es una función bloqueante, sólo recibe mensajes que hayan sido publicados al canal.
-/// Devuelve el nombre del canal y la cantidad de clientes suscritos al canal.
-pub fn subscribe(cmd: &[RespType], tx: &Sender<WorkerMessage>, addrs: SocketAddr) -> RespType {
-    let (messages_sender, messages_receiver) = mpsc::channel();
-
-    for channel in &cmd[1..] {
-        if let RespType::RBulkString(channel) = channel {
-            tx.send(WorkerMessage::Subscribe(
-                channel.to_string(),
-                addrs,
-                messages_sender.clone(),
-            ))
-            .expect("Could not send Subscribe message");
-
-            if let Ok(n_channels) = messages_receiver.recv() {
-                return RespType::RArray(vec![
-                    RespType::RBulkString(String::from("subscribe")),
-                    RespType::RBulkString(channel.to_string()),
-                    RespType::RInteger(n_channels),
-                ]);
-            }
-        }
+/// Una vez que el cliente se suscribe a un canal, sólo puede ejecutar comandos Pub/Sub.
+/// Por cada canal se devuelve un mensaje push con su nombre y la cantidad de canales y patrones a los que
+/// está suscrito el cliente. Los mensajes se agrupan en un RArray y se le envían al cliente por separado
+/// (ver `has_multiple_replies`).
+pub fn subscribe(cmd: &[RespType], tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    if cmd.len() < 2 {
+        return wrong_arguments("subscribe");
     }
-    RespType::RArray(vec![])
+    update_each(cmd, tx, "subscribe", |channel, sender| {
+        WorkerMessage::Subscribe(channel, client_id, sender)
+    })
 }
 
-/// Desuscribe un cliente del canal indicado.
+/// Desuscribe un cliente de los canales indicados.
 ///
 /// Si no se espefica un canal, se lo desuscribe de todos a los que se haya suscrito.
-/// Devuelve el nombre del canal y la cantidad de suscriptores actualizada.
-pub fn unsubscribe(cmd: &[RespType], tx: &Sender<WorkerMessage>, addrs: SocketAddr) -> RespType {
-    let (messages_sender, messages_receiver) = mpsc::channel();
+/// Por cada canal se devuelve un mensaje push con su nombre y la cantidad de suscripciones que le quedan al cliente.
+/// Si el cliente no estaba suscrito a ningún canal, el nombre del mensaje es nulo.
+pub fn unsubscribe(cmd: &[RespType], tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
     if cmd.len() > 1 {
-        for channel in &cmd[1..] {
-            if let RespType::RBulkString(channel) = channel {
-                tx.send(WorkerMessage::Unsubscribe(
-                    channel.to_string(),
-                    addrs,
-                    messages_sender.clone(),
-                ))
-                .expect("Could not send Unsubscribe message");
-
-                if let Ok(n_channels) = messages_receiver.recv() {
-                    return RespType::RArray(vec![
-                        RespType::RBulkString(String::from("unsubscribe")),
-                        RespType::RBulkString(channel.to_string()),
-                        RespType::RInteger(n_channels),
-                    ]);
-                }
-            }
-        }
+        update_each(cmd, tx, "unsubscribe", |channel, sender| {
+            WorkerMessage::Unsubscribe(channel, client_id, sender)
+        })
+    } else {
+        update_all(tx, "unsubscribe", |sender| {
+            WorkerMessage::UnsubscribeAll(client_id, sender)
+        })
+    }
+}
+
+/// Suscribe un cliente a los patrones glob indicados.
+///
+/// El cliente recibe los mensajes publicados en cualquier canal cuyo nombre cumpla alguno de sus patrones,
+/// junto con el patrón y el canal por el cual llegan.
+/// Responde de la misma manera que `subscribe`.
+pub fn psubscribe(cmd: &[RespType], tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    if cmd.len() < 2 {
+        return wrong_arguments("psubscribe");
+    }
+    update_each(cmd, tx, "psubscribe", |pattern, sender| {
+        WorkerMessage::PSubscribe(pattern, client_id, sender)
+    })
+}
+
+/// Desuscribe un cliente de los patrones indicados, o de todos si no se especifica ninguno.
+///
+/// Responde de la misma manera que `unsubscribe`.
+pub fn punsubscribe(cmd: &[RespType], tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    if cmd.len() > 1 {
+        update_each(cmd, tx, "punsubscribe", |pattern, sender| {
+            WorkerMessage::PUnsubscribe(pattern, client_id, sender)
+        })
+    } else {
+        update_all(tx, "punsubscribe", |sender| {
+            WorkerMessage::PUnsubscribeAll(client_id, sender)
+        })
+    }
+}
+
+/// Suscribe un cliente a los canales sharded indicados.
+///
+/// Los canales sharded se registran por separado de los canales globales: sólo reciben los mensajes publicados con `spublish`.
+/// Por cada canal se devuelve un mensaje push con su nombre y la cantidad de canales sharded a los que está suscrito el cliente.
+pub fn ssubscribe(cmd: &[RespType], tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    if cmd.len() < 2 {
+        return wrong_arguments("ssubscribe");
+    }
+    update_each(cmd, tx, "ssubscribe", |channel, sender| {
+        WorkerMessage::SSubscribe(channel, client_id, sender)
+    })
+}
+
+/// Desuscribe un cliente de los canales sharded indicados, o de todos si no se especifica ninguno.
+///
+/// Responde de la misma manera que `unsubscribe`, con la cantidad de canales sharded que le quedan al cliente.
+pub fn sunsubscribe(cmd: &[RespType], tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    if cmd.len() > 1 {
+        update_each(cmd, tx, "sunsubscribe", |channel, sender| {
+            WorkerMessage::SUnsubscribe(channel, client_id, sender)
+        })
     } else {
-        tx.send(WorkerMessage::UnsubscribeAll(addrs, messages_sender))
-            .expect("Could not send UnsubscribeAll message");
-        if let Ok(n_channels) = messages_receiver.recv() {
-            return RespType::RArray(vec![
-                RespType::RBulkString(String::from("unsubscribe")),
-                RespType::RBulkString("bar".to_string()),
-                RespType::RInteger(n_channels),
-            ]);
+        update_all(tx, "sunsubscribe", |sender| {
+            WorkerMessage::SUnsubscribeAll(client_id, sender)
+        })
+    }
+}
+
+/// Publica un mensaje en el canal sharded pedido.
+///
+/// Sólo lo reciben los clientes suscritos al canal con `ssubscribe`.
+/// Devuelve la cantidad de clientes que recibieron el mensaje.
+pub fn spublish(cmd: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
+    if cmd.len() != 3 {
+        return wrong_arguments("spublish");
+    }
+    if let (Some(channel), Some(message)) = (cmd[1].as_str(), cmd[2].as_str()) {
+        let (response_sender, response_receiver) = mpsc::channel();
+        tx.send(WorkerMessage::SPublish(
+            channel.to_string(),
+            response_sender,
+            message.to_string(),
+        ))
+        .expect("Could not send SPublish message");
+        return RespType::RInteger(response_receiver.recv().unwrap_or(0));
+    }
+    RespType::RInteger(0)
+}
+
+/// Indica si el comando responde con un mensaje por cada canal o patrón, en lugar de una única respuesta.
+///
+/// Es el caso de los comandos de suscripción: el RArray que devuelven contiene los mensajes que deben enviarse al cliente.
+///
+/// # Example
+/// ```
+/// use proyecto_taller_1::services::commands::command_pubsub;
+/// use proyecto_taller_1::services::utils::resp_type::RespType;
+///
+/// assert!(command_pubsub::has_multiple_replies(&RespType::RBulkString("PSUBSCRIBE".into())));
+/// assert!(!command_pubsub::has_multiple_replies(&RespType::RBulkString("publish".into())));
+/// ```
+pub fn has_multiple_replies(command: &RespType) -> bool {
+    [
+        "subscribe",
+        "unsubscribe",
+        "psubscribe",
+        "punsubscribe",
+        "ssubscribe",
+        "sunsubscribe",
+    ]
+    .iter()
+    .any(|name| command.is_word(name))
+}
+
+/// Envía al servidor un mensaje por cada canal o patrón del comando y arma las confirmaciones con
+/// la cantidad de suscripciones que devuelve el servidor.
+fn update_each<F>(cmd: &[RespType], tx: &Sender<WorkerMessage>, kind: &str, message: F) -> RespType
+where
+    F: Fn(String, Sender<usize>) -> WorkerMessage,
+{
+    let (messages_sender, messages_receiver) = mpsc::channel();
+    let mut replies = Vec::new();
+    for name in cmd[1..].iter().filter_map(RespType::as_str) {
+        tx.send(message(name.to_string(), messages_sender.clone()))
+            .expect("Could not send subscription message");
+        if let Ok(count) = messages_receiver.recv() {
+            replies.push(subscription_reply(kind, Some(name), count));
         }
     }
-    RespType::RArray(vec![])
+    RespType::RArray(replies)
+}
+
+/// Desuscribe al cliente de todos sus canales o patrones y arma una confirmación por cada uno.
+///
+/// Cada confirmación indica las suscripciones que le quedaban al cliente luego de desuscribirlo de ese canal o patrón.
+fn update_all<F>(tx: &Sender<WorkerMessage>, kind: &str, message: F) -> RespType
+where
+    F: FnOnce(Sender<(Vec<String>, usize)>) -> WorkerMessage,
+{
+    let (messages_sender, messages_receiver) = mpsc::channel();
+    tx.send(message(messages_sender))
+        .expect("Could not send unsubscribe message");
+    let (removed, remaining) = messages_receiver.recv().unwrap_or_default();
+    if removed.is_empty() {
+        return RespType::RArray(vec![subscription_reply(kind, None, remaining)]);
+    }
+    let total = remaining + removed.len();
+    RespType::RArray(
+        removed
+            .iter()
+            .enumerate()
+            .map(|(i, name)| subscription_reply(kind, Some(name), total - i - 1))
+            .collect(),
+    )
+}
+
+/// Arma la confirmación de una suscripción o desuscripción, como un mensaje push para los clientes RESP3.
+fn subscription_reply(kind: &str, name: Option<&str>, count: usize) -> RespType {
+    RespType::RPush(vec![
+        RespType::RBulkString(kind.into()),
+        name.map_or(RespType::RNullBulkString(), |name| {
+            RespType::RBulkString(name.into())
+        }),
+        RespType::RInteger(count),
+    ])
+}
+
+/// Error que se devuelve cuando un comando de suscripción no recibe canales ni patrones.
+fn wrong_arguments(command: &str) -> RespType {
+    RespType::RError(format!(
+        "ERR wrong number of arguments for '{}' command",
+        command
+    ))
 }
 
 /// Publica un mensaje en el canal pedido.
@@ -80,8 +205,8 @@ pub fn unsubscribe(cmd: &[RespType], tx: &Sender<WorkerMessage>, addrs: SocketAd
 /// A cada cliente suscrito al canal especificado se le envía, además del mensaje, el canal por el cual llega.
 /// Este comando devuelve la cantidad de clientes que recibieron el mensaje.
 pub fn publish(cmd: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
-    if let RespType::RBulkString(channel) = &cmd[1] {
-        if let RespType::RBulkString(message) = &cmd[2] {
+    if let Some(channel) = cmd[1].as_str() {
+        if let Some(message) = cmd[2].as_str() {
             let (response_sender, response_receiver) = mpsc::channel();
             tx.send(WorkerMessage::Publish(
                 channel.to_string(),
@@ -102,14 +227,25 @@ pub fn publish(cmd: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
 ///
 /// Si el comando es seguido por "channels" se listan todos los canales activos.
 /// Si el comando es seguido por "numsub" se listan los canales especificados y el numero de suscriptores.
+/// Si el comando es seguido por "numpat" se devuelve la cantidad de patrones a los que hay clientes suscritos.
+/// "shardchannels" y "shardnumsub" se comportan como "channels" y "numsub" sobre los canales sharded.
 pub fn pubsub(cmd: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
-    if let RespType::RBulkString(command) = &cmd[1] {
-        match command.as_str() {
+    if let Some(command) = cmd[1].as_str() {
+        match command.to_lowercase().as_str() {
             "channels" => {
-                return pubsub_channels(cmd, tx);
+                return pubsub_channels(cmd, tx, WorkerMessage::Channels);
             }
             "numsub" => {
-                return pubsub_numsub(cmd, tx);
+                return pubsub_numsub(cmd, tx, WorkerMessage::Numsub);
+            }
+            "shardchannels" => {
+                return pubsub_channels(cmd, tx, WorkerMessage::ShardChannels);
+            }
+            "shardnumsub" => {
+                return pubsub_numsub(cmd, tx, WorkerMessage::ShardNumsub);
+            }
+            "numpat" => {
+                return pubsub_numpat(tx);
             }
             _ => {}
         }
@@ -122,22 +258,24 @@ pub fn pubsub(cmd: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
 /// Canales activos son aquellos que tengan al menos un suscriptor.
 /// Si se especifica un patrón, se listan los canales cuyo nombre cumplan el patrón,
 /// sino se listan todos.
-fn pubsub_channels(cmd: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
+/// `message` construye el mensaje que se le envía al servidor, según se listen los canales globales o los sharded.
+fn pubsub_channels(
+    cmd: &[RespType],
+    tx: &Sender<WorkerMessage>,
+    message: fn(Sender<Vec<RespType>>, Option<String>) -> WorkerMessage,
+) -> RespType {
     let (response_sender, response_receiver) = mpsc::channel();
     if cmd.len() >= 3 {
-        if let RespType::RBulkString(pattern) = &cmd[2] {
-            tx.send(WorkerMessage::Channels(
-                response_sender,
-                Some(pattern.to_string()),
-            ))
-            .expect("Could not send Channels message");
+        if let Some(pattern) = cmd[2].as_str() {
+            tx.send(message(response_sender, Some(pattern.to_string())))
+                .expect("Could not send Channels message");
 
             if let Ok(res) = response_receiver.recv() {
                 return RespType::RArray(res);
             }
         }
     } else {
-        tx.send(WorkerMessage::Channels(response_sender, None))
+        tx.send(message(response_sender, None))
             .expect("Could not send Channels message");
 
         if let Ok(res) = response_receiver.recv() {
@@ -151,15 +289,20 @@ fn pubsub_channels(cmd: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
 ///
 /// Retorna una lista de canales y su cantidad de suscriptores en la forma (canal, cantidad).
 /// El orden de la lista es el mismo que en los parametros del comando [chequear esto]
-fn pubsub_numsub(cmd: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
+/// `message` construye el mensaje que se le envía al servidor, según se consulten los canales globales o los sharded.
+fn pubsub_numsub(
+    cmd: &[RespType],
+    tx: &Sender<WorkerMessage>,
+    message: fn(Vec<String>, Sender<Vec<RespType>>) -> WorkerMessage,
+) -> RespType {
     let (messages_sender, messages_receiver) = mpsc::channel();
     let mut channels = Vec::new();
     for channel in &cmd[2..] {
-        if let RespType::RBulkString(channel) = channel {
+        if let Some(channel) = channel.as_str() {
             channels.push(channel.to_string());
         }
     }
-    tx.send(WorkerMessage::Numsub(channels, messages_sender))
+    tx.send(message(channels, messages_sender))
         .expect("Could not send Numsub message");
 
     if let Ok(res) = messages_receiver.recv() {
@@ -167,3 +310,11 @@ fn pubsub_numsub(cmd: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
     }
     RespType::RArray(vec![])
 }
+
+/// Devuelve la cantidad de patrones distintos a los que hay al menos un cliente suscrito.
+fn pubsub_numpat(tx: &Sender<WorkerMessage>) -> RespType {
+    let (response_sender, response_receiver) = mpsc::channel();
+    tx.send(WorkerMessage::Numpat(response_sender))
+        .expect("Could not send Numpat message");
+    RespType::RInteger(response_receiver.recv().unwrap_or(0))
+}
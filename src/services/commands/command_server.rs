@@ -1,21 +1,229 @@
 //! Servicio que implementa todos los comandos de tipo Server
 
 use crate::domain::entities::config::Config;
-use crate::domain::entities::message::WorkerMessage;
+use crate::domain::entities::keyspace_events::KeyspaceEvents;
+use crate::domain::entities::message::{ShutdownOptions, WorkerMessage};
+use crate::domain::entities::session::Session;
 use crate::domain::implementations::database::Database;
+use crate::domain::implementations::output_queue::{parse_memory, OutputBufferLimit};
+use crate::services::commands::{command_acl, command_cluster, command_connection};
+use crate::services::replication_service;
 use crate::services::utils::glob_pattern;
-use crate::services::utils::resp_type::RespType;
-use std::net::SocketAddr;
+use crate::services::utils::resp_type::{RespType, RESP2, RESP3};
 use std::sync::mpsc::{self, Sender};
 use std::sync::{Arc, RwLock};
 
+/// Atributos de la configuración del cluster, que no pueden modificarse con `config set`.
+const CLUSTER_STARTUP_ATTRIBUTES: [&str; 5] = [
+    "cluster-enabled",
+    "cluster-config-file",
+    "cluster-node-timeout",
+    "cluster-port",
+    "cluster-announce-ip",
+];
+
 /// Pasa al cliente a estado "monitor".
 ///
 /// Envía una directiva al server para que el cliente pase a un estado pasivo donde solo reciba una copia en tiempo real de
 /// todos los comandos que se envíen al servidor.
-pub fn monitor(tx: &Sender<WorkerMessage>, addrs: SocketAddr) {
-    tx.send(WorkerMessage::SetMonitor(addrs))
+/// Devuelve "OK", que no se le envía al cliente: el server se lo envía antes de la copia del primer comando.
+pub fn monitor(tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    tx.send(WorkerMessage::SetMonitor(client_id))
         .expect("Could not send monitor message");
+    RespType::RSimpleString(String::from("OK"))
+}
+
+/// Verifica las opciones del comando `shutdown`.
+///
+/// El servidor no se detiene en este momento: si las opciones son válidas, el worker que atiende al cliente
+/// le pide que se detenga luego de terminar el comando (ver `server_service::handle_connection`).
+/// Devuelve "OK", que no se le envía al cliente, o error si alguna opción no es válida.
+pub fn shutdown(cmd: &[RespType]) -> RespType {
+    match get_shutdown_options(cmd) {
+        Ok(_) => RespType::RSimpleString(String::from("OK")),
+        Err(error) => error,
+    }
+}
+
+/// Interpreta las opciones del comando `shutdown`, que pueden indicarse en cualquier orden:
+/// * NOSAVE: no guarda los datos antes de detener el servidor.
+/// * SAVE: guarda los datos antes de detener el servidor (comportamiento por defecto).
+/// * NOW: no espera a que terminen los comandos que se están ejecutando.
+/// * FORCE: detiene el servidor aunque los datos no se hayan podido guardar.
+///
+/// Devuelve error si alguna opción no es válida o si se indican `NOSAVE` y `SAVE` a la vez.
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_server;
+/// use proyecto_taller_1::domain::entities::message::ShutdownOptions;
+///
+/// let cmd = vec![
+///     RespType::RBulkString("shutdown".into()),
+///     RespType::RBulkString("nosave".into()),
+///     RespType::RBulkString("force".into()),
+/// ];
+/// let options = command_server::get_shutdown_options(&cmd).unwrap();
+/// assert_eq!(options, ShutdownOptions { save: false, now: false, force: true });
+///
+/// let cmd = vec![
+///     RespType::RBulkString("shutdown".into()),
+///     RespType::RBulkString("nosave".into()),
+///     RespType::RBulkString("save".into()),
+/// ];
+/// assert!(command_server::get_shutdown_options(&cmd).is_err());
+/// ```
+pub fn get_shutdown_options(cmd: &[RespType]) -> Result<ShutdownOptions, RespType> {
+    let mut options = ShutdownOptions::default();
+    let mut save = None;
+    for arg in cmd.iter().skip(1) {
+        match arg.as_str().map(str::to_lowercase).as_deref() {
+            Some("nosave") if save != Some(true) => save = Some(false),
+            Some("save") if save != Some(false) => save = Some(true),
+            Some("now") => options.now = true,
+            Some("force") => options.force = true,
+            _ => return Err(RespType::RError(String::from("ERR syntax error"))),
+        }
+    }
+    options.save = save.unwrap_or(options.save);
+    Ok(options)
+}
+
+/// Convierte al servidor en réplica de otro, o en master, según los argumentos de `replicaof` (o `slaveof`):
+/// * `<host> <port>`: descarta la conexión con el master anterior, si existía, y comienza a replicar el indicado
+///   (ver `replication_service::start_replication`). Mientras se sincroniza, el servidor mantiene sus datos.
+/// * `no one`: deja de replicar y conserva los datos que tenía, pasando a aceptar comandos de escritura.
+///
+/// Devuelve "OK", o error si los argumentos no son válidos.
+pub fn replicaof(
+    cmd: &[RespType],
+    tx: &Sender<WorkerMessage>,
+    database: &Arc<RwLock<Database>>,
+    config: &Arc<RwLock<Config>>,
+) -> RespType {
+    if cmd.len() != 3 {
+        return RespType::RError(format!(
+            "ERR wrong number of arguments for '{}' command",
+            cmd[0].as_str().unwrap_or("replicaof").to_lowercase()
+        ));
+    }
+    let master = if cmd[1].is_word("no") && cmd[2].is_word("one") {
+        None
+    } else {
+        match (cmd[1].as_str(), cmd[2].as_str().map(str::parse::<u16>)) {
+            (Some(host), Some(Ok(port))) if port > 0 => Some((host.to_string(), port)),
+            _ => return RespType::RError(String::from("ERR Invalid master port")),
+        }
+    };
+    start_replicaof(master, tx, database, config);
+    RespType::RSimpleString(String::from("OK"))
+}
+
+/// Le indica a la entidad `Server` el nuevo master, o que deja de ser réplica si es `None`, y en el primer caso
+/// inicia la replicación.
+///
+/// Se utiliza con el comando `replicaof` y al iniciar el servidor, si la configuración especifica `replicaof <host> <port>`.
+pub fn start_replicaof(
+    master: Option<(String, u16)>,
+    tx: &Sender<WorkerMessage>,
+    database: &Arc<RwLock<Database>>,
+    config: &Arc<RwLock<Config>>,
+) {
+    let (generation_tx, generation_rx) = mpsc::channel();
+    tx.send(WorkerMessage::ReplicaOf(master.clone(), generation_tx))
+        .expect("Could not send ReplicaOf message");
+    if let (Some((host, port)), Ok(generation)) = (master, generation_rx.recv()) {
+        replication_service::start_replication(
+            generation,
+            host,
+            port,
+            database.clone(),
+            config.clone(),
+            tx.clone(),
+        );
+    }
+}
+
+/// Configura la conexión de una réplica, que la envía durante el handshake con su master:
+/// * `listening-port <port>`: puerto en el que la réplica recibe conexiones, que se informa en `info replication`.
+/// * `capa <capacidad>`: capacidades de la réplica, que se aceptan y se ignoran.
+/// * `ack <offset>`: offset de replicación que procesó la réplica. No tiene respuesta.
+/// * `getack`: el master pide un `ack`, por lo que se ignora si lo recibe un master.
+///
+/// Devuelve "OK", o error si alguna opción no es válida.
+pub fn replconf(cmd: &[RespType], tx: &Sender<WorkerMessage>, client_id: usize) -> RespType {
+    if cmd.len() == 3 && cmd[1].is_word("ack") {
+        if let Some(Ok(offset)) = cmd[2].as_str().map(str::parse::<usize>) {
+            tx.send(WorkerMessage::ReplicaAck(client_id, offset))
+                .expect("Could not send ReplicaAck message");
+        }
+        return RespType::RSimpleString(String::from("OK"));
+    }
+    if cmd.len().is_multiple_of(2) {
+        return RespType::RError(String::from("ERR syntax error"));
+    }
+    for option in cmd[1..].chunks(2) {
+        match option[0].as_str().map(str::to_lowercase).as_deref() {
+            Some("listening-port") => match option[1].as_str().map(str::parse::<u16>) {
+                Some(Ok(port)) => tx
+                    .send(WorkerMessage::ReplicaListeningPort(client_id, port))
+                    .expect("Could not send ReplicaListeningPort message"),
+                _ => return RespType::RError(String::from("ERR value is out of range")),
+            },
+            Some("capa") | Some("getack") => {}
+            _ => {
+                return RespType::RError(format!("ERR Unrecognized REPLCONF option: {}", option[0]))
+            }
+        }
+    }
+    RespType::RSimpleString(String::from("OK"))
+}
+
+/// Sincroniza una réplica, como respuesta a `psync` (o `sync`).
+///
+/// Siempre se realiza una sincronización completa: la entidad `Server` le envía a la réplica una copia de todos los
+/// datos y, a continuación, cada comando de escritura que se ejecute. Para que ningún comando quede fuera de la copia
+/// ni se aplique dos veces, quien atiende al cliente no ejecuta comandos de escritura mientras tanto
+/// (ver `server_service::handle_request`).
+/// Devuelve "FULLRESYNC", que no se le envía al cliente ya que el server le envía la copia, o error si el servidor es
+/// una réplica, ya que no se admite replicar una réplica.
+pub fn psync(
+    tx: &Sender<WorkerMessage>,
+    client_id: usize,
+    database: &Arc<RwLock<Database>>,
+) -> RespType {
+    if is_replica(tx) {
+        return RespType::RError(String::from(
+            "ERR Replicas can't be synchronized from another replica",
+        ));
+    }
+    let snapshot = database
+        .read()
+        .expect("Could not get database lock on psync")
+        .snapshot();
+    let (sync_tx, sync_rx) = mpsc::channel();
+    tx.send(WorkerMessage::AddReplica(client_id, snapshot, sync_tx))
+        .expect("Could not send AddReplica message");
+    let _ = sync_rx.recv();
+    RespType::RSimpleString(String::from("FULLRESYNC"))
+}
+
+/// Indica si el servidor es una réplica.
+pub fn is_replica(tx: &Sender<WorkerMessage>) -> bool {
+    let (replica_tx, replica_rx) = mpsc::channel();
+    tx.send(WorkerMessage::IsReplica(replica_tx))
+        .expect("Could not send IsReplica message");
+    replica_rx.recv().unwrap_or(false)
+}
+
+/// Indica si las réplicas rechazan los comandos de escritura de sus clientes, según el atributo `replica-read-only`
+/// de la configuración (por defecto `yes`).
+pub fn is_replica_read_only(config: &Arc<RwLock<Config>>) -> bool {
+    config
+        .read()
+        .ok()
+        .and_then(|conf| conf.get_attribute(String::from("replica-read-only")).ok())
+        .is_none_or(|read_only| !read_only.eq_ignore_ascii_case("no"))
 }
 
 /// Devuelve información y estadísticas sobre el servidor.
@@ -36,36 +244,42 @@ pub fn monitor(tx: &Sender<WorkerMessage>, addrs: SocketAddr) {
 /// * errorstats: Estadisticas de errores Redis
 /// * all: Todas las secciones de información, excluyendo módulos
 /// * everything: Todas las secciones de información, incluyendo módulos
+///
 /// Si no se especifica ningún parámetro, se retorna toda la información (all).
-pub fn info(cmd: &[RespType], tx: &Sender<WorkerMessage>) -> RespType {
-    if cmd.len() == 2 {
-        if let RespType::RBulkString(section) = &cmd[1] {
-            match section.as_str() {
-                "server" => return RespType::RBulkString(get_server_info(tx)),
-                "clients" => return RespType::RBulkString(get_clients_info(tx)),
-                "memory" => return RespType::RBulkString(get_memory_info()),
-                "persistence" => return RespType::RBulkString(get_persistence_info()),
-                "stats" => return RespType::RBulkString(get_stats_info(tx)),
-                "replication" => return RespType::RBulkString(get_replication_info()),
-                "cpu" => return RespType::RBulkString(get_cpu_info()),
-                "commandstats" => return RespType::RBulkString(get_commandstats_info()),
-                "cluster" => return RespType::RBulkString(get_cluster_info()),
-                "modules" => return RespType::RBulkString(get_modules_info()),
-                "keyspace" => return RespType::RBulkString(get_keyspace_info()),
-                "errorstats" => return RespType::RBulkString(get_errorstats_info()),
-                "all" => return RespType::RBulkString(get_all_info(tx)),
-                "everything" => return RespType::RBulkString(get_everything_info(tx)),
-                _ => return RespType::RNullBulkString(),
-            }
+/// La información se devuelve como un verbatim string de texto, que los clientes RESP2 reciben como bulk string.
+pub fn info(
+    cmd: &[RespType],
+    tx: &Sender<WorkerMessage>,
+    config: &Arc<RwLock<Config>>,
+) -> RespType {
+    let info = if cmd.len() == 2 {
+        match cmd[1].as_str().map(str::to_lowercase).as_deref() {
+            Some("server") => get_server_info(tx),
+            Some("clients") => get_clients_info(tx),
+            Some("memory") => get_memory_info(),
+            Some("persistence") => get_persistence_info(),
+            Some("stats") => get_stats_info(tx),
+            Some("replication") => get_replication_info(tx, config),
+            Some("cpu") => get_cpu_info(),
+            Some("commandstats") => get_commandstats_info(),
+            Some("cluster") => get_cluster_info(config),
+            Some("modules") => get_modules_info(),
+            Some("keyspace") => get_keyspace_info(),
+            Some("errorstats") => get_errorstats_info(),
+            Some("all") => get_all_info(tx, config),
+            Some("everything") => get_everything_info(tx, config),
+            _ => return RespType::RNullBulkString(),
         }
     } else if cmd.len() == 1 {
-        return RespType::RBulkString(get_all_info(tx));
-    }
-    RespType::RNullBulkString()
+        get_all_info(tx, config)
+    } else {
+        return RespType::RNullBulkString();
+    };
+    RespType::RVerbatimString(String::from("txt"), info)
 }
 
 /// Devuelve toda la información y estadísticas del servidor, incluidos los módulos.
-fn get_everything_info(tx: &Sender<WorkerMessage>) -> String {
+fn get_everything_info(tx: &Sender<WorkerMessage>, config: &Arc<RwLock<Config>>) -> String {
     let info = format!(
         "{}{}{}{}{}{}{}{}{}{}{}{}",
         get_server_info(tx),
@@ -73,10 +287,10 @@ fn get_everything_info(tx: &Sender<WorkerMessage>) -> String {
         get_memory_info(),
         get_persistence_info(),
         get_stats_info(tx),
-        get_replication_info(),
+        get_replication_info(tx, config),
         get_cpu_info(),
         get_commandstats_info(),
-        get_cluster_info(),
+        get_cluster_info(config),
         get_modules_info(),
         get_keyspace_info(),
         get_errorstats_info()
@@ -85,7 +299,7 @@ fn get_everything_info(tx: &Sender<WorkerMessage>) -> String {
 }
 
 /// Devuelve toda la información y estadísticas del servidor, excluyendo los módulos.
-fn get_all_info(tx: &Sender<WorkerMessage>) -> String {
+fn get_all_info(tx: &Sender<WorkerMessage>, config: &Arc<RwLock<Config>>) -> String {
     let info = format!(
         "{}{}{}{}{}{}{}{}{}{}{}",
         get_server_info(tx),
@@ -93,10 +307,10 @@ fn get_all_info(tx: &Sender<WorkerMessage>) -> String {
         get_memory_info(),
         get_persistence_info(),
         get_stats_info(tx),
-        get_replication_info(),
+        get_replication_info(tx, config),
         get_cpu_info(),
         get_commandstats_info(),
-        get_cluster_info(),
+        get_cluster_info(config),
         get_keyspace_info(),
         get_errorstats_info()
     );
@@ -146,9 +360,18 @@ fn get_persistence_info() -> String {
     String::from("# Persistence\r\nloading:0\r\ncurrent_cow_size:0\r\ncurrent_save_keys_processed:0\r\ncurrent_save_keys_total:0\r\nrdb_changes_since_last_save:13896285\r\nrdb_bgsave_in_progress:0\r\nrdb_last_save_time:1622557783\r\nrdb_last_bgsave_status:ok\r\nrdb_last_bgsave_time_sec:-1\r\nrdb_current_bgsave_time_sec:-1\r\nrdb_last_cow_size:0\r\n")
 }
 
-/// Devuelve información sobre replicación
-fn get_replication_info() -> String {
-    String::from("# Replication\r\nrole:master\r\nconnected_slaves:0\r\nmaster_failover_state:no-failover\r\nmaster_replid:435ed80c4d896f3b6e3a19e10951bacd77bef04b\r\nmaster_replid2:0000000000000000000000000000000000000000\r\nmaster_repl_offset:0\r\nsecond_repl_offset:-1\r\nrepl_backlog_active:0\r\nrepl_backlog_size:1048576\r\nrepl_backlog_first_byte_offset:0\r\nrepl_backlog_histlen:0\r\n")
+/// Devuelve información sobre la replicación: el rol del servidor, la conexión con su master y las réplicas conectadas.
+fn get_replication_info(tx: &Sender<WorkerMessage>, config: &Arc<RwLock<Config>>) -> String {
+    let (info_tx, info_rx) = mpsc::channel();
+    tx.send(WorkerMessage::InfoReplication(
+        is_replica_read_only(config),
+        info_tx,
+    ))
+    .expect("Could not send InfoReplication message");
+    if let Ok(info) = info_rx.recv() {
+        return info;
+    }
+    String::from("# Replication\r\n")
 }
 
 /// Devuelve información sobre el uso de CPU
@@ -161,9 +384,12 @@ fn get_commandstats_info() -> String {
     String::from("# Commandstats\r\ncmdstat_TYPE: calls=1231,rejected_calls=123,failed_calls=32")
 }
 
-/// Devuelve información de clusters
-fn get_cluster_info() -> String {
-    String::from("# Cluster\r\ncluster_enabled:0\r\n")
+/// Devuelve información de clusters: si el servidor está en modo cluster (ver `command_cluster::is_cluster_enabled`).
+fn get_cluster_info(config: &Arc<RwLock<Config>>) -> String {
+    format!(
+        "# Cluster\r\ncluster_enabled:{}\r\n",
+        command_cluster::is_cluster_enabled(config) as usize
+    )
 }
 
 /// Devuelve información de módulos
@@ -193,11 +419,11 @@ fn get_errorstats_info() -> String {
 ///
 /// # let db = Database::new("dummy_db_dbsize.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string()]),
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into()),
 /// ).build());
-/// database.write().unwrap().add("nombre".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType("fruta".to_string())
+/// database.write().unwrap().add(b"nombre".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("fruta".into())
 /// ).build());
 ///
 /// let dbsize = command_server::dbsize(&database);
@@ -227,15 +453,15 @@ pub fn dbsize(database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_flushdb.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::ListType(vec!["kiwi".to_string(),"pomelo".to_string(),"sandia".to_string()])
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::ListType(vec![b"kiwi".to_vec(),b"pomelo".to_vec(),b"sandia".to_vec()].into())
 /// ).build());
-/// database.write().unwrap().add("nombre".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType("fruta".to_string())
+/// database.write().unwrap().add(b"nombre".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("fruta".into())
 /// ).build());
 ///
 /// let removed = command_server::flushdb(&database);
-/// assert_eq!(removed, RespType::RBulkString("Erased database".to_string()));
+/// assert_eq!(removed, RespType::RBulkString("Erased database".into()));
 /// assert_eq!(command_server::dbsize(&database), RespType::RInteger(0));
 /// # std::fs::remove_file("dummy_db_flushdb.csv").unwrap();
 /// ```
@@ -244,13 +470,14 @@ pub fn flushdb(database: &Arc<RwLock<Database>>) -> RespType {
         .write()
         .expect("Could not get write of database on flushdb");
     new_database.clean_items();
-    RespType::RBulkString("Erased database".to_string())
+    RespType::RBulkString("Erased database".into())
 }
 
 /// Retorna los parámetros de configuración del servidor.
 ///
-/// Busca en la configuración el valor del atributo especificado
-/// En caso de encontrarlo, lo devuelve, sino devuelve Error.
+/// Busca en la configuración los atributos que coincidan con el patrón especificado
+/// y los devuelve como un map de nombre y valor, que los clientes RESP2 reciben como un array con ambos elementos intercalados.
+/// Si el pedido no es válido, devuelve Error.
 /// # Ejemplo
 /// ```
 /// # use proyecto_taller_1::services::utils::resp_type::RespType;
@@ -265,14 +492,15 @@ pub fn flushdb(database: &Arc<RwLock<Database>>) -> RespType {
 /// .unwrap();
 ///
 /// let mut c = Arc::new(RwLock::new(config));
-/// let res = command_server::config_get(&c, &vec![RespType::RBulkString(String::from("get")), RespType::RBulkString(String::from("maxmemory"))]);
+/// let res = command_server::config_get(&c, &vec![RespType::RBulkString("get".into()), RespType::RBulkString("maxmemory".into())]);
 ///
-/// assert_eq!(res, RespType::RArray(vec![RespType::RBulkString("maxmemory".to_string()), RespType::RBulkString("2mb".to_string())]));
+/// assert_eq!(res, RespType::RMap(vec![(RespType::RBulkString("maxmemory".into()), RespType::RBulkString("2mb".into()))]));
+/// assert_eq!(res.into_protocol(2), RespType::RArray(vec![RespType::RBulkString("maxmemory".into()), RespType::RBulkString("2mb".into())]));
 /// # std::fs::remove_file("./src/dummy_config_get.txt").unwrap();
 /// ```
 pub fn config_get(config: &Arc<RwLock<Config>>, cmd: &[RespType]) -> RespType {
     if cmd.len() == 2 {
-        if let RespType::RBulkString(field_name) = &cmd[1] {
+        if let Some(field_name) = cmd[1].as_str() {
             if let Ok(conf) = config.read() {
                 let mut matches = Vec::new();
                 conf.get_all_attributes().iter().for_each(|attribute| {
@@ -280,11 +508,13 @@ pub fn config_get(config: &Arc<RwLock<Config>>, cmd: &[RespType]) -> RespType {
                         field_name.as_bytes(),
                         attribute.0.to_owned().as_bytes(),
                     ) {
-                        matches.push(RespType::RBulkString(attribute.0.to_owned()));
-                        matches.push(RespType::RBulkString(attribute.1.to_owned()));
+                        matches.push((
+                            RespType::RBulkString(attribute.0.to_owned().into()),
+                            RespType::RBulkString(attribute.1.to_owned().into()),
+                        ));
                     }
                 });
-                return RespType::RArray(matches);
+                return RespType::RMap(matches);
             }
             return RespType::RError(String::from("Parameter missing"));
         }
@@ -295,32 +525,83 @@ pub fn config_get(config: &Arc<RwLock<Config>>, cmd: &[RespType]) -> RespType {
 /// Reconfigura parámetros de configuración.
 ///
 /// Configura el campo `field` con el valor especificado.
+/// Si el campo es `requirepass`, además actualiza la contraseña del usuario ACL `default`; si el valor es vacío,
+/// el usuario deja de requerir contraseña.
+/// Si el campo es `client-output-buffer-limit`, el valor debe tener el formato `<clase> <hard> <soft> <segundos>` (ver `OutputBufferLimit`).
+/// Si el campo es `maxmemory`, el valor debe ser una cantidad de bytes, con unidades opcionales.
+/// Si el campo es `notify-keyspace-events`, además actualiza los eventos sobre las claves que notifica la base de datos (ver `KeyspaceEvents`).
 /// En caso de exito devuelve "Ok", sino devuelve Error
 /// # Ejemplo
 /// ```
 /// # use proyecto_taller_1::services::utils::resp_type::RespType;
 /// # use proyecto_taller_1::domain::entities::config::Config;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
 /// # use proyecto_taller_1::services::commands::command_server;
-/// # use std::sync::{Arc, RwLock};
+/// # use std::sync::{Arc, RwLock, mpsc};
 ///
+/// let (tx, _rx) = mpsc::channel();
 /// # std::fs::File::create("./src/dummy_config_set.txt").unwrap();
 /// let mut config = Config::new("./src/dummy_config_set.txt".to_string());
 /// let mut c = Arc::new(RwLock::new(config));
+/// let database = Arc::new(RwLock::new(Database::new("dummy_db_config_set.csv".to_string())));
 ///
-/// let res = command_server::config_set(&c, &vec![RespType::RBulkString(String::from("set")), RespType::RBulkString(String::from("maxmemory")), RespType::RBulkString(String::from("2mb"))]);
+/// let res = command_server::config_set(&c, &vec![RespType::RBulkString("set".into()), RespType::RBulkString("maxmemory".into()), RespType::RBulkString("2mb".into())], &tx, &database);
 /// assert_eq!(res, RespType::RSimpleString("Ok".to_string()));
 ///
-/// let res = command_server::config_get(&c, &vec![RespType::RBulkString(String::from("get")), RespType::RBulkString(String::from("maxmemory"))]);
-/// assert_eq!(res, RespType::RArray(vec![RespType::RBulkString("maxmemory".to_string()), RespType::RBulkString("2mb".to_string())]));
+/// let res = command_server::config_set(&c, &vec![RespType::RBulkString("set".into()), RespType::RBulkString("notify-keyspace-events".into()), RespType::RBulkString("Kx?".into())], &tx, &database);
+/// assert_eq!(res, RespType::RError("ERR Invalid notify-keyspace-events 'Kx?'".to_string()));
+///
+/// let res = command_server::config_get(&c, &vec![RespType::RBulkString("get".into()), RespType::RBulkString("maxmemory".into())]);
+/// assert_eq!(res, RespType::RMap(vec![(RespType::RBulkString("maxmemory".into()), RespType::RBulkString("2mb".into()))]));
 /// # std::fs::remove_file("./src/dummy_config_set.txt").unwrap();
+/// # let _ = std::fs::remove_file("dummy_db_config_set.csv");
 /// ```
-pub fn config_set(config: &Arc<RwLock<Config>>, cmd: &[RespType]) -> RespType {
+pub fn config_set(
+    config: &Arc<RwLock<Config>>,
+    cmd: &[RespType],
+    tx: &Sender<WorkerMessage>,
+    database: &Arc<RwLock<Database>>,
+) -> RespType {
     if cmd.len() == 3 {
-        if let RespType::RBulkString(field) = &cmd[1] {
-            if let RespType::RBulkString(value) = &cmd[2] {
+        if let Some(field) = cmd[1].as_str() {
+            if let Some(value) = cmd[2].as_str() {
+                if field.eq_ignore_ascii_case("client-output-buffer-limit") {
+                    if let Err(e) = OutputBufferLimit::parse_pubsub(value) {
+                        return RespType::RError(format!("ERR {}", e));
+                    }
+                }
+                // el cluster se configura al iniciar el servidor (ver `cluster_service::start_cluster`)
+                if CLUSTER_STARTUP_ATTRIBUTES
+                    .iter()
+                    .any(|attribute| field.eq_ignore_ascii_case(attribute))
+                {
+                    return RespType::RError(format!(
+                        "ERR CONFIG SET failed (possibly related to argument '{}') - can't set immutable config",
+                        field
+                    ));
+                }
+                if field.eq_ignore_ascii_case("maxmemory") && parse_memory(value).is_none() {
+                    return RespType::RError(format!("ERR Invalid maxmemory '{}'", value));
+                }
+                let mut events = None;
+                if field.eq_ignore_ascii_case("notify-keyspace-events") {
+                    match KeyspaceEvents::parse(value) {
+                        Ok(parsed) => events = Some(parsed),
+                        Err(e) => return RespType::RError(format!("ERR {}", e)),
+                    }
+                }
                 if let Ok(mut conf) = config.write() {
                     match conf.set_attribute(String::from(field), String::from(value)) {
                         Ok(_) => {
+                            if field.eq_ignore_ascii_case("requirepass") {
+                                command_acl::set_requirepass(tx, value);
+                            }
+                            if let Some(events) = events {
+                                database
+                                    .write()
+                                    .expect("Could not get database lock on config set")
+                                    .set_notify_keyspace_events(events);
+                            }
                             return RespType::RSimpleString(String::from("Ok"));
                         }
                         Err(e) => {
@@ -333,3 +614,126 @@ pub fn config_set(config: &Arc<RwLock<Config>>, cmd: &[RespType]) -> RespType {
     }
     RespType::RError(String::from("Invalid request"))
 }
+
+/// Negocia la versión del protocolo RESP con el cliente.
+///
+/// Admite opcionalmente la versión del protocolo (2 o 3) seguida de las opciones `AUTH <usuario> <contraseña>`
+/// y `SETNAME <nombre>`. Si se especifica AUTH y las credenciales corresponden a un usuario ACL, la conexión queda
+/// autenticada con ese usuario. Si la conexión no está autenticada y no se especifica AUTH devuelve un error `NOAUTH`.
+/// Si la negociación es exitosa actualiza la versión del protocolo de la conexión y devuelve un map
+/// con información del servidor, que los clientes RESP2 reciben como array.
+/// Si la versión no es soportada devuelve un error `NOPROTO` y la conexión mantiene su protocolo.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::entities::session::Session;
+/// # use proyecto_taller_1::services::commands::command_server;
+/// # use std::sync::mpsc;
+///
+/// let (tx, _rx) = mpsc::channel();
+/// let client_id = 1;
+/// let mut session = Session::new(true);
+///
+/// let res = command_server::hello(&[RespType::RBulkString("hello".into()), RespType::RBulkString("3".into())], &tx, client_id, &mut session);
+/// assert_eq!(session.get_protocol(), 3);
+/// if let RespType::RMap(fields) = res {
+///     assert!(fields.contains(&(RespType::RBulkString("proto".into()), RespType::RInteger(3))));
+/// }
+///
+/// let res = command_server::hello(&[RespType::RBulkString("hello".into()), RespType::RBulkString("4".into())], &tx, client_id, &mut session);
+/// assert_eq!(res, RespType::RError("NOPROTO unsupported protocol version".to_string()));
+/// assert_eq!(session.get_protocol(), 3);
+/// ```
+pub fn hello(
+    cmd: &[RespType],
+    tx: &Sender<WorkerMessage>,
+    client_id: usize,
+    session: &mut Session,
+) -> RespType {
+    let mut new_protocol = session.get_protocol();
+    let mut name = None;
+    let mut user = None;
+    let mut pos = 1;
+    if let Some(version) = cmd.get(1) {
+        match version.as_str().map(str::parse::<usize>) {
+            Some(Ok(version)) if version == RESP2 || version == RESP3 => new_protocol = version,
+            Some(Ok(_)) => {
+                return RespType::RError(String::from("NOPROTO unsupported protocol version"))
+            }
+            _ => {
+                return RespType::RError(String::from(
+                    "Protocol version is not an integer or out of range",
+                ))
+            }
+        }
+        pos = 2;
+    }
+    while pos < cmd.len() {
+        if cmd[pos].is_word("auth") && pos + 2 < cmd.len() {
+            let username = cmd[pos + 1].as_str().unwrap_or_default();
+            if !command_acl::authenticate(tx, client_id, username, &cmd[pos + 2]) {
+                return RespType::RError(String::from(
+                    "WRONGPASS invalid username-password pair or user is disabled.",
+                ));
+            }
+            user = Some(username.to_string());
+            pos += 3;
+        } else if cmd[pos].is_word("setname") && pos + 1 < cmd.len() {
+            match cmd[pos + 1].as_str() {
+                Some(client_name) if command_connection::is_valid_client_name(client_name) => {
+                    name = Some(client_name.to_string());
+                }
+                _ => {
+                    return RespType::RError(String::from(
+                        "Client names cannot contain spaces, newlines or special characters.",
+                    ))
+                }
+            }
+            pos += 2;
+        } else {
+            return RespType::RError(String::from("Syntax error in HELLO option"));
+        }
+    }
+
+    if user.is_none() && !session.is_authenticated() {
+        return RespType::RError(String::from("NOAUTH HELLO must be called with the client already authenticated, otherwise the HELLO <proto> AUTH <user> <pass> option can be used to authenticate the client and select the RESP protocol version at the same time"));
+    }
+    if user.is_some() {
+        session.set_user(user);
+    }
+    session.set_protocol(new_protocol);
+    tx.send(WorkerMessage::SetProtocol(client_id, new_protocol))
+        .expect("Could not send SetProtocol message");
+    if let Some(name) = name {
+        tx.send(WorkerMessage::SetClientName(client_id, Some(name)))
+            .expect("Could not send SetClientName message");
+    }
+
+    RespType::RMap(vec![
+        (
+            RespType::RBulkString("server".into()),
+            RespType::RBulkString("redis".into()),
+        ),
+        (
+            RespType::RBulkString("version".into()),
+            RespType::RBulkString("6.2.3".into()),
+        ),
+        (
+            RespType::RBulkString("proto".into()),
+            RespType::RInteger(new_protocol),
+        ),
+        (
+            RespType::RBulkString("mode".into()),
+            RespType::RBulkString("standalone".into()),
+        ),
+        (
+            RespType::RBulkString("role".into()),
+            RespType::RBulkString("master".into()),
+        ),
+        (
+            RespType::RBulkString("modules".into()),
+            RespType::RArray(vec![]),
+        ),
+    ])
+}
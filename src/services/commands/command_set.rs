@@ -1,9 +1,14 @@
 //! Servicio que implementa todos los comandos de tipo Set
 
 use crate::domain::implementations::database::Database;
+use crate::errors::database_error::{DatabaseError, WRONGTYPE};
 use crate::services::utils::resp_type::RespType;
+use std::collections::HashSet;
 use std::sync::{Arc, RwLock};
 
+/// Máxima cantidad de elementos que puede devolver SRANDMEMBER con un `count` negativo.
+const SRANDMEMBER_MAX_COUNT: isize = 1_000_000;
+
 /// Agrega un elemento al set de la `key` dada
 ///
 /// A partir de una `key` dada, se busca el set asociado y se le agrega el string que se
@@ -28,17 +33,17 @@ use std::sync::{Arc, RwLock};
 /// # let db = Database::new("dummy_db_add.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 /// let mut set = HashSet::new();
-/// set.insert("kiwi".to_string());
-/// set.insert("pomelo".to_string());
-/// set.insert("sandia".to_string());
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
+/// set.insert(b"kiwi".to_vec());
+/// set.insert(b"pomelo".to_vec());
+/// set.insert(b"sandia".to_vec());
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
 ///     ValueType::SetType(set)
 /// ).build());
 ///
 /// let res = command_set::add(&vec![
-///     RespType::RBulkString("SADD".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("frutillas".to_string()),
+///     RespType::RBulkString("SADD".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("frutillas".into()),
 /// ], &database);
 ///
 /// # match res {
@@ -51,13 +56,13 @@ use std::sync::{Arc, RwLock};
 /// ```
 pub fn add(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() > 2 {
-        if let RespType::RBulkString(key) = &cmd[1] {
+        if let Some(key) = cmd[1].as_bytes() {
             let mut db = database
                 .write()
                 .expect("Could not get database lock on add");
             let mut values_to_add = Vec::new();
             for n in cmd.iter().skip(2) {
-                if let RespType::RBulkString(value) = n {
+                if let Some(value) = n.as_bytes() {
                     values_to_add.push(value);
                 }
             }
@@ -66,7 +71,7 @@ pub fn add(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
                     return RespType::RInteger(added);
                 }
                 None => {
-                    return RespType::RError(String::from("Value stored should be a set"));
+                    return RespType::RError(String::from(WRONGTYPE));
                 }
             }
         }
@@ -76,7 +81,8 @@ pub fn add(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 
 /// Retorna la cantidad de elementos del SET almacenado en `key`.
 ///
-/// Si la `key` no existe o el valor almacenado en la `key` no es un SET, retorna 0.
+/// Si la `key` no existe, retorna 0.
+/// Si el valor almacenado en la `key` no es un SET, retorna Error.
 ///
 /// # Ejemplo
 /// ```
@@ -90,39 +96,43 @@ pub fn add(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let db = Database::new("dummy_db_scard.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 /// let mut set = HashSet::new();
-/// set.insert("kiwi".to_string());
-/// set.insert("pomelo".to_string());
-/// set.insert("sandia".to_string());
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
+/// set.insert(b"kiwi".to_vec());
+/// set.insert(b"pomelo".to_vec());
+/// set.insert(b"sandia".to_vec());
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
 ///     ValueType::SetType(set)).build());
 ///
 /// let res = command_set::scard(&vec![
-///     RespType::RBulkString("SCARD".to_string()),
-///     RespType::RBulkString("frutas".to_string())],
+///     RespType::RBulkString("SCARD".into()),
+///     RespType::RBulkString("frutas".into())],
 ///     &database);
 ///
 /// assert_eq!(res, RespType::RInteger(3));
 /// # let _ = std::fs::remove_file("dummy_db_scard.csv");
 /// ```
 pub fn scard(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
-    if cmd.len() > 1 {
-        if let RespType::RBulkString(key) = &cmd[1] {
-            let db = database
-                .read()
-                .expect("Could not get database read lock on scard");
-            let (item, expired) = db.check_timeout_item(key);
-            if item.is_some() && expired {
-                drop(db);
-                database
-                    .write()
-                    .expect("Could not get database write lock on scard")
-                    .remove_expired_key(key)
-            }
-            let db = database
-                .read()
-                .expect("Could not get database read lock on scard");
-            return RespType::RInteger(db.get_len_of_set(key));
+    if cmd.len() != 2 {
+        return wrong_number_of_arguments("scard");
+    }
+    if let Some(key) = cmd[1].as_bytes() {
+        let db = database
+            .read()
+            .expect("Could not get database read lock on scard");
+        let (item, expired) = db.check_timeout_item(key);
+        if item.is_some() && expired {
+            drop(db);
+            database
+                .write()
+                .expect("Could not get database write lock on scard")
+                .remove_expired_key(key)
         }
+        let db = database
+            .read()
+            .expect("Could not get database read lock on scard");
+        return match db.get_set(key) {
+            Ok(set) => RespType::RInteger(set.map_or(0, HashSet::len)),
+            Err(e) => database_error_to_resp(e),
+        };
     }
     RespType::RInteger(0)
 }
@@ -130,7 +140,8 @@ pub fn scard(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// Retorna si el elemento pertenece al SET almacenado en la clave especificada.
 ///
 /// Si el elemento pertenece al SET, retorna 1.
-/// Si la clave no existe o el valor almacenado no es un SET, retorna 0.
+/// Si la clave no existe, retorna 0.
+/// Si el valor almacenado no es un SET, retorna Error.
 ///
 /// # Ejemplo
 /// ```
@@ -144,42 +155,46 @@ pub fn scard(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let db = Database::new("dummy_db_sismember.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 /// let mut set = HashSet::new();
-/// set.insert("kiwi".to_string());
-/// set.insert("pomelo".to_string());
-/// set.insert("sandia".to_string());
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
+/// set.insert(b"kiwi".to_vec());
+/// set.insert(b"pomelo".to_vec());
+/// set.insert(b"sandia".to_vec());
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
 ///     ValueType::SetType(set)
 /// ).build());
 ///
 /// let res = command_set::sismember(&vec![
-///     RespType::RBulkString("SISMEMBER".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("pomelo".to_string())],
+///     RespType::RBulkString("SISMEMBER".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("pomelo".into())],
 ///     &database);
 ///
 /// assert_eq!(res, RespType::RInteger(1));
 /// # let _ = std::fs::remove_file("dummy_db_sismember.csv");
 /// ```
 pub fn sismember(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
-    if cmd.len() > 2 {
-        if let RespType::RBulkString(key) = &cmd[1] {
+    if cmd.len() != 3 {
+        return wrong_number_of_arguments("sismember");
+    }
+    if let Some(key) = cmd[1].as_bytes() {
+        let db = database
+            .read()
+            .expect("Could not get database read lock on smembers");
+        let (item, expired) = db.check_timeout_item(key);
+        if item.is_some() && expired {
+            drop(db);
+            database
+                .write()
+                .expect("Could not get database write lock on smembers")
+                .remove_expired_key(key)
+        }
+        if let Some(member) = cmd[2].as_bytes() {
             let db = database
                 .read()
                 .expect("Could not get database read lock on smembers");
-            let (item, expired) = db.check_timeout_item(key);
-            if item.is_some() && expired {
-                drop(db);
-                database
-                    .write()
-                    .expect("Could not get database write lock on smembers")
-                    .remove_expired_key(key)
-            }
-            if let RespType::RBulkString(member) = &cmd[2] {
-                let db = database
-                    .read()
-                    .expect("Could not get database read lock on smembers");
-                return RespType::RInteger(db.is_member_of_set(key, member));
-            }
+            return match db.get_set(key) {
+                Ok(set) => RespType::RInteger(set.is_some_and(|set| set.contains(member)) as usize),
+                Err(e) => database_error_to_resp(e),
+            };
         }
     }
     RespType::RInteger(0)
@@ -188,7 +203,8 @@ pub fn sismember(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType
 /// Retorna todos los elementos pertenecientes al SET almacenado en la clave especificada.
 ///
 /// Devuelve un array con todos los elementos que pertenecen al SET.
-/// Si la clave no existe, o si la clave no almacena un valor de tipo SET, devuelve un array nulo.
+/// Si la clave no existe, devuelve un array nulo.
+/// Si la clave no almacena un valor de tipo SET, retorna Error.
 /// # Ejemplo
 /// ```
 /// # use proyecto_taller_1::services::utils::resp_type::RespType;
@@ -201,23 +217,23 @@ pub fn sismember(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType
 /// # let db = Database::new("dummy_db_smembers.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 /// let mut set = HashSet::new();
-/// set.insert("kiwi".to_string());
-/// set.insert("pomelo".to_string());
-/// set.insert("sandia".to_string());
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
+/// set.insert(b"kiwi".to_vec());
+/// set.insert(b"pomelo".to_vec());
+/// set.insert(b"sandia".to_vec());
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
 ///     ValueType::SetType(set)
 /// ).build());
 ///
 /// let res = command_set::smembers(&vec![
-///     RespType::RBulkString("SMEMBERS".to_string()),
-///     RespType::RBulkString("frutas".to_string())],
+///     RespType::RBulkString("SMEMBERS".into()),
+///     RespType::RBulkString("frutas".into())],
 ///     &database);
 ///
 /// # match res {
 /// # RespType::RArray(array) => {
-/// assert!(array.contains(&RespType::RBulkString("kiwi".to_string())));
-/// assert!(array.contains(&RespType::RBulkString("pomelo".to_string())));
-/// assert!(array.contains(&RespType::RBulkString("sandia".to_string())));
+/// assert!(array.contains(&RespType::RBulkString("kiwi".into())));
+/// assert!(array.contains(&RespType::RBulkString("pomelo".into())));
+/// assert!(array.contains(&RespType::RBulkString("sandia".into())));
 ///# }
 /// # _ => assert!(false)
 /// # }
@@ -225,30 +241,33 @@ pub fn sismember(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType
 /// # let _ = std::fs::remove_file("dummy_db_smembers.csv");
 /// ```
 pub fn smembers(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
-    if cmd.len() > 1 {
-        if let RespType::RBulkString(key) = &cmd[1] {
-            let db = database
-                .read()
-                .expect("Could not get database read lock on smembers");
-            let (item, expired) = db.check_timeout_item(key);
-            if item.is_some() && expired {
-                drop(db);
-                database
-                    .write()
-                    .expect("Could not get database write lock on smembers")
-                    .remove_expired_key(key)
-            }
-            let db = database
-                .read()
-                .expect("Could not get database read lock on smembers");
-            let members = db.get_members_of_set(key);
-            return RespType::RArray(
-                members
-                    .iter()
-                    .map(|member| RespType::RBulkString(member.to_string()))
-                    .collect(),
-            );
+    if cmd.len() != 2 {
+        return wrong_number_of_arguments("smembers");
+    }
+    if let Some(key) = cmd[1].as_bytes() {
+        let db = database
+            .read()
+            .expect("Could not get database read lock on smembers");
+        let (item, expired) = db.check_timeout_item(key);
+        if item.is_some() && expired {
+            drop(db);
+            database
+                .write()
+                .expect("Could not get database write lock on smembers")
+                .remove_expired_key(key)
         }
+        let db = database
+            .read()
+            .expect("Could not get database read lock on smembers");
+        return match db.get_set(key) {
+            Ok(set) => RespType::RArray(
+                set.into_iter()
+                    .flatten()
+                    .map(|member| RespType::RBulkString(member.to_vec()))
+                    .collect(),
+            ),
+            Err(e) => database_error_to_resp(e),
+        };
     }
     RespType::RNullArray()
 }
@@ -272,18 +291,18 @@ pub fn smembers(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType
 /// # let db = Database::new("dummy_db_srem.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
 /// let mut set = HashSet::new();
-/// set.insert("kiwi".to_string());
-/// set.insert("pomelo".to_string());
-/// set.insert("sandia".to_string());
-/// database.write().unwrap().add("frutas".to_string(),ValueTimeItemBuilder::new(
+/// set.insert(b"kiwi".to_vec());
+/// set.insert(b"pomelo".to_vec());
+/// set.insert(b"sandia".to_vec());
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
 ///     ValueType::SetType(set)
 /// ).build());
 ///
 /// let res = command_set::srem(&vec![
-///     RespType::RBulkString("SREM".to_string()),
-///     RespType::RBulkString("frutas".to_string()),
-///     RespType::RBulkString("sandia".to_string()),
-///     RespType::RBulkString("pomelo".to_string())],
+///     RespType::RBulkString("SREM".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("sandia".into()),
+///     RespType::RBulkString("pomelo".into())],
 ///     &database);
 ///
 /// assert_eq!(res, RespType::RInteger(2));
@@ -292,12 +311,12 @@ pub fn smembers(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType
 pub fn srem(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     let mut deleted = 0;
     if cmd.len() > 1 {
-        if let RespType::RBulkString(key) = &cmd[1] {
+        if let Some(key) = cmd[1].as_bytes() {
             let mut db = database
                 .write()
                 .expect("Could not get database lock on srem");
             for n in cmd.iter().skip(2) {
-                if let RespType::RBulkString(member) = n {
+                if let Some(member) = n.as_bytes() {
                     let removed = db.remove_member_from_set(key, member);
                     match removed {
                         Some(rem) => {
@@ -306,10 +325,7 @@ pub fn srem(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
                             }
                         }
                         None => {
-                            return RespType::RError(format!(
-                                "Value stored at key {} is not a Set",
-                                key
-                            ));
+                            return RespType::RError(String::from(WRONGTYPE));
                         }
                     }
                 }
@@ -318,3 +334,612 @@ pub fn srem(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     }
     RespType::RInteger(deleted)
 }
+
+/// Retorna los elementos que pertenecen a todos los SETs almacenados en las claves especificadas.
+///
+/// Las claves que no existen se consideran SETs vacíos, por lo que la intersección resulta vacía.
+/// Si alguna clave almacena un valor que no es de tipo SET, retorna Error.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_set;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+/// # use std::collections::HashSet;
+///
+/// # let db = Database::new("dummy_db_sinter_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"pomelo".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"palta".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"verdes".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+///
+/// let res = command_set::sinter(&vec![
+///     RespType::RBulkString("SINTER".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("verdes".into())],
+///     &database);
+///
+/// assert_eq!(res, RespType::RArray(vec![RespType::RBulkString("kiwi".into())]));
+/// # let _ = std::fs::remove_file("dummy_db_sinter_command.csv");
+/// ```
+pub fn sinter(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() < 2 {
+        return RespType::RError(String::from("Invalid command sinter"));
+    }
+    let db = database
+        .read()
+        .expect("Could not get database read lock on sinter");
+    match db.intersect_sets(&get_keys(&cmd[1..])) {
+        Ok(set) => members_to_resp(set),
+        Err(e) => database_error_to_resp(e),
+    }
+}
+
+/// Retorna los elementos que pertenecen a alguno de los SETs almacenados en las claves especificadas.
+///
+/// Las claves que no existen se consideran SETs vacíos.
+/// Si alguna clave almacena un valor que no es de tipo SET, retorna Error.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_set;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+/// # use std::collections::HashSet;
+///
+/// # let db = Database::new("dummy_db_sunion_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"pomelo".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"palta".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"verdes".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+///
+/// let res = command_set::sunion(&vec![
+///     RespType::RBulkString("SUNION".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("verdes".into())],
+///     &database);
+///
+/// # match res {
+/// # RespType::RArray(array) => {
+/// assert_eq!(array.len(), 3);
+/// # }
+/// # _ => assert!(false)
+/// # }
+/// # let _ = std::fs::remove_file("dummy_db_sunion_command.csv");
+/// ```
+pub fn sunion(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() < 2 {
+        return RespType::RError(String::from("Invalid command sunion"));
+    }
+    let db = database
+        .read()
+        .expect("Could not get database read lock on sunion");
+    match db.union_sets(&get_keys(&cmd[1..])) {
+        Ok(set) => members_to_resp(set),
+        Err(e) => database_error_to_resp(e),
+    }
+}
+
+/// Retorna los elementos del SET almacenado en la primera clave que no pertenecen a los SETs de las claves restantes.
+///
+/// Las claves que no existen se consideran SETs vacíos.
+/// Si alguna clave almacena un valor que no es de tipo SET, retorna Error.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_set;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+/// # use std::collections::HashSet;
+///
+/// # let db = Database::new("dummy_db_sdiff_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"pomelo".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"palta".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"verdes".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+///
+/// let res = command_set::sdiff(&vec![
+///     RespType::RBulkString("SDIFF".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("verdes".into())],
+///     &database);
+///
+/// assert_eq!(res, RespType::RArray(vec![RespType::RBulkString("pomelo".into())]));
+/// # let _ = std::fs::remove_file("dummy_db_sdiff_command.csv");
+/// ```
+pub fn sdiff(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() < 2 {
+        return RespType::RError(String::from("Invalid command sdiff"));
+    }
+    let db = database
+        .read()
+        .expect("Could not get database read lock on sdiff");
+    match db.diff_sets(&get_keys(&cmd[1..])) {
+        Ok(set) => members_to_resp(set),
+        Err(e) => database_error_to_resp(e),
+    }
+}
+
+/// Igual que SINTER, pero guarda el resultado en la clave `destination`.
+///
+/// Si `destination` ya existe, se reemplaza su valor. Si el resultado es vacío, se elimina `destination`.
+/// Retorna la cantidad de elementos del SET resultante.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_set;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+/// # use std::collections::HashSet;
+///
+/// # let db = Database::new("dummy_db_sinterstore_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"pomelo".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"palta".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"verdes".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+///
+/// let res = command_set::sinterstore(&vec![
+///     RespType::RBulkString("SINTERSTORE".into()),
+///     RespType::RBulkString("frutas_verdes".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("verdes".into())],
+///     &database);
+///
+/// assert_eq!(res, RespType::RInteger(1));
+/// assert_eq!(database.read().unwrap().is_member_of_set(b"frutas_verdes", b"kiwi"), 1);
+/// # let _ = std::fs::remove_file("dummy_db_sinterstore_command.csv");
+/// ```
+pub fn sinterstore(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    store(cmd, database, Database::intersect_sets)
+}
+
+/// Igual que SUNION, pero guarda el resultado en la clave `destination`.
+///
+/// Si `destination` ya existe, se reemplaza su valor. Si el resultado es vacío, se elimina `destination`.
+/// Retorna la cantidad de elementos del SET resultante.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_set;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+/// # use std::collections::HashSet;
+///
+/// # let db = Database::new("dummy_db_sunionstore_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"pomelo".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+///
+/// let res = command_set::sunionstore(&vec![
+///     RespType::RBulkString("SUNIONSTORE".into()),
+///     RespType::RBulkString("frutas_copia".into()),
+///     RespType::RBulkString("frutas".into())],
+///     &database);
+///
+/// assert_eq!(res, RespType::RInteger(2));
+/// # let _ = std::fs::remove_file("dummy_db_sunionstore_command.csv");
+/// ```
+pub fn sunionstore(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    store(cmd, database, Database::union_sets)
+}
+
+/// Igual que SDIFF, pero guarda el resultado en la clave `destination`.
+///
+/// Si `destination` ya existe, se reemplaza su valor. Si el resultado es vacío, se elimina `destination`.
+/// Retorna la cantidad de elementos del SET resultante.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_set;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+/// # use std::collections::HashSet;
+///
+/// # let db = Database::new("dummy_db_sdiffstore_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"pomelo".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+///
+/// let res = command_set::sdiffstore(&vec![
+///     RespType::RBulkString("SDIFFSTORE".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("frutas".into())],
+///     &database);
+///
+/// assert_eq!(res, RespType::RInteger(0));
+/// assert!(database.write().unwrap().get_live_item(b"frutas").is_none());
+/// # let _ = std::fs::remove_file("dummy_db_sdiffstore_command.csv");
+/// ```
+pub fn sdiffstore(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    store(cmd, database, Database::diff_sets)
+}
+
+/// Retorna la cantidad de elementos de la intersección de los SETs almacenados en las claves especificadas.
+///
+/// El comando recibe `SINTERCARD numkeys key [key ...] [LIMIT limit]`.
+/// Si se especifica LIMIT (distinto de 0), el resultado no supera dicho límite.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_set;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+/// # use std::collections::HashSet;
+///
+/// # let db = Database::new("dummy_db_sintercard_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"pomelo".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+///
+/// let res = command_set::sintercard(&vec![
+///     RespType::RBulkString("SINTERCARD".into()),
+///     RespType::RBulkString("1".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("limit".into()),
+///     RespType::RBulkString("1".into())],
+///     &database);
+///
+/// assert_eq!(res, RespType::RInteger(1));
+///
+/// let res = command_set::sintercard(&vec![
+///     RespType::RBulkString("SINTERCARD".into()),
+///     RespType::RBulkString("18446744073709551615".into()),
+///     RespType::RBulkString("frutas".into())],
+///     &database);
+///
+/// assert_eq!(res, RespType::RError("numkeys should be greater than 0".to_string()));
+/// # let _ = std::fs::remove_file("dummy_db_sintercard_command.csv");
+/// ```
+pub fn sintercard(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() < 3 {
+        return RespType::RError(String::from("Invalid command sintercard"));
+    }
+    let numkeys = match cmd[1].as_str() {
+        Some(numkeys) => match numkeys.parse::<isize>() {
+            Ok(n) if n > 0 => n as usize,
+            _ => return RespType::RError(String::from("numkeys should be greater than 0")),
+        },
+        _ => return RespType::RError(String::from("Invalid command sintercard")),
+    };
+    if numkeys > cmd.len() - 2 {
+        return RespType::RError(String::from(
+            "Number of keys can't be greater than number of args",
+        ));
+    }
+    let mut limit = 0;
+    if cmd.len() == numkeys + 4 {
+        if let (Some(option), Some(value)) = (cmd[numkeys + 2].as_str(), cmd[numkeys + 3].as_str())
+        {
+            if !option.eq_ignore_ascii_case("limit") {
+                return RespType::RError(String::from("syntax error"));
+            }
+            match value.parse::<usize>() {
+                Ok(l) => limit = l,
+                _ => return RespType::RError(String::from("LIMIT can't be negative")),
+            }
+        }
+    } else if cmd.len() != numkeys + 2 {
+        return RespType::RError(String::from("syntax error"));
+    }
+    let db = database
+        .read()
+        .expect("Could not get database read lock on sintercard");
+    match db.intersect_sets(&get_keys(&cmd[2..numkeys + 2])) {
+        Ok(set) if limit != 0 => RespType::RInteger(set.len().min(limit)),
+        Ok(set) => RespType::RInteger(set.len()),
+        Err(e) => database_error_to_resp(e),
+    }
+}
+
+/// Retorna, para cada elemento especificado, si pertenece al SET almacenado en `key`.
+///
+/// Devuelve un array con 1 por cada elemento que pertenece al SET y 0 por cada elemento que no.
+/// Si la clave no existe, todos los valores son 0.
+/// Si el valor almacenado no es de tipo SET, retorna Error.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_set;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+/// # use std::collections::HashSet;
+///
+/// # let db = Database::new("dummy_db_smismember_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"pomelo".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+///
+/// let res = command_set::smismember(&vec![
+///     RespType::RBulkString("SMISMEMBER".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("kiwi".into()),
+///     RespType::RBulkString("palta".into())],
+///     &database);
+///
+/// assert_eq!(res, RespType::RArray(vec![RespType::RInteger(1), RespType::RInteger(0)]));
+/// # let _ = std::fs::remove_file("dummy_db_smismember_command.csv");
+/// ```
+pub fn smismember(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() > 2 {
+        if let Some(key) = cmd[1].as_bytes() {
+            let db = database
+                .read()
+                .expect("Could not get database read lock on smismember");
+            let set = match db.get_set(key) {
+                Ok(set) => set,
+                Err(e) => return database_error_to_resp(e),
+            };
+            return RespType::RArray(
+                cmd[2..]
+                    .iter()
+                    .filter_map(RespType::as_bytes)
+                    .map(|member| {
+                        RespType::RInteger(set.is_some_and(|set| set.contains(member)) as usize)
+                    })
+                    .collect(),
+            );
+        }
+    }
+    RespType::RError(String::from("Invalid command smismember"))
+}
+
+/// Mueve un elemento del SET almacenado en `source` al SET almacenado en `destination`.
+///
+/// Si `destination` no existe, se crea.
+/// Retorna 1 si el elemento fue movido y 0 si no pertenecía a `source`.
+/// Si alguna de las claves almacena un valor que no es de tipo SET, retorna Error.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_set;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+/// # use std::collections::HashSet;
+///
+/// # let db = Database::new("dummy_db_smove_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"pomelo".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+///
+/// let res = command_set::smove(&vec![
+///     RespType::RBulkString("SMOVE".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("compradas".into()),
+///     RespType::RBulkString("kiwi".into())],
+///     &database);
+///
+/// assert_eq!(res, RespType::RInteger(1));
+/// # let _ = std::fs::remove_file("dummy_db_smove_command.csv");
+/// ```
+pub fn smove(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 4 {
+        if let (Some(source), Some(destination), Some(member)) =
+            (cmd[1].as_bytes(), cmd[2].as_bytes(), cmd[3].as_bytes())
+        {
+            let mut db = database
+                .write()
+                .expect("Could not get database lock on smove");
+            return match db.move_member_between_sets(source, destination, member) {
+                Ok(moved) => RespType::RInteger(moved as usize),
+                Err(e) => database_error_to_resp(e),
+            };
+        }
+    }
+    RespType::RError(String::from("Invalid command smove"))
+}
+
+/// Elimina y retorna elementos al azar del SET almacenado en `key`.
+///
+/// Sin el parámetro `count`, retorna un único elemento o `nil` si la clave no existe.
+/// Con `count`, retorna un array con hasta `count` elementos.
+/// Si el SET queda vacío, se elimina la clave.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_set;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+/// # use std::collections::HashSet;
+///
+/// # let db = Database::new("dummy_db_spop_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec(), b"pomelo".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+///
+/// let res = command_set::spop(&vec![
+///     RespType::RBulkString("SPOP".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("5".into())],
+///     &database);
+///
+/// # match res {
+/// # RespType::RArray(array) => {
+/// assert_eq!(array.len(), 2);
+/// # }
+/// # _ => assert!(false)
+/// # }
+/// assert!(database.write().unwrap().get_live_item(b"frutas").is_none());
+/// # let _ = std::fs::remove_file("dummy_db_spop_command.csv");
+/// ```
+pub fn spop(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 2 || cmd.len() == 3 {
+        if let Some(key) = cmd[1].as_bytes() {
+            let count = match cmd.get(2).map(RespType::as_str) {
+                Some(Some(count)) => match count.parse::<usize>() {
+                    Ok(c) => Some(c),
+                    Err(_) => {
+                        return RespType::RError(String::from(
+                            "value is out of range, must be positive",
+                        ))
+                    }
+                },
+                _ => None,
+            };
+            let mut db = database
+                .write()
+                .expect("Could not get database lock on spop");
+            return match db.pop_members_from_set(key, count.unwrap_or(1)) {
+                Ok(popped) => match count {
+                    Some(_) => members_to_resp(popped),
+                    None => match popped.into_iter().next() {
+                        Some(member) => RespType::RBulkString(member),
+                        None => RespType::RNullBulkString(),
+                    },
+                },
+                Err(e) => database_error_to_resp(e),
+            };
+        }
+    }
+    RespType::RError(String::from("Invalid command spop"))
+}
+
+/// Retorna elementos al azar del SET almacenado en `key`, sin eliminarlos.
+///
+/// Sin el parámetro `count`, retorna un único elemento o `nil` si la clave no existe.
+/// Si `count` es positivo, retorna un array con hasta `count` elementos distintos.
+/// Si `count` es negativo, retorna un array con exactamente `|count|` elementos, que pueden repetirse.
+/// Si `|count|` supera el millón de elementos, retorna error.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::services::commands::command_set;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+/// # use std::collections::HashSet;
+///
+/// # let db = Database::new("dummy_db_srandmember_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// let set: HashSet<Vec<u8>> = vec![b"kiwi".to_vec()].into_iter().collect();
+/// database.write().unwrap().add(b"frutas".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::SetType(set)).build());
+///
+/// let res = command_set::srandmember(&vec![
+///     RespType::RBulkString("SRANDMEMBER".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("-3".into())],
+///     &database);
+///
+/// assert_eq!(res, RespType::RArray(vec![RespType::RBulkString("kiwi".into()); 3]));
+/// # let _ = std::fs::remove_file("dummy_db_srandmember_command.csv");
+/// ```
+pub fn srandmember(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 2 || cmd.len() == 3 {
+        if let Some(key) = cmd[1].as_bytes() {
+            let count = match cmd.get(2).map(RespType::as_str) {
+                Some(Some(count)) => match count.parse::<isize>() {
+                    Ok(c) if c < -SRANDMEMBER_MAX_COUNT => {
+                        return RespType::RError(String::from("value is out of range"))
+                    }
+                    Ok(c) => Some(c),
+                    Err(_) => {
+                        return RespType::RError(String::from(
+                            "value is not an integer or out of range",
+                        ))
+                    }
+                },
+                _ => None,
+            };
+            let db = database
+                .read()
+                .expect("Could not get database read lock on srandmember");
+            return match db.get_random_members_of_set(key, count.unwrap_or(1)) {
+                Ok(members) => match count {
+                    Some(_) => members_to_resp(members),
+                    None => match members.into_iter().next() {
+                        Some(member) => RespType::RBulkString(member),
+                        None => RespType::RNullBulkString(),
+                    },
+                },
+                Err(e) => database_error_to_resp(e),
+            };
+        }
+    }
+    RespType::RError(String::from("Invalid command srandmember"))
+}
+
+type SetOperation = fn(&Database, &[Vec<u8>]) -> Result<HashSet<Vec<u8>>, DatabaseError>;
+
+/// Aplica `operation` sobre las claves de `cmd[2..]` y guarda el resultado en `cmd[1]`.
+fn store(cmd: &[RespType], database: &Arc<RwLock<Database>>, operation: SetOperation) -> RespType {
+    if cmd.len() > 2 {
+        if let Some(destination) = cmd[1].as_bytes() {
+            let mut db = database
+                .write()
+                .expect("Could not get database lock on store");
+            return match operation(&db, &get_keys(&cmd[2..])) {
+                Ok(set) => {
+                    let event = cmd[0].as_str().unwrap_or_default().to_lowercase();
+                    RespType::RInteger(db.store_set(destination, set, &event))
+                }
+                Err(e) => database_error_to_resp(e),
+            };
+        }
+    }
+    RespType::RError(String::from("Invalid request"))
+}
+
+fn get_keys(cmd: &[RespType]) -> Vec<Vec<u8>> {
+    cmd.iter()
+        .filter_map(|key| key.as_bytes().map(<[u8]>::to_vec))
+        .collect()
+}
+
+fn members_to_resp<T: IntoIterator<Item = Vec<u8>>>(members: T) -> RespType {
+    RespType::RArray(members.into_iter().map(RespType::RBulkString).collect())
+}
+
+fn wrong_number_of_arguments(command: &str) -> RespType {
+    RespType::RError(format!(
+        "ERR wrong number of arguments for '{}' command",
+        command
+    ))
+}
+
+fn database_error_to_resp(error: DatabaseError) -> RespType {
+    match error {
+        DatabaseError::InvalidParameter(err) => RespType::RError(err),
+        DatabaseError::InvalidValueType(err) => RespType::RError(err),
+        DatabaseError::MissingKey() => RespType::RNullBulkString(),
+    }
+}
@@ -2,7 +2,9 @@
 
 use crate::domain::entities::key_value_item::ValueTimeItemBuilder;
 use crate::domain::entities::key_value_item::ValueType;
+use crate::domain::entities::keyspace_events::EventClass;
 use crate::errors::database_error::DatabaseError;
+use crate::services::utils::lcs::longest_common_subsequence;
 use crate::{domain::implementations::database::Database, services::utils::resp_type::RespType};
 use std::vec;
 use std::{
@@ -25,14 +27,14 @@ use std::{
 ///
 /// # let db = Database::new("dummy_db_append.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("animal".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType("puerco".to_string())
+/// database.write().unwrap().add(b"animal".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("puerco".into())
 /// ).build());
 ///
 /// let res = command_string::append(&vec![
-///     RespType::RBulkString("APPEND".to_string()),
-///     RespType::RBulkString("animal".to_string()),
-///     RespType::RBulkString("espin".to_string()),
+///     RespType::RBulkString("APPEND".into()),
+///     RespType::RBulkString("animal".into()),
+///     RespType::RBulkString("espin".into()),
 /// ], &database);
 ///
 /// # match res {
@@ -46,11 +48,11 @@ use std::{
 /// ```
 pub fn append(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() > 2 {
-        if let RespType::RBulkString(key) = &cmd[1] {
+        if let Some(key) = cmd[1].as_bytes() {
             let mut db = database
                 .write()
                 .expect("Could not get database lock on append");
-            if let RespType::RBulkString(str_to_append) = &cmd[2] {
+            if let Some(str_to_append) = cmd[2].as_bytes() {
                 return RespType::RInteger(db.append_string(key, str_to_append));
             }
         }
@@ -74,14 +76,14 @@ pub fn append(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_decrby.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("edad".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType("30".to_string())
+/// database.write().unwrap().add(b"edad".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("30".into())
 /// ).build());
 ///
 /// let res = command_string::decrby(&vec![
-///     RespType::RBulkString("DECRBY".to_string()),
-///     RespType::RBulkString("edad".to_string()),
-///     RespType::RBulkString("7".to_string()),
+///     RespType::RBulkString("DECRBY".into()),
+///     RespType::RBulkString("edad".into()),
+///     RespType::RBulkString("7".into()),
 /// ], &database);
 ///
 /// # match res {
@@ -95,11 +97,11 @@ pub fn append(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// ```
 pub fn decrby(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() > 2 {
-        if let RespType::RBulkString(key) = &cmd[1] {
+        if let Some(key) = cmd[1].as_bytes() {
             let mut db = database
                 .write()
                 .expect("Could not get database lock on decrby");
-            if let RespType::RBulkString(decr) = &cmd[2] {
+            if let Some(decr) = cmd[2].as_str() {
                 let number = decr.parse::<i64>();
                 return match number {
                     Ok(decr) => match db.decrement_key_by(key, decr) {
@@ -134,14 +136,14 @@ pub fn decrby(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_incrby.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("edad".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType("30".to_string())
+/// database.write().unwrap().add(b"edad".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("30".into())
 /// ).build());
 ///
 /// let res = command_string::incrby(&vec![
-///     RespType::RBulkString("INCRBY".to_string()),
-///     RespType::RBulkString("edad".to_string()),
-///     RespType::RBulkString("7".to_string()),
+///     RespType::RBulkString("INCRBY".into()),
+///     RespType::RBulkString("edad".into()),
+///     RespType::RBulkString("7".into()),
 /// ], &database);
 ///
 /// # match res {
@@ -155,15 +157,15 @@ pub fn decrby(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// ```
 pub fn incrby(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() > 2 {
-        if let RespType::RBulkString(key) = &cmd[1] {
+        if let Some(key) = cmd[1].as_bytes() {
             let mut db = database
                 .write()
                 .expect("Could not get database lock on incrby");
-            if let RespType::RBulkString(incr) = &cmd[2] {
+            if let Some(incr) = cmd[2].as_str() {
                 let number = incr.parse::<i64>();
                 return match number {
                     Ok(incr) => match db.increment_key_by(key, incr) {
-                        Ok(res) => RespType::RInteger(res.try_into().unwrap()),
+                        Ok(res) => integer_to_resp(res),
                         Err(e) => match e {
                             DatabaseError::InvalidParameter(err) => RespType::RError(err),
                             DatabaseError::InvalidValueType(err) => RespType::RError(err),
@@ -193,18 +195,18 @@ pub fn incrby(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_get.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("nombre".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType("alfonso".to_string())
+/// database.write().unwrap().add(b"nombre".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("alfonso".into())
 /// ).build());
 ///
 /// let res = command_string::get(&vec![
-///     RespType::RBulkString("GET".to_string()),
-///     RespType::RBulkString("nombre".to_string()),
+///     RespType::RBulkString("GET".into()),
+///     RespType::RBulkString("nombre".into()),
 /// ], &database);
 ///
 /// # match res {
 /// #    RespType::RBulkString(n) => {
-///     assert_eq!(n, "alfonso".to_string())
+///     assert_eq!(n, b"alfonso")
 /// # }
 /// #    _ => assert!(false)
 /// # }
@@ -213,7 +215,7 @@ pub fn incrby(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// ```
 pub fn get(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() > 1 {
-        if let RespType::RBulkString(key) = &cmd[1] {
+        if let Some(key) = cmd[1].as_bytes() {
             let db = database
                 .read()
                 .expect("Could not get database read lock on get");
@@ -255,22 +257,22 @@ pub fn get(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_mget.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("nombre".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType("alfonso".to_string())
+/// database.write().unwrap().add(b"nombre".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("alfonso".into())
 /// ).build());
-/// database.write().unwrap().add("apellido".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType("alvarez".to_string())
+/// database.write().unwrap().add(b"apellido".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("alvarez".into())
 /// ).build());
 ///
 /// let res = command_string::mget(&vec![
-///     RespType::RBulkString("MGET".to_string()),
-///     RespType::RBulkString("nombre".to_string()),
-///     RespType::RBulkString("apellido".to_string()),
+///     RespType::RBulkString("MGET".into()),
+///     RespType::RBulkString("nombre".into()),
+///     RespType::RBulkString("apellido".into()),
 /// ], &database);
 ///
 /// # match res {
 /// #    RespType::RArray(full_name) => {
-///    assert_eq!(full_name, vec![RespType::RBulkString("alfonso".to_string()), RespType::RBulkString("alvarez".to_string())])
+///    assert_eq!(full_name, vec![RespType::RBulkString("alfonso".into()), RespType::RBulkString("alvarez".into())])
 /// # }
 /// #    _ => assert!(false)
 /// # }
@@ -284,7 +286,7 @@ pub fn mget(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
             let db = database
                 .read()
                 .expect("Could not get database read lock on mget");
-            if let RespType::RBulkString(current_key) = n {
+            if let Some(current_key) = n.as_bytes() {
                 let (item, expired) = db.check_timeout_item(current_key);
                 if item.is_some() && expired {
                     drop(db);
@@ -324,19 +326,19 @@ pub fn mget(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_getdel.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("nombre".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType("alfonso".to_string())
+/// database.write().unwrap().add(b"nombre".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("alfonso".into())
 /// ).build());
 ///
 /// let res = command_string::getdel(&vec![
-///     RespType::RBulkString("GETDEL".to_string()),
-///     RespType::RBulkString("nombre".to_string()),
-///     RespType::RBulkString("alfredo".to_string()),
+///     RespType::RBulkString("GETDEL".into()),
+///     RespType::RBulkString("nombre".into()),
+///     RespType::RBulkString("alfredo".into()),
 /// ], &database);
 ///
 /// # match res {
 /// #    RespType::RBulkString(old_name) => {
-///         assert_eq!(old_name, "alfonso".to_string())
+///         assert_eq!(old_name, b"alfonso")
 /// #    }
 /// #    _ => assert!(false)
 /// # }
@@ -345,7 +347,7 @@ pub fn mget(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// ```
 pub fn getdel(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() > 1 {
-        if let RespType::RBulkString(key) = &cmd[1] {
+        if let Some(key) = cmd[1].as_bytes() {
             let mut db = database
                 .write()
                 .expect("Could not get database write lock on getdel");
@@ -378,19 +380,19 @@ pub fn getdel(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_getset.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("nombre".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType("alfonso".to_string())
+/// database.write().unwrap().add(b"nombre".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("alfonso".into())
 /// ).build());
 ///
 /// let res = command_string::getdel(&vec![
-///     RespType::RBulkString("GETSET".to_string()),
-///     RespType::RBulkString("nombre".to_string()),
-///     RespType::RBulkString("alfredo".to_string()),
+///     RespType::RBulkString("GETSET".into()),
+///     RespType::RBulkString("nombre".into()),
+///     RespType::RBulkString("alfredo".into()),
 /// ], &database);
 ///
 /// # match res {
 /// #    RespType::RBulkString(old_name) => {
-///         assert_eq!(old_name, "alfonso".to_string())
+///         assert_eq!(old_name, b"alfonso")
 /// #    }
 /// #    _ => assert!(false)
 /// # }
@@ -399,11 +401,11 @@ pub fn getdel(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// ```
 pub fn getset(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() > 2 {
-        if let RespType::RBulkString(key) = &cmd[1] {
+        if let Some(key) = cmd[1].as_bytes() {
             let mut db = database
                 .write()
                 .expect("Could not get database lock on getset");
-            if let RespType::RBulkString(new_value) = &cmd[2] {
+            if let Some(new_value) = cmd[2].as_bytes() {
                 return match db.getset_value_by_key(key, new_value) {
                     Ok(str) => RespType::RBulkString(str),
                     Err(e) => match e {
@@ -433,13 +435,13 @@ pub fn getset(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 ///
 /// # let db = Database::new("dummy_db_strlen.csv".to_string());
 /// # let mut database = Arc::new(RwLock::new(db));
-/// database.write().unwrap().add("animal".to_string(),ValueTimeItemBuilder::new(
-///     ValueType::StringType("puerco".to_string())
+/// database.write().unwrap().add(b"animal".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("puerco".into())
 /// ).build());
 ///
 /// let res = command_string::strlen(&vec![
-///     RespType::RBulkString("STRLEN".to_string()),
-///     RespType::RBulkString("animal".to_string()),
+///     RespType::RBulkString("STRLEN".into()),
+///     RespType::RBulkString("animal".into()),
 /// ], &database);
 ///
 /// # match res {
@@ -453,7 +455,7 @@ pub fn getset(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// ```
 pub fn strlen(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
     if cmd.len() > 1 {
-        if let RespType::RBulkString(key) = &cmd[1] {
+        if let Some(key) = cmd[1].as_bytes() {
             let db = database
                 .read()
                 .expect("Could not get database read lock on strlen");
@@ -493,16 +495,16 @@ pub fn strlen(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
 /// let res = command_string::mset(&vec![
-///     RespType::RBulkString("MSET".to_string()),
-///     RespType::RBulkString("nombre".to_string()),
-///     RespType::RBulkString("alfredo".to_string()),
-///     RespType::RBulkString("apellido".to_string()),
-///     RespType::RBulkString("alvarez".to_string()),
+///     RespType::RBulkString("MSET".into()),
+///     RespType::RBulkString("nombre".into()),
+///     RespType::RBulkString("alfredo".into()),
+///     RespType::RBulkString("apellido".into()),
+///     RespType::RBulkString("alvarez".into()),
 /// ], &database);
 ///
 /// # match res {
 /// #    RespType::RBulkString(response) => {
-///         assert_eq!(response, "Ok".to_string())
+///         assert_eq!(response, b"Ok")
 /// #    }
 /// #    _ => assert!(false)
 /// # }
@@ -514,37 +516,36 @@ pub fn mset(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
         .write()
         .expect("Could not get database lock on mset");
     if cmd.len() % 2 == 1 {
-        let mut vec_aux = vec![];
-        for elemento in cmd.iter().skip(1) {
-            if let RespType::RBulkString(current_elemento) = elemento {
-                vec_aux.push(current_elemento.to_string());
+        for pair in cmd[1..].chunks(2) {
+            if let (Some(key), Some(value)) = (pair[0].as_bytes(), pair[1].as_bytes()) {
+                let vt_item =
+                    ValueTimeItemBuilder::new(ValueType::StringType(value.to_vec())).build();
+                db.add(key.to_vec(), vt_item);
+                db.notify(EventClass::String, "set", key);
             }
         }
-        for (pos, e) in vec_aux.iter().enumerate().step_by(2) {
-            let vt_item =
-                ValueTimeItemBuilder::new(ValueType::StringType(vec_aux[pos + 1].to_string()))
-                    .build();
-            db.add(e.to_string(), vt_item);
-        }
-        RespType::RBulkString("Ok".to_string())
+        RespType::RBulkString("Ok".into())
     } else {
-        RespType::RBulkString("One or more parameters are missing".to_string())
+        RespType::RBulkString("One or more parameters are missing".into())
     }
 }
 
 /// Actualiza el valor de las clave especificada.
 ///
-/// Si la clave ya contenía un valor, lo reemplaza sin importar el tipo de dato.
+/// Si la clave ya contenía un valor, lo reemplaza sin importar el tipo de dato, y descarta su tiempo de expiración.
 /// Admite los siguientes parámetros:
 /// * EX: Tiempo de expiración en segundos.
 /// * PX: Tiempo de expiración en milisegundos.
 /// * EXAT: Tiempo UNIX en que va a expirar la clave, en segundos.
 /// * PXAT: Tiempo UNIX en que va a expirar la clave, en milisegundos.
+/// * KEEPTTL: Conserva el tiempo de expiración que tenía la clave.
 /// * NX: Actualiza la clave solo si no existia previamente.
 /// * XX: Actualiza la clave solo si ya existía previamente.
+/// * GET: Devuelve el valor anterior de la clave, o `nil` si no existía.
 ///
 /// Esta función devuelve `Ok` si la clave fue actualizada correctamente.
 /// Si recibe el parametro NX o XX y no se cumple la condición, devuelve `nil`.
+/// Con el parámetro GET, devuelve error si el valor anterior no es de tipo string, y en ese caso no actualiza la clave.
 ///
 /// # Ejemplo
 /// ```
@@ -557,14 +558,14 @@ pub fn mset(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # let mut database = Arc::new(RwLock::new(db));
 ///
 /// let res = command_string::set(&vec![
-///     RespType::RBulkString("SET".to_string()),
-///     RespType::RBulkString("nombre".to_string()),
-///     RespType::RBulkString("alfredo".to_string()),
+///     RespType::RBulkString("SET".into()),
+///     RespType::RBulkString("nombre".into()),
+///     RespType::RBulkString("alfredo".into()),
 /// ], &database);
 ///
 /// # match res {
 /// #    RespType::RBulkString(response) => {
-///         assert_eq!(response, "Ok".to_string())
+///         assert_eq!(response, b"Ok")
 /// #    }
 /// #    _ => assert!(false)
 /// # }
@@ -572,16 +573,30 @@ pub fn mset(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # std::fs::remove_file("dummy_db_set.csv");
 /// ```
 pub fn set(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
-    if cmd.len() > 1 {
-        if let RespType::RBulkString(key) = &cmd[1] {
-            if let RespType::RBulkString(value) = &cmd[2] {
+    if cmd.len() > 2 {
+        if let Some(key) = cmd[1].as_bytes() {
+            if let Some(value) = cmd[2].as_bytes() {
                 let options = generate_options(cmd);
+                let keep_ttl = has_flag(cmd, "keepttl");
+                let get = has_flag(cmd, "get");
+                if keep_ttl && options[0].1.is_some() {
+                    return RespType::RError(String::from("syntax error"));
+                }
                 let mut db = database
                     .write()
                     .expect("Could not get database lock on set");
-                let timeout = (&options[0].0.to_owned(), options[0].1);
-                if db.set_string(key, value, timeout, options[1].1) {
-                    return RespType::RBulkString(String::from("Ok"));
+                let old_value = match db.get_string_value_by_key(key) {
+                    Ok(str) => RespType::RBulkString(str),
+                    Err(DatabaseError::MissingKey()) => RespType::RNullBulkString(),
+                    Err(e) if get => return database_error_to_resp(e),
+                    Err(_) => RespType::RNullBulkString(),
+                };
+                let timeout = (&options[0].0, options[0].1.as_ref());
+                let updated = db.set_string(key, value, timeout, options[1].1.as_ref(), keep_ttl);
+                if get {
+                    return old_value;
+                } else if updated {
+                    return RespType::RBulkString("Ok".into());
                 } else {
                     return RespType::RNullBulkString();
                 }
@@ -606,26 +621,27 @@ pub fn set(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
 /// # use proyecto_taller_1::services::utils::resp_type::RespType;
 ///
 /// let cmd = vec![
-///     RespType::RBulkString("SET".to_string()),
-///     RespType::RBulkString("nombre".to_string()),
-///     RespType::RBulkString("alfredo".to_string()),
-///     RespType::RBulkString("px".to_string()),
-///     RespType::RBulkString("10".to_string()),
-///     RespType::RBulkString("xx".to_string())];
+///     RespType::RBulkString("SET".into()),
+///     RespType::RBulkString("nombre".into()),
+///     RespType::RBulkString("alfredo".into()),
+///     RespType::RBulkString("px".into()),
+///     RespType::RBulkString("10".into()),
+///     RespType::RBulkString("xx".into())];
 /// let res = command_string::generate_options(&cmd);
-/// assert_eq!(res, vec![(String::from("px"), Some(&String::from("10"))), (String::from("set_if_exists"), Some(&String::from("xx")))]);
+/// assert_eq!(res, vec![(String::from("px"), Some(String::from("10"))), (String::from("set_if_exists"), Some(String::from("xx")))]);
 /// ```
-pub fn generate_options(cmd: &[RespType]) -> Vec<(String, Option<&String>)> {
+pub fn generate_options(cmd: &[RespType]) -> Vec<(String, Option<String>)> {
     let mut options = vec![
         (String::from("expire_at"), None),
         (String::from("set_if_exists"), None),
     ];
     for (pos, argumento) in cmd.iter().skip(3).enumerate() {
-        if let RespType::RBulkString(arg) = argumento {
+        if let Some(arg) = argumento.as_str() {
+            let arg = arg.to_lowercase();
             if (arg == "ex") || (arg == "px") || (arg == "exat") || (arg == "pxat") {
-                if let RespType::RBulkString(expire_at) = &cmd[pos + 4] {
-                    options[0].0 = arg.to_string();
-                    options[0].1 = Some(expire_at);
+                if let Some(expire_at) = cmd.get(pos + 4).and_then(RespType::as_str) {
+                    options[0].0 = arg;
+                    options[0].1 = Some(expire_at.to_string());
                 }
             } else if arg == "xx" || arg == "nx" {
                 options[1].1 = Some(arg);
@@ -634,3 +650,650 @@ pub fn generate_options(cmd: &[RespType]) -> Vec<(String, Option<&String>)> {
     }
     options
 }
+
+/// Incrementa en una unidad el valor almacenado en `key`.
+///
+/// Si la clave no existe, se crea con valor 0 antes de realizar la operación.
+/// Retorna el resultado de la operación.
+/// Devuelve error si el valor almacenado en `key` no es de tipo string o si no se puede representar como número entero.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::commands::command_string;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_incr.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"edad".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("30".into())
+/// ).build());
+///
+/// let res = command_string::incr(&vec![
+///     RespType::RBulkString("INCR".into()),
+///     RespType::RBulkString("edad".into()),
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RInteger(31));
+///
+/// # let _ = std::fs::remove_file("dummy_db_incr.csv");
+/// ```
+pub fn incr(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 2 {
+        if let Some(key) = cmd[1].as_bytes() {
+            let mut db = database
+                .write()
+                .expect("Could not get database lock on incr");
+            return match db.increment_key_by(key, 1) {
+                Ok(res) => integer_to_resp(res),
+                Err(e) => database_error_to_resp(e),
+            };
+        }
+    }
+    RespType::RError(String::from("Invalid command incr"))
+}
+
+/// Decrementa en una unidad el valor almacenado en `key`.
+///
+/// Si la clave no existe, se crea con valor 0 antes de realizar la operación.
+/// Retorna el resultado de la operación.
+/// Devuelve error si el valor almacenado en `key` no es de tipo string o si no se puede representar como número entero.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::commands::command_string;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+///
+/// # let db = Database::new("dummy_db_decr.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+///
+/// let res = command_string::decr(&vec![
+///     RespType::RBulkString("DECR".into()),
+///     RespType::RBulkString("contador".into()),
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RSignedNumber(-1));
+///
+/// # let _ = std::fs::remove_file("dummy_db_decr.csv");
+/// ```
+pub fn decr(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 2 {
+        if let Some(key) = cmd[1].as_bytes() {
+            let mut db = database
+                .write()
+                .expect("Could not get database lock on decr");
+            return match db.decrement_key_by(key, 1) {
+                Ok(res) => integer_to_resp(res),
+                Err(e) => database_error_to_resp(e),
+            };
+        }
+    }
+    RespType::RError(String::from("Invalid command decr"))
+}
+
+/// Incrementa el valor decimal almacenado en `key` en `incr` unidades.
+///
+/// Si la clave no existe, se crea con valor 0 antes de realizar la operación.
+/// Retorna el resultado de la operación como string.
+/// Devuelve error si el valor almacenado en `key` no es de tipo string o si no se puede representar como número decimal.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::commands::command_string;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_incrbyfloat.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"precio".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("10.50".into())
+/// ).build());
+///
+/// let res = command_string::incrbyfloat(&vec![
+///     RespType::RBulkString("INCRBYFLOAT".into()),
+///     RespType::RBulkString("precio".into()),
+///     RespType::RBulkString("0.1".into()),
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RBulkString("10.6".into()));
+///
+/// # let _ = std::fs::remove_file("dummy_db_incrbyfloat.csv");
+/// ```
+pub fn incrbyfloat(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 3 {
+        if let (Some(key), Some(incr)) = (cmd[1].as_bytes(), cmd[2].as_str()) {
+            let incr = match incr.parse::<f64>() {
+                Ok(incr) if incr.is_finite() => incr,
+                _ => return RespType::RError(String::from("value is not a valid float")),
+            };
+            let mut db = database
+                .write()
+                .expect("Could not get database lock on incrbyfloat");
+            return match db.increment_key_by_float(key, incr) {
+                Ok(res) => RespType::RBulkString(res.into()),
+                Err(e) => database_error_to_resp(e),
+            };
+        }
+    }
+    RespType::RError(String::from("Invalid command incrbyfloat"))
+}
+
+/// Actualiza el valor de la clave especificada y establece su tiempo de expiración en segundos.
+///
+/// Equivale a `SET key value EX seconds`.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::commands::command_string;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+///
+/// # let db = Database::new("dummy_db_setex.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+///
+/// let res = command_string::setex(&vec![
+///     RespType::RBulkString("SETEX".into()),
+///     RespType::RBulkString("sesion".into()),
+///     RespType::RBulkString("60".into()),
+///     RespType::RBulkString("abc123".into()),
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RBulkString("Ok".into()));
+///
+/// # let _ = std::fs::remove_file("dummy_db_setex.csv");
+/// ```
+pub fn setex(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    set_with_expiration(cmd, database, "ex")
+}
+
+/// Actualiza el valor de la clave especificada y establece su tiempo de expiración en milisegundos.
+///
+/// Equivale a `SET key value PX milliseconds`.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::commands::command_string;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+///
+/// # let db = Database::new("dummy_db_psetex.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+///
+/// let res = command_string::psetex(&vec![
+///     RespType::RBulkString("PSETEX".into()),
+///     RespType::RBulkString("sesion".into()),
+///     RespType::RBulkString("0".into()),
+///     RespType::RBulkString("abc123".into()),
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RError("invalid expire time in 'psetex' command".to_string()));
+///
+/// # let _ = std::fs::remove_file("dummy_db_psetex.csv");
+/// ```
+pub fn psetex(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    set_with_expiration(cmd, database, "px")
+}
+
+/// Actualiza el valor de la clave especificada solo si la clave no existe.
+///
+/// Retorna 1 si la clave fue actualizada y 0 si ya existía.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::commands::command_string;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_setnx.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"nombre".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("alfonso".into())
+/// ).build());
+///
+/// let res = command_string::setnx(&vec![
+///     RespType::RBulkString("SETNX".into()),
+///     RespType::RBulkString("nombre".into()),
+///     RespType::RBulkString("alfredo".into()),
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RInteger(0));
+///
+/// # let _ = std::fs::remove_file("dummy_db_setnx.csv");
+/// ```
+pub fn setnx(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 3 {
+        if let (Some(key), Some(value)) = (cmd[1].as_bytes(), cmd[2].as_bytes()) {
+            let mut db = database
+                .write()
+                .expect("Could not get database lock on setnx");
+            let updated = db.set_string(
+                key,
+                value,
+                (&String::new(), None),
+                Some(&String::from("nx")),
+                false,
+            );
+            return RespType::RInteger(updated as usize);
+        }
+    }
+    RespType::RError(String::from("Invalid command setnx"))
+}
+
+/// Actualiza el valor de las claves especificadas, solo si ninguna de ellas existe.
+///
+/// La operación es atómica: se actualizan todas las claves o ninguna.
+/// Retorna 1 si las claves fueron actualizadas y 0 si alguna ya existía.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::commands::command_string;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+///
+/// # let db = Database::new("dummy_db_msetnx.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+///
+/// let res = command_string::msetnx(&vec![
+///     RespType::RBulkString("MSETNX".into()),
+///     RespType::RBulkString("nombre".into()),
+///     RespType::RBulkString("alfredo".into()),
+///     RespType::RBulkString("apellido".into()),
+///     RespType::RBulkString("alvarez".into()),
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RInteger(1));
+///
+/// # let _ = std::fs::remove_file("dummy_db_msetnx.csv");
+/// ```
+pub fn msetnx(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() < 3 || cmd.len().is_multiple_of(2) {
+        return RespType::RError(String::from(
+            "wrong number of arguments for 'msetnx' command",
+        ));
+    }
+    let pairs: Vec<(Vec<u8>, Vec<u8>)> = cmd[1..]
+        .chunks(2)
+        .filter_map(|pair| match (pair[0].as_bytes(), pair[1].as_bytes()) {
+            (Some(key), Some(value)) => Some((key.to_vec(), value.to_vec())),
+            _ => None,
+        })
+        .collect();
+    let mut db = database
+        .write()
+        .expect("Could not get database lock on msetnx");
+    if pairs.iter().any(|(key, _)| db.key_exists(key.to_vec())) {
+        return RespType::RInteger(0);
+    }
+    for (key, value) in pairs {
+        db.add(
+            key.to_vec(),
+            ValueTimeItemBuilder::new(ValueType::StringType(value)).build(),
+        );
+        db.notify(EventClass::String, "set", &key);
+    }
+    RespType::RInteger(1)
+}
+
+/// Devuelve la subcadena del valor almacenado en `key`, entre las posiciones `start` y `end` (inclusive).
+///
+/// Los índices negativos se cuentan desde el final del string.
+/// Si la clave no existe, devuelve un string vacío.
+/// Devuelve error si el valor almacenado en `key` no es de tipo string.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::commands::command_string;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_getrange_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"animal".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("puercoespin".into())
+/// ).build());
+///
+/// let res = command_string::getrange(&vec![
+///     RespType::RBulkString("GETRANGE".into()),
+///     RespType::RBulkString("animal".into()),
+///     RespType::RBulkString("-5".into()),
+///     RespType::RBulkString("-1".into()),
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RBulkString("espin".into()));
+///
+/// # let _ = std::fs::remove_file("dummy_db_getrange_command.csv");
+/// ```
+pub fn getrange(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 4 {
+        if let (Some(key), Some(start), Some(end)) =
+            (cmd[1].as_bytes(), cmd[2].as_str(), cmd[3].as_str())
+        {
+            return match (start.parse::<isize>(), end.parse::<isize>()) {
+                (Ok(start), Ok(end)) => {
+                    let db = database
+                        .read()
+                        .expect("Could not get database read lock on getrange");
+                    match db.get_range_of_string(key, start, end) {
+                        Ok(str) => RespType::RBulkString(str),
+                        Err(e) => database_error_to_resp(e),
+                    }
+                }
+                _ => RespType::RError(String::from("value is not an integer or out of range")),
+            };
+        }
+    }
+    RespType::RError(String::from("Invalid command getrange"))
+}
+
+/// Sobrescribe parte del valor almacenado en `key` a partir de la posición `offset`.
+///
+/// Si el string es más corto que `offset`, se completa con bytes nulos.
+/// Si la clave no existe, se considera un string vacío.
+/// Retorna la longitud del string luego de la modificación.
+/// Devuelve error si el valor almacenado en `key` no es de tipo string.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::commands::command_string;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_setrange_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"saludo".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("hola mundo".into())
+/// ).build());
+///
+/// let res = command_string::setrange(&vec![
+///     RespType::RBulkString("SETRANGE".into()),
+///     RespType::RBulkString("saludo".into()),
+///     RespType::RBulkString("5".into()),
+///     RespType::RBulkString("gente".into()),
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RInteger(10));
+///
+/// # let _ = std::fs::remove_file("dummy_db_setrange_command.csv");
+/// ```
+pub fn setrange(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() == 4 {
+        if let (Some(key), Some(offset), Some(value)) =
+            (cmd[1].as_bytes(), cmd[2].as_str(), cmd[3].as_bytes())
+        {
+            return match offset.parse::<usize>() {
+                Ok(offset) => {
+                    let mut db = database
+                        .write()
+                        .expect("Could not get database lock on setrange");
+                    match db.set_range_of_string(key, offset, value) {
+                        Ok(len) => RespType::RInteger(len),
+                        Err(e) => database_error_to_resp(e),
+                    }
+                }
+                Err(_) => RespType::RError(String::from("offset is out of range")),
+            };
+        }
+    }
+    RespType::RError(String::from("Invalid command setrange"))
+}
+
+/// Devuelve el valor almacenado en `key` y opcionalmente actualiza su tiempo de expiración.
+///
+/// Admite los siguientes parámetros:
+/// * EX: Tiempo de expiración en segundos.
+/// * PX: Tiempo de expiración en milisegundos.
+/// * EXAT: Tiempo UNIX en que va a expirar la clave, en segundos.
+/// * PXAT: Tiempo UNIX en que va a expirar la clave, en milisegundos.
+/// * PERSIST: Elimina el tiempo de expiración de la clave.
+///
+/// Si la clave no existe, devuelve `nil`.
+/// Devuelve error si el valor almacenado en `key` no es de tipo string.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::commands::command_string;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_getex_command.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"nombre".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("alfonso".into())
+/// ).with_timeout(4_000_000_000).build());
+///
+/// let res = command_string::getex(&vec![
+///     RespType::RBulkString("GETEX".into()),
+///     RespType::RBulkString("nombre".into()),
+///     RespType::RBulkString("persist".into()),
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RBulkString("alfonso".into()));
+///
+/// # let _ = std::fs::remove_file("dummy_db_getex_command.csv");
+/// ```
+pub fn getex(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() < 2 || cmd.len() > 4 {
+        return RespType::RError(String::from("Invalid command getex"));
+    }
+    let key = match cmd[1].as_bytes() {
+        Some(key) => key,
+        None => return RespType::RError(String::from("Invalid command getex")),
+    };
+    let args: Vec<String> = cmd[2..]
+        .iter()
+        .filter_map(|arg| arg.as_str().map(String::from))
+        .collect();
+    if args.len() != cmd.len() - 2 {
+        return RespType::RError(String::from("Invalid command getex"));
+    }
+    let option = args
+        .first()
+        .map(|arg| arg.to_lowercase())
+        .unwrap_or_default();
+    let timeout = match args.len() {
+        0 => (&option, None),
+        1 if option == "persist" => (&option, None),
+        2 if ["ex", "px", "exat", "pxat"].contains(&option.as_str()) => {
+            match args[1].parse::<u64>() {
+                Ok(time) if time > 0 => (&option, Some(&args[1])),
+                _ => {
+                    return RespType::RError(String::from("invalid expire time in 'getex' command"))
+                }
+            }
+        }
+        _ => return RespType::RError(String::from("syntax error")),
+    };
+    let mut db = database
+        .write()
+        .expect("Could not get database lock on getex");
+    match db.getex_value_by_key(key, timeout) {
+        Ok(str) => RespType::RBulkString(str),
+        Err(e) => database_error_to_resp(e),
+    }
+}
+
+/// Devuelve la subsecuencia común más larga entre los valores almacenados en `key1` y `key2`.
+///
+/// Las claves que no existen se consideran strings vacíos.
+/// Admite los siguientes parámetros:
+/// * LEN: Devuelve solo la longitud de la subsecuencia.
+/// * IDX: Devuelve las posiciones de los rangos que componen la subsecuencia y su longitud.
+/// * MINMATCHLEN: Junto con IDX, omite los rangos de longitud menor a la especificada.
+/// * WITHMATCHLEN: Junto con IDX, incluye la longitud de cada rango.
+///
+/// Devuelve error si alguno de los valores no es de tipo string.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::commands::command_string;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use std::sync::{Arc, RwLock};
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueTimeItemBuilder, ValueType};
+///
+/// # let db = Database::new("dummy_db_lcs.csv".to_string());
+/// # let mut database = Arc::new(RwLock::new(db));
+/// database.write().unwrap().add(b"key1".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("ohmytext".into())
+/// ).build());
+/// database.write().unwrap().add(b"key2".to_vec(),ValueTimeItemBuilder::new(
+///     ValueType::StringType("mynewtext".into())
+/// ).build());
+///
+/// let res = command_string::lcs(&vec![
+///     RespType::RBulkString("LCS".into()),
+///     RespType::RBulkString("key1".into()),
+///     RespType::RBulkString("key2".into()),
+/// ], &database);
+///
+/// assert_eq!(res, RespType::RBulkString("mytext".into()));
+///
+/// # let _ = std::fs::remove_file("dummy_db_lcs.csv");
+/// ```
+pub fn lcs(cmd: &[RespType], database: &Arc<RwLock<Database>>) -> RespType {
+    if cmd.len() < 3 {
+        return RespType::RError(String::from("Invalid command lcs"));
+    }
+    let (mut len, mut idx, mut with_match_len, mut min_match_len) = (false, false, false, 0);
+    let mut options = cmd[3..].iter();
+    while let Some(option) = options.next() {
+        match option.as_str().unwrap_or_default().to_lowercase().as_str() {
+            "len" => len = true,
+            "idx" => idx = true,
+            "withmatchlen" => with_match_len = true,
+            "minmatchlen" => match options.next() {
+                Some(min) => match min.as_str().unwrap_or_default().parse::<usize>() {
+                    Ok(min) => min_match_len = min,
+                    Err(_) => {
+                        return RespType::RError(String::from(
+                            "value is not an integer or out of range",
+                        ))
+                    }
+                },
+                _ => return RespType::RError(String::from("syntax error")),
+            },
+            _ => return RespType::RError(String::from("syntax error")),
+        }
+    }
+    if len && idx {
+        return RespType::RError(String::from(
+            "If you want both the length and indexes, please just use IDX.",
+        ));
+    }
+    let mut values = vec![];
+    {
+        let db = database
+            .read()
+            .expect("Could not get database read lock on lcs");
+        for key in &cmd[1..3] {
+            if let Some(key) = key.as_bytes() {
+                match db.get_string_value_by_key(key) {
+                    Ok(str) => values.push(str),
+                    Err(DatabaseError::MissingKey()) => values.push(Vec::new()),
+                    Err(e) => return database_error_to_resp(e),
+                }
+            }
+        }
+    }
+    if values.len() != 2 {
+        return RespType::RError(String::from("Invalid command lcs"));
+    }
+    let (subsequence, matches) = longest_common_subsequence(&values[0], &values[1]);
+    if len {
+        return RespType::RInteger(subsequence.len());
+    }
+    if !idx {
+        return RespType::RBulkString(subsequence);
+    }
+    let matches = matches
+        .iter()
+        .filter(|m| m.len >= min_match_len)
+        .map(|m| {
+            let mut range = vec![
+                RespType::RArray(vec![RespType::RInteger(m.a.0), RespType::RInteger(m.a.1)]),
+                RespType::RArray(vec![RespType::RInteger(m.b.0), RespType::RInteger(m.b.1)]),
+            ];
+            if with_match_len {
+                range.push(RespType::RInteger(m.len));
+            }
+            RespType::RArray(range)
+        })
+        .collect();
+    RespType::RArray(vec![
+        RespType::RBulkString("matches".into()),
+        RespType::RArray(matches),
+        RespType::RBulkString("len".into()),
+        RespType::RInteger(subsequence.len()),
+    ])
+}
+
+/// Implementación común de SETEX y PSETEX. `unit` indica si el tiempo está en segundos (ex) o milisegundos (px).
+fn set_with_expiration(cmd: &[RespType], database: &Arc<RwLock<Database>>, unit: &str) -> RespType {
+    let command = if unit == "ex" { "setex" } else { "psetex" };
+    if cmd.len() == 4 {
+        if let (Some(key), Some(time), Some(value)) =
+            (cmd[1].as_bytes(), cmd[2].as_str(), cmd[3].as_bytes())
+        {
+            match time.parse::<u64>() {
+                Ok(t) if t > 0 => {}
+                _ => {
+                    return RespType::RError(format!(
+                        "invalid expire time in '{}' command",
+                        command
+                    ))
+                }
+            }
+            let mut db = database
+                .write()
+                .expect("Could not get database lock on setex");
+            db.set_string(
+                key,
+                value,
+                (&unit.to_string(), Some(&time.to_string())),
+                None,
+                false,
+            );
+            return RespType::RBulkString("Ok".into());
+        }
+    }
+    RespType::RError(format!("Invalid command {}", command))
+}
+
+/// Indica si `flag` se encuentra entre los parámetros opcionales del comando SET.
+fn has_flag(cmd: &[RespType], flag: &str) -> bool {
+    cmd.iter().skip(3).any(|arg| arg.is_word(flag))
+}
+
+/// Representa un resultado entero, que puede ser negativo, como `RespType`.
+fn integer_to_resp(number: i64) -> RespType {
+    if number < 0 {
+        RespType::RSignedNumber(number as isize)
+    } else {
+        RespType::RInteger(number as usize)
+    }
+}
+
+fn database_error_to_resp(error: DatabaseError) -> RespType {
+    match error {
+        DatabaseError::InvalidParameter(err) => RespType::RError(err),
+        DatabaseError::InvalidValueType(err) => RespType::RError(err),
+        DatabaseError::MissingKey() => RespType::RNullBulkString(),
+    }
+}
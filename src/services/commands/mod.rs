@@ -1,5 +1,8 @@
 //! Servicios que implementan los comandos ingresados por el usuario.
-//! Se agrupan por tipo: key, list, pubsub, server, set, string.
+//! Se agrupan por tipo: acl, cluster, connection, key, list, pubsub, server, set, string.
+pub mod command_acl;
+pub mod command_cluster;
+pub mod command_connection;
 pub mod command_key;
 pub mod command_list;
 pub mod command_pubsub;
@@ -1,19 +1,37 @@
 //! Servicio para manejar la bajada a un archivo de la base de datos en memoria !
 
+use crate::domain::entities::config::Config;
+use crate::domain::entities::message::WorkerMessage;
 use crate::domain::implementations::database::Database;
+use crate::domain::implementations::output_queue::parse_memory;
+use crate::services::replication_service::get_deletion_commands;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
 use std::sync::{Arc, RwLock};
-use std::thread;
 use std::time::Duration;
 
 const TIME_TO_SAVE_IN_FILE: u64 = 60 * 2; // in secs
+const TIME_TO_EXPIRE_KEYS: u64 = 1; // in secs
 
-/// Itera infinitamente y cada 2 minutos hace una bajada de los datos en memoria
+/// Cada 2 minutos hace una bajada de los datos en memoria
 /// a un archivo definido en el archivo de configuración.
 ///
-pub fn dump_to_file(database: Arc<RwLock<Database>>) {
+/// Si el motor de almacenamiento es persistente (ver `Database::has_persistent_storage`) no hace falta la bajada
+/// periódica, por lo que solo espera a que se cierre el canal.
+///
+/// Itera hasta que se cierra el canal `stop`, lo que interrumpe la espera hasta la próxima bajada.
+pub fn dump_to_file(database: Arc<RwLock<Database>>, stop: Receiver<()>) {
+    let persistent = database
+        .read()
+        .map(|db| db.has_persistent_storage())
+        .unwrap_or(false);
     loop {
-        save_database(database.clone());
-        thread::sleep(Duration::from_secs(TIME_TO_SAVE_IN_FILE));
+        if !persistent {
+            save_database(database.clone());
+        }
+        match stop.recv_timeout(Duration::from_secs(TIME_TO_SAVE_IN_FILE)) {
+            Err(RecvTimeoutError::Timeout) => continue,
+            _ => break,
+        }
     }
 }
 
@@ -24,8 +42,77 @@ pub fn dump_to_file(database: Arc<RwLock<Database>>) {
 fn save_database(database: Arc<RwLock<Database>>) {
     println!("Saving database to dump");
     if let Ok(db) = Arc::try_unwrap(database).unwrap_err().try_read() {
-        db.save_items_to_file()
+        if let Err(e) = db.save_items_to_file() {
+            println!("Database couldn't be saved into file: {}", e);
+        }
     } else {
         println!("Database couldn't be saved into file");
     }
 }
+
+/// Cada segundo elimina las claves expiradas y, si la memoria estimada supera el atributo `maxmemory` de la
+/// configuración, desaloja las claves accedidas hace más tiempo (ver `Database::evict_keys`). Las claves eliminadas
+/// se guardan en el motor de almacenamiento.
+///
+/// Las notificaciones de esos eventos se envían a la entidad `Server` para que las publique, junto con las claves
+/// eliminadas para que las invalide a los clientes que las siguen y las elimine en las réplicas.
+/// Las réplicas no eliminan las claves expiradas por su cuenta: esperan el `del` que les envía el master.
+/// `maxmemory` se lee en cada iteración, por lo que puede modificarse con `config set`; si no está especificado,
+/// no es válido o es 0, no se desaloja ninguna clave.
+/// Itera hasta que se cierra el canal `stop`.
+pub fn expire_keys(
+    database: Arc<RwLock<Database>>,
+    config: Arc<RwLock<Config>>,
+    server_sender: Sender<WorkerMessage>,
+    stop: Receiver<()>,
+) {
+    while let Err(RecvTimeoutError::Timeout) =
+        stop.recv_timeout(Duration::from_secs(TIME_TO_EXPIRE_KEYS))
+    {
+        let maxmemory = config
+            .read()
+            .ok()
+            .and_then(|conf| conf.get_attribute(String::from("maxmemory")).ok())
+            .and_then(|maxmemory| parse_memory(&maxmemory))
+            .unwrap_or(0);
+        let (replica_tx, replica_rx) = mpsc::channel();
+        if server_sender
+            .send(WorkerMessage::IsReplica(replica_tx))
+            .is_err()
+        {
+            break;
+        }
+        let is_replica = replica_rx.recv().unwrap_or(false);
+        let (notifications, modified_keys) = match database.write() {
+            Ok(mut db) => {
+                if !is_replica {
+                    db.remove_expired_keys();
+                }
+                db.evict_keys(maxmemory);
+                if let Err(e) = db.sync_storage() {
+                    println!("Storage couldn't be synchronized: {}", e);
+                }
+                // se envían con el lock tomado, para que lleguen a las réplicas antes que cualquier comando posterior
+                for command in get_deletion_commands(&mut db) {
+                    let _ = server_sender.send(WorkerMessage::Propagate(command));
+                }
+                (db.take_notifications(), db.take_modified_keys())
+            }
+            Err(_) => break,
+        };
+        if !notifications.is_empty()
+            && server_sender
+                .send(WorkerMessage::KeyspaceNotifications(notifications))
+                .is_err()
+        {
+            break;
+        }
+        if !modified_keys.is_empty()
+            && server_sender
+                .send(WorkerMessage::InvalidateKeys(modified_keys, None))
+                .is_err()
+        {
+            break;
+        }
+    }
+}
@@ -1,10 +1,14 @@
 //! Servicios para comunicar solicitudes de clientes con la base de datos del servidor.
 
+pub mod cluster_service;
 pub mod commander;
 pub mod commands;
 pub mod database_service;
 pub mod parser_service;
+pub mod proxy_service;
+pub mod replication_service;
 pub mod server_service;
+pub mod tls_service;
 pub mod utils;
 pub mod web_server_parser_service;
 pub mod worker_service;
@@ -1,9 +1,21 @@
 //! Servicio para transformar un mensaje que llega por stream a un tipo de dato RESP y viceversa.
 
-use super::utils::resp_type::RespType;
+use super::utils::resp_type::{format_double, RespType};
 use crate::errors::parse_error::ParseError;
 
-/// Recibe una response de tipo RespType y lo traduce a un String respetando el protocolo RESP.
+/// Longitud máxima de un comando inline, en bytes.
+pub const MAX_INLINE_LEN: usize = 64 * 1024;
+
+/// Longitud máxima de un bulk string, en bytes (equivalente a `proto-max-bulk-len` de Redis).
+pub const MAX_BULK_LEN: usize = 512 * 1024 * 1024;
+
+/// Cantidad máxima de elementos de un array.
+pub const MAX_MULTIBULK_LEN: usize = i32::MAX as usize;
+
+/// Profundidad máxima de arrays anidados.
+pub const MAX_NESTING_DEPTH: usize = 32;
+
+/// Recibe una response de tipo RespType y lo traduce a un vector de bytes respetando el protocolo RESP.
 ///
 /// El string debe respetar el protocolo RESP, esto es que se cumplan las siguientes reglas:
 ///
@@ -23,54 +35,92 @@ use crate::errors::parse_error::ParseError;
 ///
 /// * Todas las respuestas deben terminar con "\r\n" (CRLF).
 ///
+/// Los tipos que solo existen en RESP3 utilizan los siguientes prefijos:
+///
+/// * Map "%", Set "~", Push ">" y Attribute "|", seguidos de la cantidad de elementos (o de pares, en el caso de maps y atributos).
+///
+/// * Double ",", Boolean "#" ("t" o "f"), Big number "(" y Null "_".
+///
+/// * Verbatim string "=", seguido del tamaño del contenido, que comienza con el formato de tres letras y ":".
+///
+/// El contenido de los bulk strings se copia sin modificaciones, por lo que puede contener cualquier byte, incluso CRLF.
+///
 /// # Ejemplo
 /// ```
 /// # use proyecto_taller_1::services::parser_service;
 /// # use proyecto_taller_1::services::utils::resp_type::RespType;
 ///
-/// let resp = RespType::RBulkString("some string".to_string());
-/// assert_eq!(parser_service::parse_response(resp), "$11\r\nsome string\r\n");
+/// let resp = RespType::RBulkString("some string".into());
+/// assert_eq!(parser_service::parse_response(resp), b"$11\r\nsome string\r\n");
 ///
-/// let resp = RespType::RArray(vec![RespType::RBulkString("first".to_string()), RespType::RBulkString("second".to_string())]);
-/// assert_eq!(parser_service::parse_response(resp), "*2\r\n$5\r\nfirst\r\n$6\r\nsecond\r\n");
+/// let resp = RespType::RArray(vec![RespType::RBulkString("first".into()), RespType::RBulkString("second".into())]);
+/// assert_eq!(parser_service::parse_response(resp), b"*2\r\n$5\r\nfirst\r\n$6\r\nsecond\r\n");
 ///
 /// ```
-pub fn parse_response(response: RespType) -> String {
+pub fn parse_response(response: RespType) -> Vec<u8> {
     match response {
-        RespType::RBulkString(string) => {
-            format!("${}\r\n{}\r\n", string.len(), string)
-        }
-        RespType::RInteger(integer) => {
-            format!(":{}\r\n", integer)
-        }
-        RespType::RSignedNumber(negative) => {
-            format!(":{}\r\n", negative)
-        }
-        RespType::RSimpleString(string) => {
-            format!("+{}\r\n", string)
+        RespType::RBulkString(bytes) => {
+            let mut final_bytes = format!("${}\r\n", bytes.len()).into_bytes();
+            final_bytes.extend_from_slice(&bytes);
+            final_bytes.extend_from_slice(b"\r\n");
+            final_bytes
         }
+        RespType::RInteger(integer) => format!(":{}\r\n", integer).into_bytes(),
+        RespType::RSignedNumber(negative) => format!(":{}\r\n", negative).into_bytes(),
+        RespType::RSimpleString(string) => format!("+{}\r\n", string).into_bytes(),
         RespType::RArray(array) => {
-            let mut final_string = String::from("");
-            final_string += "*";
-            final_string += &array.len().to_string();
-            final_string += "\r\n";
+            let mut final_bytes = format!("*{}\r\n", array.len()).into_bytes();
             for element in array {
-                final_string += &parse_response(element);
+                final_bytes.extend(parse_response(element));
             }
-            final_string
+            final_bytes
+        }
+        RespType::RError(message) => format!("-{}\r\n", message).into_bytes(),
+        RespType::RNullBulkString() => b"$-1\r\n".to_vec(),
+        RespType::RNullArray() => b"*-1\r\n".to_vec(),
+        RespType::RMap(pairs) => parse_pairs('%', pairs),
+        RespType::RSet(elements) => parse_aggregate('~', elements),
+        RespType::RPush(elements) => parse_aggregate('>', elements),
+        RespType::RDouble(double) => format!(",{}\r\n", format_double(double)).into_bytes(),
+        RespType::RBoolean(boolean) => {
+            format!("#{}\r\n", if boolean { 't' } else { 'f' }).into_bytes()
+        }
+        RespType::RBigNumber(number) => format!("({}\r\n", number).into_bytes(),
+        RespType::RVerbatimString(format, text) => {
+            let content = format!("{}:{}", format, text);
+            format!("={}\r\n{}\r\n", content.len(), content).into_bytes()
         }
-        RespType::RError(message) => {
-            format!("-{}\r\n", message)
+        RespType::RNull() => b"_\r\n".to_vec(),
+        RespType::RAttribute(attributes, value) => {
+            let mut final_bytes = parse_pairs('|', attributes);
+            final_bytes.extend(parse_response(*value));
+            final_bytes
         }
-        RespType::RNullBulkString() => "$-1\r\n".to_string(),
-        RespType::RNullArray() => "*-1\r\n".to_string(),
     }
 }
 
+fn parse_aggregate(prefix: char, elements: Vec<RespType>) -> Vec<u8> {
+    let mut final_bytes = format!("{}{}\r\n", prefix, elements.len()).into_bytes();
+    for element in elements {
+        final_bytes.extend(parse_response(element));
+    }
+    final_bytes
+}
+
+fn parse_pairs(prefix: char, pairs: Vec<(RespType, RespType)>) -> Vec<u8> {
+    let mut final_bytes = format!("{}{}\r\n", prefix, pairs.len()).into_bytes();
+    for (key, value) in pairs {
+        final_bytes.extend(parse_response(key));
+        final_bytes.extend(parse_response(value));
+    }
+    final_bytes
+}
+
 /// Traduce un vector de bytes segun el protocolo RESP a un tipo de dato RespType.
 ///
 /// Verifica que sea un array de bulk strings, si no lo es arroja error de tipo InvalidRequest.
 /// Valida que contenga bytes, si vector está vacío arroja error de tipo InvalidSize.
+/// Si el primer byte no corresponde a ningún tipo RESP, interpreta el mensaje como un comando inline (ver `parse_inline`).
 ///
 /// # Ejemplo
 /// ```
@@ -79,12 +129,18 @@ pub fn parse_response(response: RespType) -> String {
 ///
 /// let request = "*2\r\n$5\r\nfirst\r\n$6\r\nsecond\r\n".as_bytes();
 /// let parsed_request = parser_service::parse_request(request).unwrap();
-/// assert_eq!(parsed_request, RespType::RArray(vec![RespType::RBulkString("first".to_string()), RespType::RBulkString("second".to_string())]));
+/// assert_eq!(parsed_request, RespType::RArray(vec![RespType::RBulkString("first".into()), RespType::RBulkString("second".into())]));
+///
+/// let parsed_request = parser_service::parse_request(b"first second\r\n").unwrap();
+/// assert_eq!(parsed_request, RespType::RArray(vec![RespType::RBulkString("first".into()), RespType::RBulkString("second".into())]));
 /// ```
 pub fn parse_request(request: &[u8]) -> Result<RespType, ParseError> {
     if request.is_empty() {
         return Err(ParseError::InvalidSize(String::from("Empty request")));
     }
+    if is_inline(request) {
+        return parse_inline(request);
+    }
     match parse(request) {
         Ok(parsed_request) => {
             if is_array_of_bulkstring(&parsed_request) {
@@ -99,6 +155,134 @@ pub fn parse_request(request: &[u8]) -> Result<RespType, ParseError> {
     }
 }
 
+/// Indica si el mensaje es un comando inline, es decir que su primer byte no corresponde a ningún tipo RESP.
+fn is_inline(request: &[u8]) -> bool {
+    !matches!(request.first(), Some(b'*' | b'$' | b'+' | b'-' | b':'))
+}
+
+/// Traduce un comando inline a un array de bulk strings.
+///
+/// Los comandos inline son una única línea terminada en "\n" (opcionalmente precedido por "\r") cuyos argumentos
+/// están separados por espacios, pensados para utilizarse desde herramientas como telnet o netcat.
+/// Los argumentos pueden escribirse entre comillas dobles, que admiten las secuencias de escape
+/// `\n`, `\r`, `\t`, `\b`, `\a`, `\\`, `\"` y `\xHH`, o entre comillas simples, que solo admiten `\'`.
+/// Devuelve error de tipo InvalidRequest si las comillas no están balanceadas o si la línea no contiene argumentos,
+/// y de tipo InvalidSize si supera `MAX_INLINE_LEN`.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::parser_service;
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+///
+/// let parsed_request = parser_service::parse_inline(b"SET clave \"hola mundo\\n\"\r\n").unwrap();
+/// assert_eq!(parsed_request, RespType::RArray(vec![
+///     RespType::RBulkString("SET".into()),
+///     RespType::RBulkString("clave".into()),
+///     RespType::RBulkString("hola mundo\n".into()),
+/// ]));
+///
+/// assert!(parser_service::parse_inline(b"SET clave \"hola\r\n").is_err());
+/// ```
+pub fn parse_inline(request: &[u8]) -> Result<RespType, ParseError> {
+    let line = request.strip_suffix(b"\n").unwrap_or(request);
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    if line.len() > MAX_INLINE_LEN {
+        return Err(ParseError::InvalidSize(String::from(
+            "too big inline request",
+        )));
+    }
+    let args = split_inline_args(line)?;
+    if args.is_empty() {
+        return Err(ParseError::InvalidRequest(String::from(
+            "Empty inline request",
+        )));
+    }
+    Ok(RespType::RArray(
+        args.into_iter().map(RespType::RBulkString).collect(),
+    ))
+}
+
+/// Separa una línea en argumentos, respetando las comillas y sus secuencias de escape.
+fn split_inline_args(line: &[u8]) -> Result<Vec<Vec<u8>>, ParseError> {
+    let unbalanced = || ParseError::InvalidRequest(String::from("unbalanced quotes in request"));
+    let mut args = Vec::new();
+    let mut pos = 0;
+    loop {
+        while pos < line.len() && line[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        if pos >= line.len() {
+            return Ok(args);
+        }
+        let mut arg = Vec::new();
+        match line[pos] {
+            b'"' => {
+                pos += 1;
+                loop {
+                    match line.get(pos) {
+                        None => return Err(unbalanced()),
+                        Some(b'"') => break,
+                        Some(b'\\') => {
+                            let escaped = *line.get(pos + 1).ok_or_else(unbalanced)?;
+                            let hex = line
+                                .get(pos + 2..pos + 4)
+                                .and_then(|digits| std::str::from_utf8(digits).ok())
+                                .and_then(|digits| u8::from_str_radix(digits, 16).ok());
+                            match (escaped, hex) {
+                                (b'x', Some(byte)) => {
+                                    arg.push(byte);
+                                    pos += 2;
+                                }
+                                (b'n', _) => arg.push(b'\n'),
+                                (b'r', _) => arg.push(b'\r'),
+                                (b't', _) => arg.push(b'\t'),
+                                (b'b', _) => arg.push(0x08),
+                                (b'a', _) => arg.push(0x07),
+                                (other, _) => arg.push(other),
+                            }
+                            pos += 2;
+                        }
+                        Some(byte) => {
+                            arg.push(*byte);
+                            pos += 1;
+                        }
+                    }
+                }
+                pos += 1;
+            }
+            b'\'' => {
+                pos += 1;
+                loop {
+                    match line.get(pos) {
+                        None => return Err(unbalanced()),
+                        Some(b'\'') => break,
+                        Some(b'\\') if line.get(pos + 1) == Some(&b'\'') => {
+                            arg.push(b'\'');
+                            pos += 2;
+                        }
+                        Some(byte) => {
+                            arg.push(*byte);
+                            pos += 1;
+                        }
+                    }
+                }
+                pos += 1;
+            }
+            _ => {
+                while pos < line.len() && !line[pos].is_ascii_whitespace() {
+                    arg.push(line[pos]);
+                    pos += 1;
+                }
+            }
+        }
+        // una comilla de cierre debe estar seguida por un espacio o por el fin de la línea
+        if pos < line.len() && !line[pos].is_ascii_whitespace() {
+            return Err(unbalanced());
+        }
+        args.push(arg);
+    }
+}
+
 /// Valida que `parsed_request` sea un array de bulk strings.
 ///
 /// Devuelve true si lo es, false si no.
@@ -108,10 +292,10 @@ pub fn parse_request(request: &[u8]) -> Result<RespType, ParseError> {
 /// # use proyecto_taller_1::services::parser_service;
 /// # use proyecto_taller_1::services::utils::resp_type::RespType;
 ///
-/// let parsed_request = RespType::RArray(vec![RespType::RBulkString("first".to_string()), RespType::RBulkString("second".to_string())]);
+/// let parsed_request = RespType::RArray(vec![RespType::RBulkString("first".into()), RespType::RBulkString("second".into())]);
 /// assert!(parser_service::is_array_of_bulkstring(&parsed_request));
 ///
-/// let parsed_request = RespType::RBulkString("hello world".to_string());
+/// let parsed_request = RespType::RBulkString("hello world".into());
 /// assert!(!parser_service::is_array_of_bulkstring(&parsed_request));
 /// ```
 pub fn is_array_of_bulkstring(parsed_request: &RespType) -> bool {
@@ -164,7 +348,8 @@ pub fn search_crlf(request: &[u8]) -> Result<usize, ParseError> {
 /// Lee una palabra desde la posición `from` hasta `to`.
 ///
 /// Si la posición inicial es mayor a la posición final, devuelve error.
-/// Devuelve los datos leidos en forma de String.
+/// Devuelve los datos leidos en forma de String. Se utiliza para las partes textuales del protocolo
+/// (longitudes, enteros, simple strings y errores); el contenido de los bulk strings se lee con `read_bytes`.
 ///
 /// # Ejemplo
 /// ```
@@ -183,6 +368,26 @@ pub fn read_word(from: usize, to: usize, request: &[u8]) -> Result<String, Parse
     Ok(String::from_utf8_lossy(slice).to_string().to_lowercase())
 }
 
+/// Lee los bytes desde la posición `from` hasta `to`, sin ninguna transformación.
+///
+/// Si la posición inicial es mayor a la posición final o `to` excede el largo de la solicitud, devuelve error.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::parser_service;
+///
+/// let request = b"$4\r\n\xff\r\nA\r\n";
+/// assert_eq!(parser_service::read_bytes(4, 8, request).unwrap(), vec![0xff, b'\r', b'\n', b'A']);
+/// ```
+pub fn read_bytes(from: usize, to: usize, request: &[u8]) -> Result<Vec<u8>, ParseError> {
+    if from > to || to > request.len() {
+        return Err(ParseError::UnexpectedError(
+            "Invalid slice of bytes".to_string(),
+        ));
+    }
+    Ok(request[from..to].to_vec())
+}
+
 /// Lee un número entero desde la posición `from` hasta `to`.
 ///
 /// Si la posición inicial es mayor a la posición final, devuelve error.
@@ -234,11 +439,11 @@ pub fn read_int(from: usize, to: usize, request: &[u8]) -> Result<usize, ParseEr
 /// # use proyecto_taller_1::services::utils::resp_type::RespType;
 ///
 /// let request = "*2\r\n$5\r\nfirst\r\n$6\r\nsecond\r\n".as_bytes();
-/// assert_eq!(parser_service::parse_request(request).unwrap(), RespType::RArray(vec![RespType::RBulkString("first".to_string()), RespType::RBulkString("second".to_string())]));
+/// assert_eq!(parser_service::parse_request(request).unwrap(), RespType::RArray(vec![RespType::RBulkString("first".into()), RespType::RBulkString("second".into())]));
 ///
 /// ```
 pub fn parse(request: &[u8]) -> Result<RespType, ParseError> {
-    if String::from_utf8_lossy(&request[request.len() - 2..]) != "\r\n" {
+    if request.len() < 2 || &request[request.len() - 2..] != b"\r\n" {
         return Err(ParseError::InvalidProtocol(
             "CRFL missing at the end of command".to_string(),
         ));
@@ -316,6 +521,7 @@ pub fn parse_simple_string(request: &[u8]) -> Result<RespType, ParseError> {
 ///
 /// El arreglo debe respetar el protocolo RESP, esto es tener la forma "${string_length}\r\n{string}\r\n".
 /// Si la solicitud es de la forma "$-1\r\n" devuelve un NullBulkString.
+/// El contenido se lee según la longitud indicada, por lo que puede contener cualquier byte, incluso CRLF.
 ///
 /// # Ejemplo
 /// ```
@@ -323,7 +529,10 @@ pub fn parse_simple_string(request: &[u8]) -> Result<RespType, ParseError> {
 /// # use proyecto_taller_1::services::utils::resp_type::RespType;
 ///
 /// let request = "$9\r\nmymessage\r\n".as_bytes();
-/// assert_eq!(parser_service::parse_bulkstring(request).unwrap(), RespType::RBulkString("mymessage".to_string()));
+/// assert_eq!(parser_service::parse_bulkstring(request).unwrap(), RespType::RBulkString("mymessage".into()));
+///
+/// let request = b"$4\r\na\r\nb\r\n";
+/// assert_eq!(parser_service::parse_bulkstring(request).unwrap(), RespType::RBulkString(b"a\r\nb".to_vec()));
 ///
 /// let request = "$-1\r\n".as_bytes();
 /// assert_eq!(parser_service::parse_bulkstring(request).unwrap(), RespType::RNullBulkString());
@@ -340,14 +549,12 @@ pub fn parse_bulkstring(request: &[u8]) -> Result<RespType, ParseError> {
     //     ));
     // }
     let size = read_int(pos + 1, crlf, request).unwrap_or(0);
-    pos = crlf + 1;
-    let slice = &request[pos + 1..];
-    let next_crlf = search_crlf(slice)?;
-    let word = read_word(0, next_crlf, slice)?;
-    if word.len() != size {
+    pos = crlf + 2;
+    if request.len() != pos + size + 2 || &request[pos + size..] != b"\r\n" {
         return Err(ParseError::InvalidSize("String size mismatch".to_string()));
     }
-    Ok(RespType::RBulkString(word))
+    let bytes = read_bytes(pos, pos + size, request)?;
+    Ok(RespType::RBulkString(bytes))
 }
 
 /// Verifica si el arreglo de bytes coincide con un NullBulkString.
@@ -444,7 +651,7 @@ pub fn get_request_len(request: &[u8]) -> usize {
 /// # use proyecto_taller_1::services::utils::resp_type::RespType;
 ///
 /// let request = "*2\r\n$5\r\nfirst\r\n$6\r\nsecond\r\n".as_bytes();
-/// assert_eq!(parser_service::parse_array(request).unwrap(), RespType::RArray(vec![RespType::RBulkString("first".to_string()), RespType::RBulkString("second".to_string())]));
+/// assert_eq!(parser_service::parse_array(request).unwrap(), RespType::RArray(vec![RespType::RBulkString("first".into()), RespType::RBulkString("second".into())]));
 ///
 /// let request = "*-1\r\n".as_bytes();
 /// assert_eq!(parser_service::parse_array(request).unwrap(), RespType::RNullArray());
@@ -452,7 +659,7 @@ pub fn get_request_len(request: &[u8]) -> usize {
 pub fn parse_array(request: &[u8]) -> Result<RespType, ParseError> {
     let mut pos = 0;
     let crlf = search_crlf(request)?;
-    if crlf == 3 {
+    if request.get(1) == Some(&b'-') {
         return check_if_array_null_type(pos, crlf, request);
     }
     let size = read_int(pos + 1, crlf, request).unwrap_or(0);
@@ -461,8 +668,10 @@ pub fn parse_array(request: &[u8]) -> Result<RespType, ParseError> {
     let mut contents = &request[pos..];
     while !contents.is_empty() {
         let request_len = get_request_len(contents);
-        // println!("contents so far: {:?}", &contents[..request_len]);
-        let parsed_request = parse(&contents[..request_len]).unwrap();
+        if request_len == 0 || request_len > contents.len() {
+            return Err(ParseError::InvalidSize(String::from("Array size mismatch")));
+        }
+        let parsed_request = parse(&contents[..request_len])?;
         vec.push(parsed_request);
         contents = &contents[request_len..];
     }
@@ -516,21 +725,15 @@ pub fn get_array_len(request: &[u8]) -> usize {
 /// assert_eq!(parser_service::get_bulkstring_len(request), 15);
 /// ```
 pub fn get_bulkstring_len(request: &[u8]) -> usize {
-    let mut len = 0;
     match search_crlf(request) {
-        Ok(crfl) => {
-            len += 1; //$
-            let size = read_int(len, crfl, request).unwrap_or(0);
-            len += crfl - len; //size
-            match search_crlf(&request[crfl + 1..]) {
-                Ok(_second_crfl) => {
-                    len += size + 4; //+ 4 bytes crfl
-                    len
-                }
-                Err(_e) => len,
+        Ok(crlf) => {
+            if check_if_bulkstring_null_type(0, crlf, request) {
+                return crlf + 2;
             }
+            let size = read_int(1, crlf, request).unwrap_or(0);
+            crlf + size + 4 //+ 4 bytes crlf
         }
-        Err(_e) => len,
+        Err(_e) => 0,
     }
 }
 
@@ -593,6 +796,95 @@ pub fn get_string_len(request: &[u8]) -> usize {
     }
 }
 
+/// Devuelve la longitud del primer mensaje RESP completo que contenga `buffer`.
+///
+/// A diferencia de `get_request_len`, no asume que el mensaje esté completo: si todavía faltan bytes
+/// por recibir devuelve `None`, para que quien lee del stream acumule más datos antes de parsear.
+/// Los comandos inline se consideran completos al recibir "\n"; si superan `MAX_INLINE_LEN` sin haberlo recibido devuelve error.
+/// Devuelve error si el mensaje no respeta el protocolo, o si declara un bulk string de más de `MAX_BULK_LEN` bytes,
+/// un array de más de `MAX_MULTIBULK_LEN` elementos o más de `MAX_NESTING_DEPTH` niveles de anidamiento.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::parser_service;
+///
+/// let buffer = b"*1\r\n$4\r\nping\r\n*1\r\n$4\r\nping\r\n";
+/// assert_eq!(parser_service::get_complete_request_len(buffer).unwrap(), Some(14));
+///
+/// let buffer = b"*2\r\n$3\r\nget\r\n$10\r\nmyke";
+/// assert_eq!(parser_service::get_complete_request_len(buffer).unwrap(), None);
+/// ```
+pub fn get_complete_request_len(buffer: &[u8]) -> Result<Option<usize>, ParseError> {
+    get_complete_len_with_depth(buffer, 0)
+}
+
+/// Calcula la longitud del primer mensaje RESP completo de `buffer`, que está anidado en `depth` arrays.
+fn get_complete_len_with_depth(buffer: &[u8], depth: usize) -> Result<Option<usize>, ParseError> {
+    if is_inline(buffer) {
+        return match buffer.iter().position(|byte| *byte == b'\n') {
+            Some(newline) if newline <= MAX_INLINE_LEN + 1 => Ok(Some(newline + 1)),
+            None if buffer.len() <= MAX_INLINE_LEN + 1 => Ok(None),
+            _ => Err(ParseError::InvalidSize(String::from(
+                "too big inline request",
+            ))),
+        };
+    }
+    let crlf = match buffer.windows(2).position(|window| window == b"\r\n") {
+        Some(crlf) => crlf,
+        None => return Ok(None),
+    };
+    match buffer[0] {
+        b'+' | b'-' | b':' => Ok(Some(crlf + 2)),
+        b'$' => {
+            let size = read_word(1, crlf, buffer)?
+                .parse::<isize>()
+                .map_err(|_| ParseError::IntParseError("Invalid bulk string length".to_string()))?;
+            if size < 0 {
+                return Ok(Some(crlf + 2));
+            }
+            if size as usize > MAX_BULK_LEN {
+                return Err(ParseError::InvalidSize("invalid bulk length".to_string()));
+            }
+            let len = (crlf + 4)
+                .checked_add(size as usize)
+                .ok_or_else(|| ParseError::InvalidSize("invalid bulk length".to_string()))?;
+            if buffer.len() < len {
+                return Ok(None);
+            }
+            if &buffer[len - 2..len] != b"\r\n" {
+                return Err(ParseError::InvalidSize("String size mismatch".to_string()));
+            }
+            Ok(Some(len))
+        }
+        b'*' => {
+            let size = read_word(1, crlf, buffer)?
+                .parse::<isize>()
+                .map_err(|_| ParseError::IntParseError("Invalid array length".to_string()))?;
+            if size > 0 && size as usize > MAX_MULTIBULK_LEN {
+                return Err(ParseError::InvalidSize(
+                    "invalid multibulk length".to_string(),
+                ));
+            }
+            if size > 0 && depth >= MAX_NESTING_DEPTH {
+                return Err(ParseError::InvalidSize(
+                    "too many nested arrays".to_string(),
+                ));
+            }
+            let mut len = crlf + 2;
+            for _ in 0..size.max(0) {
+                match get_complete_len_with_depth(&buffer[len..], depth + 1)? {
+                    Some(element_len) => len += element_len,
+                    None => return Ok(None),
+                }
+            }
+            Ok(Some(len))
+        }
+        _ => Err(ParseError::InvalidProtocol(
+            "First byte must be one of the following: $, +, :, *, -".to_string(),
+        )),
+    }
+}
+
 #[test]
 fn parse_returns_ok_when_given_valid_simple_string() {
     let req = b"+Ok\r\n";
@@ -655,7 +947,7 @@ fn parse_returns_ok_when_given_valid_bulkstring() {
     assert!(result.is_ok());
     match result.unwrap() {
         RespType::RBulkString(s) => {
-            assert_eq!(s, "foobar".to_string())
+            assert_eq!(s, b"foobar".to_vec())
         }
         _ => assert!(false),
     }
@@ -690,7 +982,7 @@ fn parse_returns_ok_when_given_empty_bulkstring() {
     assert!(result.is_ok());
     match result.unwrap() {
         RespType::RBulkString(s) => {
-            assert_eq!(s, "".to_string())
+            assert_eq!(s, b"".to_vec())
         }
         _ => assert!(false),
     }
@@ -719,9 +1011,9 @@ fn parse_returns_ok_when_given_array_of_bulkstrings() {
             assert_eq!(
                 v,
                 vec![
-                    RespType::RBulkString(String::from("foobar")),
-                    RespType::RBulkString(String::from("key")),
-                    RespType::RBulkString(String::from("value"))
+                    RespType::RBulkString("foobar".into()),
+                    RespType::RBulkString("key".into()),
+                    RespType::RBulkString("value".into())
                 ]
             )
         }
@@ -739,9 +1031,9 @@ fn parse_request_returns_ok_when_given_valid_array_of_bulkstrings() {
             assert_eq!(
                 v,
                 vec![
-                    RespType::RBulkString(String::from("foobar")),
-                    RespType::RBulkString(String::from("key")),
-                    RespType::RBulkString(String::from("value"))
+                    RespType::RBulkString("foobar".into()),
+                    RespType::RBulkString("key".into()),
+                    RespType::RBulkString("value".into())
                 ]
             )
         }
@@ -759,10 +1051,10 @@ fn parse_returns_ok_when_given_array_of_bulkstrings_and_integers() {
             assert_eq!(
                 v,
                 vec![
-                    RespType::RBulkString(String::from("foobar")),
+                    RespType::RBulkString("foobar".into()),
                     RespType::RInteger(5),
                     RespType::RInteger(10),
-                    RespType::RBulkString(String::from("value"))
+                    RespType::RBulkString("value".into())
                 ]
             )
         }
@@ -914,56 +1206,56 @@ fn parse_request_returns_error_when_given_array_of_integers() {
 fn parse_response_returns_ok_when_given_string() {
     let result = parse_response(RespType::RSimpleString(String::from("test")));
     let expected = "+test\r\n".to_string();
-    assert_eq!(result, expected);
+    assert_eq!(result, expected.as_bytes());
 }
 
 #[test]
 fn parse_response_returns_ok_when_given_bulkstring() {
-    let result = parse_response(RespType::RBulkString(String::from("test")));
+    let result = parse_response(RespType::RBulkString("test".into()));
     let expected = "$4\r\ntest\r\n".to_string();
-    assert_eq!(result, expected);
+    assert_eq!(result, expected.as_bytes());
 }
 
 #[test]
 fn parse_response_returns_ok_when_given_empty_bulkstring() {
-    let result = parse_response(RespType::RBulkString(String::from("")));
+    let result = parse_response(RespType::RBulkString("".into()));
     let expected = "$0\r\n\r\n".to_string();
-    assert_eq!(result, expected);
+    assert_eq!(result, expected.as_bytes());
 }
 
 #[test]
 fn parse_response_returns_ok_when_given_empty_array() {
     let result = parse_response(RespType::RArray(vec![]));
     let expected = "*0\r\n".to_string();
-    assert_eq!(result, expected);
+    assert_eq!(result, expected.as_bytes());
 }
 
 #[test]
 fn parse_response_returns_ok_when_given_null_bulkstring() {
     let result = parse_response(RespType::RNullBulkString());
     let expected = "$-1\r\n".to_string();
-    assert_eq!(result, expected);
+    assert_eq!(result, expected.as_bytes());
 }
 
 #[test]
 fn parse_response_returns_ok_when_given_null_array() {
     let result = parse_response(RespType::RNullArray());
     let expected = "*-1\r\n".to_string();
-    assert_eq!(result, expected);
+    assert_eq!(result, expected.as_bytes());
 }
 
 #[test]
 fn parse_response_returns_ok_when_given_integer() {
     let result = parse_response(RespType::RInteger(5));
     let expected = ":5\r\n";
-    assert_eq!(result, expected);
+    assert_eq!(result, expected.as_bytes());
 }
 
 #[test]
 fn parse_response_returns_ok_when_given_error() {
     let result = parse_response(RespType::RError("error some error".to_string()));
     let expected = "-error some error\r\n".to_string();
-    assert_eq!(result, expected);
+    assert_eq!(result, expected.as_bytes());
 }
 
 #[test]
@@ -973,7 +1265,7 @@ fn parse_response_returns_ok_when_given_array_of_strings() {
         RespType::RSimpleString("b".to_string()),
     ]));
     let expected = "*2\r\n+a\r\n+b\r\n".to_string();
-    assert_eq!(result, expected);
+    assert_eq!(result, expected.as_bytes());
 }
 
 #[test]
@@ -985,7 +1277,7 @@ fn parse_response_returns_ok_when_given_array_of_integers() {
         RespType::RInteger(11),
     ]));
     let expected = "*4\r\n:2\r\n:3\r\n:10\r\n:11\r\n".to_string();
-    assert_eq!(result, expected);
+    assert_eq!(result, expected.as_bytes());
 }
 
 #[test]
@@ -995,7 +1287,7 @@ fn parse_response_returns_ok_when_given_array_of_errors() {
         RespType::RError("message2".to_string()),
     ]));
     let expected = "*2\r\n-message1\r\n-message2\r\n".to_string();
-    assert_eq!(result, expected);
+    assert_eq!(result, expected.as_bytes());
 }
 
 #[test]
@@ -1006,5 +1298,169 @@ fn parse_response_returns_ok_when_given_array_of_arrays() {
         RespType::RArray(vec![RespType::RInteger(7)]),
     ]));
     let expected = "*3\r\n-message1\r\n-message2\r\n*1\r\n:7\r\n".to_string();
-    assert_eq!(result, expected);
+    assert_eq!(result, expected.as_bytes());
+}
+
+#[test]
+fn parse_request_keeps_binary_bulkstring_with_crlf_inside() {
+    let req = b"*3\r\n$3\r\nSET\r\n$5\r\nclave\r\n$6\r\n\xff\r\n\x00\r\n\r\n";
+    let result = parse_request(req).unwrap();
+    assert_eq!(
+        result,
+        RespType::RArray(vec![
+            RespType::RBulkString(b"SET".to_vec()),
+            RespType::RBulkString(b"clave".to_vec()),
+            RespType::RBulkString(b"\xff\r\n\x00\r\n".to_vec()),
+        ])
+    );
+}
+
+#[test]
+fn get_complete_request_len_waits_for_split_bulkstring() {
+    let req = b"*2\r\n$3\r\nget\r\n$6\r\nab\r\ncd\r\n";
+    assert_eq!(get_complete_request_len(&req[..20]).unwrap(), None);
+    assert_eq!(get_complete_request_len(req).unwrap(), Some(req.len()));
+}
+
+#[test]
+fn parse_response_returns_resp3_map() {
+    let result = parse_response(RespType::RMap(vec![(
+        RespType::RBulkString("proto".into()),
+        RespType::RInteger(3),
+    )]));
+    assert_eq!(result, b"%1\r\n$5\r\nproto\r\n:3\r\n");
+}
+
+#[test]
+fn parse_response_returns_resp3_scalars() {
+    assert_eq!(parse_response(RespType::RDouble(3.25)), b",3.25\r\n");
+    assert_eq!(
+        parse_response(RespType::RDouble(f64::INFINITY)),
+        b",inf\r\n"
+    );
+    assert_eq!(parse_response(RespType::RBoolean(true)), b"#t\r\n");
+    assert_eq!(parse_response(RespType::RBoolean(false)), b"#f\r\n");
+    assert_eq!(
+        parse_response(RespType::RBigNumber(
+            "3492890328409238509324850943850943825024385".into()
+        )),
+        b"(3492890328409238509324850943850943825024385\r\n"
+    );
+    assert_eq!(parse_response(RespType::RNull()), b"_\r\n");
+    assert_eq!(
+        parse_response(RespType::RVerbatimString(
+            "txt".into(),
+            "Some string".into()
+        )),
+        b"=15\r\ntxt:Some string\r\n"
+    );
+}
+
+#[test]
+fn parse_response_returns_resp3_set_push_and_attribute() {
+    assert_eq!(
+        parse_response(RespType::RSet(vec![RespType::RInteger(1)])),
+        b"~1\r\n:1\r\n"
+    );
+    assert_eq!(
+        parse_response(RespType::RPush(vec![
+            RespType::RBulkString("message".into()),
+            RespType::RBulkString("canal".into()),
+        ])),
+        b">2\r\n$7\r\nmessage\r\n$5\r\ncanal\r\n"
+    );
+    assert_eq!(
+        parse_response(RespType::RAttribute(
+            vec![(
+                RespType::RSimpleString("ttl".into()),
+                RespType::RInteger(10)
+            )],
+            Box::new(RespType::RInteger(1)),
+        )),
+        b"|1\r\n+ttl\r\n:10\r\n:1\r\n"
+    );
+}
+
+#[test]
+fn resp3_types_are_downgraded_for_resp2_clients() {
+    let response = RespType::RArray(vec![
+        RespType::RSet(vec![RespType::RBoolean(true)]),
+        RespType::RDouble(1.5),
+        RespType::RNull(),
+        RespType::RVerbatimString("txt".into(), "texto".into()),
+    ]);
+    assert_eq!(
+        parse_response(response.into_protocol(2)),
+        b"*4\r\n*1\r\n:1\r\n$3\r\n1.5\r\n$-1\r\n$5\r\ntexto\r\n"
+    );
+}
+
+#[test]
+fn parse_request_accepts_inline_command() {
+    let result = parse_request(b"PING\r\n").unwrap();
+    assert_eq!(
+        result,
+        RespType::RArray(vec![RespType::RBulkString("PING".into())])
+    );
+
+    let result = parse_request(b"  set   clave  valor\n").unwrap();
+    assert_eq!(
+        result,
+        RespType::RArray(vec![
+            RespType::RBulkString("set".into()),
+            RespType::RBulkString("clave".into()),
+            RespType::RBulkString("valor".into()),
+        ])
+    );
+}
+
+#[test]
+fn parse_inline_handles_quotes_and_escapes() {
+    let result = parse_inline(b"set \"a b\\x41\\\"\" 'it\\'s' \"\"\r\n").unwrap();
+    assert_eq!(
+        result,
+        RespType::RArray(vec![
+            RespType::RBulkString("set".into()),
+            RespType::RBulkString("a bA\"".into()),
+            RespType::RBulkString("it's".into()),
+            RespType::RBulkString("".into()),
+        ])
+    );
+}
+
+#[test]
+fn parse_inline_returns_error_when_quotes_are_unbalanced() {
+    let expected = ParseError::InvalidRequest("unbalanced quotes in request".to_string());
+    assert_eq!(parse_inline(b"get \"clave\r\n"), Err(expected));
+    assert!(parse_inline(b"get 'clave\r\n").is_err());
+    assert!(parse_inline(b"get \"clave\"x\r\n").is_err());
+}
+
+#[test]
+fn get_complete_request_len_rejects_oversized_lengths() {
+    assert!(get_complete_request_len(b"$536870913\r\n").is_err());
+    assert!(get_complete_request_len(b"$9223372036854775807\r\n").is_err());
+    assert_eq!(get_complete_request_len(b"$536870912\r\n").unwrap(), None);
+    assert!(get_complete_request_len(b"*9223372036854775807\r\n").is_err());
+    let nested = b"*1\r\n".repeat(MAX_NESTING_DEPTH + 1);
+    assert!(get_complete_request_len(&nested).is_err());
+    let nested = b"*1\r\n".repeat(MAX_NESTING_DEPTH);
+    assert_eq!(get_complete_request_len(&nested).unwrap(), None);
+}
+
+#[test]
+fn get_complete_request_len_handles_inline_commands() {
+    assert_eq!(get_complete_request_len(b"PING\r\nPING").unwrap(), Some(6));
+    assert_eq!(get_complete_request_len(b"PIN").unwrap(), None);
+    let too_big = vec![b'a'; MAX_INLINE_LEN + 2];
+    assert!(get_complete_request_len(&too_big).is_err());
+}
+
+#[test]
+fn parse_returns_ok_when_given_array_with_two_digit_length() {
+    let elements: Vec<RespType> = (0..12)
+        .map(|i| RespType::RBulkString(i.to_string().into()))
+        .collect();
+    let request = parse_response(RespType::RArray(elements.clone()));
+    assert_eq!(parse(&request).unwrap(), RespType::RArray(elements));
 }
@@ -0,0 +1,405 @@
+//! Servicio que implementa el proxy, que reparte los comandos de los clientes entre varios servidores Redis.
+
+use crate::domain::entities::hash_ring::HashRing;
+use crate::services::commander::get_command_keys;
+use crate::services::parser_service::{
+    get_complete_request_len, parse, parse_request, parse_response,
+};
+use crate::services::utils::resp_type::RespType;
+use crate::services::worker_service::ThreadPool;
+use std::collections::{hash_map::Entry, BTreeMap, HashMap};
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, sleep};
+use std::time::Duration;
+
+/// Dirección en la que el proxy recibe conexiones si la configuración no especifica `bind`.
+pub const DEFAULT_PROXY_BIND: &str = "127.0.0.1";
+
+/// Cantidad de clientes que el proxy atiende en simultáneo.
+const PROXY_WORKERS: usize = 10;
+
+/// Tiempo entre dos verificaciones del estado de los servidores.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tiempo máximo de espera para conectarse a un servidor y, al verificar su estado, para recibir su respuesta.
+const BACKEND_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Tiempo máximo de espera para enviar un comando de un cliente a un servidor y para recibir su respuesta.
+const BACKEND_REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Estado compartido del proxy: los servidores a los que reparte los comandos, el anillo que asigna cada clave a un
+/// servidor y si cada servidor respondió a la última verificación de estado.
+#[derive(Debug)]
+struct Proxy {
+    backends: Vec<String>,
+    ring: HashRing,
+    healthy: Vec<AtomicBool>,
+}
+
+impl Proxy {
+    fn new(backends: Vec<String>) -> Self {
+        Proxy {
+            ring: HashRing::new(&backends),
+            healthy: backends.iter().map(|_| AtomicBool::new(true)).collect(),
+            backends,
+        }
+    }
+
+    /// Retorna el servidor al que le corresponde la clave.
+    ///
+    /// Devuelve error si el servidor está caído: sus claves no pasan a otro servidor, ya que este no tiene sus datos.
+    fn route(&self, key: &RespType) -> Result<usize, Vec<u8>> {
+        let node = self
+            .ring
+            .get_node(key.as_bytes().unwrap_or_default())
+            .ok_or_else(|| error_reply(String::from("ERR no backend available")))?;
+        if !self.healthy[node].load(Ordering::SeqCst) {
+            return Err(error_reply(format!(
+                "ERR backend {} is down",
+                self.backends[node]
+            )));
+        }
+        Ok(node)
+    }
+}
+
+/// Inicia el proxy.
+///
+/// Recibe conexiones en cada uno de los `listeners` y reparte los comandos de cada cliente entre los servidores de
+/// `backends`, según el hash de sus claves. Además, verifica periódicamente con `PING` que cada servidor esté
+/// disponible.
+pub fn init(listeners: Vec<TcpListener>, backends: Vec<String>) {
+    let proxy = Arc::new(Proxy::new(backends));
+    let checked = Arc::clone(&proxy);
+    thread::spawn(move || check_backends(checked));
+    let pool = Arc::new(ThreadPool::new(PROXY_WORKERS));
+    let handles: Vec<_> = listeners
+        .into_iter()
+        .map(|listener| {
+            let proxy = Arc::clone(&proxy);
+            let pool = Arc::clone(&pool);
+            thread::spawn(move || accept_clients(listener, proxy, pool))
+        })
+        .collect();
+    for handle in handles {
+        let _ = handle.join();
+    }
+}
+
+/// Recibe los clientes de `listener` y atiende a cada uno en un hilo del pool.
+fn accept_clients(listener: TcpListener, proxy: Arc<Proxy>, pool: Arc<ThreadPool>) {
+    if let Ok(addr) = listener.local_addr() {
+        println!("Proxy listening on {}", addr);
+    }
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let proxy = Arc::clone(&proxy);
+                pool.spawn(move || handle_client(stream, proxy));
+            }
+            Err(e) => println!("Couldn't accept proxy client: {}", e),
+        }
+    }
+}
+
+/// Atiende a un cliente del proxy hasta que cierre la conexión o envíe `QUIT`.
+///
+/// Las solicitudes se leen con el mismo formato que el servidor (RESP o comandos inline) y se responden en orden.
+fn handle_client(mut stream: TcpStream, proxy: Arc<Proxy>) {
+    let mut session = ProxySession::new(proxy);
+    let mut pending = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        loop {
+            let len = match get_complete_request_len(&pending) {
+                Ok(Some(len)) => len,
+                Ok(None) => break,
+                Err(e) => {
+                    let _ = stream.write_all(&protocol_error(e));
+                    return;
+                }
+            };
+            let request: Vec<u8> = pending.drain(..len).collect();
+            let args = match parse_request(&request) {
+                Ok(RespType::RArray(args)) => args,
+                Ok(_) => continue,
+                Err(e) => {
+                    let _ = stream.write_all(&protocol_error(e));
+                    return;
+                }
+            };
+            if args.is_empty() {
+                continue;
+            }
+            let quit = args[0].is_word("quit");
+            let reply = if quit {
+                parse_response(RespType::RSimpleString(String::from("OK")))
+            } else {
+                session.execute(&args)
+            };
+            if stream
+                .write_all(&reply)
+                .and_then(|_| stream.flush())
+                .is_err()
+                || quit
+            {
+                return;
+            }
+        }
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => return,
+            Ok(size) => pending.extend_from_slice(&buf[..size]),
+        }
+    }
+}
+
+/// Conexiones de un cliente del proxy con los servidores, que se abren a medida que se necesitan.
+struct ProxySession {
+    proxy: Arc<Proxy>,
+    connections: HashMap<usize, BackendConnection>,
+}
+
+impl ProxySession {
+    fn new(proxy: Arc<Proxy>) -> Self {
+        ProxySession {
+            proxy,
+            connections: HashMap::new(),
+        }
+    }
+
+    /// Resuelve un comando y devuelve la respuesta para el cliente, ya codificada.
+    ///
+    /// `PING` y `ECHO` se responden sin consultar a los servidores; `MGET`, `MSET`, `DEL` y `EXISTS` se dividen entre
+    /// los servidores de sus claves; el resto de los comandos se reenvían al servidor de sus claves.
+    fn execute(&mut self, args: &[RespType]) -> Vec<u8> {
+        let command = args
+            .first()
+            .and_then(RespType::as_str)
+            .unwrap_or_default()
+            .to_lowercase();
+        let result = match command.as_str() {
+            "ping" if args.len() == 1 => Ok(parse_response(RespType::RSimpleString(String::from(
+                "PONG",
+            )))),
+            "ping" | "echo" if args.len() == 2 => Ok(parse_response(args[1].clone())),
+            "ping" | "echo" => Err(wrong_arguments(&command)),
+            "mget" if args.len() > 1 => self.mget(args),
+            "mset" if args.len() > 1 && args.len() % 2 == 1 => self.mset(args),
+            "del" | "exists" if args.len() > 1 => self.count_keys(args),
+            "mget" | "mset" | "del" | "exists" => Err(wrong_arguments(&command)),
+            _ => self.forward(&command, args),
+        };
+        result.unwrap_or_else(|error| error)
+    }
+
+    /// Reenvía el comando al servidor de sus claves y devuelve su respuesta sin modificarla.
+    ///
+    /// Devuelve error si el comando no tiene claves o si sus claves pertenecen a distintos servidores.
+    fn forward(&mut self, command: &str, args: &[RespType]) -> Result<Vec<u8>, Vec<u8>> {
+        let keys = get_command_keys(args);
+        if keys.is_empty() {
+            return Err(error_reply(format!(
+                "ERR '{}' command is not supported by the proxy",
+                command
+            )));
+        }
+        let node = self.proxy.route(keys[0])?;
+        for key in &keys[1..] {
+            if self.proxy.route(key)? != node {
+                return Err(error_reply(String::from(
+                    "ERR keys in request don't hash to the same backend",
+                )));
+            }
+        }
+        self.request(node, args.to_vec())
+    }
+
+    /// Obtiene los valores de las claves de cada servidor y los devuelve en el orden en que se pidieron.
+    fn mget(&mut self, args: &[RespType]) -> Result<Vec<u8>, Vec<u8>> {
+        let keys = &args[1..];
+        let mut values = vec![RespType::RNullBulkString(); keys.len()];
+        for (node, indexes) in self.group_by_backend(keys.iter())? {
+            let mut cmd = vec![args[0].clone()];
+            cmd.extend(indexes.iter().map(|index| keys[*index].clone()));
+            match self.request_parsed(node, cmd)? {
+                RespType::RArray(items) if items.len() == indexes.len() => {
+                    for (index, item) in indexes.into_iter().zip(items) {
+                        values[index] = item;
+                    }
+                }
+                _ => return Err(unexpected_reply(&self.proxy.backends[node])),
+            }
+        }
+        Ok(parse_response(RespType::RArray(values)))
+    }
+
+    /// Guarda en cada servidor los pares de sus claves y devuelve la respuesta de los servidores.
+    ///
+    /// A diferencia de un único servidor, la operación no es atómica: si un servidor falla, los demás pueden
+    /// haber guardado sus claves.
+    fn mset(&mut self, args: &[RespType]) -> Result<Vec<u8>, Vec<u8>> {
+        let pairs: Vec<&[RespType]> = args[1..].chunks(2).collect();
+        let mut reply = Vec::new();
+        for (node, indexes) in self.group_by_backend(pairs.iter().map(|pair| &pair[0]))? {
+            let mut cmd = vec![args[0].clone()];
+            for index in indexes {
+                cmd.extend_from_slice(pairs[index]);
+            }
+            reply = self.request(node, cmd)?;
+            if reply.starts_with(b"-") {
+                return Err(reply);
+            }
+        }
+        Ok(reply)
+    }
+
+    /// Envía `DEL` o `EXISTS` a cada servidor con sus claves y devuelve la suma de las respuestas.
+    fn count_keys(&mut self, args: &[RespType]) -> Result<Vec<u8>, Vec<u8>> {
+        let keys = &args[1..];
+        let mut total = 0;
+        for (node, indexes) in self.group_by_backend(keys.iter())? {
+            let mut cmd = vec![args[0].clone()];
+            cmd.extend(indexes.iter().map(|index| keys[*index].clone()));
+            match self.request_parsed(node, cmd)? {
+                RespType::RInteger(count) => total += count,
+                _ => return Err(unexpected_reply(&self.proxy.backends[node])),
+            }
+        }
+        Ok(parse_response(RespType::RInteger(total)))
+    }
+
+    /// Agrupa las posiciones de las claves según el servidor al que le corresponde cada una.
+    fn group_by_backend<'a, I>(&self, keys: I) -> Result<BTreeMap<usize, Vec<usize>>, Vec<u8>>
+    where
+        I: Iterator<Item = &'a RespType>,
+    {
+        let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (index, key) in keys.enumerate() {
+            groups
+                .entry(self.proxy.route(key)?)
+                .or_default()
+                .push(index);
+        }
+        Ok(groups)
+    }
+
+    /// Envía el comando al servidor y devuelve su respuesta interpretada, o la respuesta original si es un error.
+    fn request_parsed(&mut self, node: usize, cmd: Vec<RespType>) -> Result<RespType, Vec<u8>> {
+        let reply = self.request(node, cmd)?;
+        if reply.starts_with(b"-") {
+            return Err(reply);
+        }
+        parse(&reply).map_err(|_| unexpected_reply(&self.proxy.backends[node]))
+    }
+
+    /// Envía el comando al servidor y devuelve su respuesta sin interpretarla.
+    ///
+    /// Si la conexión con el servidor falla se descarta, para abrir una nueva en el próximo comando.
+    fn request(&mut self, node: usize, cmd: Vec<RespType>) -> Result<Vec<u8>, Vec<u8>> {
+        let proxy = Arc::clone(&self.proxy);
+        let backend = &proxy.backends[node];
+        let request = parse_response(RespType::RArray(cmd));
+        let result = match self.connections.entry(node) {
+            Entry::Occupied(entry) => entry.into_mut().request(&request),
+            Entry::Vacant(entry) => BackendConnection::connect(backend, BACKEND_REPLY_TIMEOUT)
+                .and_then(|connection| entry.insert(connection).request(&request)),
+        };
+        result.map_err(|e| {
+            self.connections.remove(&node);
+            error_reply(format!("ERR backend {} unavailable: {}", backend, e))
+        })
+    }
+}
+
+/// Conexión con un servidor, que acumula los bytes recibidos hasta completar cada respuesta.
+struct BackendConnection {
+    stream: TcpStream,
+    pending: Vec<u8>,
+}
+
+impl BackendConnection {
+    /// Se conecta al servidor; el envío de cada solicitud y la lectura de su respuesta fallan si tardan más de
+    /// `timeout`.
+    fn connect(backend: &str, timeout: Duration) -> io::Result<Self> {
+        let addr = backend
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, "invalid backend address"))?;
+        let stream = TcpStream::connect_timeout(&addr, BACKEND_TIMEOUT)?;
+        stream.set_read_timeout(Some(timeout))?;
+        stream.set_write_timeout(Some(timeout))?;
+        Ok(BackendConnection {
+            stream,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Envía una solicitud ya codificada y devuelve la respuesta completa.
+    fn request(&mut self, request: &[u8]) -> io::Result<Vec<u8>> {
+        self.stream.write_all(request)?;
+        self.stream.flush()?;
+        let mut buf = [0u8; 4096];
+        loop {
+            match get_complete_request_len(&self.pending) {
+                Ok(Some(len)) => return Ok(self.pending.drain(..len).collect()),
+                Ok(None) => {}
+                Err(e) => return Err(io::Error::new(ErrorKind::InvalidData, e.to_string())),
+            }
+            match self.stream.read(&mut buf)? {
+                0 => return Err(ErrorKind::UnexpectedEof.into()),
+                size => self.pending.extend_from_slice(&buf[..size]),
+            }
+        }
+    }
+}
+
+/// Envía `PING` a cada servidor una vez por `HEALTH_CHECK_INTERVAL` y marca como caídos a los que no responden.
+fn check_backends(proxy: Arc<Proxy>) {
+    let mut connections: Vec<Option<BackendConnection>> =
+        proxy.backends.iter().map(|_| None).collect();
+    let ping = parse_response(RespType::RArray(vec![RespType::RBulkString("PING".into())]));
+    loop {
+        for (node, backend) in proxy.backends.iter().enumerate() {
+            if connections[node].is_none() {
+                connections[node] = BackendConnection::connect(backend, BACKEND_TIMEOUT).ok();
+            }
+            let healthy = match connections[node].as_mut().map(|c| c.request(&ping)) {
+                Some(Ok(reply)) => reply == b"+PONG\r\n",
+                _ => false,
+            };
+            if !healthy {
+                connections[node] = None;
+            }
+            if proxy.healthy[node].swap(healthy, Ordering::SeqCst) != healthy {
+                println!(
+                    "Backend {} is {}",
+                    backend,
+                    if healthy { "up" } else { "down" }
+                );
+            }
+        }
+        sleep(HEALTH_CHECK_INTERVAL);
+    }
+}
+
+fn error_reply(message: String) -> Vec<u8> {
+    parse_response(RespType::RError(message))
+}
+
+fn wrong_arguments(command: &str) -> Vec<u8> {
+    error_reply(format!(
+        "ERR wrong number of arguments for '{}' command",
+        command
+    ))
+}
+
+fn unexpected_reply(backend: &str) -> Vec<u8> {
+    error_reply(format!("ERR unexpected reply from backend {}", backend))
+}
+
+fn protocol_error(error: impl std::fmt::Display) -> Vec<u8> {
+    error_reply(format!("ERR Protocol error: {}", error))
+}
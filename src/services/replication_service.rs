@@ -0,0 +1,414 @@
+//! Servicio que mantiene la conexión de una réplica con su master y aplica los comandos de escritura que recibe.
+
+use crate::domain::entities::config::Config;
+use crate::domain::entities::key_value_item::KeyAccessTime;
+use crate::domain::entities::message::WorkerMessage;
+use crate::domain::entities::replication::MasterLinkEvent;
+use crate::domain::entities::session::Session;
+use crate::domain::implementations::database::Database;
+use crate::services::commander::handle_command;
+use crate::services::parser_service::{get_complete_request_len, parse_request, parse_response};
+use crate::services::server_service::publish_database_events;
+use crate::services::utils::resp_type::RespType;
+use std::error::Error;
+use std::io::{ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Tiempo que se espera antes de volver a intentar la conexión con el master.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Intervalo con el que la réplica le confirma al master el offset que procesó (`replconf ack`).
+const ACK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tiempo máximo que se espera cada respuesta del master durante la sincronización.
+const SYNC_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Inicia, en otro hilo de ejecución, la replicación del master que se encuentra en `host:port`.
+///
+/// El hilo se conecta con el master, realiza el handshake (`ping`, `auth` si la configuración especifica
+/// `masterauth`, `replconf` y `psync`), reemplaza los datos de `database` por la copia completa que recibe y luego
+/// aplica cada comando de escritura que le envía el master. Si se pierde la conexión, vuelve a conectarse y a
+/// sincronizar los datos. Cada evento de la conexión se informa a la entidad `Server` junto con `generation`: el hilo
+/// termina cuando la conexión deja de ser la vigente, por `replicaof` o al detenerse el servidor.
+pub fn start_replication(
+    generation: usize,
+    host: String,
+    port: u16,
+    database: Arc<RwLock<Database>>,
+    config: Arc<RwLock<Config>>,
+    tx: Sender<WorkerMessage>,
+) {
+    thread::spawn(move || {
+        while notify(&tx, generation, MasterLinkEvent::Connecting) {
+            let stream = match TcpStream::connect((host.as_str(), port)) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    println!("Couldn't connect to master {}:{}: {}", host, port, e);
+                    thread::sleep(RECONNECT_INTERVAL);
+                    continue;
+                }
+            };
+            let link = match stream.try_clone() {
+                Ok(link) => link,
+                Err(_) => continue,
+            };
+            if !notify(&tx, generation, MasterLinkEvent::Sync(link)) {
+                return;
+            }
+            let mut master = MasterConnection::new(stream);
+            match replicate(&mut master, generation, &database, &config, &tx) {
+                Ok(()) => return,
+                Err(e) => {
+                    println!("Lost connection with master {}:{}: {}", host, port, e);
+                    thread::sleep(RECONNECT_INTERVAL);
+                }
+            }
+        }
+    });
+}
+
+/// Devuelve el comando que se envía a las réplicas luego de que el master ejecutó `request` y respondió `reply`.
+///
+/// Los comandos cuyo efecto depende del azar o del momento en que se ejecutan se reemplazan por uno equivalente a
+/// lo que ocurrió en el master, según el estado de `database` luego de ejecutarlos:
+/// * `spop` se envía como `srem` de los elementos que se eliminaron.
+/// * `expire` y `expireat` se envían como `expireat` con el momento en que expira la clave.
+/// * `setex`, `psetex` y `set` con `ex` o `px` se envían como `set` con `pxat`, y `getex` con `ex` o `px` como
+///   `getex` con `pxat`.
+///
+/// Así, la clave expira en las réplicas en el mismo momento que en el master, sin importar cuándo reciben el comando.
+/// Si la clave ya no existe se envía `del`. Si el comando no modificó nada que deba replicarse devuelve None.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::RespType;
+/// # use proyecto_taller_1::domain::implementations::database::Database;
+/// # use proyecto_taller_1::domain::entities::key_value_item::{ValueType, ValueTimeItemBuilder};
+/// use proyecto_taller_1::services::replication_service::get_propagated_command;
+///
+/// let mut db = Database::new("dummy_db_propagated.csv".to_string());
+/// let spop = vec![RespType::RBulkString("spop".into()), RespType::RBulkString("frutas".into())];
+/// let propagated = get_propagated_command(&spop, &RespType::RBulkString("kiwi".into()), &db);
+/// assert_eq!(propagated, Some(RespType::RArray(vec![
+///     RespType::RBulkString("srem".into()),
+///     RespType::RBulkString("frutas".into()),
+///     RespType::RBulkString("kiwi".into()),
+/// ])));
+/// assert_eq!(get_propagated_command(&spop, &RespType::RNullBulkString(), &db), None);
+///
+/// db.add(b"mascota".to_vec(), ValueTimeItemBuilder::new(ValueType::StringType("perro".into())).with_timeout(4102444800).build());
+/// let setex = vec![
+///     RespType::RBulkString("SETEX".into()),
+///     RespType::RBulkString("mascota".into()),
+///     RespType::RBulkString("10".into()),
+///     RespType::RBulkString("perro".into()),
+/// ];
+/// let propagated = get_propagated_command(&setex, &RespType::RBulkString("Ok".into()), &db);
+/// assert_eq!(propagated, Some(RespType::RArray(vec![
+///     RespType::RBulkString("set".into()),
+///     RespType::RBulkString("mascota".into()),
+///     RespType::RBulkString("perro".into()),
+///     RespType::RBulkString("pxat".into()),
+///     RespType::RBulkString("4102444800000".into()),
+/// ])));
+/// # let _ = std::fs::remove_file("dummy_db_propagated.csv");
+/// ```
+pub fn get_propagated_command(
+    request: &[RespType],
+    reply: &RespType,
+    database: &Database,
+) -> Option<RespType> {
+    let command = request.first()?.as_str()?.to_lowercase();
+    match command.as_str() {
+        "spop" => {
+            let members = match reply {
+                RespType::RBulkString(member) => vec![RespType::RBulkString(member.clone())],
+                RespType::RArray(members) if !members.is_empty() => members.clone(),
+                _ => return None,
+            };
+            let mut srem = vec![
+                RespType::RBulkString("srem".into()),
+                request.get(1)?.clone(),
+            ];
+            srem.extend(members);
+            Some(RespType::RArray(srem))
+        }
+        "expire" | "expireat" => {
+            if reply != &RespType::RInteger(1) {
+                return None;
+            }
+            let key = request.get(1)?;
+            Some(match get_expire_at(database, key) {
+                Some(expire_at) => RespType::RArray(vec![
+                    RespType::RBulkString("expireat".into()),
+                    key.clone(),
+                    RespType::RBulkString(expire_at.to_string().into()),
+                ]),
+                None => get_del_command(key),
+            })
+        }
+        "setex" | "psetex" if request.len() == 4 => {
+            let key = request.get(1)?;
+            Some(match get_expire_at(database, key) {
+                Some(expire_at) => RespType::RArray(vec![
+                    RespType::RBulkString("set".into()),
+                    key.clone(),
+                    request[3].clone(),
+                    RespType::RBulkString("pxat".into()),
+                    RespType::RBulkString((expire_at * 1000).to_string().into()),
+                ]),
+                None => get_del_command(key),
+            })
+        }
+        "set" | "getex" => {
+            // posición de la opción de expiración relativa: en `set` se busca a partir del valor, en `getex` luego de la clave
+            let first_option = if command == "set" { 3 } else { 2 };
+            let position = (first_option..request.len().saturating_sub(1)).find(|position| {
+                request[*position].is_word("ex") || request[*position].is_word("px")
+            });
+            let position = match position {
+                Some(position) => position,
+                None => return Some(RespType::RArray(request.to_vec())),
+            };
+            let key = request.get(1)?;
+            let expire_at = match get_expire_at(database, key) {
+                Some(expire_at) => expire_at,
+                None => return Some(get_del_command(key)),
+            };
+            let mut command = request.to_vec();
+            command[position] = RespType::RBulkString("pxat".into());
+            command[position + 1] = RespType::RBulkString((expire_at * 1000).to_string().into());
+            Some(RespType::RArray(command))
+        }
+        _ => Some(RespType::RArray(request.to_vec())),
+    }
+}
+
+/// Retira las claves que la base de datos eliminó por sí misma, al expirar o al desalojarlas (ver
+/// `Database::take_deleted_keys`), y devuelve los comandos `del` que replican su eliminación.
+///
+/// Las réplicas no eliminan las claves expiradas por su cuenta sino que esperan estos comandos, para que sus datos
+/// no dependan de cuándo revisan las expiraciones.
+pub fn get_deletion_commands(database: &mut Database) -> Vec<RespType> {
+    database
+        .take_deleted_keys()
+        .into_iter()
+        .map(|key| get_del_command(&RespType::RBulkString(key)))
+        .collect()
+}
+
+/// Devuelve el momento, en segundos desde la época UNIX, en que expira `key`, o None si la clave no existe o es
+/// persistente.
+fn get_expire_at(database: &Database, key: &RespType) -> Option<u64> {
+    match database
+        .check_timeout_item(key.as_bytes()?)
+        .0?
+        .get_timeout()
+    {
+        KeyAccessTime::Volatile(expire_at) => Some(*expire_at),
+        KeyAccessTime::Persistent => None,
+    }
+}
+
+/// Devuelve el comando `del` de la clave.
+fn get_del_command(key: &RespType) -> RespType {
+    RespType::RArray(vec![RespType::RBulkString("del".into()), key.clone()])
+}
+
+/// Informa un evento de la conexión con el master a la entidad `Server`.
+///
+/// Devuelve false si la conexión dejó de ser la vigente o el servidor se detuvo, en cuyo caso el hilo debe terminar.
+fn notify(tx: &Sender<WorkerMessage>, generation: usize, event: MasterLinkEvent) -> bool {
+    let (link_tx, link_rx) = mpsc::channel();
+    tx.send(WorkerMessage::MasterLink(generation, event, link_tx))
+        .is_ok()
+        && link_rx.recv().unwrap_or(false)
+}
+
+/// Sincroniza los datos con el master y luego aplica los comandos de escritura que recibe.
+///
+/// Devuelve Ok si la conexión dejó de ser la vigente, o error si se perdió o el master respondió algo inesperado.
+fn replicate(
+    master: &mut MasterConnection,
+    generation: usize,
+    database: &Arc<RwLock<Database>>,
+    config: &Arc<RwLock<Config>>,
+    tx: &Sender<WorkerMessage>,
+) -> Result<(), Box<dyn Error>> {
+    let (replid, mut offset) = full_sync(master, database, config, tx)?;
+    if !notify(tx, generation, MasterLinkEvent::Connected(replid, offset)) {
+        return Ok(());
+    }
+    master.stream.set_read_timeout(Some(ACK_INTERVAL))?;
+    let mut session = Session::new(true);
+    let mut last_ack = Instant::now();
+    loop {
+        let mut applied = false;
+        while let Some(len) =
+            get_complete_request_len(&master.pending).map_err(|e| e.to_string())?
+        {
+            let request: Vec<u8> = master.pending.drain(..len).collect();
+            match parse_request(&request).map_err(|e| e.to_string())? {
+                RespType::RArray(args)
+                    if args.first().is_some_and(|cmd| cmd.is_word("replconf"))
+                        && args.get(1).is_some_and(|arg| arg.is_word("getack")) =>
+                {
+                    master.send_ack(offset)?;
+                    last_ack = Instant::now();
+                }
+                RespType::RArray(args) if args.first().is_some_and(|cmd| cmd.is_word("ping")) => {}
+                command => {
+                    let _ = handle_command(command, tx, 0, database, config, false, &mut session);
+                    publish_database_events(database, tx, 0);
+                }
+            }
+            offset += len;
+            applied = true;
+        }
+        if applied && !notify(tx, generation, MasterLinkEvent::Offset(offset)) {
+            return Ok(());
+        }
+        if last_ack.elapsed() >= ACK_INTERVAL {
+            master.send_ack(offset)?;
+            last_ack = Instant::now();
+        }
+        match master.fill() {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Realiza el handshake con el master y reemplaza los datos por la copia completa que envía como respuesta a `psync`.
+///
+/// Devuelve el identificador de replicación del master y el offset a partir del cual se reciben sus comandos.
+fn full_sync(
+    master: &mut MasterConnection,
+    database: &Arc<RwLock<Database>>,
+    config: &Arc<RwLock<Config>>,
+    tx: &Sender<WorkerMessage>,
+) -> Result<(String, usize), Box<dyn Error>> {
+    let (port, masteruser, masterauth) = match config.read() {
+        Ok(conf) => (
+            conf.get_attribute(String::from("port")).unwrap_or_default(),
+            conf.get_attribute(String::from("masteruser")).ok(),
+            conf.get_attribute(String::from("masterauth")).ok(),
+        ),
+        Err(_) => return Err("config lock poisoned".into()),
+    };
+    master.stream.set_read_timeout(Some(SYNC_TIMEOUT))?;
+
+    master.send(&["PING"])?;
+    let pong = master.read_line()?;
+    // si el master requiere contraseña responde NOAUTH, que se resuelve a continuación con `auth`
+    if !pong.starts_with('+') && !pong.starts_with("-NOAUTH") {
+        return Err(format!("unexpected reply to PING: {}", pong).into());
+    }
+    if let Some(password) = masterauth {
+        match masteruser {
+            Some(user) => master.send(&["AUTH", &user, &password])?,
+            None => master.send(&["AUTH", &password])?,
+        }
+        master.expect_ok("AUTH")?;
+    }
+    master.send(&["REPLCONF", "listening-port", &port])?;
+    master.expect_ok("REPLCONF listening-port")?;
+    master.send(&["REPLCONF", "capa", "psync2"])?;
+    master.expect_ok("REPLCONF capa")?;
+
+    master.send(&["PSYNC", "?", "-1"])?;
+    let reply = master.read_line()?;
+    let mut words = reply.split_whitespace();
+    let (replid, offset) = match (words.next(), words.next(), words.next()) {
+        (Some("+FULLRESYNC"), Some(replid), Some(offset)) => (replid.to_string(), offset.parse()?),
+        _ => return Err(format!("unexpected reply to PSYNC: {}", reply).into()),
+    };
+    let size: usize = match master.read_line()?.strip_prefix('$') {
+        Some(size) => size.parse()?,
+        None => return Err("invalid snapshot header".into()),
+    };
+    let snapshot = master.read_bytes(size)?;
+    database
+        .write()
+        .map_err(|_| "database lock poisoned")?
+        .load_snapshot(&snapshot);
+    publish_database_events(database, tx, 0);
+    Ok((replid, offset))
+}
+
+/// Conexión con el master, que acumula los bytes recibidos hasta completar cada respuesta o comando.
+struct MasterConnection {
+    stream: TcpStream,
+    pending: Vec<u8>,
+}
+
+impl MasterConnection {
+    fn new(stream: TcpStream) -> Self {
+        MasterConnection {
+            stream,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Envía un comando al master.
+    fn send(&mut self, args: &[&str]) -> std::io::Result<()> {
+        let command = RespType::RArray(
+            args.iter()
+                .map(|arg| RespType::RBulkString(arg.to_string().into()))
+                .collect(),
+        );
+        self.stream.write_all(&parse_response(command))?;
+        self.stream.flush()
+    }
+
+    /// Le confirma al master el offset procesado.
+    fn send_ack(&mut self, offset: usize) -> std::io::Result<()> {
+        self.send(&["REPLCONF", "ACK", &offset.to_string()])
+    }
+
+    /// Lee más bytes del master; devuelve error si cerró la conexión.
+    fn fill(&mut self) -> std::io::Result<()> {
+        let mut buf = [0u8; 4096];
+        match self.stream.read(&mut buf)? {
+            0 => Err(ErrorKind::UnexpectedEof.into()),
+            size => {
+                self.pending.extend_from_slice(&buf[..size]);
+                Ok(())
+            }
+        }
+    }
+
+    /// Lee una línea de respuesta del master, sin el salto de línea final.
+    fn read_line(&mut self) -> std::io::Result<String> {
+        loop {
+            if let Some(crlf) = self.pending.windows(2).position(|window| window == b"\r\n") {
+                let line: Vec<u8> = self.pending.drain(..crlf + 2).collect();
+                return Ok(String::from_utf8_lossy(&line[..crlf]).to_string());
+            }
+            self.fill()?;
+        }
+    }
+
+    /// Lee exactamente `size` bytes del master.
+    fn read_bytes(&mut self, size: usize) -> std::io::Result<Vec<u8>> {
+        while self.pending.len() < size {
+            self.fill()?;
+        }
+        Ok(self.pending.drain(..size).collect())
+    }
+
+    /// Lee una respuesta del master y devuelve error si no es un simple string.
+    fn expect_ok(&mut self, command: &str) -> Result<(), Box<dyn Error>> {
+        let reply = self.read_line()?;
+        if reply.starts_with('+') {
+            Ok(())
+        } else {
+            Err(format!("unexpected reply to {}: {}", command, reply).into())
+        }
+    }
+}
@@ -1,94 +1,816 @@
 //! Servicio para iniciar el servidor y manejar mensajes de clientes.
 
-use super::parser_service::{parse_request, parse_response};
+use super::parser_service::{get_complete_request_len, parse_request, parse_response};
 use super::worker_service::ThreadPool;
 use crate::domain::entities::client::Client;
+use crate::domain::entities::cluster::{key_hash_slot, SlotRoute};
 use crate::domain::entities::config::Config;
-use crate::domain::entities::message::WorkerMessage;
+use crate::domain::entities::keyspace_events::KeyspaceEvents;
+use crate::domain::entities::message::{ShutdownOptions, ShutdownRequest, WorkerMessage};
+use crate::domain::entities::session::{Session, DEFAULT_USER};
+use crate::domain::implementations::connection::Connection;
 use crate::domain::implementations::database::Database;
-use crate::services::commander::handle_command;
-use crate::services::database_service::dump_to_file;
+use crate::domain::implementations::output_queue::{
+    OutputBufferLimit, OutputQueue, DEFAULT_PUBSUB_LIMIT,
+};
+use crate::errors::parse_error::ParseError;
+use crate::services::cluster_service::start_cluster;
+use crate::services::commander::{
+    execute_command, get_access_error, get_command_keys, get_redacted_command, is_write_command,
+};
+use crate::services::commands::{command_acl, command_cluster, command_pubsub, command_server};
+use crate::services::database_service::{dump_to_file, expire_keys};
+use crate::services::replication_service::{get_deletion_commands, get_propagated_command};
+use crate::services::tls_service::create_tls_config;
+use crate::services::utils::resp_type::RespType;
+use rustls::ServerConfig;
+use signal_hook::consts::{SIGINT, SIGTERM};
+use signal_hook::iterator::Signals;
 use std::error::Error;
-use std::io::{Read, Write};
-use std::net::{TcpListener, TcpStream};
-use std::sync::mpsc::{self, Sender};
-use std::sync::{Arc, RwLock};
-use std::time::Duration;
-use std::{thread, u64};
+use std::fs;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::UnixListener;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Tiempo máximo de inactividad de un cliente, en segundos, si la configuración no especifica uno válido.
+const DEFAULT_TIMEOUT: u64 = 300;
+
+/// Intervalo con el que un cliente suspendido vuelve a consultar si puede ejecutar su comando.
+const PAUSE_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Intervalo con el que los listeners vuelven a consultar si hay nuevos clientes o si el servidor se detuvo.
+const ACCEPT_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Tiempo máximo que se espera a que terminen los comandos en curso antes de detener el servidor.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Direcciones donde se reciben conexiones si la configuración no especifica la directiva `bind`.
+pub const DEFAULT_BIND: &str = "127.0.0.1 -::1";
+
+/// Error con el que se rechazan las conexiones externas cuando el servidor está en modo protegido.
+const PROTECTED_MODE_ERROR: &str = "DENIED Redis is running in protected mode because protected mode is enabled and no password is set for the default user. In this mode connections are only accepted from the loopback interface. If you want to connect from external computers to Redis you may adopt one of the following solutions: 1) Disable protected mode sending the command 'CONFIG SET protected-mode no' from the loopback interface. 2) Set the protected-mode option to 'no' in the configuration file and restart the server. 3) Set up an authentication password for the default user.";
 
 /// Inicia la conexion TCP
 ///
-/// Crea un Threadpool con 10 workers y en un hilo de ejecución distinto crea una conexión TCP
-/// que va a quedar pendiente de recibir clientes y mensajes nuevos.
+/// Crea un Threadpool con 10 workers y en hilos de ejecución distintos crea las conexiones TCP
+/// que van a quedar pendientes de recibir clientes y mensajes nuevos, una por cada dirección de `dir` (ver `parse_bind_addresses`).
 /// Establece un channel entre la entidad `Server` y el cliente para que cada cliente pueda recibir y enviar información
 /// al servidor de manera concurrente.
 /// En un tercer hilo de ejecución se hace una bajada periódica de los datos almacenados en Database al archivo `dump.rdb`.
-/// Si la configuración no tiene especificado un timeout válido, se asigna 300 segundos por defecto.
-pub fn init(db: Database, config: Config, dir: String, server_sender: Sender<WorkerMessage>) {
+/// En otro hilo se le pide periódicamente al servidor que desconecte a los clientes inactivos y a los suscriptores
+/// que no reciben sus mensajes a tiempo (ver `check_clients`), y en otro se eliminan las claves expiradas y se desalojan
+/// claves si se supera `maxmemory` (ver `database_service::expire_keys`).
+/// Los eventos sobre las claves se notifican según el atributo `notify-keyspace-events` de la configuración.
+/// Antes de recibir clientes se configuran los usuarios ACL (ver `configure_acl`) y, si la configuración especifica
+/// `replicaof <host> <port>`, se inicia la replicación de ese master (ver `replication_service::start_replication`).
+/// Si especifica `cluster-enabled yes`, el servidor es un nodo de un cluster (ver `cluster_service::start_cluster`).
+/// Si la configuración especifica un `tls-port`, se reciben también conexiones cifradas con TLS en ese puerto
+/// (ver `tls_service::create_tls_config`), que se atienden con los mismos workers y de la misma manera que las conexiones en texto plano.
+/// De la misma manera, si especifica un `unixsocket`, se reciben conexiones locales en ese socket Unix (ver `bind_unix_socket`).
+///
+/// El servidor se detiene con el comando `shutdown` o al recibir las señales SIGTERM o SIGINT (ver `wait_for_shutdown`).
+/// Entonces deja de recibir clientes, cierra sus conexiones, espera a que terminen los workers y los demás hilos,
+/// elimina el socket Unix y le indica a la entidad `Server` que escriba el log pendiente y deje de atender mensajes.
+pub fn init(mut db: Database, config: Config, dir: String, server_sender: Sender<WorkerMessage>) {
     let port = config
         .get_attribute(String::from("port"))
-        .expect("Error: Port config not set.");
-    let timeout = config
-        .get_attribute("timeout".to_string())
-        .expect("Error: Timeout config not set.")
-        .parse::<u64>()
-        .unwrap_or(300);
-    let pool = ThreadPool::new(10);
+        .expect("Error: Port config not set.")
+        .parse::<u16>()
+        .expect("Error: Invalid port.");
+    if let Err(e) = configure_acl(&config, &server_sender) {
+        panic!("ACL file couldn't be loaded. Error: {}", e);
+    }
+    if let Ok(events) = config.get_attribute(String::from("notify-keyspace-events")) {
+        match KeyspaceEvents::parse(&events) {
+            Ok(events) => db.set_notify_keyspace_events(events),
+            Err(e) => panic!("Keyspace events couldn't be configured. Error: {}", e),
+        }
+    }
+    let addresses = match parse_bind_addresses(&dir) {
+        Ok(addresses) => addresses,
+        Err(e) => panic!("Invalid bind address. Error: {}", e),
+    };
+    let mut listeners = match bind_tcp_listeners(&addresses, port) {
+        Ok(listeners) => listeners
+            .into_iter()
+            .map(|listener| Listener::Tcp(listener, None))
+            .collect::<Vec<Listener>>(),
+        Err(e) => panic!("Listener couldn't be created. Error: {}", e),
+    };
+    match configure_tls(&config) {
+        Ok(Some((tls_port, tls_config))) => match bind_tcp_listeners(&addresses, tls_port) {
+            Ok(tls_listeners) => listeners.extend(
+                tls_listeners
+                    .into_iter()
+                    .map(|listener| Listener::Tcp(listener, Some(tls_config.clone()))),
+            ),
+            Err(e) => panic!("TLS listener couldn't be created. Error: {}", e),
+        },
+        Ok(None) => {}
+        Err(e) => panic!("TLS couldn't be configured. Error: {}", e),
+    };
+    let mut unix_socket = None;
+    match bind_unix_socket(&config) {
+        Ok(Some(unix_listener)) => {
+            unix_socket = unix_listener
+                .local_addr()
+                .ok()
+                .and_then(|address| address.as_pathname().map(Path::to_path_buf));
+            listeners.push(Listener::Unix(unix_listener));
+        }
+        Ok(None) => {}
+        Err(e) => panic!("Unix socket listener couldn't be created. Error: {}", e),
+    };
+    let pool = Arc::new(ThreadPool::new(10));
     let database = Arc::new(RwLock::new(db));
     let conf = Arc::new(RwLock::new(config));
-    let cloned_db = database.clone();
+    let state = Arc::new(ServerState::default());
+    match get_configured_master(&conf) {
+        Ok(Some(master)) => {
+            command_server::start_replicaof(Some(master), &server_sender, &database, &conf)
+        }
+        Ok(None) => {}
+        Err(e) => panic!("Replication couldn't be configured. Error: {}", e),
+    }
+    if command_cluster::is_cluster_enabled(&conf) {
+        if let Err(e) = start_cluster(&conf, &addresses, port, &server_sender) {
+            panic!("Cluster couldn't be started. Error: {}", e);
+        }
+    }
+    let (shutdown_sender, shutdown_receiver) = mpsc::channel();
 
-    match TcpListener::bind(format!("{}:{}", dir, port)) {
-        Ok(listener) => {
+    let cloned_db = database.clone();
+    let (saver_stop, saver_stop_receiver) = mpsc::channel::<()>();
+    let saver = thread::spawn(move || {
+        dump_to_file(cloned_db, saver_stop_receiver);
+    });
+    let idle_conf = conf.clone();
+    let idle_sender = server_sender.clone();
+    let (idle_stop, idle_stop_receiver) = mpsc::channel::<()>();
+    let idle = thread::spawn(move || {
+        check_clients(idle_conf, idle_sender, idle_stop_receiver);
+    });
+    let expire_db = database.clone();
+    let expire_conf = conf.clone();
+    let expire_sender = server_sender.clone();
+    let (expire_stop, expire_stop_receiver) = mpsc::channel::<()>();
+    let expire = thread::spawn(move || {
+        expire_keys(expire_db, expire_conf, expire_sender, expire_stop_receiver);
+    });
+    let signals = handle_signals(shutdown_sender.clone());
+    let handles: Vec<thread::JoinHandle<()>> = listeners
+        .into_iter()
+        .map(|listener| {
+            let pool = pool.clone();
+            let server_sender = server_sender.clone();
+            let database = database.clone();
+            let conf = conf.clone();
+            let state = state.clone();
+            let shutdown_sender = shutdown_sender.clone();
             thread::spawn(move || {
-                dump_to_file(cloned_db);
-            });
-            for stream in listener.incoming() {
-                match stream {
-                    Ok(stream) => {
-                        let tx = server_sender.clone();
-                        let conf_lock = conf.clone();
-                        let cloned_database = database.clone();
-                        stream
-                            .set_read_timeout(Some(Duration::from_secs(timeout)))
-                            .expect("Could not set a read timeout");
-
-                        pool.spawn(|| {
-                            handle_connection(stream, tx, cloned_database, conf_lock)
-                                .expect("Unexpected Redis Server error");
-                        });
-                    }
-                    Err(_) => {
-                        println!("Couldn't get stream");
-                        continue;
-                    }
-                }
+                accept_connections(
+                    listener,
+                    &pool,
+                    server_sender,
+                    database,
+                    conf,
+                    state,
+                    shutdown_sender,
+                );
+            })
+        })
+        .collect();
+
+    wait_for_shutdown(shutdown_receiver, &state, &database, &server_sender);
+    println!("Shutting down...");
+    state.stopped.store(true, Ordering::SeqCst);
+    for handle in handles {
+        if handle.join().is_err() {
+            println!("Couldn't join listener thread");
+        }
+    }
+    let (closed_tx, closed_rx) = mpsc::channel();
+    if server_sender
+        .send(WorkerMessage::CloseAllClients(closed_tx))
+        .is_ok()
+    {
+        let _ = closed_rx.recv();
+    }
+    // los listeners ya terminaron, por lo que esta es la última referencia al pool y se espera a los workers
+    drop(pool);
+    drop(saver_stop);
+    drop(idle_stop);
+    drop(expire_stop);
+    if saver.join().is_err() || idle.join().is_err() || expire.join().is_err() {
+        println!("Couldn't join background thread");
+    }
+    if let Some((handle, signals_thread)) = signals {
+        handle.close();
+        if signals_thread.join().is_err() {
+            println!("Couldn't join signals thread");
+        }
+    }
+    if let Some(path) = unix_socket {
+        let _ = fs::remove_file(path);
+    }
+    let (exit_tx, exit_rx) = mpsc::channel();
+    if server_sender.send(WorkerMessage::Shutdown(exit_tx)).is_ok() {
+        let _ = exit_rx.recv();
+    }
+    println!("Server is now ready to exit, bye bye...");
+}
+
+/// Estado de ejecución del servidor, compartido entre los listeners y los workers que atienden a los clientes.
+/// * stopping: mientras el servidor se prepara para detenerse no se reciben clientes ni se ejecutan comandos nuevos.
+/// * stopped: el servidor se detuvo; los listeners terminan y los clientes que esperaban para ejecutar un comando se desconectan.
+/// * in_flight: cantidad de comandos que se están ejecutando.
+/// * replicating: alguna réplica se sincronizó con el servidor, por lo que los comandos de escritura se ordenan (ver `begin_write`).
+/// * unordered_writes: cantidad de comandos de escritura que se están ejecutando sin ordenar.
+/// * replication: se toma mientras se ejecuta un comando de escritura y se lo envía a las réplicas, o mientras se
+///   sincroniza una réplica, para que las réplicas reciban los comandos en el mismo orden en que se aplicaron.
+#[derive(Debug, Default)]
+struct ServerState {
+    stopping: AtomicBool,
+    stopped: AtomicBool,
+    in_flight: AtomicUsize,
+    replicating: AtomicBool,
+    unordered_writes: AtomicUsize,
+    replication: Mutex<()>,
+}
+
+impl ServerState {
+    /// Registra que un cliente comienza a ejecutar un comando.
+    ///
+    /// Si el servidor se está preparando para detenerse, espera hasta que lo haga o se cancele la detención.
+    /// Devuelve false si el servidor se detuvo, en cuyo caso el comando no debe ejecutarse.
+    fn begin_command(&self) -> bool {
+        loop {
+            // primero se registra el comando, para que quien detiene el servidor lo espere si no vio el aviso
+            self.in_flight.fetch_add(1, Ordering::SeqCst);
+            if !self.stopping.load(Ordering::SeqCst) {
+                return true;
+            }
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            if self.stopped.load(Ordering::SeqCst) {
+                return false;
+            }
+            thread::sleep(PAUSE_CHECK_INTERVAL);
+        }
+    }
+
+    /// Registra que un cliente terminó de ejecutar un comando.
+    fn end_command(&self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Registra que un cliente comienza a ejecutar un comando de escritura.
+    ///
+    /// Mientras ninguna réplica se haya sincronizado los comandos se ejecutan en paralelo; a partir de entonces se
+    /// ejecutan de a uno, para que se envíen a las réplicas en el mismo orden en que se aplicaron.
+    fn begin_write(&self) -> WriteGuard<'_> {
+        if !self.replicating.load(Ordering::SeqCst) {
+            // primero se registra el comando, para que la sincronización lo espere si no vio el aviso
+            self.unordered_writes.fetch_add(1, Ordering::SeqCst);
+            if !self.replicating.load(Ordering::SeqCst) {
+                return WriteGuard {
+                    state: self,
+                    lock: None,
+                };
+            }
+            self.unordered_writes.fetch_sub(1, Ordering::SeqCst);
+        }
+        WriteGuard {
+            state: self,
+            lock: Some(self.lock_replication()),
+        }
+    }
+
+    /// Registra que un cliente comienza a sincronizar una réplica.
+    ///
+    /// Espera a que terminen los comandos de escritura en curso, para que cada uno quede en la copia de los datos
+    /// o se le envíe a la réplica después de ella, pero no ambas cosas.
+    fn begin_sync(&self) -> WriteGuard<'_> {
+        self.replicating.store(true, Ordering::SeqCst);
+        let lock = self.lock_replication();
+        while self.unordered_writes.load(Ordering::SeqCst) > 0 {
+            thread::sleep(ACCEPT_CHECK_INTERVAL);
+        }
+        WriteGuard {
+            state: self,
+            lock: Some(lock),
+        }
+    }
+
+    /// Toma el lock que ordena los comandos de escritura; un comando que falló mientras lo tenía no impide tomarlo.
+    fn lock_replication(&self) -> MutexGuard<'_, ()> {
+        self.replication
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Espera a que terminen los comandos que se están ejecutando, como máximo `SHUTDOWN_TIMEOUT`.
+    fn wait_for_commands(&self) {
+        let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+        while self.in_flight.load(Ordering::SeqCst) > 0 {
+            if Instant::now() >= deadline {
+                println!("Timed out waiting for in-flight commands");
+                return;
+            }
+            thread::sleep(PAUSE_CHECK_INTERVAL);
+        }
+    }
+}
+
+/// Comando de escritura, o sincronización de una réplica, en curso (ver `ServerState::begin_write`).
+///
+/// Al descartarse se libera el lock que ordena los comandos o, si no se tomó, se descuenta el comando.
+struct WriteGuard<'a> {
+    state: &'a ServerState,
+    lock: Option<MutexGuard<'a, ()>>,
+}
+
+impl Drop for WriteGuard<'_> {
+    fn drop(&mut self) {
+        if self.lock.is_none() {
+            self.state.unordered_writes.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Espera pedidos para detener el servidor hasta que alguno pueda completarse.
+///
+/// Con cada pedido deja de recibir clientes y comandos nuevos, espera a que terminen los comandos en curso
+/// (salvo que se indique `NOW`) y guarda los datos (salvo que se indique `NOSAVE`). Si no pudieron guardarse
+/// y no se indicó `FORCE`, vuelve a recibir clientes y comandos y le informa el error a quien lo pidió.
+fn wait_for_shutdown(
+    requests: Receiver<ShutdownRequest>,
+    state: &ServerState,
+    database: &Arc<RwLock<Database>>,
+    tx: &Sender<WorkerMessage>,
+) {
+    for request in requests.iter() {
+        log(String::from("Preparing to shut down the server\r\n"), tx);
+        state.stopping.store(true, Ordering::SeqCst);
+        if !request.options.now {
+            state.wait_for_commands();
+        }
+        let result = if request.options.save {
+            save_before_shutdown(database, request.options.force, tx)
+        } else {
+            Ok(())
+        };
+        if result.is_err() {
+            log(
+                String::from("Errors trying to shut down the server\r\n"),
+                tx,
+            );
+            state.stopping.store(false, Ordering::SeqCst);
+        }
+        let done = result.is_ok();
+        if let Some(reply) = request.reply {
+            let _ = reply.send(result);
+        }
+        if done {
+            return;
+        }
+    }
+}
+
+/// Guarda los datos antes de detener el servidor.
+///
+/// Toma el lock de escritura de la base de datos para que la bajada periódica no escriba el archivo al mismo tiempo.
+/// Si no pudieron guardarse devuelve error, salvo que `force` indique que el servidor debe detenerse igual.
+fn save_before_shutdown(
+    database: &Arc<RwLock<Database>>,
+    force: bool,
+    tx: &Sender<WorkerMessage>,
+) -> Result<(), String> {
+    log(String::from("Saving the final dump before exiting\r\n"), tx);
+    let saved = match database.write() {
+        Ok(mut db) => db
+            .sync_storage()
+            .and_then(|_| db.save_items_to_file())
+            .map_err(|e| e.to_string()),
+        Err(_) => Err(String::from("database lock poisoned")),
+    };
+    match saved {
+        Ok(()) => {
+            log(String::from("Database saved on disk\r\n"), tx);
+            Ok(())
+        }
+        Err(e) => {
+            log(format!("Error trying to save the database: {}\r\n", e), tx);
+            if force {
+                Ok(())
+            } else {
+                Err(String::from("ERR Errors trying to SHUTDOWN. Check logs."))
             }
         }
+    }
+}
+
+/// Pide que se detenga el servidor, guardando los datos, al recibir las señales SIGTERM o SIGINT.
+///
+/// Devuelve el manejador con el que se deja de escuchar las señales y el hilo que las atiende, o None si no pudieron registrarse.
+fn handle_signals(
+    shutdown: Sender<ShutdownRequest>,
+) -> Option<(signal_hook::iterator::Handle, thread::JoinHandle<()>)> {
+    let mut signals = match Signals::new([SIGTERM, SIGINT]) {
+        Ok(signals) => signals,
         Err(e) => {
-            panic!("Listener couldn't be created. Error: {}", e.to_string());
+            println!("Couldn't register signal handlers: {}", e);
+            return None;
+        }
+    };
+    let handle = signals.handle();
+    let thread = thread::spawn(move || {
+        for signal in signals.forever() {
+            println!("Received signal {}, scheduling shutdown...", signal);
+            let request = ShutdownRequest {
+                options: ShutdownOptions::default(),
+                reply: None,
+            };
+            if shutdown.send(request).is_err() {
+                break;
+            }
+        }
+    });
+    Some((handle, thread))
+}
+
+/// Listener sobre el que se reciben clientes: un puerto TCP, cuyas conexiones se cifran si tiene configuración TLS, o un socket Unix.
+enum Listener {
+    Tcp(TcpListener, Option<Arc<ServerConfig>>),
+    Unix(UnixListener),
+}
+
+impl Listener {
+    /// Espera a que se conecte un nuevo cliente y crea su conexión.
+    ///
+    /// La conexión se crea bloqueante aunque el listener no lo sea.
+    fn accept(&self) -> Result<Connection, std::io::Error> {
+        match self {
+            Listener::Tcp(listener, tls_config) => {
+                let (stream, _) = listener.accept()?;
+                stream.set_nonblocking(false)?;
+                match tls_config {
+                    Some(tls_config) => Connection::new_tls(stream, tls_config.clone()),
+                    None => Ok(Connection::Tcp(stream)),
+                }
+            }
+            Listener::Unix(listener) => {
+                let (stream, _) = listener.accept()?;
+                stream.set_nonblocking(false)?;
+                Ok(Connection::Unix(stream))
+            }
+        }
+    }
+
+    /// Indica si `accept` debe devolver un error de tipo `WouldBlock` en lugar de esperar a un nuevo cliente.
+    fn set_nonblocking(&self, nonblocking: bool) -> Result<(), std::io::Error> {
+        match self {
+            Listener::Tcp(listener, _) => listener.set_nonblocking(nonblocking),
+            Listener::Unix(listener) => listener.set_nonblocking(nonblocking),
+        }
+    }
+}
+
+/// Recibe clientes en el listener y delega la atención de cada uno en un worker del pool.
+///
+/// El listener no es bloqueante, para poder consultar periódicamente el estado del servidor: mientras se prepara
+/// para detenerse no se reciben clientes, y una vez detenido se deja de escuchar.
+fn accept_connections(
+    listener: Listener,
+    pool: &ThreadPool,
+    server_sender: Sender<WorkerMessage>,
+    database: Arc<RwLock<Database>>,
+    conf: Arc<RwLock<Config>>,
+    state: Arc<ServerState>,
+    shutdown_sender: Sender<ShutdownRequest>,
+) {
+    if let Err(e) = listener.set_nonblocking(true) {
+        println!("Couldn't set listener in non-blocking mode: {}", e);
+        return;
+    }
+    while !state.stopped.load(Ordering::SeqCst) {
+        if state.stopping.load(Ordering::SeqCst) {
+            thread::sleep(ACCEPT_CHECK_INTERVAL);
+            continue;
+        }
+        let connection = match listener.accept() {
+            Ok(connection) => connection,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_CHECK_INTERVAL);
+                continue;
+            }
+            Err(e) => {
+                println!("Couldn't get stream: {}", e);
+                continue;
+            }
+        };
+        let tx = server_sender.clone();
+        let conf_lock = conf.clone();
+        let cloned_database = database.clone();
+        let state = state.clone();
+        let shutdown = shutdown_sender.clone();
+
+        pool.spawn(|| {
+            if let Err(e) =
+                handle_connection(connection, tx, cloned_database, conf_lock, state, shutdown)
+            {
+                println!("Unexpected Redis Server error: {}", e);
+            }
+        });
+    }
+}
+
+/// Interpreta las direcciones de la directiva `bind`, separadas por espacios.
+///
+/// Cada dirección puede ser una IPv4 o IPv6, `*` para todas las interfaces IPv4 o `::*` para todas las interfaces IPv6.
+/// Si la dirección comienza con `-`, es opcional: si no puede utilizarse, el servidor inicia igual.
+/// Devuelve las direcciones junto con si son opcionales, o error si alguna no es válida.
+///
+/// # Ejemplo
+/// ```
+/// use proyecto_taller_1::services::server_service::parse_bind_addresses;
+/// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+///
+/// let addresses = parse_bind_addresses("127.0.0.1 -::1").unwrap();
+/// assert_eq!(addresses, vec![
+///     (IpAddr::V4(Ipv4Addr::LOCALHOST), false),
+///     (IpAddr::V6(Ipv6Addr::LOCALHOST), true),
+/// ]);
+/// ```
+pub fn parse_bind_addresses(dir: &str) -> Result<Vec<(IpAddr, bool)>, String> {
+    let addresses = dir
+        .split_whitespace()
+        .map(|address| {
+            let (address, optional) = match address.strip_prefix('-') {
+                Some(address) => (address, true),
+                None => (address, false),
+            };
+            let ip = match address {
+                "*" => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                "::*" => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+                _ => address
+                    .parse::<IpAddr>()
+                    .map_err(|_| format!("Invalid address: {}", address))?,
+            };
+            Ok((ip, optional))
+        })
+        .collect::<Result<Vec<(IpAddr, bool)>, String>>()?;
+    if addresses.is_empty() {
+        return Err(String::from("No address to bind"));
+    }
+    Ok(addresses)
+}
+
+/// Crea un listener TCP en el puerto indicado por cada una de las direcciones.
+///
+/// Si una dirección opcional no puede utilizarse (por ejemplo, si el host no tiene IPv6) se omite.
+/// Devuelve error si no puede utilizarse alguna dirección obligatoria o ninguna de las direcciones.
+pub fn bind_tcp_listeners(
+    addresses: &[(IpAddr, bool)],
+    port: u16,
+) -> Result<Vec<TcpListener>, String> {
+    let mut listeners = Vec::new();
+    for (ip, optional) in addresses {
+        let address = SocketAddr::new(*ip, port);
+        match TcpListener::bind(address) {
+            Ok(listener) => listeners.push(listener),
+            Err(e) if *optional => println!("Skipping optional address {}: {}", address, e),
+            Err(e) => return Err(format!("{}: {}", address, e)),
+        }
+    }
+    if listeners.is_empty() {
+        return Err(format!("No address could be bound on port {}", port));
+    }
+    Ok(listeners)
+}
+
+/// Crea el listener del socket Unix indicado en el atributo `unixsocket` de la configuración.
+///
+/// Si ya existe un archivo en esa ruta (por ejemplo, de una ejecución anterior) se reemplaza.
+/// Los permisos del socket se toman de `unixsocketperm`, en octal (por ejemplo, `700`); si no se especifica se conservan los del sistema.
+/// Devuelve None si la configuración no especifica un `unixsocket`.
+fn bind_unix_socket(config: &Config) -> Result<Option<UnixListener>, String> {
+    let path = match config.get_attribute(String::from("unixsocket")) {
+        Ok(path) if !path.is_empty() => path,
+        _ => return Ok(None),
+    };
+    let permissions = match config.get_attribute(String::from("unixsocketperm")) {
+        Ok(perm) => Some(
+            u32::from_str_radix(&perm, 8)
+                .map_err(|_| format!("Invalid unixsocketperm: {}", perm))?,
+        ),
+        Err(_) => None,
+    };
+    if fs::metadata(&path).is_ok() {
+        fs::remove_file(&path).map_err(|e| format!("{}: {}", path, e))?;
+    }
+    let listener = UnixListener::bind(&path).map_err(|e| format!("{}: {}", path, e))?;
+    if let Some(permissions) = permissions {
+        fs::set_permissions(&path, fs::Permissions::from_mode(permissions))
+            .map_err(|e| format!("{}: {}", path, e))?;
+    }
+    Ok(Some(listener))
+}
+
+/// Obtiene el puerto y la configuración de las conexiones TLS.
+///
+/// Devuelve None si la configuración no especifica un `tls-port` o si es 0, en cuyo caso no se reciben conexiones TLS.
+fn configure_tls(config: &Config) -> Result<Option<(u16, Arc<ServerConfig>)>, String> {
+    let tls_port = match config.get_attribute(String::from("tls-port")) {
+        Ok(tls_port) => tls_port
+            .parse::<u16>()
+            .map_err(|_| format!("Invalid tls-port: {}", tls_port))?,
+        Err(_) => return Ok(None),
+    };
+    if tls_port == 0 {
+        return Ok(None);
+    }
+    Ok(Some((tls_port, create_tls_config(config)?)))
+}
+
+/// Configura los usuarios ACL del servidor.
+///
+/// Si la configuración especifica `requirepass`, se asigna esa contraseña al usuario `default`.
+/// Si especifica `aclfile`, se cargan los usuarios definidos en ese archivo, uno por línea con el formato
+/// `user <nombre> <regla> <regla> ...`. Los usuarios del archivo se definen únicamente con sus reglas,
+/// por lo que reemplazan a los existentes, incluido el usuario `default`.
+/// Devuelve error si el archivo no puede leerse o alguna de sus líneas no es válida.
+fn configure_acl(config: &Config, server_sender: &Sender<WorkerMessage>) -> Result<(), String> {
+    if let Ok(requirepass) = config.get_attribute(String::from("requirepass")) {
+        command_acl::set_requirepass(server_sender, &requirepass);
+    }
+    let path = match config.get_attribute(String::from("aclfile")) {
+        Ok(path) => path,
+        Err(_) => return Ok(()),
+    };
+    let content = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+    for (number, line) in content.lines().enumerate() {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            None => continue,
+            Some("user") => {}
+            Some(_) => {
+                return Err(format!(
+                    "{}:{}: should start with user keyword",
+                    path,
+                    number + 1
+                ))
+            }
+        }
+        let username = words
+            .next()
+            .ok_or_else(|| format!("{}:{}: missing username", path, number + 1))?;
+        let mut rules = vec![String::from("reset")];
+        rules.extend(words.map(String::from));
+        let (acl_tx, acl_rx) = mpsc::channel();
+        server_sender
+            .send(WorkerMessage::AclSetUser(
+                username.to_string(),
+                rules,
+                acl_tx,
+            ))
+            .map_err(|e| e.to_string())?;
+        acl_rx
+            .recv()
+            .map_err(|e| e.to_string())?
+            .map_err(|e| format!("{}:{}: {}", path, number + 1, e))?;
+    }
+    Ok(())
+}
+
+/// Interpreta el atributo `replicaof <host> <port>` de la configuración.
+///
+/// Devuelve None si no está especificado, o error si no es válido.
+fn get_configured_master(config: &Arc<RwLock<Config>>) -> Result<Option<(String, u16)>, String> {
+    let replicaof = match config
+        .read()
+        .ok()
+        .and_then(|conf| conf.get_attribute(String::from("replicaof")).ok())
+    {
+        Some(replicaof) => replicaof,
+        None => return Ok(None),
+    };
+    let mut words = replicaof.split_whitespace();
+    match (
+        words.next(),
+        words.next().map(str::parse::<u16>),
+        words.next(),
+    ) {
+        (Some(host), Some(Ok(port)), None) if port > 0 => Ok(Some((host.to_string(), port))),
+        _ => Err(format!("Invalid replicaof '{}'", replicaof)),
+    }
+}
+
+/// Indica si el servidor está en modo protegido, en el que solo se aceptan conexiones desde la misma máquina.
+///
+/// El modo protegido se activa si el atributo `protected-mode` de la configuración es `yes`, o si no se especifica,
+/// y el usuario `default` no requiere contraseña. Se consulta con cada conexión, por lo que puede modificarse con `config set`.
+fn is_protected_mode(config: &Arc<RwLock<Config>>, tx: &Sender<WorkerMessage>) -> bool {
+    let enabled = config
+        .read()
+        .ok()
+        .and_then(|conf| conf.get_attribute(String::from("protected-mode")).ok())
+        .is_none_or(|mode| mode.eq_ignore_ascii_case("yes"));
+    enabled && command_acl::is_nopass_user(tx, DEFAULT_USER)
+}
+
+/// Le pide periódicamente al servidor que desconecte a los clientes inactivos y a los suscriptores que superan el límite
+/// de su cola de salida, hasta que se cierra el canal `stop`.
+///
+/// El tiempo máximo de inactividad, en segundos, se lee en cada iteración del atributo `timeout` de la configuración,
+/// por lo que puede modificarse con `config set`. Si es 0 los clientes nunca se desconectan por inactividad.
+/// Si la configuración no tiene especificado un timeout válido, se asigna 300 segundos por defecto.
+/// De la misma manera se lee el límite de la clase `pubsub` de `client-output-buffer-limit`; si no es válido
+/// se utiliza `DEFAULT_PUBSUB_LIMIT`.
+fn check_clients(
+    config: Arc<RwLock<Config>>,
+    server_sender: Sender<WorkerMessage>,
+    stop: Receiver<()>,
+) {
+    while let Err(RecvTimeoutError::Timeout) = stop.recv_timeout(Duration::from_secs(1)) {
+        let timeout = config
+            .read()
+            .ok()
+            .and_then(|conf| conf.get_attribute(String::from("timeout")).ok())
+            .and_then(|timeout| timeout.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_TIMEOUT);
+        if timeout > 0
+            && server_sender
+                .send(WorkerMessage::CloseIdleClients(timeout))
+                .is_err()
+        {
+            break;
+        }
+        let limit = config
+            .read()
+            .ok()
+            .and_then(|conf| {
+                conf.get_attribute(String::from("client-output-buffer-limit"))
+                    .ok()
+            })
+            .and_then(|limit| OutputBufferLimit::parse_pubsub(&limit).ok())
+            .unwrap_or(DEFAULT_PUBSUB_LIMIT);
+        if server_sender
+            .send(WorkerMessage::CheckOutputBuffers(limit))
+            .is_err()
+        {
+            break;
         }
     }
-    println!("Shutting down...");
 }
 
 /// Lee e interpreta mensajes del cliente.
 ///
-/// Recibe un stream proveniente de la conexión TCP, un sender de mensajes de tipo WorkerMessage, una base de datos de tipo Database dentro de un RwLock
-/// y la configuración Config dentro de un RwLock.
-/// Lee el stream de datos recibido del cliente, lo decodifica, mediante la función handle_command realiza la operación que corresponda y luego
-/// escribe una respuesta sobre el mismo stream. La lectura se hace dentro de un ciclo loop
-/// hasta que se cierre la conexión por parte del cliente o se produzca algún error interno.
-pub fn handle_connection(
-    mut stream: TcpStream,
+/// Recibe un stream proveniente de la conexión TCP, TLS o del socket Unix, un sender de mensajes de tipo WorkerMessage, una base de datos de tipo Database dentro de un RwLock,
+/// la configuración Config dentro de un RwLock, el estado de ejecución del servidor y un sender para pedir que se detenga.
+/// Lee el stream de datos recibido del cliente, lo decodifica, mediante la función execute_command realiza la operación que corresponda y luego
+/// encola la respuesta en la cola de salida del cliente. La lectura se hace dentro de un ciclo loop
+/// hasta que se cierre la conexión por parte del cliente, se detenga el servidor o se produzca algún error interno.
+fn handle_connection(
+    mut stream: Connection,
     tx: Sender<WorkerMessage>,
     database: Arc<RwLock<Database>>,
     config: Arc<RwLock<Config>>,
+    state: Arc<ServerState>,
+    shutdown: Sender<ShutdownRequest>,
 ) -> Result<(), Box<dyn Error>> {
-    let client_addrs = stream.peer_addr()?;
-    let client = Client::new(client_addrs, stream.try_clone()?);
-    tx.send(WorkerMessage::AddClient(client))
+    let client_addrs = stream.peer_address()?;
+    if !stream.is_loopback() && is_protected_mode(&config, &tx) {
+        log(
+            format!(
+                "Connection from {} refused: protected mode is enabled\r\n",
+                client_addrs
+            ),
+            &tx,
+        );
+        stream.write_all(&parse_response(RespType::RError(String::from(
+            PROTECTED_MODE_ERROR,
+        ))))?;
+        stream.flush()?;
+        return Ok(());
+    }
+    let client = Client::new(client_addrs.clone(), stream.try_clone()?)?;
+    // las respuestas se encolan en la cola de salida del cliente, detrás de los mensajes que ya le haya enviado el servidor
+    let output = client.get_output();
+    let (id_tx, id_rx) = mpsc::channel();
+    tx.send(WorkerMessage::AddClient(Box::new(client), id_tx))
         .expect("Could not send client to server");
+    let client_id = id_rx.recv()?;
+    let _registration = ClientRegistration {
+        client_id,
+        tx: tx.clone(),
+    };
 
     log(
         format!("Connection to address {} established\r\n", client_addrs),
@@ -99,7 +821,15 @@ pub fn handle_connection(
         &tx,
     );
 
-    loop {
+    // acumula los bytes recibidos hasta completar cada mensaje, ya que un mensaje puede llegar
+    // partido en varias lecturas y una misma lectura puede traer varios mensajes
+    let mut pending: Vec<u8> = Vec::new();
+    // estado de la conexión: versión del protocolo RESP, que puede cambiar con el comando `hello`, y usuario autenticado.
+    // Si el usuario `default` no requiere contraseña, la conexión queda autenticada desde el inicio
+    let mut session = Session::new(command_acl::is_nopass_user(&tx, DEFAULT_USER));
+    // si el servidor se detuvo mientras se registraba el cliente, su conexión ya no se cerrará desde el servidor
+    let mut closed = state.stopped.load(Ordering::SeqCst);
+    while !closed {
         let mut buf = [0u8; 512];
         match stream.read(&mut buf) {
             Ok(0) => {
@@ -122,65 +852,50 @@ pub fn handle_connection(
                     ),
                     &tx,
                 );
-
-                match parse_request(&buf[..size]) {
-                    Ok(parsed_request) => {
-                        log(format!("Parsed request: {:?}\r\n", parsed_request), &tx);
-                        verbose(format!("Parsed request: {:?}\r\n", parsed_request), &tx);
-                        let mut subscribed = false;
-                        let (ps_sender, ps_recv) = mpsc::channel();
-                        tx.send(WorkerMessage::NewOperation(
-                            parsed_request.clone(),
-                            client_addrs,
-                            ps_sender,
-                        ))
-                        .unwrap();
-
-                        if let Ok(pubsub_state) = ps_recv.recv() {
-                            subscribed = pubsub_state;
-                        }
-
-                        match handle_command(
-                            parsed_request,
-                            &tx,
-                            client_addrs,
-                            &database,
-                            &config,
-                            subscribed,
-                        ) {
-                            Ok(res) => {
-                                let response = parse_response(res);
-                                log(
-                                    format!(
-                                        "Response for {}. Message: {:?}. Response: {}\r\n",
-                                        client_addrs,
-                                        String::from_utf8_lossy(&buf[..size]),
-                                        response
-                                    ),
-                                    &tx,
-                                );
-                                verbose(
-                                    format!(
-                                        "Response for {}. Message: {:?}. Response: {}\r\n",
-                                        client_addrs,
-                                        String::from_utf8_lossy(&buf[..size]),
-                                        response
-                                    ),
-                                    &tx,
-                                );
-                                stream.write_all(response.as_bytes())?;
-                                stream.flush()?;
+                pending.extend_from_slice(&buf[..size]);
+                loop {
+                    match get_complete_request_len(&pending) {
+                        Ok(Some(len)) => {
+                            let request: Vec<u8> = pending.drain(..len).collect();
+                            // las líneas vacías de los clientes inline se ignoran
+                            if request.iter().all(u8::is_ascii_whitespace) {
+                                continue;
                             }
-                            Err(e) => {
-                                let response = format!("Error: {}", e.to_string());
-                                stream.write_all(response.as_bytes())?;
-                                stream.flush()?;
+                            if !state.begin_command() {
+                                closed = true;
+                                break;
+                            }
+                            let outcome = handle_request(
+                                &output,
+                                client_id,
+                                &request,
+                                &tx,
+                                &database,
+                                &config,
+                                &state,
+                                &mut session,
+                            );
+                            state.end_command();
+                            let keep_open = match outcome? {
+                                RequestOutcome::KeepOpen => true,
+                                RequestOutcome::Close => false,
+                                RequestOutcome::Shutdown(options) => {
+                                    !request_shutdown(&output, options, &shutdown)?
+                                }
+                            };
+                            if !keep_open {
+                                closed = true;
+                                break;
                             }
                         }
-                    }
-                    Err(e) => {
-                        println!("Error trying to parse request: {:?}", e);
-                        continue;
+                        Ok(None) => break,
+                        Err(e) => {
+                            // no es posible saber dónde empieza el próximo mensaje, por lo que se cierra la conexión
+                            println!("Error trying to parse request: {:?}", e);
+                            write_protocol_error(&output, e)?;
+                            closed = true;
+                            break;
+                        }
                     }
                 }
             }
@@ -191,8 +906,6 @@ pub fn handle_connection(
         }
     }
 
-    tx.send(WorkerMessage::CloseClient(client_addrs))
-        .expect("Could not close client");
     log(
         format!("Connection to address {} closed\r\n", client_addrs),
         &tx,
@@ -204,6 +917,406 @@ pub fn handle_connection(
     Ok(())
 }
 
+/// Registro de un cliente en el servidor durante su conexión.
+///
+/// Al descartarse da de baja al cliente, con lo que se lo elimina de los canales y patrones a los que estaba suscrito,
+/// aun si la conexión termina por un error de lectura o escritura.
+struct ClientRegistration {
+    client_id: usize,
+    tx: Sender<WorkerMessage>,
+}
+
+impl Drop for ClientRegistration {
+    fn drop(&mut self) {
+        // si el servidor ya se detuvo, no hay registro del cual dar de baja al cliente
+        let _ = self.tx.send(WorkerMessage::CloseClient(self.client_id));
+    }
+}
+
+/// Pide que se detenga el servidor, por el comando `shutdown` del cliente.
+///
+/// Si el servidor no puede detenerse le responde al cliente el motivo.
+/// Devuelve true si el servidor se detiene, en cuyo caso debe cerrarse la conexión.
+fn request_shutdown(
+    output: &OutputQueue,
+    options: ShutdownOptions,
+    shutdown: &Sender<ShutdownRequest>,
+) -> Result<bool, Box<dyn Error>> {
+    let (reply_tx, reply_rx) = mpsc::channel();
+    let request = ShutdownRequest {
+        options,
+        reply: Some(reply_tx),
+    };
+    if shutdown.send(request).is_err() {
+        return Ok(true);
+    }
+    match reply_rx.recv() {
+        Ok(Err(e)) => {
+            output.push(parse_response(RespType::RError(e)))?;
+            Ok(false)
+        }
+        _ => Ok(true),
+    }
+}
+
+/// Qué hacer con la conexión luego de atender un mensaje del cliente.
+/// * KeepOpen: seguir leyendo mensajes.
+/// * Close: cerrarla (comando `quit`).
+/// * Shutdown: pedir que se detenga el servidor (comando `shutdown`), sin haberle respondido al cliente.
+enum RequestOutcome {
+    KeepOpen,
+    Close,
+    Shutdown(ShutdownOptions),
+}
+
+/// Interpreta un mensaje completo del cliente, realiza la operación que corresponda y encola la respuesta en la cola de salida del cliente.
+///
+/// La respuesta se codifica según la versión del protocolo RESP que utiliza la conexión.
+/// Si el comando `shutdown` puede ejecutarse no se responde, ya que la conexión se cierra al detenerse el servidor.
+/// Si el servidor es una réplica de solo lectura, los comandos de escritura se rechazan (ver `get_read_only_error`);
+/// si es master, los que se ejecutan se envían a las réplicas.
+/// En modo cluster, los comandos sobre claves que atiende otro nodo se redirigen (ver `get_cluster_error`).
+#[allow(clippy::too_many_arguments)]
+fn handle_request(
+    output: &OutputQueue,
+    client_id: usize,
+    request: &[u8],
+    tx: &Sender<WorkerMessage>,
+    database: &Arc<RwLock<Database>>,
+    config: &Arc<RwLock<Config>>,
+    state: &ServerState,
+    session: &mut Session,
+) -> Result<RequestOutcome, Box<dyn Error>> {
+    let mut outcome = RequestOutcome::KeepOpen;
+    match parse_request(request) {
+        Ok(parsed_request) => {
+            // las contraseñas no se escriben en el log
+            let logged_request = match &parsed_request {
+                RespType::RArray(array) => format!("{:?}", get_redacted_command(array)),
+                request => format!("{:?}", request),
+            };
+            log(format!("Parsed request: {}\r\n", logged_request), tx);
+            verbose(format!("Parsed request: {}\r\n", logged_request), tx);
+            let mut subscribed = false;
+            let mut shutdown = None;
+            let mut multiple_replies = false;
+            let mut no_reply = false;
+            let mut is_write = false;
+            let mut is_sync = false;
+            // los comandos rechazados por falta de autenticación o de permisos no se suspenden, no se envían a los
+            // clientes en estado "monitor" y no registran las claves que leen
+            let access_error = match &parsed_request {
+                RespType::RArray(array) => get_access_error(array, tx, client_id, session),
+                _ => None,
+            };
+            let accepted = access_error.is_none();
+            if let RespType::RArray(array) = &parsed_request {
+                match array.first() {
+                    Some(cmd) if cmd.is_word("quit") => outcome = RequestOutcome::Close,
+                    Some(cmd) if cmd.is_word("monitor") => no_reply = true,
+                    Some(cmd) if cmd.is_word("psync") || cmd.is_word("sync") => {
+                        no_reply = true;
+                        is_sync = true;
+                    }
+                    Some(cmd) if cmd.is_word("replconf") => {
+                        no_reply = array.get(1).is_some_and(|arg| arg.is_word("ack"))
+                    }
+                    Some(cmd) if cmd.is_word("shutdown") => {
+                        shutdown = command_server::get_shutdown_options(array).ok()
+                    }
+                    Some(cmd) => multiple_replies = command_pubsub::has_multiple_replies(cmd),
+                    _ => {}
+                }
+                is_write = accepted && is_write_command(array);
+                if accepted {
+                    wait_if_paused(array, tx);
+                }
+            }
+            if accepted {
+                let (ps_sender, ps_recv) = mpsc::channel();
+                tx.send(WorkerMessage::NewOperation(
+                    parsed_request.clone(),
+                    client_id,
+                    ps_sender,
+                ))
+                .unwrap();
+
+                if let Ok(pubsub_state) = ps_recv.recv() {
+                    subscribed = pubsub_state;
+                }
+                if let RespType::RArray(array) = &parsed_request {
+                    track_read_keys(array, client_id, tx);
+                }
+            }
+
+            let read_only_error = match &parsed_request {
+                RespType::RArray(array) if is_write && session.is_authenticated() => {
+                    get_read_only_error(array, tx, config)
+                }
+                _ => None,
+            };
+            let cluster_error = match &parsed_request {
+                RespType::RArray(array) if accepted && session.is_authenticated() => {
+                    get_cluster_error(array, tx, database, config, session)
+                }
+                _ => None,
+            };
+            let write_guard = if is_sync {
+                Some(state.begin_sync())
+            } else if is_write {
+                Some(state.begin_write())
+            } else {
+                None
+            };
+            let propagated = parsed_request.clone();
+            let result = match access_error.or(read_only_error).or(cluster_error) {
+                Some(error) => Err(error.into()),
+                None => execute_command(
+                    parsed_request,
+                    tx,
+                    client_id,
+                    database,
+                    config,
+                    subscribed,
+                    session,
+                ),
+            };
+            // `asking` solo vale para el comando siguiente
+            if !matches!(&propagated, RespType::RArray(array) if array.first().is_some_and(|cmd| cmd.is_word("asking")))
+            {
+                session.set_asking(false);
+            }
+            if let (RespType::RArray(array), Ok(reply)) = (&propagated, &result) {
+                if is_write && !matches!(reply, RespType::RError(_)) {
+                    // las claves que expiraron al ejecutar el comando se eliminan en las réplicas antes de enviarles
+                    // el comando; ambos se envían con el lock de la base de datos para que otro cliente no se interponga
+                    if let Ok(mut db) = database.write() {
+                        for command in get_deletion_commands(&mut db) {
+                            tx.send(WorkerMessage::Propagate(command)).unwrap();
+                        }
+                        if let Some(command) = get_propagated_command(array, reply, &db) {
+                            tx.send(WorkerMessage::Propagate(command)).unwrap();
+                        }
+                    }
+                }
+            }
+            drop(write_guard);
+            publish_database_events(database, tx, client_id);
+            match (result, shutdown) {
+                (Ok(RespType::RSimpleString(_)), Some(options)) => {
+                    outcome = RequestOutcome::Shutdown(options);
+                }
+                // la respuesta de `monitor` y la de `psync` las envía el server por la cola de salida del cliente,
+                // y `replconf ack` no tiene respuesta
+                (Ok(reply), _) if no_reply && !matches!(reply, RespType::RError(_)) => {}
+                (Ok(res), _) => {
+                    let protocol = session.get_protocol();
+                    let response = match res {
+                        // cada confirmación de una suscripción se envía como un mensaje independiente
+                        RespType::RArray(replies) if multiple_replies => replies
+                            .into_iter()
+                            .flat_map(|reply| parse_response(reply.into_protocol(protocol)))
+                            .collect(),
+                        res => parse_response(res.into_protocol(protocol)),
+                    };
+                    log(
+                        format!(
+                            "Response for client {}. Message: {}. Response: {}\r\n",
+                            client_id,
+                            logged_request,
+                            String::from_utf8_lossy(&response)
+                        ),
+                        tx,
+                    );
+                    verbose(
+                        format!(
+                            "Response for client {}. Message: {}. Response: {}\r\n",
+                            client_id,
+                            logged_request,
+                            String::from_utf8_lossy(&response)
+                        ),
+                        tx,
+                    );
+                    output.push(response)?;
+                }
+                (Err(e), _) => {
+                    output.push(parse_response(RespType::RError(e.to_string())))?;
+                }
+            }
+        }
+        Err(e) => {
+            println!("Error trying to parse request: {:?}", e);
+            write_protocol_error(output, e)?;
+        }
+    }
+    Ok(outcome)
+}
+
+/// Envía a la entidad `Server` los mensajes que notifican los eventos sobre las claves registrados por la base de datos,
+/// para que los publique, y las claves que modificó el comando del cliente, para que las invalide a quienes las siguen.
+///
+/// Antes guarda las modificaciones del comando en el motor de almacenamiento (ver `Database::sync_storage`) y envía a
+/// las réplicas la eliminación de las claves que expiraron al ejecutarlo (ver `get_deletion_commands`).
+pub fn publish_database_events(
+    database: &Arc<RwLock<Database>>,
+    tx: &Sender<WorkerMessage>,
+    client_id: usize,
+) {
+    let (notifications, modified_keys) = match database.write() {
+        Ok(mut db) => {
+            if let Err(e) = db.sync_storage() {
+                println!("Storage couldn't be synchronized: {}", e);
+            }
+            for command in get_deletion_commands(&mut db) {
+                tx.send(WorkerMessage::Propagate(command)).unwrap();
+            }
+            (db.take_notifications(), db.take_modified_keys())
+        }
+        Err(_) => return,
+    };
+    if !notifications.is_empty() {
+        tx.send(WorkerMessage::KeyspaceNotifications(notifications))
+            .unwrap();
+    }
+    if !modified_keys.is_empty() {
+        tx.send(WorkerMessage::InvalidateKeys(
+            modified_keys,
+            Some(client_id),
+        ))
+        .unwrap();
+    }
+}
+
+/// Devuelve el error con el que se rechaza un comando de escritura si el servidor es una réplica de solo lectura
+/// (ver `command_server::is_replica_read_only`).
+///
+/// `publish` y `spublish` se admiten, ya que no modifican la base de datos.
+fn get_read_only_error(
+    request: &[RespType],
+    tx: &Sender<WorkerMessage>,
+    config: &Arc<RwLock<Config>>,
+) -> Option<String> {
+    if request
+        .first()
+        .is_some_and(|cmd| cmd.is_word("publish") || cmd.is_word("spublish"))
+    {
+        return None;
+    }
+    if command_server::is_replica_read_only(config) && command_server::is_replica(tx) {
+        return Some(String::from(
+            "READONLY You can't write against a read only replica.",
+        ));
+    }
+    None
+}
+
+/// Devuelve el error con el que se rechaza un comando si el servidor está en modo cluster y no atiende sus claves.
+///
+/// * Si las claves pertenecen a slots distintos, el comando se rechaza con `CROSSSLOT`.
+/// * Si el slot lo atiende otro nodo, se redirige al cliente con `MOVED <slot> <ip>:<puerto>`.
+/// * Si el slot se está migrando a otro nodo, se atiende si sus claves existen localmente; si no existe ninguna se
+///   redirige con `ASK <slot> <ip>:<puerto>`, y si solo existen algunas se rechaza con `TRYAGAIN`.
+/// * Si el slot se está importando desde otro nodo, solo se atiende si el cliente envió `asking` antes del comando;
+///   si no, se redirige con `MOVED` al nodo que todavía lo atiende.
+/// * Si el cluster no atiende comandos, se rechaza con `CLUSTERDOWN`.
+///
+/// Los comandos sin claves siempre se atienden.
+fn get_cluster_error(
+    request: &[RespType],
+    tx: &Sender<WorkerMessage>,
+    database: &Arc<RwLock<Database>>,
+    config: &Arc<RwLock<Config>>,
+    session: &Session,
+) -> Option<String> {
+    if !command_cluster::is_cluster_enabled(config) {
+        return None;
+    }
+    let keys = get_command_keys(request);
+    let mut slots = keys
+        .iter()
+        .map(|key| key_hash_slot(key.as_bytes().unwrap_or_default()));
+    let slot = slots.next()?;
+    if slots.any(|other| other != slot) {
+        return Some(String::from(
+            "CROSSSLOT Keys in request don't hash to the same slot",
+        ));
+    }
+    match command_cluster::get_slot_route(slot, tx)? {
+        SlotRoute::Local => None,
+        SlotRoute::Moved(address) => Some(format!("MOVED {} {}", slot, address)),
+        SlotRoute::Importing(_) if session.is_asking() => None,
+        SlotRoute::Importing(address) => Some(format!("MOVED {} {}", slot, address)),
+        SlotRoute::Migrating(address) => {
+            let db = database.read().ok()?;
+            let existing = keys
+                .iter()
+                .filter_map(|key| key.as_bytes())
+                .filter(|key| matches!(db.key_exists_expired(key.to_vec()), (true, false)))
+                .count();
+            if existing == keys.len() {
+                None
+            } else if existing == 0 {
+                Some(format!("ASK {} {}", slot, address))
+            } else {
+                Some(String::from(
+                    "TRYAGAIN Multiple keys request during rehashing of slot",
+                ))
+            }
+        }
+        SlotRoute::Unassigned => Some(String::from("CLUSTERDOWN Hash slot not served")),
+        SlotRoute::Down => Some(String::from("CLUSTERDOWN The cluster is down")),
+    }
+}
+
+/// Envía a la entidad `Server` las claves que lee el comando, para que las recuerde si el cliente activó `client tracking`.
+///
+/// Se envían antes de ejecutar el comando, para que una modificación posterior de otro cliente siempre las invalide.
+fn track_read_keys(request: &[RespType], client_id: usize, tx: &Sender<WorkerMessage>) {
+    if is_write_command(request) {
+        return;
+    }
+    let keys: Vec<Vec<u8>> = get_command_keys(request)
+        .into_iter()
+        .filter_map(RespType::as_bytes)
+        .map(<[u8]>::to_vec)
+        .collect();
+    if !keys.is_empty() {
+        tx.send(WorkerMessage::TrackKeys(client_id, keys)).unwrap();
+    }
+}
+
+/// Espera a que termine la suspensión de comandos iniciada con `client pause`, si el comando está alcanzado por ella.
+///
+/// Los comandos `client` nunca se suspenden, para que siempre sea posible ejecutar `client unpause`.
+fn wait_if_paused(request: &[RespType], tx: &Sender<WorkerMessage>) {
+    let command = match request.first().and_then(RespType::as_str) {
+        Some(command) => command.to_lowercase(),
+        None => return,
+    };
+    if command == "client" {
+        return;
+    }
+    let is_write = is_write_command(request);
+    loop {
+        let (pause_tx, pause_rx) = mpsc::channel();
+        tx.send(WorkerMessage::CheckPause(is_write, pause_tx))
+            .expect("Could not send CheckPause message");
+        match pause_rx.recv() {
+            Ok(Some(remaining)) => thread::sleep(remaining.min(PAUSE_CHECK_INTERVAL)),
+            _ => return,
+        }
+    }
+}
+
+/// Responde al cliente con un error de protocolo.
+fn write_protocol_error(output: &OutputQueue, error: ParseError) -> Result<(), Box<dyn Error>> {
+    output.push(parse_response(RespType::RError(format!(
+        "ERR Protocol error: {}",
+        error
+    ))))?;
+    Ok(())
+}
+
 /// Envia un mensaje al Logger.
 ///
 /// El sender envia el mensaje al servidor para que lo escriba en el archivo de logs.
@@ -219,3 +1332,21 @@ fn verbose(msg: String, tx: &Sender<WorkerMessage>) {
     tx.send(WorkerMessage::Verb(msg))
         .expect("Could not send verbose");
 }
+
+#[test]
+fn test_001_parse_bind_addresses_with_wildcards() {
+    let addresses = parse_bind_addresses("* -::*").unwrap();
+    assert_eq!(
+        addresses,
+        vec![
+            (IpAddr::V4(Ipv4Addr::UNSPECIFIED), false),
+            (IpAddr::V6(Ipv6Addr::UNSPECIFIED), true),
+        ]
+    );
+}
+
+#[test]
+fn test_002_parse_bind_addresses_rejects_invalid_address() {
+    assert!(parse_bind_addresses("127.0.0.1 localhost").is_err());
+    assert!(parse_bind_addresses("").is_err());
+}
@@ -0,0 +1,78 @@
+//! Servicio para configurar las conexiones TLS del servidor a partir de certificados locales.
+
+use crate::domain::entities::config::Config;
+use rustls::crypto::{ring, CryptoProvider};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use std::sync::Arc;
+
+/// Crea la configuración TLS del servidor a partir de los atributos de la configuración.
+///
+/// * `tls-cert-file` y `tls-key-file`: certificado (en formato PEM, puede incluir la cadena completa) y clave privada del servidor. Son obligatorios.
+/// * `tls-ca-cert-file`: certificados de las autoridades con las que se verifican los certificados de los clientes.
+/// * `tls-auth-clients`: `yes` exige que los clientes presenten un certificado válido, `optional` lo verifica
+///   únicamente si el cliente lo presenta y `no` no lo solicita. Si no se especifica, se exige cuando hay
+///   un `tls-ca-cert-file` configurado.
+///
+/// Devuelve error si alguno de los archivos no puede leerse o no es válido.
+pub fn create_tls_config(config: &Config) -> Result<Arc<ServerConfig>, String> {
+    let cert_file = config
+        .get_attribute(String::from("tls-cert-file"))
+        .map_err(|_| String::from("tls-cert-file is required to enable TLS"))?;
+    let key_file = config
+        .get_attribute(String::from("tls-key-file"))
+        .map_err(|_| String::from("tls-key-file is required to enable TLS"))?;
+    let ca_file = config.get_attribute(String::from("tls-ca-cert-file")).ok();
+    let auth_clients = config
+        .get_attribute(String::from("tls-auth-clients"))
+        .unwrap_or_else(|_| String::from(if ca_file.is_some() { "yes" } else { "no" }))
+        .to_lowercase();
+
+    let certs = load_certificates(&cert_file)?;
+    let key =
+        PrivateKeyDer::from_pem_file(&key_file).map_err(|e| format!("{}: {}", key_file, e))?;
+
+    let provider: Arc<CryptoProvider> = Arc::new(ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?;
+    let builder = match (auth_clients.as_str(), ca_file) {
+        ("no", _) => builder.with_no_client_auth(),
+        ("yes", Some(ca_file)) | ("optional", Some(ca_file)) => {
+            let mut roots = RootCertStore::empty();
+            for cert in load_certificates(&ca_file)? {
+                roots.add(cert).map_err(|e| format!("{}: {}", ca_file, e))?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+            let verifier = if auth_clients == "optional" {
+                verifier.allow_unauthenticated()
+            } else {
+                verifier
+            };
+            builder.with_client_cert_verifier(verifier.build().map_err(|e| e.to_string())?)
+        }
+        ("yes", None) | ("optional", None) => {
+            return Err(String::from(
+                "tls-ca-cert-file is required to authenticate clients",
+            ))
+        }
+        (other, _) => return Err(format!("Invalid tls-auth-clients value: {}", other)),
+    };
+    let server_config = builder
+        .with_single_cert(certs, key)
+        .map_err(|e| e.to_string())?;
+    Ok(Arc::new(server_config))
+}
+
+/// Lee todos los certificados de un archivo PEM.
+fn load_certificates(path: &str) -> Result<Vec<CertificateDer<'static>>, String> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| format!("{}: {}", path, e))?;
+    if certs.is_empty() {
+        return Err(format!("{}: no certificates found", path));
+    }
+    Ok(certs)
+}
@@ -0,0 +1,29 @@
+//! Servicio auxiliar que calcula el CRC16 con el que Redis Cluster asigna las claves a los hash slots.
+
+/// Polinomio del CRC16 CCITT (variante XMODEM), el mismo que utiliza Redis.
+const POLYNOMIAL: u16 = 0x1021;
+
+/// Calcula el CRC16 CCITT (variante XMODEM) de los bytes recibidos.
+///
+/// # Ejemplo
+///
+/// ```
+/// use proyecto_taller_1::services::utils::crc16::crc16;
+///
+/// assert_eq!(crc16(b"123456789"), 0x31c3);
+/// assert_eq!(crc16(b""), 0);
+/// ```
+pub fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in bytes {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ POLYNOMIAL
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
@@ -120,48 +120,45 @@ pub fn g_match(pattern: &[u8], string: &[u8]) -> bool {
 
 #[test]
 fn test_01_wildcard_is_match() {
-    assert_eq!(g_match(b"*.md", b"banana.md"), true);
+    assert!(g_match(b"*.md", b"banana.md"));
 }
 
 #[test]
 fn test_02_wildcard_is_not_match() {
-    assert_eq!(g_match(b"*.md", b"banana.ad"), false);
+    assert!(!g_match(b"*.md", b"banana.ad"));
 }
 
 #[test]
 fn test_03_pattern_in_brackets_is_match() {
-    assert_eq!(g_match(b"[cbr]at", b"cat"), true);
+    assert!(g_match(b"[cbr]at", b"cat"));
 }
 
 #[test]
 fn test_04_question_mark_is_match() {
-    assert_eq!(g_match(b"?at.md", b"cat.md"), true);
+    assert!(g_match(b"?at.md", b"cat.md"));
 }
 
 #[test]
 fn test_05_backslash_is_match() {
-    assert_eq!(g_match(b"set\\*.md", b"set*.md"), true);
+    assert!(g_match(b"set\\*.md", b"set*.md"));
 }
 
 #[test]
 fn test_06_multiple_wildcards_is_match() {
-    assert_eq!(
-        g_match(b"*max-*-entries*", b"hash-max-zipmap-entries"),
-        true
-    );
+    assert!(g_match(b"*max-*-entries*", b"hash-max-zipmap-entries"));
 }
 
 #[test]
 fn test_07_pattern_in_brackets_is_not_match() {
-    assert_eq!(g_match(b"[br]", b"cat"), false);
+    assert!(!g_match(b"[br]", b"cat"));
 }
 
 #[test]
 fn test_08_pattern_in_range_is_match() {
-    assert_eq!(g_match(b"[a-e]at", b"cat"), true);
+    assert!(g_match(b"[a-e]at", b"cat"));
 }
 
 #[test]
 fn test_09_pattern_in_range_is_not_match() {
-    assert_eq!(g_match(b"[n-o]", b"cat"), false);
+    assert!(!g_match(b"[n-o]", b"cat"));
 }
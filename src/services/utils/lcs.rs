@@ -0,0 +1,81 @@
+//! Servicio auxiliar para calcular la subsecuencia común más larga (LCS) entre dos cadenas.
+
+/// Rango de posiciones que coincide en ambas cadenas.
+///
+/// `a` y `b` son los índices (inclusivos) de inicio y fin del rango en cada cadena.
+#[derive(Debug, PartialEq)]
+pub struct LcsMatch {
+    pub a: (usize, usize),
+    pub b: (usize, usize),
+    pub len: usize,
+}
+
+/// Calcula la subsecuencia común más larga entre `a` y `b`.
+///
+/// Devuelve la subsecuencia y los rangos contiguos que la componen, ordenados desde el
+/// final de las cadenas hacia el principio (el mismo orden que utiliza Redis).
+///
+/// # Ejemplos
+///
+/// ```
+/// use proyecto_taller_1::services::utils::lcs::{longest_common_subsequence, LcsMatch};
+///
+/// let (lcs, matches) = longest_common_subsequence(b"ohmytext", b"mynewtext");
+/// assert_eq!(lcs, b"mytext".to_vec());
+/// assert_eq!(matches[0], LcsMatch { a: (4, 7), b: (5, 8), len: 4 });
+/// assert_eq!(matches[1], LcsMatch { a: (2, 3), b: (0, 1), len: 2 });
+/// ```
+pub fn longest_common_subsequence(a: &[u8], b: &[u8]) -> (Vec<u8>, Vec<LcsMatch>) {
+    // dp[i][j] es el largo de la LCS entre a[..i] y b[..j]
+    let mut dp = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            dp[i][j] = if a[i - 1] == b[j - 1] {
+                dp[i - 1][j - 1] + 1
+            } else {
+                dp[i - 1][j].max(dp[i][j - 1])
+            };
+        }
+    }
+
+    let mut lcs = Vec::with_capacity(dp[a.len()][b.len()]);
+    let mut matches = Vec::new();
+    let mut current: Option<LcsMatch> = None;
+    let (mut i, mut j) = (a.len(), b.len());
+    // recorro la tabla desde el final, agrupando las coincidencias contiguas en rangos
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] {
+            lcs.push(a[i - 1]);
+            match current.as_mut() {
+                Some(range) => {
+                    range.a.0 = i - 1;
+                    range.b.0 = j - 1;
+                    range.len += 1;
+                }
+                None => {
+                    current = Some(LcsMatch {
+                        a: (i - 1, i - 1),
+                        b: (j - 1, j - 1),
+                        len: 1,
+                    })
+                }
+            }
+            i -= 1;
+            j -= 1;
+        } else {
+            if let Some(range) = current.take() {
+                matches.push(range);
+            }
+            if dp[i - 1][j] > dp[i][j - 1] {
+                i -= 1;
+            } else {
+                j -= 1;
+            }
+        }
+    }
+    if let Some(range) = current {
+        matches.push(range);
+    }
+    lcs.reverse();
+    (lcs, matches)
+}
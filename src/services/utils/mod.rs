@@ -1,3 +1,7 @@
 //! Servicios de apoyo.
+pub mod crc16;
 pub mod glob_pattern;
+pub mod lcs;
+pub mod random;
 pub mod resp_type;
+pub mod sha256;
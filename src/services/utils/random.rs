@@ -0,0 +1,88 @@
+//! Servicio auxiliar para generar números pseudoaleatorios.
+//!
+//! Se utiliza `RandomState` de la biblioteca estándar como fuente de entropía, ya que cada
+//! instancia se inicializa con claves distintas.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::SystemTime;
+
+/// Devuelve un índice aleatorio en el rango [0, `bound`).
+///
+/// Si `bound` es 0, devuelve 0.
+///
+/// # Ejemplo
+///
+/// ```
+/// use proyecto_taller_1::services::utils::random::random_index;
+///
+/// let index = random_index(10);
+/// assert!(index < 10);
+/// ```
+pub fn random_index(bound: usize) -> usize {
+    if bound == 0 {
+        return 0;
+    }
+    let mut hasher = RandomState::new().build_hasher();
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    hasher.write_u64(now);
+    (hasher.finish() % bound as u64) as usize
+}
+
+/// Devuelve `count` elementos distintos elegidos al azar de `elements`.
+///
+/// Si `count` es mayor a la cantidad de elementos, los devuelve todos en orden aleatorio.
+///
+/// # Ejemplo
+///
+/// ```
+/// use proyecto_taller_1::services::utils::random::sample;
+///
+/// let elements = vec![1, 2, 3, 4, 5];
+/// let chosen = sample(elements, 3);
+/// assert_eq!(chosen.len(), 3);
+/// ```
+pub fn sample<T>(mut elements: Vec<T>, count: usize) -> Vec<T> {
+    let count = count.min(elements.len());
+    for i in 0..count {
+        let j = i + random_index(elements.len() - i);
+        elements.swap(i, j);
+    }
+    elements.truncate(count);
+    elements
+}
+
+/// Devuelve hasta `count` elementos distintos elegidos al azar de `elements`.
+///
+/// A diferencia de `sample`, recorre el iterador una única vez sin materializarlo, por lo que
+/// sólo se reserva memoria para los `count` elementos elegidos.
+///
+/// # Ejemplo
+///
+/// ```
+/// use proyecto_taller_1::services::utils::random::sample_iter;
+///
+/// let chosen = sample_iter(1..=100, 3);
+/// assert_eq!(chosen.len(), 3);
+/// assert!(chosen.iter().all(|n| (1..=100).contains(n)));
+/// ```
+pub fn sample_iter<T, I: IntoIterator<Item = T>>(elements: I, count: usize) -> Vec<T> {
+    let mut chosen = Vec::new();
+    if count == 0 {
+        return chosen;
+    }
+    for (seen, element) in elements.into_iter().enumerate() {
+        if seen < count {
+            chosen.push(element);
+        } else {
+            let j = random_index(seen + 1);
+            if j < count {
+                chosen[j] = element;
+            }
+        }
+    }
+    chosen
+}
@@ -11,18 +11,48 @@ use std::fmt::{Display, Error, Formatter};
 /// * Integer
 ///
 /// Además, admite valores nulos como una variación de bulk strings y arrays (Null bulk string y null array, respectivamente).
+///
+/// Los bulk strings son binary safe: su contenido es un vector de bytes arbitrario, que no necesariamente es UTF-8.
+///
+/// Los clientes que negocian RESP3 mediante el comando `HELLO` admiten además los siguientes tipos:
+/// * Map: pares clave-valor
+/// * Set: elementos sin orden
+/// * Double: números de punto flotante
+/// * Boolean
+/// * Big number: enteros de precisión arbitraria
+/// * Verbatim string: texto acompañado de su formato (por ejemplo `txt`)
+/// * Null: único valor nulo, reemplaza a null bulk string y null array
+/// * Attribute: información auxiliar que precede a una respuesta
+/// * Push: mensajes que el servidor envía sin que el cliente los pida, como los de Pub/Sub
+///
+/// Para los clientes RESP2 estos tipos se convierten a su equivalente mediante `into_protocol`.
 #[derive(Debug, PartialEq, Clone)]
 pub enum RespType {
     RSimpleString(String),
     RError(String),
     RInteger(usize),
     RSignedNumber(isize),
-    RBulkString(String),
+    RBulkString(Vec<u8>),
     RArray(Vec<RespType>),
     RNullBulkString(),
     RNullArray(),
+    RMap(Vec<(RespType, RespType)>),
+    RSet(Vec<RespType>),
+    RDouble(f64),
+    RBoolean(bool),
+    RBigNumber(String),
+    RVerbatimString(String, String),
+    RNull(),
+    RAttribute(Vec<(RespType, RespType)>, Box<RespType>),
+    RPush(Vec<RespType>),
 }
 
+/// Versión del protocolo RESP que utilizan los clientes por defecto.
+pub const RESP2: usize = 2;
+
+/// Versión del protocolo RESP que se negocia con el comando `HELLO 3`.
+pub const RESP3: usize = 3;
+
 /// Implementa el Trait `Display`.
 ///
 /// En el caso de `RArray`, muestra los elementos del vector separados por un espacio en blanco.
@@ -34,12 +64,12 @@ impl Display for RespType {
             RespType::RError(str) => write!(f, "{}", str),
             RespType::RInteger(int) => write!(f, "{}", int),
             RespType::RSignedNumber(int) => write!(f, "{}", int),
-            RespType::RBulkString(str) => write!(f, "{}", str),
+            RespType::RBulkString(bytes) => write!(f, "{}", String::from_utf8_lossy(bytes)),
             RespType::RArray(array) => {
                 let mut concatenated_elements = String::new();
                 for e in array {
-                    if let RespType::RBulkString(str) = e {
-                        concatenated_elements.push_str(str);
+                    if let RespType::RBulkString(bytes) = e {
+                        concatenated_elements.push_str(&String::from_utf8_lossy(bytes));
                         concatenated_elements.push(' ');
                     }
                 }
@@ -47,6 +77,171 @@ impl Display for RespType {
             }
             RespType::RNullBulkString() => write!(f, "(nil)"),
             RespType::RNullArray() => write!(f, "(nil)"),
+            RespType::RMap(pairs) => {
+                let mut concatenated_elements = String::new();
+                for (key, value) in pairs {
+                    concatenated_elements.push_str(&format!("{} {} ", key, value));
+                }
+                write!(f, "{}", concatenated_elements)
+            }
+            RespType::RSet(elements) | RespType::RPush(elements) => {
+                write!(f, "{}", RespType::RArray(elements.to_vec()))
+            }
+            RespType::RDouble(double) => write!(f, "{}", format_double(*double)),
+            RespType::RBoolean(boolean) => write!(f, "{}", boolean),
+            RespType::RBigNumber(number) => write!(f, "{}", number),
+            RespType::RVerbatimString(_, text) => write!(f, "{}", text),
+            RespType::RNull() => write!(f, "(nil)"),
+            RespType::RAttribute(_, value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl RespType {
+    /// Devuelve el contenido de un bulk string como texto.
+    ///
+    /// Pensado para los comandos que trabajan con claves, opciones o números. Devuelve `None` si no es un
+    /// bulk string o si su contenido no es UTF-8 válido.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::services::utils::resp_type::RespType;
+    ///
+    /// assert_eq!(RespType::RBulkString(b"clave".to_vec()).as_str(), Some("clave"));
+    /// assert_eq!(RespType::RBulkString(vec![0xff, 0xfe]).as_str(), None);
+    /// assert_eq!(RespType::RInteger(1).as_str(), None);
+    /// ```
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            RespType::RBulkString(bytes) => std::str::from_utf8(bytes).ok(),
+            _ => None,
+        }
+    }
+
+    /// Devuelve el contenido de un bulk string como bytes, sin ninguna transformación.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::services::utils::resp_type::RespType;
+    ///
+    /// assert_eq!(RespType::RBulkString(vec![0xff, b'\r', b'\n']).as_bytes(), Some(&[0xff, b'\r', b'\n'][..]));
+    /// assert_eq!(RespType::RNullBulkString().as_bytes(), None);
+    /// ```
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            RespType::RBulkString(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Indica si el contenido de un bulk string coincide con `word`, sin distinguir mayúsculas de minúsculas.
+    ///
+    /// Se utiliza para reconocer nombres de comandos y opciones.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::services::utils::resp_type::RespType;
+    ///
+    /// assert!(RespType::RBulkString(b"KeepTTL".to_vec()).is_word("keepttl"));
+    /// assert!(!RespType::RBulkString(b"keep".to_vec()).is_word("keepttl"));
+    /// ```
+    pub fn is_word(&self, word: &str) -> bool {
+        match self {
+            RespType::RBulkString(bytes) => bytes.eq_ignore_ascii_case(word.as_bytes()),
+            _ => false,
+        }
+    }
+
+    /// Adapta la respuesta a la versión del protocolo que negoció el cliente.
+    ///
+    /// Para RESP2 los tipos de RESP3 se reemplazan por su equivalente: los maps se aplanan en un array
+    /// de claves y valores, los sets y mensajes push pasan a ser arrays, los booleanos se representan
+    /// como 1 o 0, los doubles, big numbers y verbatim strings como bulk strings, y se descartan los atributos.
+    /// Para RESP3 los valores nulos se representan con el tipo Null.
+    ///
+    /// # Ejemplo
+    /// ```
+    /// # use proyecto_taller_1::services::utils::resp_type::{RespType, RESP2, RESP3};
+    ///
+    /// let map = RespType::RMap(vec![(RespType::RBulkString("proto".into()), RespType::RInteger(2))]);
+    /// assert_eq!(
+    ///     map.into_protocol(RESP2),
+    ///     RespType::RArray(vec![RespType::RBulkString("proto".into()), RespType::RInteger(2)])
+    /// );
+    ///
+    /// let array = RespType::RArray(vec![RespType::RNullBulkString()]);
+    /// assert_eq!(array.into_protocol(RESP3), RespType::RArray(vec![RespType::RNull()]));
+    /// ```
+    pub fn into_protocol(self, protocol: usize) -> RespType {
+        if protocol >= RESP3 {
+            self.into_resp3()
+        } else {
+            self.into_resp2()
+        }
+    }
+
+    fn into_resp2(self) -> RespType {
+        match self {
+            RespType::RArray(elements) | RespType::RSet(elements) | RespType::RPush(elements) => {
+                RespType::RArray(elements.into_iter().map(RespType::into_resp2).collect())
+            }
+            RespType::RMap(pairs) => RespType::RArray(
+                pairs
+                    .into_iter()
+                    .flat_map(|(key, value)| vec![key.into_resp2(), value.into_resp2()])
+                    .collect(),
+            ),
+            RespType::RDouble(double) => RespType::RBulkString(format_double(double).into()),
+            RespType::RBoolean(boolean) => RespType::RInteger(boolean as usize),
+            RespType::RBigNumber(number) => RespType::RBulkString(number.into()),
+            RespType::RVerbatimString(_, text) => RespType::RBulkString(text.into()),
+            RespType::RNull() => RespType::RNullBulkString(),
+            RespType::RAttribute(_, value) => value.into_resp2(),
+            other => other,
+        }
+    }
+
+    fn into_resp3(self) -> RespType {
+        match self {
+            RespType::RNullBulkString() | RespType::RNullArray() => RespType::RNull(),
+            RespType::RArray(elements) => {
+                RespType::RArray(elements.into_iter().map(RespType::into_resp3).collect())
+            }
+            RespType::RSet(elements) => {
+                RespType::RSet(elements.into_iter().map(RespType::into_resp3).collect())
+            }
+            RespType::RPush(elements) => {
+                RespType::RPush(elements.into_iter().map(RespType::into_resp3).collect())
+            }
+            RespType::RMap(pairs) => RespType::RMap(
+                pairs
+                    .into_iter()
+                    .map(|(key, value)| (key.into_resp3(), value.into_resp3()))
+                    .collect(),
+            ),
+            RespType::RAttribute(attributes, value) => {
+                RespType::RAttribute(attributes, Box::new(value.into_resp3()))
+            }
+            other => other,
         }
     }
 }
+
+/// Representa un double con el formato de RESP3, que utiliza `inf`, `-inf` y `nan` para los valores especiales.
+///
+/// # Ejemplo
+/// ```
+/// # use proyecto_taller_1::services::utils::resp_type::format_double;
+///
+/// assert_eq!(format_double(1.5), "1.5");
+/// assert_eq!(format_double(f64::NEG_INFINITY), "-inf");
+/// ```
+pub fn format_double(double: f64) -> String {
+    if double.is_nan() {
+        String::from("nan")
+    } else if double.is_infinite() {
+        String::from(if double > 0.0 { "inf" } else { "-inf" })
+    } else {
+        double.to_string()
+    }
+}
@@ -38,7 +38,7 @@ pub fn get_body_as_resp(string: String) -> RespType {
             .split(' ')
             .collect::<Vec<&str>>()
             .iter()
-            .map(|e| RespType::RBulkString(e.to_string()))
+            .map(|e| RespType::RBulkString(e.to_string().into()))
             .collect(),
     )
 }
@@ -50,3 +50,37 @@ impl ThreadPool {
         }
     }
 }
+
+/// Al destruir el threadpool se le indica a cada worker que deje de atender clientes
+/// y se espera a que terminen las tareas que están ejecutando.
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        for _ in &self.workers {
+            if self.sender.send(Message::Terminate).is_err() {
+                println!("Oops! Failed sending message");
+            }
+        }
+        for worker in &mut self.workers {
+            worker.shutdown();
+        }
+    }
+}
+
+#[test]
+fn test_01_drop_waits_for_pending_jobs() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    let finished = Arc::new(AtomicUsize::new(0));
+    let pool = ThreadPool::new(2);
+    for _ in 0..4 {
+        let finished = finished.clone();
+        pool.spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            finished.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+    drop(pool);
+    assert_eq!(finished.load(Ordering::SeqCst), 4);
+}
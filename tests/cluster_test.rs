@@ -0,0 +1,258 @@
+extern crate redis;
+
+use redis::{Connection, RedisResult, Value};
+use std::{
+    error::Error,
+    net::TcpStream,
+    panic,
+    process::{Child, Command, Stdio},
+    thread::sleep,
+    time::Duration,
+};
+
+const NODE_PORTS: [&str; 3] = ["8098", "8099", "8100"];
+
+type TestResult = Result<(), Box<dyn Error>>;
+
+/// Inicia un cluster de tres nodos y verifica la asignación de slots, las redirecciones y la detección de fallas.
+#[test]
+fn test_cluster_redirects_commands_to_the_node_that_serves_the_slot() {
+    let mut processes = Vec::new();
+    let mut files = Vec::new();
+    for port in NODE_PORTS {
+        let config = format!("./src/dummy_cluster_{}.conf", port);
+        let nodes = format!("./src/dummy_cluster_nodes_{}.conf", port);
+        let db = format!("./src/dummy_cluster_{}.rdb", port);
+        let log = format!("./src/dummy_cluster_{}.log", port);
+        let _ = std::fs::remove_file(&db);
+        let _ = std::fs::remove_file(&nodes);
+        std::fs::write(
+            &config,
+            format!(
+                "port {}\ndbfilename {}\nlogfile {}\nverbose 0\ncluster-enabled yes\ncluster-config-file {}\ncluster-node-timeout 1000\n",
+                port, db, log, nodes
+            ),
+        )
+        .unwrap();
+        std::fs::File::create(&log).unwrap();
+        processes.push(
+            Command::new(env!("CARGO_BIN_EXE_proyecto_taller_1"))
+                .arg(&config)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .unwrap(),
+        );
+        files.extend([config, nodes, db, log]);
+    }
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| check_cluster(&mut processes[1])));
+    for process in processes.iter_mut() {
+        let _ = process.kill();
+        let _ = process.wait();
+    }
+    for file in files {
+        let _ = std::fs::remove_file(file);
+    }
+    result.unwrap().unwrap();
+}
+
+fn connect(port: &str) -> Result<Connection, Box<dyn Error>> {
+    for _ in 0..50 {
+        if TcpStream::connect(format!("127.0.0.1:{}", port)).is_ok() {
+            break;
+        }
+        sleep(Duration::from_millis(100));
+    }
+    Ok(redis::Client::open(format!("redis://127.0.0.1:{}/", port))?.get_connection()?)
+}
+
+/// Devuelve el código y el detalle del error con el que respondió el servidor.
+fn error<T>(result: RedisResult<T>) -> String {
+    match result {
+        Ok(_) => String::from("no error"),
+        Err(e) => format!(
+            "{} {}",
+            e.code().unwrap_or_default(),
+            e.detail().unwrap_or_default()
+        ),
+    }
+}
+
+/// Reintenta la consulta hasta que cumpla la condición, durante 10 segundos como máximo.
+fn wait_until<F: FnMut() -> bool>(mut condition: F) -> bool {
+    for _ in 0..100 {
+        if condition() {
+            return true;
+        }
+        sleep(Duration::from_millis(100));
+    }
+    false
+}
+
+fn cluster_info(node: &mut Connection) -> String {
+    redis::cmd("CLUSTER")
+        .arg("INFO")
+        .query::<String>(node)
+        .unwrap_or_default()
+}
+
+fn check_cluster(second_node: &mut Child) -> TestResult {
+    let mut nodes = [
+        connect(NODE_PORTS[0])?,
+        connect(NODE_PORTS[1])?,
+        connect(NODE_PORTS[2])?,
+    ];
+    let ranges = [(0, 5460), (5461, 10922), (10923, 16383)];
+    for (node, (start, end)) in nodes.iter_mut().zip(ranges) {
+        let reply: String = redis::cmd("CLUSTER")
+            .arg("ADDSLOTSRANGE")
+            .arg(start)
+            .arg(end)
+            .query(node)?;
+        assert_eq!(reply, "OK");
+    }
+    for port in &NODE_PORTS[1..] {
+        let reply: String = redis::cmd("CLUSTER")
+            .arg("MEET")
+            .arg("127.0.0.1")
+            .arg(*port)
+            .query(&mut nodes[0])?;
+        assert_eq!(reply, "OK");
+    }
+    for node in nodes.iter_mut() {
+        assert!(wait_until(|| {
+            let info = cluster_info(node);
+            info.contains("cluster_state:ok") && info.contains("cluster_known_nodes:3")
+        }));
+    }
+    let ids: Vec<String> = nodes
+        .iter_mut()
+        .map(|node| redis::cmd("CLUSTER").arg("MYID").query(node))
+        .collect::<RedisResult<Vec<String>>>()?;
+
+    let info: String = redis::cmd("INFO").arg("cluster").query(&mut nodes[0])?;
+    assert!(info.contains("cluster_enabled:1"));
+    let slot: usize = redis::cmd("CLUSTER")
+        .arg("KEYSLOT")
+        .arg("foo")
+        .query(&mut nodes[0])?;
+    assert_eq!(slot, 12182);
+    let slots: Vec<Value> = redis::cmd("CLUSTER").arg("SLOTS").query(&mut nodes[1])?;
+    assert_eq!(slots.len(), 3);
+    let shards: Vec<Value> = redis::cmd("CLUSTER").arg("SHARDS").query(&mut nodes[1])?;
+    assert_eq!(shards.len(), 3);
+    let description: String = redis::cmd("CLUSTER").arg("NODES").query(&mut nodes[1])?;
+    assert_eq!(description.lines().count(), 3);
+    assert!(description.contains(&format!("{} 127.0.0.1:8100@18100 master - ", ids[2])));
+
+    // cada clave se atiende en el nodo de su slot
+    let set = redis::cmd("SET")
+        .arg("foo")
+        .arg("bar")
+        .query::<String>(&mut nodes[0]);
+    assert_eq!(error(set), "MOVED 12182 127.0.0.1:8100");
+    let set: String = redis::cmd("SET")
+        .arg("foo")
+        .arg("bar")
+        .query(&mut nodes[2])?;
+    assert_eq!(set, "Ok");
+    let cross = redis::cmd("MSET")
+        .arg("foo")
+        .arg("1")
+        .arg("bar")
+        .arg("2")
+        .query::<String>(&mut nodes[2]);
+    assert!(error(cross).starts_with("CROSSSLOT"));
+    let mset: String = redis::cmd("MSET")
+        .arg("{foo}.a")
+        .arg("1")
+        .arg("{foo}.b")
+        .arg("2")
+        .query(&mut nodes[2])?;
+    assert_eq!(mset, "Ok");
+    let count: usize = redis::cmd("CLUSTER")
+        .arg("COUNTKEYSINSLOT")
+        .arg(12182)
+        .query(&mut nodes[2])?;
+    assert_eq!(count, 3);
+    let keys: Vec<String> = redis::cmd("CLUSTER")
+        .arg("GETKEYSINSLOT")
+        .arg(12182)
+        .arg(2)
+        .query(&mut nodes[2])?;
+    assert_eq!(keys.len(), 2);
+    assert!(keys
+        .iter()
+        .all(|key| ["foo", "{foo}.a", "{foo}.b"].contains(&key.as_str())));
+
+    // mientras se mueve el slot, las claves que ya no están en el origen se piden al destino con ASK
+    let reply: String = redis::cmd("CLUSTER")
+        .arg("SETSLOT")
+        .arg(12182)
+        .arg("IMPORTING")
+        .arg(&ids[2])
+        .query(&mut nodes[0])?;
+    assert_eq!(reply, "OK");
+    let reply: String = redis::cmd("CLUSTER")
+        .arg("SETSLOT")
+        .arg(12182)
+        .arg("MIGRATING")
+        .arg(&ids[0])
+        .query(&mut nodes[2])?;
+    assert_eq!(reply, "OK");
+    let get: String = redis::cmd("GET").arg("foo").query(&mut nodes[2])?;
+    assert_eq!(get, "bar");
+    let ask = redis::cmd("GET")
+        .arg("{foo}.c")
+        .query::<Option<String>>(&mut nodes[2]);
+    assert_eq!(error(ask), "ASK 12182 127.0.0.1:8098");
+    let moved = redis::cmd("GET")
+        .arg("{foo}.c")
+        .query::<Option<String>>(&mut nodes[0]);
+    assert_eq!(error(moved), "MOVED 12182 127.0.0.1:8100");
+    let asking: String = redis::cmd("ASKING").query(&mut nodes[0])?;
+    assert_eq!(asking, "OK");
+    let set: String = redis::cmd("SET")
+        .arg("{foo}.c")
+        .arg("3")
+        .query(&mut nodes[0])?;
+    assert_eq!(set, "Ok");
+    for node in [0, 2] {
+        let reply: String = redis::cmd("CLUSTER")
+            .arg("SETSLOT")
+            .arg(12182)
+            .arg("NODE")
+            .arg(&ids[0])
+            .query(&mut nodes[node])?;
+        assert_eq!(reply, "OK");
+    }
+    let get: String = redis::cmd("GET").arg("{foo}.c").query(&mut nodes[0])?;
+    assert_eq!(get, "3");
+    assert!(wait_until(|| {
+        error(
+            redis::cmd("GET")
+                .arg("foo")
+                .query::<Option<String>>(&mut nodes[1]),
+        ) == "MOVED 12182 127.0.0.1:8098"
+    }));
+
+    // al caer un nodo, los demás dejan de atender comandos
+    second_node.kill()?;
+    second_node.wait()?;
+    assert!(wait_until(
+        || cluster_info(&mut nodes[0]).contains("cluster_state:fail")
+    ));
+    let down = redis::cmd("GET")
+        .arg("{foo}.c")
+        .query::<Option<String>>(&mut nodes[0]);
+    assert_eq!(error(down), "CLUSTERDOWN The cluster is down");
+    assert!(wait_until(|| {
+        redis::cmd("CLUSTER")
+            .arg("NODES")
+            .query::<String>(&mut nodes[2])
+            .unwrap_or_default()
+            .contains(&format!("{} 127.0.0.1:8099@18099 master,fail ", ids[1]))
+    }));
+    Ok(())
+}
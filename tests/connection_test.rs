@@ -8,12 +8,22 @@ use proyecto_taller_1::{
     services::{server_service, worker_service::ThreadPool},
 };
 use redis::RedisError;
+use rustls::crypto::ring;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
 
 use proyecto_taller_1::domain::entities::key_value_item::ValueTimeItemBuilder;
 use std::{
     collections::HashSet,
+    convert::TryFrom,
     error::Error,
     fmt,
+    io::{Read, Write},
+    net::TcpStream,
+    os::unix::{fs::PermissionsExt, net::UnixStream},
+    path::Path,
+    process::{Command, Stdio},
     sync::{mpsc, Arc, Barrier, Mutex},
     thread::{self, sleep},
     time::Duration,
@@ -21,6 +31,27 @@ use std::{
 };
 
 const ADDR: &str = "redis://127.0.0.1:8080/";
+const UNIX_SOCKET: &str = "./src/dummy_redis.sock";
+const TLS_PORT: &str = "8443";
+const TLS_CA_KEY: &str = "./src/dummy_tls_ca.key";
+const TLS_CA_CERT: &str = "./src/dummy_tls_ca.crt";
+const TLS_KEY: &str = "./src/dummy_tls.key";
+const TLS_CSR: &str = "./src/dummy_tls.csr";
+const TLS_CERT: &str = "./src/dummy_tls.crt";
+const TLS_EXT: &str = "./src/dummy_tls.ext";
+const REPLICA_PORT: &str = "8091";
+const REPLICA_CONFIG: &str = "./src/dummy_replica_config.txt";
+const REPLICA_DB: &str = "./src/dummy_replica_database.txt";
+const REPLICA_LOG: &str = "./src/dummy_replica_log.txt";
+const TLS_FILES: [&str; 7] = [
+    TLS_CA_KEY,
+    TLS_CA_CERT,
+    TLS_KEY,
+    TLS_CSR,
+    TLS_CERT,
+    TLS_EXT,
+    "./src/dummy_tls_ca.srl",
+];
 
 type TestResult = Result<(), Box<dyn Error>>;
 type TestFunction = fn() -> TestResult;
@@ -81,445 +112,573 @@ fn test_main() {
         .set_attribute(String::from("timeout"), String::from("300"))
         .unwrap();
 
+    config
+        .set_attribute(String::from("unixsocket"), String::from(UNIX_SOCKET))
+        .unwrap();
+
+    config
+        .set_attribute(String::from("unixsocketperm"), String::from("700"))
+        .unwrap();
+
+    config
+        .set_attribute(
+            String::from("client-output-buffer-limit"),
+            String::from("pubsub 1mb 0 0"),
+        )
+        .unwrap();
+
+    // el listener TLS sólo se habilita si es posible generar los certificados con openssl
+    if generate_tls_certificates() {
+        for (attribute, value) in [
+            ("tls-port", TLS_PORT),
+            ("tls-cert-file", TLS_CERT),
+            ("tls-key-file", TLS_KEY),
+            ("tls-ca-cert-file", TLS_CA_CERT),
+        ] {
+            config
+                .set_attribute(String::from(attribute), String::from(value))
+                .unwrap();
+        }
+    }
+
     let mut database = Database::new(db_file);
 
-    let added_item_1 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("value_key_1")))
-            .with_timeout(1925487534)
-            .build();
+    let added_item_1 = ValueTimeItemBuilder::new(ValueType::StringType("value_key_1".into()))
+        .with_timeout(1925487534)
+        .build();
 
-    database.add(String::from("key_1"), added_item_1);
+    database.add(b"key_1".to_vec(), added_item_1);
 
-    let added_item_2 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("value_key_2")))
-            .with_timeout(1635597186)
-            .build();
+    let added_item_2 = ValueTimeItemBuilder::new(ValueType::StringType("value_key_2".into()))
+        .with_timeout(1935597186)
+        .build();
 
-    database.add(String::from("key_2"), added_item_2);
+    database.add(b"key_2".to_vec(), added_item_2);
 
-    let added_item_3 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("value_key_3")))
-            .with_timeout(1635597186)
-            .build();
+    let added_item_3 = ValueTimeItemBuilder::new(ValueType::StringType("value_key_3".into()))
+        .with_timeout(1935597186)
+        .build();
 
-    database.add(String::from("key_3"), added_item_3);
+    database.add(b"key_3".to_vec(), added_item_3);
 
-    let added_item_4 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("value_key_4")))
-            .with_timeout(1635597186)
-            .build();
+    let added_item_4 = ValueTimeItemBuilder::new(ValueType::StringType("value_key_4".into()))
+        .with_timeout(1935597186)
+        .build();
 
-    database.add(String::from("key_4"), added_item_4);
+    database.add(b"key_4".to_vec(), added_item_4);
 
-    let added_item_5 = ValueTimeItemBuilder::new(ValueType::StringType(String::from("Hello")))
-        .with_timeout(1635597186)
+    let added_item_5 = ValueTimeItemBuilder::new(ValueType::StringType("Hello".into()))
+        .with_timeout(1935597186)
         .build();
-    database.add(String::from("mykey"), added_item_5);
+    database.add(b"mykey".to_vec(), added_item_5);
 
-    let added_item_6 = ValueTimeItemBuilder::new(ValueType::StringType(String::from("10")))
-        .with_timeout(1635597186)
+    let added_item_6 = ValueTimeItemBuilder::new(ValueType::StringType("10".into()))
+        .with_timeout(1935597186)
         .build();
-    database.add(String::from("key_to_decr"), added_item_6);
+    database.add(b"key_to_decr".to_vec(), added_item_6);
 
-    let added_item_7 = ValueTimeItemBuilder::new(ValueType::StringType(String::from("10")))
-        .with_timeout(1635597186)
+    let added_item_7 = ValueTimeItemBuilder::new(ValueType::StringType("10".into()))
+        .with_timeout(1935597186)
         .build();
 
-    database.add(String::from("key_to_incr"), added_item_7);
+    database.add(b"key_to_incr".to_vec(), added_item_7);
 
-    let added_item_8 = ValueTimeItemBuilder::new(ValueType::StringType(String::from("Hello")))
-        .with_timeout(1635597186)
+    let added_item_8 = ValueTimeItemBuilder::new(ValueType::StringType("Hello".into()))
+        .with_timeout(1935597186)
         .build();
 
-    database.add(String::from("key_getdel"), added_item_8);
+    database.add(b"key_getdel".to_vec(), added_item_8);
 
-    let added_item_9 = ValueTimeItemBuilder::new(ValueType::StringType(String::from("OldValue")))
-        .with_timeout(1635597186)
+    let added_item_9 = ValueTimeItemBuilder::new(ValueType::StringType("OldValue".into()))
+        .with_timeout(1935597186)
         .build();
-    database.add(String::from("key_getset"), added_item_9);
-    let added_item_10 =
-        ValueTimeItemBuilder::new(ValueType::StringType("hola".to_string())).build();
-    database.add(String::from("mget_1"), added_item_10);
-    let added_item_11 =
-        ValueTimeItemBuilder::new(ValueType::StringType("chau".to_string())).build();
-    database.add(String::from("mget_2"), added_item_11);
-
-    let added_item_12 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "15".to_string(),
-        "18".to_string(),
-        "12".to_string(),
-        "54".to_string(),
-        "22".to_string(),
-        "45".to_string(),
-    ]))
+    database.add(b"key_getset".to_vec(), added_item_9);
+    let added_item_10 = ValueTimeItemBuilder::new(ValueType::StringType("hola".into())).build();
+    database.add(b"mget_1".to_vec(), added_item_10);
+    let added_item_11 = ValueTimeItemBuilder::new(ValueType::StringType("chau".into())).build();
+    database.add(b"mget_2".to_vec(), added_item_11);
+
+    let added_item_12 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"15".to_vec(),
+            b"18".to_vec(),
+            b"12".to_vec(),
+            b"54".to_vec(),
+            b"22".to_vec(),
+            b"45".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    database.add(String::from("edades_amigos"), added_item_12);
+    database.add(b"edades_amigos".to_vec(), added_item_12);
 
-    let added_item_13 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("10"))).build();
-    database.add(String::from("edad_maria"), added_item_13);
+    let added_item_13 = ValueTimeItemBuilder::new(ValueType::StringType("10".into())).build();
+    database.add(b"edad_maria".to_vec(), added_item_13);
 
-    let added_item_14 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("11"))).build();
-    database.add(String::from("edad_clara"), added_item_14);
+    let added_item_14 = ValueTimeItemBuilder::new(ValueType::StringType("11".into())).build();
+    database.add(b"edad_clara".to_vec(), added_item_14);
 
-    let added_item_15 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("12"))).build();
-    database.add(String::from("edad_josefina"), added_item_15);
+    let added_item_15 = ValueTimeItemBuilder::new(ValueType::StringType("12".into())).build();
+    database.add(b"edad_josefina".to_vec(), added_item_15);
 
-    let added_item_16 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("13"))).build();
+    let added_item_16 = ValueTimeItemBuilder::new(ValueType::StringType("13".into())).build();
 
-    database.add(String::from("edad_luz"), added_item_16);
+    database.add(b"edad_luz".to_vec(), added_item_16);
 
-    let added_item_17 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "clara".to_string(),
-        "maria".to_string(),
-        "luz".to_string(),
-        "josefina".to_string(),
-    ]))
+    let added_item_17 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"clara".to_vec(),
+            b"maria".to_vec(),
+            b"luz".to_vec(),
+            b"josefina".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
 
-    database.add(String::from("grupo_amigas"), added_item_17);
+    database.add(b"grupo_amigas".to_vec(), added_item_17);
 
-    let added_item_18 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("55"))).build();
-    database.add(String::from("edad_mariana"), added_item_18);
+    let added_item_18 = ValueTimeItemBuilder::new(ValueType::StringType("55".into())).build();
+    database.add(b"edad_mariana".to_vec(), added_item_18);
 
-    let added_item_list_19 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "pomelo".to_string(),
-        "sandia".to_string(),
-        "kiwi".to_string(),
-        "mandarina".to_string(),
-    ]))
+    let added_item_list_19 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"pomelo".to_vec(),
+            b"sandia".to_vec(),
+            b"kiwi".to_vec(),
+            b"mandarina".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("frutas"), added_item_list_19);
-
-    let added_item_list_20 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "tamarindo".to_string(),
-        "grosella".to_string(),
-        "pomelo_negro".to_string(),
-        "coco".to_string(),
-    ]))
+    database.add(b"frutas".to_vec(), added_item_list_19);
+
+    let added_item_list_20 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"tamarindo".to_vec(),
+            b"grosella".to_vec(),
+            b"pomelo_negro".to_vec(),
+            b"coco".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("frutas_raras"), added_item_list_20);
-
-    let added_item_list_21 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "jinete_1".to_string(),
-        "jinete_2".to_string(),
-        "jinete_3".to_string(),
-        "jinete_4".to_string(),
-        "jinete_5".to_string(),
-        "jinete_6".to_string(),
-        "jinete_7".to_string(),
-        "jinete_8".to_string(),
-    ]))
+    database.add(b"frutas_raras".to_vec(), added_item_list_20);
+
+    let added_item_list_21 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"jinete_1".to_vec(),
+            b"jinete_2".to_vec(),
+            b"jinete_3".to_vec(),
+            b"jinete_4".to_vec(),
+            b"jinete_5".to_vec(),
+            b"jinete_6".to_vec(),
+            b"jinete_7".to_vec(),
+            b"jinete_8".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("jinetes_de_tucuman"), added_item_list_21);
-
-    let added_item_22 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "argentina".to_string(),
-        "brasil".to_string(),
-        "uruguay".to_string(),
-        "chile".to_string(),
-    ]))
+    database.add(b"jinetes_de_tucuman".to_vec(), added_item_list_21);
+
+    let added_item_22 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"argentina".to_vec(),
+            b"brasil".to_vec(),
+            b"uruguay".to_vec(),
+            b"chile".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("paises"), added_item_22);
-
-    let added_item_23 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "jujuy".to_string(),
-        "mendoza".to_string(),
-        "corrientes".to_string(),
-        "misiones".to_string(),
-    ]))
+    database.add(b"paises".to_vec(), added_item_22);
+
+    let added_item_23 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"jujuy".to_vec(),
+            b"mendoza".to_vec(),
+            b"corrientes".to_vec(),
+            b"misiones".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("provincias"), added_item_23);
-
-    let added_item_24 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "italia".to_string(),
-        "francia".to_string(),
-        "españa".to_string(),
-        "portugal".to_string(),
-    ]))
+    database.add(b"provincias".to_vec(), added_item_23);
+
+    let added_item_24 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"italia".to_vec(),
+            b"francia".to_vec(),
+            "españa".as_bytes().to_vec(),
+            b"portugal".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("paises2"), added_item_24);
-
-    let added_item_25 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "chubut".to_string(),
-        "formosa".to_string(),
-        "chaco".to_string(),
-        "catamarca".to_string(),
-    ]))
+    database.add(b"paises2".to_vec(), added_item_24);
+
+    let added_item_25 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"chubut".to_vec(),
+            b"formosa".to_vec(),
+            b"chaco".to_vec(),
+            b"catamarca".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("provincias2"), added_item_25);
+    database.add(b"provincias2".to_vec(), added_item_25);
 
     let mut set = HashSet::new();
-    set.insert("value_1".to_string());
-    set.insert("value_2".to_string());
+    set.insert(b"value_1".to_vec());
+    set.insert(b"value_2".to_vec());
     let added_item_set_1 = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    database.add(String::from("set_values_1"), added_item_set_1);
+    database.add(b"set_values_1".to_vec(), added_item_set_1);
 
     let mut set = HashSet::new();
-    set.insert("value_1".to_string());
-    set.insert("value_2".to_string());
+    set.insert(b"value_1".to_vec());
+    set.insert(b"value_2".to_vec());
     let added_item_set_2 = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    database.add(String::from("set_values_2"), added_item_set_2);
+    database.add(b"set_values_2".to_vec(), added_item_set_2);
 
     let mut set = HashSet::new();
-    set.insert("value_1".to_string());
-    set.insert("value_2".to_string());
-    set.insert("value_3".to_string());
+    set.insert(b"value_1".to_vec());
+    set.insert(b"value_2".to_vec());
+    set.insert(b"value_3".to_vec());
     let added_item_list_26 = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    database.add(String::from("set_remove_1"), added_item_list_26);
+    database.add(b"set_remove_1".to_vec(), added_item_list_26);
 
     let mut set = HashSet::new();
-    set.insert("value_1".to_string());
-    set.insert("value_2".to_string());
-    set.insert("value_3".to_string());
+    set.insert(b"value_1".to_vec());
+    set.insert(b"value_2".to_vec());
+    set.insert(b"value_3".to_vec());
     let added_item_list_27 = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    database.add(String::from("set_remove_2"), added_item_list_27);
+    database.add(b"set_remove_2".to_vec(), added_item_list_27);
 
     let mut set = HashSet::new();
-    set.insert("value_2".to_string());
-    set.insert("value_3".to_string());
+    set.insert(b"value_2".to_vec());
+    set.insert(b"value_3".to_vec());
     let added_item_list_28 = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    database.add(String::from("set_remove_3"), added_item_list_28);
+    database.add(b"set_remove_3".to_vec(), added_item_list_28);
 
     let added_item_list_29 =
-        ValueTimeItemBuilder::new(ValueType::ListType(vec!["item_1".to_string()])).build();
-    database.add(String::from("set_remove_4"), added_item_list_29);
-
-    let added_item_list_30 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "dog".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "dear".to_string(),
-    ]))
+        ValueTimeItemBuilder::new(ValueType::ListType(vec![b"item_1".to_vec()].into())).build();
+    database.add(b"set_remove_4".to_vec(), added_item_list_29);
+
+    let added_item_list_30 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"dog".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"dear".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("love_the_dog"), added_item_list_30);
-
-    let added_item_list_31 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "cat".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "dear".to_string(),
-    ]))
+    database.add(b"love_the_dog".to_vec(), added_item_list_30);
+
+    let added_item_list_31 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"cat".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"dear".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("love_the_cat"), added_item_list_31);
-
-    let added_item_list_32 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "my".to_string(),
-        "bunny".to_string(),
-        "my".to_string(),
-        "friend".to_string(),
-        "my".to_string(),
-        "family".to_string(),
-        "my".to_string(),
-        "dear".to_string(),
-    ]))
+    database.add(b"love_the_cat".to_vec(), added_item_list_31);
+
+    let added_item_list_32 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"my".to_vec(),
+            b"bunny".to_vec(),
+            b"my".to_vec(),
+            b"friend".to_vec(),
+            b"my".to_vec(),
+            b"family".to_vec(),
+            b"my".to_vec(),
+            b"dear".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("love_the_bunny"), added_item_list_32);
+    database.add(b"love_the_bunny".to_vec(), added_item_list_32);
 
     let added_persistent =
-        ValueTimeItemBuilder::new(ValueType::StringType("persistente".to_string())).build();
-    database.add(String::from("persistente"), added_persistent);
+        ValueTimeItemBuilder::new(ValueType::StringType("persistente".into())).build();
+    database.add(b"persistente".to_vec(), added_persistent);
 
-    let added_item_33 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "chocolate".to_string(),
-        "frutilla".to_string(),
-    ]))
+    let added_item_33 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![b"chocolate".to_vec(), b"frutilla".to_vec()].into(),
+    ))
     .build();
-    database.add(String::from("sabores"), added_item_33);
-
-    let added_item_34 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "baldur".to_string(),
-        "odin".to_string(),
-        "freya".to_string(),
-        "mimir".to_string(),
-    ]))
+    database.add(b"sabores".to_vec(), added_item_33);
+
+    let added_item_34 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"baldur".to_vec(),
+            b"odin".to_vec(),
+            b"freya".to_vec(),
+            b"mimir".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("norse_gods"), added_item_34);
-
-    let added_item_35 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "hera".to_string(),
-        "afrodita".to_string(),
-        "chaos".to_string(),
-        "artemis".to_string(),
-    ]))
+    database.add(b"norse_gods".to_vec(), added_item_34);
+
+    let added_item_35 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"hera".to_vec(),
+            b"afrodita".to_vec(),
+            b"chaos".to_vec(),
+            b"artemis".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("greek_gods"), added_item_35);
-
-    let added_item_36 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "isis".to_string(),
-        "osiris".to_string(),
-        "horus".to_string(),
-        "set".to_string(),
-    ]))
+    database.add(b"greek_gods".to_vec(), added_item_35);
+
+    let added_item_36 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"isis".to_vec(),
+            b"osiris".to_vec(),
+            b"horus".to_vec(),
+            b"set".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("egyptian_gods"), added_item_36);
-
-    let added_item_37 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "pineapple_1".to_string(),
-        "pineapple_2".to_string(),
-        "pineapple_3".to_string(),
-        "pineapple_4".to_string(),
-    ]))
+    database.add(b"egyptian_gods".to_vec(), added_item_36);
+
+    let added_item_37 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"pineapple_1".to_vec(),
+            b"pineapple_2".to_vec(),
+            b"pineapple_3".to_vec(),
+            b"pineapple_4".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("pineapple_mascots"), added_item_37);
-
-    let added_item_38 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "banana_1".to_string(),
-        "banana_2".to_string(),
-        "banana_3".to_string(),
-        "banana_4".to_string(),
-    ]))
+    database.add(b"pineapple_mascots".to_vec(), added_item_37);
+
+    let added_item_38 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"banana_1".to_vec(),
+            b"banana_2".to_vec(),
+            b"banana_3".to_vec(),
+            b"banana_4".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("banana_mascots"), added_item_38);
+    database.add(b"banana_mascots".to_vec(), added_item_38);
 
-    let added_item_39 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "borussia".to_string(),
-        "werder".to_string(),
-        "bayer".to_string(),
-    ]))
+    let added_item_39 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![b"borussia".to_vec(), b"werder".to_vec(), b"bayer".to_vec()].into(),
+    ))
     .build();
-    database.add(String::from("equipos_de_la_bundesliga"), added_item_39);
-
-    let added_item_40 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "chocolate a la leche condensada".to_string(),
-        "chocolate werder".to_string(),
-        "chocolate aires del Huapi".to_string(),
-    ]))
+    database.add(b"equipos_de_la_bundesliga".to_vec(), added_item_39);
+
+    let added_item_40 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"chocolate a la leche condensada".to_vec(),
+            b"chocolate werder".to_vec(),
+            b"chocolate aires del Huapi".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("sabores_de_chocolate"), added_item_40);
-
-    let added_item_41 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "banana_passion_1".to_string(),
-        "banana_passion_2".to_string(),
-        "banana_passion_3".to_string(),
-        "banana_passion_4".to_string(),
-    ]))
+    database.add(b"sabores_de_chocolate".to_vec(), added_item_40);
+
+    let added_item_41 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"banana_passion_1".to_vec(),
+            b"banana_passion_2".to_vec(),
+            b"banana_passion_3".to_vec(),
+            b"banana_passion_4".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("banana_passions"), added_item_41);
-
-    let added_item_42 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "carpintero_1".to_string(),
-        "carpintero_2".to_string(),
-        "carpintero_3".to_string(),
-        "carpintero_4".to_string(),
-    ]))
+    database.add(b"banana_passions".to_vec(), added_item_41);
+
+    let added_item_42 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"carpintero_1".to_vec(),
+            b"carpintero_2".to_vec(),
+            b"carpintero_3".to_vec(),
+            b"carpintero_4".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("pajaros_carpinteros"), added_item_42);
+    database.add(b"pajaros_carpinteros".to_vec(), added_item_42);
 
     let added_item_43 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("jugo de kiwi"))).build();
-    database.add(String::from("botellon_de_jugo_1"), added_item_43);
+        ValueTimeItemBuilder::new(ValueType::StringType("jugo de kiwi".into())).build();
+    database.add(b"botellon_de_jugo_1".to_vec(), added_item_43);
 
     let added_item_44 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("jugo de grosellas"))).build();
-    database.add(String::from("botellon_de_jugo_2"), added_item_44);
+        ValueTimeItemBuilder::new(ValueType::StringType("jugo de grosellas".into())).build();
+    database.add(b"botellon_de_jugo_2".to_vec(), added_item_44);
 
     let added_item_45 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("jugo de tamarindo"))).build();
-    database.add(String::from("botellon_de_jugo_3"), added_item_45);
+        ValueTimeItemBuilder::new(ValueType::StringType("jugo de tamarindo".into())).build();
+    database.add(b"botellon_de_jugo_3".to_vec(), added_item_45);
 
     let added_item_46 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("jugo de remolacha"))).build();
-    database.add(String::from("botellon_de_jugo_10"), added_item_46);
+        ValueTimeItemBuilder::new(ValueType::StringType("jugo de remolacha".into())).build();
+    database.add(b"botellon_de_jugo_10".to_vec(), added_item_46);
 
     let added_item_47 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("jugo de damasco"))).build();
-    database.add(String::from("botellon_de_jugo_23"), added_item_47);
+        ValueTimeItemBuilder::new(ValueType::StringType("jugo de damasco".into())).build();
+    database.add(b"botellon_de_jugo_23".to_vec(), added_item_47);
 
     let mut set = HashSet::new();
-    set.insert("granadero_espigado_1".to_string());
-    set.insert("granadero_espigado_2".to_string());
+    set.insert(b"granadero_espigado_1".to_vec());
+    set.insert(b"granadero_espigado_2".to_vec());
     let added_item_set_48 = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    database.add(String::from("granaderos_espigados"), added_item_set_48);
+    database.add(b"granaderos_espigados".to_vec(), added_item_set_48);
 
     let mut set = HashSet::new();
-    set.insert("granadero_atolondrado_1".to_string());
-    set.insert("granadero_atolondrado_2".to_string());
+    set.insert(b"granadero_atolondrado_1".to_vec());
+    set.insert(b"granadero_atolondrado_2".to_vec());
     let added_item_set_49 = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    database.add(String::from("granaderos_atolondrados"), added_item_set_49);
+    database.add(b"granaderos_atolondrados".to_vec(), added_item_set_49);
 
     let mut set = HashSet::new();
-    set.insert("granadero_taciturno_1".to_string());
-    set.insert("granadero_taciturno_2".to_string());
+    set.insert(b"granadero_taciturno_1".to_vec());
+    set.insert(b"granadero_taciturno_2".to_vec());
     let added_item_set_50 = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
-    database.add(String::from("granaderos_taciturnos"), added_item_set_50);
+    database.add(b"granaderos_taciturnos".to_vec(), added_item_set_50);
 
     let mut set = HashSet::new();
-    set.insert("granadero_eliminado_1".to_string());
-    set.insert("granadero_eliminado_2".to_string());
+    set.insert(b"granadero_eliminado_1".to_vec());
+    set.insert(b"granadero_eliminado_2".to_vec());
     let added_item_set_51 = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
     database.add(
-        String::from("granaderos_eliminados_tipo_set"),
+        b"granaderos_eliminados_tipo_set".to_vec(),
         added_item_set_51,
     );
 
-    let added_item_52 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "granadero_eliminado_1".to_string(),
-        "granadero_eliminado_2".to_string(),
-        "granadero_eliminado_3".to_string(),
-        "granadero_eliminado_4".to_string(),
-    ]))
+    let added_item_52 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"granadero_eliminado_1".to_vec(),
+            b"granadero_eliminado_2".to_vec(),
+            b"granadero_eliminado_3".to_vec(),
+            b"granadero_eliminado_4".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(
-        String::from("granaderos_eliminados_tipo_list"),
-        added_item_52,
-    );
-
-    let added_item_53 =
-        ValueTimeItemBuilder::new(ValueType::StringType(String::from("value_key_999")))
-            .with_timeout(1635597186)
-            .build();
+    database.add(b"granaderos_eliminados_tipo_list".to_vec(), added_item_52);
 
-    database.add(String::from("key_999"), added_item_53);
+    let added_item_53 = ValueTimeItemBuilder::new(ValueType::StringType("value_key_999".into()))
+        .with_timeout(1935597186)
+        .build();
 
-    let added_item_54 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "molleja_frita_1".to_string(),
-        "molleja_frita_2".to_string(),
-        "molleja_frita_3".to_string(),
-        "molleja_frita_4".to_string(),
-    ]))
+    database.add(b"key_999".to_vec(), added_item_53);
+
+    let added_item_54 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"molleja_frita_1".to_vec(),
+            b"molleja_frita_2".to_vec(),
+            b"molleja_frita_3".to_vec(),
+            b"molleja_frita_4".to_vec(),
+        ]
+        .into(),
+    ))
+    .build();
+    database.add(b"mollejas_fritas".to_vec(), added_item_54);
+
+    let added_item_55 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"molleja_gratinada_1".to_vec(),
+            b"molleja_gratinada_2".to_vec(),
+            b"molleja_gratinada_3".to_vec(),
+            b"molleja_gratinada_4".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("mollejas_fritas"), added_item_54);
-
-    let added_item_55 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "molleja_gratinada_1".to_string(),
-        "molleja_gratinada_2".to_string(),
-        "molleja_gratinada_3".to_string(),
-        "molleja_gratinada_4".to_string(),
-    ]))
+    database.add(b"mollejas_gratinadas".to_vec(), added_item_55);
+
+    let added_item_56 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"molleja_salteada_1".to_vec(),
+            b"molleja_salteada_2".to_vec(),
+            b"molleja_salteada_3".to_vec(),
+            b"molleja_salteada_4".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("mollejas_gratinadas"), added_item_55);
-
-    let added_item_56 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "molleja_salteada_1".to_string(),
-        "molleja_salteada_2".to_string(),
-        "molleja_salteada_3".to_string(),
-        "molleja_salteada_4".to_string(),
-    ]))
+    database.add(b"mollejas_salteadas".to_vec(), added_item_56);
+
+    let added_item_57 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"molleja_estofada_1".to_vec(),
+            b"molleja_estofada_2".to_vec(),
+            b"molleja_estofada_3".to_vec(),
+            b"molleja_estofada_4".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("mollejas_salteadas"), added_item_56);
-
-    let added_item_57 = ValueTimeItemBuilder::new(ValueType::ListType(vec![
-        "molleja_estofada_1".to_string(),
-        "molleja_estofada_2".to_string(),
-        "molleja_estofada_3".to_string(),
-        "molleja_estofada_4".to_string(),
-    ]))
+    database.add(b"mollejas_estofadas".to_vec(), added_item_57);
+
+    let added_item_58 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"tarea_1".to_vec(),
+            b"tarea_3".to_vec(),
+            b"tarea_1".to_vec(),
+        ]
+        .into(),
+    ))
     .build();
-    database.add(String::from("mollejas_estofadas"), added_item_57);
+    database.add(b"tareas_pendientes".to_vec(), added_item_58);
+
+    let added_item_59 = ValueTimeItemBuilder::new(ValueType::ListType(
+        vec![
+            b"trabajo_1".to_vec(),
+            b"trabajo_2".to_vec(),
+            b"trabajo_3".to_vec(),
+        ]
+        .into(),
+    ))
+    .build();
+    database.add(b"trabajos_en_cola".to_vec(), added_item_59);
+
+    let mut set = HashSet::new();
+    set.insert(b"frutilla".to_vec());
+    set.insert(b"kiwi".to_vec());
+    set.insert(b"pomelo".to_vec());
+    let added_item_set_60 = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
+    database.add(b"frutas_de_estacion".to_vec(), added_item_set_60);
+
+    let mut set = HashSet::new();
+    set.insert(b"kiwi".to_vec());
+    set.insert(b"palta".to_vec());
+    let added_item_set_61 = ValueTimeItemBuilder::new(ValueType::SetType(set)).build();
+    database.add(b"frutas_verdes".to_vec(), added_item_set_61);
+
+    let added_item_62 = ValueTimeItemBuilder::new(ValueType::StringType("ohmytext".into())).build();
+    database.add(b"texto_original".to_vec(), added_item_62);
+
+    let added_item_63 =
+        ValueTimeItemBuilder::new(ValueType::StringType("mynewtext".into())).build();
+    database.add(b"texto_modificado".to_vec(), added_item_63);
 
     //--------------------------------------------------------------------------------------------------------------------------------------------
     //--------------------------------------------------------------------------------------------------------------------------------------------
@@ -531,12 +690,20 @@ fn test_main() {
     let server_receiver = Arc::new(Mutex::new(server_receiver));
     let port = String::from("8080");
     let verbose = String::from("0");
-    let dir = String::from("127.0.0.1");
+    let dir = String::from("127.0.0.1 -::1");
+    let server_dir = dir.clone();
 
     thread::spawn(|| {
         let h = thread::spawn(|| {
-            let mut server =
-                Server::new(port, log_file, verbose, server_receiver, config_path).unwrap();
+            let mut server = Server::new(
+                server_dir,
+                port,
+                log_file,
+                verbose,
+                server_receiver,
+                config_path,
+            )
+            .unwrap();
             server.listen();
         });
 
@@ -574,6 +741,10 @@ fn test_main() {
     std::fs::remove_file("./src/dummy_config.txt").unwrap();
     std::fs::remove_file("./src/dummy_log.txt").unwrap();
     std::fs::remove_file("./src/dummy_database.txt").unwrap();
+    let _ = std::fs::remove_file(UNIX_SOCKET);
+    for file in TLS_FILES {
+        let _ = std::fs::remove_file(file);
+    }
 }
 
 const TESTS: &[Test] = &[
@@ -585,6 +756,10 @@ const TESTS: &[Test] = &[
         name: "server command: config set maxmemory",
         func: test_config_set_maxmemory,
     },
+    Test {
+        name: "server command: config set invalid client-output-buffer-limit",
+        func: test_config_set_invalid_output_buffer_limit,
+    },
     Test {
         name: "server command: config get *",
         func: test_config_get_all,
@@ -593,6 +768,10 @@ const TESTS: &[Test] = &[
         name: "server command: config get",
         func: test_config_get_returns_error_missing_parameter,
     },
+    Test {
+        name: "server command: shutdown with invalid options keeps the server running",
+        func: test_shutdown_with_invalid_options,
+    },
     // Test {
     //     name: "server command: dbsize",
     //     func: test_dbsize,
@@ -694,7 +873,7 @@ const TESTS: &[Test] = &[
         func: test_keys_copy_with_replace,
     },
     Test {
-        name: "keys command: copy with no replace cannot replace destination key - error",
+        name: "keys command: copy with no replace does not replace destination key",
         func: test_keys_copy_sin_replace_arroja_error_porque_la_clave_destino_ya_existe,
     },
     Test {
@@ -885,6 +1064,38 @@ const TESTS: &[Test] = &[
         name: "string command: set mykeyset setvalue with xx argument successful as key already exists",
         func: test_string_set_with_xx_argument_succesfull_as_key_already_exists,
     },
+    Test {
+        name: "string command: incr and decr on non-existing key",
+        func: test_string_incr_and_decr_on_non_existing_key,
+    },
+    Test {
+        name: "string command: getrange with negative indexes",
+        func: test_string_getrange_with_negative_indexes,
+    },
+    Test {
+        name: "string command: set with get returns old value",
+        func: test_string_set_with_get_returns_old_value,
+    },
+    Test {
+        name: "string command: lcs",
+        func: test_string_lcs,
+    },
+    Test {
+        name: "string command: set and get binary value with crlf inside",
+        func: test_string_set_get_binary_value,
+    },
+    Test {
+        name: "string command: set and get value larger than read buffer",
+        func: test_string_set_get_large_value,
+    },
+    Test {
+        name: "string command: set keeps value case",
+        func: test_string_set_keeps_value_case,
+    },
+    Test {
+        name: "list and set commands: binary keys and elements are stored",
+        func: test_lpush_y_sadd_guardan_claves_y_elementos_binarios,
+    },
     Test {
         name: "list command: lpush values into key - list type",
         func: test_lpush_se_guardan_valores_en_una_lista_que_no_existe_previamente,
@@ -1066,6 +1277,18 @@ const TESTS: &[Test] = &[
         name: "list command: lset cannot set new element in list type value out of bounds error",
         func: test_list_no_reemplaza_un_elemento_de_value_list_type_con_indice_fuera_de_rango_error,
     },
+    Test {
+        name: "list command: linsert before pivot",
+        func: test_list_linsert,
+    },
+    Test {
+        name: "list command: lpos with rank and count",
+        func: test_list_lpos,
+    },
+    Test {
+        name: "list command: lmove from right to left",
+        func: test_list_lmove,
+    },
     Test {
         name: "set command: sadd",
         func: test_set_add,
@@ -1142,10 +1365,70 @@ const TESTS: &[Test] = &[
         name: "set command: srem error",
         func: test_set_srem_removes_returns_error,
     },
+    Test {
+        name: "set command: sinter",
+        func: test_set_sinter,
+    },
+    Test {
+        name: "set command: sunionstore",
+        func: test_set_sunionstore,
+    },
+    Test {
+        name: "set command: sintercard with limit",
+        func: test_set_sintercard_with_limit,
+    },
+    Test {
+        name: "set command: smismember",
+        func: test_set_smismember,
+    },
+    Test {
+        name: "set command: srandmember with negative count",
+        func: test_set_srandmember_with_negative_count,
+    },
+    Test {
+        name: "set command: srandmember with huge negative count",
+        func: test_set_srandmember_with_huge_negative_count,
+    },
     Test {
         name: "pubsub commands: subscribe pubsub channels numsub",
         func: test_pubsub,
     },
+    Test {
+        name: "pubsub commands: psubscribe receives pmessage and numpat",
+        func: test_psubscribe_receives_pattern_messages,
+    },
+    Test {
+        name: "pubsub commands: punsubscribe replies once per pattern",
+        func: test_punsubscribe_replies_once_per_pattern,
+    },
+    Test {
+        name: "pubsub commands: slow subscriber over output buffer limit is disconnected",
+        func: test_slow_subscriber_is_disconnected,
+    },
+    Test {
+        name: "pubsub commands: ssubscribe spublish shardchannels shardnumsub",
+        func: test_sharded_pubsub,
+    },
+    Test {
+        name: "pubsub commands: keyspace notifications of writes and expired keys",
+        func: test_keyspace_notifications,
+    },
+    Test {
+        name: "client tracking command: read key is invalidated when modified",
+        func: test_client_tracking_invalidates_read_keys,
+    },
+    Test {
+        name: "monitor command: streams commands with redis format",
+        func: test_monitor_streams_commands,
+    },
+    Test {
+        name: "monitor command: denied commands are not streamed",
+        func: test_monitor_skips_denied_commands,
+    },
+    Test {
+        name: "replicaof command: replica synchronizes and receives writes",
+        func: test_replicaof_synchronizes_and_propagates_writes,
+    },
     Test {
         name: "rpush command: new list",
         func: test_rpush_lista_inexistente
@@ -1153,6 +1436,110 @@ const TESTS: &[Test] = &[
     Test {
         name: "info",
         func: test_info
+    },
+    Test {
+        name: "hello command: negotiates resp3",
+        func: test_hello_negotiates_resp3
+    },
+    Test {
+        name: "hello command: keeps resp2 without version",
+        func: test_hello_without_version_keeps_resp2
+    },
+    Test {
+        name: "hello command: unsupported version",
+        func: test_hello_unsupported_version
+    },
+    Test {
+        name: "inline protocol: set and get quoted value",
+        func: test_inline_set_and_get_quoted_value
+    },
+    Test {
+        name: "inline protocol: unbalanced quotes",
+        func: test_inline_unbalanced_quotes
+    },
+    Test {
+        name: "connection commands: ping and echo",
+        func: test_ping_and_echo
+    },
+    Test {
+        name: "connection commands: ping in subscribed mode",
+        func: test_ping_in_subscribed_mode
+    },
+    Test {
+        name: "connection commands: client setname getname info",
+        func: test_client_setname_getname_info
+    },
+    Test {
+        name: "connection commands: client id is unique",
+        func: test_client_id_is_unique
+    },
+    Test {
+        name: "connection commands: reset clears name",
+        func: test_reset_clears_name
+    },
+    Test {
+        name: "connection commands: quit closes connection",
+        func: test_quit_closes_connection
+    },
+    Test {
+        name: "connection commands: client list by id",
+        func: test_client_list_by_id
+    },
+    Test {
+        name: "connection commands: client kill by id",
+        func: test_client_kill_by_id
+    },
+    Test {
+        name: "connection commands: client kill unknown address",
+        func: test_client_kill_unknown_address
+    },
+    Test {
+        name: "connection commands: client pause write",
+        func: test_client_pause_write
+    },
+    Test {
+        name: "connection commands: auth without requirepass",
+        func: test_auth_without_requirepass
+    },
+    Test {
+        name: "connection commands: hello auth without requirepass",
+        func: test_hello_auth_without_requirepass
+    },
+    Test {
+        name: "acl commands: worker restricted to key pattern and categories",
+        func: test_acl_worker_permissions
+    },
+    Test {
+        name: "acl commands: setuser getuser users deluser",
+        func: test_acl_getuser_and_users
+    },
+    Test {
+        name: "acl commands: cat",
+        func: test_acl_cat
+    },
+    Test {
+        name: "acl commands: auth with unknown user",
+        func: test_acl_auth_unknown_user
+    },
+    Test {
+        name: "bind: connection over ipv6 loopback",
+        func: test_bind_ipv6_loopback
+    },
+    Test {
+        name: "unix socket: commands over unix socket",
+        func: test_unix_socket_commands
+    },
+    Test {
+        name: "unix socket: permissions",
+        func: test_unix_socket_permissions
+    },
+    Test {
+        name: "tls: commands over tls with client certificate",
+        func: test_tls_with_client_certificate
+    },
+    Test {
+        name: "tls: client without certificate is rejected",
+        func: test_tls_without_client_certificate
     }
 ];
 
@@ -1162,123 +1549,803 @@ fn connect() -> Result<redis::Connection, Box<dyn Error>> {
     Ok(con)
 }
 
+/// Envía una línea al servidor como lo haría un cliente telnet y devuelve la respuesta sin procesar.
+fn send_inline(stream: &mut TcpStream, line: &str) -> Result<String, Box<dyn Error>> {
+    stream.write_all(line.as_bytes())?;
+    let mut buf = [0u8; 512];
+    let size = stream.read(&mut buf)?;
+    Ok(String::from_utf8_lossy(&buf[..size]).to_string())
+}
+
+fn test_inline_set_and_get_quoted_value() -> TestResult {
+    let mut stream = TcpStream::connect("127.0.0.1:8080")?;
+    let set = send_inline(&mut stream, "SET inline_key \"hola mundo\\n\"\r\n")?;
+    let get = send_inline(&mut stream, "\r\nget inline_key\n")?;
+
+    if set == "$2\r\nOk\r\n" && get == "$11\r\nhola mundo\n\r\n" {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("$2\r\nOk\r\n $11\r\nhola mundo\n\r\n"),
+            got: format!("{:?} {:?}", set, get),
+        }))
+    }
+}
+
+fn test_inline_unbalanced_quotes() -> TestResult {
+    let mut stream = TcpStream::connect("127.0.0.1:8080")?;
+    let ret = send_inline(&mut stream, "SET inline_key \"hola\r\n")?;
+
+    if ret == "-ERR Protocol error: unbalanced quotes in request\r\n" {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("-ERR Protocol error: unbalanced quotes in request\r\n"),
+            got: ret,
+        }))
+    }
+}
+
 //-------------------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------------------
-//-----------------------------------------------------SERVER COMMANDS-----------------------------------------------------------
+//-----------------------------------------------------CONNECTION COMMANDS-------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------------------
 
-pub fn test_info() -> TestResult {
+fn test_ping_and_echo() -> TestResult {
     let mut con = connect()?;
-    let ret: Result<String, RedisError> = redis::cmd("INFO").query(&mut con);
-    return if ret.is_ok() {
+    let pong: String = redis::cmd("PING").query(&mut con)?;
+    let echo: String = redis::cmd("ECHO").arg("hola mundo").query(&mut con)?;
+
+    if pong == "PONG" && echo == "hola mundo" {
         Ok(())
     } else {
         Err(Box::new(ReturnError {
-            expected: String::from(""),
-            got: ret.err().unwrap().to_string(),
+            expected: String::from("PONG hola mundo"),
+            got: format!("{} {}", pong, echo),
         }))
-    };
+    }
 }
 
-fn test_config_get_verbose() -> TestResult {
-    let mut con = connect()?;
-    let ret: Vec<String> = redis::cmd("CONFIG")
-        .arg("get")
-        .arg("verbose")
-        .query(&mut con)?;
+fn test_ping_in_subscribed_mode() -> TestResult {
+    let mut stream = TcpStream::connect("127.0.0.1:8080")?;
+    // se utiliza un canal que ya espera test_pubsub, ya que los tests se ejecutan en paralelo y
+    // test_pubsub verifica la lista completa de canales activos
+    send_inline(&mut stream, "SUBSCRIBE helloworld\r\n")?;
+    let ret = send_inline(&mut stream, "PING\r\n")?;
+    send_inline(&mut stream, "RESET\r\n")?;
 
-    if &ret[1] == &String::from("1") {
-        return Ok(());
+    if ret == "*2\r\n$4\r\npong\r\n$0\r\n\r\n" {
+        Ok(())
     } else {
-        return Err(Box::new(ReturnError {
-            expected: String::from("1"),
-            got: String::from(&ret[1]),
-        }));
+        Err(Box::new(ReturnError {
+            expected: String::from("*2\r\n$4\r\npong\r\n$0\r\n\r\n"),
+            got: ret,
+        }))
     }
 }
 
-fn test_config_get_all() -> TestResult {
+fn test_client_setname_getname_info() -> TestResult {
     let mut con = connect()?;
-    let ret: Vec<String> = redis::cmd("CONFIG").arg("get").arg("*").query(&mut con)?;
+    let _: () = redis::cmd("CLIENT")
+        .arg("SETNAME")
+        .arg("conexion-test")
+        .query(&mut con)?;
+    let _: () = redis::cmd("CLIENT")
+        .arg("SETINFO")
+        .arg("LIB-NAME")
+        .arg("redis-rs")
+        .query(&mut con)?;
+    let name: String = redis::cmd("CLIENT").arg("GETNAME").query(&mut con)?;
+    let info: String = redis::cmd("CLIENT").arg("INFO").query(&mut con)?;
 
-    if ret[0].len() > 0 {
-        return Ok(());
+    if name == "conexion-test"
+        && info.contains("name=conexion-test ")
+        && info.contains("lib-name=redis-rs ")
+        && info.contains("cmd=client")
+    {
+        Ok(())
     } else {
-        return Err(Box::new(ReturnError {
-            expected: String::from("Must contain verbose"),
-            got: format!("{:?}", ret),
-        }));
+        Err(Box::new(ReturnError {
+            expected: String::from("conexion-test"),
+            got: format!("{} {}", name, info),
+        }))
     }
 }
 
-fn test_config_get_returns_error_missing_parameter() -> TestResult {
+fn test_client_id_is_unique() -> TestResult {
     let mut con = connect()?;
-    let ret: Result<String, RedisError> = redis::cmd("CONFIG").arg("get").query(&mut con);
+    let mut other_con = connect()?;
+    let id: usize = redis::cmd("CLIENT").arg("ID").query(&mut con)?;
+    let other_id: usize = redis::cmd("CLIENT").arg("ID").query(&mut other_con)?;
 
-    if ret.is_err() {
-        return Ok(());
+    if id > 0 && id != other_id {
+        Ok(())
     } else {
-        return Err(Box::new(ReturnError {
-            expected: String::from("Error missing parameter"),
-            got: format!("{:?}", ret),
-        }));
+        Err(Box::new(ReturnError {
+            expected: String::from("Different ids"),
+            got: format!("{} {}", id, other_id),
+        }))
     }
 }
 
-fn test_config_set_maxmemory() -> TestResult {
+fn test_reset_clears_name() -> TestResult {
     let mut con = connect()?;
-    let ret: String = redis::cmd("CONFIG")
-        .arg("set")
-        .arg("maxmemory")
-        .arg("2mb")
+    let _: () = redis::cmd("CLIENT")
+        .arg("SETNAME")
+        .arg("temporal")
         .query(&mut con)?;
+    let reset: String = redis::cmd("RESET").query(&mut con)?;
+    let name: Option<String> = redis::cmd("CLIENT").arg("GETNAME").query(&mut con)?;
 
-    if ret == String::from("Ok") {
-        return Ok(());
+    if reset == "RESET" && name.is_none() {
+        Ok(())
     } else {
-        return Err(Box::new(ReturnError {
-            expected: String::from("Ok"),
-            got: ret,
-        }));
+        Err(Box::new(ReturnError {
+            expected: String::from("RESET None"),
+            got: format!("{} {:?}", reset, name),
+        }))
     }
 }
 
-// no lo testeo porque el resultado depende de si se ejecuta antes o despues de borrar una clave
-fn _test_dbsize() -> TestResult {
-    let mut con = connect()?;
-    let ret: usize = redis::cmd("DBSIZE").query(&mut con)?;
+fn test_quit_closes_connection() -> TestResult {
+    let mut stream = TcpStream::connect("127.0.0.1:8080")?;
+    let ret = send_inline(&mut stream, "QUIT\r\n")?;
+    let mut buf = [0u8; 16];
+    let size = stream.read(&mut buf)?;
 
-    if ret == 4 {
-        return Ok(());
+    if ret == "+OK\r\n" && size == 0 {
+        Ok(())
     } else {
-        return Err(Box::new(ReturnError {
-            expected: String::from("4"),
-            got: ret.to_string(),
-        }));
+        Err(Box::new(ReturnError {
+            expected: String::from("+OK\r\n and closed connection"),
+            got: format!("{:?} {}", ret, size),
+        }))
     }
 }
 
-// no lo testeo porque depende el orden en que se ejecuten podrian fallarme los otros tests
-fn _test_flushdb() -> TestResult {
+fn test_client_list_by_id() -> TestResult {
     let mut con = connect()?;
-    let ret: String = redis::cmd("FLUSHDB").query(&mut con)?;
+    let _: () = redis::cmd("CLIENT")
+        .arg("SETNAME")
+        .arg("listado")
+        .query(&mut con)?;
+    let id: usize = redis::cmd("CLIENT").arg("ID").query(&mut con)?;
+    let list: String = redis::cmd("CLIENT")
+        .arg("LIST")
+        .arg("ID")
+        .arg(id)
+        .query(&mut con)?;
 
-    if ret == String::from("Erased database") {
-        return Ok(());
+    if list.starts_with(&format!("id={} ", id))
+        && list.contains(" name=listado ")
+        && list.lines().count() == 1
+    {
+        Ok(())
     } else {
-        return Err(Box::new(ReturnError {
-            expected: String::from("Erased database"),
-            got: ret,
-        }));
+        Err(Box::new(ReturnError {
+            expected: format!("id={} ... name=listado", id),
+            got: list,
+        }))
     }
 }
 
-//-------------------------------------------------------------------------------------------------------------------------------
-//-------------------------------------------------------------------------------------------------------------------------------
-//-----------------------------------------------------KEYS COMMANDS-------------------------------------------------------------
-//-------------------------------------------------------------------------------------------------------------------------------
-//-------------------------------------------------------------------------------------------------------------------------------
+fn test_client_kill_by_id() -> TestResult {
+    let mut con = connect()?;
+    let mut victim = connect()?;
+    let victim_id: usize = redis::cmd("CLIENT").arg("ID").query(&mut victim)?;
+    let killed: usize = redis::cmd("CLIENT")
+        .arg("KILL")
+        .arg("ID")
+        .arg(victim_id)
+        .query(&mut con)?;
+    let ret: Result<String, RedisError> = redis::cmd("PING").query(&mut victim);
+
+    if killed == 1 && ret.is_err() {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("1 killed and closed connection"),
+            got: format!("{} {:?}", killed, ret),
+        }))
+    }
+}
+
+fn test_client_kill_unknown_address() -> TestResult {
+    let mut con = connect()?;
+    let ret: Result<String, RedisError> = redis::cmd("CLIENT")
+        .arg("KILL")
+        .arg("10.0.0.1:1")
+        .query(&mut con);
+
+    match ret {
+        Err(e) if e.to_string().contains("No such client") => Ok(()),
+        _ => Err(Box::new(ReturnError {
+            expected: String::from("No such client"),
+            got: format!("{:?}", ret),
+        })),
+    }
+}
+
+fn test_client_pause_write() -> TestResult {
+    let mut con = connect()?;
+    let _: () = redis::cmd("CLIENT")
+        .arg("PAUSE")
+        .arg(300)
+        .arg("WRITE")
+        .query(&mut con)?;
+    let start = std::time::Instant::now();
+    let _: Option<String> = redis::cmd("GET").arg("pause_key").query(&mut con)?;
+    let read_elapsed = start.elapsed();
+    let _: String = redis::cmd("SET")
+        .arg("pause_key")
+        .arg("valor")
+        .query(&mut con)?;
+    let write_elapsed = start.elapsed();
+
+    if read_elapsed < Duration::from_millis(200) && write_elapsed >= Duration::from_millis(250) {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("read immediately, write after pause"),
+            got: format!("{:?} {:?}", read_elapsed, write_elapsed),
+        }))
+    }
+}
+
+fn test_auth_without_requirepass() -> TestResult {
+    let mut con = connect()?;
+    let ret: Result<String, RedisError> = redis::cmd("AUTH").arg("secreto").query(&mut con);
+
+    match ret {
+        Err(e) if e.to_string().contains("without any password configured") => Ok(()),
+        _ => Err(Box::new(ReturnError {
+            expected: String::from("AUTH called without any password configured error"),
+            got: format!("{:?}", ret),
+        })),
+    }
+}
+
+fn test_hello_auth_without_requirepass() -> TestResult {
+    let mut con = connect()?;
+    let ret: redis::Value = redis::cmd("HELLO")
+        .arg(2)
+        .arg("AUTH")
+        .arg("default")
+        .arg("cualquiera")
+        .query(&mut con)?;
+
+    if let redis::Value::Array(fields) = &ret {
+        if fields.contains(&redis::Value::BulkString(b"proto".to_vec())) {
+            return Ok(());
+        }
+    }
+    Err(Box::new(ReturnError {
+        expected: String::from("Array with proto 2"),
+        got: format!("{:?}", ret),
+    }))
+}
+
+//-------------------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+//-----------------------------------------------------ACL COMMANDS--------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+
+fn test_acl_worker_permissions() -> TestResult {
+    let mut admin = connect()?;
+    redis::cmd("ACL")
+        .arg("SETUSER")
+        .arg("acl_worker")
+        .arg("on")
+        .arg(">secreto")
+        .arg("~job:*")
+        .arg("+@all")
+        .arg("-@admin")
+        .arg("-@dangerous")
+        .query::<String>(&mut admin)?;
+
+    let mut worker = connect()?;
+    redis::cmd("AUTH")
+        .arg("acl_worker")
+        .arg("secreto")
+        .query::<String>(&mut worker)?;
+    let whoami: String = redis::cmd("ACL").arg("WHOAMI").query(&mut worker)?;
+    redis::cmd("SET")
+        .arg("job:acl")
+        .arg("1")
+        .query::<String>(&mut worker)?;
+    let key_denied: Result<String, RedisError> =
+        redis::cmd("GET").arg("user:acl").query(&mut worker);
+    let command_denied: Result<Vec<String>, RedisError> = redis::cmd("CONFIG")
+        .arg("GET")
+        .arg("port")
+        .query(&mut worker);
+    let log: redis::Value = redis::cmd("ACL").arg("LOG").query(&mut admin)?;
+    let deleted: usize = redis::cmd("ACL")
+        .arg("DELUSER")
+        .arg("acl_worker")
+        .query(&mut admin)?;
+
+    let log_has_key_denial = matches!(&log, redis::Value::Array(entries) if entries.iter().any(|entry| {
+        matches!(entry, redis::Value::Array(fields) if fields.contains(&redis::Value::BulkString(b"user:acl".to_vec())))
+    }));
+    match (key_denied, command_denied) {
+        (Err(key_error), Err(command_error))
+            if whoami == "acl_worker"
+                && key_error
+                    .to_string()
+                    .contains("No permissions to access a key")
+                && command_error.to_string().contains("'config|get'")
+                && log_has_key_denial
+                && deleted == 1 =>
+        {
+            Ok(())
+        }
+        (key_denied, command_denied) => Err(Box::new(ReturnError {
+            expected: String::from("NOPERM key and command errors logged in ACL LOG"),
+            got: format!(
+                "{} {:?} {:?} {:?} {}",
+                whoami, key_denied, command_denied, log, deleted
+            ),
+        })),
+    }
+}
+
+fn test_acl_getuser_and_users() -> TestResult {
+    let mut con = connect()?;
+    redis::cmd("ACL")
+        .arg("SETUSER")
+        .arg("acl_reader")
+        .arg("on")
+        .arg("nopass")
+        .arg("~*")
+        .arg("+@read")
+        .query::<String>(&mut con)?;
+    let user: redis::Value = redis::cmd("ACL")
+        .arg("GETUSER")
+        .arg("acl_reader")
+        .query(&mut con)?;
+    let users: Vec<String> = redis::cmd("ACL").arg("USERS").query(&mut con)?;
+    let deleted: usize = redis::cmd("ACL")
+        .arg("DELUSER")
+        .arg("acl_reader")
+        .query(&mut con)?;
+
+    let expected_flags = redis::Value::Array(vec![
+        redis::Value::BulkString(b"on".to_vec()),
+        redis::Value::BulkString(b"nopass".to_vec()),
+    ]);
+    match &user {
+        redis::Value::Array(fields)
+            if fields.contains(&expected_flags)
+                && fields.contains(&redis::Value::BulkString(b"-@all +@read".to_vec()))
+                && users.contains(&String::from("acl_reader"))
+                && deleted == 1 =>
+        {
+            Ok(())
+        }
+        _ => Err(Box::new(ReturnError {
+            expected: String::from("acl_reader with flags on nopass and commands -@all +@read"),
+            got: format!("{:?} {:?} {}", user, users, deleted),
+        })),
+    }
+}
+
+fn test_acl_cat() -> TestResult {
+    let mut con = connect()?;
+    let categories: Vec<String> = redis::cmd("ACL").arg("CAT").query(&mut con)?;
+    let admin: Vec<String> = redis::cmd("ACL").arg("CAT").arg("admin").query(&mut con)?;
+
+    if categories.contains(&String::from("read")) && admin.contains(&String::from("config")) {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("read category and config admin command"),
+            got: format!("{:?} {:?}", categories, admin),
+        }))
+    }
+}
+
+fn test_acl_auth_unknown_user() -> TestResult {
+    let mut con = connect()?;
+    let ret: Result<String, RedisError> = redis::cmd("AUTH")
+        .arg("acl_unknown")
+        .arg("secreto")
+        .query(&mut con);
+
+    match ret {
+        Err(e) if e.to_string().contains("WRONGPASS") => Ok(()),
+        _ => Err(Box::new(ReturnError {
+            expected: String::from("WRONGPASS error"),
+            got: format!("{:?}", ret),
+        })),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+//-----------------------------------------------------BIND----------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+
+fn test_bind_ipv6_loopback() -> TestResult {
+    // el servidor escucha en ::1 de manera opcional, por lo que solo se prueba si el host tiene IPv6
+    if std::net::TcpListener::bind("[::1]:0").is_err() {
+        println!("Skipping IPv6 test: IPv6 is not available");
+        return Ok(());
+    }
+    let client = redis::Client::open("redis://[::1]:8080/")?;
+    let mut con = client.get_connection()?;
+    let pong: String = redis::cmd("PING").query(&mut con)?;
+    let info: String = redis::cmd("CLIENT").arg("INFO").query(&mut con)?;
+
+    if pong == "PONG" && info.contains("laddr=[::1]:8080 ") {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("PONG and client info with laddr=[::1]:8080"),
+            got: format!("{} {}", pong, info),
+        }))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+//-----------------------------------------------------UNIX SOCKET---------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+
+fn test_unix_socket_commands() -> TestResult {
+    let mut stream = UnixStream::connect(UNIX_SOCKET)?;
+    let mut buf = [0u8; 512];
+    stream.write_all(b"SET unix_key hola\r\n")?;
+    let size = stream.read(&mut buf)?;
+    let set = String::from_utf8_lossy(&buf[..size]).to_string();
+    stream.write_all(b"CLIENT INFO\r\n")?;
+    let size = stream.read(&mut buf)?;
+    let info = String::from_utf8_lossy(&buf[..size]).to_string();
+
+    // los clientes del socket se identifican con la ruta del socket y el puerto 0
+    let addr = format!("addr={}:0 ", UNIX_SOCKET);
+    if set == "$2\r\nOk\r\n" && info.contains(&addr) && info.contains("flags=U ") {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: format!("$2\r\nOk\r\n and client info with {}flags=U", addr),
+            got: format!("{:?} {:?}", set, info),
+        }))
+    }
+}
+
+fn test_unix_socket_permissions() -> TestResult {
+    let mode = std::fs::metadata(UNIX_SOCKET)?.permissions().mode() & 0o777;
+
+    if mode == 0o700 {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("700"),
+            got: format!("{:o}", mode),
+        }))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+//-----------------------------------------------------TLS-----------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+
+/// Genera con openssl una autoridad certificante y un certificado firmado por ella para 127.0.0.1,
+/// que se utiliza tanto en el servidor como en los clientes. Devuelve false si no es posible generarlos.
+fn generate_tls_certificates() -> bool {
+    let openssl = |args: &[&str]| {
+        Command::new("openssl")
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    };
+    std::fs::write(
+        TLS_EXT,
+        "basicConstraints=CA:FALSE\nsubjectAltName=IP:127.0.0.1\nextendedKeyUsage=serverAuth,clientAuth\n",
+    )
+    .is_ok()
+        && openssl(&[
+            "req", "-x509", "-newkey", "rsa:2048", "-nodes", "-days", "1", "-subj", "/CN=Test CA",
+            "-addext", "basicConstraints=critical,CA:TRUE", "-addext", "keyUsage=keyCertSign",
+            "-keyout", TLS_CA_KEY, "-out", TLS_CA_CERT,
+        ])
+        && openssl(&[
+            "req", "-newkey", "rsa:2048", "-nodes", "-subj", "/CN=127.0.0.1", "-keyout", TLS_KEY,
+            "-out", TLS_CSR,
+        ])
+        && openssl(&[
+            "x509", "-req", "-days", "1", "-in", TLS_CSR, "-CA", TLS_CA_CERT, "-CAkey", TLS_CA_KEY,
+            "-CAcreateserial", "-extfile", TLS_EXT, "-out", TLS_CERT,
+        ])
+}
+
+/// Abre una conexión TLS con el servidor, presentando el certificado de cliente si se indica.
+fn tls_connect(
+    with_certificate: bool,
+) -> Result<StreamOwned<ClientConnection, TcpStream>, Box<dyn Error>> {
+    let mut roots = RootCertStore::empty();
+    for cert in CertificateDer::pem_file_iter(TLS_CA_CERT)? {
+        roots.add(cert?)?;
+    }
+    let builder = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()?
+        .with_root_certificates(roots);
+    let config = if with_certificate {
+        let certs = CertificateDer::pem_file_iter(TLS_CERT)?.collect::<Result<Vec<_>, _>>()?;
+        builder.with_client_auth_cert(certs, PrivateKeyDer::from_pem_file(TLS_KEY)?)?
+    } else {
+        builder.with_no_client_auth()
+    };
+    let connection = ClientConnection::new(
+        Arc::new(config),
+        ServerName::try_from("127.0.0.1")?.to_owned(),
+    )?;
+    let socket = TcpStream::connect(format!("127.0.0.1:{}", TLS_PORT))?;
+    Ok(StreamOwned::new(connection, socket))
+}
+
+fn test_tls_with_client_certificate() -> TestResult {
+    if !Path::new(TLS_CERT).exists() {
+        println!("Skipping TLS test: openssl is not available");
+        return Ok(());
+    }
+    let mut stream = tls_connect(true)?;
+    stream.write_all(b"*3\r\n$3\r\nSET\r\n$7\r\ntls_key\r\n$4\r\nhola\r\n")?;
+    let mut buf = [0u8; 512];
+    let size = stream.read(&mut buf)?;
+    let set = String::from_utf8_lossy(&buf[..size]).to_string();
+    stream.write_all(b"*2\r\n$3\r\nGET\r\n$7\r\ntls_key\r\n")?;
+    let size = stream.read(&mut buf)?;
+    let get = String::from_utf8_lossy(&buf[..size]).to_string();
+
+    if set == "$2\r\nOk\r\n" && get == "$4\r\nhola\r\n" {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("$2\r\nOk\r\n $4\r\nhola\r\n"),
+            got: format!("{:?} {:?}", set, get),
+        }))
+    }
+}
+
+fn test_tls_without_client_certificate() -> TestResult {
+    if !Path::new(TLS_CERT).exists() {
+        println!("Skipping TLS test: openssl is not available");
+        return Ok(());
+    }
+    let mut stream = tls_connect(false)?;
+    let mut buf = [0u8; 512];
+    // en TLS 1.3 el servidor verifica el certificado del cliente luego de que este finaliza el handshake,
+    // por lo que el rechazo se observa al leer la respuesta
+    let ret = stream
+        .write_all(b"*1\r\n$4\r\nPING\r\n")
+        .and_then(|_| stream.read(&mut buf));
+
+    match ret {
+        Err(_) | Ok(0) => Ok(()),
+        Ok(size) => Err(Box::new(ReturnError {
+            expected: String::from("TLS handshake error"),
+            got: String::from_utf8_lossy(&buf[..size]).to_string(),
+        })),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+//-----------------------------------------------------SERVER COMMANDS-----------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+
+pub fn test_info() -> TestResult {
+    let mut con = connect()?;
+    let ret: Result<String, RedisError> = redis::cmd("INFO").query(&mut con);
+    return if ret.is_ok() {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from(""),
+            got: ret.err().unwrap().to_string(),
+        }))
+    };
+}
+
+fn test_shutdown_with_invalid_options() -> TestResult {
+    let mut con = connect()?;
+    let ret: Result<String, RedisError> = redis::cmd("SHUTDOWN")
+        .arg("NOSAVE")
+        .arg("SAVE")
+        .query(&mut con);
+    if !matches!(&ret, Err(e) if e.to_string().contains("syntax error")) {
+        return Err(Box::new(ReturnError {
+            expected: String::from("syntax error"),
+            got: format!("{:?}", ret),
+        }));
+    }
+    let ret: String = redis::cmd("PING").query(&mut con)?;
+    if ret == "PONG" {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("PONG"),
+            got: ret,
+        }))
+    }
+}
+
+fn test_hello_negotiates_resp3() -> TestResult {
+    let mut con = connect()?;
+    let ret: redis::Value = redis::cmd("HELLO").arg(3).query(&mut con)?;
+
+    if let redis::Value::Map(fields) = &ret {
+        if fields.contains(&(
+            redis::Value::BulkString(b"proto".to_vec()),
+            redis::Value::Int(3),
+        )) {
+            return Ok(());
+        }
+    }
+    Err(Box::new(ReturnError {
+        expected: String::from("Map with proto 3"),
+        got: format!("{:?}", ret),
+    }))
+}
+
+fn test_hello_without_version_keeps_resp2() -> TestResult {
+    let mut con = connect()?;
+    let ret: redis::Value = redis::cmd("HELLO").query(&mut con)?;
+
+    if let redis::Value::Array(fields) = &ret {
+        if fields.windows(2).any(|pair| {
+            pair[0] == redis::Value::BulkString(b"proto".to_vec())
+                && pair[1] == redis::Value::Int(2)
+        }) {
+            return Ok(());
+        }
+    }
+    Err(Box::new(ReturnError {
+        expected: String::from("Array with proto 2"),
+        got: format!("{:?}", ret),
+    }))
+}
+
+fn test_hello_unsupported_version() -> TestResult {
+    let mut con = connect()?;
+    let ret: Result<redis::Value, RedisError> = redis::cmd("HELLO").arg(4).query(&mut con);
+
+    match ret {
+        Err(e) if e.to_string().contains("NOPROTO") => Ok(()),
+        _ => Err(Box::new(ReturnError {
+            expected: String::from("NOPROTO error"),
+            got: format!("{:?}", ret),
+        })),
+    }
+}
+
+fn test_config_get_verbose() -> TestResult {
+    let mut con = connect()?;
+    let ret: Vec<String> = redis::cmd("CONFIG")
+        .arg("get")
+        .arg("verbose")
+        .query(&mut con)?;
+
+    if &ret[1] == &String::from("1") {
+        return Ok(());
+    } else {
+        return Err(Box::new(ReturnError {
+            expected: String::from("1"),
+            got: String::from(&ret[1]),
+        }));
+    }
+}
+
+fn test_config_get_all() -> TestResult {
+    let mut con = connect()?;
+    let ret: Vec<String> = redis::cmd("CONFIG").arg("get").arg("*").query(&mut con)?;
+
+    if ret[0].len() > 0 {
+        return Ok(());
+    } else {
+        return Err(Box::new(ReturnError {
+            expected: String::from("Must contain verbose"),
+            got: format!("{:?}", ret),
+        }));
+    }
+}
+
+fn test_config_get_returns_error_missing_parameter() -> TestResult {
+    let mut con = connect()?;
+    let ret: Result<String, RedisError> = redis::cmd("CONFIG").arg("get").query(&mut con);
+
+    if ret.is_err() {
+        return Ok(());
+    } else {
+        return Err(Box::new(ReturnError {
+            expected: String::from("Error missing parameter"),
+            got: format!("{:?}", ret),
+        }));
+    }
+}
+
+fn test_config_set_maxmemory() -> TestResult {
+    let mut con = connect()?;
+    let ret: String = redis::cmd("CONFIG")
+        .arg("set")
+        .arg("maxmemory")
+        .arg("2mb")
+        .query(&mut con)?;
+
+    if ret == String::from("Ok") {
+        return Ok(());
+    } else {
+        return Err(Box::new(ReturnError {
+            expected: String::from("Ok"),
+            got: ret,
+        }));
+    }
+}
+
+fn test_config_set_invalid_output_buffer_limit() -> TestResult {
+    let mut con = connect()?;
+    let ret: Result<String, RedisError> = redis::cmd("CONFIG")
+        .arg("set")
+        .arg("client-output-buffer-limit")
+        .arg("pubsub 1mb")
+        .query(&mut con);
+
+    if ret.is_err() {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("Error invalid client-output-buffer-limit"),
+            got: format!("{:?}", ret),
+        }))
+    }
+}
+
+// no lo testeo porque el resultado depende de si se ejecuta antes o despues de borrar una clave
+fn _test_dbsize() -> TestResult {
+    let mut con = connect()?;
+    let ret: usize = redis::cmd("DBSIZE").query(&mut con)?;
+
+    if ret == 4 {
+        return Ok(());
+    } else {
+        return Err(Box::new(ReturnError {
+            expected: String::from("4"),
+            got: ret.to_string(),
+        }));
+    }
+}
+
+// no lo testeo porque depende el orden en que se ejecuten podrian fallarme los otros tests
+fn _test_flushdb() -> TestResult {
+    let mut con = connect()?;
+    let ret: String = redis::cmd("FLUSHDB").query(&mut con)?;
+
+    if ret == String::from("Erased database") {
+        return Ok(());
+    } else {
+        return Err(Box::new(ReturnError {
+            expected: String::from("Erased database"),
+            got: ret,
+        }));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+//-----------------------------------------------------KEYS COMMANDS-------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------------------
 
 fn test_keys_del_string_type() -> TestResult {
     let mut con = connect()?;
@@ -1488,7 +2555,7 @@ fn test_keys_expireat() -> TestResult {
     let mut con = connect()?;
     let ret: usize = redis::cmd("EXPIREAT")
         .arg("key_1")
-        .arg(1725487534)
+        .arg(1925487534)
         .query(&mut con)?;
 
     return if ret == 1 {
@@ -1505,7 +2572,7 @@ fn test_keys_expireat_no_aplica_expiracion_porque_la_clave_no_existe() -> TestRe
     let mut con = connect()?;
     let ret: usize = redis::cmd("EXPIREAT")
         .arg("esta_clave_es_demasiado_larga_para_ser_una_clave_real")
-        .arg(1725487534)
+        .arg(1925487534)
         .query(&mut con)?;
 
     return if ret == 0 {
@@ -1651,17 +2718,17 @@ fn test_keys_copy_with_replace() -> TestResult {
 
 fn test_keys_copy_sin_replace_arroja_error_porque_la_clave_destino_ya_existe() -> TestResult {
     let mut con = connect()?;
-    let ret: Result<String, RedisError> = redis::cmd("COPY")
+    let ret: usize = redis::cmd("COPY")
         .arg("key_1")
         .arg("key_999")
-        .query(&mut con);
+        .query(&mut con)?;
 
-    if ret.is_err() {
+    if ret == 0 {
         return Ok(());
     } else {
         return Err(Box::new(ReturnError {
-            expected: String::from("error - key does not exist"),
-            got: format!("{:?}", ret),
+            expected: String::from("0"),
+            got: ret.to_string(),
         }));
     }
 }
@@ -1839,18 +2906,11 @@ fn test_type_gets_value_type_set() -> TestResult {
 fn test_type_gets_value_type_set_no_devuelve_nadacuando_se_aplica_type_para_clave_inexistente(
 ) -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("TYPE")
+    let _: () = redis::cmd("TYPE")
         .arg("granaderos_amalgamados")
         .query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 fn test_keys_gets_keys_that_match_a_pattern() -> TestResult {
@@ -1910,19 +2970,23 @@ fn test_keys_gets_keys_that_match_a_pattern_con_signo_de_pregunta() -> TestResul
 
 fn test_se_obtienen_solo_las_claves_que_tienen_value_tipo_string() -> TestResult {
     let mut con = connect()?;
-    let ret: Vec<String> = redis::cmd("MGET")
+    let ret: Vec<Option<String>> = redis::cmd("MGET")
         .arg("edad_luz")
         .arg("edad_maria")
         .arg("edades_amigos")
         .arg("grupo_amigas")
         .query(&mut con)?;
 
-    if &ret[0] == &String::from("13") && &ret[1] == &String::from("10") {
+    if ret[0].as_deref() == Some("13")
+        && ret[1].as_deref() == Some("10")
+        && ret[2].is_none()
+        && ret[3].is_none()
+    {
         return Ok(());
     } else {
         return Err(Box::new(ReturnError {
-            expected: String::from("13 10"),
-            got: format!("{} {}", ret[0], ret[1]),
+            expected: String::from("13 10 nil nil"),
+            got: format!("{:?}", ret),
         }));
     }
 }
@@ -2199,19 +3263,12 @@ fn test_string_get_devuelve_error_cuando_se_aplica_get_para_valor_que_no_es_stri
 }
 fn test_string_get_devuelve_nulo_cuando_se_aplica_get_para_clave_inexistente() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("GET")
+    let _: () = redis::cmd("GET")
         .arg("ricardito_corazon_de_surubi")
         .arg(3)
         .query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 fn test_string_strlen() -> TestResult {
@@ -2281,18 +3338,11 @@ fn test_string_getdel() -> TestResult {
 
 fn test_string_getdel_devuelve_nulo_cuando_se_aplica_getdel_para_clave_inexistente() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("GETDEL")
+    let _: () = redis::cmd("GETDEL")
         .arg("alfredito_corazon_de_cachalote")
         .query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 fn test_string_getdel_devuelve_error_cuando_se_aplica_getdel_para_valor_que_no_es_string(
@@ -2320,7 +3370,7 @@ fn test_string_getset() -> TestResult {
         .query(&mut con)?;
 
     let ret_stored_new_value: String = redis::cmd("GET").arg("key_getset").query(&mut con)?;
-    if (ret == String::from("OldValue")) && (ret_stored_new_value == String::from("newvalue")) {
+    if (ret == String::from("OldValue")) && (ret_stored_new_value == String::from("NewValue")) {
         return Ok(());
     } else {
         return Err(Box::new(ReturnError {
@@ -2353,19 +3403,12 @@ fn test_string_getset_devuelve_error_cuando_se_aplica_getset_para_valor_que_no_e
 
 fn test_string_getset_devuelve_nulo_cuando_se_aplica_getset_para_clave_inexistente() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("GETSET")
+    let _: () = redis::cmd("GETSET")
         .arg("alfredito_corazon_de_cachalote")
         .arg("NewValue")
         .query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 fn test_string_set() -> TestResult {
@@ -2476,6 +3519,182 @@ fn test_string_set_with_xx_argument_succesfull_as_key_already_exists() -> TestRe
     }
 }
 
+fn test_string_incr_and_decr_on_non_existing_key() -> TestResult {
+    let mut con = connect()?;
+    let _: isize = redis::cmd("INCR")
+        .arg("contador_de_visitas")
+        .query(&mut con)?;
+    let _: isize = redis::cmd("DECR")
+        .arg("contador_de_visitas")
+        .query(&mut con)?;
+    let ret: isize = redis::cmd("DECR")
+        .arg("contador_de_visitas")
+        .query(&mut con)?;
+
+    if ret == -1 {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("-1"),
+            got: ret.to_string(),
+        }))
+    }
+}
+
+fn test_string_getrange_with_negative_indexes() -> TestResult {
+    let mut con = connect()?;
+    let ret: String = redis::cmd("GETRANGE")
+        .arg("texto_original")
+        .arg("-4")
+        .arg("-1")
+        .query(&mut con)?;
+
+    if ret == "text" {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("text"),
+            got: ret,
+        }))
+    }
+}
+
+fn test_string_set_with_get_returns_old_value() -> TestResult {
+    let mut con = connect()?;
+    let _: String = redis::cmd("SET")
+        .arg("texto_para_reemplazar")
+        .arg("viejo")
+        .query(&mut con)?;
+    let ret: String = redis::cmd("SET")
+        .arg("texto_para_reemplazar")
+        .arg("nuevo")
+        .arg("GET")
+        .query(&mut con)?;
+
+    if ret == "viejo" {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("viejo"),
+            got: ret,
+        }))
+    }
+}
+
+fn test_string_lcs() -> TestResult {
+    let mut con = connect()?;
+    let ret: String = redis::cmd("LCS")
+        .arg("texto_original")
+        .arg("texto_modificado")
+        .query(&mut con)?;
+
+    if ret == "mytext" {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("mytext"),
+            got: ret,
+        }))
+    }
+}
+
+fn test_string_set_get_binary_value() -> TestResult {
+    let mut con = connect()?;
+    let value: Vec<u8> = vec![
+        0xff, 0x00, b'\r', b'\n', 0xc3, 0x28, b'$', b'3', b'\r', b'\n',
+    ];
+    let _: String = redis::cmd("SET")
+        .arg("valor_binario")
+        .arg(&value)
+        .query(&mut con)?;
+    let ret: Vec<u8> = redis::cmd("GET").arg("valor_binario").query(&mut con)?;
+
+    if ret == value {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: format!("{:?}", value),
+            got: format!("{:?}", ret),
+        }))
+    }
+}
+
+fn test_string_set_get_large_value() -> TestResult {
+    let mut con = connect()?;
+    let value: Vec<u8> = (0..2048).map(|i| (i % 256) as u8).collect();
+    let _: String = redis::cmd("SET")
+        .arg("valor_grande")
+        .arg(&value)
+        .query(&mut con)?;
+    let ret: Vec<u8> = redis::cmd("GET").arg("valor_grande").query(&mut con)?;
+
+    if ret == value {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: format!("{} bytes", value.len()),
+            got: format!("{} bytes", ret.len()),
+        }))
+    }
+}
+
+fn test_string_set_keeps_value_case() -> TestResult {
+    let mut con = connect()?;
+    let _: String = redis::cmd("SET")
+        .arg("valor_con_mayusculas")
+        .arg("Hola Mundo")
+        .arg("EX")
+        .arg(100)
+        .query(&mut con)?;
+    let ret: String = redis::cmd("GET")
+        .arg("valor_con_mayusculas")
+        .query(&mut con)?;
+
+    if ret == "Hola Mundo" {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("Hola Mundo"),
+            got: ret,
+        }))
+    }
+}
+
+fn test_lpush_y_sadd_guardan_claves_y_elementos_binarios() -> TestResult {
+    let mut con = connect()?;
+    let clave_lista: Vec<u8> = vec![b'l', 0xff, 0x00];
+    let clave_set: Vec<u8> = vec![b's', 0xfe, 0x00];
+    let binario: Vec<u8> = vec![0xff, 0xfe, b'a'];
+    let _: usize = redis::cmd("LPUSH")
+        .arg(&clave_lista)
+        .arg("valido")
+        .arg(&binario)
+        .query(&mut con)?;
+    let _: usize = redis::cmd("SADD")
+        .arg(&clave_set)
+        .arg(&binario)
+        .query(&mut con)?;
+    let lista: Vec<Vec<u8>> = redis::cmd("LRANGE")
+        .arg(&clave_lista)
+        .arg(0)
+        .arg(-1)
+        .query(&mut con)?;
+    let miembros: Vec<Vec<u8>> = redis::cmd("SMEMBERS").arg(&clave_set).query(&mut con)?;
+
+    if lista == vec![binario.clone(), b"valido".to_vec()] && miembros == vec![binario.clone()] {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: format!(
+                "{:?} {:?}",
+                vec![binario.clone(), b"valido".to_vec()],
+                vec![binario]
+            ),
+            got: format!("{:?} {:?}", lista, miembros),
+        }))
+    }
+}
+
 fn test_string_set_with_xx_argument_key_does_not_already_exist_then_throws_error() -> TestResult {
     let mut con = connect()?;
     let ret: Result<String, RedisError> = redis::cmd("SET")
@@ -2496,19 +3715,12 @@ fn test_string_set_with_xx_argument_key_does_not_already_exist_then_throws_error
 
 fn test_string_set_devuelve_nulo_cuando_se_aplica_set_para_clave_inexistente() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("SET")
+    let _: () = redis::cmd("SET")
         .arg("alfredito_corazon_de_cachalote")
         .arg("valueset")
         .query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 //-------------------------------------------------------------------------------------------------------------------------------
@@ -2782,12 +3994,12 @@ fn test_se_devuelve_lista_vacia_porque_limite_inferior_supera_al_limite_superior
         .arg("5")
         .arg("3")
         .query(&mut con)?;
-    if &ret == &vec!["".to_string()] {
+    if ret.is_empty() {
         return Ok(());
     } else {
         return Err(Box::new(ReturnError {
-            expected: String::from(""),
-            got: format!("{}", ret[0]),
+            expected: String::from("[]"),
+            got: format!("{:?}", ret),
         }));
     }
 }
@@ -2910,13 +4122,14 @@ fn test_list_no_reemplaza_un_elemento_de_value_list_type_con_indice_fuera_de_ran
         .query(&mut con);
     assert!(ret.is_err());
 
-    return if ret.is_err() {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: String::from("Value stored at key set_remove_4 is not a Set"),
-            got: ret.unwrap(),
-        }))
+    return match ret {
+        Err(_) => Ok(()),
+        Ok(got) => Err(Box::new(ReturnError {
+            expected: String::from(
+                "WRONGTYPE Operation against a key holding the wrong kind of value",
+            ),
+            got,
+        })),
     };
 }
 
@@ -2958,13 +4171,12 @@ pub fn test_list_index_no_list_type_error() -> TestResult {
         .query(&mut con);
     assert!(ret.is_err());
 
-    return if ret.is_err() {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
+    return match ret {
+        Err(_) => Ok(()),
+        Ok(got) => Err(Box::new(ReturnError {
             expected: String::from("Value is not a list"),
-            got: ret.unwrap(),
-        }))
+            got,
+        })),
     };
 }
 
@@ -3016,66 +4228,38 @@ pub fn test_list_lpop() -> TestResult {
 
 pub fn test_list_lpop_sin_count_devuelve_nil_cuando_la_clave_no_existe() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("LPOP")
+    let _: () = redis::cmd("LPOP")
         .arg("listado_de_franceses_que_estudiaron_en_brest")
         .query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 pub fn test_list_lpop_con_count_devuelve_nil_cuando_la_clave_no_existe() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("LPOP")
+    let _: () = redis::cmd("LPOP")
         .arg("listado_de_franceses_que_estudiaron_en_lyon")
         .arg("2")
         .query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 pub fn test_list_lpop_con_count_devuelve_nil_cuando_el_tipo_del_valor_no_es_list() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("LPOP")
+    let _: () = redis::cmd("LPOP")
         .arg("edad_maria")
         .arg("2")
         .query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 pub fn test_list_lpop_sin_count_devuelve_nil_cuando_el_tipo_del_valor_no_es_list() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("LPOP").arg("edad_maria").query(&mut con)?;
+    let _: () = redis::cmd("LPOP").arg("edad_maria").query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 pub fn test_list_lpop_con_count_devuelve_menos_elementos_que_los_que_indica_count_porque_count_es_mayor_que_list_len(
@@ -3188,66 +4372,38 @@ pub fn test_list_rpop_with_count_greater_than_list_lenght() -> TestResult {
 
 pub fn test_list_rpop_sin_count_devuelve_nil_cuando_la_clave_no_existe() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("RPOP")
+    let _: () = redis::cmd("RPOP")
         .arg("listado_de_franceses_que_estudiaron_en_brest")
         .query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 pub fn test_list_rpop_con_count_devuelve_nil_cuando_la_clave_no_existe() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("RPOP")
+    let _: () = redis::cmd("RPOP")
         .arg("listado_de_franceses_que_estudiaron_en_brest")
         .arg("4")
         .query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 pub fn test_list_rpop_con_count_devuelve_nil_cuando_el_tipo_del_valor_no_es_list() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("RPOP")
+    let _: () = redis::cmd("RPOP")
         .arg("edad_maria")
         .arg("2")
         .query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 pub fn test_list_rpop_sin_count_devuelve_nil_cuando_el_tipo_del_valor_no_es_list() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("RPOP").arg("edad_maria").query(&mut con)?;
+    let _: () = redis::cmd("RPOP").arg("edad_maria").query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 pub fn test_list_rpushx() -> TestResult {
@@ -3311,51 +4467,110 @@ pub fn test_list_rpush() -> TestResult {
         .arg("chocolate marroc")
         .query(&mut con)?;
 
-    return if ret == 5 {
+    return if ret == 5 {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: 5.to_string(),
+            got: ret.to_string(),
+        }))
+    };
+}
+
+pub fn test_list_rpush_nonexisting_key_creates_key_value_pair_and_returns_list_size() -> TestResult
+{
+    let mut con = connect()?;
+    let ret: usize = redis::cmd("RPUSH")
+        .arg("sabores_silvestres_de_helado")
+        .arg("frutos rojos del este")
+        .arg("menta de primera cosecha")
+        .arg("grosellas aireadas")
+        .query(&mut con)?;
+
+    return if ret == 3 {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: 3.to_string(),
+            got: ret.to_string(),
+        }))
+    };
+}
+pub fn test_list_rpush_arrroja_error_cuando_se_intenta_almacenar_dato_en_una_clave_que_no_guarda_un_valor_de_tipo_list(
+) -> TestResult {
+    let mut con = connect()?;
+    let ret: Result<String, RedisError> = redis::cmd("RPUSH")
+        .arg("edad_maria")
+        .arg("25")
+        .query(&mut con);
+
+    if ret.is_err() {
+        return Ok(());
+    } else {
+        return Err(Box::new(ReturnError {
+            expected: String::from("Error missing parameter"),
+            got: format!("{:?}", ret),
+        }));
+    };
+}
+
+pub fn test_list_linsert() -> TestResult {
+    let mut con = connect()?;
+    let ret: isize = redis::cmd("LINSERT")
+        .arg("tareas_pendientes")
+        .arg("BEFORE")
+        .arg("tarea_3")
+        .arg("tarea_2")
+        .query(&mut con)?;
+
+    if ret == 4 {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: 4.to_string(),
+            got: ret.to_string(),
+        }))
+    }
+}
+
+pub fn test_list_lpos() -> TestResult {
+    let mut con = connect()?;
+    let ret: Vec<usize> = redis::cmd("LPOS")
+        .arg("tareas_pendientes")
+        .arg("tarea_1")
+        .arg("RANK")
+        .arg("-1")
+        .arg("COUNT")
+        .arg("0")
+        .query(&mut con)?;
+
+    if ret.len() == 2 && ret[0] > ret[1] {
         Ok(())
     } else {
         Err(Box::new(ReturnError {
-            expected: 5.to_string(),
-            got: ret.to_string(),
+            expected: String::from("two positions, last match first"),
+            got: format!("{:?}", ret),
         }))
-    };
+    }
 }
 
-pub fn test_list_rpush_nonexisting_key_creates_key_value_pair_and_returns_list_size() -> TestResult
-{
+pub fn test_list_lmove() -> TestResult {
     let mut con = connect()?;
-    let ret: usize = redis::cmd("RPUSH")
-        .arg("sabores_silvestres_de_helado")
-        .arg("frutos rojos del este")
-        .arg("menta de primera cosecha")
-        .arg("grosellas aireadas")
+    let ret: String = redis::cmd("LMOVE")
+        .arg("trabajos_en_cola")
+        .arg("trabajos_en_proceso")
+        .arg("RIGHT")
+        .arg("LEFT")
         .query(&mut con)?;
 
-    return if ret == 3 {
+    if ret == "trabajo_3" {
         Ok(())
     } else {
         Err(Box::new(ReturnError {
-            expected: 3.to_string(),
-            got: ret.to_string(),
+            expected: String::from("trabajo_3"),
+            got: ret,
         }))
-    };
-}
-pub fn test_list_rpush_arrroja_error_cuando_se_intenta_almacenar_dato_en_una_clave_que_no_guarda_un_valor_de_tipo_list(
-) -> TestResult {
-    let mut con = connect()?;
-    let ret: Result<String, RedisError> = redis::cmd("RPUSHX")
-        .arg("edad_maria")
-        .arg("25")
-        .query(&mut con);
-
-    if ret.is_err() {
-        return Ok(());
-    } else {
-        return Err(Box::new(ReturnError {
-            expected: String::from("Error missing parameter"),
-            got: format!("{:?}", ret),
-        }));
-    };
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------------------
@@ -3591,18 +4806,11 @@ pub fn test_set_smembers() -> TestResult {
 
 pub fn test_set_smembers_devuelve_nil_cuando_la_clave_no_existe() -> TestResult {
     let mut con = connect()?;
-    let ret: () = redis::cmd("SMEMBERS")
+    let _: () = redis::cmd("SMEMBERS")
         .arg("granaderos_acobardados")
         .query(&mut con)?;
 
-    return if ret == () {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
-            expected: format!(""),
-            got: format!("{:?}", ret),
-        }))
-    };
+    return Ok(());
 }
 
 pub fn test_set_members_arroja_error_cuando_la_clave_contiene_un_valor_que_no_es_de_tipo_set(
@@ -3662,13 +4870,12 @@ pub fn test_set_srem_removes_zero_values() -> TestResult {
         .arg("wawa")
         .query(&mut con);
 
-    return if ret.is_err() {
-        Ok(())
-    } else {
-        Err(Box::new(ReturnError {
+    return match ret {
+        Err(_) => Ok(()),
+        Ok(got) => Err(Box::new(ReturnError {
             expected: format!("Value stored is not a set"),
-            got: ret.unwrap(),
-        }))
+            got,
+        })),
     };
 }
 
@@ -3680,14 +4887,120 @@ pub fn test_set_srem_removes_returns_error() -> TestResult {
         .query(&mut con);
     assert!(ret.is_err());
 
-    return if ret.is_err() {
+    return match ret {
+        Err(_) => Ok(()),
+        Ok(got) => Err(Box::new(ReturnError {
+            expected: String::from(
+                "WRONGTYPE Operation against a key holding the wrong kind of value",
+            ),
+            got,
+        })),
+    };
+}
+
+pub fn test_set_sinter() -> TestResult {
+    let mut con = connect()?;
+    let ret: Vec<String> = redis::cmd("SINTER")
+        .arg("frutas_de_estacion")
+        .arg("frutas_verdes")
+        .query(&mut con)?;
+
+    if ret == vec!["kiwi".to_string()] {
         Ok(())
     } else {
         Err(Box::new(ReturnError {
-            expected: String::from("Value stored at key set_remove_4 is not a Set"),
-            got: ret.unwrap(),
+            expected: String::from("[\"kiwi\"]"),
+            got: format!("{:?}", ret),
         }))
-    };
+    }
+}
+
+pub fn test_set_sunionstore() -> TestResult {
+    let mut con = connect()?;
+    let ret: usize = redis::cmd("SUNIONSTORE")
+        .arg("todas_las_frutas")
+        .arg("frutas_de_estacion")
+        .arg("frutas_verdes")
+        .query(&mut con)?;
+
+    if ret == 4 {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("4"),
+            got: ret.to_string(),
+        }))
+    }
+}
+
+pub fn test_set_sintercard_with_limit() -> TestResult {
+    let mut con = connect()?;
+    let ret: usize = redis::cmd("SINTERCARD")
+        .arg("1")
+        .arg("frutas_de_estacion")
+        .arg("LIMIT")
+        .arg("2")
+        .query(&mut con)?;
+
+    if ret == 2 {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("2"),
+            got: ret.to_string(),
+        }))
+    }
+}
+
+pub fn test_set_smismember() -> TestResult {
+    let mut con = connect()?;
+    let ret: Vec<usize> = redis::cmd("SMISMEMBER")
+        .arg("frutas_verdes")
+        .arg("palta")
+        .arg("pomelo")
+        .query(&mut con)?;
+
+    if ret == vec![1, 0] {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("[1, 0]"),
+            got: format!("{:?}", ret),
+        }))
+    }
+}
+
+pub fn test_set_srandmember_with_negative_count() -> TestResult {
+    let mut con = connect()?;
+    let ret: Vec<String> = redis::cmd("SRANDMEMBER")
+        .arg("frutas_verdes")
+        .arg("-5")
+        .query(&mut con)?;
+
+    if ret.len() == 5 && ret.iter().all(|m| m == "kiwi" || m == "palta") {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("5 members of frutas_verdes"),
+            got: format!("{:?}", ret),
+        }))
+    }
+}
+
+pub fn test_set_srandmember_with_huge_negative_count() -> TestResult {
+    let mut con = connect()?;
+    let ret: Result<Vec<String>, redis::RedisError> = redis::cmd("SRANDMEMBER")
+        .arg("frutas_verdes")
+        .arg("-9223372036854775807")
+        .query(&mut con);
+
+    match ret {
+        Err(_) => Ok(()),
+        Ok(got) => Err(Box::new(ReturnError {
+            expected: String::from("value is out of range"),
+            got: format!("{:?}", got),
+        })),
+    }
 }
 
 fn test_rpush_lista_inexistente() -> TestResult {
@@ -3819,3 +5132,467 @@ fn test_pubsub() -> TestResult {
         }));
     }
 }
+
+fn test_psubscribe_receives_pattern_messages() -> TestResult {
+    let mut pubsub_con = connect()?;
+    let barrier = Arc::new(Barrier::new(2));
+    let pubsub_barrier = barrier.clone();
+    let (msg_sender, msg_receiver) = std::sync::mpsc::channel();
+    thread::spawn(move || {
+        let mut pubsub = pubsub_con.as_pubsub();
+        pubsub.psubscribe("news.*").unwrap();
+        let _ = pubsub_barrier.wait();
+        let msg = pubsub.get_message().unwrap();
+        let _ = msg_sender.send((
+            msg.get_pattern::<String>().unwrap_or_default(),
+            msg.get_channel_name().to_string(),
+            msg.get_payload::<String>().unwrap_or_default(),
+        ));
+    });
+
+    let _ = barrier.wait();
+    let mut con = connect()?;
+    let numpat: usize = redis::cmd("PUBSUB").arg("NUMPAT").query(&mut con)?;
+    let receivers: usize = redis::cmd("PUBLISH")
+        .arg("news.sports")
+        .arg("gol")
+        .query(&mut con)?;
+    let msg = msg_receiver.recv_timeout(Duration::from_secs(5))?;
+
+    let expected = (
+        String::from("news.*"),
+        String::from("news.sports"),
+        String::from("gol"),
+    );
+    if numpat >= 1 && receivers == 1 && msg == expected {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: format!("numpat >= 1, publish: 1, message: {:?}", expected),
+            got: format!(
+                "numpat: {}, publish: {}, message: {:?}",
+                numpat, receivers, msg
+            ),
+        }))
+    }
+}
+
+fn test_punsubscribe_replies_once_per_pattern() -> TestResult {
+    let mut stream = TcpStream::connect("127.0.0.1:8080")?;
+    let subscribed = send_inline(&mut stream, "PSUBSCRIBE alpha.* beta.*\r\n")?;
+    let unsubscribed = send_inline(&mut stream, "PUNSUBSCRIBE\r\n")?;
+    send_inline(&mut stream, "RESET\r\n")?;
+
+    let expected_subscribed = "*3\r\n$10\r\npsubscribe\r\n$7\r\nalpha.*\r\n:1\r\n*3\r\n$10\r\npsubscribe\r\n$6\r\nbeta.*\r\n:2\r\n";
+    // el orden en que se desuscriben los patrones no está definido
+    let expected_unsubscribed = [
+        "*3\r\n$12\r\npunsubscribe\r\n$7\r\nalpha.*\r\n:1\r\n*3\r\n$12\r\npunsubscribe\r\n$6\r\nbeta.*\r\n:0\r\n",
+        "*3\r\n$12\r\npunsubscribe\r\n$6\r\nbeta.*\r\n:1\r\n*3\r\n$12\r\npunsubscribe\r\n$7\r\nalpha.*\r\n:0\r\n",
+    ];
+    if subscribed == expected_subscribed && expected_unsubscribed.contains(&unsubscribed.as_str()) {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: format!("{}{}", expected_subscribed, expected_unsubscribed[0]),
+            got: format!("{}{}", subscribed, unsubscribed),
+        }))
+    }
+}
+
+fn test_slow_subscriber_is_disconnected() -> TestResult {
+    // el suscriptor nunca lee sus mensajes, por lo que se acumulan en su cola de salida hasta superar
+    // el límite de 1mb configurado para la clase pubsub
+    let mut subscriber = TcpStream::connect("127.0.0.1:8080")?;
+    send_inline(&mut subscriber, "PSUBSCRIBE slow.*\r\n")?;
+
+    let mut con = connect()?;
+    let message = "x".repeat(64 * 1024);
+    let mut receivers = 1;
+    for _ in 0..512 {
+        receivers = redis::cmd("PUBLISH")
+            .arg("slow.news")
+            .arg(&message)
+            .query(&mut con)?;
+        if receivers == 0 {
+            break;
+        }
+    }
+
+    if receivers == 0 {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("slow subscriber disconnected"),
+            got: format!("publish: {}", receivers),
+        }))
+    }
+}
+
+fn test_sharded_pubsub() -> TestResult {
+    let mut subscriber = TcpStream::connect("127.0.0.1:8080")?;
+    let subscribed = send_inline(&mut subscriber, "SSUBSCRIBE shard.news\r\n")?;
+
+    let mut con = connect()?;
+    let global_receivers: usize = redis::cmd("PUBLISH")
+        .arg("shard.news")
+        .arg("hola")
+        .query(&mut con)?;
+    let shard_receivers: usize = redis::cmd("SPUBLISH")
+        .arg("shard.news")
+        .arg("hola")
+        .query(&mut con)?;
+    let channels: Vec<String> = redis::cmd("PUBSUB")
+        .arg("SHARDCHANNELS")
+        .arg("shard.*")
+        .query(&mut con)?;
+    let numsub: Vec<String> = redis::cmd("PUBSUB")
+        .arg("SHARDNUMSUB")
+        .arg("shard.news")
+        .query(&mut con)?;
+
+    let mut buf = [0u8; 512];
+    let size = subscriber.read(&mut buf)?;
+    let message = String::from_utf8_lossy(&buf[..size]).to_string();
+    let unsubscribed = send_inline(&mut subscriber, "SUNSUBSCRIBE\r\n")?;
+
+    let expected = format!(
+        "{}{}{}",
+        "*3\r\n$10\r\nssubscribe\r\n$10\r\nshard.news\r\n:1\r\n",
+        "*3\r\n$8\r\nsmessage\r\n$10\r\nshard.news\r\n$4\r\nhola\r\n",
+        "*3\r\n$12\r\nsunsubscribe\r\n$10\r\nshard.news\r\n:0\r\n"
+    );
+    let got = format!("{}{}{}", subscribed, message, unsubscribed);
+    if global_receivers == 0
+        && shard_receivers == 1
+        && channels == vec![String::from("shard.news")]
+        && numsub == vec![String::from("shard.news"), String::from("1")]
+        && got == expected
+    {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: format!(
+                "publish: 0, spublish: 1, shardchannels: [\"shard.news\"], shardnumsub: [\"shard.news\", \"1\"], {}",
+                expected
+            ),
+            got: format!(
+                "publish: {}, spublish: {}, shardchannels: {:?}, shardnumsub: {:?}, {}",
+                global_receivers, shard_receivers, channels, numsub, got
+            ),
+        }))
+    }
+}
+
+fn test_keyspace_notifications() -> TestResult {
+    let mut con = connect()?;
+    redis::cmd("CONFIG")
+        .arg("set")
+        .arg("notify-keyspace-events")
+        .arg("KA")
+        .query::<String>(&mut con)?;
+    let mut subscriber = TcpStream::connect("127.0.0.1:8080")?;
+    send_inline(&mut subscriber, "PSUBSCRIBE __keyspace@0__:notify.*\r\n")?;
+
+    redis::cmd("SET")
+        .arg("notify.key")
+        .arg("hola")
+        .query::<String>(&mut con)?;
+    redis::cmd("DEL")
+        .arg("notify.key")
+        .query::<usize>(&mut con)?;
+    redis::cmd("SET")
+        .arg("notify.temp")
+        .arg("hola")
+        .arg("PX")
+        .arg("100")
+        .query::<String>(&mut con)?;
+
+    // la clave temporal no se vuelve a acceder, por lo que la elimina el ciclo de expiración activa
+    subscriber.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut received = String::new();
+    let mut buf = [0u8; 512];
+    while !received.contains("expired") {
+        match subscriber.read(&mut buf) {
+            Ok(size) if size > 0 => received.push_str(&String::from_utf8_lossy(&buf[..size])),
+            _ => break,
+        }
+    }
+
+    let events: Vec<&str> = received
+        .split("\r\n")
+        .filter(|line| ["set", "del", "expire", "expired"].contains(line))
+        .collect();
+    let expected = vec!["set", "del", "set", "expire", "expired"];
+    if events == expected && received.contains("__keyspace@0__:notify.temp") {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: format!("{:?}", expected),
+            got: received,
+        }))
+    }
+}
+
+fn test_client_tracking_invalidates_read_keys() -> TestResult {
+    let mut con = connect()?;
+    let mut tracker = TcpStream::connect("127.0.0.1:8080")?;
+    send_inline(&mut tracker, "HELLO 3\r\n")?;
+    let tracking = send_inline(&mut tracker, "CLIENT TRACKING ON\r\n")?;
+    send_inline(&mut tracker, "GET tracking.key\r\n")?;
+
+    redis::cmd("SET")
+        .arg("tracking.key")
+        .arg("hola")
+        .query::<String>(&mut con)?;
+
+    tracker.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut received = String::new();
+    let mut buf = [0u8; 512];
+    while !received.contains("tracking.key") {
+        match tracker.read(&mut buf) {
+            Ok(size) if size > 0 => received.push_str(&String::from_utf8_lossy(&buf[..size])),
+            _ => break,
+        }
+    }
+
+    let expected = ">2\r\n$10\r\ninvalidate\r\n*1\r\n$12\r\ntracking.key\r\n";
+    if tracking == "+OK\r\n" && received == expected {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: expected.to_string(),
+            got: format!("{}{}", tracking, received),
+        }))
+    }
+}
+
+fn test_monitor_skips_denied_commands() -> TestResult {
+    let mut admin = connect()?;
+    redis::cmd("ACL")
+        .arg("SETUSER")
+        .arg("monitor_worker")
+        .arg("on")
+        .arg(">secreto")
+        .arg("~monitor.allowed*")
+        .arg("+@all")
+        .query::<String>(&mut admin)?;
+    let mut monitor = TcpStream::connect("127.0.0.1:8080")?;
+    let reply = send_inline(&mut monitor, "MONITOR\r\n")?;
+
+    let mut worker = connect()?;
+    redis::cmd("AUTH")
+        .arg("monitor_worker")
+        .arg("secreto")
+        .query::<String>(&mut worker)?;
+    let denied: Result<String, RedisError> = redis::cmd("SET")
+        .arg("monitor.denied")
+        .arg("1")
+        .query(&mut worker);
+    redis::cmd("SET")
+        .arg("monitor.allowed")
+        .arg("1")
+        .query::<String>(&mut worker)?;
+    redis::cmd("ACL")
+        .arg("DELUSER")
+        .arg("monitor_worker")
+        .query::<usize>(&mut admin)?;
+
+    monitor.set_read_timeout(Some(Duration::from_secs(5)))?;
+    // el comando permitido se envía después del rechazado, por lo que al recibirlo ya se habría recibido el otro
+    let mut received = reply.clone();
+    let mut buf = [0u8; 512];
+    while !received.contains("\"monitor.allowed\"") {
+        match monitor.read(&mut buf) {
+            Ok(size) if size > 0 => received.push_str(&String::from_utf8_lossy(&buf[..size])),
+            _ => break,
+        }
+    }
+    if denied.is_err()
+        && received.contains("\"monitor.allowed\"")
+        && !received.contains("\"monitor.denied\"")
+    {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: String::from("only the allowed command streamed"),
+            got: format!("{:?} {}", denied, received),
+        }))
+    }
+}
+
+fn test_monitor_streams_commands() -> TestResult {
+    let mut con = connect()?;
+    let mut monitor = TcpStream::connect("127.0.0.1:8080")?;
+    let reply = send_inline(&mut monitor, "MONITOR\r\n")?;
+
+    redis::cmd("SET")
+        .arg("monitor.key")
+        .arg("hola \"mundo\"")
+        .query::<String>(&mut con)?;
+
+    monitor.set_read_timeout(Some(Duration::from_secs(5)))?;
+    // la misma lectura que trae la confirmación puede traer los comandos de otros clientes
+    let mut received = reply.clone();
+    let mut buf = [0u8; 512];
+    while !received.contains("\"monitor.key\"") {
+        match monitor.read(&mut buf) {
+            Ok(size) if size > 0 => received.push_str(&String::from_utf8_lossy(&buf[..size])),
+            _ => break,
+        }
+    }
+    // los demás tests se ejecutan en paralelo, por lo que se busca la línea del comando entre las recibidas
+    let expected = "] \"SET\" \"monitor.key\" \"hola \\\"mundo\\\"\"";
+    let line = received
+        .split("\r\n")
+        .find(|line| line.contains("\"monitor.key\""))
+        .unwrap_or_default();
+    if reply.starts_with("+OK\r\n")
+        && line.starts_with('+')
+        && line.contains(" [0 127.0.0.1:")
+        && line.ends_with(expected)
+    {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: expected.to_string(),
+            got: format!("{}{}", reply, line),
+        }))
+    }
+}
+
+/// Inicia otra instancia del servidor en `REPLICA_PORT`, la convierte en réplica del servidor de los tests y verifica
+/// que reciba tanto los datos previos como las escrituras posteriores.
+fn test_replicaof_synchronizes_and_propagates_writes() -> TestResult {
+    std::fs::write(
+        REPLICA_CONFIG,
+        format!(
+            "port {}\ndbfilename {}\nlogfile {}\nverbose 0\n",
+            REPLICA_PORT, REPLICA_DB, REPLICA_LOG
+        ),
+    )?;
+    std::fs::File::create(REPLICA_LOG)?;
+    let mut replica = Command::new(env!("CARGO_BIN_EXE_proyecto_taller_1"))
+        .arg(REPLICA_CONFIG)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let result = check_replication();
+    let _ = replica.kill();
+    let _ = replica.wait();
+    for file in [REPLICA_CONFIG, REPLICA_DB, REPLICA_LOG] {
+        let _ = std::fs::remove_file(file);
+    }
+    result
+}
+
+fn check_replication() -> TestResult {
+    let mut con = connect()?;
+    redis::cmd("SET")
+        .arg("replication.before")
+        .arg("snapshot")
+        .query::<String>(&mut con)?;
+
+    let address = format!("127.0.0.1:{}", REPLICA_PORT);
+    let mut stream = None;
+    for _ in 0..50 {
+        if let Ok(connected) = TcpStream::connect(&address) {
+            stream = Some(connected);
+            break;
+        }
+        sleep(Duration::from_millis(100));
+    }
+    let mut stream = stream.ok_or("replica did not start")?;
+    let reply = send_inline(&mut stream, "REPLICAOF 127.0.0.1 8080\r\n")?;
+    if reply != "+OK\r\n" {
+        return Err(Box::new(ReturnError {
+            expected: String::from("+OK"),
+            got: reply,
+        }));
+    }
+
+    // la segunda clave se escribe una vez sincronizada la réplica, para que la reciba como comando y no en la copia
+    let mut replica_con = redis::Client::open(format!("redis://{}/", address))?.get_connection()?;
+    for _ in 0..100 {
+        let info: String = redis::cmd("INFO")
+            .arg("replication")
+            .query(&mut replica_con)?;
+        if info.contains("master_link_status:up\r\n") {
+            break;
+        }
+        sleep(Duration::from_millis(100));
+    }
+    redis::cmd("SET")
+        .arg("replication.after")
+        .arg("propagated")
+        .query::<String>(&mut con)?;
+    let mut values = (None, None);
+    for _ in 0..100 {
+        values = redis::cmd("MGET")
+            .arg("replication.before")
+            .arg("replication.after")
+            .query::<(Option<String>, Option<String>)>(&mut replica_con)?;
+        if values.1.is_some() {
+            break;
+        }
+        sleep(Duration::from_millis(100));
+    }
+    // la expiración relativa llega a la réplica como un momento absoluto, por lo que expira cuando en el master
+    redis::cmd("SET")
+        .arg("replication.volatile")
+        .arg("expira")
+        .arg("EX")
+        .arg(100)
+        .query::<String>(&mut con)?;
+    let master_ttl = redis::cmd("TTL")
+        .arg("replication.volatile")
+        .query::<i64>(&mut con)?;
+    let mut ttl = -2;
+    for _ in 0..100 {
+        ttl = redis::cmd("TTL")
+            .arg("replication.volatile")
+            .query::<i64>(&mut replica_con)?;
+        if ttl != -2 {
+            break;
+        }
+        sleep(Duration::from_millis(100));
+    }
+    let info: String = redis::cmd("INFO")
+        .arg("replication")
+        .query(&mut replica_con)?;
+    let write: Result<String, RedisError> = redis::cmd("SET")
+        .arg("replication.after")
+        .arg("local")
+        .query(&mut replica_con);
+    let sort_store: Result<usize, RedisError> = redis::cmd("SORT")
+        .arg("replication.list")
+        .arg("STORE")
+        .arg("replication.sorted")
+        .query(&mut replica_con);
+    let master_info: String = redis::cmd("INFO").arg("replication").query(&mut con)?;
+
+    if values
+        == (
+            Some(String::from("snapshot")),
+            Some(String::from("propagated")),
+        )
+        && ttl == master_ttl
+        && info.contains("role:slave\r\nmaster_host:127.0.0.1\r\nmaster_port:8080\r\n")
+        && info.contains("master_link_status:up\r\n")
+        && matches!(&write, Err(e) if e.to_string().contains("read only replica"))
+        && matches!(&sort_store, Err(e) if e.to_string().contains("read only replica"))
+        && master_info.contains(&format!("port={},state=online", REPLICA_PORT))
+    {
+        Ok(())
+    } else {
+        Err(Box::new(ReturnError {
+            expected: format!(
+                "(snapshot, propagated), ttl {}, role:slave, READONLY",
+                master_ttl
+            ),
+            got: format!(
+                "{:?} {} {} {:?} {:?} {}",
+                values, ttl, info, write, sort_store, master_info
+            ),
+        }))
+    }
+}
@@ -0,0 +1,168 @@
+extern crate redis;
+
+use redis::{Connection, RedisError};
+use std::{
+    error::Error,
+    net::TcpStream,
+    panic,
+    process::{Child, Command, Stdio},
+    thread::sleep,
+    time::Duration,
+};
+
+const BACKEND_PORTS: [&str; 2] = ["8095", "8096"];
+const PROXY_PORT: &str = "8097";
+const PROXY_CONFIG: &str = "./src/dummy_proxy.conf";
+
+type TestResult = Result<(), Box<dyn Error>>;
+
+/// Inicia dos servidores y un proxy que reparte las claves entre ellos, y verifica los comandos a través del proxy.
+#[test]
+fn test_proxy_routes_and_splits_commands_between_backends() {
+    let mut processes = Vec::new();
+    let mut files = vec![String::from(PROXY_CONFIG)];
+    for port in BACKEND_PORTS {
+        let config = format!("./src/dummy_proxy_backend_{}.conf", port);
+        let db = format!("./src/dummy_proxy_backend_{}.rdb", port);
+        let log = format!("./src/dummy_proxy_backend_{}.log", port);
+        let _ = std::fs::remove_file(&db);
+        std::fs::write(
+            &config,
+            format!(
+                "port {}\ndbfilename {}\nlogfile {}\nverbose 0\n",
+                port, db, log
+            ),
+        )
+        .unwrap();
+        std::fs::File::create(&log).unwrap();
+        processes.push(spawn(env!("CARGO_BIN_EXE_proyecto_taller_1"), &config));
+        files.extend([config, db, log]);
+    }
+    // el proxy se inicia con los servidores disponibles, ya que los marca como caídos si no responden
+    for port in BACKEND_PORTS {
+        wait_for(port);
+    }
+    std::fs::write(
+        PROXY_CONFIG,
+        format!(
+            "port {}\nbind 127.0.0.1 -::1\nbackends 127.0.0.1:{} 127.0.0.1:{}\n",
+            PROXY_PORT, BACKEND_PORTS[0], BACKEND_PORTS[1]
+        ),
+    )
+    .unwrap();
+    processes.push(spawn(env!("CARGO_BIN_EXE_proxy"), PROXY_CONFIG));
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| check_proxy(&mut processes[1])));
+    for process in processes.iter_mut() {
+        let _ = process.kill();
+        let _ = process.wait();
+    }
+    for file in files {
+        let _ = std::fs::remove_file(file);
+    }
+    result.unwrap().unwrap();
+}
+
+fn spawn(program: &str, config: &str) -> Child {
+    Command::new(program)
+        .arg(config)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap()
+}
+
+fn wait_for(port: &str) {
+    for _ in 0..50 {
+        if TcpStream::connect(format!("127.0.0.1:{}", port)).is_ok() {
+            break;
+        }
+        sleep(Duration::from_millis(100));
+    }
+}
+
+fn connect(port: &str) -> Result<Connection, Box<dyn Error>> {
+    wait_for(port);
+    Ok(redis::Client::open(format!("redis://127.0.0.1:{}/", port))?.get_connection()?)
+}
+
+fn check_proxy(second_backend: &mut Child) -> TestResult {
+    let mut backends = [connect(BACKEND_PORTS[0])?, connect(BACKEND_PORTS[1])?];
+    let mut proxy = connect(PROXY_PORT)?;
+    let keys: Vec<String> = (0..100).map(|i| format!("proxy:{}", i)).collect();
+
+    let mut mset = redis::cmd("MSET");
+    for key in &keys {
+        mset.arg(key).arg(format!("valor {}", key));
+    }
+    assert_eq!(mset.query::<String>(&mut proxy)?, "Ok");
+    let values: Vec<Option<String>> = redis::cmd("MGET")
+        .arg(&keys)
+        .arg("proxy:inexistente")
+        .query(&mut proxy)?;
+    let mut expected: Vec<Option<String>> = keys
+        .iter()
+        .map(|key| Some(format!("valor {}", key)))
+        .collect();
+    expected.push(None);
+    assert_eq!(values, expected);
+
+    // cada servidor guarda una parte de las claves
+    let mut owners = Vec::new();
+    for backend in backends.iter_mut() {
+        let mut owned = Vec::new();
+        for key in &keys {
+            if redis::cmd("EXISTS").arg(key).query::<usize>(backend)? == 1 {
+                owned.push(key.clone());
+            }
+        }
+        owners.push(owned);
+    }
+    assert!(!owners[0].is_empty() && !owners[1].is_empty());
+    assert_eq!(owners[0].len() + owners[1].len(), keys.len());
+
+    let get: String = redis::cmd("GET").arg(&owners[1][0]).query(&mut proxy)?;
+    assert_eq!(get, format!("valor {}", owners[1][0]));
+    let exists: usize = redis::cmd("EXISTS")
+        .arg(&owners[0][0])
+        .arg(&owners[1][0])
+        .arg("proxy:inexistente")
+        .query(&mut proxy)?;
+    assert_eq!(exists, 2);
+    let deleted: usize = redis::cmd("DEL").arg(&keys[..10]).query(&mut proxy)?;
+    assert_eq!(deleted, 10);
+
+    let cross: Result<String, RedisError> = redis::cmd("RENAME")
+        .arg(&owners[0][20])
+        .arg(&owners[1][20])
+        .query(&mut proxy);
+    assert!(cross
+        .unwrap_err()
+        .to_string()
+        .contains("don't hash to the same backend"));
+    let keyless: Result<Vec<String>, RedisError> = redis::cmd("KEYS").arg("*").query(&mut proxy);
+    assert!(keyless.unwrap_err().to_string().contains("not supported"));
+
+    // al caer un servidor, sus claves no pasan al otro, que no tiene sus datos
+    second_backend.kill()?;
+    second_backend.wait()?;
+    let mut set: Result<String, RedisError> = Ok(String::new());
+    for _ in 0..50 {
+        set = redis::cmd("SET")
+            .arg(&owners[1][20])
+            .arg("reubicada")
+            .query(&mut proxy);
+        if matches!(&set, Err(e) if e.to_string().contains("is down")) {
+            break;
+        }
+        sleep(Duration::from_millis(100));
+    }
+    assert!(set.unwrap_err().to_string().contains("is down"));
+    let moved: usize = redis::cmd("EXISTS")
+        .arg(&owners[1][20])
+        .query(&mut backends[0])?;
+    assert_eq!(moved, 0);
+    let get: String = redis::cmd("GET").arg(&owners[0][20]).query(&mut proxy)?;
+    assert_eq!(get, format!("valor {}", owners[0][20]));
+    Ok(())
+}